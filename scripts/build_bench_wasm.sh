@@ -31,6 +31,7 @@ benches=(
   ecdsa_sign
   ecdsa_verify
   pedersen_hash
+  poseidon_hash
   rfc6979_generate_k
 )
 
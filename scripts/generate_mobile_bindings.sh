@@ -0,0 +1,23 @@
+#!/bin/bash
+
+# Generates the Kotlin and Swift bindings of `starknet-mobile` into `target/mobile-bindings`
+
+set -e
+
+SCRIPT_DIR=$( cd -- "$( dirname -- "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )
+REPO_ROOT=$( dirname -- $SCRIPT_DIR )
+
+case "$(uname)" in
+  Darwin) LIBRARY="libstarknet_mobile.dylib" ;;
+  *) LIBRARY="libstarknet_mobile.so" ;;
+esac
+
+cd $REPO_ROOT
+cargo build --package starknet-mobile --release --lib
+
+rm -rf $REPO_ROOT/target/mobile-bindings
+cargo run --package starknet-mobile --features cli --bin uniffi-bindgen -- generate \
+  --library $REPO_ROOT/target/release/$LIBRARY \
+  --language kotlin \
+  --language swift \
+  --out-dir $REPO_ROOT/target/mobile-bindings
@@ -0,0 +1,512 @@
+//! Command line companion for `starknet-rs`, built with the `cli` feature:
+//!
+//! ```sh
+//! cargo install starknet --features cli
+//! starknet-cli call 0x049d...4dc7 balanceOf 0x1234
+//! ```
+
+use clap::{Arg, ArgMatches, Command};
+use starknet::{
+    accounts::{
+        Account, AccountFactory, Call, ConnectedAccount, OpenZeppelinAccountFactory,
+        SingleOwnerAccount,
+    },
+    contract::ContractFactory,
+    core::{
+        chain_id,
+        types::{BlockId, CallFunction, ContractArtifact, FieldElement, TransactionStatus},
+        utils::{cairo_short_string_to_felt, get_selector_from_name},
+    },
+    providers::{Provider, SequencerGatewayProvider},
+    signers::{LocalWallet, SigningKey},
+};
+use std::{error::Error, io::BufRead, path::Path, sync::Arc, time::Duration};
+use url::Url;
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+const PRIVATE_KEY_ENV: &str = "STARKNET_PRIVATE_KEY";
+const KEYSTORE_ENV: &str = "STARKNET_KEYSTORE";
+const KEYSTORE_PASSWORD_ENV: &str = "STARKNET_KEYSTORE_PASSWORD";
+const ACCOUNT_ENV: &str = "STARKNET_ACCOUNT";
+
+fn cli() -> Command<'static> {
+    let calldata = Arg::new("calldata")
+        .help("Calldata elements, in hex or decimal")
+        .multiple_values(true);
+    let watch = Arg::new("watch")
+        .long("watch")
+        .help("Waits until the transaction is accepted on L1 or rejected");
+
+    Command::new("starknet-cli")
+        .about("Interacts with Starknet from the command line")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("network")
+                .long("network")
+                .global(true)
+                .takes_value(true)
+                .default_value("goerli")
+                .help("mainnet, goerli, goerli-2, or the base URL of a sequencer"),
+        )
+        .arg(
+            Arg::new("chain-id")
+                .long("chain-id")
+                .global(true)
+                .takes_value(true)
+                .help("Chain ID short string, for sequencers given by URL [default: SN_GOERLI]"),
+        )
+        .arg(
+            Arg::new("account")
+                .long("account")
+                .global(true)
+                .takes_value(true)
+                .help("Address of the account sending transactions [env: STARKNET_ACCOUNT]"),
+        )
+        .arg(
+            Arg::new("keystore")
+                .long("keystore")
+                .global(true)
+                .takes_value(true)
+                .help("Keystore of the account signer [env: STARKNET_KEYSTORE]"),
+        )
+        .subcommand(
+            Command::new("call")
+                .about("Calls a view function")
+                .arg(Arg::new("contract").required(true))
+                .arg(Arg::new("function").required(true))
+                .arg(calldata.clone()),
+        )
+        .subcommand(
+            Command::new("invoke")
+                .about("Invokes a function from the account")
+                .arg(Arg::new("contract").required(true))
+                .arg(Arg::new("function").required(true))
+                .arg(calldata.clone())
+                .arg(watch.clone()),
+        )
+        .subcommand(
+            Command::new("declare")
+                .about("Declares a compiled contract from the account")
+                .arg(Arg::new("artifact").required(true))
+                .arg(watch.clone()),
+        )
+        .subcommand(
+            Command::new("deploy")
+                .about("Deploys a declared class through the Universal Deployer Contract")
+                .arg(Arg::new("class-hash").required(true))
+                .arg(calldata.help("Constructor calldata elements, in hex or decimal"))
+                .arg(
+                    Arg::new("salt")
+                        .long("salt")
+                        .takes_value(true)
+                        .help("Deployment salt [default: random]"),
+                )
+                .arg(watch.clone()),
+        )
+        .subcommand(account_command(&watch))
+        .subcommand(keystore_command())
+        .subcommand(tx_command())
+}
+
+fn account_command(watch: &Arg<'static>) -> Command<'static> {
+    let class_hash = Arg::new("class-hash")
+        .long("class-hash")
+        .takes_value(true)
+        .required(true);
+    let salt = Arg::new("salt")
+        .long("salt")
+        .takes_value(true)
+        .default_value("0");
+
+    Command::new("account")
+        .about("Manages OpenZeppelin accounts")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("address")
+                .about("Prints the address of the account of the keystore signer")
+                .arg(class_hash.clone())
+                .arg(salt.clone()),
+        )
+        .subcommand(
+            Command::new("deploy")
+                .about("Deploys the account of the keystore signer, once funded")
+                .arg(class_hash)
+                .arg(salt)
+                .arg(watch.clone()),
+        )
+}
+
+fn keystore_command() -> Command<'static> {
+    let path = Arg::new("path").required(true);
+
+    Command::new("keystore")
+        .about("Manages encrypted signer keystores")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("new")
+                .about("Creates a keystore with a random key")
+                .arg(path.clone()),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Creates a keystore from a private key [env: STARKNET_PRIVATE_KEY]")
+                .arg(path.clone()),
+        )
+        .subcommand(
+            Command::new("inspect")
+                .about("Prints the public key of a keystore")
+                .arg(path),
+        )
+}
+
+fn tx_command() -> Command<'static> {
+    let hash = Arg::new("hash").required(true);
+
+    Command::new("tx")
+        .about("Inspects transactions")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("status")
+                .about("Prints the status of a transaction")
+                .arg(hash.clone()),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Prints the status of a transaction until it's final")
+                .arg(hash)
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .takes_value(true)
+                        .default_value("10")
+                        .help("Polling interval in seconds"),
+                ),
+        )
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run(cli().get_matches()).await {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(matches: ArgMatches) -> CliResult<()> {
+    let (provider, chain_id) = network(&matches)?;
+
+    match matches.subcommand() {
+        Some(("call", sub)) => {
+            let result = provider
+                .call_contract(
+                    CallFunction {
+                        contract_address: felt_arg(sub, "contract")?,
+                        entry_point_selector: selector(sub.value_of("function").unwrap())?,
+                        calldata: calldata_arg(sub)?,
+                    },
+                    BlockId::Latest,
+                )
+                .await?;
+            for element in result.result {
+                println!("{element:#x}");
+            }
+        }
+        Some(("invoke", sub)) => {
+            let account = account(&matches, provider, chain_id)?;
+            let result = account
+                .execute(vec![Call {
+                    to: felt_arg(sub, "contract")?,
+                    selector: selector(sub.value_of("function").unwrap())?,
+                    calldata: calldata_arg(sub)?,
+                }])
+                .send()
+                .await?;
+            println!("Transaction hash: {:#x}", result.transaction_hash);
+            if sub.is_present("watch") {
+                watch(account.provider(), result.transaction_hash, 10).await?;
+            }
+        }
+        Some(("declare", sub)) => {
+            let artifact: ContractArtifact =
+                serde_json::from_str(&std::fs::read_to_string(sub.value_of("artifact").unwrap())?)?;
+            let class_hash = artifact.class_hash()?;
+
+            let account = account(&matches, provider, chain_id)?;
+            let result = account.declare(Arc::new(artifact)).send().await?;
+            println!("Class hash: {class_hash:#x}");
+            println!("Transaction hash: {:#x}", result.transaction_hash);
+            if sub.is_present("watch") {
+                watch(account.provider(), result.transaction_hash, 10).await?;
+            }
+        }
+        Some(("deploy", sub)) => {
+            let salt = match sub.value_of("salt") {
+                Some(salt) => parse_felt(salt)?,
+                None => SigningKey::from_random().secret_scalar(),
+            };
+            let constructor_calldata = calldata_arg(sub)?;
+
+            let account = account(&matches, provider, chain_id)?;
+            let factory = ContractFactory::new(felt_arg(sub, "class-hash")?, &account);
+            let address = factory.predict_address(&constructor_calldata, salt, false);
+            let result = factory
+                .deploy(&constructor_calldata, salt, false)
+                .send()
+                .await?;
+            println!("Contract address: {address:#x}");
+            println!("Transaction hash: {:#x}", result.transaction_hash);
+            if sub.is_present("watch") {
+                watch(account.provider(), result.transaction_hash, 10).await?;
+            }
+        }
+        Some(("account", sub)) => {
+            let (command, sub) = sub.subcommand().unwrap();
+            let factory = OpenZeppelinAccountFactory::new(
+                felt_arg(sub, "class-hash")?,
+                chain_id,
+                LocalWallet::from(signing_key(&matches)?),
+                provider,
+            )
+            .await?;
+            let deployment = factory.deploy(felt_arg(sub, "salt")?);
+            println!("Account address: {:#x}", deployment.address());
+
+            if command == "deploy" {
+                let result = deployment.send().await?;
+                println!("Transaction hash: {:#x}", result.transaction_hash);
+                if sub.is_present("watch") {
+                    watch(factory.provider(), result.transaction_hash, 10).await?;
+                }
+            }
+        }
+        Some(("keystore", sub)) => match sub.subcommand().unwrap() {
+            ("new", sub) => {
+                let key = SigningKey::from_random();
+                save_keystore(&key, sub.value_of("path").unwrap())?;
+                println!("Public key: {:#x}", key.verifying_key().scalar());
+            }
+            ("import", sub) => {
+                let private_key = std::env::var(PRIVATE_KEY_ENV)
+                    .map_err(|_| format!("{PRIVATE_KEY_ENV} is not set"))?;
+                let key = SigningKey::from_secret_scalar(parse_felt(&private_key)?);
+                save_keystore(&key, sub.value_of("path").unwrap())?;
+                println!("Public key: {:#x}", key.verifying_key().scalar());
+            }
+            (_, sub) => {
+                let key = load_keystore(sub.value_of("path").unwrap())?;
+                println!("Public key: {:#x}", key.verifying_key().scalar());
+            }
+        },
+        Some(("tx", sub)) => match sub.subcommand().unwrap() {
+            ("status", sub) => {
+                let status = provider
+                    .get_transaction_status(felt_arg(sub, "hash")?)
+                    .await?;
+                println!("{:?}", status.status);
+            }
+            (_, sub) => {
+                let interval = sub.value_of("interval").unwrap().parse()?;
+                watch(&provider, felt_arg(sub, "hash")?, interval).await?;
+            }
+        },
+        _ => unreachable!("subcommand is required"),
+    }
+
+    Ok(())
+}
+
+/// The sequencer and chain ID of the selected network.
+fn network(matches: &ArgMatches) -> CliResult<(SequencerGatewayProvider, FieldElement)> {
+    let network = matches.value_of("network").unwrap();
+    Ok(match network {
+        "mainnet" => (
+            SequencerGatewayProvider::starknet_alpha_mainnet(),
+            chain_id::MAINNET,
+        ),
+        "goerli" => (
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+            chain_id::TESTNET,
+        ),
+        "goerli-2" => (
+            SequencerGatewayProvider::starknet_alpha_goerli_2(),
+            chain_id::TESTNET2,
+        ),
+        base_url => {
+            let base_url = base_url.trim_end_matches('/');
+            let chain_id =
+                cairo_short_string_to_felt(matches.value_of("chain-id").unwrap_or("SN_GOERLI"))?;
+            (
+                SequencerGatewayProvider::new(
+                    Url::parse(&format!("{base_url}/gateway"))?,
+                    Url::parse(&format!("{base_url}/feeder_gateway"))?,
+                ),
+                chain_id,
+            )
+        }
+    })
+}
+
+fn account(
+    matches: &ArgMatches,
+    provider: SequencerGatewayProvider,
+    chain_id: FieldElement,
+) -> CliResult<SingleOwnerAccount<SequencerGatewayProvider, LocalWallet>> {
+    let address = match matches.value_of("account") {
+        Some(address) => address.to_owned(),
+        None => std::env::var(ACCOUNT_ENV)
+            .map_err(|_| format!("--account or {ACCOUNT_ENV} is required"))?,
+    };
+
+    Ok(SingleOwnerAccount::new(
+        provider,
+        LocalWallet::from(signing_key(matches)?),
+        parse_felt(&address)?,
+        chain_id,
+    ))
+}
+
+/// The signing key from `STARKNET_PRIVATE_KEY` if set, or from the keystore otherwise.
+fn signing_key(matches: &ArgMatches) -> CliResult<SigningKey> {
+    if let Ok(private_key) = std::env::var(PRIVATE_KEY_ENV) {
+        return Ok(SigningKey::from_secret_scalar(parse_felt(&private_key)?));
+    }
+
+    let path = match matches.value_of("keystore") {
+        Some(path) => path.to_owned(),
+        None => std::env::var(KEYSTORE_ENV)
+            .map_err(|_| format!("--keystore, {KEYSTORE_ENV} or {PRIVATE_KEY_ENV} is required"))?,
+    };
+    load_keystore(&path)
+}
+
+fn load_keystore(path: &str) -> CliResult<SigningKey> {
+    Ok(SigningKey::from_keystore(path, &password()?)?)
+}
+
+fn save_keystore(key: &SigningKey, path: &str) -> CliResult<()> {
+    if Path::new(path).exists() {
+        return Err(format!("{path} already exists").into());
+    }
+    Ok(key.save_as_keystore(path, &password()?)?)
+}
+
+/// The keystore password from `STARKNET_KEYSTORE_PASSWORD` if set, or from stdin otherwise.
+fn password() -> CliResult<String> {
+    if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(password);
+    }
+
+    eprint!("Keystore password: ");
+    let mut password = String::new();
+    std::io::stdin().lock().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// Prints the status of a transaction whenever it changes, until it's accepted on L1 or
+/// rejected.
+async fn watch<P>(provider: &P, transaction_hash: FieldElement, interval: u64) -> CliResult<()>
+where
+    P: Provider,
+    P::Error: 'static,
+{
+    let mut last_status = None;
+    loop {
+        let status = provider.get_transaction_status(transaction_hash).await?;
+        if last_status != Some(status.status) {
+            println!("{:?}", status.status);
+            last_status = Some(status.status);
+        }
+
+        match status.status {
+            TransactionStatus::AcceptedOnL1 => return Ok(()),
+            TransactionStatus::Rejected => {
+                let reason = status
+                    .transaction_failure_reason
+                    .and_then(|reason| reason.error_message)
+                    .unwrap_or_default();
+                return Err(format!("transaction rejected: {reason}").into());
+            }
+            _ => tokio::time::sleep(Duration::from_secs(interval)).await,
+        }
+    }
+}
+
+fn felt_arg(matches: &ArgMatches, name: &str) -> CliResult<FieldElement> {
+    parse_felt(matches.value_of(name).unwrap())
+}
+
+fn calldata_arg(matches: &ArgMatches) -> CliResult<Vec<FieldElement>> {
+    matches
+        .values_of("calldata")
+        .into_iter()
+        .flatten()
+        .map(parse_felt)
+        .collect()
+}
+
+/// Parses a felt in hex (`0x`-prefixed) or decimal.
+fn parse_felt(value: &str) -> CliResult<FieldElement> {
+    let felt = match value.strip_prefix("0x") {
+        Some(_) => FieldElement::from_hex_be(value),
+        None => FieldElement::from_dec_str(value),
+    };
+    felt.map_err(|_| format!("invalid felt: {value}").into())
+}
+
+/// Parses a selector in hex, or computes it from an entrypoint name.
+fn selector(value: &str) -> CliResult<FieldElement> {
+    if value.starts_with("0x") {
+        parse_felt(value)
+    } else {
+        Ok(get_selector_from_name(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli() {
+        cli().debug_assert();
+
+        let matches = cli()
+            .try_get_matches_from([
+                "starknet-cli",
+                "invoke",
+                "0x1234",
+                "transfer",
+                "0x5",
+                "10",
+                "--network",
+                "mainnet",
+                "--watch",
+            ])
+            .unwrap();
+        assert_eq!(matches.value_of("network"), Some("mainnet"));
+
+        let (_, sub) = matches.subcommand().unwrap();
+        assert!(sub.is_present("watch"));
+        assert_eq!(
+            calldata_arg(sub).unwrap(),
+            vec![FieldElement::from(5u64), FieldElement::from(10u64)]
+        );
+
+        assert!(cli()
+            .try_get_matches_from(["starknet-cli", "account", "deploy"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_felt("0x10").unwrap(), FieldElement::from(16u64));
+        assert_eq!(parse_felt("10").unwrap(), FieldElement::from(10u64));
+        assert!(parse_felt("0xzz").is_err());
+        assert_eq!(
+            selector("balanceOf").unwrap(),
+            get_selector_from_name("balanceOf").unwrap()
+        );
+        assert_eq!(selector("0x1").unwrap(), FieldElement::ONE);
+    }
+}
@@ -3,6 +3,11 @@ use super::{
     PreparedDeclaration, RawDeclaration,
 };
 
+use crate::{
+    fee_strategy::{resolve_max_fee, PercentileOfRecentBlocks},
+    FeeStrategy,
+};
+
 use starknet_core::{
     crypto::compute_hash_on_elements,
     types::{
@@ -28,7 +33,7 @@ impl<'a, A> Declaration<'a, A> {
             contract_class,
             nonce: None,
             max_fee: None,
-            fee_estimate_multiplier: 1.1,
+            fee_strategy: Arc::new(PercentileOfRecentBlocks::default()),
         }
     }
 
@@ -46,6 +51,18 @@ impl<'a, A> Declaration<'a, A> {
         }
     }
 
+    /// Sets the strategy used for deciding `max_fee` when it's not manually specified. Defaults
+    /// to the default [PercentileOfRecentBlocks].
+    pub fn fee_strategy<S>(self, fee_strategy: S) -> Self
+    where
+        S: FeeStrategy + 'static,
+    {
+        Self {
+            fee_strategy: Arc::new(fee_strategy),
+            ..self
+        }
+    }
+
     /// Calling this function after manually specifying `nonce` and `max_fee` turns [Declaration] into
     /// [PreparedDeclaration]. Returns `Err` if either field is `None`.
     pub fn prepared(self) -> Result<PreparedDeclaration<'a, A>, NotPreparedError> {
@@ -111,7 +128,13 @@ where
             Some(value) => value,
             None => {
                 let fee_estimate = self.estimate_fee_with_nonce(nonce).await?;
-                ((fee_estimate.overall_fee as f64 * self.fee_estimate_multiplier) as u64).into()
+                resolve_max_fee(
+                    self.account.provider(),
+                    self.fee_strategy.as_ref(),
+                    &fee_estimate,
+                )
+                .await
+                .map_err(AccountError::Provider)?
             }
         };
 
@@ -137,20 +160,42 @@ where
                 max_fee: FieldElement::ZERO,
             },
         };
-        let declare = prepared.get_declare_request().await?;
-
-        self.account
-            .provider()
-            .estimate_fee(
-                AccountTransaction::Declare(declare),
-                self.account.block_id(),
-            )
-            .await
-            .map_err(AccountError::Provider)
+        let estimate = async {
+            let declare = prepared.get_declare_request().await?;
+
+            self.account
+                .provider()
+                .estimate_fee(
+                    AccountTransaction::Declare(declare),
+                    self.account.block_id(),
+                )
+                .await
+                .map_err(AccountError::Provider)
+        };
+
+        instrument!(
+            estimate,
+            "estimate_fee",
+            account = %format_args!("{:#x}", self.account.address()),
+            nonce = %format_args!("{nonce:#x}")
+        )
+        .await
     }
 }
 
 impl RawDeclaration {
+    pub fn contract_class(&self) -> &ContractArtifact {
+        &self.contract_class
+    }
+
+    pub fn nonce(&self) -> FieldElement {
+        self.nonce
+    }
+
+    pub fn max_fee(&self) -> FieldElement {
+        self.max_fee
+    }
+
     pub fn transaction_hash(
         &self,
         chain_id: FieldElement,
@@ -189,12 +234,24 @@ where
         &self,
     ) -> Result<AddTransactionResult, AccountError<A::SignError, <A::Provider as Provider>::Error>>
     {
-        let tx_request = self.get_declare_request().await?;
-        self.account
-            .provider()
-            .add_transaction(TransactionRequest::Declare(tx_request))
-            .await
-            .map_err(AccountError::Provider)
+        let send = async {
+            let tx_request = self.get_declare_request().await?;
+            self.account
+                .provider()
+                .add_transaction(TransactionRequest::Declare(tx_request))
+                .await
+                .map_err(AccountError::Provider)
+        };
+
+        // The transaction hash is left out as it requires hashing the class again
+        instrument!(
+            send,
+            "send",
+            account = %format_args!("{:#x}", self.account.address()),
+            nonce = %format_args!("{:#x}", self.inner.nonce),
+            max_fee = %format_args!("{:#x}", self.inner.max_fee)
+        )
+        .await
     }
 
     pub async fn get_declare_request(
@@ -0,0 +1,236 @@
+use super::{
+    AccountError, ConnectedAccount, ContractRevert, Execution, PreparedExecution, RawExecution,
+};
+use crate::Call;
+
+use starknet_core::types::{FieldElement, TransactionSimulationInfo};
+use starknet_providers::Provider;
+
+/// Selector for "__validate__"
+const SELECTOR_VALIDATE: FieldElement = FieldElement::from_mont([
+    12839380474732690315,
+    12285784131008321395,
+    8092503639404068326,
+    53381634366368345,
+]);
+
+/// Outcome of [Execution::diagnose].
+#[derive(Debug)]
+pub enum SimulationDiagnosis {
+    /// The whole execution simulated successfully.
+    Success(Box<TransactionSimulationInfo>),
+    /// The account rejects the transaction regardless of its calls, e.g. because `__validate__`
+    /// fails on an invalid signature or nonce, or the max fee is too low.
+    AccountFailure(Box<ContractRevert>),
+    /// A call makes the execution revert.
+    Failure(Box<CallFailure>),
+}
+
+/// The call responsible for a reverted execution.
+#[derive(Debug)]
+pub struct CallFailure {
+    /// Index of the first call whose inclusion makes the execution revert.
+    pub call_index: usize,
+    /// The failing call.
+    pub call: Call,
+    /// Revert returned when simulating the calls up to and including the failing one.
+    pub revert: ContractRevert,
+}
+
+impl<'a, A> Execution<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Simulates the execution and, if it reverts, bisects over prefixes of the calls to find
+    /// out which call causes the failure. Only a logarithmic number of simulations is needed
+    /// as any prefix containing the failing call reverts too.
+    ///
+    /// Reverts in `__validate__` are reported as [SimulationDiagnosis::AccountFailure] without
+    /// bisecting. So are failures where the revert doesn't tell the entry point, but the
+    /// execution without any call reverts as well.
+    pub async fn diagnose(
+        &self,
+    ) -> Result<SimulationDiagnosis, AccountError<A::SignError, <A::Provider as Provider>::Error>>
+    {
+        let nonce = match self.nonce {
+            Some(value) => value,
+            None => self
+                .account
+                .get_nonce()
+                .await
+                .map_err(AccountError::Provider)?,
+        };
+        let max_fee = self.max_fee.unwrap_or(FieldElement::ZERO);
+
+        let full_error = match self
+            .simulate_prefix(self.calls.len(), nonce, max_fee)
+            .await?
+        {
+            Ok(result) => return Ok(SimulationDiagnosis::Success(Box::new(result))),
+            Err(err) => err,
+        };
+        if self.calls.is_empty() || full_error.selector == Some(SELECTOR_VALIDATE) {
+            return Ok(SimulationDiagnosis::AccountFailure(Box::new(full_error)));
+        }
+
+        // Invariant: the prefix of length `high` fails, while the one of length `low` succeeds
+        let mut low = 0;
+        let mut high = self.calls.len();
+        let mut revert = full_error;
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            match self.simulate_prefix(mid, nonce, max_fee).await? {
+                Ok(_) => low = mid,
+                Err(err) => {
+                    high = mid;
+                    revert = err;
+                }
+            }
+        }
+
+        // The empty prefix is assumed to succeed above, which only holds if the failure comes
+        // from the calls
+        if high == 1 {
+            if let Err(err) = self.simulate_prefix(0, nonce, max_fee).await? {
+                return Ok(SimulationDiagnosis::AccountFailure(Box::new(err)));
+            }
+        }
+
+        let call_index = high - 1;
+        Ok(SimulationDiagnosis::Failure(Box::new(CallFailure {
+            call_index,
+            call: self.calls[call_index].clone(),
+            revert,
+        })))
+    }
+
+    /// Simulates the first `len` calls. Reverts are returned in the inner `Result`.
+    async fn simulate_prefix(
+        &self,
+        len: usize,
+        nonce: FieldElement,
+        max_fee: FieldElement,
+    ) -> Result<
+        Result<TransactionSimulationInfo, ContractRevert>,
+        AccountError<A::SignError, <A::Provider as Provider>::Error>,
+    > {
+        let prepared = PreparedExecution {
+            account: self.account,
+            inner: RawExecution {
+                calls: self.calls[..len].to_vec(),
+                nonce,
+                max_fee,
+            },
+        };
+
+        match prepared.simulate().await {
+            Ok(result) => Ok(Ok(result)),
+            Err(AccountError::Reverted(revert)) => Ok(Err(revert)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Account, SingleOwnerAccount};
+
+    use starknet_core::{chain_id, utils::get_selector_from_name};
+    use starknet_providers::{ChainFixture, MockProvider};
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    const ACCOUNT: FieldElement = FieldElement::TWO;
+
+    fn test_account(fixture: ChainFixture) -> SingleOwnerAccount<MockProvider, LocalWallet> {
+        SingleOwnerAccount::new(
+            fixture
+                .account(ACCOUNT, FieldElement::ONE, FieldElement::ONE)
+                .build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            ACCOUNT,
+            chain_id::TESTNET,
+        )
+    }
+
+    fn test_calls() -> Vec<Call> {
+        (0..4u64)
+            .map(|index| Call {
+                to: (0x10 + index).into(),
+                selector: (0x20 + index).into(),
+                calldata: vec![FieldElement::ONE],
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_validate_selector() {
+        assert_eq!(
+            SELECTOR_VALIDATE,
+            get_selector_from_name("__validate__").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_call_failure() {
+        let account = test_account(ChainFixture::new().execution_revert(
+            FieldElement::from(0x12u64),
+            FieldElement::from(0x22u64),
+            "Error at pc=0:1:\nError in the called contract (0x12):\nError message: boom",
+        ));
+
+        let failure = match account.execute(test_calls()).diagnose().await.unwrap() {
+            SimulationDiagnosis::Failure(failure) => failure,
+            diagnosis => panic!("unexpected diagnosis: {diagnosis:?}"),
+        };
+        assert_eq!(failure.call_index, 2);
+        assert_eq!(failure.call.to, FieldElement::from(0x12u64));
+        assert_eq!(
+            failure.revert.contract_address,
+            Some(FieldElement::from(0x12u64))
+        );
+        assert_eq!(failure.revert.reason.as_deref(), Some("boom"));
+
+        assert!(matches!(
+            account.execute(test_calls()[..2].to_vec()).diagnose().await,
+            Ok(SimulationDiagnosis::Success(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_validation_failure() {
+        // Cairo 1 reverts name the failing entry point
+        let account = test_account(ChainFixture::new().validation_revert(
+            ACCOUNT,
+            format!(
+                "Error in the called contract (contract address: {ACCOUNT:#x}, class hash: 0x1, \
+                selector: {SELECTOR_VALIDATE:#x}):\nExecution failed. Failure reason: \
+                0x696e76616c6964207369676e6174757265 ('invalid signature')."
+            ),
+        ));
+        match account.execute(test_calls()).diagnose().await.unwrap() {
+            SimulationDiagnosis::AccountFailure(revert) => {
+                assert_eq!(revert.reason.as_deref(), Some("invalid signature"))
+            }
+            diagnosis => panic!("unexpected diagnosis: {diagnosis:?}"),
+        }
+
+        // Cairo 0 ones don't, which the execution without calls tells apart from a failing call
+        let account = test_account(ChainFixture::new().validation_revert(
+            ACCOUNT,
+            "Error at pc=0:1:\nError in the called contract (0x2):\nError message: invalid signature",
+        ));
+        assert!(matches!(
+            account.execute(test_calls()).diagnose().await,
+            Ok(SimulationDiagnosis::AccountFailure(_))
+        ));
+
+        // Without any call to blame
+        assert!(matches!(
+            account.execute(vec![]).diagnose().await,
+            Ok(SimulationDiagnosis::AccountFailure(_))
+        ));
+    }
+}
@@ -1,8 +1,11 @@
 use super::{
-    super::NotPreparedError, Account, AccountError, ConnectedAccount, Execution, PreparedExecution,
-    RawExecution,
+    super::NotPreparedError, map_provider_error, Account, AccountError, ConnectedAccount,
+    Execution, ExecutionEncoding, PreparedExecution, RawExecution,
+};
+use crate::{
+    fee_strategy::{resolve_max_fee, PercentileOfRecentBlocks},
+    Call, FeeStrategy,
 };
-use crate::Call;
 
 use starknet_core::{
     crypto::compute_hash_on_elements,
@@ -12,6 +15,7 @@ use starknet_core::{
     },
 };
 use starknet_providers::Provider;
+use std::sync::Arc;
 
 /// Cairo string for "invoke"
 const PREFIX_INVOKE: FieldElement = FieldElement::from_mont([
@@ -28,7 +32,7 @@ impl<'a, A> Execution<'a, A> {
             calls,
             nonce: None,
             max_fee: None,
-            fee_estimate_multiplier: 1.1,
+            fee_strategy: Arc::new(PercentileOfRecentBlocks::default()),
         }
     }
 
@@ -46,6 +50,18 @@ impl<'a, A> Execution<'a, A> {
         }
     }
 
+    /// Sets the strategy used for deciding `max_fee` when it's not manually specified. Defaults
+    /// to the default [PercentileOfRecentBlocks].
+    pub fn fee_strategy<S>(self, fee_strategy: S) -> Self
+    where
+        S: FeeStrategy + 'static,
+    {
+        Self {
+            fee_strategy: Arc::new(fee_strategy),
+            ..self
+        }
+    }
+
     /// Calling this function after manually specifying `nonce` and `max_fee` turns [Execution] into
     /// [PreparedExecution]. Returns `Err` if either field is `None`.
     pub fn prepared(self) -> Result<PreparedExecution<'a, A>, NotPreparedError> {
@@ -99,7 +115,7 @@ where
         self.prepare().await?.send().await
     }
 
-    async fn prepare(
+    pub(super) async fn prepare(
         &self,
     ) -> Result<
         PreparedExecution<'a, A>,
@@ -120,7 +136,13 @@ where
             Some(value) => value,
             None => {
                 let fee_estimate = self.estimate_fee_with_nonce(nonce).await?;
-                ((fee_estimate.overall_fee as f64 * self.fee_estimate_multiplier) as u64).into()
+                resolve_max_fee(
+                    self.account.provider(),
+                    self.fee_strategy.as_ref(),
+                    &fee_estimate,
+                )
+                .await
+                .map_err(AccountError::Provider)?
             }
         };
 
@@ -146,24 +168,71 @@ where
                 max_fee: FieldElement::ZERO,
             },
         };
-        let invoke = prepared
-            .get_invoke_request()
-            .await
-            .map_err(AccountError::Signing)?;
+        let estimate = async {
+            let invoke = prepared
+                .get_invoke_request()
+                .await
+                .map_err(AccountError::Signing)?;
 
-        self.account
-            .provider()
-            .estimate_fee(
-                AccountTransaction::InvokeFunction(invoke),
-                self.account.block_id(),
-            )
-            .await
-            .map_err(AccountError::Provider)
+            self.account
+                .provider()
+                .estimate_fee(
+                    AccountTransaction::InvokeFunction(invoke),
+                    self.account.block_id(),
+                )
+                .await
+                .map_err(map_provider_error)
+        };
+
+        instrument!(
+            estimate,
+            "estimate_fee",
+            account = %format_args!("{:#x}", self.account.address()),
+            nonce = %format_args!("{nonce:#x}")
+        )
+        .await
     }
 }
 
 impl RawExecution {
-    pub fn raw_calldata(&self) -> Vec<FieldElement> {
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    pub fn nonce(&self) -> FieldElement {
+        self.nonce
+    }
+
+    pub fn max_fee(&self) -> FieldElement {
+        self.max_fee
+    }
+
+    pub fn raw_calldata(&self, encoding: ExecutionEncoding) -> Vec<FieldElement> {
+        match encoding {
+            ExecutionEncoding::Legacy => self.legacy_calldata(),
+            ExecutionEncoding::New => self.new_calldata(),
+        }
+    }
+
+    pub fn transaction_hash(
+        &self,
+        chain_id: FieldElement,
+        address: FieldElement,
+        encoding: ExecutionEncoding,
+    ) -> FieldElement {
+        compute_hash_on_elements(&[
+            PREFIX_INVOKE,
+            FieldElement::ONE, // version
+            address,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&self.raw_calldata(encoding)),
+            self.max_fee,
+            chain_id,
+            self.nonce,
+        ])
+    }
+
+    fn legacy_calldata(&self) -> Vec<FieldElement> {
         let mut concated_calldata: Vec<FieldElement> = vec![];
         let mut execute_calldata: Vec<FieldElement> = vec![self.calls.len().into()];
         for call in self.calls.iter() {
@@ -184,23 +253,18 @@ impl RawExecution {
         execute_calldata
     }
 
-    pub fn transaction_hash(&self, chain_id: FieldElement, address: FieldElement) -> FieldElement {
-        compute_hash_on_elements(&[
-            PREFIX_INVOKE,
-            FieldElement::ONE, // version
-            address,
-            FieldElement::ZERO, // entry_point_selector
-            compute_hash_on_elements(&self.raw_calldata()),
-            self.max_fee,
-            chain_id,
-            self.nonce,
-        ])
-    }
-}
+    fn new_calldata(&self) -> Vec<FieldElement> {
+        let mut execute_calldata: Vec<FieldElement> = vec![self.calls.len().into()];
+        for call in self.calls.iter() {
+            execute_calldata.push(call.to); // to
+            execute_calldata.push(call.selector); // selector
+            execute_calldata.push(call.calldata.len().into()); // calldata.len()
+            for item in call.calldata.iter() {
+                execute_calldata.push(*item); // calldata
+            }
+        }
 
-impl<'a, A> PreparedExecution<'a, A> {
-    pub fn raw_calldata(&self) -> Vec<FieldElement> {
-        self.inner.raw_calldata()
+        execute_calldata
     }
 }
 
@@ -208,11 +272,37 @@ impl<'a, A> PreparedExecution<'a, A>
 where
     A: Account,
 {
+    /// The `__execute__` calldata of this execution, encoded as expected by the account.
+    pub fn raw_calldata(&self) -> Vec<FieldElement> {
+        self.inner.raw_calldata(self.account.execution_encoding())
+    }
+
     /// Locally calculates the hash of the transaction to be sent from this execution given the
     /// parameters.
     pub fn transaction_hash(&self) -> FieldElement {
-        self.inner
-            .transaction_hash(self.account.chain_id(), self.account.address())
+        self.inner.transaction_hash(
+            self.account.chain_id(),
+            self.account.address(),
+            self.account.execution_encoding(),
+        )
+    }
+
+    /// Signs the execution and builds the transaction request without submitting it. This only
+    /// requires an [Account], making it possible to sign transactions offline and broadcast them
+    /// later with [ConnectedAccount::broadcast] or
+    /// [Provider::add_transaction](starknet_providers::Provider::add_transaction).
+    pub async fn get_invoke_request(
+        &self,
+    ) -> Result<InvokeFunctionTransactionRequest, A::SignError> {
+        let signature = self.account.sign_execution(&self.inner).await?;
+
+        Ok(InvokeFunctionTransactionRequest {
+            contract_address: self.account.address(),
+            calldata: self.raw_calldata(),
+            signature,
+            max_fee: self.inner.max_fee,
+            nonce: self.inner.nonce,
+        })
     }
 }
 
@@ -224,15 +314,27 @@ where
         &self,
     ) -> Result<AddTransactionResult, AccountError<A::SignError, <A::Provider as Provider>::Error>>
     {
-        let tx_request = self
-            .get_invoke_request()
-            .await
-            .map_err(AccountError::Signing)?;
-        self.account
-            .provider()
-            .add_transaction(TransactionRequest::InvokeFunction(tx_request))
-            .await
-            .map_err(AccountError::Provider)
+        let send = async {
+            let tx_request = self
+                .get_invoke_request()
+                .await
+                .map_err(AccountError::Signing)?;
+            self.account
+                .provider()
+                .add_transaction(TransactionRequest::InvokeFunction(tx_request))
+                .await
+                .map_err(map_provider_error)
+        };
+
+        instrument!(
+            send,
+            "send",
+            account = %format_args!("{:#x}", self.account.address()),
+            nonce = %format_args!("{:#x}", self.inner.nonce),
+            max_fee = %format_args!("{:#x}", self.inner.max_fee),
+            transaction_hash = %format_args!("{:#x}", self.transaction_hash())
+        )
+        .await
     }
 
     pub async fn simulate(
@@ -252,20 +354,110 @@ where
                 self.account.block_id(),
             )
             .await
-            .map_err(AccountError::Provider)
+            .map_err(map_provider_error)
     }
+}
 
-    pub async fn get_invoke_request(
-        &self,
-    ) -> Result<InvokeFunctionTransactionRequest, A::SignError> {
-        let signature = self.account.sign_execution(&self.inner).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(InvokeFunctionTransactionRequest {
-            contract_address: self.account.address(),
-            calldata: self.raw_calldata(),
-            signature,
-            max_fee: self.inner.max_fee,
-            nonce: self.inner.nonce,
-        })
+    fn test_execution() -> RawExecution {
+        RawExecution {
+            calls: vec![
+                Call {
+                    to: FieldElement::from_hex_be("0x1111").unwrap(),
+                    selector: FieldElement::from_hex_be("0x2222").unwrap(),
+                    calldata: vec![FieldElement::ONE, FieldElement::TWO],
+                },
+                Call {
+                    to: FieldElement::from_hex_be("0x3333").unwrap(),
+                    selector: FieldElement::from_hex_be("0x4444").unwrap(),
+                    calldata: vec![FieldElement::THREE],
+                },
+            ],
+            nonce: FieldElement::ZERO,
+            max_fee: FieldElement::ZERO,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_legacy_calldata() {
+        let calldata = test_execution().raw_calldata(ExecutionEncoding::Legacy);
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::from_hex_be("0x1111").unwrap(),
+                FieldElement::from_hex_be("0x2222").unwrap(),
+                FieldElement::ZERO,
+                FieldElement::TWO,
+                FieldElement::from_hex_be("0x3333").unwrap(),
+                FieldElement::from_hex_be("0x4444").unwrap(),
+                FieldElement::TWO,
+                FieldElement::ONE,
+                FieldElement::THREE,
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::THREE,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_new_calldata() {
+        let calldata = test_execution().raw_calldata(ExecutionEncoding::New);
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::from_hex_be("0x1111").unwrap(),
+                FieldElement::from_hex_be("0x2222").unwrap(),
+                FieldElement::TWO,
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::from_hex_be("0x3333").unwrap(),
+                FieldElement::from_hex_be("0x4444").unwrap(),
+                FieldElement::ONE,
+                FieldElement::THREE,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_transaction_hash_matches_sign_context() {
+        let execution = test_execution();
+        let chain_id = FieldElement::from_hex_be("0x534e5f474f45524c49").unwrap();
+        let address = FieldElement::from_hex_be("0x5555").unwrap();
+
+        for encoding in [ExecutionEncoding::Legacy, ExecutionEncoding::New] {
+            let context = starknet_signers::InvokeContext {
+                sender_address: address,
+                chain_id,
+                encoding: encoding.into(),
+                calls: execution
+                    .calls()
+                    .iter()
+                    .map(|call| starknet_signers::CallContext {
+                        to: call.to,
+                        selector: call.selector,
+                        calldata: call.calldata.clone(),
+                    })
+                    .collect(),
+                max_fee: execution.max_fee(),
+                nonce: execution.nonce(),
+                is_query: false,
+            };
+
+            assert_eq!(
+                context.transaction_hash(),
+                execution.transaction_hash(chain_id, address, encoding)
+            );
+        }
     }
 }
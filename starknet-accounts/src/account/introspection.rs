@@ -0,0 +1,236 @@
+use super::{ConnectedAccount, ContractRevert};
+
+use starknet_core::types::{CallFunction, FieldElement};
+use starknet_providers::{Provider, ProviderError};
+
+/// Selector for "get_public_key"
+const SELECTOR_GET_PUBLIC_KEY: FieldElement = FieldElement::from_mont([
+    5983463759964889175,
+    12079404926548571373,
+    14757966273196622458,
+    519850620901034680,
+]);
+
+/// Selector for "getPublicKey"
+const SELECTOR_GET_PUBLIC_KEY_CAMEL: FieldElement = FieldElement::from_mont([
+    2855105476460595123,
+    7694545803709995991,
+    13920766374511119240,
+    341288489095031238,
+]);
+
+/// Selector for "get_signer"
+const SELECTOR_GET_SIGNER: FieldElement = FieldElement::from_mont([
+    18157973451846304013,
+    10450279030615752036,
+    16496923423424276844,
+    368184217634285129,
+]);
+
+/// Selector for "getSigner"
+const SELECTOR_GET_SIGNER_CAMEL: FieldElement = FieldElement::from_mont([
+    7895897793681836289,
+    17240968914466665807,
+    12471379084001270314,
+    546500369521461831,
+]);
+
+/// Selector for "get_owner"
+const SELECTOR_GET_OWNER: FieldElement = FieldElement::from_mont([
+    18395449382186227442,
+    17068803144574902052,
+    4416748967258047070,
+    442619815559462169,
+]);
+
+/// Selector for "get_guardian"
+const SELECTOR_GET_GUARDIAN: FieldElement = FieldElement::from_mont([
+    14332321936309964291,
+    8280125715962647494,
+    6112789511728604338,
+    240305754789198771,
+]);
+
+/// Selector for "getGuardian"
+const SELECTOR_GET_GUARDIAN_CAMEL: FieldElement = FieldElement::from_mont([
+    16090516637507059378,
+    3075514913400711500,
+    9733519803833994317,
+    302517553881068223,
+]);
+
+/// Selector for "get_version"
+const SELECTOR_GET_VERSION: FieldElement = FieldElement::from_mont([
+    843745694034400210,
+    10798941708311215702,
+    17197088686503255267,
+    177771018813312686,
+]);
+
+/// Selector for "getVersion"
+const SELECTOR_GET_VERSION_CAMEL: FieldElement = FieldElement::from_mont([
+    5395624809308585004,
+    13307284651102987744,
+    9481212759385736873,
+    265359506450716353,
+]);
+
+/// View functions returning the account signer keys, across OpenZeppelin, Argent and Braavos
+/// implementations.
+pub(super) const PUBLIC_KEY_SELECTORS: [FieldElement; 5] = [
+    SELECTOR_GET_PUBLIC_KEY,
+    SELECTOR_GET_PUBLIC_KEY_CAMEL,
+    SELECTOR_GET_SIGNER,
+    SELECTOR_GET_SIGNER_CAMEL,
+    SELECTOR_GET_OWNER,
+];
+
+pub(super) const GUARDIAN_SELECTORS: [FieldElement; 2] =
+    [SELECTOR_GET_GUARDIAN, SELECTOR_GET_GUARDIAN_CAMEL];
+
+pub(super) const VERSION_SELECTORS: [FieldElement; 2] =
+    [SELECTOR_GET_VERSION, SELECTOR_GET_VERSION_CAMEL];
+
+/// Calls the first view function in `selectors` that exists on the account contract. Only calls
+/// failing because the entry point doesn't exist fall through to the next selector, and the error
+/// of the last attempt is returned if none exists.
+pub(super) async fn call_first_available<A>(
+    account: &A,
+    selectors: &[FieldElement],
+) -> Result<Vec<FieldElement>, ProviderError<<A::Provider as Provider>::Error>>
+where
+    A: ConnectedAccount + Sync,
+{
+    let mut last_error = None;
+
+    for selector in selectors.iter() {
+        match account
+            .provider()
+            .call_contract(
+                CallFunction {
+                    contract_address: account.address(),
+                    entry_point_selector: *selector,
+                    calldata: vec![],
+                },
+                account.block_id(),
+            )
+            .await
+        {
+            Ok(result) => return Ok(result.result),
+            Err(err)
+                if ContractRevert::parse(&err.to_string())
+                    .is_some_and(|revert| revert.is_entry_point_not_found()) =>
+            {
+                last_error = Some(err)
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // Safe to unwrap as `selectors` is never empty
+    Err(last_error.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SingleOwnerAccount;
+
+    use starknet_core::{chain_id, utils::get_selector_from_name};
+    use starknet_providers::{ChainFixture, MockProvider, MockProviderError};
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    const ACCOUNT: FieldElement = FieldElement::TWO;
+
+    fn test_account(fixture: ChainFixture) -> SingleOwnerAccount<MockProvider, LocalWallet> {
+        SingleOwnerAccount::new(
+            fixture.deploy(ACCOUNT, FieldElement::ONE).build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            ACCOUNT,
+            chain_id::TESTNET,
+        )
+    }
+
+    fn entry_point_not_found(selector: FieldElement) -> String {
+        format!("Entry point {selector:#x} not found in contract with class hash 0x1.")
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_introspection_selectors() {
+        let names = [
+            "get_public_key",
+            "getPublicKey",
+            "get_signer",
+            "getSigner",
+            "get_owner",
+            "get_guardian",
+            "getGuardian",
+            "get_version",
+            "getVersion",
+        ];
+        let selectors = PUBLIC_KEY_SELECTORS
+            .iter()
+            .chain(GUARDIAN_SELECTORS.iter())
+            .chain(VERSION_SELECTORS.iter());
+
+        for (name, selector) in names.iter().zip(selectors) {
+            assert_eq!(*selector, get_selector_from_name(name).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_first_available() {
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(
+                    ACCOUNT,
+                    SELECTOR_GET_VERSION,
+                    entry_point_not_found(SELECTOR_GET_VERSION),
+                )
+                .call_result(ACCOUNT, SELECTOR_GET_VERSION_CAMEL, vec![FieldElement::TWO]),
+        );
+        assert_eq!(
+            call_first_available(&account, &VERSION_SELECTORS)
+                .await
+                .unwrap(),
+            [FieldElement::TWO]
+        );
+
+        // Other failures are returned instead of trying the next selector
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(
+                    ACCOUNT,
+                    SELECTOR_GET_VERSION,
+                    "Error in the called contract: out of gas",
+                )
+                .call_result(ACCOUNT, SELECTOR_GET_VERSION_CAMEL, vec![FieldElement::TWO]),
+        );
+        assert!(matches!(
+            call_first_available(&account, &VERSION_SELECTORS).await,
+            Err(ProviderError::Other(MockProviderError::Reverted(message)))
+                if message.contains("out of gas")
+        ));
+
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(
+                    ACCOUNT,
+                    SELECTOR_GET_VERSION,
+                    entry_point_not_found(SELECTOR_GET_VERSION),
+                )
+                .call_revert(
+                    ACCOUNT,
+                    SELECTOR_GET_VERSION_CAMEL,
+                    entry_point_not_found(SELECTOR_GET_VERSION_CAMEL),
+                ),
+        );
+        assert!(matches!(
+            call_first_available(&account, &VERSION_SELECTORS).await,
+            Err(ProviderError::Other(MockProviderError::Reverted(message)))
+                if message == entry_point_not_found(SELECTOR_GET_VERSION_CAMEL)
+        ));
+    }
+}
@@ -1,15 +1,36 @@
-use crate::Call;
+use crate::{Call, FeeStrategy, PreparedAccountDeployment};
 
 use async_trait::async_trait;
 use starknet_core::types::{
     contract_artifact::{CompressProgramError, ComputeClassHashError},
-    BlockId, ContractArtifact, FieldElement,
+    AbiEntry, AccountTransaction, AddTransactionResult, AsStarknetError, BlockId, ContractArtifact,
+    FeeEstimate, FieldElement, StarknetError, TransactionRequest,
 };
 use starknet_providers::{Provider, ProviderError};
+use starknet_signers::CalldataEncoding;
 use std::{error::Error, sync::Arc};
 
 mod declaration;
 mod execution;
+mod introspection;
+mod signature;
+
+mod diagnosis;
+pub use diagnosis::{CallFailure, SimulationDiagnosis};
+
+mod preview;
+pub use preview::{ArgumentPreview, CallPreview, ExecutionPreview};
+
+mod revert;
+use revert::map_provider_error;
+pub use revert::ContractRevert;
+
+mod resubmission;
+pub(crate) use resubmission::bump_max_fee;
+pub use resubmission::{ResubmissionPolicy, ResubmissionStatus, ResubmittingExecution};
+
+mod upgrade;
+pub use upgrade::AccountImplementation;
 
 /// The standard Starknet account contract interface. It makes no assumption about the underlying
 /// signer or provider. Account implementations that come with an active connection to the network
@@ -24,6 +45,19 @@ pub trait Account: Sized {
 
     fn chain_id(&self) -> FieldElement;
 
+    /// The `__execute__` calldata layout expected by the account contract. Defaults to
+    /// [ExecutionEncoding::Legacy] for compatibility with existing Cairo 0 accounts.
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        ExecutionEncoding::Legacy
+    }
+
+    /// Whether signing requires user interaction, e.g. confirming on a hardware device. Fee
+    /// estimation requires a signature as well, so callers should specify `max_fee` manually to
+    /// avoid prompting users twice for the same transaction. Defaults to `false`.
+    fn is_signer_interactive(&self) -> bool {
+        false
+    }
+
     async fn sign_execution(
         &self,
         execution: &RawExecution,
@@ -41,6 +75,30 @@ pub trait Account: Sized {
     fn declare(&self, contract_class: Arc<ContractArtifact>) -> Declaration<Self> {
         Declaration::new(contract_class, self)
     }
+
+    /// Creates an execution meant to be sent right after `deployment` deploys this account, without
+    /// waiting for the deployment to be accepted. The execution is assigned the nonce following
+    /// the one of the deployment.
+    ///
+    /// Transactions in this version of the protocol can't carry deployment data, so deploying and
+    /// executing still takes two transactions. As the account doesn't exist before the deployment,
+    /// `max_fee` can't be estimated and must be set manually.
+    fn execute_with_deployment<F>(
+        &self,
+        calls: Vec<Call>,
+        deployment: &PreparedAccountDeployment<'_, F>,
+    ) -> Execution<'_, Self> {
+        Execution::new(calls, self).nonce(deployment.nonce() + FieldElement::ONE)
+    }
+
+    /// Upgrades the account contract to `new_class_hash` through its own `upgrade` entrypoint.
+    fn upgrade(
+        &self,
+        implementation: AccountImplementation,
+        new_class_hash: FieldElement,
+    ) -> Execution<'_, Self> {
+        Execution::upgrade(self, implementation, new_class_hash)
+    }
 }
 
 /// An [Account] implementation that also comes with a [Provider]. Functionalities that require a
@@ -65,6 +123,165 @@ pub trait ConnectedAccount: Account {
             .get_nonce(self.address(), self.block_id())
             .await
     }
+
+    /// Reads the class hash of the deployed account contract.
+    async fn get_class_hash(
+        &self,
+    ) -> Result<FieldElement, ProviderError<<Self::Provider as Provider>::Error>> {
+        self.provider()
+            .get_class_hash_at(self.address(), self.block_id())
+            .await
+    }
+
+    /// Reads the public key(s) of the signer(s) registered on the account contract, e.g. to check
+    /// that the local signer matches the deployed account before sending transactions.
+    async fn get_public_keys(
+        &self,
+    ) -> Result<Vec<FieldElement>, ProviderError<<Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        introspection::call_first_available(self, &introspection::PUBLIC_KEY_SELECTORS).await
+    }
+
+    /// Reads the guardian key of accounts supporting guardians (e.g. Argent X). A value of `0`
+    /// means that no guardian is set.
+    async fn get_guardian(
+        &self,
+    ) -> Result<FieldElement, ProviderError<<Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        let result =
+            introspection::call_first_available(self, &introspection::GUARDIAN_SELECTORS).await?;
+        Ok(result.first().cloned().unwrap_or(FieldElement::ZERO))
+    }
+
+    /// Reads the implementation version reported by the account contract, usually a Cairo short
+    /// string such as "0.2.3".
+    async fn get_implementation_version(
+        &self,
+    ) -> Result<FieldElement, ProviderError<<Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        let result =
+            introspection::call_first_available(self, &introspection::VERSION_SELECTORS).await?;
+        Ok(result.first().cloned().unwrap_or(FieldElement::ZERO))
+    }
+
+    /// Submits an already-signed transaction as-is without re-signing. The transaction can be
+    /// produced offline (e.g. with [PreparedExecution::get_invoke_request]) or by another system.
+    async fn broadcast(
+        &self,
+        tx: TransactionRequest,
+    ) -> Result<AddTransactionResult, ProviderError<<Self::Provider as Provider>::Error>> {
+        self.provider().add_transaction(tx).await
+    }
+
+    /// Estimates the fees of a batch of executions in a single provider request. Executions that
+    /// don't specify a `nonce` are assigned sequential nonces following the previous execution
+    /// (or the current account nonce for the first one), as if sent one after another.
+    async fn estimate_fee_bulk(
+        &self,
+        executions: &[Execution<'_, Self>],
+    ) -> Result<Vec<FeeEstimate>, AccountError<Self::SignError, <Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        let mut next_nonce = match executions.first() {
+            Some(Execution {
+                nonce: Some(nonce), ..
+            }) => *nonce,
+            Some(_) => self.get_nonce().await.map_err(AccountError::Provider)?,
+            None => return Ok(vec![]),
+        };
+
+        let mut txs = vec![];
+        for execution in executions.iter() {
+            let nonce = execution.nonce.unwrap_or(next_nonce);
+            next_nonce = nonce + FieldElement::ONE;
+
+            let prepared = PreparedExecution {
+                account: self,
+                inner: RawExecution {
+                    calls: execution.calls.clone(),
+                    nonce,
+                    max_fee: FieldElement::ZERO,
+                },
+            };
+            let invoke = prepared
+                .get_invoke_request()
+                .await
+                .map_err(AccountError::Signing)?;
+
+            txs.push(AccountTransaction::InvokeFunction(invoke));
+        }
+
+        self.provider()
+            .estimate_fee_bulk(&txs, self.block_id())
+            .await
+            .map_err(map_provider_error)
+    }
+
+    /// Re-reads the class hash of the account after an [upgrade](Account::upgrade) to check that
+    /// it now runs `expected_class_hash`. For proxy-based accounts the implementation class hash
+    /// is read from the proxy instead.
+    async fn verify_upgrade(
+        &self,
+        implementation: AccountImplementation,
+        expected_class_hash: FieldElement,
+    ) -> Result<bool, ProviderError<<Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        upgrade::verify_upgrade(self, implementation, expected_class_hash).await
+    }
+
+    /// Checks a signature over `message_hash` (e.g. a SNIP-12 typed data hash) against the
+    /// account contract by calling its `is_valid_signature` view, or its legacy
+    /// `isValidSignature` one if the former doesn't exist. Accounts reverting on invalid
+    /// signatures, instead of returning, also yield `false`.
+    async fn verify_signature(
+        &self,
+        message_hash: FieldElement,
+        signature: &[FieldElement],
+    ) -> Result<bool, ProviderError<<Self::Provider as Provider>::Error>>
+    where
+        Self: Sync,
+    {
+        signature::verify_signature(self, message_hash, signature).await
+    }
+
+    /// Detects the [ExecutionEncoding] expected by the account by inspecting the ABI of its
+    /// class. Accounts exposing a `call_array` argument on `__execute__` use the legacy encoding.
+    ///
+    /// Classes that aren't legacy (Cairo 0) artifacts, such as Sierra classes, use the new
+    /// encoding. Errors fetching the class are returned.
+    async fn detect_execution_encoding(
+        &self,
+    ) -> Result<ExecutionEncoding, ProviderError<<Self::Provider as Provider>::Error>> {
+        let class_hash = self
+            .provider()
+            .get_class_hash_at(self.address(), self.block_id())
+            .await?;
+        let class = self.provider().get_raw_class_by_hash(class_hash).await?;
+
+        Ok(match serde_json::from_value::<ContractArtifact>(class) {
+            Ok(class) => ExecutionEncoding::from_abi(&class.abi),
+            Err(_) => ExecutionEncoding::New,
+        })
+    }
+}
+
+/// The layout of the calldata sent to the account's `__execute__` entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionEncoding {
+    /// The call array layout used by Cairo 0 accounts: all call metadata followed by the
+    /// concatenated calldata.
+    Legacy,
+    /// The `Array<Call>` layout used by Cairo 1 accounts, with each call's calldata inlined.
+    New,
 }
 
 /// An intermediate type allowing users to optionally specify `nonce` and/or `max_fee`.
@@ -75,7 +292,7 @@ pub struct Execution<'a, A> {
     calls: Vec<Call>,
     nonce: Option<FieldElement>,
     max_fee: Option<FieldElement>,
-    fee_estimate_multiplier: f64,
+    fee_strategy: Arc<dyn FeeStrategy>,
 }
 
 /// An intermediate type allowing users to optionally specify `nonce` and/or `max_fee`.
@@ -86,7 +303,7 @@ pub struct Declaration<'a, A> {
     contract_class: Arc<ContractArtifact>,
     nonce: Option<FieldElement>,
     max_fee: Option<FieldElement>,
-    fee_estimate_multiplier: f64,
+    fee_strategy: Arc<dyn FeeStrategy>,
 }
 
 /// [Execution] but with `nonce` and `max_fee` already determined.
@@ -125,12 +342,57 @@ pub enum AccountError<S, P> {
     Signing(S),
     #[error(transparent)]
     Provider(ProviderError<P>),
+    /// The transaction was rejected by the provider because a contract reverted.
+    #[error(transparent)]
+    Reverted(ContractRevert),
     #[error(transparent)]
     ClassHashCalculation(ComputeClassHashError),
     #[error(transparent)]
     ClassCompression(CompressProgramError),
 }
 
+impl<S, P> AsStarknetError for AccountError<S, P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Provider(err) => err.starknet_error(),
+            Self::Reverted(_) => Some(StarknetError::ContractError),
+            Self::Signing(_) | Self::ClassHashCalculation(_) | Self::ClassCompression(_) => None,
+        }
+    }
+}
+
+impl ExecutionEncoding {
+    /// Determines the encoding from the ABI of an account class. The legacy layout is identified
+    /// by the `call_array` argument of `__execute__`.
+    pub fn from_abi(abi: &[AbiEntry]) -> Self {
+        let is_legacy = abi.iter().any(|entry| match entry {
+            AbiEntry::Function(function) => {
+                function.name == "__execute__"
+                    && function
+                        .inputs
+                        .iter()
+                        .any(|input| input.name == "call_array")
+            }
+            _ => false,
+        });
+
+        if is_legacy {
+            Self::Legacy
+        } else {
+            Self::New
+        }
+    }
+}
+
+impl From<ExecutionEncoding> for CalldataEncoding {
+    fn from(value: ExecutionEncoding) -> Self {
+        match value {
+            ExecutionEncoding::Legacy => Self::Legacy,
+            ExecutionEncoding::New => Self::New,
+        }
+    }
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl<A> Account for &A
@@ -147,6 +409,14 @@ where
         (*self).chain_id()
     }
 
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        (*self).execution_encoding()
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        (*self).is_signer_interactive()
+    }
+
     async fn sign_execution(
         &self,
         execution: &RawExecution,
@@ -178,6 +448,14 @@ where
         self.as_ref().chain_id()
     }
 
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.as_ref().execution_encoding()
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.as_ref().is_signer_interactive()
+    }
+
     async fn sign_execution(
         &self,
         execution: &RawExecution,
@@ -209,6 +487,14 @@ where
         self.as_ref().chain_id()
     }
 
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.as_ref().execution_encoding()
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.as_ref().is_signer_interactive()
+    }
+
     async fn sign_execution(
         &self,
         execution: &RawExecution,
@@ -256,3 +542,63 @@ where
         self.as_ref().provider()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SingleOwnerAccount;
+
+    use starknet_core::{chain_id, types::StarknetError};
+    use starknet_providers::{ChainFixture, MockProvider};
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    const ACCOUNT: FieldElement = FieldElement::TWO;
+
+    fn test_account(fixture: ChainFixture) -> SingleOwnerAccount<MockProvider, LocalWallet> {
+        SingleOwnerAccount::new(
+            fixture.deploy(ACCOUNT, FieldElement::ONE).build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            ACCOUNT,
+            chain_id::TESTNET,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_detect_execution_encoding() {
+        let artifact: ContractArtifact =
+            serde_json::from_str(include_str!("../../test-data/artifacts/oz_account.txt")).unwrap();
+        let account = test_account(ChainFixture::new().declare(FieldElement::ONE, &artifact));
+        assert_eq!(
+            account.detect_execution_encoding().await.unwrap(),
+            ExecutionEncoding::Legacy
+        );
+
+        // Sierra classes can't be deserialized as legacy artifacts
+        let account = test_account(ChainFixture::new().declare_json(
+            FieldElement::ONE,
+            serde_json::json!({
+                "sierra_program": ["0x1"],
+                "contract_class_version": "0.1.0",
+                "entry_points_by_type": {
+                    "EXTERNAL": [],
+                    "L1_HANDLER": [],
+                    "CONSTRUCTOR": []
+                },
+                "abi": "[]"
+            }),
+        ));
+        assert_eq!(
+            account.detect_execution_encoding().await.unwrap(),
+            ExecutionEncoding::New
+        );
+
+        let account = test_account(ChainFixture::new());
+        assert!(matches!(
+            account.detect_execution_encoding().await,
+            Err(ProviderError::StarknetError(
+                StarknetError::ClassHashNotFound
+            ))
+        ));
+    }
+}
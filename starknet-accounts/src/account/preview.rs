@@ -0,0 +1,289 @@
+use super::{ConnectedAccount, Execution};
+
+use starknet_core::{
+    types::{AbiEntry, AbiFunctionEntry, FieldElement},
+    utils::get_selector_from_name,
+};
+use starknet_providers::Provider;
+use std::{collections::HashMap, fmt};
+
+/// Display-friendly summary of an [Execution], intended for confirmation prompts.
+#[derive(Debug, Clone)]
+pub struct ExecutionPreview {
+    pub calls: Vec<CallPreview>,
+    /// Nonce as currently set on the builder, if any.
+    pub nonce: Option<FieldElement>,
+    /// Maximum fee as currently set on the builder, if any.
+    pub max_fee: Option<FieldElement>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CallPreview {
+    pub to: FieldElement,
+    pub selector: FieldElement,
+    /// Name of the function called, if found in the target contract ABI.
+    pub function_name: Option<String>,
+    /// Calldata decoded against the function inputs, if the ABI is known and the calldata matches.
+    pub arguments: Option<Vec<ArgumentPreview>>,
+    pub calldata: Vec<FieldElement>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArgumentPreview {
+    pub name: String,
+    pub r#type: String,
+    pub value: Vec<FieldElement>,
+}
+
+impl<'a, A> Execution<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Builds a summary of the execution without signing it. ABIs of the target contracts are
+    /// fetched from the provider to resolve function names and decode calldata. Contracts whose
+    /// class can't be fetched are shown with raw selectors and calldata.
+    pub async fn preview(&self) -> ExecutionPreview {
+        let mut abis: HashMap<FieldElement, Option<Vec<AbiEntry>>> = HashMap::new();
+
+        for call in self.calls.iter() {
+            if abis.contains_key(&call.to) {
+                continue;
+            }
+
+            let abi = match self
+                .account
+                .provider()
+                .get_class_hash_at(call.to, self.account.block_id())
+                .await
+            {
+                Ok(class_hash) => self
+                    .account
+                    .provider()
+                    .get_class_by_hash(class_hash)
+                    .await
+                    .ok()
+                    .map(|class| class.abi),
+                Err(_) => None,
+            };
+            abis.insert(call.to, abi);
+        }
+
+        ExecutionPreview {
+            calls: self
+                .calls
+                .iter()
+                .map(|call| {
+                    let function = abis
+                        .get(&call.to)
+                        .and_then(|abi| abi.as_ref())
+                        .and_then(|abi| find_function(abi, call.selector).map(|func| (abi, func)));
+
+                    match function {
+                        Some((abi, function)) => CallPreview {
+                            to: call.to,
+                            selector: call.selector,
+                            function_name: Some(function.name.clone()),
+                            arguments: decode_arguments(abi, function, &call.calldata),
+                            calldata: call.calldata.clone(),
+                        },
+                        None => CallPreview {
+                            to: call.to,
+                            selector: call.selector,
+                            function_name: None,
+                            arguments: None,
+                            calldata: call.calldata.clone(),
+                        },
+                    }
+                })
+                .collect(),
+            nonce: self.nonce,
+            max_fee: self.max_fee,
+        }
+    }
+}
+
+impl fmt::Display for ExecutionPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (ind, call) in self.calls.iter().enumerate() {
+            match &call.function_name {
+                Some(name) => writeln!(f, "Call #{ind}: {name} on {:#064x}", call.to)?,
+                None => writeln!(
+                    f,
+                    "Call #{ind}: {:#064x} on {:#064x}",
+                    call.selector, call.to
+                )?,
+            }
+
+            match &call.arguments {
+                Some(arguments) => {
+                    for argument in arguments.iter() {
+                        write!(f, "  {}: {} =", argument.name, argument.r#type)?;
+                        for value in argument.value.iter() {
+                            write!(f, " {value:#x}")?;
+                        }
+                        writeln!(f)?;
+                    }
+                }
+                None => {
+                    write!(f, "  calldata:")?;
+                    for value in call.calldata.iter() {
+                        write!(f, " {value:#x}")?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+        }
+
+        match self.nonce {
+            Some(nonce) => writeln!(f, "Nonce: {nonce}")?,
+            None => writeln!(f, "Nonce: (latest)")?,
+        }
+        match self.max_fee {
+            Some(max_fee) => write!(f, "Max fee: {max_fee} wei"),
+            None => write!(f, "Max fee: (estimated)"),
+        }
+    }
+}
+
+fn find_function(abi: &[AbiEntry], selector: FieldElement) -> Option<&AbiFunctionEntry> {
+    abi.iter().find_map(|entry| match entry {
+        AbiEntry::Function(function)
+            if get_selector_from_name(&function.name).ok() == Some(selector) =>
+        {
+            Some(function)
+        }
+        _ => None,
+    })
+}
+
+/// Splits calldata into the function inputs. Returns `None` if the calldata doesn't match the
+/// inputs or they use types that can't be sized.
+fn decode_arguments(
+    abi: &[AbiEntry],
+    function: &AbiFunctionEntry,
+    calldata: &[FieldElement],
+) -> Option<Vec<ArgumentPreview>> {
+    let mut arguments: Vec<ArgumentPreview> = vec![];
+    let mut offset = 0usize;
+
+    for input in function.inputs.iter() {
+        let size = if input.r#type == "felt" {
+            1
+        } else if let Some(element_type) = input.r#type.strip_suffix('*') {
+            // Arrays are preceded by their length argument
+            let len: u64 = (*arguments.last()?.value.first()?).try_into().ok()?;
+            usize::try_from(len)
+                .ok()?
+                .checked_mul(type_size(abi, element_type)?)?
+        } else {
+            type_size(abi, &input.r#type)?
+        };
+
+        let end = offset.checked_add(size)?;
+        let value = calldata.get(offset..end)?.to_vec();
+        offset = end;
+
+        arguments.push(ArgumentPreview {
+            name: input.name.clone(),
+            r#type: input.r#type.clone(),
+            value,
+        });
+    }
+
+    if offset == calldata.len() {
+        Some(arguments)
+    } else {
+        None
+    }
+}
+
+fn type_size(abi: &[AbiEntry], r#type: &str) -> Option<usize> {
+    if r#type == "felt" {
+        return Some(1);
+    }
+
+    abi.iter().find_map(|entry| match entry {
+        AbiEntry::Struct(value) if value.name == r#type => Some(value.size as usize),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_arguments() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "members": [
+                        { "name": "low", "offset": 0, "type": "felt" },
+                        { "name": "high", "offset": 1, "type": "felt" }
+                    ],
+                    "name": "Uint256",
+                    "size": 2,
+                    "type": "struct"
+                },
+                {
+                    "inputs": [
+                        { "name": "recipient", "type": "felt" },
+                        { "name": "amount", "type": "Uint256" },
+                        { "name": "data_len", "type": "felt" },
+                        { "name": "data", "type": "felt*" }
+                    ],
+                    "name": "transfer",
+                    "outputs": [],
+                    "type": "function"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let function = find_function(&abi, get_selector_from_name("transfer").unwrap()).unwrap();
+        let arguments = decode_arguments(
+            &abi,
+            function,
+            &[
+                FieldElement::from_hex_be("0x1234").unwrap(),
+                FieldElement::from(100u32),
+                FieldElement::ZERO,
+                FieldElement::TWO,
+                FieldElement::ONE,
+                FieldElement::THREE,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(arguments.len(), 4);
+        assert_eq!(arguments[1].name, "amount");
+        assert_eq!(
+            arguments[1].value,
+            vec![FieldElement::from(100u32), FieldElement::ZERO]
+        );
+        assert_eq!(
+            arguments[3].value,
+            vec![FieldElement::ONE, FieldElement::THREE]
+        );
+
+        // Mismatched calldata length
+        assert!(decode_arguments(&abi, function, &[FieldElement::ONE]).is_none());
+
+        // Array lengths overflowing the calldata size
+        for data_len in [u64::MAX, u64::MAX - 3] {
+            assert!(decode_arguments(
+                &abi,
+                function,
+                &[
+                    FieldElement::from_hex_be("0x1234").unwrap(),
+                    FieldElement::from(100u32),
+                    FieldElement::ZERO,
+                    FieldElement::from(data_len),
+                    FieldElement::ONE,
+                ],
+            )
+            .is_none());
+        }
+    }
+}
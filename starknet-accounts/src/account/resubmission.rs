@@ -0,0 +1,223 @@
+use super::{AccountError, ConnectedAccount, Execution, PreparedExecution, RawExecution};
+use crate::Call;
+
+use starknet_core::types::{FieldElement, TransactionStatus};
+use starknet_providers::Provider;
+
+/// Controls when a sent transaction is considered stuck and how it's resubmitted.
+///
+/// The deadline is expressed as a number of [polls](ResubmittingExecution::poll) instead of a
+/// duration, leaving the polling interval (and the async runtime used for sleeping) to the caller.
+#[derive(Debug, Clone, Copy)]
+pub struct ResubmissionPolicy {
+    /// Number of polls without the transaction being accepted before it's resubmitted.
+    pub polls_before_resubmission: u32,
+    /// Multiplier applied to `max_fee` on each resubmission.
+    pub fee_bump_multiplier: f64,
+    /// Maximum number of submissions, including the initial one.
+    pub max_attempts: usize,
+}
+
+/// A transaction sent with [Execution::send_with_resubmission], tracked across resubmissions.
+/// All attempts share the same nonce, so at most one of them can land.
+#[derive(Debug)]
+pub struct ResubmittingExecution<'a, A> {
+    account: &'a A,
+    calls: Vec<Call>,
+    nonce: FieldElement,
+    max_fee: FieldElement,
+    policy: ResubmissionPolicy,
+    attempts: Vec<FieldElement>,
+    polls_since_submission: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResubmissionStatus {
+    /// None of the attempts has been accepted yet.
+    Waiting,
+    /// The transaction was stuck and has been resubmitted with a higher `max_fee`.
+    Resubmitted {
+        attempt: usize,
+        transaction_hash: FieldElement,
+    },
+    /// An attempt has been accepted.
+    Landed {
+        attempt: usize,
+        transaction_hash: FieldElement,
+    },
+    /// The latest attempt was rejected by the sequencer.
+    Rejected {
+        attempt: usize,
+        transaction_hash: FieldElement,
+    },
+    /// The transaction is still not accepted after the maximum number of attempts.
+    Stuck,
+}
+
+impl Default for ResubmissionPolicy {
+    fn default() -> Self {
+        Self {
+            polls_before_resubmission: 10,
+            fee_bump_multiplier: 1.5,
+            max_attempts: 3,
+        }
+    }
+}
+
+impl<'a, A> Execution<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Sends the transaction and returns a handle for detecting whether it gets stuck, in which
+    /// case it's resubmitted with the same nonce and a higher `max_fee`.
+    pub async fn send_with_resubmission(
+        &self,
+        policy: ResubmissionPolicy,
+    ) -> Result<
+        ResubmittingExecution<'a, A>,
+        AccountError<A::SignError, <A::Provider as Provider>::Error>,
+    > {
+        let prepared = self.prepare().await?;
+        let result = prepared.send().await?;
+
+        Ok(ResubmittingExecution {
+            account: self.account,
+            calls: self.calls.clone(),
+            nonce: prepared.inner.nonce,
+            max_fee: prepared.inner.max_fee,
+            policy,
+            attempts: vec![result.transaction_hash],
+            polls_since_submission: 0,
+        })
+    }
+}
+
+impl<'a, A> ResubmittingExecution<'a, A> {
+    /// Hashes of all submitted attempts, in submission order.
+    pub fn attempts(&self) -> &[FieldElement] {
+        &self.attempts
+    }
+
+    /// `max_fee` of the latest attempt.
+    pub fn max_fee(&self) -> FieldElement {
+        self.max_fee
+    }
+}
+
+impl<'a, A> ResubmittingExecution<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Checks the status of all attempts, resubmitting the transaction if the latest attempt has
+    /// been pending for longer than the policy allows. Callers are expected to call this
+    /// periodically until a final status is returned.
+    pub async fn poll(
+        &mut self,
+    ) -> Result<ResubmissionStatus, AccountError<A::SignError, <A::Provider as Provider>::Error>>
+    {
+        let latest_attempt = self.attempts.len() - 1;
+
+        for (attempt, transaction_hash) in self.attempts.iter().enumerate() {
+            let status = self
+                .account
+                .provider()
+                .get_transaction_status(*transaction_hash)
+                .await
+                .map_err(AccountError::Provider)?
+                .status;
+
+            match status {
+                TransactionStatus::Pending
+                | TransactionStatus::AcceptedOnL2
+                | TransactionStatus::AcceptedOnL1 => {
+                    return Ok(ResubmissionStatus::Landed {
+                        attempt,
+                        transaction_hash: *transaction_hash,
+                    })
+                }
+                TransactionStatus::Rejected if attempt == latest_attempt => {
+                    return Ok(ResubmissionStatus::Rejected {
+                        attempt,
+                        transaction_hash: *transaction_hash,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        self.polls_since_submission += 1;
+        if self.polls_since_submission < self.policy.polls_before_resubmission {
+            return Ok(ResubmissionStatus::Waiting);
+        }
+        if self.attempts.len() >= self.policy.max_attempts {
+            return Ok(ResubmissionStatus::Stuck);
+        }
+
+        self.resubmit().await
+    }
+
+    async fn resubmit(
+        &mut self,
+    ) -> Result<ResubmissionStatus, AccountError<A::SignError, <A::Provider as Provider>::Error>>
+    {
+        // Re-estimates as the network conditions might have changed since the last attempt
+        let fee_estimate = Execution::new(self.calls.clone(), self.account)
+            .nonce(self.nonce)
+            .estimate_fee()
+            .await?;
+        let max_fee = bump_max_fee(
+            self.max_fee,
+            fee_estimate.overall_fee,
+            self.policy.fee_bump_multiplier,
+        );
+
+        let prepared = PreparedExecution {
+            account: self.account,
+            inner: RawExecution {
+                calls: self.calls.clone(),
+                nonce: self.nonce,
+                max_fee,
+            },
+        };
+        let result = prepared.send().await?;
+
+        self.max_fee = max_fee;
+        self.attempts.push(result.transaction_hash);
+        self.polls_since_submission = 0;
+
+        Ok(ResubmissionStatus::Resubmitted {
+            attempt: self.attempts.len() - 1,
+            transaction_hash: result.transaction_hash,
+        })
+    }
+}
+
+/// Bumps the previous `max_fee`, or the fresh estimate if it has grown beyond that.
+pub(crate) fn bump_max_fee(
+    previous_max_fee: FieldElement,
+    estimated_fee: u64,
+    multiplier: f64,
+) -> FieldElement {
+    let previous_max_fee: u64 = previous_max_fee.try_into().unwrap_or(u64::MAX);
+    let base = previous_max_fee.max(estimated_fee);
+
+    ((base as f64 * multiplier) as u64).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_bump_max_fee() {
+        assert_eq!(
+            bump_max_fee(FieldElement::from(1000u64), 800, 1.5),
+            FieldElement::from(1500u64)
+        );
+        assert_eq!(
+            bump_max_fee(FieldElement::from(1000u64), 2000, 1.5),
+            FieldElement::from(3000u64)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use super::AccountError;
+
+use starknet_core::{types::FieldElement, utils::parse_cairo_short_string};
+use starknet_providers::ProviderError;
+
+/// Substrings identifying provider errors caused by a contract reverting, rather than by the
+/// transaction being malformed or the provider failing.
+const REVERT_MARKERS: [&str; 6] = [
+    "Error in the called contract",
+    "Error at pc=",
+    "Error message:",
+    "Execution was reverted",
+    "Failure reason:",
+    "not found in contract",
+];
+
+/// A contract revert, decoded from the error message returned by the provider.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("execution reverted: {}", self.reason.as_deref().unwrap_or(&self.message))]
+pub struct ContractRevert {
+    /// Innermost contract in the reported call stack. This might be a contract called by the
+    /// target of the failing call.
+    pub contract_address: Option<FieldElement>,
+    /// Selector of the failing entry point, if reported.
+    pub selector: Option<FieldElement>,
+    /// Decoded panic reason, taken from the `Error message:` line of `with_attr` errors, or from
+    /// the panic data where it consists of printable short strings.
+    pub reason: Option<String>,
+    /// Raw panic data, if any.
+    pub data: Vec<FieldElement>,
+    /// The original error message.
+    pub message: String,
+}
+
+impl ContractRevert {
+    /// Decodes a provider error message, returning `None` if it's not a contract revert.
+    pub fn parse(message: &str) -> Option<Self> {
+        if !REVERT_MARKERS.iter().any(|marker| message.contains(marker)) {
+            return None;
+        }
+
+        let (contract_address, selector) = match innermost_called_contract(message) {
+            Some(frame) => (
+                first_hex_after(frame, "").and_then(|hex| FieldElement::from_hex_be(hex).ok()),
+                first_hex_after(frame, "selector: ")
+                    .and_then(|hex| FieldElement::from_hex_be(hex).ok()),
+            ),
+            None => (None, None),
+        };
+        let selector = selector.or_else(|| {
+            first_hex_after(message, "Entry point ")
+                .and_then(|hex| FieldElement::from_hex_be(hex).ok())
+        });
+
+        let data = panic_data(message);
+        let reason = message
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix("Error message: "))
+            .map(|reason| reason.trim().to_owned())
+            .or_else(|| decode_reason(&data));
+
+        Some(Self {
+            contract_address,
+            selector,
+            reason,
+            data,
+            message: message.to_owned(),
+        })
+    }
+
+    /// Whether the revert is caused by calling an entry point the contract doesn't have.
+    pub fn is_entry_point_not_found(&self) -> bool {
+        self.message.contains("not found in contract")
+            || self.reason.as_deref() == Some("ENTRYPOINT_NOT_FOUND")
+    }
+}
+
+/// Maps a provider error to [AccountError::Reverted] if it's caused by a contract revert, and to
+/// [AccountError::Provider] otherwise.
+pub(super) fn map_provider_error<S, P>(err: ProviderError<P>) -> AccountError<S, P>
+where
+    P: std::error::Error,
+{
+    match ContractRevert::parse(&err.to_string()) {
+        Some(revert) => AccountError::Reverted(revert),
+        None => AccountError::Provider(err),
+    }
+}
+
+/// Returns the content of the innermost `Error in the called contract (...)` frame, which is
+/// either a plain address or a list like `contract address: 0x..., class hash: 0x..., selector:
+/// 0x...`.
+fn innermost_called_contract(message: &str) -> Option<&str> {
+    const PATTERN: &str = "Error in the called contract (";
+
+    let start = message.rfind(PATTERN)? + PATTERN.len();
+    let end = start + message[start..].find(')')?;
+
+    Some(&message[start..end])
+}
+
+/// Finds the first hex number following `prefix`.
+fn first_hex_after<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = text.find(prefix)? + prefix.len();
+    let start = start + text[start..].find("0x")?;
+    let len = text[start + 2..]
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(text.len() - start - 2);
+
+    if len == 0 {
+        None
+    } else {
+        Some(&text[start..start + 2 + len])
+    }
+}
+
+/// Extracts the felts following the `Failure reason:` of Cairo 1 panics, formatted either as a
+/// single value or as a list.
+fn panic_data(message: &str) -> Vec<FieldElement> {
+    const PATTERN: &str = "Failure reason:";
+
+    let start = match message.rfind(PATTERN) {
+        Some(index) => index + PATTERN.len(),
+        None => return vec![],
+    };
+    let line = message[start..].lines().next().unwrap_or_default();
+
+    // Short string annotations like `('Insufficient balance')` are skipped by only taking the
+    // `0x`-prefixed tokens
+    line.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter_map(|token| token.strip_prefix("0x"))
+        .filter_map(|hex| FieldElement::from_hex_be(hex).ok())
+        .collect()
+}
+
+fn decode_reason(data: &[FieldElement]) -> Option<String> {
+    let decoded = data
+        .iter()
+        .map(|felt| match parse_cairo_short_string(felt) {
+            Ok(decoded)
+                if !decoded.is_empty()
+                    && decoded.chars().all(|c| c.is_ascii_graphic() || c == ' ') =>
+            {
+                Some(decoded)
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if decoded.is_empty() {
+        None
+    } else {
+        Some(decoded.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_cairo_0_revert() {
+        let message =
+            "Error at pc=0:1:\nError in the called contract (0x1234):\nError at pc=0:2:\n\
+            Error in the called contract (0x5678):\nError message: insufficient balance\n\
+            Error at pc=0:3:";
+
+        let revert = ContractRevert::parse(message).unwrap();
+        assert_eq!(
+            revert.contract_address,
+            Some(FieldElement::from_hex_be("0x5678").unwrap())
+        );
+        assert_eq!(revert.selector, None);
+        assert_eq!(revert.reason.as_deref(), Some("insufficient balance"));
+        assert!(revert.data.is_empty());
+        assert_eq!(revert.message, message);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_cairo_1_revert() {
+        let message = "Error in the called contract (contract address: 0xabc, class hash: \
+            0xdef, selector: 0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e):\n\
+            Execution failed. Failure reason: 0x753235365f737562204f766572666c6f77 \
+            ('u256_sub Overflow').";
+
+        let revert = ContractRevert::parse(message).unwrap();
+        assert_eq!(
+            revert.contract_address,
+            Some(FieldElement::from_hex_be("0xabc").unwrap())
+        );
+        assert_eq!(
+            revert.selector,
+            Some(
+                FieldElement::from_hex_be(
+                    "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(
+            revert.data,
+            vec![FieldElement::from_hex_be("0x753235365f737562204f766572666c6f77").unwrap()]
+        );
+        assert_eq!(revert.reason.as_deref(), Some("u256_sub Overflow"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_entry_point_not_found() {
+        let message = "Error at pc=0:10:\nError in the called contract (0x1234):\n\
+            Entry point 0x5678 not found in contract with class hash 0x9abc.";
+
+        let revert = ContractRevert::parse(message).unwrap();
+        assert_eq!(
+            revert.selector,
+            Some(FieldElement::from_hex_be("0x5678").unwrap())
+        );
+        assert_eq!(revert.reason, None);
+        assert!(revert.is_entry_point_not_found());
+
+        // As returned for direct calls, without a stack trace
+        let revert = ContractRevert::parse(
+            "Entry point 0x5678 not found in contract with class hash 0x9abc. \
+            (EntryPointNotFoundInContract)",
+        )
+        .unwrap();
+        assert!(revert.is_entry_point_not_found());
+
+        let revert = ContractRevert::parse(
+            "Execution failed. Failure reason: 0x454e545259504f494e545f4e4f545f464f554e44 \
+            ('ENTRYPOINT_NOT_FOUND').",
+        )
+        .unwrap();
+        assert!(revert.is_entry_point_not_found());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_non_revert() {
+        assert_eq!(ContractRevert::parse("Invalid transaction nonce"), None);
+    }
+}
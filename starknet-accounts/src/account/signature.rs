@@ -0,0 +1,223 @@
+use super::{ConnectedAccount, ContractRevert};
+
+use starknet_core::types::{CallFunction, FieldElement, StarknetError};
+use starknet_providers::{Provider, ProviderError};
+
+/// Selector for "is_valid_signature"
+const SELECTOR_IS_VALID_SIGNATURE: FieldElement = FieldElement::from_mont([
+    12122642798644213989,
+    6282523370647325,
+    11612572549337501840,
+    251265868754590281,
+]);
+
+/// Selector for "isValidSignature"
+const SELECTOR_IS_VALID_SIGNATURE_CAMEL: FieldElement = FieldElement::from_mont([
+    14354634675100728833,
+    11450622612510350613,
+    12444972924358274138,
+    6877239785405797,
+]);
+
+/// Cairo string for "VALID"
+const VALID: FieldElement = FieldElement::from_mont([
+    18446732218902960001,
+    18446744073709551615,
+    18446744073709551615,
+    576259220591366032,
+]);
+
+pub(super) async fn verify_signature<A>(
+    account: &A,
+    message_hash: FieldElement,
+    signature: &[FieldElement],
+) -> Result<bool, ProviderError<<A::Provider as Provider>::Error>>
+where
+    A: ConnectedAccount + Sync,
+{
+    let mut calldata = vec![message_hash, signature.len().into()];
+    calldata.extend_from_slice(signature);
+
+    let snake_case_result = account
+        .provider()
+        .call_contract(
+            CallFunction {
+                contract_address: account.address(),
+                entry_point_selector: SELECTOR_IS_VALID_SIGNATURE,
+                calldata: calldata.clone(),
+            },
+            account.block_id(),
+        )
+        .await;
+
+    let result = match snake_case_result {
+        Ok(result) => result,
+        // Older accounts only expose the camelCase entrypoint
+        Err(err) if is_entry_point_not_found(&err) => {
+            match account
+                .provider()
+                .call_contract(
+                    CallFunction {
+                        contract_address: account.address(),
+                        entry_point_selector: SELECTOR_IS_VALID_SIGNATURE_CAMEL,
+                        calldata,
+                    },
+                    account.block_id(),
+                )
+                .await
+            {
+                Ok(result) => result,
+                Err(err) => return revert_as_invalid(err),
+            }
+        }
+        Err(err) => return revert_as_invalid(err),
+    };
+
+    Ok(is_valid_result(&result.result))
+}
+
+fn is_entry_point_not_found<E>(err: &ProviderError<E>) -> bool
+where
+    E: std::error::Error,
+{
+    match err {
+        ProviderError::StarknetError(err) => *err == StarknetError::InvalidMessageSelector,
+        _ => ContractRevert::parse(&err.to_string())
+            .is_some_and(|revert| revert.is_entry_point_not_found()),
+    }
+}
+
+/// Accounts asserting on the signature, instead of returning, revert on invalid ones. Other
+/// errors, including a missing entrypoint, are passed on.
+fn revert_as_invalid<E>(err: ProviderError<E>) -> Result<bool, ProviderError<E>>
+where
+    E: std::error::Error,
+{
+    let reverted = match &err {
+        ProviderError::StarknetError(err) => *err == StarknetError::ContractError,
+        _ => ContractRevert::parse(&err.to_string()).is_some(),
+    };
+
+    if reverted && !is_entry_point_not_found(&err) {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+/// Accounts return either the Cairo string "VALID" or a boolean `1` for valid signatures.
+fn is_valid_result(result: &[FieldElement]) -> bool {
+    matches!(result.first(), Some(value) if *value == VALID || *value == FieldElement::ONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SingleOwnerAccount;
+
+    use starknet_core::chain_id;
+    use starknet_providers::{ChainFixture, MockProvider};
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    const ACCOUNT: FieldElement = FieldElement::TWO;
+
+    const NOT_FOUND: &str = "Entry point 0x1 not found in contract with class hash 0x1.";
+
+    fn test_account(fixture: ChainFixture) -> SingleOwnerAccount<MockProvider, LocalWallet> {
+        SingleOwnerAccount::new(
+            fixture.deploy(ACCOUNT, FieldElement::ONE).build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            ACCOUNT,
+            chain_id::TESTNET,
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_is_valid_result() {
+        assert!(is_valid_result(&[
+            starknet_core::utils::cairo_short_string_to_felt("VALID").unwrap()
+        ]));
+        assert!(is_valid_result(&[FieldElement::ONE]));
+        assert!(!is_valid_result(&[FieldElement::ZERO]));
+        assert!(!is_valid_result(&[]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_selectors() {
+        assert_eq!(
+            SELECTOR_IS_VALID_SIGNATURE,
+            starknet_core::utils::get_selector_from_name("is_valid_signature").unwrap()
+        );
+        assert_eq!(
+            SELECTOR_IS_VALID_SIGNATURE_CAMEL,
+            starknet_core::utils::get_selector_from_name("isValidSignature").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_camel_case_fallback() {
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(ACCOUNT, SELECTOR_IS_VALID_SIGNATURE, NOT_FOUND)
+                .call_result(
+                    ACCOUNT,
+                    SELECTOR_IS_VALID_SIGNATURE_CAMEL,
+                    vec![FieldElement::ONE],
+                ),
+        );
+        assert!(account
+            .verify_signature(FieldElement::ONE, &[FieldElement::ONE])
+            .await
+            .unwrap());
+
+        // Accounts without either entrypoint can't validate signatures at all
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(ACCOUNT, SELECTOR_IS_VALID_SIGNATURE, NOT_FOUND)
+                .call_revert(ACCOUNT, SELECTOR_IS_VALID_SIGNATURE_CAMEL, NOT_FOUND),
+        );
+        assert!(account
+            .verify_signature(FieldElement::ONE, &[FieldElement::ONE])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_revert() {
+        // The camelCase entrypoint is only tried when the snake_case one is missing, so it
+        // returning `VALID` would make this test fail
+        let account = test_account(
+            ChainFixture::new()
+                .call_revert(
+                    ACCOUNT,
+                    SELECTOR_IS_VALID_SIGNATURE,
+                    "Error at pc=0:1:\nError in the called contract (0x2):\n\
+                    Error message: invalid signature",
+                )
+                .call_result(ACCOUNT, SELECTOR_IS_VALID_SIGNATURE_CAMEL, vec![VALID]),
+        );
+        assert!(!account
+            .verify_signature(FieldElement::ONE, &[FieldElement::ONE])
+            .await
+            .unwrap());
+
+        // Other errors aren't taken for invalid signatures
+        let account = SingleOwnerAccount::new(
+            ChainFixture::new().build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            ACCOUNT,
+            chain_id::TESTNET,
+        );
+        assert!(matches!(
+            account
+                .verify_signature(FieldElement::ONE, &[FieldElement::ONE])
+                .await,
+            Err(ProviderError::StarknetError(
+                StarknetError::ContractNotFound
+            ))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use super::{Account, ConnectedAccount, Execution};
+use crate::Call;
+
+use starknet_core::types::{CallFunction, FieldElement};
+use starknet_providers::{Provider, ProviderError};
+
+/// Selector for "upgrade"
+const SELECTOR_UPGRADE: FieldElement = FieldElement::from_mont([
+    15459639288944153756,
+    7372596265144009856,
+    6997576704933554724,
+    65148535841277313,
+]);
+
+/// Selector for "get_implementation"
+const SELECTOR_GET_IMPLEMENTATION: FieldElement = FieldElement::from_mont([
+    2598193208928338172,
+    13646626679660679359,
+    1923811282448450916,
+    154991016872417555,
+]);
+
+/// Selector for "get_implementation_hash"
+const SELECTOR_GET_IMPLEMENTATION_HASH: FieldElement = FieldElement::from_mont([
+    17233785268311264684,
+    16469083800356640100,
+    16908937661856766698,
+    552324211118325588,
+]);
+
+/// Account contract implementations with known `upgrade` entrypoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountImplementation {
+    /// OpenZeppelin accounts: `upgrade(new_implementation)`.
+    OpenZeppelin,
+    /// Argent X accounts: `upgrade(implementation, calldata_len, calldata)`, with empty calldata.
+    Argent,
+    /// Braavos accounts: `upgrade(new_implementation)`.
+    Braavos,
+}
+
+impl AccountImplementation {
+    /// Builds the call to the account's own `upgrade` entrypoint.
+    pub fn upgrade_call(
+        &self,
+        account_address: FieldElement,
+        new_class_hash: FieldElement,
+    ) -> Call {
+        let calldata = match self {
+            Self::OpenZeppelin | Self::Braavos => vec![new_class_hash],
+            Self::Argent => vec![new_class_hash, FieldElement::ZERO],
+        };
+
+        Call {
+            to: account_address,
+            selector: SELECTOR_UPGRADE,
+            calldata,
+        }
+    }
+
+    /// Selector of the view function returning the implementation class hash of proxy-based
+    /// deployments.
+    fn implementation_getter(&self) -> FieldElement {
+        match self {
+            Self::OpenZeppelin => SELECTOR_GET_IMPLEMENTATION_HASH,
+            Self::Argent | Self::Braavos => SELECTOR_GET_IMPLEMENTATION,
+        }
+    }
+}
+
+impl<'a, A> Execution<'a, A>
+where
+    A: Account,
+{
+    /// Creates an execution that upgrades the account to `new_class_hash` using the `upgrade`
+    /// entrypoint of `implementation`.
+    pub fn upgrade(
+        account: &'a A,
+        implementation: AccountImplementation,
+        new_class_hash: FieldElement,
+    ) -> Self {
+        Self::new(
+            vec![implementation.upgrade_call(account.address(), new_class_hash)],
+            account,
+        )
+    }
+}
+
+/// Checks whether `account` now runs `expected_class_hash`, either as its own
+/// class or, for proxy-based deployments, as the implementation behind the proxy.
+pub(super) async fn verify_upgrade<A>(
+    account: &A,
+    implementation: AccountImplementation,
+    expected_class_hash: FieldElement,
+) -> Result<bool, ProviderError<<A::Provider as Provider>::Error>>
+where
+    A: ConnectedAccount + Sync,
+{
+    let class_hash = account
+        .provider()
+        .get_class_hash_at(account.address(), account.block_id())
+        .await?;
+    if class_hash == expected_class_hash {
+        return Ok(true);
+    }
+
+    let call_result = account
+        .provider()
+        .call_contract(
+            CallFunction {
+                contract_address: account.address(),
+                entry_point_selector: implementation.implementation_getter(),
+                calldata: vec![],
+            },
+            account.block_id(),
+        )
+        .await;
+
+    match call_result {
+        Ok(result) => Ok(result.result.first() == Some(&expected_class_hash)),
+        // Not a proxy: the class hash read above is authoritative
+        Err(ProviderError::StarknetError(_)) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_upgrade_call() {
+        let address = FieldElement::from_hex_be("0x1234").unwrap();
+        let class_hash = FieldElement::from_hex_be("0x5678").unwrap();
+
+        let oz_call = AccountImplementation::OpenZeppelin.upgrade_call(address, class_hash);
+        assert_eq!(oz_call.to, address);
+        assert_eq!(
+            oz_call.selector,
+            starknet_core::utils::get_selector_from_name("upgrade").unwrap()
+        );
+        assert_eq!(oz_call.calldata, vec![class_hash]);
+
+        let argent_call = AccountImplementation::Argent.upgrade_call(address, class_hash);
+        assert_eq!(argent_call.calldata, vec![class_hash, FieldElement::ZERO]);
+
+        let braavos_call = AccountImplementation::Braavos.upgrade_call(address, class_hash);
+        assert_eq!(braavos_call.calldata, vec![class_hash]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_implementation_getter_selectors() {
+        assert_eq!(
+            AccountImplementation::OpenZeppelin.implementation_getter(),
+            starknet_core::utils::get_selector_from_name("get_implementation_hash").unwrap()
+        );
+        assert_eq!(
+            AccountImplementation::Argent.implementation_getter(),
+            starknet_core::utils::get_selector_from_name("get_implementation").unwrap()
+        );
+    }
+}
@@ -1,8 +1,14 @@
-use starknet_core::types::FieldElement;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use starknet_core::{serde::unsigned_field_element::UfeHex, types::FieldElement};
 
-#[derive(Debug, Clone)]
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Call {
+    #[serde_as(as = "UfeHex")]
     pub to: FieldElement,
+    #[serde_as(as = "UfeHex")]
     pub selector: FieldElement,
+    #[serde_as(as = "Vec<UfeHex>")]
     pub calldata: Vec<FieldElement>,
 }
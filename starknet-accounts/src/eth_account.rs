@@ -0,0 +1,199 @@
+use crate::{Account, ConnectedAccount, ExecutionEncoding, RawDeclaration, RawExecution};
+
+use async_trait::async_trait;
+use k256::ecdsa::SigningKey;
+use starknet_core::types::{contract_artifact::ComputeClassHashError, FieldElement};
+use starknet_providers::Provider;
+use starknet_signers::{secp256k1::eth_address, Secp256k1Signer};
+
+/// An account controlled by an Ethereum (secp256k1) key. Transaction hashes are signed as
+/// [EIP-191](https://eips.ethereum.org/EIPS/eip-191) personal messages, the same way wallets like
+/// MetaMask sign arbitrary 32-byte messages.
+///
+/// Signatures are encoded as `[r.low, r.high, s.low, s.high, y_parity]`, the layout expected by
+/// Eth-flavored account classes taking a `Secp256k1Signature` argument.
+///
+/// Any [Secp256k1Signer] can be used, such as a local [SigningKey] or a Ledger device.
+#[derive(Debug, Clone)]
+pub struct EthAccount<P, S = SigningKey>
+where
+    P: Provider + Send,
+    S: Secp256k1Signer + Send,
+{
+    provider: P,
+    signer: S,
+    address: FieldElement,
+    chain_id: FieldElement,
+    encoding: ExecutionEncoding,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignError<S> {
+    #[error(transparent)]
+    Signer(S),
+    #[error(transparent)]
+    ClassHash(ComputeClassHashError),
+}
+
+impl<P, S> EthAccount<P, S>
+where
+    P: Provider + Sync + Send,
+    S: Secp256k1Signer + Sync + Send,
+{
+    pub fn new(provider: P, signer: S, address: FieldElement, chain_id: FieldElement) -> Self {
+        Self {
+            provider,
+            signer,
+            address,
+            chain_id,
+            // Eth-flavored accounts are all Cairo 1 contracts
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    /// Sets the `__execute__` calldata encoding expected by the account contract. Defaults to
+    /// [ExecutionEncoding::New].
+    pub fn set_execution_encoding(&mut self, encoding: ExecutionEncoding) -> &mut Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// The 20-byte Ethereum address of the signer.
+    pub async fn get_eth_address(&self) -> Result<[u8; 20], S::GetPublicKeyError> {
+        Ok(eth_address(&self.signer.get_public_key().await?))
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Vec<FieldElement>, S::SignError> {
+        Ok(self.signer.sign_hash(hash).await?.to_felts())
+    }
+}
+
+impl<P> EthAccount<P, SigningKey>
+where
+    P: Provider + Sync + Send,
+{
+    /// The 20-byte Ethereum address of the signing key.
+    pub fn eth_address(&self) -> [u8; 20] {
+        eth_address(self.signer.verifying_key())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P, S> Account for EthAccount<P, S>
+where
+    P: Provider + Sync + Send,
+    S: Secp256k1Signer + Sync + Send,
+{
+    type SignError = SignError<S::SignError>;
+
+    fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    fn chain_id(&self) -> FieldElement {
+        self.chain_id
+    }
+
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.encoding
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+
+    async fn sign_execution(
+        &self,
+        execution: &RawExecution,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, self.encoding);
+        self.sign_hash(&tx_hash).await.map_err(SignError::Signer)
+    }
+
+    async fn sign_declaration(
+        &self,
+        declaration: &RawDeclaration,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let tx_hash = declaration
+            .transaction_hash(self.chain_id, self.address)
+            .map_err(SignError::ClassHash)?;
+        self.sign_hash(&tx_hash).await.map_err(SignError::Signer)
+    }
+}
+
+impl<P, S> ConnectedAccount for EthAccount<P, S>
+where
+    P: Provider + Sync + Send,
+    S: Secp256k1Signer + Sync + Send,
+{
+    type Provider = P;
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+    use starknet_core::chain_id;
+    use starknet_providers::SequencerGatewayProvider;
+    use starknet_signers::secp256k1::eip191_hash;
+
+    fn test_account() -> EthAccount<SequencerGatewayProvider> {
+        let signing_key = SigningKey::from_slice(&[
+            0x4c, 0x08, 0x83, 0xa6, 0x91, 0x02, 0x93, 0x7d, 0x62, 0x31, 0x47, 0x1b, 0x5d, 0xbb,
+            0x62, 0x04, 0xfe, 0x51, 0x29, 0x61, 0x70, 0x82, 0x79, 0x2a, 0xe4, 0x68, 0xd0, 0x1a,
+            0x3f, 0x36, 0x23, 0x18,
+        ])
+        .unwrap();
+
+        EthAccount::new(
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+            signing_key,
+            FieldElement::from_hex_be("0x1234").unwrap(),
+            chain_id::TESTNET,
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_eth_address() {
+        // Well-known address of this test key
+        assert_eq!(
+            hex_encode(&test_account().eth_address()),
+            "2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_recovers_to_signer() {
+        let account = test_account();
+        let hash = FieldElement::from_hex_be("0x1234abcd").unwrap();
+
+        let signature = account.sign_hash(&hash).await.unwrap();
+        assert_eq!(signature.len(), 5);
+
+        let mut signature_bytes = [0u8; 64];
+        signature_bytes[..16].copy_from_slice(&signature[1].to_bytes_be()[16..]);
+        signature_bytes[16..32].copy_from_slice(&signature[0].to_bytes_be()[16..]);
+        signature_bytes[32..48].copy_from_slice(&signature[3].to_bytes_be()[16..]);
+        signature_bytes[48..].copy_from_slice(&signature[2].to_bytes_be()[16..]);
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            &eip191_hash(&hash),
+            &Signature::from_slice(&signature_bytes).unwrap(),
+            RecoveryId::new(signature[4] == FieldElement::ONE, false),
+        )
+        .unwrap();
+
+        assert_eq!(&recovered, account.signer.verifying_key());
+    }
+
+    fn hex_encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
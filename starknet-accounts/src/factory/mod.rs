@@ -1,15 +1,20 @@
 use super::NotPreparedError;
+use crate::{
+    fee_strategy::{resolve_max_fee, PercentileOfRecentBlocks},
+    FeeStrategy,
+};
 
 use async_trait::async_trait;
 use starknet_core::{
     crypto::compute_hash_on_elements,
     types::{
-        AccountTransaction, AddTransactionResult, BlockId, DeployAccountTransactionRequest,
-        FeeEstimate, FieldElement, StarknetError, TransactionRequest,
+        AccountTransaction, AddTransactionResult, AsStarknetError, BlockId,
+        DeployAccountTransactionRequest, FeeEstimate, FieldElement, StarknetError,
+        TransactionRequest,
     },
 };
 use starknet_providers::{Provider, ProviderError};
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
 pub mod argent;
 pub mod open_zeppelin;
@@ -78,7 +83,7 @@ pub struct AccountDeployment<'f, F> {
     /// after failed transactions can be included in blocks.
     nonce: Option<FieldElement>,
     max_fee: Option<FieldElement>,
-    fee_estimate_multiplier: f64,
+    fee_strategy: Arc<dyn FeeStrategy>,
 }
 
 /// [AccountDeployment] but with `nonce` and `max_fee` already determined.
@@ -104,6 +109,15 @@ pub enum AccountFactoryError<S, P> {
     Provider(ProviderError<P>),
 }
 
+impl<S, P> AsStarknetError for AccountFactoryError<S, P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Signing(_) => None,
+            Self::Provider(err) => err.starknet_error(),
+        }
+    }
+}
+
 impl<'f, F> AccountDeployment<'f, F> {
     pub fn new(salt: FieldElement, factory: &'f F) -> Self {
         Self {
@@ -111,7 +125,7 @@ impl<'f, F> AccountDeployment<'f, F> {
             salt,
             nonce: None,
             max_fee: None,
-            fee_estimate_multiplier: 1.1,
+            fee_strategy: Arc::new(PercentileOfRecentBlocks::default()),
         }
     }
 
@@ -129,6 +143,18 @@ impl<'f, F> AccountDeployment<'f, F> {
         }
     }
 
+    /// Sets the strategy used for deciding `max_fee` when it's not manually specified. Defaults
+    /// to the default [PercentileOfRecentBlocks].
+    pub fn fee_strategy<S>(self, fee_strategy: S) -> Self
+    where
+        S: FeeStrategy + 'static,
+    {
+        Self {
+            fee_strategy: Arc::new(fee_strategy),
+            ..self
+        }
+    }
+
     /// Calling this function after manually specifying `nonce` and `max_fee` turns
     /// [AccountDeployment] into [PreparedAccountDeployment]. Returns `Err` if either field is
     /// `None`.
@@ -222,7 +248,13 @@ where
             Some(value) => value,
             None => {
                 let fee_estimate = self.estimate_fee_with_nonce(nonce).await?;
-                ((fee_estimate.overall_fee as f64 * self.fee_estimate_multiplier) as u64).into()
+                resolve_max_fee(
+                    self.factory.provider(),
+                    self.fee_strategy.as_ref(),
+                    &fee_estimate,
+                )
+                .await
+                .map_err(AccountFactoryError::Provider)?
             }
         };
 
@@ -249,19 +281,43 @@ where
                 max_fee: FieldElement::ZERO,
             },
         };
-        let deploy = prepared
-            .get_deploy_request()
-            .await
-            .map_err(AccountFactoryError::Signing)?;
+        let estimate = async {
+            let deploy = prepared
+                .get_deploy_request()
+                .await
+                .map_err(AccountFactoryError::Signing)?;
+
+            self.factory
+                .provider()
+                .estimate_fee(
+                    AccountTransaction::DeployAccount(deploy),
+                    self.factory.block_id(),
+                )
+                .await
+                .map_err(AccountFactoryError::Provider)
+        };
 
-        self.factory
-            .provider()
-            .estimate_fee(
-                AccountTransaction::DeployAccount(deploy),
-                self.factory.block_id(),
-            )
-            .await
-            .map_err(AccountFactoryError::Provider)
+        instrument!(
+            estimate,
+            "estimate_fee",
+            account = %format_args!("{:#x}", prepared.address()),
+            nonce = %format_args!("{nonce:#x}")
+        )
+        .await
+    }
+}
+
+impl RawAccountDeployment {
+    pub fn salt(&self) -> FieldElement {
+        self.salt
+    }
+
+    pub fn nonce(&self) -> FieldElement {
+        self.nonce
+    }
+
+    pub fn max_fee(&self) -> FieldElement {
+        self.max_fee
     }
 }
 
@@ -272,6 +328,10 @@ impl<'f, F> PreparedAccountDeployment<'f, F> {
             inner: raw_deployment,
         }
     }
+
+    pub fn nonce(&self) -> FieldElement {
+        self.inner.nonce
+    }
 }
 
 impl<'f, F> PreparedAccountDeployment<'f, F>
@@ -309,18 +369,33 @@ where
         AddTransactionResult,
         AccountFactoryError<F::SignError, <F::Provider as Provider>::Error>,
     > {
-        let tx_request = self
-            .get_deploy_request()
-            .await
-            .map_err(AccountFactoryError::Signing)?;
-        self.factory
-            .provider()
-            .add_transaction(TransactionRequest::DeployAccount(tx_request))
-            .await
-            .map_err(AccountFactoryError::Provider)
+        let send = async {
+            let tx_request = self
+                .get_deploy_request()
+                .await
+                .map_err(AccountFactoryError::Signing)?;
+            self.factory
+                .provider()
+                .add_transaction(TransactionRequest::DeployAccount(tx_request))
+                .await
+                .map_err(AccountFactoryError::Provider)
+        };
+
+        instrument!(
+            send,
+            "send",
+            account = %format_args!("{:#x}", self.address()),
+            nonce = %format_args!("{:#x}", self.inner.nonce),
+            max_fee = %format_args!("{:#x}", self.inner.max_fee),
+            transaction_hash = %format_args!("{:#x}", self.transaction_hash())
+        )
+        .await
     }
 
-    async fn get_deploy_request(&self) -> Result<DeployAccountTransactionRequest, F::SignError> {
+    /// Signs the deployment and builds the transaction request without submitting it.
+    pub async fn get_deploy_request(
+        &self,
+    ) -> Result<DeployAccountTransactionRequest, F::SignError> {
         let signature = self.factory.sign_deployment(&self.inner).await?;
 
         Ok(DeployAccountTransactionRequest {
@@ -3,7 +3,7 @@ use crate::{AccountFactory, PreparedAccountDeployment, RawAccountDeployment};
 use async_trait::async_trait;
 use starknet_core::types::FieldElement;
 use starknet_providers::Provider;
-use starknet_signers::Signer;
+use starknet_signers::{DeployAccountContext, SignContext, SignRequest, Signer};
 
 pub struct OpenZeppelinAccountFactory<S, P> {
     class_hash: FieldElement,
@@ -64,9 +64,21 @@ where
         &self,
         deployment: &RawAccountDeployment,
     ) -> Result<Vec<FieldElement>, Self::SignError> {
-        let tx_hash =
-            PreparedAccountDeployment::from_raw(deployment.clone(), self).transaction_hash();
-        let signature = self.signer.sign_hash(&tx_hash).await?;
+        let prepared = PreparedAccountDeployment::from_raw(deployment.clone(), self);
+        let request = SignRequest {
+            hash: prepared.transaction_hash(),
+            context: SignContext::DeployAccount(DeployAccountContext {
+                contract_address: prepared.address(),
+                chain_id: self.chain_id,
+                class_hash: self.class_hash(),
+                contract_address_salt: deployment.salt(),
+                constructor_calldata: self.calldata(),
+                max_fee: deployment.max_fee(),
+                nonce: deployment.nonce(),
+                is_query: false,
+            }),
+        };
+        let signature = self.signer.sign_request(&request).await?;
 
         Ok(vec![signature.r, signature.s])
     }
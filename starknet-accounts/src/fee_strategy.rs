@@ -0,0 +1,229 @@
+use starknet_core::types::{BlockId, FeeEstimate, FieldElement};
+use starknet_providers::{Provider, ProviderError};
+use std::fmt::Debug;
+
+/// Strategy for deciding the `max_fee` of a transaction when it's not manually specified. The
+/// strategy is consulted with the fee estimate of the transaction, along with the gas prices of
+/// the most recent [recent_blocks](FeeStrategy::recent_blocks) blocks.
+///
+/// The default strategy is [PercentileOfRecentBlocks::default()].
+pub trait FeeStrategy: Debug + Send + Sync {
+    /// Number of recent blocks whose gas prices should be fetched from the provider before
+    /// calling [max_fee](FeeStrategy::max_fee). Defaults to `0`, in which case no block is read.
+    fn recent_blocks(&self) -> u64 {
+        0
+    }
+
+    /// Computes `max_fee` from the fee estimate. `recent_gas_prices` is ordered from the latest
+    /// block backwards.
+    fn max_fee(&self, estimate: &FeeEstimate, recent_gas_prices: &[u64]) -> FieldElement;
+}
+
+/// Scales the estimated fee by a constant multiplier, ignoring recent gas prices.
+#[derive(Debug, Clone, Copy)]
+pub struct EstimateMultiplier(pub f64);
+
+/// Adds a constant amount of wei on top of the estimated fee.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantTip(pub u64);
+
+/// Prices the estimated gas usage with a percentile of the gas prices of recent blocks, falling
+/// back to the estimated gas price if it's higher, then scales it by a safety margin to absorb
+/// gas price increases until the transaction is included.
+///
+/// Each transaction costs `blocks` extra block requests. Providers that can't serve blocks, such
+/// as the JSON-RPC one, get the estimated gas price scaled by the margin.
+///
+/// This is the default strategy, sampling the 75th percentile of the last 5 blocks with a margin
+/// of `1.1`.
+#[derive(Debug, Clone, Copy)]
+pub struct PercentileOfRecentBlocks {
+    /// Number of recent blocks to sample.
+    pub blocks: u64,
+    /// Percentile in the range `[0, 100]`.
+    pub percentile: f64,
+    /// Multiplier applied on top of the priced gas usage.
+    pub margin: f64,
+}
+
+impl Default for EstimateMultiplier {
+    fn default() -> Self {
+        Self(1.1)
+    }
+}
+
+impl Default for PercentileOfRecentBlocks {
+    fn default() -> Self {
+        Self {
+            blocks: 5,
+            percentile: 75.0,
+            margin: 1.1,
+        }
+    }
+}
+
+impl FeeStrategy for EstimateMultiplier {
+    fn max_fee(&self, estimate: &FeeEstimate, _recent_gas_prices: &[u64]) -> FieldElement {
+        ((estimate.overall_fee as f64 * self.0) as u64).into()
+    }
+}
+
+impl FeeStrategy for ConstantTip {
+    fn max_fee(&self, estimate: &FeeEstimate, _recent_gas_prices: &[u64]) -> FieldElement {
+        estimate.overall_fee.saturating_add(self.0).into()
+    }
+}
+
+impl FeeStrategy for PercentileOfRecentBlocks {
+    fn recent_blocks(&self) -> u64 {
+        self.blocks
+    }
+
+    fn max_fee(&self, estimate: &FeeEstimate, recent_gas_prices: &[u64]) -> FieldElement {
+        let mut gas_prices = recent_gas_prices.to_vec();
+        gas_prices.sort_unstable();
+
+        let percentile_price = percentile_of_sorted(&gas_prices, self.percentile).unwrap_or(0);
+
+        let gas_price = percentile_price.max(estimate.gas_price);
+        let fee = estimate.gas_usage.saturating_mul(gas_price);
+        ((fee as f64 * self.margin) as u64).into()
+    }
+}
+
+/// The nearest-rank `percentile`, in the range `[0, 100]`, of `values` sorted in ascending order.
+pub(crate) fn percentile_of_sorted(values: &[u64], percentile: f64) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (values.len() - 1) as f64).round() as usize;
+    Some(values[rank])
+}
+
+/// Resolves `max_fee` by consulting `strategy`, fetching recent block gas prices if requested.
+/// Recent gas prices are best-effort: sampling stops at the first block the provider fails to
+/// return, as the estimate alone is still enough to price the transaction.
+pub(crate) async fn resolve_max_fee<P>(
+    provider: &P,
+    strategy: &dyn FeeStrategy,
+    estimate: &FeeEstimate,
+) -> Result<FieldElement, ProviderError<P::Error>>
+where
+    P: Provider,
+{
+    let recent_blocks = strategy.recent_blocks();
+
+    let mut recent_gas_prices = vec![];
+    if recent_blocks > 0 {
+        if let Ok(latest_block) = provider.get_block(BlockId::Latest).await {
+            recent_gas_prices.extend(u64::try_from(latest_block.gas_price).ok());
+
+            if let Some(latest_block_number) = latest_block.block_number {
+                for offset in 1..recent_blocks.min(latest_block_number + 1) {
+                    match provider
+                        .get_block(BlockId::Number(latest_block_number - offset))
+                        .await
+                    {
+                        Ok(block) => recent_gas_prices.extend(u64::try_from(block.gas_price).ok()),
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(strategy.max_fee(estimate, &recent_gas_prices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FeeUnit;
+    use starknet_providers::ChainFixture;
+
+    fn test_estimate() -> FeeEstimate {
+        FeeEstimate {
+            overall_fee: 1000,
+            unit: FeeUnit::Wei,
+            gas_price: 10,
+            gas_usage: 100,
+        }
+    }
+
+    fn test_mock_estimate() -> FeeEstimate {
+        FeeEstimate {
+            overall_fee: 10_000_000_000_000,
+            unit: FeeUnit::Wei,
+            gas_price: 1_000_000_000,
+            gas_usage: 10_000,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_estimate_multiplier() {
+        assert_eq!(
+            EstimateMultiplier::default().max_fee(&test_estimate(), &[]),
+            FieldElement::from(1100u64)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_constant_tip() {
+        assert_eq!(
+            ConstantTip(234).max_fee(&test_estimate(), &[]),
+            FieldElement::from(1234u64)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_percentile_of_recent_blocks() {
+        let strategy = PercentileOfRecentBlocks {
+            blocks: 5,
+            percentile: 50.0,
+            margin: 1.5,
+        };
+
+        assert_eq!(
+            strategy.max_fee(&test_estimate(), &[30, 5, 20, 40, 8]),
+            FieldElement::from(3000u64)
+        );
+
+        // Estimated gas price is used when recent blocks are cheaper
+        assert_eq!(
+            strategy.max_fee(&test_estimate(), &[1, 2, 3]),
+            FieldElement::from(1500u64)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_max_fee() {
+        let strategy = PercentileOfRecentBlocks::default();
+
+        // The mock estimate prices 10_000 gas at 1 gwei
+        let provider = ChainFixture::new()
+            .block_number(10)
+            .gas_price(10, 3_000_000_000)
+            .gas_price(9, 2_000_000_000)
+            .build();
+        assert_eq!(
+            resolve_max_fee(&provider, &strategy, &test_mock_estimate())
+                .await
+                .unwrap(),
+            FieldElement::from(22_000_000_000_000u64)
+        );
+
+        // Only the blocks that exist are sampled
+        let provider = ChainFixture::new().gas_price(0, 2_000_000_000).build();
+        assert_eq!(
+            resolve_max_fee(&provider, &strategy, &test_mock_estimate())
+                .await
+                .unwrap(),
+            FieldElement::from(22_000_000_000_000u64)
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{fee_strategy::percentile_of_sorted, FeeStrategy};
+
+use starknet_core::types::{Block, BlockId, FeeEstimate, FieldElement};
+use starknet_providers::{Provider, ProviderError};
+use std::collections::VecDeque;
+
+const DEFAULT_WINDOW: usize = 20;
+
+const DEFAULT_AMOUNT_MULTIPLIER: f64 = 1.1;
+
+/// Samples the gas prices of recent blocks, recommending resource bounds from rolling
+/// percentiles of them.
+///
+/// Blocks only expose the L1 gas price in this version of Starknet, which is the only resource
+/// priced by the oracle.
+///
+/// ```ignore
+/// let mut oracle = GasOracle::new(provider);
+/// oracle.refresh().await?;
+///
+/// let result = account
+///     .execute(calls)
+///     .fee_strategy(oracle.fee_strategy(75.0))
+///     .send()
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct GasOracle<P> {
+    provider: P,
+    window: usize,
+    amount_multiplier: f64,
+    samples: VecDeque<GasPriceSample>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPriceSample {
+    pub block_number: u64,
+    pub gas_price: u64,
+}
+
+/// The maximum amount of a resource a transaction may consume, and the maximum price paid per
+/// unit of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceBounds {
+    pub max_amount: u64,
+    pub max_price_per_unit: u64,
+}
+
+/// A [FeeStrategy] pricing the estimated gas usage with a gas price recommended by a
+/// [GasOracle]. The price is fixed when the strategy is created.
+#[derive(Debug, Clone, Copy)]
+pub struct OracleFeeStrategy {
+    pub gas_price: u64,
+    pub amount_multiplier: f64,
+}
+
+impl<P> GasOracle<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            window: DEFAULT_WINDOW,
+            amount_multiplier: DEFAULT_AMOUNT_MULTIPLIER,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of recent blocks percentiles are computed over. Defaults to `20`.
+    pub fn window(self, window: usize) -> Self {
+        let mut oracle = Self {
+            window: window.max(1),
+            ..self
+        };
+        oracle.trim();
+        oracle
+    }
+
+    /// Sets the multiplier applied to the estimated gas usage for the maximum amount of gas in
+    /// recommended bounds. Defaults to `1.1`.
+    pub fn amount_multiplier(self, amount_multiplier: f64) -> Self {
+        Self {
+            amount_multiplier,
+            ..self
+        }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Samples in the window, from the oldest block.
+    pub fn samples(&self) -> impl Iterator<Item = &GasPriceSample> {
+        self.samples.iter()
+    }
+
+    /// Records the gas price of block `block_number`, as an alternative to
+    /// [refresh](GasOracle::refresh) for applications already following new blocks. Blocks not
+    /// newer than the last one recorded are ignored.
+    pub fn record(&mut self, block_number: u64, gas_price: u64) {
+        if self
+            .samples
+            .back()
+            .is_some_and(|last| last.block_number >= block_number)
+        {
+            return;
+        }
+
+        self.samples.push_back(GasPriceSample {
+            block_number,
+            gas_price,
+        });
+        self.trim();
+    }
+
+    /// The `percentile`, in the range `[0, 100]`, of the gas prices in the window. Returns `None`
+    /// if no block has been sampled yet.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        let mut gas_prices = self
+            .samples
+            .iter()
+            .map(|sample| sample.gas_price)
+            .collect::<Vec<_>>();
+        gas_prices.sort_unstable();
+
+        percentile_of_sorted(&gas_prices, percentile)
+    }
+
+    /// Recommends bounds for the gas usage of `estimate`, priced at `percentile` of recent gas
+    /// prices, or at the estimated gas price if it's higher.
+    pub fn resource_bounds(&self, estimate: &FeeEstimate, percentile: f64) -> ResourceBounds {
+        self.fee_strategy(percentile).resource_bounds(estimate)
+    }
+
+    /// A [FeeStrategy] pricing gas at `percentile` of the gas prices currently in the window.
+    pub fn fee_strategy(&self, percentile: f64) -> OracleFeeStrategy {
+        OracleFeeStrategy {
+            gas_price: self.percentile(percentile).unwrap_or(0),
+            amount_multiplier: self.amount_multiplier,
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.samples.len() > self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    fn record_block(&mut self, block: &Block) {
+        if let (Some(block_number), Ok(gas_price)) =
+            (block.block_number, u64::try_from(block.gas_price))
+        {
+            self.record(block_number, gas_price);
+        }
+    }
+}
+
+impl<P> GasOracle<P>
+where
+    P: Provider,
+{
+    /// Samples the blocks added since the last refresh, up to the size of the window.
+    pub async fn refresh(&mut self) -> Result<(), ProviderError<P::Error>> {
+        let latest_block = self.provider.get_block(BlockId::Latest).await?;
+        let latest_block_number = match latest_block.block_number {
+            Some(block_number) => block_number,
+            None => return Ok(()),
+        };
+
+        let window_start = latest_block_number.saturating_sub(self.window as u64 - 1);
+        let from_block = match self.samples.back() {
+            Some(last) => (last.block_number + 1).max(window_start),
+            None => window_start,
+        };
+
+        for block_number in from_block..latest_block_number {
+            let block = self
+                .provider
+                .get_block(BlockId::Number(block_number))
+                .await?;
+            self.record_block(&block);
+        }
+        self.record_block(&latest_block);
+
+        Ok(())
+    }
+}
+
+impl ResourceBounds {
+    /// The legacy `max_fee` covering the bounds.
+    pub fn max_fee(&self) -> FieldElement {
+        self.max_amount
+            .saturating_mul(self.max_price_per_unit)
+            .into()
+    }
+}
+
+impl OracleFeeStrategy {
+    pub fn resource_bounds(&self, estimate: &FeeEstimate) -> ResourceBounds {
+        ResourceBounds {
+            max_amount: (estimate.gas_usage as f64 * self.amount_multiplier) as u64,
+            max_price_per_unit: self.gas_price.max(estimate.gas_price),
+        }
+    }
+}
+
+impl FeeStrategy for OracleFeeStrategy {
+    fn max_fee(&self, estimate: &FeeEstimate, _recent_gas_prices: &[u64]) -> FieldElement {
+        self.resource_bounds(estimate).max_fee()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FeeUnit;
+    use starknet_providers::SequencerGatewayProvider;
+
+    fn test_estimate() -> FeeEstimate {
+        FeeEstimate {
+            overall_fee: 1000,
+            unit: FeeUnit::Wei,
+            gas_price: 10,
+            gas_usage: 100,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_rolling_percentiles() {
+        let mut oracle =
+            GasOracle::new(SequencerGatewayProvider::starknet_alpha_goerli()).window(4);
+        assert_eq!(oracle.percentile(50.0), None);
+
+        for (block_number, gas_price) in [(1, 50), (2, 40), (3, 30), (4, 20), (5, 10)] {
+            oracle.record(block_number, gas_price);
+        }
+        // Blocks already sampled are ignored
+        oracle.record(3, 1000);
+
+        assert_eq!(
+            oracle
+                .samples()
+                .map(|sample| sample.block_number)
+                .collect::<Vec<_>>(),
+            [2, 3, 4, 5]
+        );
+        assert_eq!(oracle.percentile(0.0), Some(10));
+        assert_eq!(oracle.percentile(75.0), Some(30));
+        assert_eq!(oracle.percentile(100.0), Some(40));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_recommended_bounds() {
+        let mut oracle = GasOracle::new(SequencerGatewayProvider::starknet_alpha_goerli())
+            .amount_multiplier(1.5);
+        for (block_number, gas_price) in [(1, 5), (2, 20), (3, 8)] {
+            oracle.record(block_number, gas_price);
+        }
+
+        let bounds = oracle.resource_bounds(&test_estimate(), 100.0);
+        assert_eq!(
+            bounds,
+            ResourceBounds {
+                max_amount: 150,
+                max_price_per_unit: 20,
+            }
+        );
+        assert_eq!(bounds.max_fee(), FieldElement::from(3000u64));
+
+        // Estimated gas price is used when recent blocks are cheaper
+        assert_eq!(
+            oracle.fee_strategy(0.0).max_fee(&test_estimate(), &[]),
+            FieldElement::from(1500u64)
+        );
+    }
+}
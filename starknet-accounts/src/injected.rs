@@ -0,0 +1,139 @@
+use crate::{Account, ConnectedAccount, RawDeclaration, RawExecution};
+
+use async_trait::async_trait;
+use starknet_core::types::{contract_artifact::ComputeClassHashError, FieldElement};
+use starknet_providers::Provider;
+use starknet_signers::{
+    injected::InjectedWalletError, CallContext, DeclareContext, InjectedWallet, InvokeContext,
+    SignContext, SignRequest,
+};
+
+/// An account controlled by a wallet injected into the page, such as Argent X or Braavos.
+///
+/// Unlike [SingleOwnerAccount](crate::SingleOwnerAccount) with an [InjectedWallet] signer, the
+/// signature is passed on exactly as returned by the wallet, so accounts producing more than 2
+/// signature elements (e.g. with a guardian) are supported.
+#[derive(Debug, Clone)]
+pub struct InjectedAccount<P>
+where
+    P: Provider + Send,
+{
+    provider: P,
+    wallet: InjectedWallet,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignError {
+    #[error(transparent)]
+    Wallet(InjectedWalletError),
+    #[error(transparent)]
+    ClassHash(ComputeClassHashError),
+}
+
+impl<P> InjectedAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    /// Uses the account selected in `wallet`. The provider should be connected to the same network
+    /// as the wallet.
+    pub fn new(provider: P, wallet: InjectedWallet) -> Self {
+        Self { provider, wallet }
+    }
+
+    pub fn wallet(&self) -> &InjectedWallet {
+        &self.wallet
+    }
+}
+
+#[async_trait(?Send)]
+impl<P> Account for InjectedAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    type SignError = SignError;
+
+    fn address(&self) -> FieldElement {
+        self.wallet.address()
+    }
+
+    fn chain_id(&self) -> FieldElement {
+        self.wallet.chain_id()
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        true
+    }
+
+    async fn sign_execution(
+        &self,
+        execution: &RawExecution,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let request = SignRequest {
+            hash: execution.transaction_hash(
+                self.chain_id(),
+                self.address(),
+                self.execution_encoding(),
+            ),
+            context: SignContext::Invoke(InvokeContext {
+                sender_address: self.address(),
+                chain_id: self.chain_id(),
+                encoding: self.execution_encoding().into(),
+                calls: execution
+                    .calls()
+                    .iter()
+                    .map(|call| CallContext {
+                        to: call.to,
+                        selector: call.selector,
+                        calldata: call.calldata.clone(),
+                    })
+                    .collect(),
+                max_fee: execution.max_fee(),
+                nonce: execution.nonce(),
+                is_query: false,
+            }),
+        };
+
+        self.wallet
+            .sign_transaction(&request)
+            .await
+            .map_err(SignError::Wallet)
+    }
+
+    async fn sign_declaration(
+        &self,
+        declaration: &RawDeclaration,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let request = SignRequest {
+            hash: declaration
+                .transaction_hash(self.chain_id(), self.address())
+                .map_err(SignError::ClassHash)?,
+            context: SignContext::Declare(DeclareContext {
+                sender_address: self.address(),
+                chain_id: self.chain_id(),
+                class_hash: declaration
+                    .contract_class()
+                    .class_hash()
+                    .map_err(SignError::ClassHash)?,
+                max_fee: declaration.max_fee(),
+                nonce: declaration.nonce(),
+                is_query: false,
+            }),
+        };
+
+        self.wallet
+            .sign_transaction(&request)
+            .await
+            .map_err(SignError::Wallet)
+    }
+}
+
+impl<P> ConnectedAccount for InjectedAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    type Provider = P;
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+}
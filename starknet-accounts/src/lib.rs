@@ -1,21 +1,88 @@
+/// Instruments a future with a span when the `tracing` feature is enabled. Span fields are not
+/// evaluated otherwise.
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {{
+        let span = tracing::info_span!($($span)+);
+        tracing::Instrument::instrument($future, span)
+    }};
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {
+        $future
+    };
+}
+
 mod account;
 pub use account::{
-    Account, AccountError, ConnectedAccount, Declaration, Execution, PreparedDeclaration,
-    PreparedExecution, RawDeclaration, RawExecution,
+    Account, AccountError, AccountImplementation, ArgumentPreview, CallFailure, CallPreview,
+    ConnectedAccount, ContractRevert, Declaration, Execution, ExecutionEncoding, ExecutionPreview,
+    PreparedDeclaration, PreparedExecution, RawDeclaration, RawExecution, ResubmissionPolicy,
+    ResubmissionStatus, ResubmittingExecution, SimulationDiagnosis,
 };
 
 mod call;
 pub use call::Call;
 
+mod fee_strategy;
+pub use fee_strategy::{ConstantTip, EstimateMultiplier, FeeStrategy, PercentileOfRecentBlocks};
+
+mod gas_oracle;
+pub use gas_oracle::{GasOracle, GasPriceSample, OracleFeeStrategy, ResourceBounds};
+
 mod factory;
 pub use factory::{
     argent::ArgentAccountFactory, open_zeppelin::OpenZeppelinAccountFactory, AccountDeployment,
     AccountFactory, AccountFactoryError, PreparedAccountDeployment, RawAccountDeployment,
 };
 
+mod nonce_manager;
+pub use nonce_manager::{InFlightTransaction, NonceDivergence, NonceManager};
+
+mod messaging;
+pub use messaging::{estimate_l1_handler_fee, L1HandlerFeeEstimate};
+
+mod pool;
+pub use pool::{AccountPool, EmptyPoolError, PoolDispatch};
+
+mod tx_queue;
+pub use tx_queue::{
+    JsonFileQueueStore, JsonFileQueueStoreError, MemoryQueueStore, QueueEntry, QueueEvent,
+    QueueStore, TxQueue, TxQueueError,
+};
+#[cfg(feature = "sled")]
+pub use tx_queue::{SledQueueStore, SledQueueStoreError};
+
+mod tracker;
+pub use tracker::{AccountTxTracker, StatusTransition, TrackedTransaction};
+
+mod wallet_store;
+pub use wallet_store::{
+    AccountConfig, AccountVariant, DeploymentStatus, WalletStore, WalletStoreError,
+};
+
+pub mod eth_account;
+pub use eth_account::EthAccount;
+
+#[cfg(all(target_arch = "wasm32", feature = "injected"))]
+pub mod injected;
+#[cfg(all(target_arch = "wasm32", feature = "injected"))]
+pub use injected::InjectedAccount;
+
+pub mod multisig;
+pub use multisig::MultisigAccount;
+
 pub mod single_owner;
 pub use single_owner::SingleOwnerAccount;
 
+pub use starknet_signers::webauthn::{WebauthnAssertion, WebauthnEncodingError};
+
+#[cfg(feature = "webauthn")]
+pub mod webauthn_account;
+#[cfg(feature = "webauthn")]
+pub use webauthn_account::WebauthnAccount;
+
 #[derive(Debug, thiserror::Error)]
 #[error("Not all fields are prepared")]
 pub struct NotPreparedError;
@@ -0,0 +1,65 @@
+use starknet_core::types::{BlockId, CallL1Handler, FeeEstimate};
+use starknet_providers::{Provider, ProviderError};
+
+/// Fee estimate for consuming an L1 -> L2 message, along with the `msg.value` to attach when
+/// sending the message on L1.
+#[derive(Debug)]
+pub struct L1HandlerFeeEstimate {
+    pub fee_estimate: FeeEstimate,
+    /// Amount of wei to send along with the message on L1 to pay for the L2 execution.
+    pub l1_value: u64,
+}
+
+impl L1HandlerFeeEstimate {
+    /// The `msg.value` of an ETH deposit of `amount` wei through the StarkGate bridge, which
+    /// expects the amount deposited plus the message fee.
+    pub fn deposit_value(&self, amount: u128) -> u128 {
+        amount.saturating_add(self.l1_value as u128)
+    }
+}
+
+/// Estimates the fee of the L1 handler invocation triggered by an L1 -> L2 message. The L1 value
+/// is the estimated fee scaled by `fee_multiplier` to leave room for gas price fluctuations
+/// until the message gets consumed.
+pub async fn estimate_l1_handler_fee<P>(
+    provider: &P,
+    call_l1_handler: CallL1Handler,
+    block_id: BlockId,
+    fee_multiplier: f64,
+) -> Result<L1HandlerFeeEstimate, ProviderError<P::Error>>
+where
+    P: Provider,
+{
+    let fee_estimate = provider
+        .estimate_message_fee(call_l1_handler, block_id)
+        .await?;
+    let l1_value = (fee_estimate.overall_fee as f64 * fee_multiplier) as u64;
+
+    Ok(L1HandlerFeeEstimate {
+        fee_estimate,
+        l1_value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FeeUnit;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_deposit_value() {
+        let estimate = L1HandlerFeeEstimate {
+            fee_estimate: FeeEstimate {
+                overall_fee: 1000,
+                unit: FeeUnit::Wei,
+                gas_price: 10,
+                gas_usage: 100,
+            },
+            l1_value: 1100,
+        };
+
+        assert_eq!(estimate.deposit_value(1_000_000), 1_001_100);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{Account, ConnectedAccount, ExecutionEncoding, RawDeclaration, RawExecution};
+
+use async_trait::async_trait;
+use starknet_core::types::{contract_artifact::ComputeClassHashError, FieldElement};
+use starknet_providers::Provider;
+use starknet_signers::{
+    collector::SignatureCollectorError, CallContext, DeclareContext, InvokeContext, SignContext,
+    SignRequest, SignatureCollector, SignatureTransport,
+};
+
+/// A multisig account whose owners sign out of band, with signatures gathered by a
+/// [SignatureCollector]. The signature is the list of `[signer, r, s]` tuples expected by Argent
+/// multisig accounts.
+#[derive(Debug)]
+pub struct MultisigAccount<P, T>
+where
+    P: Provider + Send,
+    T: SignatureTransport + Send,
+{
+    provider: P,
+    collector: SignatureCollector<T>,
+    address: FieldElement,
+    chain_id: FieldElement,
+    encoding: ExecutionEncoding,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignError<T> {
+    #[error(transparent)]
+    Collector(SignatureCollectorError<T>),
+    #[error(transparent)]
+    ClassHash(ComputeClassHashError),
+}
+
+impl<P, T> MultisigAccount<P, T>
+where
+    P: Provider + Sync + Send,
+    T: SignatureTransport + Sync + Send,
+{
+    pub fn new(
+        provider: P,
+        collector: SignatureCollector<T>,
+        address: FieldElement,
+        chain_id: FieldElement,
+    ) -> Self {
+        Self {
+            provider,
+            collector,
+            address,
+            chain_id,
+            // Argent multisig accounts are all Cairo 1 contracts
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    /// Sets the `__execute__` calldata encoding expected by the account contract. Defaults to
+    /// [ExecutionEncoding::New].
+    pub fn set_execution_encoding(&mut self, encoding: ExecutionEncoding) -> &mut Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn collector(&self) -> &SignatureCollector<T> {
+        &self.collector
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P, T> Account for MultisigAccount<P, T>
+where
+    P: Provider + Sync + Send,
+    T: SignatureTransport + Sync + Send,
+{
+    type SignError = SignError<T::Error>;
+
+    fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    fn chain_id(&self) -> FieldElement {
+        self.chain_id
+    }
+
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.encoding
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        true
+    }
+
+    async fn sign_execution(
+        &self,
+        execution: &RawExecution,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let request = SignRequest {
+            hash: execution.transaction_hash(self.chain_id, self.address, self.encoding),
+            context: SignContext::Invoke(InvokeContext {
+                sender_address: self.address,
+                chain_id: self.chain_id,
+                encoding: self.encoding.into(),
+                calls: execution
+                    .calls()
+                    .iter()
+                    .map(|call| CallContext {
+                        to: call.to,
+                        selector: call.selector,
+                        calldata: call.calldata.clone(),
+                    })
+                    .collect(),
+                max_fee: execution.max_fee(),
+                nonce: execution.nonce(),
+                is_query: false,
+            }),
+        };
+        let signature = self
+            .collector
+            .collect(&request)
+            .await
+            .map_err(SignError::Collector)?;
+
+        Ok(signature.to_felts())
+    }
+
+    async fn sign_declaration(
+        &self,
+        declaration: &RawDeclaration,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let request = SignRequest {
+            hash: declaration
+                .transaction_hash(self.chain_id, self.address)
+                .map_err(SignError::ClassHash)?,
+            context: SignContext::Declare(DeclareContext {
+                sender_address: self.address,
+                chain_id: self.chain_id,
+                class_hash: declaration
+                    .contract_class()
+                    .class_hash()
+                    .map_err(SignError::ClassHash)?,
+                max_fee: declaration.max_fee(),
+                nonce: declaration.nonce(),
+                is_query: false,
+            }),
+        };
+        let signature = self
+            .collector
+            .collect(&request)
+            .await
+            .map_err(SignError::Collector)?;
+
+        Ok(signature.to_felts())
+    }
+}
+
+impl<P, T> ConnectedAccount for MultisigAccount<P, T>
+where
+    P: Provider + Sync + Send,
+    T: SignatureTransport + Sync + Send,
+{
+    type Provider = P;
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+}
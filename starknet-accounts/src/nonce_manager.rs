@@ -0,0 +1,242 @@
+use crate::ConnectedAccount;
+
+use starknet_core::types::FieldElement;
+use starknet_providers::{Provider, ProviderError};
+use std::collections::BTreeMap;
+
+/// Assigns nonces locally so that multiple transactions can be sent without waiting for each of
+/// them to be accepted, while keeping track of in-flight transactions to detect when the local
+/// view diverges from the chain.
+#[derive(Debug, Default, Clone)]
+pub struct NonceManager {
+    next_nonce: Option<u64>,
+    in_flight: BTreeMap<u64, FieldElement>,
+}
+
+/// A sent transaction whose nonce hasn't been consumed on-chain yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InFlightTransaction {
+    pub nonce: FieldElement,
+    pub transaction_hash: FieldElement,
+}
+
+/// Result of comparing the locally assigned nonces with the account nonce on-chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonceDivergence {
+    /// Local and on-chain nonces are consistent.
+    InSync,
+    /// The transaction expected next by the chain is not in flight (e.g. it was dropped), so all
+    /// transactions with higher nonces are stuck.
+    Gap {
+        chain_nonce: FieldElement,
+        stuck: Vec<InFlightTransaction>,
+    },
+    /// The chain has moved past the next local nonce (e.g. another system sent transactions
+    /// from the same account), so using the local nonce would reuse a consumed one.
+    Reuse {
+        chain_nonce: FieldElement,
+        local_nonce: FieldElement,
+    },
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the nonce to use for the next transaction, fetching the account nonce on first
+    /// use.
+    pub async fn next_nonce<A>(
+        &mut self,
+        account: &A,
+    ) -> Result<FieldElement, ProviderError<<A::Provider as Provider>::Error>>
+    where
+        A: ConnectedAccount + Sync,
+    {
+        let nonce = match self.next_nonce {
+            Some(nonce) => nonce,
+            None => felt_to_nonce(account.get_nonce().await?),
+        };
+        self.next_nonce = Some(nonce + 1);
+
+        Ok(nonce.into())
+    }
+
+    /// Gives back a nonce obtained from [next_nonce](Self::next_nonce) that ended up not being
+    /// used, e.g. because sending the transaction failed. Only the latest assigned nonce can be
+    /// given back, as later ones would otherwise be left with a gap.
+    pub fn release(&mut self, nonce: FieldElement) {
+        let nonce = felt_to_nonce(nonce);
+        if self.next_nonce == Some(nonce + 1) {
+            self.next_nonce = Some(nonce);
+        }
+    }
+
+    /// Records a transaction sent with a nonce obtained from [next_nonce](Self::next_nonce).
+    pub fn record_sent(&mut self, nonce: FieldElement, transaction_hash: FieldElement) {
+        self.in_flight
+            .insert(felt_to_nonce(nonce), transaction_hash);
+    }
+
+    /// Transactions sent but not yet known to be included, ordered by nonce.
+    pub fn in_flight(&self) -> Vec<InFlightTransaction> {
+        self.in_flight
+            .iter()
+            .map(|(nonce, transaction_hash)| InFlightTransaction {
+                nonce: (*nonce).into(),
+                transaction_hash: *transaction_hash,
+            })
+            .collect()
+    }
+
+    /// Fetches the account nonce and compares it with the local state.
+    pub async fn check<A>(
+        &self,
+        account: &A,
+    ) -> Result<NonceDivergence, ProviderError<<A::Provider as Provider>::Error>>
+    where
+        A: ConnectedAccount + Sync,
+    {
+        Ok(self.divergence(account.get_nonce().await?))
+    }
+
+    /// Compares the local state with `chain_nonce`, the nonce expected next by the account
+    /// contract.
+    pub fn divergence(&self, chain_nonce: FieldElement) -> NonceDivergence {
+        let next_nonce = match self.next_nonce {
+            Some(value) => value,
+            None => return NonceDivergence::InSync,
+        };
+        let chain_nonce_value = felt_to_nonce(chain_nonce);
+
+        if next_nonce < chain_nonce_value {
+            NonceDivergence::Reuse {
+                chain_nonce,
+                local_nonce: next_nonce.into(),
+            }
+        } else if next_nonce > chain_nonce_value && !self.in_flight.contains_key(&chain_nonce_value)
+        {
+            NonceDivergence::Gap {
+                chain_nonce,
+                stuck: self
+                    .in_flight
+                    .range(chain_nonce_value..)
+                    .map(|(nonce, transaction_hash)| InFlightTransaction {
+                        nonce: (*nonce).into(),
+                        transaction_hash: *transaction_hash,
+                    })
+                    .collect(),
+            }
+        } else {
+            NonceDivergence::InSync
+        }
+    }
+
+    /// Resynchronizes with `chain_nonce`. Transactions whose nonces have been consumed are
+    /// forgotten. On divergence, local nonces restart from `chain_nonce` and the in-flight
+    /// transactions that can no longer land are returned so they can be re-signed and resent.
+    pub fn resync(&mut self, chain_nonce: FieldElement) -> Vec<InFlightTransaction> {
+        let divergence = self.divergence(chain_nonce);
+        let chain_nonce_value = felt_to_nonce(chain_nonce);
+
+        // Nonces below the chain nonce are consumed
+        self.in_flight = self.in_flight.split_off(&chain_nonce_value);
+
+        match divergence {
+            NonceDivergence::InSync => vec![],
+            NonceDivergence::Gap { stuck, .. } => {
+                self.in_flight.clear();
+                self.next_nonce = Some(chain_nonce_value);
+                stuck
+            }
+            NonceDivergence::Reuse { .. } => {
+                self.next_nonce = Some(chain_nonce_value);
+                vec![]
+            }
+        }
+    }
+}
+
+// Field elements don't order numerically, so nonces are tracked as integers. Account nonces never
+// get anywhere close to `u64::MAX`.
+fn felt_to_nonce(nonce: FieldElement) -> u64 {
+    nonce.try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager_with_in_flight(next_nonce: u64, in_flight: &[u64]) -> NonceManager {
+        NonceManager {
+            next_nonce: Some(next_nonce),
+            in_flight: in_flight
+                .iter()
+                .map(|nonce| (*nonce, FieldElement::from(*nonce + 100)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_nonce_release() {
+        let mut manager = manager_with_in_flight(8, &[]);
+
+        // Only the latest assigned nonce can be released
+        manager.release(6u64.into());
+        assert_eq!(manager.next_nonce, Some(8));
+        manager.release(7u64.into());
+        assert_eq!(manager.next_nonce, Some(7));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_nonce_in_sync() {
+        let manager = manager_with_in_flight(8, &[5, 6, 7]);
+
+        assert_eq!(manager.divergence(5u64.into()), NonceDivergence::InSync);
+        assert_eq!(manager.divergence(8u64.into()), NonceDivergence::InSync);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_nonce_gap() {
+        // Transaction with nonce 6 got dropped
+        let mut manager = manager_with_in_flight(8, &[5, 7]);
+
+        let divergence = manager.divergence(6u64.into());
+        assert_eq!(
+            divergence,
+            NonceDivergence::Gap {
+                chain_nonce: 6u64.into(),
+                stuck: vec![InFlightTransaction {
+                    nonce: 7u64.into(),
+                    transaction_hash: 107u64.into(),
+                }],
+            }
+        );
+
+        let resent = manager.resync(6u64.into());
+        assert_eq!(resent.len(), 1);
+        assert!(manager.in_flight().is_empty());
+        assert_eq!(manager.next_nonce, Some(6));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_nonce_reuse() {
+        let mut manager = manager_with_in_flight(8, &[7]);
+
+        assert_eq!(
+            manager.divergence(10u64.into()),
+            NonceDivergence::Reuse {
+                chain_nonce: 10u64.into(),
+                local_nonce: 8u64.into(),
+            }
+        );
+
+        assert!(manager.resync(10u64.into()).is_empty());
+        assert!(manager.in_flight().is_empty());
+        assert_eq!(manager.next_nonce, Some(10));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{AccountError, Call, ConnectedAccount, InFlightTransaction, NonceManager};
+
+use starknet_core::types::{AddTransactionResult, FieldElement};
+use starknet_providers::{Provider, ProviderError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Dispatches executions across several accounts in a round-robin fashion, each with its own
+/// [NonceManager]. Since transactions from the same account must be ordered by nonce, spreading
+/// them over multiple funded accounts increases throughput.
+///
+/// Executions can be sent concurrently through a shared pool. Each account is locked while its
+/// execution is sent, so that nonces are still assigned and sent in order.
+#[derive(Debug)]
+pub struct AccountPool<A> {
+    members: Vec<PoolMember<A>>,
+    next_member: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct PoolMember<A> {
+    account: A,
+    nonce_manager: Mutex<NonceManager>,
+}
+
+/// An execution sent through an [AccountPool].
+#[derive(Debug)]
+pub struct PoolDispatch {
+    /// Index of the account in the pool.
+    pub account_index: usize,
+    pub account_address: FieldElement,
+    pub nonce: FieldElement,
+    pub result: AddTransactionResult,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("account pool is empty")]
+pub struct EmptyPoolError;
+
+impl<A> AccountPool<A> {
+    pub fn new(accounts: Vec<A>) -> Result<Self, EmptyPoolError> {
+        if accounts.is_empty() {
+            return Err(EmptyPoolError);
+        }
+
+        Ok(Self {
+            members: accounts
+                .into_iter()
+                .map(|account| PoolMember {
+                    account,
+                    nonce_manager: Mutex::new(NonceManager::new()),
+                })
+                .collect(),
+            next_member: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn account(&self, index: usize) -> Option<&A> {
+        self.members.get(index).map(|member| &member.account)
+    }
+
+    /// Locks the nonce manager of the account at `index`, waiting for its execution in progress
+    /// if any.
+    pub async fn nonce_manager(&self, index: usize) -> Option<MutexGuard<'_, NonceManager>> {
+        match self.members.get(index) {
+            Some(member) => Some(member.nonce_manager.lock().await),
+            None => None,
+        }
+    }
+}
+
+impl<A> AccountPool<A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Sends `calls` from the next account in the pool, using a locally assigned nonce so that
+    /// it doesn't need to wait for previous transactions of the same account to be accepted.
+    pub async fn execute(
+        &self,
+        calls: Vec<Call>,
+    ) -> Result<PoolDispatch, AccountError<A::SignError, <A::Provider as Provider>::Error>> {
+        let account_index = self.next_member.fetch_add(1, Ordering::Relaxed) % self.members.len();
+
+        let member = &self.members[account_index];
+        let mut nonce_manager = member.nonce_manager.lock().await;
+        let nonce = nonce_manager
+            .next_nonce(&member.account)
+            .await
+            .map_err(AccountError::Provider)?;
+
+        match member.account.execute(calls).nonce(nonce).send().await {
+            Ok(result) => {
+                nonce_manager.record_sent(nonce, result.transaction_hash);
+
+                Ok(PoolDispatch {
+                    account_index,
+                    account_address: member.account.address(),
+                    nonce,
+                    result,
+                })
+            }
+            Err(err) => {
+                nonce_manager.release(nonce);
+                Err(err)
+            }
+        }
+    }
+
+    /// Resynchronizes the nonce managers of all accounts with the chain, returning, for each
+    /// account index, the in-flight transactions that need to be resent.
+    pub async fn resync(
+        &self,
+    ) -> Result<
+        Vec<(usize, Vec<InFlightTransaction>)>,
+        ProviderError<<A::Provider as Provider>::Error>,
+    > {
+        let mut stuck = vec![];
+
+        for (index, member) in self.members.iter().enumerate() {
+            let mut nonce_manager = member.nonce_manager.lock().await;
+            let chain_nonce = member.account.get_nonce().await?;
+            let transactions = nonce_manager.resync(chain_nonce);
+            if !transactions.is_empty() {
+                stuck.push((index, transactions));
+            }
+        }
+
+        Ok(stuck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SingleOwnerAccount;
+
+    use starknet_core::{chain_id, types::TransactionRequest};
+    use starknet_providers::{ChainFixture, MockProvider};
+    use starknet_signers::{LocalWallet, SigningKey};
+    use std::sync::Arc;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_empty_pool() {
+        assert!(AccountPool::<()>::new(vec![]).is_err());
+
+        let pool = AccountPool::new(vec![(), ()]).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(pool.account(2).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_executions() {
+        let addresses = [FieldElement::TWO, FieldElement::THREE];
+        let provider = Arc::new(
+            addresses
+                .iter()
+                .fold(ChainFixture::new(), |fixture, address| {
+                    fixture.account(*address, FieldElement::ONE, FieldElement::ONE)
+                })
+                .build(),
+        );
+        let pool = AccountPool::new(
+            addresses
+                .iter()
+                .map(|address| {
+                    SingleOwnerAccount::new(
+                        provider.clone(),
+                        LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+                        *address,
+                        chain_id::TESTNET,
+                    )
+                })
+                .collect::<Vec<SingleOwnerAccount<Arc<MockProvider>, LocalWallet>>>(),
+        )
+        .unwrap();
+
+        let call = Call {
+            to: FieldElement::ONE,
+            selector: FieldElement::ONE,
+            calldata: vec![],
+        };
+        let results = tokio::join!(
+            pool.execute(vec![call.clone()]),
+            pool.execute(vec![call.clone()]),
+            pool.execute(vec![call.clone()]),
+            pool.execute(vec![call]),
+        );
+        for result in [results.0, results.1, results.2, results.3] {
+            result.unwrap();
+        }
+
+        // Each account sent its transactions with consecutive nonces
+        for address in addresses {
+            let nonces = provider
+                .submitted()
+                .into_iter()
+                .filter_map(|tx| match tx {
+                    TransactionRequest::InvokeFunction(tx) if tx.contract_address == address => {
+                        Some(tx.nonce)
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(nonces, [FieldElement::ZERO, FieldElement::ONE]);
+        }
+        assert_eq!(pool.nonce_manager(0).await.unwrap().in_flight().len(), 2);
+    }
+}
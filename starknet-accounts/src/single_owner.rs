@@ -1,9 +1,11 @@
-use crate::{Account, ConnectedAccount, RawDeclaration, RawExecution};
+use crate::{Account, ConnectedAccount, ExecutionEncoding, RawDeclaration, RawExecution};
 
 use async_trait::async_trait;
 use starknet_core::types::{contract_artifact::ComputeClassHashError, FieldElement};
 use starknet_providers::Provider;
-use starknet_signers::Signer;
+use starknet_signers::{
+    CallContext, DeclareContext, InvokeContext, SignContext, SignRequest, Signer,
+};
 
 #[derive(Debug, Clone)]
 pub struct SingleOwnerAccount<P, S>
@@ -15,6 +17,7 @@ where
     signer: S,
     address: FieldElement,
     chain_id: FieldElement,
+    encoding: ExecutionEncoding,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -36,8 +39,18 @@ where
             signer,
             address,
             chain_id,
+            encoding: ExecutionEncoding::Legacy,
         }
     }
+
+    /// Sets the `__execute__` calldata encoding expected by the account contract. Accounts use
+    /// [ExecutionEncoding::Legacy] by default. Use
+    /// [detect_execution_encoding](ConnectedAccount::detect_execution_encoding) to find out the
+    /// encoding from the deployed account class.
+    pub fn set_execution_encoding(&mut self, encoding: ExecutionEncoding) -> &mut Self {
+        self.encoding = encoding;
+        self
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -57,14 +70,41 @@ where
         self.chain_id
     }
 
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.encoding
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+
     async fn sign_execution(
         &self,
         execution: &RawExecution,
     ) -> Result<Vec<FieldElement>, Self::SignError> {
-        let tx_hash = execution.transaction_hash(self.chain_id, self.address);
+        let request = SignRequest {
+            hash: execution.transaction_hash(self.chain_id, self.address, self.encoding),
+            context: SignContext::Invoke(InvokeContext {
+                sender_address: self.address,
+                chain_id: self.chain_id,
+                encoding: self.encoding.into(),
+                calls: execution
+                    .calls()
+                    .iter()
+                    .map(|call| CallContext {
+                        to: call.to,
+                        selector: call.selector,
+                        calldata: call.calldata.clone(),
+                    })
+                    .collect(),
+                max_fee: execution.max_fee(),
+                nonce: execution.nonce(),
+                is_query: false,
+            }),
+        };
         let signature = self
             .signer
-            .sign_hash(&tx_hash)
+            .sign_request(&request)
             .await
             .map_err(SignError::Signer)?;
 
@@ -75,12 +115,25 @@ where
         &self,
         declaration: &RawDeclaration,
     ) -> Result<Vec<FieldElement>, Self::SignError> {
-        let tx_hash = declaration
-            .transaction_hash(self.chain_id, self.address)
-            .map_err(SignError::ClassHash)?;
+        let request = SignRequest {
+            hash: declaration
+                .transaction_hash(self.chain_id, self.address)
+                .map_err(SignError::ClassHash)?,
+            context: SignContext::Declare(DeclareContext {
+                sender_address: self.address,
+                chain_id: self.chain_id,
+                class_hash: declaration
+                    .contract_class()
+                    .class_hash()
+                    .map_err(SignError::ClassHash)?,
+                max_fee: declaration.max_fee(),
+                nonce: declaration.nonce(),
+                is_query: false,
+            }),
+        };
         let signature = self
             .signer
-            .sign_hash(&tx_hash)
+            .sign_request(&request)
             .await
             .map_err(SignError::Signer)?;
 
@@ -0,0 +1,142 @@
+use crate::{AccountError, ConnectedAccount, Execution};
+
+use starknet_core::types::{AddTransactionResult, FieldElement, TransactionStatus};
+use starknet_providers::{Provider, ProviderError};
+
+/// Remembers the transactions sent by an account and reports their status transitions until
+/// they reach finality, i.e. until they're accepted on L1 or rejected.
+///
+/// Transitions are pulled with [poll](AccountTxTracker::poll), leaving the polling interval
+/// (and the async runtime used for sleeping) to the caller.
+#[derive(Debug)]
+pub struct AccountTxTracker<'a, A> {
+    account: &'a A,
+    transactions: Vec<TrackedTransaction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedTransaction {
+    pub transaction_hash: FieldElement,
+    /// Last observed status. `None` if the transaction hasn't been polled yet.
+    pub status: Option<TransactionStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusTransition {
+    pub transaction_hash: FieldElement,
+    pub from: Option<TransactionStatus>,
+    pub to: TransactionStatus,
+}
+
+impl<'a, A> AccountTxTracker<'a, A> {
+    pub fn new(account: &'a A) -> Self {
+        Self {
+            account,
+            transactions: vec![],
+        }
+    }
+
+    /// Starts tracking a transaction sent by the account.
+    pub fn track(&mut self, transaction_hash: FieldElement) {
+        if !self
+            .transactions
+            .iter()
+            .any(|tx| tx.transaction_hash == transaction_hash)
+        {
+            self.transactions.push(TrackedTransaction {
+                transaction_hash,
+                status: None,
+            });
+        }
+    }
+
+    /// Transactions that haven't reached finality yet.
+    pub fn pending(&self) -> &[TrackedTransaction] {
+        &self.transactions
+    }
+}
+
+impl<'a, A> AccountTxTracker<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Sends the execution and tracks the resulting transaction.
+    pub async fn send(
+        &mut self,
+        execution: &Execution<'_, A>,
+    ) -> Result<AddTransactionResult, AccountError<A::SignError, <A::Provider as Provider>::Error>>
+    {
+        let result = execution.send().await?;
+        self.track(result.transaction_hash);
+        Ok(result)
+    }
+
+    /// Fetches the status of every pending transaction and returns the transitions observed since
+    /// the last poll. Transactions reaching finality are reported one last time and then dropped.
+    pub async fn poll(
+        &mut self,
+    ) -> Result<Vec<StatusTransition>, ProviderError<<A::Provider as Provider>::Error>> {
+        let mut transitions = vec![];
+
+        for tx in self.transactions.iter_mut() {
+            let status = instrument!(
+                self.account
+                    .provider()
+                    .get_transaction_status(tx.transaction_hash),
+                "poll",
+                transaction_hash = %format_args!("{:#x}", tx.transaction_hash)
+            )
+            .await?
+            .status;
+
+            if tx.status != Some(status) {
+                transitions.push(StatusTransition {
+                    transaction_hash: tx.transaction_hash,
+                    from: tx.status,
+                    to: status,
+                });
+                tx.status = Some(status);
+            }
+        }
+
+        self.transactions
+            .retain(|tx| !matches!(tx.status, Some(status) if is_final(status)));
+
+        Ok(transitions)
+    }
+}
+
+fn is_final(status: TransactionStatus) -> bool {
+    matches!(
+        status,
+        TransactionStatus::AcceptedOnL1 | TransactionStatus::Rejected
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_track_deduplicates() {
+        let account = ();
+        let mut tracker = AccountTxTracker::new(&account);
+
+        tracker.track(FieldElement::ONE);
+        tracker.track(FieldElement::TWO);
+        tracker.track(FieldElement::ONE);
+
+        assert_eq!(tracker.pending().len(), 2);
+        assert!(tracker.pending().iter().all(|tx| tx.status.is_none()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_final_statuses() {
+        assert!(is_final(TransactionStatus::AcceptedOnL1));
+        assert!(is_final(TransactionStatus::Rejected));
+        assert!(!is_final(TransactionStatus::AcceptedOnL2));
+        assert!(!is_final(TransactionStatus::Pending));
+    }
+}
@@ -0,0 +1,867 @@
+use crate::{
+    account::bump_max_fee,
+    fee_strategy::{resolve_max_fee, PercentileOfRecentBlocks},
+    AccountError, Call, ConnectedAccount, Execution, FeeStrategy, ResubmissionPolicy,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use starknet_core::{
+    serde::unsigned_field_element::UfeHex,
+    types::{BlockId, FieldElement, TransactionStatus},
+};
+use starknet_providers::{Provider, ProviderError};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Persistence backend of a [TxQueue], saving entries whenever they change so that the queue
+/// can be reopened after a restart.
+pub trait QueueStore {
+    type Error: std::error::Error;
+
+    fn load(&mut self) -> Result<Vec<QueueEntry>, Self::Error>;
+
+    fn save(&mut self, entry: &QueueEntry) -> Result<(), Self::Error>;
+
+    fn remove(&mut self, id: u64) -> Result<(), Self::Error>;
+}
+
+/// A queue of executions sent by an account in order, persisted in a [QueueStore].
+///
+/// Transactions are sent one at a time, so that a rejected transaction never leaves the ones
+/// queued after it with a nonce gap. The transaction in flight is resubmitted with a bumped fee
+/// according to the [ResubmissionPolicy] when it gets stuck.
+///
+/// The queue is advanced with [process](TxQueue::process), leaving the polling interval (and the
+/// async runtime used for sleeping) to the caller.
+///
+/// Nonces are derived from the previous entry once it lands, as nodes can still report the old
+/// nonce while the transaction is pending.
+#[derive(Debug)]
+pub struct TxQueue<'a, A, S> {
+    account: &'a A,
+    store: S,
+    policy: ResubmissionPolicy,
+    fee_strategy: Arc<dyn FeeStrategy>,
+    entries: BTreeMap<u64, QueueEntry>,
+    next_id: u64,
+    next_nonce: Option<FieldElement>,
+}
+
+/// An execution in a [TxQueue].
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub id: u64,
+    pub calls: Vec<Call>,
+    /// Assigned right before the first submission, and kept across resubmissions unless the
+    /// sequencer rejects it as invalid.
+    #[serde_as(as = "Option<UfeHex>")]
+    pub nonce: Option<FieldElement>,
+    /// `max_fee` of the latest attempt.
+    #[serde_as(as = "Option<UfeHex>")]
+    pub max_fee: Option<FieldElement>,
+    /// Hashes of all submitted attempts, in submission order. Each hash is saved before sending,
+    /// so that an attempt sent right before a crash is still polled.
+    #[serde_as(as = "Vec<UfeHex>")]
+    pub attempts: Vec<FieldElement>,
+    pub polls_since_submission: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueEvent {
+    Submitted {
+        id: u64,
+        transaction_hash: FieldElement,
+    },
+    Resubmitted {
+        id: u64,
+        attempt: usize,
+        transaction_hash: FieldElement,
+    },
+    /// The entry has been accepted and removed from the queue.
+    Landed {
+        id: u64,
+        transaction_hash: FieldElement,
+    },
+    /// The entry has been rejected by the sequencer and removed from the queue.
+    Rejected {
+        id: u64,
+        transaction_hash: FieldElement,
+    },
+    /// The entry is still not accepted after the maximum number of attempts. It's kept in the
+    /// queue, blocking the following entries, until [removed](TxQueue::remove).
+    Stuck { id: u64 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TxQueueError<S, E> {
+    #[error("queue store error: {0}")]
+    Store(#[source] S),
+    #[error(transparent)]
+    Account(E),
+}
+
+/// A [QueueStore] keeping entries in memory only, for tests and short-lived processes.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryQueueStore {
+    entries: BTreeMap<u64, QueueEntry>,
+}
+
+/// A [QueueStore] saving each entry as a JSON file named after its id in a directory. Files are
+/// replaced atomically, so that an entry is never left half-written.
+#[derive(Debug, Clone)]
+pub struct JsonFileQueueStore {
+    dir: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonFileQueueStoreError {
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error("invalid queue entry {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A [QueueStore] saving entries in a [sled] database, keyed by their big-endian id. Every
+/// change is flushed to disk before returning.
+#[cfg(feature = "sled")]
+#[derive(Debug, Clone)]
+pub struct SledQueueStore {
+    tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+#[derive(Debug, thiserror::Error)]
+pub enum SledQueueStoreError {
+    #[error(transparent)]
+    Sled(sled::Error),
+    #[error("invalid queue entry {id}: {source}")]
+    Json {
+        id: u64,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+type ProcessError<A, S> = TxQueueError<
+    <S as QueueStore>::Error,
+    AccountError<
+        <A as crate::Account>::SignError,
+        <<A as ConnectedAccount>::Provider as Provider>::Error,
+    >,
+>;
+
+impl<'a, A, S> TxQueue<'a, A, S>
+where
+    S: QueueStore,
+{
+    /// Opens the queue of `account`, restoring the entries saved in `store`.
+    pub fn open(
+        account: &'a A,
+        mut store: S,
+        policy: ResubmissionPolicy,
+    ) -> Result<Self, S::Error> {
+        let entries = store
+            .load()?
+            .into_iter()
+            .map(|entry| (entry.id, entry))
+            .collect::<BTreeMap<_, _>>();
+        let next_id = entries.keys().next_back().map_or(0, |id| id + 1);
+
+        Ok(Self {
+            account,
+            store,
+            policy,
+            fee_strategy: Arc::new(PercentileOfRecentBlocks::default()),
+            entries,
+            next_id,
+            next_nonce: None,
+        })
+    }
+
+    /// Sets the strategy used for deciding `max_fee` of first attempts. Defaults to
+    /// the default [PercentileOfRecentBlocks].
+    pub fn fee_strategy<F>(self, fee_strategy: F) -> Self
+    where
+        F: FeeStrategy + 'static,
+    {
+        Self {
+            fee_strategy: Arc::new(fee_strategy),
+            ..self
+        }
+    }
+
+    /// Entries in the queue, in the order they're sent.
+    pub fn entries(&self) -> impl Iterator<Item = &QueueEntry> {
+        self.entries.values()
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Adds an execution to the end of the queue, returning its id.
+    pub fn enqueue(&mut self, calls: Vec<Call>) -> Result<u64, S::Error> {
+        let entry = QueueEntry {
+            id: self.next_id,
+            calls,
+            nonce: None,
+            max_fee: None,
+            attempts: vec![],
+            polls_since_submission: 0,
+        };
+        self.store.save(&entry)?;
+
+        self.next_id += 1;
+        self.entries.insert(entry.id, entry);
+        Ok(self.next_id - 1)
+    }
+
+    /// Drops an entry, e.g. one reported as [stuck](QueueEvent::Stuck). Its attempts might still
+    /// land if they were submitted.
+    pub fn remove(&mut self, id: u64) -> Result<Option<QueueEntry>, S::Error> {
+        self.store.remove(id)?;
+        Ok(self.entries.remove(&id))
+    }
+}
+
+impl<'a, A, S> TxQueue<'a, A, S>
+where
+    A: ConnectedAccount + Sync,
+    S: QueueStore,
+{
+    /// Checks the transaction in flight, and submits the next entry once it's final. Callers are
+    /// expected to call this periodically while the queue is not empty.
+    pub async fn process(&mut self) -> Result<Vec<QueueEvent>, ProcessError<A, S>> {
+        let mut events = vec![];
+
+        while let Some(mut entry) = self.entries.values().next().cloned() {
+            if entry.attempts.is_empty() {
+                let transaction_hash = self.submit(&mut entry).await?;
+                events.push(QueueEvent::Submitted {
+                    id: entry.id,
+                    transaction_hash,
+                });
+                break;
+            }
+
+            match self.poll(&mut entry).await? {
+                Some(event @ (QueueEvent::Landed { .. } | QueueEvent::Rejected { .. })) => {
+                    self.remove(entry.id).map_err(TxQueueError::Store)?;
+                    events.push(event);
+                }
+                Some(event) => {
+                    events.push(event);
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Submits the first attempt of `entry`. The nonce is saved before sending, so that a
+    /// restarted queue resubmits with the same nonce. A nonce rejected as invalid is replaced
+    /// with a freshly fetched one.
+    async fn submit(&mut self, entry: &mut QueueEntry) -> Result<FieldElement, ProcessError<A, S>> {
+        match self.try_submit(entry).await {
+            Err(TxQueueError::Account(err)) if is_invalid_nonce(&err.to_string()) => {
+                self.next_nonce = None;
+                entry.nonce = None;
+                self.try_submit(entry).await
+            }
+            result => result,
+        }
+    }
+
+    async fn try_submit(
+        &mut self,
+        entry: &mut QueueEntry,
+    ) -> Result<FieldElement, ProcessError<A, S>> {
+        let nonce = match entry.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let nonce = self
+                    .fetch_nonce()
+                    .await
+                    .map_err(|err| TxQueueError::Account(AccountError::Provider(err)))?;
+                entry.nonce = Some(nonce);
+                self.save(entry).map_err(TxQueueError::Store)?;
+                nonce
+            }
+        };
+
+        let fee_estimate = Execution::new(entry.calls.clone(), self.account)
+            .nonce(nonce)
+            .estimate_fee()
+            .await
+            .map_err(TxQueueError::Account)?;
+        let max_fee = resolve_max_fee(
+            self.account.provider(),
+            self.fee_strategy.as_ref(),
+            &fee_estimate,
+        )
+        .await
+        .map_err(|err| TxQueueError::Account(AccountError::Provider(err)))?;
+
+        self.send(entry, nonce, max_fee).await
+    }
+
+    /// The nonce following the previous entry, or the account nonce in the pending block if it's
+    /// higher or the previous entry is unknown.
+    async fn fetch_nonce(
+        &self,
+    ) -> Result<FieldElement, ProviderError<<A::Provider as Provider>::Error>> {
+        let chain_nonce = self
+            .account
+            .provider()
+            .get_nonce(self.account.address(), BlockId::Pending)
+            .await?;
+
+        Ok(match self.next_nonce {
+            Some(next_nonce) if next_nonce > chain_nonce => next_nonce,
+            _ => chain_nonce,
+        })
+    }
+
+    /// Polls the attempts of `entry`, resubmitting it if the policy allows. Returns `None` while
+    /// waiting for the latest attempt.
+    async fn poll(
+        &mut self,
+        entry: &mut QueueEntry,
+    ) -> Result<Option<QueueEvent>, ProcessError<A, S>> {
+        let latest_attempt = entry.attempts.len() - 1;
+
+        for (attempt, transaction_hash) in entry.attempts.iter().enumerate() {
+            let status = self
+                .account
+                .provider()
+                .get_transaction_status(*transaction_hash)
+                .await
+                .map_err(|err| TxQueueError::Account(AccountError::Provider(err)))?
+                .status;
+
+            match status {
+                TransactionStatus::Pending
+                | TransactionStatus::AcceptedOnL2
+                | TransactionStatus::AcceptedOnL1 => {
+                    self.next_nonce = entry.nonce.map(|nonce| nonce + FieldElement::ONE);
+                    return Ok(Some(QueueEvent::Landed {
+                        id: entry.id,
+                        transaction_hash: *transaction_hash,
+                    }));
+                }
+                TransactionStatus::Rejected if attempt == latest_attempt => {
+                    self.next_nonce = None;
+                    return Ok(Some(QueueEvent::Rejected {
+                        id: entry.id,
+                        transaction_hash: *transaction_hash,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        entry.polls_since_submission += 1;
+        if entry.polls_since_submission < self.policy.polls_before_resubmission {
+            self.save(entry).map_err(TxQueueError::Store)?;
+            return Ok(None);
+        }
+        if entry.attempts.len() >= self.policy.max_attempts {
+            self.save(entry).map_err(TxQueueError::Store)?;
+            return Ok(Some(QueueEvent::Stuck { id: entry.id }));
+        }
+
+        let nonce = entry.nonce.expect("submitted entries have a nonce");
+        // Re-estimates as the network conditions might have changed since the last attempt
+        let fee_estimate = Execution::new(entry.calls.clone(), self.account)
+            .nonce(nonce)
+            .estimate_fee()
+            .await
+            .map_err(TxQueueError::Account)?;
+        let max_fee = bump_max_fee(
+            entry.max_fee.unwrap_or_default(),
+            fee_estimate.overall_fee,
+            self.policy.fee_bump_multiplier,
+        );
+
+        let transaction_hash = self.send(entry, nonce, max_fee).await?;
+        Ok(Some(QueueEvent::Resubmitted {
+            id: entry.id,
+            attempt: entry.attempts.len() - 1,
+            transaction_hash,
+        }))
+    }
+
+    async fn send(
+        &mut self,
+        entry: &mut QueueEntry,
+        nonce: FieldElement,
+        max_fee: FieldElement,
+    ) -> Result<FieldElement, ProcessError<A, S>> {
+        let execution = Execution::new(entry.calls.clone(), self.account)
+            .nonce(nonce)
+            .max_fee(max_fee)
+            .prepared()
+            .expect("nonce and max_fee are set");
+        let transaction_hash = execution.transaction_hash();
+
+        let previous_max_fee = entry.max_fee.replace(max_fee);
+        let previous_polls = std::mem::take(&mut entry.polls_since_submission);
+        entry.attempts.push(transaction_hash);
+        self.save(entry).map_err(TxQueueError::Store)?;
+
+        match execution.send().await {
+            Ok(result) => {
+                if result.transaction_hash != transaction_hash {
+                    *entry.attempts.last_mut().unwrap() = result.transaction_hash;
+                    self.save(entry).map_err(TxQueueError::Store)?;
+                }
+                Ok(result.transaction_hash)
+            }
+            Err(err) => {
+                entry.attempts.pop();
+                entry.max_fee = previous_max_fee;
+                entry.polls_since_submission = previous_polls;
+                self.save(entry).map_err(TxQueueError::Store)?;
+                Err(TxQueueError::Account(err))
+            }
+        }
+    }
+
+    fn save(&mut self, entry: &QueueEntry) -> Result<(), S::Error> {
+        self.store.save(entry)?;
+        self.entries.insert(entry.id, entry.clone());
+        Ok(())
+    }
+}
+
+fn is_invalid_nonce(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("invalid transaction nonce")
+        || message.contains("invalidtransactionnonce")
+        || message.contains("invalid_transaction_nonce")
+}
+
+impl MemoryQueueStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl QueueStore for MemoryQueueStore {
+    type Error = std::convert::Infallible;
+
+    fn load(&mut self) -> Result<Vec<QueueEntry>, Self::Error> {
+        Ok(self.entries.values().cloned().collect())
+    }
+
+    fn save(&mut self, entry: &QueueEntry) -> Result<(), Self::Error> {
+        self.entries.insert(entry.id, entry.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, id: u64) -> Result<(), Self::Error> {
+        self.entries.remove(&id);
+        Ok(())
+    }
+}
+
+impl JsonFileQueueStore {
+    /// Stores entries in `dir`, which is created on first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+}
+
+impl QueueStore for JsonFileQueueStore {
+    type Error = JsonFileQueueStoreError;
+
+    fn load(&mut self) -> Result<Vec<QueueEntry>, Self::Error> {
+        let dir_entries = match std::fs::read_dir(&self.dir) {
+            Ok(dir_entries) => dir_entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(JsonFileQueueStoreError::Io(err)),
+        };
+
+        let mut entries = vec![];
+        for dir_entry in dir_entries {
+            let path = dir_entry.map_err(JsonFileQueueStoreError::Io)?.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+
+            let json = std::fs::read_to_string(&path).map_err(JsonFileQueueStoreError::Io)?;
+            let entry = serde_json::from_str(&json)
+                .map_err(|source| JsonFileQueueStoreError::Json { path, source })?;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    fn save(&mut self, entry: &QueueEntry) -> Result<(), Self::Error> {
+        std::fs::create_dir_all(&self.dir).map_err(JsonFileQueueStoreError::Io)?;
+
+        let path = self.entry_path(entry.id);
+        let json =
+            serde_json::to_string(entry).map_err(|source| JsonFileQueueStoreError::Json {
+                path: path.clone(),
+                source,
+            })?;
+
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, json).map_err(JsonFileQueueStoreError::Io)?;
+        std::fs::rename(&temp_path, &path).map_err(JsonFileQueueStoreError::Io)
+    }
+
+    fn remove(&mut self, id: u64) -> Result<(), Self::Error> {
+        match std::fs::remove_file(self.entry_path(id)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(JsonFileQueueStoreError::Io(err))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl SledQueueStore {
+    /// Opens or creates the database at `path`, storing entries in its `tx_queue` tree.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SledQueueStoreError> {
+        // Changes are flushed on every write, so the background flusher isn't needed
+        let tree = sled::Config::new()
+            .path(path)
+            .flush_every_ms(None)
+            .open()
+            .and_then(|db| db.open_tree("tx_queue"))
+            .map_err(SledQueueStoreError::Sled)?;
+        Ok(Self::new(tree))
+    }
+
+    /// Stores entries in `tree`, which shouldn't be used for anything else.
+    pub fn new(tree: sled::Tree) -> Self {
+        Self { tree }
+    }
+
+    pub fn tree(&self) -> &sled::Tree {
+        &self.tree
+    }
+}
+
+#[cfg(feature = "sled")]
+impl QueueStore for SledQueueStore {
+    type Error = SledQueueStoreError;
+
+    fn load(&mut self) -> Result<Vec<QueueEntry>, Self::Error> {
+        self.tree
+            .iter()
+            .map(|item| {
+                let (key, value) = item.map_err(SledQueueStoreError::Sled)?;
+                serde_json::from_slice(&value).map_err(|source| SledQueueStoreError::Json {
+                    id: key
+                        .as_ref()
+                        .try_into()
+                        .map(u64::from_be_bytes)
+                        .unwrap_or_default(),
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    fn save(&mut self, entry: &QueueEntry) -> Result<(), Self::Error> {
+        let json = serde_json::to_vec(entry).map_err(|source| SledQueueStoreError::Json {
+            id: entry.id,
+            source,
+        })?;
+
+        self.tree
+            .insert(entry.id.to_be_bytes(), json)
+            .map_err(SledQueueStoreError::Sled)?;
+        self.tree.flush().map_err(SledQueueStoreError::Sled)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, id: u64) -> Result<(), Self::Error> {
+        self.tree
+            .remove(id.to_be_bytes())
+            .map_err(SledQueueStoreError::Sled)?;
+        self.tree.flush().map_err(SledQueueStoreError::Sled)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SingleOwnerAccount;
+
+    use starknet_core::{chain_id, types::TransactionRequest};
+    use starknet_providers::{ChainFixture, SequencerGatewayProvider};
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    fn test_account() -> SingleOwnerAccount<SequencerGatewayProvider, LocalWallet> {
+        SingleOwnerAccount::new(
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            FieldElement::TWO,
+            chain_id::TESTNET,
+        )
+    }
+
+    fn test_call(selector: u64) -> Call {
+        Call {
+            to: FieldElement::ONE,
+            selector: selector.into(),
+            calldata: vec![FieldElement::TWO],
+        }
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_queue_survives_restart() {
+        let dir =
+            std::env::temp_dir().join(format!("starknet-accounts-tx-queue-{}", std::process::id()));
+        let account = test_account();
+
+        let mut queue =
+            TxQueue::open(&account, JsonFileQueueStore::new(&dir), Default::default()).unwrap();
+        assert_eq!(queue.enqueue(vec![test_call(1)]).unwrap(), 0);
+        assert_eq!(queue.enqueue(vec![test_call(2)]).unwrap(), 1);
+        assert_eq!(queue.enqueue(vec![test_call(3)]).unwrap(), 2);
+        queue.remove(1).unwrap();
+        drop(queue);
+
+        let mut queue =
+            TxQueue::open(&account, JsonFileQueueStore::new(&dir), Default::default()).unwrap();
+        assert_eq!(
+            queue
+                .entries()
+                .map(|entry| (entry.id, entry.calls[0].selector))
+                .collect::<Vec<_>>(),
+            [(0, FieldElement::ONE), (2, FieldElement::from(3u64))]
+        );
+        assert_eq!(queue.enqueue(vec![test_call(4)]).unwrap(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "sled")]
+    fn test_sled_queue_survives_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "starknet-accounts-tx-queue-sled-{}",
+            std::process::id()
+        ));
+        let account = test_account();
+
+        let mut queue = TxQueue::open(
+            &account,
+            SledQueueStore::open(&path).unwrap(),
+            Default::default(),
+        )
+        .unwrap();
+        assert_eq!(queue.enqueue(vec![test_call(1)]).unwrap(), 0);
+        assert_eq!(queue.enqueue(vec![test_call(2)]).unwrap(), 1);
+        assert_eq!(queue.enqueue(vec![test_call(3)]).unwrap(), 2);
+        queue.remove(1).unwrap();
+        drop(queue);
+
+        let mut queue = TxQueue::open(
+            &account,
+            SledQueueStore::open(&path).unwrap(),
+            Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            queue
+                .entries()
+                .map(|entry| (entry.id, entry.calls[0].selector))
+                .collect::<Vec<_>>(),
+            [(0, FieldElement::ONE), (2, FieldElement::from(3u64))]
+        );
+        assert_eq!(queue.enqueue(vec![test_call(4)]).unwrap(), 3);
+        drop(queue);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_queue_processes_in_order() {
+        let account = SingleOwnerAccount::new(
+            ChainFixture::new()
+                .account(FieldElement::TWO, FieldElement::ONE, FieldElement::ONE)
+                .nonce(FieldElement::TWO, FieldElement::from(5u64))
+                .build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            FieldElement::TWO,
+            chain_id::TESTNET,
+        );
+
+        let mut queue =
+            TxQueue::open(&account, MemoryQueueStore::new(), Default::default()).unwrap();
+        queue.enqueue(vec![test_call(1)]).unwrap();
+        queue.enqueue(vec![test_call(2)]).unwrap();
+
+        let submitted = match queue.process().await.unwrap()[..] {
+            [QueueEvent::Submitted {
+                id: 0,
+                transaction_hash,
+            }] => transaction_hash,
+            ref events => panic!("unexpected events: {events:?}"),
+        };
+        let events = queue.process().await.unwrap();
+        assert!(matches!(
+            events[..],
+            [
+                QueueEvent::Landed {
+                    id: 0,
+                    transaction_hash,
+                },
+                QueueEvent::Submitted { id: 1, .. },
+            ] if transaction_hash == submitted
+        ));
+
+        let nonces = account
+            .provider()
+            .submitted()
+            .into_iter()
+            .map(|tx| match tx {
+                TransactionRequest::InvokeFunction(tx) => tx.nonce,
+                _ => panic!("unexpected transaction type"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(nonces, [FieldElement::from(5u64), FieldElement::from(6u64)]);
+    }
+
+    /// Fails every save once `saves_left` runs out, as if the process crashed.
+    #[derive(Debug)]
+    struct CrashingStore {
+        inner: MemoryQueueStore,
+        saves_left: usize,
+    }
+
+    impl QueueStore for CrashingStore {
+        type Error = std::io::Error;
+
+        fn load(&mut self) -> Result<Vec<QueueEntry>, Self::Error> {
+            Ok(self.inner.load().unwrap())
+        }
+
+        fn save(&mut self, entry: &QueueEntry) -> Result<(), Self::Error> {
+            if self.saves_left == 0 {
+                return Err(std::io::Error::other("crashed"));
+            }
+            self.saves_left -= 1;
+            self.inner.save(entry).unwrap();
+            Ok(())
+        }
+
+        fn remove(&mut self, id: u64) -> Result<(), Self::Error> {
+            self.inner.remove(id).unwrap();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_crash_after_send() {
+        let account = SingleOwnerAccount::new(
+            ChainFixture::new()
+                .account(FieldElement::TWO, FieldElement::ONE, FieldElement::ONE)
+                .nonce(FieldElement::TWO, FieldElement::from(5u64))
+                .build(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            FieldElement::TWO,
+            chain_id::TESTNET,
+        );
+        let policy = ResubmissionPolicy {
+            polls_before_resubmission: 1,
+            ..Default::default()
+        };
+
+        // Saves the entry, its nonce and the attempt, then crashes once the attempt is sent
+        let store = CrashingStore {
+            inner: MemoryQueueStore::new(),
+            saves_left: 3,
+        };
+        let mut queue = TxQueue::open(&account, store, policy).unwrap();
+        queue.enqueue(vec![test_call(1)]).unwrap();
+        assert!(matches!(queue.process().await, Err(TxQueueError::Store(_))));
+        assert_eq!(account.provider().submitted().len(), 1);
+
+        // The attempt was saved before sending, so it's resubmitted with the same nonce instead
+        // of being sent again as a new transaction
+        let store = queue.store().inner.clone();
+        drop(queue);
+        let mut queue = TxQueue::open(&account, store, policy).unwrap();
+        assert_eq!(queue.entries().next().unwrap().attempts.len(), 1);
+        assert!(matches!(
+            queue.process().await.unwrap()[..],
+            [QueueEvent::Resubmitted {
+                id: 0,
+                attempt: 1,
+                ..
+            }]
+        ));
+
+        let nonces = account
+            .provider()
+            .submitted()
+            .into_iter()
+            .map(|tx| match tx {
+                TransactionRequest::InvokeFunction(tx) => tx.nonce,
+                _ => panic!("unexpected transaction type"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(nonces, [FieldElement::from(5u64), FieldElement::from(5u64)]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_is_invalid_nonce() {
+        assert!(is_invalid_nonce(
+            "Invalid transaction nonce. Expected: 6, got: 5. (InvalidTransactionNonce)"
+        ));
+        assert!(is_invalid_nonce("INVALID_TRANSACTION_NONCE"));
+        assert!(!is_invalid_nonce("Actual fee exceeded max fee"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_queue_entry_serde() {
+        let entry = QueueEntry {
+            id: 7,
+            calls: vec![test_call(1)],
+            nonce: Some(FieldElement::from(5u64)),
+            max_fee: Some(FieldElement::from(1000u64)),
+            attempts: vec![FieldElement::from(0xabcu64)],
+            polls_since_submission: 2,
+        };
+
+        let entry: QueueEntry =
+            serde_json::from_str(&serde_json::to_string(&entry).unwrap()).unwrap();
+        assert_eq!(entry.nonce, Some(FieldElement::from(5u64)));
+        assert_eq!(entry.max_fee, Some(FieldElement::from(1000u64)));
+        assert_eq!(entry.attempts, [FieldElement::from(0xabcu64)]);
+        assert_eq!(entry.calls[0].calldata, [FieldElement::TWO]);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::SingleOwnerAccount;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use starknet_core::{
+    serde::unsigned_field_element::{UfeHex, UfeHexOption},
+    types::FieldElement,
+};
+use starknet_providers::Provider;
+use starknet_signers::{KeystoreError, LocalWallet, SigningKey};
+use std::path::{Path, PathBuf};
+
+/// Name of the directory wallets are stored in under the home directory, as suggested by
+/// `starkli`.
+const DEFAULT_DIR: &str = ".starkli-wallets";
+
+const ACCOUNT_FILE: &str = "account.json";
+
+const KEYSTORE_FILE: &str = "keystore.json";
+
+const ACCOUNT_CONFIG_VERSION: u64 = 1;
+
+/// Named accounts and their keystores, stored on disk in the layout used with `starkli`.
+///
+/// Each wallet is a directory named after it, holding the account config as `account.json` and
+/// the encrypted signing key as `keystore.json`. Both files can be passed to `starkli` with
+/// `--account` and `--keystore`.
+#[derive(Debug, Clone)]
+pub struct WalletStore {
+    root: PathBuf,
+}
+
+/// The `starkli` account config, with the chain the account is deployed on.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountConfig {
+    pub version: u64,
+    pub variant: AccountVariant,
+    pub deployment: DeploymentStatus,
+    /// Not part of the `starkli` format, which ignores it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "UfeHexOption")]
+    pub chain_id: Option<FieldElement>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountVariant {
+    OpenZeppelin {
+        version: u64,
+        #[serde_as(as = "UfeHex")]
+        public_key: FieldElement,
+        #[serde(default)]
+        legacy: bool,
+    },
+    Argent {
+        version: u64,
+        #[serde_as(as = "UfeHex")]
+        implementation: FieldElement,
+        #[serde_as(as = "UfeHex")]
+        signer: FieldElement,
+        #[serde_as(as = "UfeHex")]
+        guardian: FieldElement,
+    },
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeploymentStatus {
+    Undeployed {
+        #[serde_as(as = "UfeHex")]
+        class_hash: FieldElement,
+        #[serde_as(as = "UfeHex")]
+        salt: FieldElement,
+    },
+    Deployed {
+        #[serde_as(as = "UfeHex")]
+        class_hash: FieldElement,
+        #[serde_as(as = "UfeHex")]
+        address: FieldElement,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WalletStoreError {
+    #[error("invalid wallet name: {0:?}")]
+    InvalidName(String),
+    #[error("wallet not found: {0}")]
+    NotFound(String),
+    #[error("wallet {0} is not deployed")]
+    NotDeployed(String),
+    #[error("wallet {0} has no chain id")]
+    MissingChainId(String),
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error(transparent)]
+    Json(serde_json::Error),
+    #[error(transparent)]
+    Keystore(KeystoreError),
+}
+
+impl WalletStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The store at `~/.starkli-wallets`, or `None` if the home directory is unknown.
+    pub fn default_location() -> Option<Self> {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+        Some(Self::new(Path::new(&home).join(DEFAULT_DIR)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The names of the wallets with an account config, in alphabetical order.
+    pub fn list(&self) -> Result<Vec<String>, WalletStoreError> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(WalletStoreError::Io(err)),
+        };
+
+        let mut names = vec![];
+        for entry in entries {
+            let entry = entry.map_err(WalletStoreError::Io)?;
+            if entry.path().join(ACCOUNT_FILE).is_file() {
+                if let Ok(name) = entry.file_name().into_string() {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    }
+
+    pub fn account_path(&self, name: &str) -> Result<PathBuf, WalletStoreError> {
+        Ok(self.wallet_dir(name)?.join(ACCOUNT_FILE))
+    }
+
+    pub fn keystore_path(&self, name: &str) -> Result<PathBuf, WalletStoreError> {
+        Ok(self.wallet_dir(name)?.join(KEYSTORE_FILE))
+    }
+
+    pub fn load_account_config(&self, name: &str) -> Result<AccountConfig, WalletStoreError> {
+        let json =
+            std::fs::read_to_string(self.account_path(name)?).map_err(|err| match err.kind() {
+                std::io::ErrorKind::NotFound => WalletStoreError::NotFound(name.to_owned()),
+                _ => WalletStoreError::Io(err),
+            })?;
+        serde_json::from_str(&json).map_err(WalletStoreError::Json)
+    }
+
+    /// Saves the account config of wallet `name`, creating the wallet if it doesn't exist.
+    pub fn save_account_config(
+        &self,
+        name: &str,
+        config: &AccountConfig,
+    ) -> Result<(), WalletStoreError> {
+        let dir = self.wallet_dir(name)?;
+        std::fs::create_dir_all(&dir).map_err(WalletStoreError::Io)?;
+
+        let json = serde_json::to_string_pretty(config).map_err(WalletStoreError::Json)?;
+        std::fs::write(dir.join(ACCOUNT_FILE), json).map_err(WalletStoreError::Io)
+    }
+
+    /// Encrypts `signing_key` with `password` as the keystore of wallet `name`.
+    pub fn save_keystore(
+        &self,
+        name: &str,
+        signing_key: &SigningKey,
+        password: &str,
+    ) -> Result<(), WalletStoreError> {
+        let dir = self.wallet_dir(name)?;
+        std::fs::create_dir_all(&dir).map_err(WalletStoreError::Io)?;
+
+        signing_key
+            .save_as_keystore(dir.join(KEYSTORE_FILE), password)
+            .map_err(WalletStoreError::Keystore)
+    }
+
+    pub fn load_signer(&self, name: &str, password: &str) -> Result<LocalWallet, WalletStoreError> {
+        let path = self.keystore_path(name)?;
+        if !path.is_file() {
+            return Err(WalletStoreError::NotFound(name.to_owned()));
+        }
+
+        SigningKey::from_keystore(path, password)
+            .map(LocalWallet::from)
+            .map_err(WalletStoreError::Keystore)
+    }
+
+    /// Loads wallet `name` as an account connected to `provider`, decrypting its keystore with
+    /// `password`. The account must be deployed, on the chain saved in its config.
+    pub fn load_account<P>(
+        &self,
+        name: &str,
+        provider: P,
+        password: &str,
+    ) -> Result<SingleOwnerAccount<P, LocalWallet>, WalletStoreError>
+    where
+        P: Provider + Sync + Send,
+    {
+        let config = self.load_account_config(name)?;
+        let address = match config.deployment {
+            DeploymentStatus::Deployed { address, .. } => address,
+            DeploymentStatus::Undeployed { .. } => {
+                return Err(WalletStoreError::NotDeployed(name.to_owned()))
+            }
+        };
+        let chain_id = config
+            .chain_id
+            .ok_or_else(|| WalletStoreError::MissingChainId(name.to_owned()))?;
+        let signer = self.load_signer(name, password)?;
+
+        Ok(SingleOwnerAccount::new(provider, signer, address, chain_id))
+    }
+
+    /// Deletes wallet `name`, including its keystore.
+    pub fn remove(&self, name: &str) -> Result<(), WalletStoreError> {
+        std::fs::remove_dir_all(self.wallet_dir(name)?).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => WalletStoreError::NotFound(name.to_owned()),
+            _ => WalletStoreError::Io(err),
+        })
+    }
+
+    /// The directory of wallet `name`, rejecting names that would escape the store.
+    fn wallet_dir(&self, name: &str) -> Result<PathBuf, WalletStoreError> {
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(WalletStoreError::InvalidName(name.to_owned()));
+        }
+
+        Ok(self.root.join(name))
+    }
+}
+
+impl AccountConfig {
+    /// Config of an OpenZeppelin account with `public_key`, deployed at `address`.
+    pub fn open_zeppelin(
+        public_key: FieldElement,
+        class_hash: FieldElement,
+        address: FieldElement,
+        chain_id: FieldElement,
+    ) -> Self {
+        Self {
+            version: ACCOUNT_CONFIG_VERSION,
+            variant: AccountVariant::OpenZeppelin {
+                version: 1,
+                public_key,
+                legacy: false,
+            },
+            deployment: DeploymentStatus::Deployed {
+                class_hash,
+                address,
+            },
+            chain_id: Some(chain_id),
+        }
+    }
+
+    /// The address of the account, if deployed.
+    pub fn address(&self) -> Option<FieldElement> {
+        match self.deployment {
+            DeploymentStatus::Deployed { address, .. } => Some(address),
+            DeploymentStatus::Undeployed { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::chain_id;
+    use starknet_providers::SequencerGatewayProvider;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_deser_starkli_account() {
+        let config: AccountConfig = serde_json::from_str(
+            r#"{
+                "version": 1,
+                "variant": {
+                    "type": "open_zeppelin",
+                    "version": 1,
+                    "public_key": "0x7e52885445756b313ea16849145363ccb73fb4ab0440dbac333cf9d13de82b9"
+                },
+                "deployment": {
+                    "status": "deployed",
+                    "class_hash": "0x48dd59fabc729a5db3afdf649ecaf388e931647ab2f53ca3c6183fa480aa292",
+                    "address": "0x7e00d496e324876bbc8531f2d9a82bf154d1a04a50218ee74cdd372f75a551a"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.address(),
+            Some(
+                FieldElement::from_hex_be(
+                    "0x7e00d496e324876bbc8531f2d9a82bf154d1a04a50218ee74cdd372f75a551a"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(config.chain_id, None);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_wallet_store_round_trip() {
+        let store = WalletStore::new(
+            std::env::temp_dir().join(format!("starknet-accounts-wallets-{}", std::process::id())),
+        );
+        let signing_key = SigningKey::from_secret_scalar(FieldElement::from(1234u64));
+        let config = AccountConfig::open_zeppelin(
+            signing_key.verifying_key().scalar(),
+            FieldElement::ONE,
+            FieldElement::TWO,
+            chain_id::TESTNET,
+        );
+
+        store.save_account_config("deployer", &config).unwrap();
+        store
+            .save_keystore("deployer", &signing_key, "password")
+            .unwrap();
+
+        assert_eq!(store.list().unwrap(), ["deployer"]);
+        assert_eq!(store.load_account_config("deployer").unwrap(), config);
+        let account = store
+            .load_account(
+                "deployer",
+                SequencerGatewayProvider::starknet_alpha_goerli(),
+                "password",
+            )
+            .unwrap();
+        assert_eq!(crate::Account::address(&account), FieldElement::TWO);
+
+        assert!(matches!(
+            store.load_account_config("missing"),
+            Err(WalletStoreError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.load_account_config("../deployer"),
+            Err(WalletStoreError::InvalidName(_))
+        ));
+
+        store.remove("deployer").unwrap();
+        assert!(store.list().unwrap().is_empty());
+        std::fs::remove_dir(store.root()).unwrap();
+    }
+}
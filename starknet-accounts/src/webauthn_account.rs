@@ -0,0 +1,136 @@
+use crate::{
+    Account, ConnectedAccount, ExecutionEncoding, RawDeclaration, RawExecution,
+    WebauthnEncodingError,
+};
+
+use async_trait::async_trait;
+use starknet_core::types::{contract_artifact::ComputeClassHashError, FieldElement};
+use starknet_providers::Provider;
+use starknet_signers::webauthn::{WebauthnAuthenticator, WebauthnSigner, WebauthnSignerError};
+
+/// An account controlled by a WebAuthn credential such as a passkey. Transaction hashes are used
+/// as assertion challenges, and signatures are encoded as
+/// [WebauthnAssertion](crate::WebauthnAssertion) felts.
+#[derive(Debug)]
+pub struct WebauthnAccount<P, A>
+where
+    P: Provider + Send,
+    A: WebauthnAuthenticator + Send,
+{
+    provider: P,
+    signer: WebauthnSigner<A>,
+    address: FieldElement,
+    chain_id: FieldElement,
+    encoding: ExecutionEncoding,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignError<A> {
+    #[error(transparent)]
+    Signer(WebauthnSignerError<A>),
+    #[error(transparent)]
+    Encoding(WebauthnEncodingError),
+    #[error(transparent)]
+    ClassHash(ComputeClassHashError),
+}
+
+impl<P, A> WebauthnAccount<P, A>
+where
+    P: Provider + Sync + Send,
+    A: WebauthnAuthenticator + Sync + Send,
+{
+    pub fn new(
+        provider: P,
+        signer: WebauthnSigner<A>,
+        address: FieldElement,
+        chain_id: FieldElement,
+    ) -> Self {
+        Self {
+            provider,
+            signer,
+            address,
+            chain_id,
+            // WebAuthn accounts are all Cairo 1 contracts
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    /// Sets the `__execute__` calldata encoding expected by the account contract. Defaults to
+    /// [ExecutionEncoding::New].
+    pub fn set_execution_encoding(&mut self, encoding: ExecutionEncoding) -> &mut Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn signer(&self) -> &WebauthnSigner<A> {
+        &self.signer
+    }
+
+    async fn sign_hash(
+        &self,
+        hash: &FieldElement,
+    ) -> Result<Vec<FieldElement>, SignError<A::Error>> {
+        self.signer
+            .sign_hash(hash)
+            .await
+            .map_err(SignError::Signer)?
+            .to_felts()
+            .map_err(SignError::Encoding)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P, A> Account for WebauthnAccount<P, A>
+where
+    P: Provider + Sync + Send,
+    A: WebauthnAuthenticator + Sync + Send,
+{
+    type SignError = SignError<A::Error>;
+
+    fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    fn chain_id(&self) -> FieldElement {
+        self.chain_id
+    }
+
+    fn execution_encoding(&self) -> ExecutionEncoding {
+        self.encoding
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        true
+    }
+
+    async fn sign_execution(
+        &self,
+        execution: &RawExecution,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, self.encoding);
+        self.sign_hash(&tx_hash).await
+    }
+
+    async fn sign_declaration(
+        &self,
+        declaration: &RawDeclaration,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let tx_hash = declaration
+            .transaction_hash(self.chain_id, self.address)
+            .map_err(SignError::ClassHash)?;
+        self.sign_hash(&tx_hash).await
+    }
+}
+
+impl<P, A> ConnectedAccount for WebauthnAccount<P, A>
+where
+    P: Provider + Sync + Send,
+    A: WebauthnAuthenticator + Sync + Send,
+{
+    type Provider = P;
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+}
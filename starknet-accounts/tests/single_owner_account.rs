@@ -1,72 +1,50 @@
-use starknet_accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
+use starknet_accounts::{Account, Call, ConnectedAccount};
 use starknet_core::{
-    chain_id,
-    types::{AddTransactionResultCode, ContractArtifact, FieldElement},
+    types::{AddTransactionResultCode, ContractArtifact, FieldElement, TransactionStatus},
     utils::get_selector_from_name,
 };
-use starknet_providers::SequencerGatewayProvider;
-use starknet_signers::{LocalWallet, SigningKey};
+use starknet_devnet::Devnet;
+use starknet_providers::Provider;
 use std::sync::Arc;
 
+/// Transfers `amount` of the fee token from the predeployed account `0` to account `1`.
+fn transfer_call(devnet: &Devnet, amount: &str) -> Call {
+    Call {
+        to: devnet.fee_token_address(),
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![
+            devnet.predeployed_accounts()[1].address,
+            FieldElement::from_dec_str(amount).unwrap(),
+            FieldElement::ZERO,
+        ],
+    }
+}
+
 #[tokio::test]
 async fn can_get_nonce() {
-    let provider = SequencerGatewayProvider::starknet_alpha_goerli();
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-        FieldElement::from_hex_be(
-            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        )
-        .unwrap(),
-    ));
-    let address = FieldElement::from_hex_be(
-        "02da37a17affbd2df4ede7120dae305ec36dfe94ec96a8c3f49bbf59f4e9a9fa",
-    )
-    .unwrap();
-
-    let account = SingleOwnerAccount::new(provider, signer, address, chain_id::TESTNET);
-
-    assert_ne!(account.get_nonce().await.unwrap(), FieldElement::ZERO);
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
+
+    assert_eq!(account.get_nonce().await.unwrap(), FieldElement::ZERO);
+
+    account
+        .execute(vec![transfer_call(&devnet, "1000")])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(account.get_nonce().await.unwrap(), FieldElement::ONE);
 }
 
 #[tokio::test]
 async fn can_estimate_fee() {
-    let provider = SequencerGatewayProvider::starknet_alpha_goerli();
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-        FieldElement::from_hex_be(
-            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        )
-        .unwrap(),
-    ));
-    let address = FieldElement::from_hex_be(
-        "02da37a17affbd2df4ede7120dae305ec36dfe94ec96a8c3f49bbf59f4e9a9fa",
-    )
-    .unwrap();
-    let tst_token_address = FieldElement::from_hex_be(
-        "07394cbe418daa16e42b87ba67372d4ab4a5df0b05c6e554d158458ce245bc10",
-    )
-    .unwrap();
-
-    let account = SingleOwnerAccount::new(provider, signer, address, chain_id::TESTNET);
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
 
     let fee_estimate = account
         .execute(vec![
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("1000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("2000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
+            transfer_call(&devnet, "1000"),
+            transfer_call(&devnet, "2000"),
         ])
         .estimate_fee()
         .await
@@ -75,48 +53,36 @@ async fn can_estimate_fee() {
     assert!(fee_estimate.overall_fee > 0);
 }
 
+#[tokio::test]
+async fn can_estimate_fee_bulk() {
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
+
+    let fee_estimates = account
+        .estimate_fee_bulk(&[
+            account.execute(vec![transfer_call(&devnet, "1000")]),
+            account.execute(vec![transfer_call(&devnet, "2000")]),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(fee_estimates.len(), 2);
+    assert!(fee_estimates
+        .iter()
+        .all(|estimate| estimate.overall_fee > 0));
+}
+
 #[tokio::test]
 async fn can_simulate_execution() {
-    // Simulates the tx in `can_execute_tst_mint()` without actually sending
-
-    let provider = SequencerGatewayProvider::starknet_alpha_goerli();
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-        FieldElement::from_hex_be(
-            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        )
-        .unwrap(),
-    ));
-    let address = FieldElement::from_hex_be(
-        "02da37a17affbd2df4ede7120dae305ec36dfe94ec96a8c3f49bbf59f4e9a9fa",
-    )
-    .unwrap();
-    let tst_token_address = FieldElement::from_hex_be(
-        "07394cbe418daa16e42b87ba67372d4ab4a5df0b05c6e554d158458ce245bc10",
-    )
-    .unwrap();
-
-    let account = SingleOwnerAccount::new(provider, signer, address, chain_id::TESTNET);
+    // Simulates the tx in `can_execute_transfers()` without actually sending
+
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
 
     let result = account
         .execute(vec![
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("1000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("2000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
+            transfer_call(&devnet, "1000"),
+            transfer_call(&devnet, "2000"),
         ])
         .simulate()
         .await
@@ -126,76 +92,34 @@ async fn can_simulate_execution() {
 }
 
 #[tokio::test]
-async fn can_execute_tst_mint() {
-    // This test case is not very useful as the sequencer will always respond with
-    // `TransactionReceived` even if the transaction will eventually fail, just like how
-    // `eth_sendRawTransaction` always responds with success except for insufficient balance. So it
-    // can't really test the execution is successful unless we:
-    //   - change to use a local testing network similar to Ganacha for Ethereum; or
-    //   - poll the transaction hash until it's processed.
-
-    let provider = SequencerGatewayProvider::starknet_alpha_goerli();
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-        FieldElement::from_hex_be(
-            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        )
-        .unwrap(),
-    ));
-    let address = FieldElement::from_hex_be(
-        "02da37a17affbd2df4ede7120dae305ec36dfe94ec96a8c3f49bbf59f4e9a9fa",
-    )
-    .unwrap();
-    let tst_token_address = FieldElement::from_hex_be(
-        "07394cbe418daa16e42b87ba67372d4ab4a5df0b05c6e554d158458ce245bc10",
-    )
-    .unwrap();
-
-    let account = SingleOwnerAccount::new(provider, signer, address, chain_id::TESTNET);
+async fn can_execute_transfers() {
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
 
     let result = account
         .execute(vec![
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("1000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
-            Call {
-                to: tst_token_address,
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: vec![
-                    address,
-                    FieldElement::from_dec_str("2000000000000000000000").unwrap(),
-                    FieldElement::ZERO,
-                ],
-            },
+            transfer_call(&devnet, "1000"),
+            transfer_call(&devnet, "2000"),
         ])
         .send()
         .await
         .unwrap();
 
     assert_eq!(result.code, AddTransactionResultCode::TransactionReceived);
+
+    // Devnet executes transactions as soon as they're received
+    let receipt = account
+        .provider()
+        .get_transaction_receipt(result.transaction_hash)
+        .await
+        .unwrap();
+    assert_eq!(receipt.status, TransactionStatus::AcceptedOnL2);
 }
 
 #[tokio::test]
 async fn can_declare_oz_account_contract() {
-    // This test case is not very useful, same as `can_execute_tst_mint` above.
-
-    let provider = SequencerGatewayProvider::starknet_alpha_goerli();
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-        FieldElement::from_hex_be(
-            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        )
-        .unwrap(),
-    ));
-    let address = FieldElement::from_hex_be(
-        "02da37a17affbd2df4ede7120dae305ec36dfe94ec96a8c3f49bbf59f4e9a9fa",
-    )
-    .unwrap();
-    let account = SingleOwnerAccount::new(provider, signer, address, chain_id::TESTNET);
+    let devnet = Devnet::spawn().await.unwrap();
+    let account = devnet.account(0);
 
     let contract_artifact: ContractArtifact =
         serde_json::from_str(include_str!("../test-data/artifacts/oz_account.txt")).unwrap();
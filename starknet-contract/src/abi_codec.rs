@@ -0,0 +1,866 @@
+use serde_json::{Map, Value};
+use starknet_accounts::Call;
+use starknet_core::{
+    types::{AbiEntry, FieldElement},
+    utils::get_selector_from_name,
+};
+use std::collections::HashMap;
+
+/// Encodes calldata and decodes outputs and events of a contract from its ABI at runtime, for
+/// tools that only learn about contracts once running, such as explorers and generic CLIs.
+///
+/// Values are represented as JSON:
+///
+/// - `felt` values are numbers, or strings in hex (`0x`-prefixed) or decimal. They're decoded
+///   as hex strings.
+/// - Structs are objects keyed by member name, or arrays of members in order. They're decoded
+///   as objects. `Uint256` can also be encoded from a single number and is split automatically.
+/// - Tuples are arrays, and named tuples can also be objects. They're decoded as arrays.
+/// - Arrays follow the Cairo convention of a `{name}_len` argument followed by a `{name}`
+///   pointer. Only `{name}` is given as an array, and the length is derived from it.
+///
+/// Arguments are given either as an object keyed by argument name, or as an array in order.
+#[derive(Debug, Clone)]
+pub struct AbiCodec {
+    functions: HashMap<String, Vec<(String, String)>>,
+    outputs: HashMap<String, Vec<(String, String)>>,
+    constructor: Vec<(String, String)>,
+    l1_handlers: HashMap<String, Vec<(String, String)>>,
+    structs: HashMap<String, Vec<(String, String)>>,
+    events: HashMap<FieldElement, AbiEvent>,
+}
+
+/// An event decoded with [AbiCodec::decode_event].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub name: String,
+    /// An object with all fields of the event, from both keys and data.
+    pub fields: Value,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AbiCodecError {
+    #[error("function not found in ABI: {0}")]
+    UnknownFunction(String),
+    #[error("event not found in ABI: {0:#064x}")]
+    UnknownEvent(FieldElement),
+    #[error("type not found in ABI: {0}")]
+    UnknownType(String),
+    #[error("array {0} is not preceded by a {0}_len argument")]
+    MissingArrayLength(String),
+    #[error("missing value for {0}")]
+    MissingValue(String),
+    #[error("invalid value for {name} of type {r#type}")]
+    InvalidValue { name: String, r#type: String },
+    #[error("expected {expected} arguments but got {actual}")]
+    ArgumentCountMismatch { expected: usize, actual: usize },
+    #[error("not enough elements to decode {0}")]
+    UnexpectedEnd(String),
+    #[error("{0} unexpected elements left after decoding")]
+    TrailingElements(usize),
+}
+
+#[derive(Debug, Clone)]
+struct AbiEvent {
+    name: String,
+    keys: Vec<(String, String)>,
+    data: Vec<(String, String)>,
+}
+
+/// A parsed Cairo 0 type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AbiType {
+    Felt,
+    Struct(String),
+    Tuple(Vec<(Option<String>, AbiType)>),
+    Pointer(Box<AbiType>),
+}
+
+impl AbiCodec {
+    pub fn new(abi: &[AbiEntry]) -> Self {
+        let mut codec = Self {
+            functions: HashMap::new(),
+            outputs: HashMap::new(),
+            constructor: vec![],
+            l1_handlers: HashMap::new(),
+            structs: HashMap::new(),
+            events: HashMap::new(),
+        };
+
+        for entry in abi.iter() {
+            match entry {
+                AbiEntry::Constructor(constructor) => {
+                    codec.constructor =
+                        params(&constructor.inputs, |input| (&input.name, &input.r#type));
+                }
+                AbiEntry::Function(function) => {
+                    codec.functions.insert(
+                        function.name.clone(),
+                        params(&function.inputs, |input| (&input.name, &input.r#type)),
+                    );
+                    codec.outputs.insert(
+                        function.name.clone(),
+                        params(&function.outputs, |output| (&output.name, &output.r#type)),
+                    );
+                }
+                AbiEntry::L1Handler(handler) => {
+                    let inputs = params(&handler.inputs, |input| (&input.name, &input.r#type));
+                    codec
+                        .l1_handlers
+                        .insert(handler.name.clone(), inputs[1.min(inputs.len())..].to_vec());
+                    codec.functions.insert(handler.name.clone(), inputs);
+                }
+                AbiEntry::Struct(abi_struct) => {
+                    let mut members = abi_struct.members.clone();
+                    members.sort_by_key(|member| member.offset);
+                    codec.structs.insert(
+                        abi_struct.name.clone(),
+                        params(&members, |member| (&member.name, &member.r#type)),
+                    );
+                }
+                AbiEntry::Event(event) => {
+                    if let Ok(selector) = get_selector_from_name(&event.name) {
+                        codec.events.insert(
+                            selector,
+                            AbiEvent {
+                                name: event.name.clone(),
+                                keys: params(&event.keys, |key| (&key.name, &key.r#type)),
+                                data: params(&event.data, |data| (&data.name, &data.r#type)),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        codec
+    }
+
+    /// Encodes the calldata for calling `function` with `args`.
+    pub fn encode_calldata(
+        &self,
+        function: &str,
+        args: &Value,
+    ) -> Result<Vec<FieldElement>, AbiCodecError> {
+        let inputs = self
+            .functions
+            .get(function)
+            .ok_or_else(|| AbiCodecError::UnknownFunction(function.to_owned()))?;
+        self.encode_params(inputs, args)
+    }
+
+    /// Builds a [Call] to `function` on the contract at `to` with `args`.
+    pub fn encode_call(
+        &self,
+        to: FieldElement,
+        function: &str,
+        args: &Value,
+    ) -> Result<Call, AbiCodecError> {
+        Ok(Call {
+            to,
+            selector: get_selector_from_name(function)
+                .map_err(|_| AbiCodecError::UnknownFunction(function.to_owned()))?,
+            calldata: self.encode_calldata(function, args)?,
+        })
+    }
+
+    /// Encodes the constructor calldata for deploying the contract with `args`.
+    pub fn encode_constructor(&self, args: &Value) -> Result<Vec<FieldElement>, AbiCodecError> {
+        self.encode_params(&self.constructor, args)
+    }
+
+    /// Encodes the payload of an L1 to L2 message for the L1 handler `handler` with `args`. The
+    /// first input of L1 handlers is the L1 sender address, which is provided by the sequencer
+    /// rather than the payload, so `args` only holds the remaining inputs.
+    pub fn encode_l1_handler_payload(
+        &self,
+        handler: &str,
+        args: &Value,
+    ) -> Result<Vec<FieldElement>, AbiCodecError> {
+        let inputs = self
+            .l1_handlers
+            .get(handler)
+            .ok_or_else(|| AbiCodecError::UnknownFunction(handler.to_owned()))?;
+        self.encode_params(inputs, args)
+    }
+
+    /// Decodes the calldata of a call to `function` into an object keyed by argument name.
+    pub fn decode_calldata(
+        &self,
+        function: &str,
+        calldata: &[FieldElement],
+    ) -> Result<Value, AbiCodecError> {
+        let inputs = self
+            .functions
+            .get(function)
+            .ok_or_else(|| AbiCodecError::UnknownFunction(function.to_owned()))?;
+
+        let mut elements = calldata.iter();
+        let decoded = self.decode_params(inputs, &mut elements)?;
+        ensure_consumed(elements)?;
+
+        Ok(decoded)
+    }
+
+    /// The names of the functions and L1 handlers of the contract.
+    pub fn function_names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(|name| name.as_str())
+    }
+
+    /// Decodes the result of calling `function` into an object keyed by output name.
+    pub fn decode_output(
+        &self,
+        function: &str,
+        output: &[FieldElement],
+    ) -> Result<Value, AbiCodecError> {
+        let outputs = self
+            .outputs
+            .get(function)
+            .ok_or_else(|| AbiCodecError::UnknownFunction(function.to_owned()))?;
+
+        let mut elements = output.iter();
+        let decoded = self.decode_params(outputs, &mut elements)?;
+        ensure_consumed(elements)?;
+
+        Ok(decoded)
+    }
+
+    /// Decodes an event emitted by the contract, identified by the selector of its name in the
+    /// first key.
+    pub fn decode_event(
+        &self,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Result<DecodedEvent, AbiCodecError> {
+        let selector = keys
+            .first()
+            .ok_or_else(|| AbiCodecError::UnexpectedEnd("event selector".into()))?;
+        let event = self
+            .events
+            .get(selector)
+            .ok_or(AbiCodecError::UnknownEvent(*selector))?;
+
+        let mut key_elements = keys[1..].iter();
+        let mut fields = match self.decode_params(&event.keys, &mut key_elements)? {
+            Value::Object(fields) => fields,
+            _ => unreachable!(),
+        };
+        ensure_consumed(key_elements)?;
+
+        let mut data_elements = data.iter();
+        if let Value::Object(data_fields) = self.decode_params(&event.data, &mut data_elements)? {
+            fields.extend(data_fields);
+        }
+        ensure_consumed(data_elements)?;
+
+        Ok(DecodedEvent {
+            name: event.name.clone(),
+            fields: Value::Object(fields),
+        })
+    }
+
+    /// The number of consecutive storage slots taken by a value of type `type_name`. Pointers
+    /// can't be stored and are rejected.
+    pub fn storage_size(&self, type_name: &str) -> Result<usize, AbiCodecError> {
+        self.type_size(&AbiType::parse(type_name))
+    }
+
+    /// Decodes a value of type `type_name` read from consecutive storage slots.
+    pub fn decode_storage(
+        &self,
+        type_name: &str,
+        slots: &[FieldElement],
+    ) -> Result<Value, AbiCodecError> {
+        let mut elements = slots.iter();
+        let decoded = self.decode_value(type_name, &AbiType::parse(type_name), &mut elements)?;
+        ensure_consumed(elements)?;
+
+        Ok(decoded)
+    }
+
+    fn type_size(&self, abi_type: &AbiType) -> Result<usize, AbiCodecError> {
+        match abi_type {
+            AbiType::Felt => Ok(1),
+            AbiType::Pointer(_) => Err(AbiCodecError::UnknownType(abi_type.to_string())),
+            AbiType::Tuple(members) => members
+                .iter()
+                .map(|(_, member_type)| self.type_size(member_type))
+                .sum(),
+            AbiType::Struct(struct_name) => self
+                .structs
+                .get(struct_name)
+                .ok_or_else(|| AbiCodecError::UnknownType(struct_name.clone()))?
+                .iter()
+                .map(|(_, member_type)| self.type_size(&AbiType::parse(member_type)))
+                .sum(),
+        }
+    }
+
+    fn encode_params(
+        &self,
+        params: &[(String, String)],
+        args: &Value,
+    ) -> Result<Vec<FieldElement>, AbiCodecError> {
+        let explicit = explicit_params(params)?;
+
+        let values: Vec<&Value> = match args {
+            Value::Object(args) => explicit
+                .iter()
+                .map(|(name, _)| {
+                    args.get(*name)
+                        .ok_or_else(|| AbiCodecError::MissingValue(name.to_string()))
+                })
+                .collect::<Result<_, _>>()?,
+            Value::Array(args) if args.len() == explicit.len() => args.iter().collect(),
+            Value::Array(args) => {
+                return Err(AbiCodecError::ArgumentCountMismatch {
+                    expected: explicit.len(),
+                    actual: args.len(),
+                })
+            }
+            Value::Null if explicit.is_empty() => vec![],
+            _ => {
+                return Err(AbiCodecError::InvalidValue {
+                    name: "arguments".into(),
+                    r#type: "object or array".into(),
+                })
+            }
+        };
+
+        let mut encoded = vec![];
+        for ((name, abi_type), value) in explicit.iter().zip(values) {
+            self.encode_value(name, abi_type, value, &mut encoded)?;
+        }
+        Ok(encoded)
+    }
+
+    fn encode_value(
+        &self,
+        name: &str,
+        abi_type: &AbiType,
+        value: &Value,
+        encoded: &mut Vec<FieldElement>,
+    ) -> Result<(), AbiCodecError> {
+        let invalid = || AbiCodecError::InvalidValue {
+            name: name.to_owned(),
+            r#type: abi_type.to_string(),
+        };
+
+        match abi_type {
+            AbiType::Felt => encoded.push(parse_felt(value).ok_or_else(invalid)?),
+            AbiType::Pointer(element_type) => {
+                let elements = value.as_array().ok_or_else(invalid)?;
+                encoded.push(elements.len().into());
+                for element in elements.iter() {
+                    self.encode_value(name, element_type, element, encoded)?;
+                }
+            }
+            AbiType::Tuple(members) => {
+                let values = match value {
+                    Value::Array(values) if values.len() == members.len() => {
+                        values.iter().collect::<Vec<_>>()
+                    }
+                    Value::Object(values) => members
+                        .iter()
+                        .map(|(member, _)| member.as_ref().and_then(|member| values.get(member)))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(invalid)?,
+                    _ => return Err(invalid()),
+                };
+                for ((_, member_type), value) in members.iter().zip(values) {
+                    self.encode_value(name, member_type, value, encoded)?;
+                }
+            }
+            AbiType::Struct(struct_name) => {
+                let members = self
+                    .structs
+                    .get(struct_name)
+                    .ok_or_else(|| AbiCodecError::UnknownType(struct_name.clone()))?;
+
+                if struct_name == "Uint256" && !value.is_object() && !value.is_array() {
+                    let [low, high] = parse_u256(value).ok_or_else(invalid)?;
+                    encoded.push(low);
+                    encoded.push(high);
+                    return Ok(());
+                }
+
+                let values = match value {
+                    Value::Object(values) => members
+                        .iter()
+                        .map(|(member, _)| {
+                            values
+                                .get(member)
+                                .ok_or_else(|| AbiCodecError::MissingValue(member.clone()))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Value::Array(values) if values.len() == members.len() => {
+                        values.iter().collect()
+                    }
+                    _ => return Err(invalid()),
+                };
+                for ((member, member_type), value) in members.iter().zip(values) {
+                    self.encode_value(member, &AbiType::parse(member_type), value, encoded)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_params<'a, I>(
+        &self,
+        params: &[(String, String)],
+        elements: &mut I,
+    ) -> Result<Value, AbiCodecError>
+    where
+        I: Iterator<Item = &'a FieldElement>,
+    {
+        let mut decoded = Map::new();
+        for (name, abi_type) in explicit_params(params)?.into_iter() {
+            decoded.insert(
+                name.to_owned(),
+                self.decode_value(name, &abi_type, elements)?,
+            );
+        }
+        Ok(Value::Object(decoded))
+    }
+
+    fn decode_value<'a, I>(
+        &self,
+        name: &str,
+        abi_type: &AbiType,
+        elements: &mut I,
+    ) -> Result<Value, AbiCodecError>
+    where
+        I: Iterator<Item = &'a FieldElement>,
+    {
+        match abi_type {
+            AbiType::Felt => {
+                let felt = elements
+                    .next()
+                    .ok_or_else(|| AbiCodecError::UnexpectedEnd(name.to_owned()))?;
+                Ok(Value::String(format!("{felt:#x}")))
+            }
+            AbiType::Pointer(element_type) => {
+                let len = elements
+                    .next()
+                    .and_then(|len| u64::try_from(*len).ok())
+                    .ok_or_else(|| AbiCodecError::UnexpectedEnd(name.to_owned()))?;
+                (0..len)
+                    .map(|_| self.decode_value(name, element_type, elements))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array)
+            }
+            AbiType::Tuple(members) => members
+                .iter()
+                .map(|(_, member_type)| self.decode_value(name, member_type, elements))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            AbiType::Struct(struct_name) => {
+                let members = self
+                    .structs
+                    .get(struct_name)
+                    .ok_or_else(|| AbiCodecError::UnknownType(struct_name.clone()))?;
+
+                let mut decoded = Map::new();
+                for (member, member_type) in members.iter() {
+                    decoded.insert(
+                        member.clone(),
+                        self.decode_value(member, &AbiType::parse(member_type), elements)?,
+                    );
+                }
+                Ok(Value::Object(decoded))
+            }
+        }
+    }
+}
+
+impl AbiType {
+    pub(crate) fn parse(type_name: &str) -> Self {
+        let type_name = type_name.trim();
+
+        if let Some(element_type) = type_name.strip_suffix('*') {
+            Self::Pointer(Box::new(Self::parse(element_type)))
+        } else if let Some(members) = type_name
+            .strip_prefix('(')
+            .and_then(|type_name| type_name.strip_suffix(')'))
+        {
+            Self::Tuple(
+                split_top_level(members)
+                    .into_iter()
+                    .filter(|member| !member.trim().is_empty())
+                    .map(|member| match member.split_once(':') {
+                        // Named tuple members look like `name: type`
+                        Some((name, member_type)) if !name.contains('(') => {
+                            (Some(name.trim().to_owned()), Self::parse(member_type))
+                        }
+                        _ => (None, Self::parse(member)),
+                    })
+                    .collect(),
+            )
+        } else if type_name == "felt" || type_name == "codeoffset" {
+            Self::Felt
+        } else {
+            Self::Struct(type_name.to_owned())
+        }
+    }
+}
+
+impl std::fmt::Display for AbiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Felt => write!(f, "felt"),
+            Self::Struct(name) => write!(f, "{name}"),
+            Self::Pointer(element_type) => write!(f, "{element_type}*"),
+            Self::Tuple(members) => {
+                write!(f, "(")?;
+                for (ind, (name, member_type)) in members.iter().enumerate() {
+                    if ind > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match name {
+                        Some(name) => write!(f, "{name}: {member_type}")?,
+                        None => write!(f, "{member_type}")?,
+                    }
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+pub(crate) fn params<T, F>(items: &[T], f: F) -> Vec<(String, String)>
+where
+    F: Fn(&T) -> (&String, &String),
+{
+    items
+        .iter()
+        .map(|item| {
+            let (name, r#type) = f(item);
+            (name.clone(), r#type.clone())
+        })
+        .collect()
+}
+
+/// Parses parameter types, dropping the `{name}_len` parameters of arrays as their values are
+/// implied by the arrays.
+pub(crate) fn explicit_params(
+    params: &[(String, String)],
+) -> Result<Vec<(&str, AbiType)>, AbiCodecError> {
+    let mut explicit: Vec<(&str, AbiType)> = vec![];
+
+    for (name, type_name) in params.iter() {
+        let abi_type = AbiType::parse(type_name);
+        if let AbiType::Pointer(_) = abi_type {
+            let len_name = format!("{name}_len");
+            match explicit.last() {
+                Some((previous, AbiType::Felt)) if *previous == len_name => {
+                    explicit.pop();
+                }
+                _ => return Err(AbiCodecError::MissingArrayLength(name.clone())),
+            }
+        }
+        explicit.push((name, abi_type));
+    }
+
+    Ok(explicit)
+}
+
+/// Splits on commas that aren't nested in parentheses.
+fn split_top_level(value: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (ind, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&value[start..ind]);
+                start = ind + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+fn parse_felt(value: &Value) -> Option<FieldElement> {
+    match value {
+        Value::Number(number) => number.as_u64().map(FieldElement::from),
+        Value::String(value) if value.starts_with("0x") => FieldElement::from_hex_be(value).ok(),
+        Value::String(value) => FieldElement::from_dec_str(value).ok(),
+        Value::Bool(value) => Some(if *value {
+            FieldElement::ONE
+        } else {
+            FieldElement::ZERO
+        }),
+        _ => None,
+    }
+}
+
+/// Parses a number into its `[low, high]` 128-bit halves. Hex strings can use the full 256-bit
+/// range, while decimal values must be below the field prime.
+fn parse_u256(value: &Value) -> Option<[FieldElement; 2]> {
+    let bytes = match value {
+        Value::String(value) if value.starts_with("0x") => {
+            let digits = value.trim_start_matches("0x");
+            if digits.is_empty() || digits.len() > 64 {
+                return None;
+            }
+
+            let mut bytes = [0u8; 32];
+            let padded = format!("{digits:0>64}");
+            for (ind, byte) in bytes.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&padded[(ind * 2)..(ind * 2 + 2)], 16).ok()?;
+            }
+            bytes
+        }
+        value => parse_felt(value)?.to_bytes_be(),
+    };
+
+    Some([
+        FieldElement::from_byte_slice_be(&bytes[16..]).ok()?,
+        FieldElement::from_byte_slice_be(&bytes[..16]).ok()?,
+    ])
+}
+
+fn ensure_consumed<'a, I>(elements: I) -> Result<(), AbiCodecError>
+where
+    I: Iterator<Item = &'a FieldElement>,
+{
+    match elements.count() {
+        0 => Ok(()),
+        count => Err(AbiCodecError::TrailingElements(count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use starknet_core::types::ContractArtifact;
+
+    fn oz_account() -> AbiCodec {
+        let artifact = serde_json::from_str::<ContractArtifact>(include_str!(
+            "../test-data/artifacts/oz_account.txt"
+        ))
+        .unwrap();
+        AbiCodec::new(&artifact.abi)
+    }
+
+    fn felts(values: &[u64]) -> Vec<FieldElement> {
+        values.iter().map(|value| (*value).into()).collect()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_encode_struct_arrays() {
+        let calldata = oz_account()
+            .encode_calldata(
+                "__execute__",
+                &json!({
+                    "call_array": [
+                        { "to": "0x10", "selector": "0x20", "data_offset": 0, "data_len": 2 },
+                        ["0x30", "0x40", 2, 1],
+                    ],
+                    "calldata": [5, "6", "0x7"],
+                }),
+            )
+            .unwrap();
+
+        assert_eq!(
+            calldata,
+            felts(&[2, 0x10, 0x20, 0, 2, 0x30, 0x40, 2, 1, 3, 5, 6, 7])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_encode_positional_args() {
+        let codec = oz_account();
+
+        let call = codec
+            .encode_call(
+                FieldElement::ONE,
+                "isValidSignature",
+                &json!(["0x1234", ["0x1", "0x2"]]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            call.selector,
+            get_selector_from_name("isValidSignature").unwrap()
+        );
+        assert_eq!(call.calldata, felts(&[0x1234, 2, 1, 2]));
+        assert!(matches!(
+            codec.encode_calldata("isValidSignature", &json!(["0x1234"])),
+            Err(AbiCodecError::ArgumentCountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            codec.encode_calldata("isValidSignature", &json!({ "hash": "0x1234" })),
+            Err(AbiCodecError::MissingValue(name)) if name == "signature"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_output() {
+        let codec = oz_account();
+
+        assert_eq!(
+            codec
+                .decode_output("__execute__", &felts(&[2, 0xa, 0xb]))
+                .unwrap(),
+            json!({ "response": ["0xa", "0xb"] })
+        );
+        assert!(matches!(
+            codec.decode_output("getPublicKey", &felts(&[1, 2])),
+            Err(AbiCodecError::TrailingElements(1))
+        ));
+        assert!(matches!(
+            codec.decode_output("getPublicKey", &[]),
+            Err(AbiCodecError::UnexpectedEnd(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_event() {
+        let artifact = serde_json::from_str::<ContractArtifact>(include_str!(
+            "../test-data/artifacts/event_example.txt"
+        ))
+        .unwrap();
+        let codec = AbiCodec::new(&artifact.abi);
+
+        let event = codec
+            .decode_event(
+                &[get_selector_from_name("initialized").unwrap()],
+                &felts(&[0x99]),
+            )
+            .unwrap();
+
+        assert_eq!(event.name, "initialized");
+        assert_eq!(event.fields, json!({ "arg": "0x99" }));
+        assert_eq!(codec.encode_constructor(&json!([1])).unwrap(), felts(&[1]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_uint256_and_tuples() {
+        let abi: Vec<AbiEntry> = serde_json::from_value(json!([
+            {
+                "members": [
+                    { "name": "low", "offset": 0, "type": "felt" },
+                    { "name": "high", "offset": 1, "type": "felt" }
+                ],
+                "name": "Uint256",
+                "size": 2,
+                "type": "struct"
+            },
+            {
+                "inputs": [
+                    { "name": "amount", "type": "Uint256" },
+                    { "name": "point", "type": "(x: felt, y: felt)" }
+                ],
+                "name": "transfer",
+                "outputs": [{ "name": "pair", "type": "(felt, Uint256)" }],
+                "type": "function"
+            }
+        ]))
+        .unwrap();
+        let codec = AbiCodec::new(&abi);
+
+        assert_eq!(
+            codec
+                .encode_calldata(
+                    "transfer",
+                    &json!({
+                        "amount": "0x200000000000000000000000000000003",
+                        "point": { "x": 4, "y": 5 }
+                    })
+                )
+                .unwrap(),
+            felts(&[3, 2, 4, 5])
+        );
+        assert_eq!(
+            codec
+                .encode_calldata("transfer", &json!([{ "low": 1, "high": 0 }, [4, 5]]))
+                .unwrap(),
+            felts(&[1, 0, 4, 5])
+        );
+        assert_eq!(
+            codec.decode_output("transfer", &felts(&[1, 2, 3])).unwrap(),
+            json!({ "pair": ["0x1", { "low": "0x2", "high": "0x3" }] })
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_l1_handler_payload() {
+        let abi = serde_json::from_value::<Vec<AbiEntry>>(json!([{
+            "type": "l1_handler",
+            "name": "handle_deposit",
+            "inputs": [
+                { "name": "from_address", "type": "felt" },
+                { "name": "account", "type": "felt" },
+                { "name": "amount", "type": "felt" },
+            ],
+            "outputs": [],
+        }]))
+        .unwrap();
+        let codec = AbiCodec::new(&abi);
+
+        assert_eq!(
+            codec
+                .encode_l1_handler_payload("handle_deposit", &json!({ "account": 1, "amount": 2 }))
+                .unwrap(),
+            felts(&[1, 2])
+        );
+        assert!(codec
+            .encode_l1_handler_payload("handle_withdrawal", &json!([]))
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_storage_layout() {
+        let codec = oz_account();
+
+        assert_eq!(codec.storage_size("felt").unwrap(), 1);
+        assert_eq!(codec.storage_size("(felt, AccountCallArray)").unwrap(), 5);
+        assert!(codec.storage_size("felt*").is_err());
+
+        assert_eq!(
+            codec
+                .decode_storage("AccountCallArray", &felts(&[1, 2, 3, 4]))
+                .unwrap(),
+            json!({
+                "to": "0x1",
+                "selector": "0x2",
+                "data_offset": "0x3",
+                "data_len": "0x4",
+            })
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_type() {
+        assert_eq!(
+            AbiType::parse("(a: felt, b: (felt, Uint256*))").to_string(),
+            "(a: felt, b: (felt, Uint256*))"
+        );
+        assert_eq!(
+            AbiType::parse("felt*"),
+            AbiType::Pointer(Box::new(AbiType::Felt))
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use starknet_core::types::AbiEntry;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// The differences between two versions of a class ABI, e.g. to gate upgrading a contract on
+/// the new class being compatible with existing callers:
+///
+/// ```ignore
+/// AbiDiff::new(&old_class.abi, &new_class.abi).ensure_compatible()?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiDiff {
+    pub changes: Vec<AbiChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntrypointKind {
+    Constructor,
+    Function,
+    L1Handler,
+}
+
+/// A change between two ABIs. Changes are breaking when existing callers or event consumers
+/// would stop working, as reported by [is_breaking](AbiChange::is_breaking).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiChange {
+    EntrypointAdded {
+        kind: EntrypointKind,
+        name: String,
+    },
+    EntrypointRemoved {
+        kind: EntrypointKind,
+        name: String,
+    },
+    /// The types of the inputs or outputs changed.
+    EntrypointSignatureChanged {
+        kind: EntrypointKind,
+        name: String,
+        old: String,
+        new: String,
+    },
+    /// Only the names of inputs or outputs changed, which doesn't affect calldata.
+    EntrypointArgumentsRenamed {
+        kind: EntrypointKind,
+        name: String,
+        old: String,
+        new: String,
+    },
+    StateMutabilityChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    EventAdded {
+        name: String,
+    },
+    EventRemoved {
+        name: String,
+    },
+    /// The types of the event members, or whether they're keys or data, changed.
+    EventLayoutChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+    EventMembersRenamed {
+        name: String,
+        old: String,
+        new: String,
+    },
+    StructAdded {
+        name: String,
+    },
+    StructRemoved {
+        name: String,
+    },
+    /// The members of a struct changed, which changes the encoding of every entrypoint and event
+    /// using it.
+    StructLayoutChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+    StructMembersRenamed {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("ABI has {} breaking change(s): {}", .0.len(), join(.0))]
+pub struct IncompatibleAbiError(pub Vec<AbiChange>);
+
+/// The members of an entrypoint, event or struct, as `(name, type)` pairs.
+type Members = Vec<(String, String)>;
+
+#[derive(Debug, Default)]
+struct AbiItems {
+    constructor: Option<(Members, Members)>,
+    functions: BTreeMap<String, (Members, Members, Option<String>)>,
+    l1_handlers: BTreeMap<String, (Members, Members)>,
+    events: BTreeMap<String, (Members, Members)>,
+    structs: BTreeMap<String, Members>,
+}
+
+impl AbiDiff {
+    pub fn new(old: &[AbiEntry], new: &[AbiEntry]) -> Self {
+        let old = AbiItems::new(old);
+        let new = AbiItems::new(new);
+        let mut changes = vec![];
+
+        if let (Some(old), Some(new)) = (&old.constructor, &new.constructor) {
+            diff_entrypoint(
+                &mut changes,
+                EntrypointKind::Constructor,
+                "constructor",
+                old,
+                new,
+            );
+        }
+
+        diff_maps(
+            &mut changes,
+            &old.functions,
+            &new.functions,
+            |changes,
+             name,
+             (old_inputs, old_outputs, old_mutability),
+             (inputs, outputs, mutability)| {
+                diff_entrypoint(
+                    changes,
+                    EntrypointKind::Function,
+                    name,
+                    &(old_inputs.clone(), old_outputs.clone()),
+                    &(inputs.clone(), outputs.clone()),
+                );
+                if old_mutability != mutability {
+                    changes.push(AbiChange::StateMutabilityChanged {
+                        name: name.to_owned(),
+                        old: old_mutability.clone(),
+                        new: mutability.clone(),
+                    });
+                }
+            },
+            |name| AbiChange::EntrypointAdded {
+                kind: EntrypointKind::Function,
+                name,
+            },
+            |name| AbiChange::EntrypointRemoved {
+                kind: EntrypointKind::Function,
+                name,
+            },
+        );
+
+        diff_maps(
+            &mut changes,
+            &old.l1_handlers,
+            &new.l1_handlers,
+            |changes, name, old, new| {
+                diff_entrypoint(changes, EntrypointKind::L1Handler, name, old, new)
+            },
+            |name| AbiChange::EntrypointAdded {
+                kind: EntrypointKind::L1Handler,
+                name,
+            },
+            |name| AbiChange::EntrypointRemoved {
+                kind: EntrypointKind::L1Handler,
+                name,
+            },
+        );
+
+        diff_maps(
+            &mut changes,
+            &old.events,
+            &new.events,
+            |changes, name, (old_keys, old_data), (keys, data)| {
+                let old_layout = format!("keys: {}, data: {}", render(old_keys), render(old_data));
+                let new_layout = format!("keys: {}, data: {}", render(keys), render(data));
+                if !same_types(old_keys, keys) || !same_types(old_data, data) {
+                    changes.push(AbiChange::EventLayoutChanged {
+                        name: name.to_owned(),
+                        old: old_layout,
+                        new: new_layout,
+                    });
+                } else if old_keys != keys || old_data != data {
+                    changes.push(AbiChange::EventMembersRenamed {
+                        name: name.to_owned(),
+                        old: old_layout,
+                        new: new_layout,
+                    });
+                }
+            },
+            |name| AbiChange::EventAdded { name },
+            |name| AbiChange::EventRemoved { name },
+        );
+
+        diff_maps(
+            &mut changes,
+            &old.structs,
+            &new.structs,
+            |changes, name, old, new| {
+                if !same_types(old, new) {
+                    changes.push(AbiChange::StructLayoutChanged {
+                        name: name.to_owned(),
+                        old: render(old),
+                        new: render(new),
+                    });
+                } else if old != new {
+                    changes.push(AbiChange::StructMembersRenamed {
+                        name: name.to_owned(),
+                        old: render(old),
+                        new: render(new),
+                    });
+                }
+            },
+            |name| AbiChange::StructAdded { name },
+            |name| AbiChange::StructRemoved { name },
+        );
+
+        Self { changes }
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &AbiChange> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.breaking_changes().next().is_none()
+    }
+
+    /// Fails with all breaking changes if there are any.
+    pub fn ensure_compatible(&self) -> Result<(), IncompatibleAbiError> {
+        let breaking_changes = self.breaking_changes().cloned().collect::<Vec<_>>();
+        if breaking_changes.is_empty() {
+            Ok(())
+        } else {
+            Err(IncompatibleAbiError(breaking_changes))
+        }
+    }
+}
+
+impl AbiChange {
+    /// Whether the change breaks existing callers or event consumers. Constructors aren't run
+    /// when upgrading, so changes to them are never breaking.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Self::EntrypointRemoved { kind, .. }
+            | Self::EntrypointSignatureChanged { kind, .. } => *kind != EntrypointKind::Constructor,
+            // Functions that used to be views may no longer be callable without a transaction
+            Self::StateMutabilityChanged { old, new, .. } => {
+                old.as_deref() == Some("view") && new.as_deref() != Some("view")
+            }
+            Self::EventRemoved { .. }
+            | Self::EventLayoutChanged { .. }
+            | Self::StructRemoved { .. }
+            | Self::StructLayoutChanged { .. } => true,
+            Self::EntrypointAdded { .. }
+            | Self::EntrypointArgumentsRenamed { .. }
+            | Self::EventAdded { .. }
+            | Self::EventMembersRenamed { .. }
+            | Self::StructAdded { .. }
+            | Self::StructMembersRenamed { .. } => false,
+        }
+    }
+}
+
+impl Display for EntrypointKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Constructor => write!(f, "constructor"),
+            Self::Function => write!(f, "function"),
+            Self::L1Handler => write!(f, "L1 handler"),
+        }
+    }
+}
+
+impl Display for AbiChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EntrypointAdded { kind, name } => write!(f, "{kind} {name} added"),
+            Self::EntrypointRemoved { kind, name } => write!(f, "{kind} {name} removed"),
+            Self::EntrypointSignatureChanged {
+                kind,
+                name,
+                old,
+                new,
+            } => write!(f, "{kind} {name} changed from {old} to {new}"),
+            Self::EntrypointArgumentsRenamed {
+                kind,
+                name,
+                old,
+                new,
+            } => write!(f, "{kind} {name} arguments renamed from {old} to {new}"),
+            Self::StateMutabilityChanged { name, old, new } => write!(
+                f,
+                "function {name} state mutability changed from {} to {}",
+                old.as_deref().unwrap_or("external"),
+                new.as_deref().unwrap_or("external")
+            ),
+            Self::EventAdded { name } => write!(f, "event {name} added"),
+            Self::EventRemoved { name } => write!(f, "event {name} removed"),
+            Self::EventLayoutChanged { name, old, new } => {
+                write!(f, "event {name} changed from {old} to {new}")
+            }
+            Self::EventMembersRenamed { name, old, new } => {
+                write!(f, "event {name} members renamed from {old} to {new}")
+            }
+            Self::StructAdded { name } => write!(f, "struct {name} added"),
+            Self::StructRemoved { name } => write!(f, "struct {name} removed"),
+            Self::StructLayoutChanged { name, old, new } => {
+                write!(f, "struct {name} changed from {old} to {new}")
+            }
+            Self::StructMembersRenamed { name, old, new } => {
+                write!(f, "struct {name} members renamed from {old} to {new}")
+            }
+        }
+    }
+}
+
+impl AbiItems {
+    fn new(abi: &[AbiEntry]) -> Self {
+        let mut items = Self::default();
+
+        for entry in abi.iter() {
+            match entry {
+                AbiEntry::Constructor(constructor) => {
+                    items.constructor = Some((
+                        members(&constructor.inputs, |input| (&input.name, &input.r#type)),
+                        members(&constructor.outputs, |output| {
+                            (&output.name, &output.r#type)
+                        }),
+                    ));
+                }
+                AbiEntry::Function(function) => {
+                    items.functions.insert(
+                        function.name.clone(),
+                        (
+                            members(&function.inputs, |input| (&input.name, &input.r#type)),
+                            members(&function.outputs, |output| (&output.name, &output.r#type)),
+                            function.state_mutability.clone(),
+                        ),
+                    );
+                }
+                AbiEntry::L1Handler(handler) => {
+                    items.l1_handlers.insert(
+                        handler.name.clone(),
+                        (
+                            members(&handler.inputs, |input| (&input.name, &input.r#type)),
+                            members(&handler.outputs, |output| (&output.name, &output.r#type)),
+                        ),
+                    );
+                }
+                AbiEntry::Event(event) => {
+                    items.events.insert(
+                        event.name.clone(),
+                        (
+                            members(&event.keys, |key| (&key.name, &key.r#type)),
+                            members(&event.data, |data| (&data.name, &data.r#type)),
+                        ),
+                    );
+                }
+                AbiEntry::Struct(abi_struct) => {
+                    let mut struct_members = abi_struct.members.clone();
+                    struct_members.sort_by_key(|member| member.offset);
+                    items.structs.insert(
+                        abi_struct.name.clone(),
+                        members(&struct_members, |member| (&member.name, &member.r#type)),
+                    );
+                }
+            }
+        }
+
+        items
+    }
+}
+
+fn members<T, F>(items: &[T], f: F) -> Members
+where
+    F: Fn(&T) -> (&String, &String),
+{
+    items
+        .iter()
+        .map(|item| {
+            let (name, member_type) = f(item);
+            (name.clone(), member_type.replace(' ', ""))
+        })
+        .collect()
+}
+
+fn diff_entrypoint(
+    changes: &mut Vec<AbiChange>,
+    kind: EntrypointKind,
+    name: &str,
+    (old_inputs, old_outputs): &(Members, Members),
+    (inputs, outputs): &(Members, Members),
+) {
+    let old = format!("{} -> {}", render(old_inputs), render(old_outputs));
+    let new = format!("{} -> {}", render(inputs), render(outputs));
+
+    if !same_types(old_inputs, inputs) || !same_types(old_outputs, outputs) {
+        changes.push(AbiChange::EntrypointSignatureChanged {
+            kind,
+            name: name.to_owned(),
+            old,
+            new,
+        });
+    } else if old_inputs != inputs || old_outputs != outputs {
+        changes.push(AbiChange::EntrypointArgumentsRenamed {
+            kind,
+            name: name.to_owned(),
+            old,
+            new,
+        });
+    }
+}
+
+/// Reports items only in `new` as added, items only in `old` as removed, and compares items in
+/// both with `diff`.
+fn diff_maps<V, D, A, R>(
+    changes: &mut Vec<AbiChange>,
+    old: &BTreeMap<String, V>,
+    new: &BTreeMap<String, V>,
+    mut diff: D,
+    added: A,
+    removed: R,
+) where
+    D: FnMut(&mut Vec<AbiChange>, &str, &V, &V),
+    A: Fn(String) -> AbiChange,
+    R: Fn(String) -> AbiChange,
+{
+    for (name, old_item) in old.iter() {
+        match new.get(name) {
+            Some(new_item) => diff(changes, name, old_item, new_item),
+            None => changes.push(removed(name.clone())),
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(added(name.clone()));
+    }
+}
+
+fn same_types(old: &Members, new: &Members) -> bool {
+    old.len() == new.len()
+        && old
+            .iter()
+            .zip(new.iter())
+            .all(|((_, old_type), (_, new_type))| old_type == new_type)
+}
+
+fn render(members: &Members) -> String {
+    let members = members
+        .iter()
+        .map(|(name, member_type)| format!("{name}: {member_type}"))
+        .collect::<Vec<_>>();
+    format!("({})", members.join(", "))
+}
+
+fn join(changes: &[AbiChange]) -> String {
+    changes
+        .iter()
+        .map(|change| change.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi(json: &str) -> Vec<AbiEntry> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_compatible_upgrade() {
+        let old = abi(r#"[
+            {"type": "function", "name": "get", "inputs": [{"name": "key", "type": "felt"}], "outputs": [{"name": "value", "type": "felt"}], "stateMutability": "view"},
+            {"type": "event", "name": "Updated", "keys": [], "data": [{"name": "key", "type": "felt"}]}
+        ]"#);
+        let new = abi(r#"[
+            {"type": "function", "name": "get", "inputs": [{"name": "id", "type": "felt"}], "outputs": [{"name": "value", "type": "felt"}], "stateMutability": "view"},
+            {"type": "function", "name": "set", "inputs": [{"name": "key", "type": "felt"}], "outputs": []},
+            {"type": "event", "name": "Updated", "keys": [], "data": [{"name": "key", "type": "felt"}]}
+        ]"#);
+
+        let diff = AbiDiff::new(&old, &new);
+        assert_eq!(
+            diff.changes,
+            vec![
+                AbiChange::EntrypointArgumentsRenamed {
+                    kind: EntrypointKind::Function,
+                    name: "get".into(),
+                    old: "(key: felt) -> (value: felt)".into(),
+                    new: "(id: felt) -> (value: felt)".into(),
+                },
+                AbiChange::EntrypointAdded {
+                    kind: EntrypointKind::Function,
+                    name: "set".into(),
+                },
+            ]
+        );
+        assert!(diff.ensure_compatible().is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_breaking_changes() {
+        let old = abi(r#"[
+            {"type": "struct", "name": "Uint256", "size": 2, "members": [{"name": "low", "offset": 0, "type": "felt"}, {"name": "high", "offset": 1, "type": "felt"}]},
+            {"type": "function", "name": "balance", "inputs": [], "outputs": [{"name": "res", "type": "Uint256"}], "stateMutability": "view"},
+            {"type": "function", "name": "burn", "inputs": [{"name": "amount", "type": "felt"}], "outputs": []},
+            {"type": "event", "name": "Burnt", "keys": [], "data": [{"name": "amount", "type": "felt"}]}
+        ]"#);
+        let new = abi(r#"[
+            {"type": "struct", "name": "Uint256", "size": 2, "members": [{"name": "low", "offset": 0, "type": "felt"}, {"name": "high", "offset": 1, "type": "felt"}]},
+            {"type": "function", "name": "balance", "inputs": [], "outputs": [{"name": "res", "type": "Uint256"}]},
+            {"type": "function", "name": "burn", "inputs": [{"name": "amount", "type": "Uint256"}], "outputs": []},
+            {"type": "event", "name": "Burnt", "keys": [{"name": "amount", "type": "felt"}], "data": []}
+        ]"#);
+
+        let err = AbiDiff::new(&old, &new).ensure_compatible().unwrap_err();
+        assert_eq!(err.0.len(), 3);
+        assert_eq!(
+            err.0[1].to_string(),
+            "function burn changed from (amount: felt) -> () to (amount: Uint256) -> ()"
+        );
+        assert!(matches!(err.0[2], AbiChange::EventLayoutChanged { .. }));
+    }
+}
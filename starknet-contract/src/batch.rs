@@ -0,0 +1,282 @@
+use crate::FunctionCall;
+
+use starknet_accounts::{Account, AccountError, Call, ConnectedAccount, Execution};
+use starknet_core::{
+    codec::{CairoDeserialize, DecodeError},
+    types::{
+        trace::FunctionInvocation, AsStarknetError, FieldElement, StarknetError,
+        TransactionSimulationInfo, TransactionTrace,
+    },
+};
+use starknet_providers::Provider;
+use std::marker::PhantomData;
+
+/// Calls to any contracts, executed together by an account in a single transaction, whose
+/// results are decoded as `R` from simulations. Usually built with [multicall!](crate::multicall)
+/// from function calls of different bindings:
+///
+/// ```ignore
+/// let batch = multicall![token.approve(amm.address(), amount), amm.swap(token.address(), amount)];
+///
+/// let (approved, amount_out) = batch.simulate(&account).await?.results;
+/// batch.execute(&account).send().await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct CallBatch<R> {
+    calls: Vec<Call>,
+    _outputs: PhantomData<fn() -> R>,
+}
+
+/// The decoded results of a simulated [CallBatch].
+#[derive(Debug)]
+pub struct BatchSimulation<R> {
+    pub results: R,
+    pub simulation: TransactionSimulationInfo,
+}
+
+/// Results of a [CallBatch], decoded from the result of each call in order.
+pub trait BatchResults: Sized {
+    fn decode_results(results: &[&[FieldElement]]) -> Result<Self, DecodeError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BatchSimulationError<S, P> {
+    #[error(transparent)]
+    Account(AccountError<S, P>),
+    #[error(transparent)]
+    Decode(BatchDecodeError),
+}
+
+impl<S, P> AsStarknetError for BatchSimulationError<S, P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Account(err) => err.starknet_error(),
+            Self::Decode(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BatchDecodeError {
+    #[error("batch calls not found in transaction trace")]
+    CallsNotFound,
+    #[error("failed to decode call result: {0}")]
+    Decode(#[source] DecodeError),
+}
+
+/// Collects function calls of any bindings into a [CallBatch], decoding results as the tuple of
+/// their output types.
+#[macro_export]
+macro_rules! multicall {
+    ($($call:expr),+ $(,)?) => {
+        $crate::CallBatch::from(($($call,)+))
+    };
+}
+
+impl<R> CallBatch<R> {
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    pub fn into_calls(self) -> Vec<Call> {
+        self.calls
+    }
+
+    /// Executes the calls with `account`, in a single transaction.
+    pub fn execute<'a, A>(&self, account: &'a A) -> Execution<'a, A>
+    where
+        A: Account,
+    {
+        account.execute(self.calls.clone())
+    }
+}
+
+impl<R> CallBatch<R>
+where
+    R: BatchResults,
+{
+    /// Simulates the execution of the calls with `account`, and decodes their results.
+    pub async fn simulate<A>(
+        &self,
+        account: &A,
+    ) -> Result<
+        BatchSimulation<R>,
+        BatchSimulationError<A::SignError, <A::Provider as Provider>::Error>,
+    >
+    where
+        A: ConnectedAccount + Sync,
+    {
+        let simulation = self
+            .execute(account)
+            .simulate()
+            .await
+            .map_err(BatchSimulationError::Account)?;
+
+        let results = self
+            .decode_trace(&simulation.trace)
+            .map_err(BatchSimulationError::Decode)?;
+        Ok(BatchSimulation {
+            results,
+            simulation,
+        })
+    }
+
+    /// Decodes the results of the calls from the trace of a transaction executing them.
+    ///
+    /// The calls are looked up as the internal calls of the account `__execute__` invocation,
+    /// or of any invocation below it in case the account is a proxy.
+    pub fn decode_trace(&self, trace: &TransactionTrace) -> Result<R, BatchDecodeError> {
+        let results = find_results(&trace.function_invocation, &self.calls)
+            .ok_or(BatchDecodeError::CallsNotFound)?;
+        R::decode_results(&results).map_err(BatchDecodeError::Decode)
+    }
+}
+
+/// Finds the first invocation, in depth-first order, whose internal calls include `calls` in
+/// order, and returns their results.
+fn find_results<'t>(
+    invocation: &'t FunctionInvocation,
+    calls: &[Call],
+) -> Option<Vec<&'t [FieldElement]>> {
+    let mut results = vec![];
+    let mut internal_calls = invocation.internal_calls.iter();
+    for call in calls.iter() {
+        let found = internal_calls.find(|internal_call| {
+            internal_call.contract_address == call.to
+                && internal_call.selector == Some(call.selector)
+                && internal_call.calldata == call.calldata
+        });
+        match found {
+            Some(internal_call) => results.push(&internal_call.result[..]),
+            None => break,
+        }
+    }
+    if results.len() == calls.len() {
+        return Some(results);
+    }
+
+    invocation
+        .internal_calls
+        .iter()
+        .find_map(|internal_call| find_results(internal_call, calls))
+}
+
+macro_rules! impl_batch_tuple {
+    ($($provider:ident $output:ident $ind:tt),+) => {
+        impl<'p, $($provider, $output),+> From<($(FunctionCall<'p, $provider, $output>,)+)>
+            for CallBatch<($($output,)+)>
+        {
+            fn from(calls: ($(FunctionCall<'p, $provider, $output>,)+)) -> Self {
+                Self {
+                    calls: vec![$(calls.$ind.into_call()),+],
+                    _outputs: PhantomData,
+                }
+            }
+        }
+
+        impl<$($output),+> BatchResults for ($($output,)+)
+        where
+            $($output: CairoDeserialize),+
+        {
+            fn decode_results(results: &[&[FieldElement]]) -> Result<Self, DecodeError> {
+                let expected = [$($ind),+].len();
+                if results.len() < expected {
+                    return Err(DecodeError::UnexpectedEnd);
+                } else if results.len() > expected {
+                    return Err(DecodeError::TrailingElements(results.len() - expected));
+                }
+
+                Ok(($($output::cairo_deserialize_all(results[$ind])?,)+))
+            }
+        }
+    };
+}
+
+impl_batch_tuple!(P0 R0 0);
+impl_batch_tuple!(P0 R0 0, P1 R1 1);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2, P3 R3 3);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2, P3 R3 3, P4 R4 4);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2, P3 R3 3, P4 R4 4, P5 R5 5);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2, P3 R3 3, P4 R4 4, P5 R5 5, P6 R6 6);
+impl_batch_tuple!(P0 R0 0, P1 R1 1, P2 R2 2, P3 R3 3, P4 R4 4, P5 R5 5, P6 R6 6, P7 R7 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{erc20::Erc20, Contract};
+    use serde_json::{json, Value};
+    use starknet_core::{codec::U256, utils::get_selector_from_name};
+
+    fn invocation(call: &Call, result: &[u64], internal_calls: Vec<Value>) -> Value {
+        json!({
+            "caller_address": "0x0",
+            "contract_address": format!("{:#x}", call.to),
+            "calldata": call.calldata.iter().map(|felt| format!("{felt:#x}")).collect::<Vec<_>>(),
+            "call_type": "CALL",
+            "class_hash": null,
+            "selector": format!("{:#x}", call.selector),
+            "entry_point_type": "EXTERNAL",
+            "result": result.iter().map(|felt| format!("{felt:#x}")).collect::<Vec<_>>(),
+            "execution_resources": {
+                "n_steps": 0,
+                "n_memory_holes": 0,
+                "builtin_instance_counter": {},
+            },
+            "internal_calls": internal_calls,
+            "events": [],
+            "messages": [],
+        })
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_proxy_account_trace() {
+        let token = Erc20::new(FieldElement::ONE, ());
+        let vault = Contract::new(FieldElement::TWO, ());
+        let deposit: FunctionCall<'_, (), U256> = vault.function(
+            get_selector_from_name("deposit").unwrap(),
+            &U256::from(5u128),
+        );
+
+        let batch = multicall![token.approve(FieldElement::TWO, U256::from(5u128)), deposit];
+        assert_eq!(batch.calls().len(), 2);
+        assert_eq!(batch.calls()[0].to, FieldElement::ONE);
+        assert_eq!(batch.calls()[1].to, FieldElement::TWO);
+
+        let execute = Call {
+            to: FieldElement::THREE,
+            selector: get_selector_from_name("__execute__").unwrap(),
+            calldata: vec![],
+        };
+        let calls = batch.calls();
+        let trace: TransactionTrace = serde_json::from_value(json!({
+            "function_invocation": invocation(&execute, &[], vec![
+                // Proxy delegating to the account implementation
+                invocation(&execute, &[], vec![
+                    invocation(&calls[0], &[1], vec![]),
+                    // The vault pulling the approved tokens
+                    invocation(&calls[0], &[1], vec![]),
+                    invocation(&calls[1], &[7, 0], vec![
+                        invocation(&calls[0], &[1], vec![]),
+                    ]),
+                ]),
+            ]),
+            "signature": [],
+        }))
+        .unwrap();
+
+        let (approved, shares) = batch.decode_trace(&trace).unwrap();
+        assert!(approved);
+        assert_eq!(shares, U256::from(7u128));
+
+        let other: CallBatch<(bool,)> =
+            multicall![token.approve(FieldElement::THREE, U256::default())];
+        assert!(matches!(
+            other.decode_trace(&trace),
+            Err(BatchDecodeError::CallsNotFound)
+        ));
+    }
+}
@@ -0,0 +1,705 @@
+//! Generating typed bindings from contract ABIs, for use in build scripts:
+//!
+//! ```ignore
+//! // build.rs
+//! let artifact: ContractArtifact = serde_json::from_str(&std::fs::read_to_string("token.json")?)?;
+//! starknet_contract::codegen::generate("Token", &artifact.abi, std::env::var("OUT_DIR")?)?;
+//!
+//! // src/lib.rs
+//! mod token {
+//!     include!(concat!(env!("OUT_DIR"), "/token.rs"));
+//! }
+//! ```
+//!
+//! The generated code depends on the `starknet` crate. Each contract gets a binding built on
+//! [Contract](crate::Contract) with one method per function returning a
+//! [FunctionCall](crate::FunctionCall), one method per L1 handler building the L1 to L2 message,
+//! a constructor arguments struct for [deploy_with_args](crate::ContractFactory::deploy_with_args),
+//! as well as all structs and events of the ABI.
+//!
+//! ABIs are in the legacy format shared by Cairo 0 contracts, where all values are made of
+//! felts. Following the convention of Cairo 0 tokens and bridges, `Uint256` structs are bound as
+//! [U256](starknet_core::codec::U256) instead of being generated.
+//!
+//! As ABIs don't declare the errors contracts revert with, they can be declared with
+//! [Bindings] to generate an error enum implementing [ContractError](crate::ContractError):
+//!
+//! ```ignore
+//! Bindings::new("Token", artifact.abi)
+//!     .error("InsufficientBalance", "ERC20: transfer amount exceeds balance")
+//!     .error("InsufficientBalance", "ERC20: insufficient balance")
+//!     .core_panics()
+//!     .generate(std::env::var("OUT_DIR")?)?;
+//! ```
+//!
+//! Contracts of a Scarb workspace can be bound by package name with [ScarbPackage] instead of
+//! reading artifacts manually.
+
+use crate::{
+    abi_codec::{explicit_params, params, AbiType},
+    AbiCodecError,
+};
+
+use starknet_core::types::AbiEntry;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+mod scarb;
+pub use scarb::ScarbPackage;
+
+const HEADER: &str = "// Code generated by `starknet_contract::codegen`. Do not edit.\n";
+
+/// Names of methods of the generated bindings that can't be used for functions.
+const RESERVED_METHODS: [&str; 3] = ["new", "contract", "address"];
+
+/// Short strings Cairo 1 core library functions panic with, and their error variants.
+const CORE_PANICS: [(&str, &str); 8] = [
+    ("U256AddOverflow", "u256_add Overflow"),
+    ("U256SubOverflow", "u256_sub Overflow"),
+    ("U256MulOverflow", "u256_mul Overflow"),
+    ("DivisionByZero", "Division by 0"),
+    ("OptionUnwrapFailed", "Option::unwrap failed."),
+    ("ResultUnwrapFailed", "Result::unwrap failed."),
+    ("IndexOutOfBounds", "Index out of bounds"),
+    ("OutOfGas", "Out of gas"),
+];
+
+/// Options for generating the bindings of a contract.
+#[derive(Debug, Clone)]
+pub struct Bindings {
+    name: String,
+    abi: Vec<AbiEntry>,
+    errors: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    #[error(transparent)]
+    Abi(AbiCodecError),
+    #[error("unsupported type {r#type} for {name}")]
+    UnsupportedType { name: String, r#type: String },
+    #[error("invalid error variant name: {0}")]
+    InvalidErrorVariant(String),
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error("failed to parse {path}: {error}")]
+    Manifest {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("invalid artifact {path}: {error}")]
+    Artifact {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+    #[error("Scarb package not found: {0}")]
+    ScarbPackageNotFound(String),
+    #[error("contract {contract} not found in artifacts of Scarb package {package}")]
+    ScarbContractNotFound { package: String, contract: String },
+}
+
+/// Generates the bindings of contract `name` into `{out_dir}/{name}.rs`, with the name in
+/// snake case, and returns the path of the file.
+pub fn generate<P>(name: &str, abi: &[AbiEntry], out_dir: P) -> Result<PathBuf, CodegenError>
+where
+    P: AsRef<Path>,
+{
+    Bindings::new(name, abi.to_vec()).generate(out_dir)
+}
+
+/// Generates the source code of the bindings of contract `name`.
+pub fn generate_source(name: &str, abi: &[AbiEntry]) -> Result<String, CodegenError> {
+    Bindings::new(name, abi.to_vec()).generate_source()
+}
+
+impl Bindings {
+    pub fn new<S>(name: S, abi: Vec<AbiEntry>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            abi,
+            errors: vec![],
+        }
+    }
+
+    /// Declares that the contract reverts with `message`, decoded as variant `variant` of the
+    /// generated `{Name}Error` enum. Several messages can be decoded as the same variant.
+    pub fn error(mut self, variant: &str, message: &str) -> Self {
+        match self.errors.iter_mut().find(|(name, _)| name == variant) {
+            Some((_, messages)) => messages.push(message.to_owned()),
+            None => self
+                .errors
+                .push((variant.to_owned(), vec![message.to_owned()])),
+        }
+        self
+    }
+
+    /// Declares the short strings Cairo 1 core library functions panic with, like
+    /// `u256_sub Overflow`, as errors.
+    pub fn core_panics(self) -> Self {
+        CORE_PANICS
+            .iter()
+            .fold(self, |bindings, (variant, message)| {
+                bindings.error(variant, message)
+            })
+    }
+
+    /// Generates the bindings into `{out_dir}/{name}.rs`, with the name in snake case, and
+    /// returns the path of the file.
+    pub fn generate<P>(&self, out_dir: P) -> Result<PathBuf, CodegenError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = out_dir
+            .as_ref()
+            .join(format!("{}.rs", to_snake_case(&self.name)));
+        std::fs::write(&path, self.generate_source()?).map_err(CodegenError::Io)?;
+        Ok(path)
+    }
+
+    /// Generates the source code of the bindings.
+    pub fn generate_source(&self) -> Result<String, CodegenError> {
+        generate_bindings(&self.name, &self.abi, &self.errors)
+    }
+}
+
+fn generate_bindings(
+    name: &str,
+    abi: &[AbiEntry],
+    errors: &[(String, Vec<String>)],
+) -> Result<String, CodegenError> {
+    let contract = to_upper_camel_case(name);
+
+    let mut structs = vec![];
+    let mut uses_u256 = false;
+    let mut events = vec![];
+    let mut functions = vec![];
+    let mut l1_handlers = vec![];
+    let mut constructor = None;
+    for entry in abi.iter() {
+        match entry {
+            AbiEntry::Struct(abi_struct) => {
+                let mut members = abi_struct.members.clone();
+                members.sort_by_key(|member| member.offset);
+                let members = params(&members, |member| (&member.name, &member.r#type));
+
+                if is_uint256(&abi_struct.name) {
+                    if members
+                        != [("low", "felt"), ("high", "felt")]
+                            .map(|(n, t)| (n.to_owned(), t.to_owned()))
+                    {
+                        return Err(CodegenError::UnsupportedType {
+                            name: abi_struct.name.clone(),
+                            r#type: format!("struct with members {members:?}"),
+                        });
+                    }
+                    uses_u256 = true;
+                    continue;
+                }
+
+                structs.push((&abi_struct.name, members));
+            }
+            AbiEntry::Event(event) => events.push((
+                &event.name,
+                params(&event.keys, |key| (&key.name, &key.r#type)),
+                params(&event.data, |data| (&data.name, &data.r#type)),
+            )),
+            AbiEntry::Function(function) => functions.push((
+                &function.name,
+                params(&function.inputs, |input| (&input.name, &input.r#type)),
+                params(&function.outputs, |output| (&output.name, &output.r#type)),
+                function.state_mutability.as_deref() == Some("view"),
+            )),
+            AbiEntry::L1Handler(handler) => l1_handlers.push((
+                &handler.name,
+                params(&handler.inputs, |input| (&input.name, &input.r#type)),
+            )),
+            AbiEntry::Constructor(abi_constructor) => {
+                constructor = Some(params(&abi_constructor.inputs, |input| {
+                    (&input.name, &input.r#type)
+                }))
+            }
+        }
+    }
+
+    let mut code = String::new();
+    let mut imports = vec!["FieldElement"];
+    let mut macros = vec![];
+    if !functions.is_empty() || !l1_handlers.is_empty() {
+        macros.push("selector");
+    }
+    if !structs.is_empty() || constructor.is_some() {
+        macros.extend(["CairoDeserialize", "CairoSerialize"]);
+    }
+    if !events.is_empty() {
+        macros.push("StarknetEvent");
+    }
+    if !l1_handlers.is_empty() {
+        imports.insert(0, "CallL1Handler");
+        imports.push("L1Address");
+    }
+
+    code.push_str(HEADER);
+    let mut contract_imports = vec!["Contract"];
+    if !errors.is_empty() {
+        contract_imports.push("ContractError");
+    }
+    if !functions.is_empty() {
+        contract_imports.push("FunctionCall");
+    }
+
+    code.push_str("\nuse ::starknet::{\n");
+    match contract_imports.len() {
+        1 => code.push_str("    contract::Contract,\n"),
+        _ => writeln!(code, "    contract::{{{}}},", contract_imports.join(", ")).unwrap(),
+    }
+    let types = match imports.len() {
+        1 => format!("types::{}", imports[0]),
+        _ => format!("types::{{{}}}", imports.join(", ")),
+    };
+    if !uses_u256 {
+        writeln!(code, "    core::{types},").unwrap();
+    } else {
+        writeln!(
+            code,
+            "    core::{{\n        codec::U256,\n        {types},\n    }},"
+        )
+        .unwrap();
+    }
+    match macros.len() {
+        0 => {}
+        1 => writeln!(code, "    macros::{},", macros[0]).unwrap(),
+        _ => writeln!(code, "    macros::{{{}}},", macros.join(", ")).unwrap(),
+    }
+    code.push_str("};\n");
+
+    writeln!(
+        code,
+        "\n#[derive(Debug, Clone)]\npub struct {contract}<P> {{\n    contract: Contract<P>,\n}}"
+    )
+    .unwrap();
+
+    if let Some(inputs) = constructor {
+        writeln!(
+            code,
+            "\n/// Constructor arguments of [{contract}], for deploying it with `deploy_with_args`."
+        )
+        .unwrap();
+        write_struct(&mut code, &format!("{contract}Constructor"), &inputs)?;
+    }
+
+    if !errors.is_empty() {
+        write_errors(&mut code, &contract, errors)?;
+    }
+
+    for (name, members) in structs.iter() {
+        code.push('\n');
+        write_struct(&mut code, &struct_name(name), members)?;
+    }
+
+    for (name, keys, data) in events.iter() {
+        writeln!(
+            code,
+            "\n#[derive(Debug, Clone, PartialEq, Eq, StarknetEvent)]\n#[event(name = {name:?})]\npub struct {} {{",
+            to_upper_camel_case(name)
+        )
+        .unwrap();
+        for (is_key, members) in [(true, keys), (false, data)] {
+            for (member, member_type) in explicit_params(members).map_err(CodegenError::Abi)? {
+                if is_key {
+                    code.push_str("    #[event(key)]\n");
+                }
+                writeln!(
+                    code,
+                    "    pub {}: {},",
+                    to_ident(member),
+                    value_type(member, &member_type)?
+                )
+                .unwrap();
+            }
+        }
+        code.push_str("}\n");
+    }
+
+    writeln!(code, "\nimpl<P> {contract}<P> {{").unwrap();
+    code.push_str(
+        "    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self {
+            contract: Contract::new(address, provider),
+        }
+    }
+
+    pub fn contract(&self) -> &Contract<P> {
+        &self.contract
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.contract.address()
+    }
+",
+    );
+
+    for (name, inputs, outputs, is_view) in functions.iter() {
+        let (args, values) = arguments(inputs)?;
+        let outputs = explicit_params(outputs)
+            .map_err(CodegenError::Abi)?
+            .into_iter()
+            .map(|(output, output_type)| value_type(output, &output_type))
+            .collect::<Result<Vec<_>, _>>()?;
+        let output = match outputs.len() {
+            1 => outputs.into_iter().next().unwrap(),
+            _ => tuple(outputs),
+        };
+
+        write_method(
+            &mut code,
+            &format!(
+                "{} `{name}`.",
+                if *is_view {
+                    "View function"
+                } else {
+                    "Function"
+                }
+            ),
+            &method_name(name),
+            args,
+            &format!("FunctionCall<'_, P, {output}>"),
+            &format!(
+                "self.contract.function(selector!({name:?}), &{})",
+                tuple(values)
+            ),
+        );
+    }
+
+    for (name, inputs) in l1_handlers.iter() {
+        // The first input is the L1 sender, provided by the sequencer
+        let (mut args, values) = arguments(&inputs[1.min(inputs.len())..])?;
+        args.insert(0, "from_address: L1Address".into());
+
+        write_method(
+            &mut code,
+            &format!("Message to L1 handler `{name}`."),
+            &format!("{}_message", to_snake_case(name)),
+            args,
+            "CallL1Handler",
+            &format!(
+                "self.contract\n            .l1_handler(from_address, selector!({name:?}), &{})",
+                tuple(values)
+            ),
+        );
+    }
+    code.push_str("}\n");
+
+    Ok(code)
+}
+
+fn write_errors(
+    code: &mut String,
+    contract: &str,
+    errors: &[(String, Vec<String>)],
+) -> Result<(), CodegenError> {
+    writeln!(
+        code,
+        "\n/// Errors [{contract}] reverts with.\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum {contract}Error {{"
+    )
+    .unwrap();
+    for (variant, messages) in errors.iter() {
+        if !is_variant_name(variant) {
+            return Err(CodegenError::InvalidErrorVariant(variant.to_owned()));
+        }
+        writeln!(
+            code,
+            "    /// Reverted with {}.\n    {variant},",
+            messages
+                .iter()
+                .map(|message| format!("`{message}`"))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        code,
+        "}}\n\nimpl ContractError for {contract}Error {{\n    fn from_revert_reason(reason: &str) -> Option<Self> {{\n        match reason {{"
+    )
+    .unwrap();
+    for (variant, messages) in errors.iter() {
+        writeln!(
+            code,
+            "            {} => Some(Self::{variant}),",
+            messages
+                .iter()
+                .map(|message| format!("{message:?}"))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        )
+        .unwrap();
+    }
+    code.push_str("            _ => None,\n        }\n    }\n}\n");
+
+    Ok(())
+}
+
+fn write_struct(
+    code: &mut String,
+    name: &str,
+    members: &[(String, String)],
+) -> Result<(), CodegenError> {
+    writeln!(
+        code,
+        "#[derive(Debug, Clone, PartialEq, Eq, CairoSerialize, CairoDeserialize)]\npub struct {name} {{"
+    )
+    .unwrap();
+    for (member, member_type) in explicit_params(members).map_err(CodegenError::Abi)? {
+        writeln!(
+            code,
+            "    pub {}: {},",
+            to_ident(member),
+            value_type(member, &member_type)?
+        )
+        .unwrap();
+    }
+    code.push_str("}\n");
+    Ok(())
+}
+
+/// Writes a method taking `&self` and `args`, with one argument per line if they don't fit on
+/// a single line.
+fn write_method(
+    code: &mut String,
+    doc: &str,
+    name: &str,
+    args: Vec<String>,
+    output: &str,
+    body: &str,
+) {
+    let mut signature = format!("    pub fn {name}(&self");
+    for arg in args.iter() {
+        write!(signature, ", {arg}").unwrap();
+    }
+    write!(signature, ") -> {output} {{").unwrap();
+
+    if signature.len() > 100 {
+        signature = format!("    pub fn {name}(\n        &self,\n");
+        for arg in args.iter() {
+            writeln!(signature, "        {arg},").unwrap();
+        }
+        write!(signature, "    ) -> {output} {{").unwrap();
+    }
+
+    writeln!(code, "\n    /// {doc}\n{signature}\n        {body}\n    }}").unwrap();
+}
+
+/// The method arguments for `inputs`, and the values to encode as calldata.
+fn arguments(inputs: &[(String, String)]) -> Result<(Vec<String>, Vec<String>), CodegenError> {
+    let mut args = vec![];
+    let mut values = vec![];
+    for (input, input_type) in explicit_params(inputs).map_err(CodegenError::Abi)? {
+        let ident = to_ident(input);
+        args.push(format!("{ident}: {}", argument_type(input, &input_type)?));
+        values.push(ident);
+    }
+    Ok((args, values))
+}
+
+/// The Rust type of a value decoded from `abi_type`.
+fn value_type(name: &str, abi_type: &AbiType) -> Result<String, CodegenError> {
+    Ok(match abi_type {
+        AbiType::Felt => "FieldElement".into(),
+        AbiType::Struct(struct_type) if is_uint256(struct_type) => "U256".into(),
+        AbiType::Struct(struct_type) => struct_name(struct_type),
+        AbiType::Tuple(members) => tuple(
+            members
+                .iter()
+                .map(|(_, member_type)| value_type(name, member_type))
+                .collect::<Result<_, _>>()?,
+        ),
+        AbiType::Pointer(element_type) => match **element_type {
+            AbiType::Pointer(_) => {
+                return Err(CodegenError::UnsupportedType {
+                    name: name.to_owned(),
+                    r#type: abi_type.to_string(),
+                })
+            }
+            _ => format!("Vec<{}>", value_type(name, element_type)?),
+        },
+    })
+}
+
+/// The Rust type of a method argument encoded as `abi_type`. Structs and arrays are borrowed.
+fn argument_type(name: &str, abi_type: &AbiType) -> Result<String, CodegenError> {
+    Ok(match abi_type {
+        AbiType::Struct(struct_type) if is_uint256(struct_type) => "U256".into(),
+        AbiType::Struct(struct_type) => format!("&{}", struct_name(struct_type)),
+        AbiType::Pointer(element_type) => format!("&[{}]", value_type(name, element_type)?),
+        _ => value_type(name, abi_type)?,
+    })
+}
+
+/// A tuple of `items`, nested in groups of 8 as tuples are only encoded up to 8 elements.
+/// Nesting doesn't change the encoding as tuples are encoded as their elements in order.
+fn tuple(items: Vec<String>) -> String {
+    if items.len() > 8 {
+        tuple(items.chunks(8).map(|chunk| tuple(chunk.to_vec())).collect())
+    } else if items.len() == 1 {
+        format!("({},)", items[0])
+    } else {
+        format!("({})", items.join(", "))
+    }
+}
+
+/// Whether Cairo struct `name` is the standard `Uint256`, bound as `U256`.
+fn is_uint256(name: &str) -> bool {
+    name.rsplit('.').next() == Some("Uint256")
+}
+
+/// The Rust name of Cairo struct `name`, from the last segment of its path.
+fn struct_name(name: &str) -> String {
+    to_upper_camel_case(name.rsplit('.').next().unwrap_or(name))
+}
+
+fn method_name(name: &str) -> String {
+    let method = to_snake_case(name);
+    if RESERVED_METHODS.contains(&method.as_str()) {
+        format!("{method}_")
+    } else {
+        to_ident(&method)
+    }
+}
+
+fn is_variant_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(first) if first.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "Self"
+}
+
+/// Escapes Rust keywords.
+fn to_ident(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        "as" | "async" | "await" | "break" | "const" | "continue" | "dyn" | "else" | "enum"
+        | "extern" | "false" | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match"
+        | "mod" | "move" | "mut" | "pub" | "ref" | "return" | "static" | "struct" | "trait"
+        | "true" | "type" | "unsafe" | "use" | "where" | "while" => format!("r#{name}"),
+        _ => name.to_owned(),
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+
+    for (ind, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && ind > 0 {
+            let previous = chars[ind - 1];
+            let next_is_lower =
+                matches!(chars.get(ind + 1), Some(next) if next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    snake
+}
+
+fn to_upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_names() {
+        assert_eq!(to_snake_case("balanceOf"), "balance_of");
+        assert_eq!(to_snake_case("tokenURI"), "token_uri");
+        assert_eq!(to_snake_case("ERC20Token"), "erc20_token");
+        assert_eq!(to_snake_case("__execute__"), "__execute__");
+        assert_eq!(to_upper_camel_case("account_created"), "AccountCreated");
+        assert_eq!(to_upper_camel_case("Transfer"), "Transfer");
+        assert_eq!(method_name("type"), "r#type");
+        assert_eq!(method_name("address"), "address_");
+        assert!(is_variant_name("InsufficientBalance"));
+        assert!(!is_variant_name("insufficient_balance"));
+        assert!(!is_variant_name("Self"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_uint256_convention() {
+        let abi = |members: serde_json::Value| -> Vec<AbiEntry> {
+            serde_json::from_value(serde_json::json!([
+                {
+                    "members": members,
+                    "name": "starkware.cairo.common.uint256.Uint256",
+                    "size": 2,
+                    "type": "struct"
+                },
+                {
+                    "inputs": [],
+                    "name": "totalSupply",
+                    "outputs": [{ "name": "supply", "type": "Uint256" }],
+                    "stateMutability": "view",
+                    "type": "function"
+                }
+            ]))
+            .unwrap()
+        };
+
+        let source = generate_source(
+            "Token",
+            &abi(serde_json::json!([
+                { "name": "low", "offset": 0, "type": "felt" },
+                { "name": "high", "offset": 1, "type": "felt" }
+            ])),
+        )
+        .unwrap();
+        assert!(source.contains("codec::U256"));
+        assert!(source.contains("FunctionCall<'_, P, U256>"));
+        assert!(!source.contains("pub struct Uint256"));
+
+        assert!(matches!(
+            generate_source(
+                "Token",
+                &abi(serde_json::json!([
+                    { "name": "high", "offset": 0, "type": "felt" },
+                    { "name": "low", "offset": 1, "type": "felt" }
+                ]))
+            ),
+            Err(CodegenError::UnsupportedType { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_nested_tuples() {
+        assert_eq!(tuple(vec![]), "()");
+        assert_eq!(tuple(vec!["a".into()]), "(a,)");
+        assert_eq!(
+            tuple((0..10).map(|ind| ind.to_string()).collect()),
+            "((0, 1, 2, 3, 4, 5, 6, 7), (8, 9))"
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use super::{Bindings, CodegenError};
+
+use serde::Deserialize;
+use starknet_core::types::AbiEntry;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "Scarb.toml";
+
+const DEFAULT_PROFILE: &str = "dev";
+
+/// A package of a Scarb workspace, for generating bindings from the contracts it compiles to.
+///
+/// Artifacts are located from the `{package}.starknet_artifacts.json` file Scarb writes in the
+/// target directory. Build scripts are rerun whenever the package is recompiled:
+///
+/// ```ignore
+/// // build.rs
+/// ScarbPackage::find("../contracts", "token")?
+///     .bindings("Token")?
+///     .generate(std::env::var("OUT_DIR")?)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScarbPackage {
+    name: String,
+    manifest_path: PathBuf,
+    target_dir: PathBuf,
+    profile: String,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<ManifestPackage>,
+    workspace: Option<ManifestWorkspace>,
+}
+
+#[derive(Deserialize)]
+struct ManifestPackage {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ManifestWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct StarknetArtifacts {
+    contracts: Vec<StarknetArtifactsContract>,
+}
+
+#[derive(Deserialize)]
+struct StarknetArtifactsContract {
+    package_name: String,
+    contract_name: String,
+    artifacts: StarknetArtifactsFiles,
+}
+
+#[derive(Deserialize)]
+struct StarknetArtifactsFiles {
+    sierra: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ContractClassAbi {
+    abi: serde_json::Value,
+}
+
+impl ScarbPackage {
+    /// Finds package `name` in the Scarb workspace containing `dir`. The workspace root is the
+    /// outermost `Scarb.toml` with a `[workspace]` section above `dir`, or the closest
+    /// `Scarb.toml` otherwise.
+    ///
+    /// The target directory is read from `SCARB_TARGET_DIR`, and defaults to `target` in the
+    /// workspace root.
+    pub fn find<P>(dir: P, name: &str) -> Result<Self, CodegenError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut root = None;
+        for ancestor in dir.as_ref().ancestors() {
+            let manifest_path = ancestor.join(MANIFEST_FILE);
+            if !manifest_path.is_file() {
+                continue;
+            }
+            let manifest = read_manifest(&manifest_path)?;
+            if root.is_none() || manifest.workspace.is_some() {
+                root = Some((ancestor.to_owned(), manifest_path, manifest));
+            }
+        }
+        let (root_dir, root_manifest_path, root_manifest) =
+            root.ok_or_else(|| CodegenError::ScarbPackageNotFound(name.to_owned()))?;
+
+        let target_dir = match std::env::var_os("SCARB_TARGET_DIR") {
+            Some(target_dir) => PathBuf::from(target_dir),
+            None => root_dir.join("target"),
+        };
+
+        let mut candidates = vec![(root_manifest_path, root_manifest.package)];
+        for member in root_manifest
+            .workspace
+            .iter()
+            .flat_map(|workspace| workspace.members.iter())
+        {
+            for member_dir in expand_member(&root_dir, member)? {
+                let manifest_path = member_dir.join(MANIFEST_FILE);
+                if manifest_path.is_file() {
+                    let package = read_manifest(&manifest_path)?.package;
+                    candidates.push((manifest_path, package));
+                }
+            }
+        }
+
+        candidates
+            .into_iter()
+            .find_map(|(manifest_path, package)| match package {
+                Some(package) if package.name == name => Some(Self {
+                    name: package.name,
+                    manifest_path,
+                    target_dir: target_dir.clone(),
+                    profile: DEFAULT_PROFILE.to_owned(),
+                }),
+                _ => None,
+            })
+            .ok_or_else(|| CodegenError::ScarbPackageNotFound(name.to_owned()))
+    }
+
+    /// Sets the profile the package is compiled with. Defaults to `dev`.
+    pub fn profile(self, profile: &str) -> Self {
+        Self {
+            profile: profile.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn manifest_path(&self) -> &Path {
+        &self.manifest_path
+    }
+
+    /// The directory the package artifacts are written to, i.e. `{target_dir}/{profile}`.
+    pub fn artifacts_dir(&self) -> PathBuf {
+        self.target_dir.join(&self.profile)
+    }
+
+    /// Reads the ABI of contract `contract` from the package artifacts, and returns its
+    /// bindings, named after the contract.
+    ///
+    /// When called from a build script, Cargo is instructed to rerun it whenever the package is
+    /// recompiled, or its manifest changes.
+    pub fn bindings(&self, contract: &str) -> Result<Bindings, CodegenError> {
+        let artifacts_dir = self.artifacts_dir();
+        let artifacts_path = artifacts_dir.join(format!("{}.starknet_artifacts.json", self.name));
+        let artifacts: StarknetArtifacts = read_json(&artifacts_path)?;
+
+        let sierra = artifacts
+            .contracts
+            .into_iter()
+            .find(|item| item.package_name == self.name && item.contract_name == contract)
+            .and_then(|item| item.artifacts.sierra)
+            .ok_or_else(|| CodegenError::ScarbContractNotFound {
+                package: self.name.clone(),
+                contract: contract.to_owned(),
+            })?;
+        let class_path = artifacts_dir.join(sierra);
+        let class: ContractClassAbi = read_json(&class_path)?;
+
+        // Older Scarb versions write the ABI as a JSON string
+        let abi: Vec<AbiEntry> = match class.abi {
+            serde_json::Value::String(abi) => serde_json::from_str(&abi),
+            abi => serde_json::from_value(abi),
+        }
+        .map_err(|error| CodegenError::Artifact {
+            path: class_path.clone(),
+            error,
+        })?;
+
+        if std::env::var_os("OUT_DIR").is_some() {
+            for path in [&self.manifest_path, &artifacts_path, &class_path] {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+
+        Ok(Bindings::new(contract, abi))
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, CodegenError> {
+    toml::from_str(&std::fs::read_to_string(path).map_err(CodegenError::Io)?).map_err(|error| {
+        CodegenError::Manifest {
+            path: path.to_owned(),
+            error,
+        }
+    })
+}
+
+fn read_json<T>(path: &Path) -> Result<T, CodegenError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(&std::fs::read_to_string(path).map_err(CodegenError::Io)?).map_err(
+        |error| CodegenError::Artifact {
+            path: path.to_owned(),
+            error,
+        },
+    )
+}
+
+/// Expands a workspace member path, where the last segment can be `*` to include all
+/// directories.
+fn expand_member(root_dir: &Path, member: &str) -> Result<Vec<PathBuf>, CodegenError> {
+    match member.strip_suffix("/*") {
+        Some(parent) => {
+            let mut dirs = std::fs::read_dir(root_dir.join(parent))
+                .map_err(CodegenError::Io)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect::<Vec<_>>();
+            dirs.sort();
+            Ok(dirs)
+        }
+        None => Ok(vec![root_dir.join(member)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a workspace with packages `token` and `vault` compiled with the `dev` profile.
+    fn write_workspace(root: &Path) {
+        let files = [
+            ("Scarb.toml", "[workspace]\nmembers = [\"packages/*\"]\n"),
+            (
+                "packages/token/Scarb.toml",
+                "[package]\nname = \"token\"\nversion = \"0.1.0\"\n",
+            ),
+            (
+                "packages/vault/Scarb.toml",
+                "[package]\nname = \"vault\"\nversion = \"0.1.0\"\n",
+            ),
+            (
+                "target/dev/token.starknet_artifacts.json",
+                r#"{"version":1,"contracts":[{"id":"1","package_name":"token","contract_name":"Token","artifacts":{"sierra":"token_Token.contract_class.json","casm":null}}]}"#,
+            ),
+            (
+                "target/dev/token_Token.contract_class.json",
+                r#"{"abi":"[{\"inputs\":[],\"name\":\"name\",\"outputs\":[{\"name\":\"name\",\"type\":\"felt\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]"}"#,
+            ),
+        ];
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_package_bindings() {
+        let root = std::env::temp_dir().join(format!("starknet-rs-scarb-{}", std::process::id()));
+        write_workspace(&root);
+
+        let package = ScarbPackage::find(root.join("packages/token/src"), "token").unwrap();
+        assert_eq!(package.name(), "token");
+        assert_eq!(
+            package.manifest_path(),
+            root.join("packages/token/Scarb.toml")
+        );
+        assert_eq!(package.artifacts_dir(), root.join("target/dev"));
+
+        let source = package
+            .bindings("Token")
+            .unwrap()
+            .generate_source()
+            .unwrap();
+        assert!(source.contains("pub struct Token<P>"));
+        assert!(source.contains("pub fn name(&self)"));
+
+        assert!(matches!(
+            package.bindings("Vault"),
+            Err(CodegenError::ScarbContractNotFound { .. })
+        ));
+        assert!(matches!(
+            ScarbPackage::find(&root, "unknown"),
+            Err(CodegenError::ScarbPackageNotFound(_))
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
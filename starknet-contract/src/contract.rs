@@ -0,0 +1,199 @@
+use starknet_accounts::{Account, Call, Execution};
+use starknet_core::{
+    codec::{CairoDeserialize, CairoSerialize, DecodeError},
+    types::{
+        AsStarknetError, BlockId, CallFunction, CallL1Handler, FieldElement, L1Address,
+        StarknetError,
+    },
+};
+use starknet_providers::{Provider, ProviderError};
+use std::marker::PhantomData;
+
+/// A deployed contract, used as the base of typed bindings.
+///
+/// Bindings wrap a `Contract` and expose one method per entrypoint returning a [FunctionCall],
+/// so that the same typed surface is used for reading with [call](FunctionCall::call) and
+/// writing with [invoke](FunctionCall::invoke):
+///
+/// ```ignore
+/// let balance = token.balance_of(holder).call().await?;
+/// token.transfer(recipient, amount).invoke(&account).send().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Contract<P> {
+    address: FieldElement,
+    provider: P,
+}
+
+/// A call to a contract function returning `R`.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct FunctionCall<'p, P, R> {
+    provider: &'p P,
+    call: Call,
+    block_id: BlockId,
+    _output: PhantomData<fn() -> R>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContractCallError<P> {
+    #[error(transparent)]
+    Provider(ProviderError<P>),
+    #[error("failed to decode call result: {0}")]
+    Decode(#[source] DecodeError),
+}
+
+impl<P> AsStarknetError for ContractCallError<P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Provider(err) => err.starknet_error(),
+            Self::Decode(_) => None,
+        }
+    }
+}
+
+impl<P> Contract<P> {
+    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self { address, provider }
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Builds a call to the function with `selector`, encoding `args` as its calldata.
+    pub fn function<A, R>(&self, selector: FieldElement, args: &A) -> FunctionCall<'_, P, R>
+    where
+        A: CairoSerialize + ?Sized,
+    {
+        FunctionCall {
+            provider: &self.provider,
+            call: Call {
+                to: self.address,
+                selector,
+                calldata: args.cairo_serialized(),
+            },
+            block_id: BlockId::Latest,
+            _output: PhantomData,
+        }
+    }
+
+    /// Builds an L1 to L2 message from the L1 contract at `from_address` to the L1 handler with
+    /// `selector`, encoding `args` as its payload. The L1 sender, which the sequencer passes as
+    /// the first handler argument, is not part of `args`.
+    ///
+    /// The message can be used to [estimate](Provider::estimate_message_fee) the L2 fee to pay
+    /// when sending it from L1.
+    pub fn l1_handler<A>(
+        &self,
+        from_address: L1Address,
+        selector: FieldElement,
+        args: &A,
+    ) -> CallL1Handler
+    where
+        A: CairoSerialize + ?Sized,
+    {
+        CallL1Handler {
+            from_address,
+            to_address: self.address,
+            entry_point_selector: selector,
+            payload: args.cairo_serialized(),
+        }
+    }
+}
+
+impl<'p, P, R> FunctionCall<'p, P, R> {
+    /// Sets the block to [call](Self::call) the function at. Defaults to [BlockId::Latest].
+    pub fn block_id(self, block_id: BlockId) -> Self {
+        Self { block_id, ..self }
+    }
+
+    /// The underlying call, e.g. to batch it with other calls in an account multicall.
+    pub fn as_call(&self) -> &Call {
+        &self.call
+    }
+
+    pub fn into_call(self) -> Call {
+        self.call
+    }
+
+    /// Sends the call in a transaction from `account`.
+    pub fn invoke<'a, A>(&self, account: &'a A) -> Execution<'a, A>
+    where
+        A: Account,
+    {
+        Execution::new(vec![self.call.clone()], account)
+    }
+}
+
+impl<'p, P, R> FunctionCall<'p, P, R>
+where
+    P: Provider + Sync,
+    R: CairoDeserialize,
+{
+    /// Calls the function without sending a transaction, and decodes its result.
+    pub async fn call(&self) -> Result<R, ContractCallError<P::Error>> {
+        let result = self
+            .provider
+            .call_contract(
+                CallFunction {
+                    contract_address: self.call.to,
+                    entry_point_selector: self.call.selector,
+                    calldata: self.call.calldata.clone(),
+                },
+                self.block_id,
+            )
+            .await
+            .map_err(ContractCallError::Provider)?;
+
+        R::cairo_deserialize_all(&result.result).map_err(ContractCallError::Decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::{codec::U256, utils::get_selector_from_name};
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_function_call() {
+        let contract = Contract::new(FieldElement::ONE, ());
+        let selector = get_selector_from_name("transfer").unwrap();
+
+        let call: FunctionCall<'_, (), bool> =
+            contract.function(selector, &(FieldElement::TWO, U256::from(3u128)));
+
+        assert_eq!(call.as_call().to, FieldElement::ONE);
+        assert_eq!(call.as_call().selector, selector);
+        assert_eq!(
+            call.into_call().calldata,
+            vec![FieldElement::TWO, FieldElement::THREE, FieldElement::ZERO]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_l1_handler() {
+        let contract = Contract::new(FieldElement::ONE, ());
+        let selector = get_selector_from_name("handle_deposit").unwrap();
+
+        let message = contract.l1_handler(
+            L1Address::from_low_u64_be(0x99),
+            selector,
+            &(FieldElement::TWO, U256::from(3u128)),
+        );
+
+        assert_eq!(message.to_address, FieldElement::ONE);
+        assert_eq!(message.entry_point_selector, selector);
+        assert_eq!(
+            message.payload,
+            vec![FieldElement::TWO, FieldElement::THREE, FieldElement::ZERO]
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Bindings for the standard ERC-1155 multi token interface.
+
+use crate::{
+    erc20::SELECTOR_BALANCEOF,
+    erc721::{SELECTOR_ISAPPROVEDFORALL, SELECTOR_SAFETRANSFERFROM, SELECTOR_SETAPPROVALFORALL},
+    events::decode_flattened,
+    Contract, FunctionCall,
+};
+
+use starknet_core::{
+    codec::{CairoDeserialize, U256},
+    event::{DecodeEventError, StarknetEvent},
+    types::FieldElement,
+};
+
+pub use crate::erc721::ApprovalForAll;
+
+/// Selector for entrypoint `balanceOfBatch`.
+const SELECTOR_BALANCEOFBATCH: FieldElement = FieldElement::from_mont([
+    5512904707656843458,
+    2574575814178348050,
+    17430642732742754437,
+    126961162493453816,
+]);
+
+/// Selector for entrypoint `safeBatchTransferFrom`.
+const SELECTOR_SAFEBATCHTRANSFERFROM: FieldElement = FieldElement::from_mont([
+    17079577542979802834,
+    16338293639958900149,
+    14411753658323890046,
+    422214866545774394,
+]);
+
+/// Selector for entrypoint `uri`.
+const SELECTOR_URI: FieldElement = FieldElement::from_mont([
+    12154038282399583974,
+    12491502373649585869,
+    2977383532908965772,
+    406055095285422915,
+]);
+
+/// Selector for event `TransferSingle`.
+const SELECTOR_TRANSFERSINGLE_EVENT: FieldElement = FieldElement::from_mont([
+    1986363494579022220,
+    17146673375846491535,
+    6125027481420860397,
+    307829215948623223,
+]);
+
+/// Selector for event `TransferBatch`.
+const SELECTOR_TRANSFERBATCH_EVENT: FieldElement = FieldElement::from_mont([
+    14114721770411318090,
+    10106114908748783105,
+    12894248477188639378,
+    518981439849896716,
+]);
+
+/// Selector for event `URI`.
+const SELECTOR_URI_EVENT: FieldElement = FieldElement::from_mont([
+    4293200714848753324,
+    10179592155775228328,
+    17989438278816899127,
+    418252884612363975,
+]);
+
+/// An ERC-1155 token, using the camelCase entrypoints supported by both Cairo 0 and Cairo 1
+/// tokens.
+#[derive(Debug, Clone)]
+pub struct Erc1155<P> {
+    contract: Contract<P>,
+}
+
+/// The `TransferSingle` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferSingle {
+    pub operator: FieldElement,
+    pub from: FieldElement,
+    pub to: FieldElement,
+    pub id: U256,
+    pub value: U256,
+}
+
+/// The `TransferBatch` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferBatch {
+    pub operator: FieldElement,
+    pub from: FieldElement,
+    pub to: FieldElement,
+    pub ids: Vec<U256>,
+    pub values: Vec<U256>,
+}
+
+/// The `URI` event. As with [Erc1155::uri], `R` is a [FieldElement] for Cairo 0 tokens and a
+/// [ByteArray](starknet_core::codec::ByteArray) for Cairo 1 tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uri<R> {
+    pub value: R,
+    pub id: U256,
+}
+
+impl<P> Erc1155<P> {
+    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self {
+            contract: Contract::new(address, provider),
+        }
+    }
+
+    pub fn contract(&self) -> &Contract<P> {
+        &self.contract
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.contract.address()
+    }
+
+    pub fn balance_of(&self, account: FieldElement, id: U256) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_BALANCEOF, &(account, id))
+    }
+
+    /// Returns the balance of each account for the token id at the same position.
+    pub fn balance_of_batch(
+        &self,
+        accounts: &[FieldElement],
+        ids: &[U256],
+    ) -> FunctionCall<'_, P, Vec<U256>> {
+        self.contract
+            .function(SELECTOR_BALANCEOFBATCH, &(accounts, ids))
+    }
+
+    pub fn is_approved_for_all(
+        &self,
+        account: FieldElement,
+        operator: FieldElement,
+    ) -> FunctionCall<'_, P, bool> {
+        self.contract
+            .function(SELECTOR_ISAPPROVEDFORALL, &(account, operator))
+    }
+
+    pub fn set_approval_for_all(
+        &self,
+        operator: FieldElement,
+        approved: bool,
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract
+            .function(SELECTOR_SETAPPROVALFORALL, &(operator, approved))
+    }
+
+    pub fn safe_transfer_from(
+        &self,
+        from: FieldElement,
+        to: FieldElement,
+        id: U256,
+        value: U256,
+        data: &[FieldElement],
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract
+            .function(SELECTOR_SAFETRANSFERFROM, &(from, to, id, value, data))
+    }
+
+    pub fn safe_batch_transfer_from(
+        &self,
+        from: FieldElement,
+        to: FieldElement,
+        ids: &[U256],
+        values: &[U256],
+        data: &[FieldElement],
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract.function(
+            SELECTOR_SAFEBATCHTRANSFERFROM,
+            &(from, to, ids, values, data),
+        )
+    }
+
+    /// The metadata URI of token `id`. Cairo 0 tokens return a short string, to be decoded as
+    /// a [FieldElement], while Cairo 1 tokens return a
+    /// [ByteArray](starknet_core::codec::ByteArray).
+    pub fn uri<R>(&self, id: U256) -> FunctionCall<'_, P, R> {
+        self.contract.function(SELECTOR_URI, &id)
+    }
+}
+
+impl StarknetEvent for TransferSingle {
+    fn selector() -> FieldElement {
+        SELECTOR_TRANSFERSINGLE_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                operator: reader.read()?,
+                from: reader.read()?,
+                to: reader.read()?,
+                id: reader.read()?,
+                value: reader.read()?,
+            })
+        })
+    }
+}
+
+impl StarknetEvent for TransferBatch {
+    fn selector() -> FieldElement {
+        SELECTOR_TRANSFERBATCH_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                operator: reader.read()?,
+                from: reader.read()?,
+                to: reader.read()?,
+                ids: reader.read()?,
+                values: reader.read()?,
+            })
+        })
+    }
+}
+
+impl<R> StarknetEvent for Uri<R>
+where
+    R: CairoDeserialize,
+{
+    fn selector() -> FieldElement {
+        SELECTOR_URI_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                value: reader.read()?,
+                id: reader.read()?,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::{codec::ByteArray, utils::get_selector_from_name};
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_selectors() {
+        for (selector, name) in [
+            (SELECTOR_BALANCEOFBATCH, "balanceOfBatch"),
+            (SELECTOR_SAFEBATCHTRANSFERFROM, "safeBatchTransferFrom"),
+            (SELECTOR_URI, "uri"),
+            (SELECTOR_TRANSFERSINGLE_EVENT, "TransferSingle"),
+            (SELECTOR_TRANSFERBATCH_EVENT, "TransferBatch"),
+            (SELECTOR_URI_EVENT, "URI"),
+        ] {
+            assert_eq!(selector, get_selector_from_name(name).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_balance_of_batch_calldata() {
+        let token = Erc1155::new(FieldElement::ONE, ());
+
+        let call = token
+            .balance_of_batch(
+                &[FieldElement::TWO, FieldElement::THREE],
+                &[U256::from(4u128), U256::from(5u128)],
+            )
+            .into_call();
+
+        assert_eq!(
+            call.calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::TWO,
+                FieldElement::THREE,
+                FieldElement::TWO,
+                FieldElement::from(4u8),
+                FieldElement::ZERO,
+                FieldElement::from(5u8),
+                FieldElement::ZERO,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_events() {
+        assert_eq!(
+            TransferBatch::decode(
+                &[
+                    SELECTOR_TRANSFERBATCH_EVENT,
+                    FieldElement::ONE,
+                    FieldElement::TWO,
+                    FieldElement::THREE
+                ],
+                &[
+                    FieldElement::ONE,
+                    FieldElement::from(7u8),
+                    FieldElement::ZERO,
+                    FieldElement::ONE,
+                    FieldElement::from(9u8),
+                    FieldElement::ZERO
+                ]
+            )
+            .unwrap(),
+            TransferBatch {
+                operator: FieldElement::ONE,
+                from: FieldElement::TWO,
+                to: FieldElement::THREE,
+                ids: vec![U256::from(7u128)],
+                values: vec![U256::from(9u128)],
+            }
+        );
+
+        let value = ByteArray(b"ipfs://x".to_vec());
+        let mut data = starknet_core::codec::CairoSerialize::cairo_serialized(&value);
+        data.extend([FieldElement::ONE, FieldElement::ZERO]);
+
+        assert_eq!(
+            Uri::<ByteArray>::decode(&[SELECTOR_URI_EVENT], &data).unwrap(),
+            Uri {
+                value,
+                id: U256::from(1u128),
+            }
+        );
+    }
+}
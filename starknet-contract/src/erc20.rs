@@ -0,0 +1,608 @@
+//! Bindings for the standard ERC-20 token interface.
+
+use crate::{events::decode_flattened, Contract, ContractCallError, ContractError, FunctionCall};
+
+use starknet_core::{
+    codec::U256,
+    event::{DecodeEventError, StarknetEvent},
+    types::{AsStarknetError, FieldElement, StarknetError},
+};
+use starknet_providers::Provider;
+use std::sync::OnceLock;
+
+/// Selector for entrypoint `name`.
+pub(crate) const SELECTOR_NAME: FieldElement = FieldElement::from_mont([
+    4539611826636167848,
+    2380157814635835479,
+    16059280649635539212,
+    204437639094763333,
+]);
+
+/// Selector for entrypoint `symbol`.
+pub(crate) const SELECTOR_SYMBOL: FieldElement = FieldElement::from_mont([
+    9178143007560336762,
+    17130963829830369960,
+    11796451914517155703,
+    179664498801601103,
+]);
+
+/// Selector for entrypoint `decimals`.
+const SELECTOR_DECIMALS: FieldElement = FieldElement::from_mont([
+    15360137715940544477,
+    12219577301920418346,
+    755531479336054762,
+    451190754876481978,
+]);
+
+/// Selector for entrypoint `totalSupply`.
+pub(crate) const SELECTOR_TOTALSUPPLY: FieldElement = FieldElement::from_mont([
+    18112103592448476716,
+    16591666299386464833,
+    7202072203292561311,
+    69716027446197474,
+]);
+
+/// Selector for entrypoint `balanceOf`.
+pub(crate) const SELECTOR_BALANCEOF: FieldElement = FieldElement::from_mont([
+    8914400797191611589,
+    3817639149632004388,
+    9799122768618501063,
+    186492163330788704,
+]);
+
+/// Selector for entrypoint `allowance`.
+const SELECTOR_ALLOWANCE: FieldElement = FieldElement::from_mont([
+    10308380584186584568,
+    17678706014116587206,
+    12509652079980974336,
+    209766809471042785,
+]);
+
+/// Selector for entrypoint `transfer`.
+const SELECTOR_TRANSFER: FieldElement = FieldElement::from_mont([
+    5927927059297104468,
+    16370534037708042650,
+    2507318034922653180,
+    437381113334062809,
+]);
+
+/// Selector for entrypoint `transferFrom`.
+pub(crate) const SELECTOR_TRANSFERFROM: FieldElement = FieldElement::from_mont([
+    12809927063331595464,
+    3869861725982937294,
+    11765991293598804043,
+    427849668778881624,
+]);
+
+/// Selector for entrypoint `approve`.
+pub(crate) const SELECTOR_APPROVE: FieldElement = FieldElement::from_mont([
+    12534173288940467319,
+    7107368782042727669,
+    13695063774359579960,
+    140600710993877394,
+]);
+
+/// Selector for event `Transfer`.
+pub(crate) const SELECTOR_TRANSFER_EVENT: FieldElement = FieldElement::from_mont([
+    10370298062762752593,
+    7288672513944573579,
+    6148261015514870755,
+    242125613396778233,
+]);
+
+/// Selector for event `Approval`.
+pub(crate) const SELECTOR_APPROVAL_EVENT: FieldElement = FieldElement::from_mont([
+    4542959414476676063,
+    1649662217974350398,
+    11875504234058983903,
+    88955340742465057,
+]);
+
+/// An ERC-20 token, using the camelCase entrypoints supported by both Cairo 0 and Cairo 1
+/// tokens.
+///
+/// Amounts can be handled in token units, e.g. `1.5`, instead of raw integers with
+/// [balance_of_formatted](Erc20::balance_of_formatted) and
+/// [transfer_units](Erc20::transfer_units), using decimals fetched once per binding.
+#[derive(Debug, Clone)]
+pub struct Erc20<P> {
+    contract: Contract<P>,
+    decimals: OnceLock<u8>,
+}
+
+/// The `Transfer` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: FieldElement,
+    pub to: FieldElement,
+    pub value: U256,
+}
+
+/// The `Approval` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    pub owner: FieldElement,
+    pub spender: FieldElement,
+    pub value: U256,
+}
+
+/// Errors reverted with by standard tokens, from the messages of both the Cairo 0 and Cairo 1
+/// OpenZeppelin implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Erc20Error {
+    #[error("transfer amount exceeds balance")]
+    InsufficientBalance,
+    #[error("insufficient allowance")]
+    InsufficientAllowance,
+    #[error("cannot transfer from the zero address")]
+    TransferFromZero,
+    #[error("cannot transfer to the zero address")]
+    TransferToZero,
+    #[error("cannot approve from the zero address")]
+    ApproveFromZero,
+    #[error("cannot approve to the zero address")]
+    ApproveToZero,
+    #[error("amount is not a valid Uint256")]
+    InvalidAmount,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseUnitsError {
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("amount has more than {0} decimals")]
+    TooManyDecimals(u8),
+    #[error("amount overflows u256")]
+    Overflow,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UnitsError<P> {
+    #[error("failed to fetch decimals: {0}")]
+    Decimals(#[source] ContractCallError<P>),
+    #[error(transparent)]
+    Parse(ParseUnitsError),
+}
+
+impl<P> AsStarknetError for UnitsError<P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Decimals(err) => err.starknet_error(),
+            Self::Parse(_) => None,
+        }
+    }
+}
+
+impl<P> Erc20<P> {
+    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self {
+            contract: Contract::new(address, provider),
+            decimals: OnceLock::new(),
+        }
+    }
+
+    /// Uses known token decimals instead of fetching them.
+    pub fn with_decimals(self, decimals: u8) -> Self {
+        Self {
+            decimals: OnceLock::from(decimals),
+            ..self
+        }
+    }
+
+    pub fn contract(&self) -> &Contract<P> {
+        &self.contract
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.contract.address()
+    }
+
+    /// The token name as a Cairo short string, which can be parsed with
+    /// [parse_cairo_short_string](starknet_core::utils::parse_cairo_short_string).
+    pub fn name(&self) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_NAME, &())
+    }
+
+    /// The token symbol as a Cairo short string, which can be parsed with
+    /// [parse_cairo_short_string](starknet_core::utils::parse_cairo_short_string).
+    pub fn symbol(&self) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_SYMBOL, &())
+    }
+
+    pub fn decimals(&self) -> FunctionCall<'_, P, u8> {
+        self.contract.function(SELECTOR_DECIMALS, &())
+    }
+
+    pub fn total_supply(&self) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_TOTALSUPPLY, &())
+    }
+
+    pub fn balance_of(&self, account: FieldElement) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_BALANCEOF, &account)
+    }
+
+    pub fn allowance(
+        &self,
+        owner: FieldElement,
+        spender: FieldElement,
+    ) -> FunctionCall<'_, P, U256> {
+        self.contract
+            .function(SELECTOR_ALLOWANCE, &(owner, spender))
+    }
+
+    pub fn transfer(&self, recipient: FieldElement, amount: U256) -> FunctionCall<'_, P, bool> {
+        self.contract
+            .function(SELECTOR_TRANSFER, &(recipient, amount))
+    }
+
+    pub fn transfer_from(
+        &self,
+        sender: FieldElement,
+        recipient: FieldElement,
+        amount: U256,
+    ) -> FunctionCall<'_, P, bool> {
+        self.contract
+            .function(SELECTOR_TRANSFERFROM, &(sender, recipient, amount))
+    }
+
+    pub fn approve(&self, spender: FieldElement, amount: U256) -> FunctionCall<'_, P, bool> {
+        self.contract.function(SELECTOR_APPROVE, &(spender, amount))
+    }
+}
+
+impl<P> Erc20<P>
+where
+    P: Provider + Sync,
+{
+    /// The token decimals, fetched on first use and cached afterwards.
+    pub async fn cached_decimals(&self) -> Result<u8, ContractCallError<P::Error>> {
+        if let Some(decimals) = self.decimals.get() {
+            return Ok(*decimals);
+        }
+
+        let decimals = self.decimals().call().await?;
+        Ok(*self.decimals.get_or_init(|| decimals))
+    }
+
+    /// The balance of `account` in token units, e.g. `1.5`.
+    pub async fn balance_of_formatted(
+        &self,
+        account: FieldElement,
+    ) -> Result<String, ContractCallError<P::Error>> {
+        let decimals = self.cached_decimals().await?;
+        let balance = self.balance_of(account).call().await?;
+        Ok(format_units(balance, decimals))
+    }
+
+    /// A transfer of `amount` in token units, e.g. `"1.5"`.
+    pub async fn transfer_units(
+        &self,
+        recipient: FieldElement,
+        amount: &str,
+    ) -> Result<FunctionCall<'_, P, bool>, UnitsError<P::Error>> {
+        let decimals = self.cached_decimals().await.map_err(UnitsError::Decimals)?;
+        let amount = parse_units(amount, decimals).map_err(UnitsError::Parse)?;
+        Ok(self.transfer(recipient, amount))
+    }
+}
+
+/// Formats a raw `amount` in units of a token with `decimals` decimals, without trailing zeros.
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let mut digits = vec![];
+    let mut value = amount;
+    while value != U256::default() {
+        let (quotient, digit) = div_rem_10(value);
+        digits.push(b'0' + digit);
+        value = quotient;
+    }
+    digits.resize(digits.len().max(decimals as usize + 1), b'0');
+    digits.reverse();
+
+    let (integer, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let fraction = match fraction.iter().rposition(|digit| *digit != b'0') {
+        Some(last) => &fraction[..=last],
+        None => &[],
+    };
+
+    let mut formatted = String::from_utf8_lossy(integer).into_owned();
+    if !fraction.is_empty() {
+        formatted.push('.');
+        formatted.push_str(&String::from_utf8_lossy(fraction));
+    }
+    formatted
+}
+
+/// Parses an amount in units of a token with `decimals` decimals, e.g. `1.5`, into its raw
+/// value.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<U256, ParseUnitsError> {
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|digit| digit.is_ascii_digit())
+    {
+        return Err(ParseUnitsError::InvalidAmount(amount.to_owned()));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(ParseUnitsError::TooManyDecimals(decimals));
+    }
+
+    integer
+        .bytes()
+        .chain(fraction.bytes())
+        .map(|digit| digit - b'0')
+        .chain(std::iter::repeat_n(0, decimals as usize - fraction.len()))
+        .try_fold(U256::default(), mul_10_add)
+        .ok_or(ParseUnitsError::Overflow)
+}
+
+fn div_rem_10(value: U256) -> (U256, u8) {
+    // The high word remainder is below 10, so shifting it into the low word can't overflow
+    // when split in 64-bit halves.
+    let high = value.high / 10;
+    let mut remainder = value.high % 10;
+
+    let mut low = 0u128;
+    for shift in [64, 0] {
+        let part = (remainder << 64) | ((value.low >> shift) & u64::MAX as u128);
+        low |= (part / 10) << shift;
+        remainder = part % 10;
+    }
+
+    (U256::from_words(low, high), remainder as u8)
+}
+
+fn mul_10_add(value: U256, digit: u8) -> Option<U256> {
+    let (low, carry) = mul_add_u128(value.low, 10, digit as u128);
+    let (high, overflow) = mul_add_u128(value.high, 10, carry);
+    match overflow {
+        0 => Some(U256::from_words(low, high)),
+        _ => None,
+    }
+}
+
+/// Computes `value * mul + add` as a low word and a carry, with `mul` and `add` small enough
+/// to not overflow 64-bit halves.
+fn mul_add_u128(value: u128, mul: u128, add: u128) -> (u128, u128) {
+    let low = (value & u64::MAX as u128) * mul + add;
+    let high = (value >> 64) * mul + (low >> 64);
+    ((high << 64) | (low & u64::MAX as u128), high >> 64)
+}
+
+impl ContractError for Erc20Error {
+    fn from_revert_reason(reason: &str) -> Option<Self> {
+        Some(match reason {
+            "ERC20: transfer amount exceeds balance" | "ERC20: insufficient balance" => {
+                Self::InsufficientBalance
+            }
+            "ERC20: insufficient allowance" => Self::InsufficientAllowance,
+            "ERC20: cannot transfer from the zero address" | "ERC20: transfer from 0" => {
+                Self::TransferFromZero
+            }
+            "ERC20: cannot transfer to the zero address" | "ERC20: transfer to 0" => {
+                Self::TransferToZero
+            }
+            "ERC20: cannot approve from the zero address" | "ERC20: approve from 0" => {
+                Self::ApproveFromZero
+            }
+            "ERC20: cannot approve to the zero address" | "ERC20: approve to 0" => {
+                Self::ApproveToZero
+            }
+            "ERC20: amount is not a valid Uint256" => Self::InvalidAmount,
+            _ => return None,
+        })
+    }
+}
+
+/// Cairo 1 tokens emit both addresses as keys, while Cairo 0 tokens emit everything as data.
+impl StarknetEvent for Transfer {
+    fn selector() -> FieldElement {
+        SELECTOR_TRANSFER_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                from: reader.read()?,
+                to: reader.read()?,
+                value: reader.read()?,
+            })
+        })
+    }
+}
+
+/// Cairo 1 tokens emit both addresses as keys, while Cairo 0 tokens emit everything as data.
+impl StarknetEvent for Approval {
+    fn selector() -> FieldElement {
+        SELECTOR_APPROVAL_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                owner: reader.read()?,
+                spender: reader.read()?,
+                value: reader.read()?,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::utils::get_selector_from_name;
+    use starknet_providers::SequencerGatewayProvider;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_selectors() {
+        for (selector, name) in [
+            (SELECTOR_NAME, "name"),
+            (SELECTOR_SYMBOL, "symbol"),
+            (SELECTOR_DECIMALS, "decimals"),
+            (SELECTOR_TOTALSUPPLY, "totalSupply"),
+            (SELECTOR_BALANCEOF, "balanceOf"),
+            (SELECTOR_ALLOWANCE, "allowance"),
+            (SELECTOR_TRANSFER, "transfer"),
+            (SELECTOR_TRANSFERFROM, "transferFrom"),
+            (SELECTOR_APPROVE, "approve"),
+            (SELECTOR_TRANSFER_EVENT, "Transfer"),
+            (SELECTOR_APPROVAL_EVENT, "Approval"),
+        ] {
+            assert_eq!(selector, get_selector_from_name(name).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_transfer_calldata() {
+        let token = Erc20::new(FieldElement::ONE, ());
+
+        let call = token
+            .transfer(FieldElement::TWO, U256::from_words(5, 1))
+            .into_call();
+
+        assert_eq!(call.selector, SELECTOR_TRANSFER);
+        assert_eq!(
+            call.calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::from(5u8),
+                FieldElement::ONE
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_transfer_layouts() {
+        let expected = Transfer {
+            from: FieldElement::ONE,
+            to: FieldElement::TWO,
+            value: U256::from(3u128),
+        };
+
+        // Cairo 0
+        assert_eq!(
+            Transfer::decode(
+                &[SELECTOR_TRANSFER_EVENT],
+                &[
+                    FieldElement::ONE,
+                    FieldElement::TWO,
+                    FieldElement::THREE,
+                    FieldElement::ZERO
+                ]
+            )
+            .unwrap(),
+            expected
+        );
+
+        // Cairo 1
+        assert_eq!(
+            Transfer::decode(
+                &[
+                    SELECTOR_TRANSFER_EVENT,
+                    FieldElement::ONE,
+                    FieldElement::TWO
+                ],
+                &[FieldElement::THREE, FieldElement::ZERO]
+            )
+            .unwrap(),
+            expected
+        );
+
+        assert!(Approval::decode(&[SELECTOR_TRANSFER_EVENT], &[]).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_units() {
+        assert_eq!(format_units(U256::from(1_500_000u128), 6), "1.5");
+        assert_eq!(format_units(U256::from(1_000_000u128), 6), "1");
+        assert_eq!(format_units(U256::from(42u128), 6), "0.000042");
+        assert_eq!(format_units(U256::default(), 18), "0");
+        assert_eq!(format_units(U256::from(12u128), 0), "12");
+        assert_eq!(
+            format_units(U256::from_words(u128::MAX, u128::MAX), 18),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+
+        assert_eq!(parse_units("1.5", 6).unwrap(), U256::from(1_500_000u128));
+        assert_eq!(parse_units("1.50", 1).unwrap(), U256::from(15u128));
+        assert_eq!(parse_units(".25", 2).unwrap(), U256::from(25u128));
+        assert_eq!(parse_units("3", 0).unwrap(), U256::from(3u128));
+        assert_eq!(
+            parse_units(
+                "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+                18
+            )
+            .unwrap(),
+            U256::from_words(u128::MAX, u128::MAX)
+        );
+        assert!(matches!(
+            parse_units("1.234", 2),
+            Err(ParseUnitsError::TooManyDecimals(2))
+        ));
+        assert!(matches!(
+            parse_units(
+                "115792089237316195423570985008687907853269984665640564039458",
+                18
+            ),
+            Err(ParseUnitsError::Overflow)
+        ));
+        for invalid in ["", ".", "1,5", "-1", "1.2.3"] {
+            assert!(matches!(
+                parse_units(invalid, 18),
+                Err(ParseUnitsError::InvalidAmount(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_units() {
+        let token = Erc20::new(
+            FieldElement::ONE,
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+        )
+        .with_decimals(6);
+
+        assert_eq!(token.cached_decimals().await.unwrap(), 6);
+        assert_eq!(
+            token
+                .transfer_units(FieldElement::TWO, "1.5")
+                .await
+                .unwrap()
+                .into_call()
+                .calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::from(1_500_000u32),
+                FieldElement::ZERO
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_errors() {
+        assert_eq!(
+            Erc20Error::from_error_message(
+                "Error at pc=0:10:\nError message: ERC20: insufficient allowance\n"
+            ),
+            Some(Erc20Error::InsufficientAllowance)
+        );
+
+        // Cairo 1: 'ERC20: transfer to 0'
+        assert_eq!(
+            Erc20Error::from_error_message(
+                "Execution was reverted; failure reason: [0x45524332303a207472616e7366657220746f2030]."
+            ),
+            Some(Erc20Error::TransferToZero)
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Bindings for the standard ERC-721 non-fungible token interface, including the metadata and
+//! enumerable extensions.
+
+use crate::{
+    erc20::{
+        SELECTOR_APPROVAL_EVENT, SELECTOR_APPROVE, SELECTOR_BALANCEOF, SELECTOR_NAME,
+        SELECTOR_SYMBOL, SELECTOR_TOTALSUPPLY, SELECTOR_TRANSFERFROM, SELECTOR_TRANSFER_EVENT,
+    },
+    events::decode_flattened,
+    Contract, FunctionCall,
+};
+
+use starknet_core::{
+    codec::U256,
+    event::{DecodeEventError, StarknetEvent},
+    types::FieldElement,
+};
+
+/// Selector for entrypoint `ownerOf`.
+const SELECTOR_OWNEROF: FieldElement = FieldElement::from_mont([
+    9698077300642100227,
+    3377717882000941710,
+    16736661404934216381,
+    203230890268235082,
+]);
+
+/// Selector for entrypoint `safeTransferFrom`.
+pub(crate) const SELECTOR_SAFETRANSFERFROM: FieldElement = FieldElement::from_mont([
+    13881630519801689070,
+    7812215764797229328,
+    7511630616343692692,
+    527363111379677162,
+]);
+
+/// Selector for entrypoint `getApproved`.
+const SELECTOR_GETAPPROVED: FieldElement = FieldElement::from_mont([
+    11295616079348781846,
+    2543802763777154383,
+    11779197730559040611,
+    194616023180702332,
+]);
+
+/// Selector for entrypoint `setApprovalForAll`.
+pub(crate) const SELECTOR_SETAPPROVALFORALL: FieldElement = FieldElement::from_mont([
+    11084106036104810565,
+    10501478094670089018,
+    5413147665925773614,
+    316384301534418599,
+]);
+
+/// Selector for entrypoint `isApprovedForAll`.
+pub(crate) const SELECTOR_ISAPPROVEDFORALL: FieldElement = FieldElement::from_mont([
+    10674520733167504750,
+    2877770633828485803,
+    2475231882692894871,
+    198107253092393317,
+]);
+
+/// Selector for entrypoint `tokenURI`.
+const SELECTOR_TOKENURI: FieldElement = FieldElement::from_mont([
+    2091095801852678759,
+    17231162349608376638,
+    6222938823409426658,
+    1993342367472756,
+]);
+
+/// Selector for entrypoint `tokenByIndex`.
+const SELECTOR_TOKENBYINDEX: FieldElement = FieldElement::from_mont([
+    14530245541925170040,
+    16856016049237940695,
+    858510634064326063,
+    195155353192647101,
+]);
+
+/// Selector for entrypoint `tokenOfOwnerByIndex`.
+const SELECTOR_TOKENOFOWNERBYINDEX: FieldElement = FieldElement::from_mont([
+    10546851545975602615,
+    15757164273998228880,
+    16351055534632248489,
+    465081345682910485,
+]);
+
+/// Selector for event `ApprovalForAll`.
+pub(crate) const SELECTOR_APPROVALFORALL_EVENT: FieldElement = FieldElement::from_mont([
+    8116136405822246833,
+    2687863585077088446,
+    5484911045727658550,
+    140047175396186335,
+]);
+
+/// An ERC-721 token, using the camelCase entrypoints supported by both Cairo 0 and Cairo 1
+/// tokens.
+#[derive(Debug, Clone)]
+pub struct Erc721<P> {
+    contract: Contract<P>,
+}
+
+/// The `Transfer` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: FieldElement,
+    pub to: FieldElement,
+    pub token_id: U256,
+}
+
+/// The `Approval` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    pub owner: FieldElement,
+    pub approved: FieldElement,
+    pub token_id: U256,
+}
+
+/// The `ApprovalForAll` event, shared with ERC-1155.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalForAll {
+    pub owner: FieldElement,
+    pub operator: FieldElement,
+    pub approved: bool,
+}
+
+impl<P> Erc721<P> {
+    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self {
+            contract: Contract::new(address, provider),
+        }
+    }
+
+    pub fn contract(&self) -> &Contract<P> {
+        &self.contract
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.contract.address()
+    }
+
+    pub fn balance_of(&self, owner: FieldElement) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_BALANCEOF, &owner)
+    }
+
+    pub fn owner_of(&self, token_id: U256) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_OWNEROF, &token_id)
+    }
+
+    pub fn get_approved(&self, token_id: U256) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_GETAPPROVED, &token_id)
+    }
+
+    pub fn is_approved_for_all(
+        &self,
+        owner: FieldElement,
+        operator: FieldElement,
+    ) -> FunctionCall<'_, P, bool> {
+        self.contract
+            .function(SELECTOR_ISAPPROVEDFORALL, &(owner, operator))
+    }
+
+    pub fn transfer_from(
+        &self,
+        from: FieldElement,
+        to: FieldElement,
+        token_id: U256,
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract
+            .function(SELECTOR_TRANSFERFROM, &(from, to, token_id))
+    }
+
+    /// Transfers the token, checking that `to` can receive it if it's a contract. `data` is
+    /// passed along to the receiver.
+    pub fn safe_transfer_from(
+        &self,
+        from: FieldElement,
+        to: FieldElement,
+        token_id: U256,
+        data: &[FieldElement],
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract
+            .function(SELECTOR_SAFETRANSFERFROM, &(from, to, token_id, data))
+    }
+
+    pub fn approve(&self, to: FieldElement, token_id: U256) -> FunctionCall<'_, P, ()> {
+        self.contract.function(SELECTOR_APPROVE, &(to, token_id))
+    }
+
+    pub fn set_approval_for_all(
+        &self,
+        operator: FieldElement,
+        approved: bool,
+    ) -> FunctionCall<'_, P, ()> {
+        self.contract
+            .function(SELECTOR_SETAPPROVALFORALL, &(operator, approved))
+    }
+
+    /// The collection name as a Cairo short string.
+    pub fn name(&self) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_NAME, &())
+    }
+
+    /// The collection symbol as a Cairo short string.
+    pub fn symbol(&self) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(SELECTOR_SYMBOL, &())
+    }
+
+    /// The token URI. Cairo 0 tokens return a short string, to be decoded as a
+    /// [FieldElement], while Cairo 1 tokens return a
+    /// [ByteArray](starknet_core::codec::ByteArray).
+    pub fn token_uri<R>(&self, token_id: U256) -> FunctionCall<'_, P, R> {
+        self.contract.function(SELECTOR_TOKENURI, &token_id)
+    }
+
+    /// Only available on tokens implementing the enumerable extension.
+    pub fn total_supply(&self) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_TOTALSUPPLY, &())
+    }
+
+    /// Only available on tokens implementing the enumerable extension.
+    pub fn token_by_index(&self, index: U256) -> FunctionCall<'_, P, U256> {
+        self.contract.function(SELECTOR_TOKENBYINDEX, &index)
+    }
+
+    /// Only available on tokens implementing the enumerable extension.
+    pub fn token_of_owner_by_index(
+        &self,
+        owner: FieldElement,
+        index: U256,
+    ) -> FunctionCall<'_, P, U256> {
+        self.contract
+            .function(SELECTOR_TOKENOFOWNERBYINDEX, &(owner, index))
+    }
+}
+
+impl StarknetEvent for Transfer {
+    fn selector() -> FieldElement {
+        SELECTOR_TRANSFER_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                from: reader.read()?,
+                to: reader.read()?,
+                token_id: reader.read()?,
+            })
+        })
+    }
+}
+
+impl StarknetEvent for Approval {
+    fn selector() -> FieldElement {
+        SELECTOR_APPROVAL_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                owner: reader.read()?,
+                approved: reader.read()?,
+                token_id: reader.read()?,
+            })
+        })
+    }
+}
+
+impl StarknetEvent for ApprovalForAll {
+    fn selector() -> FieldElement {
+        SELECTOR_APPROVALFORALL_EVENT
+    }
+
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+        decode_flattened(keys, data, Self::selector(), |reader| {
+            Ok(Self {
+                owner: reader.read()?,
+                operator: reader.read()?,
+                approved: reader.read()?,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::utils::get_selector_from_name;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_selectors() {
+        for (selector, name) in [
+            (SELECTOR_OWNEROF, "ownerOf"),
+            (SELECTOR_SAFETRANSFERFROM, "safeTransferFrom"),
+            (SELECTOR_GETAPPROVED, "getApproved"),
+            (SELECTOR_SETAPPROVALFORALL, "setApprovalForAll"),
+            (SELECTOR_ISAPPROVEDFORALL, "isApprovedForAll"),
+            (SELECTOR_TOKENURI, "tokenURI"),
+            (SELECTOR_TOKENBYINDEX, "tokenByIndex"),
+            (SELECTOR_TOKENOFOWNERBYINDEX, "tokenOfOwnerByIndex"),
+            (SELECTOR_APPROVALFORALL_EVENT, "ApprovalForAll"),
+        ] {
+            assert_eq!(selector, get_selector_from_name(name).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_safe_transfer_calldata() {
+        let token = Erc721::new(FieldElement::ONE, ());
+
+        let call = token
+            .safe_transfer_from(
+                FieldElement::ONE,
+                FieldElement::TWO,
+                U256::from(7u128),
+                &[FieldElement::THREE],
+            )
+            .into_call();
+
+        assert_eq!(
+            call.calldata,
+            vec![
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::from(7u8),
+                FieldElement::ZERO,
+                FieldElement::ONE,
+                FieldElement::THREE,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_events() {
+        // Cairo 1 tokens emit all fields of `Transfer` as keys
+        assert_eq!(
+            Transfer::decode(
+                &[
+                    SELECTOR_TRANSFER_EVENT,
+                    FieldElement::ONE,
+                    FieldElement::TWO,
+                    FieldElement::THREE,
+                    FieldElement::ZERO
+                ],
+                &[]
+            )
+            .unwrap(),
+            Transfer {
+                from: FieldElement::ONE,
+                to: FieldElement::TWO,
+                token_id: U256::from(3u128),
+            }
+        );
+
+        assert_eq!(
+            ApprovalForAll::decode(
+                &[SELECTOR_APPROVALFORALL_EVENT],
+                &[FieldElement::ONE, FieldElement::TWO, FieldElement::ONE]
+            )
+            .unwrap(),
+            ApprovalForAll {
+                owner: FieldElement::ONE,
+                operator: FieldElement::TWO,
+                approved: true,
+            }
+        );
+    }
+}
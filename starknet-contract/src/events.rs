@@ -0,0 +1,337 @@
+use crate::Contract;
+
+use starknet_core::{
+    codec::{DecodeError, FeltReader},
+    event::{strip_selector, DecodeEventError, StarknetEvent},
+    types::FieldElement,
+};
+use starknet_providers::jsonrpc::{
+    models::{BlockId, BlockTag, EmittedEvent, EventFilter},
+    JsonRpcClient, JsonRpcClientError, JsonRpcTransport,
+};
+use std::{collections::VecDeque, marker::PhantomData};
+
+const DEFAULT_CHUNK_SIZE: u64 = 100;
+
+/// A query for the events of type `E` emitted by a contract, created with
+/// [Contract::events].
+#[derive(Debug)]
+#[must_use]
+pub struct EventQuery<'c, T, E> {
+    client: &'c JsonRpcClient<T>,
+    address: FieldElement,
+    from_block: Option<BlockId>,
+    to_block: Option<BlockId>,
+    chunk_size: u64,
+    _event: PhantomData<fn() -> E>,
+}
+
+/// Decoded events matching an [EventQuery], fetched page by page as they're consumed.
+///
+/// Events are pulled with [next](EventStream::next). Once the stream is exhausted, it can be
+/// resumed later from the last [block number](TypedEvent::block_number) seen to listen for new
+/// events.
+#[derive(Debug)]
+pub struct EventStream<'c, T, E> {
+    client: &'c JsonRpcClient<T>,
+    filter: EventFilter,
+    chunk_size: u64,
+    buffer: VecDeque<EmittedEvent>,
+    continuation_token: Option<String>,
+    exhausted: bool,
+    _event: PhantomData<fn() -> E>,
+}
+
+/// A decoded event along with where it was emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedEvent<E> {
+    pub event: E,
+    pub from_address: FieldElement,
+    pub block_hash: FieldElement,
+    pub block_number: u64,
+    pub transaction_hash: FieldElement,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventStreamError<T> {
+    #[error(transparent)]
+    Provider(JsonRpcClientError<T>),
+    #[error("failed to decode event in transaction {transaction_hash:#064x}: {error}")]
+    Decode {
+        transaction_hash: FieldElement,
+        error: DecodeEventError,
+    },
+}
+
+impl<T> Contract<JsonRpcClient<T>> {
+    /// Queries events of type `E` emitted by the contract. All blocks are included unless
+    /// restricted with [from_block](EventQuery::from_block) or [to_block](EventQuery::to_block).
+    pub fn events<E>(&self) -> EventQuery<'_, T, E>
+    where
+        E: StarknetEvent,
+    {
+        EventQuery {
+            client: self.provider(),
+            address: self.address(),
+            from_block: None,
+            to_block: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            _event: PhantomData,
+        }
+    }
+}
+
+impl<'c, T, E> EventQuery<'c, T, E> {
+    pub fn from_block(self, block_number: u64) -> Self {
+        Self {
+            from_block: Some(BlockId::Number(block_number)),
+            ..self
+        }
+    }
+
+    pub fn to_block(self, block_number: u64) -> Self {
+        Self {
+            to_block: Some(BlockId::Number(block_number)),
+            ..self
+        }
+    }
+
+    /// Includes events up to the pending block.
+    pub fn to_pending(self) -> Self {
+        Self {
+            to_block: Some(BlockId::Tag(BlockTag::Pending)),
+            ..self
+        }
+    }
+
+    /// Sets the number of events fetched per request. Defaults to 100.
+    pub fn chunk_size(self, chunk_size: u64) -> Self {
+        Self { chunk_size, ..self }
+    }
+}
+
+impl<'c, T, E> EventQuery<'c, T, E>
+where
+    E: StarknetEvent,
+{
+    pub fn stream(self) -> EventStream<'c, T, E> {
+        EventStream {
+            client: self.client,
+            filter: EventFilter {
+                from_block: self.from_block,
+                to_block: self.to_block,
+                address: Some(self.address),
+                keys: Some(vec![E::selector()]),
+            },
+            chunk_size: self.chunk_size,
+            buffer: VecDeque::new(),
+            continuation_token: None,
+            exhausted: false,
+            _event: PhantomData,
+        }
+    }
+}
+
+impl<'c, T, E> EventStream<'c, T, E>
+where
+    T: JsonRpcTransport + Sync,
+    E: StarknetEvent,
+{
+    /// Returns the next event, fetching the next page if needed, or `None` once all matching
+    /// events have been returned. Events of other types sharing a key with `E` are skipped.
+    pub async fn next(&mut self) -> Option<Result<TypedEvent<E>, EventStreamError<T::Error>>> {
+        loop {
+            if let Some(event) = self.buffer.pop_front() {
+                if event.keys.first() != Some(&E::selector()) {
+                    continue;
+                }
+
+                return Some(
+                    E::decode(&event.keys, &event.data)
+                        .map(|decoded| TypedEvent {
+                            event: decoded,
+                            from_address: event.from_address,
+                            block_hash: event.block_hash,
+                            block_number: event.block_number,
+                            transaction_hash: event.transaction_hash,
+                        })
+                        .map_err(|error| EventStreamError::Decode {
+                            transaction_hash: event.transaction_hash,
+                            error,
+                        }),
+                );
+            }
+
+            if self.exhausted {
+                return None;
+            }
+
+            let page = match self
+                .client
+                .get_events(
+                    self.filter.clone(),
+                    self.continuation_token.take(),
+                    self.chunk_size,
+                )
+                .await
+            {
+                Ok(page) => page,
+                Err(err) => return Some(Err(EventStreamError::Provider(err))),
+            };
+
+            self.exhausted = page.continuation_token.is_none();
+            self.continuation_token = page.continuation_token;
+            self.buffer.extend(page.events);
+        }
+    }
+
+    /// Collects all remaining events.
+    pub async fn collect(mut self) -> Result<Vec<TypedEvent<E>>, EventStreamError<T::Error>> {
+        let mut events = vec![];
+        while let Some(event) = self.next().await {
+            events.push(event?);
+        }
+        Ok(events)
+    }
+}
+
+/// Decodes an event from its keys following the selector, then its data, as if they were a
+/// single list. Decoding is then the same whether fields are emitted as keys, like in Cairo 1
+/// token standards, or as data, like in Cairo 0.
+pub(crate) fn decode_flattened<T, F>(
+    keys: &[FieldElement],
+    data: &[FieldElement],
+    selector: FieldElement,
+    decode: F,
+) -> Result<T, DecodeEventError>
+where
+    F: FnOnce(&mut FeltReader<'_>) -> Result<T, DecodeError>,
+{
+    let felts = strip_selector(keys, selector)?
+        .iter()
+        .chain(data.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut reader = FeltReader::new(&felts);
+    let value = decode(&mut reader)?;
+    reader.finish()?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{json, Value};
+    use starknet_core::{event::strip_selector, utils::get_selector_from_name};
+    use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse};
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Transfer {
+        to: FieldElement,
+    }
+
+    impl StarknetEvent for Transfer {
+        fn selector() -> FieldElement {
+            get_selector_from_name("Transfer").unwrap()
+        }
+
+        fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+            strip_selector(keys, Self::selector())?;
+            Ok(Self {
+                to: starknet_core::codec::CairoDeserialize::cairo_deserialize_all(data)?,
+            })
+        }
+    }
+
+    /// Serves pre-defined pages of events, recording the continuation tokens requested.
+    #[derive(Debug)]
+    struct MockTransport {
+        pages: Mutex<Vec<Value>>,
+        requests: Mutex<Vec<Value>>,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    #[async_trait]
+    impl JsonRpcTransport for MockTransport {
+        type Error = MockError;
+
+        async fn send_request<P, R>(
+            &self,
+            _method: JsonRpcMethod,
+            params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: Serialize + Send,
+            R: DeserializeOwned,
+        {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(serde_json::to_value(params).unwrap());
+
+            let page = self.pages.lock().unwrap().remove(0);
+            Ok(serde_json::from_value(json!({ "id": 1, "result": page })).unwrap())
+        }
+    }
+
+    fn event(selector: &str, data: &str, block_number: u64) -> Value {
+        json!({
+            "from_address": "0x1",
+            "keys": [format!("{:#x}", get_selector_from_name(selector).unwrap())],
+            "data": [data],
+            "block_hash": "0x2",
+            "block_number": block_number,
+            "transaction_hash": "0x3",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stream_pages() {
+        let transport = MockTransport {
+            pages: Mutex::new(vec![
+                json!({
+                    "events": [event("Transfer", "0xa", 5), event("Approval", "0xb", 5)],
+                    "continuation_token": "page2",
+                }),
+                json!({ "events": [event("Transfer", "0xc", 6)] }),
+            ]),
+            requests: Mutex::new(vec![]),
+        };
+        let contract = Contract::new(FieldElement::ONE, JsonRpcClient::new(&transport));
+
+        let events = contract
+            .events::<Transfer>()
+            .from_block(5)
+            .chunk_size(2)
+            .stream()
+            .collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.event.to, event.block_number))
+                .collect::<Vec<_>>(),
+            vec![
+                (FieldElement::from_hex_be("0xa").unwrap(), 5),
+                (FieldElement::from_hex_be("0xc").unwrap(), 6)
+            ]
+        );
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0][0]["from_block"], json!({ "block_number": 5 }));
+        assert_eq!(requests[0][0]["chunk_size"], json!(2));
+        assert_eq!(requests[1][0]["continuation_token"], json!("page2"));
+    }
+}
@@ -1,5 +1,14 @@
-use starknet_accounts::{Account, Call, Execution};
-use starknet_core::types::FieldElement;
+use crate::Contract;
+
+use starknet_accounts::{Account, AccountError, Call, ConnectedAccount, Execution};
+use starknet_core::{
+    codec::CairoSerialize,
+    crypto::pedersen_hash,
+    types::{AsStarknetError, BlockId, FieldElement, StarknetError, TransactionStatus},
+    utils::get_contract_address,
+};
+use starknet_providers::{Provider, ProviderError};
+use std::future::Future;
 
 /// The default UDC address: 0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf.
 const UDC_ADDRESS: FieldElement = FieldElement::from_mont([
@@ -23,6 +32,59 @@ pub struct ContractFactory<A> {
     account: A,
 }
 
+/// A deployment through the UDC whose address is known before it's sent.
+#[must_use]
+pub struct Deployment<'f, A> {
+    execution: Execution<'f, A>,
+    address: FieldElement,
+}
+
+/// A deployment that has been sent and is waiting to be accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingDeployment {
+    pub transaction_hash: FieldElement,
+    pub address: FieldElement,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeploymentError<P> {
+    #[error(transparent)]
+    Provider(ProviderError<P>),
+    #[error("deployment transaction rejected: {}", reason.as_deref().unwrap_or("unknown reason"))]
+    Rejected { reason: Option<String> },
+    #[error("deployed class hash mismatch: expected {expected:#064x}, got {actual:#064x}")]
+    ClassHashMismatch {
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeployAndWaitError<S, P> {
+    #[error(transparent)]
+    Account(AccountError<S, P>),
+    #[error(transparent)]
+    Deployment(DeploymentError<P>),
+}
+
+impl<P> AsStarknetError for DeploymentError<P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Provider(err) => err.starknet_error(),
+            Self::Rejected { .. } | Self::ClassHashMismatch { .. } => None,
+        }
+    }
+}
+
+impl<S, P> AsStarknetError for DeployAndWaitError<S, P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Account(err) => err.starknet_error(),
+            Self::Deployment(err) => err.starknet_error(),
+        }
+    }
+}
+
 impl<A> ContractFactory<A> {
     pub fn new(class_hash: FieldElement, account: A) -> Self {
         Self::new_with_udc(class_hash, account, UDC_ADDRESS)
@@ -35,6 +97,10 @@ impl<A> ContractFactory<A> {
             account,
         }
     }
+
+    pub fn class_hash(&self) -> FieldElement {
+        self.class_hash
+    }
 }
 
 impl<A> ContractFactory<A>
@@ -75,4 +141,328 @@ where
             &self.account,
         )
     }
+
+    /// Same as [deploy](Self::deploy), with constructor arguments encoded from `args`, and the
+    /// address of the contract predicted before sending.
+    pub fn deploy_with_args<C>(
+        &self,
+        args: &C,
+        salt: FieldElement,
+        unique: bool,
+    ) -> Deployment<'_, A>
+    where
+        C: CairoSerialize + ?Sized,
+    {
+        let constructor_calldata = args.cairo_serialized();
+
+        Deployment {
+            address: self.predict_address(&constructor_calldata, salt, unique),
+            execution: self.deploy(constructor_calldata, salt, unique),
+        }
+    }
+
+    /// Locally calculates the address the contract would be deployed at. Unique deployments mix
+    /// the account address into the salt, so that only this account can deploy at the address.
+    pub fn predict_address(
+        &self,
+        constructor_calldata: &[FieldElement],
+        salt: FieldElement,
+        unique: bool,
+    ) -> FieldElement {
+        if unique {
+            get_contract_address(
+                pedersen_hash(&self.account.address(), &salt),
+                self.class_hash,
+                constructor_calldata,
+                self.udc_address,
+            )
+        } else {
+            get_contract_address(
+                salt,
+                self.class_hash,
+                constructor_calldata,
+                FieldElement::ZERO,
+            )
+        }
+    }
+}
+
+impl<A> ContractFactory<A>
+where
+    A: ConnectedAccount + Sync,
+{
+    /// Deploys the contract with [deploy_with_args](Self::deploy_with_args) and waits for the
+    /// deployment to be accepted, awaiting `sleep` between polls. The contract is returned only
+    /// once the class hash at the predicted address is verified to be the factory class.
+    pub async fn deploy_and_wait<C, F, Fut>(
+        &self,
+        args: &C,
+        salt: FieldElement,
+        unique: bool,
+        sleep: F,
+    ) -> Result<
+        Contract<&A::Provider>,
+        DeployAndWaitError<A::SignError, <A::Provider as Provider>::Error>,
+    >
+    where
+        C: CairoSerialize + ?Sized,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let provider = self.account.provider();
+
+        let pending = self
+            .deploy_with_args(args, salt, unique)
+            .send()
+            .await
+            .map_err(DeployAndWaitError::Account)?;
+        let address = pending
+            .wait_for_acceptance(provider, sleep)
+            .await
+            .map_err(DeployAndWaitError::Deployment)?;
+        pending
+            .verify_class_hash(provider, self.class_hash)
+            .await
+            .map_err(DeployAndWaitError::Deployment)?;
+
+        Ok(Contract::new(address, provider))
+    }
+}
+
+impl<'f, A> Deployment<'f, A> {
+    /// The address the contract will be deployed at.
+    pub fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    pub fn nonce(self, nonce: FieldElement) -> Self {
+        Self {
+            execution: self.execution.nonce(nonce),
+            ..self
+        }
+    }
+
+    pub fn max_fee(self, max_fee: FieldElement) -> Self {
+        Self {
+            execution: self.execution.max_fee(max_fee),
+            ..self
+        }
+    }
+
+    /// The underlying UDC call, e.g. for estimating fees or batching it with other calls.
+    pub fn into_execution(self) -> Execution<'f, A> {
+        self.execution
+    }
+}
+
+impl<'f, A> Deployment<'f, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    pub async fn send(
+        &self,
+    ) -> Result<PendingDeployment, AccountError<A::SignError, <A::Provider as Provider>::Error>>
+    {
+        let result = self.execution.send().await?;
+
+        Ok(PendingDeployment {
+            transaction_hash: result.transaction_hash,
+            address: self.address,
+        })
+    }
+}
+
+impl PendingDeployment {
+    /// Checks whether the deployment has been accepted on L2. Returns `false` while it's still
+    /// pending.
+    pub async fn poll<P>(&self, provider: &P) -> Result<bool, DeploymentError<P::Error>>
+    where
+        P: Provider + Sync,
+    {
+        let status = provider
+            .get_transaction_status(self.transaction_hash)
+            .await
+            .map_err(DeploymentError::Provider)?;
+
+        match status.status {
+            TransactionStatus::AcceptedOnL2 | TransactionStatus::AcceptedOnL1 => Ok(true),
+            TransactionStatus::Rejected => Err(DeploymentError::Rejected {
+                reason: status
+                    .transaction_failure_reason
+                    .and_then(|reason| reason.error_message),
+            }),
+            _ => Ok(false),
+        }
+    }
+
+    /// Polls until the deployment is accepted on L2 and returns the contract address. `sleep`
+    /// is awaited between polls, leaving the polling interval (and the async runtime used for
+    /// sleeping) to the caller.
+    pub async fn wait_for_acceptance<P, F, Fut>(
+        &self,
+        provider: &P,
+        mut sleep: F,
+    ) -> Result<FieldElement, DeploymentError<P::Error>>
+    where
+        P: Provider + Sync,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        while !self.poll(provider).await? {
+            sleep().await;
+        }
+        Ok(self.address)
+    }
+
+    /// Checks that the contract at the deployment address is of class `class_hash`.
+    pub async fn verify_class_hash<P>(
+        &self,
+        provider: &P,
+        class_hash: FieldElement,
+    ) -> Result<(), DeploymentError<P::Error>>
+    where
+        P: Provider + Sync,
+    {
+        let actual = provider
+            .get_class_hash_at(self.address, BlockId::Latest)
+            .await
+            .map_err(DeploymentError::Provider)?;
+
+        if actual == class_hash {
+            Ok(())
+        } else {
+            Err(DeploymentError::ClassHashMismatch {
+                expected: class_hash,
+                actual,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::json;
+    use starknet_accounts::SingleOwnerAccount;
+    use starknet_core::{chain_id, codec::U256};
+    use starknet_providers::{
+        jsonrpc::{JsonRpcClient, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport},
+        SequencerGatewayProvider,
+    };
+    use starknet_signers::{LocalWallet, SigningKey};
+
+    /// Responds to `starknet_getClassHashAt` with a fixed class hash.
+    #[derive(Debug)]
+    struct MockTransport {
+        class_hash: &'static str,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    #[async_trait]
+    impl JsonRpcTransport for MockTransport {
+        type Error = MockError;
+
+        async fn send_request<P, R>(
+            &self,
+            method: JsonRpcMethod,
+            _params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: Serialize + Send,
+            R: DeserializeOwned,
+        {
+            assert!(matches!(method, JsonRpcMethod::GetClassHashAt));
+            Ok(serde_json::from_value(json!({ "id": 1, "result": self.class_hash })).unwrap())
+        }
+    }
+
+    fn factory() -> ContractFactory<SingleOwnerAccount<SequencerGatewayProvider, LocalWallet>> {
+        let account = SingleOwnerAccount::new(
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            FieldElement::from_hex_be("0x1234").unwrap(),
+            chain_id::TESTNET,
+        );
+        ContractFactory::new(FieldElement::from_hex_be("0x5678").unwrap(), account)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_deploy_with_args() {
+        let factory = factory();
+        let salt = FieldElement::from_hex_be("0x99").unwrap();
+        let args = (FieldElement::ONE, U256::from(5u128));
+
+        let deployment = factory.deploy_with_args(&args, salt, true);
+        let constructor_calldata = vec![
+            FieldElement::ONE,
+            FieldElement::from(5u8),
+            FieldElement::ZERO,
+        ];
+
+        assert_eq!(
+            deployment.address(),
+            get_contract_address(
+                pedersen_hash(&FieldElement::from_hex_be("0x1234").unwrap(), &salt),
+                factory.class_hash,
+                &constructor_calldata,
+                UDC_ADDRESS,
+            )
+        );
+
+        // The UDC calldata ends with the constructor calldata and its length
+        let raw_calldata = deployment
+            .nonce(FieldElement::ZERO)
+            .max_fee(FieldElement::ZERO)
+            .into_execution()
+            .prepared()
+            .unwrap()
+            .raw_calldata();
+        assert!(raw_calldata.ends_with(&[
+            FieldElement::THREE,
+            FieldElement::ONE,
+            FieldElement::from(5u8),
+            FieldElement::ZERO
+        ]));
+        assert_eq!(
+            factory.predict_address(&constructor_calldata, salt, false),
+            get_contract_address(
+                salt,
+                factory.class_hash,
+                &constructor_calldata,
+                FieldElement::ZERO
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_class_hash() {
+        let pending = PendingDeployment {
+            transaction_hash: FieldElement::ONE,
+            address: FieldElement::TWO,
+        };
+        let class_hash = FieldElement::from_hex_be("0x5678").unwrap();
+
+        let provider = JsonRpcClient::new(MockTransport {
+            class_hash: "0x5678",
+        });
+        pending
+            .verify_class_hash(&provider, class_hash)
+            .await
+            .unwrap();
+
+        let provider = JsonRpcClient::new(MockTransport {
+            class_hash: "0x9999",
+        });
+        assert!(matches!(
+            pending.verify_class_hash(&provider, class_hash).await,
+            Err(DeploymentError::ClassHashMismatch { .. })
+        ));
+    }
 }
@@ -0,0 +1,549 @@
+use crate::{Contract, EventStreamError, TypedEvent};
+
+use async_trait::async_trait;
+use starknet_core::{event::StarknetEvent, types::FieldElement};
+use starknet_providers::jsonrpc::{
+    models::{BlockId, MaybePendingBlockWithTxHashes},
+    JsonRpcClient, JsonRpcClientError, JsonRpcTransport,
+};
+use std::{collections::VecDeque, marker::PhantomData, sync::Mutex};
+
+const DEFAULT_REORG_DEPTH: usize = 64;
+
+/// The position of the last event handled by an [EventIndexer].
+///
+/// Events have no index of their own in JSON-RPC responses, so `event_index` is the index of the
+/// event among those of the indexed type in its block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+    pub transaction_hash: FieldElement,
+    pub event_index: u64,
+}
+
+/// Storage for the checkpoint of an [EventIndexer], so that indexing resumes where it stopped
+/// after restarts.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait CheckpointStore {
+    type Error: std::error::Error + Send;
+
+    async fn load(&self) -> Result<Option<Checkpoint>, Self::Error>;
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Self::Error>;
+}
+
+/// Handles the events of type `E` found by an [EventIndexer].
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait EventHandler<E> {
+    type Error: std::error::Error + Send;
+
+    async fn handle(&mut self, event: &TypedEvent<E>) -> Result<(), Self::Error>;
+
+    /// Called after a reorg, when the events handled after `checkpoint` are no longer part of
+    /// the chain and must be reverted. They're handled again if they're included in the new
+    /// chain.
+    async fn rollback(&mut self, checkpoint: &Checkpoint) -> Result<(), Self::Error>;
+}
+
+/// A [CheckpointStore] keeping the checkpoint in memory.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoint: Mutex<Option<Checkpoint>>,
+}
+
+/// Indexes the events of type `E` emitted by a contract, dispatching them to a handler.
+///
+/// Each [poll](EventIndexer::poll) handles the events emitted up to the latest block since the
+/// last checkpoint. Before that, the block of the checkpoint is checked to still be part of the
+/// chain. If it isn't, the indexer rolls back to the last checkpoint that still is, among those
+/// of the last [reorg_depth](EventIndexer::reorg_depth) blocks with events.
+#[derive(Debug)]
+pub struct EventIndexer<'c, T, E, S, H> {
+    contract: &'c Contract<JsonRpcClient<T>>,
+    store: S,
+    handler: H,
+    from_block: u64,
+    chunk_size: Option<u64>,
+    reorg_depth: usize,
+    recent: Option<VecDeque<Checkpoint>>,
+    _event: PhantomData<fn() -> E>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError<T, S, H> {
+    #[error(transparent)]
+    Provider(JsonRpcClientError<T>),
+    #[error(transparent)]
+    Stream(EventStreamError<T>),
+    #[error("checkpoint store error: {0}")]
+    Store(#[source] S),
+    #[error("event handler error: {0}")]
+    Handler(#[source] H),
+    #[error("no checkpoint left to roll back to after reorg of block {0}")]
+    ReorgTooDeep(u64),
+}
+
+impl<'c, T, E, S, H> EventIndexer<'c, T, E, S, H> {
+    pub fn new(contract: &'c Contract<JsonRpcClient<T>>, store: S, handler: H) -> Self {
+        Self {
+            contract,
+            store,
+            handler,
+            from_block: 0,
+            chunk_size: None,
+            reorg_depth: DEFAULT_REORG_DEPTH,
+            recent: None,
+            _event: PhantomData,
+        }
+    }
+
+    /// Sets the block indexing starts at when there's no checkpoint yet. Defaults to 0.
+    pub fn from_block(self, from_block: u64) -> Self {
+        Self { from_block, ..self }
+    }
+
+    /// Sets the number of events fetched per request.
+    pub fn chunk_size(self, chunk_size: u64) -> Self {
+        Self {
+            chunk_size: Some(chunk_size),
+            ..self
+        }
+    }
+
+    /// Sets the number of blocks with events whose checkpoints are kept to recover from reorgs.
+    /// Defaults to 64.
+    pub fn reorg_depth(self, reorg_depth: usize) -> Self {
+        Self {
+            reorg_depth: reorg_depth.max(1),
+            ..self
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+}
+
+impl<'c, T, E, S, H> EventIndexer<'c, T, E, S, H>
+where
+    T: JsonRpcTransport + Sync,
+    E: StarknetEvent,
+    S: CheckpointStore,
+    H: EventHandler<E>,
+{
+    /// Handles all events emitted up to the latest block since the last checkpoint, and returns
+    /// the number of events handled.
+    pub async fn poll(&mut self) -> Result<usize, IndexerError<T::Error, S::Error, H::Error>> {
+        let client = self.contract.provider();
+
+        if self.recent.is_none() {
+            let checkpoint = self.store.load().await.map_err(IndexerError::Store)?;
+            self.recent = Some(checkpoint.into_iter().collect());
+        }
+
+        self.check_reorg().await?;
+
+        let latest = client
+            .block_number()
+            .await
+            .map_err(IndexerError::Provider)?;
+        let checkpoint = self.checkpoint();
+        let from_block = checkpoint.map_or(self.from_block, |checkpoint| checkpoint.block_number);
+        if from_block > latest {
+            return Ok(0);
+        }
+
+        let mut query = self
+            .contract
+            .events::<E>()
+            .from_block(from_block)
+            .to_block(latest);
+        if let Some(chunk_size) = self.chunk_size {
+            query = query.chunk_size(chunk_size);
+        }
+        let mut stream = query.stream();
+
+        let mut handled = 0;
+        let mut position: Option<(u64, u64)> = None;
+        while let Some(event) = stream.next().await {
+            let event = event.map_err(IndexerError::Stream)?;
+
+            let event_index = match position {
+                Some((block_number, index)) if block_number == event.block_number => index + 1,
+                _ => 0,
+            };
+            position = Some((event.block_number, event_index));
+
+            if let Some(checkpoint) = checkpoint {
+                if event.block_number == checkpoint.block_number
+                    && event_index <= checkpoint.event_index
+                {
+                    continue;
+                }
+            }
+
+            self.handler
+                .handle(&event)
+                .await
+                .map_err(IndexerError::Handler)?;
+
+            let checkpoint = Checkpoint {
+                block_number: event.block_number,
+                block_hash: event.block_hash,
+                transaction_hash: event.transaction_hash,
+                event_index,
+            };
+            self.store
+                .save(&checkpoint)
+                .await
+                .map_err(IndexerError::Store)?;
+            self.push_checkpoint(checkpoint);
+            handled += 1;
+        }
+
+        Ok(handled)
+    }
+
+    /// Polls for new events until an error occurs, calling `sleep` between polls.
+    pub async fn run<F, Fut>(
+        &mut self,
+        mut sleep: F,
+    ) -> Result<(), IndexerError<T::Error, S::Error, H::Error>>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        loop {
+            self.poll().await?;
+            sleep().await;
+        }
+    }
+
+    /// Rolls back to the last recent checkpoint still part of the chain, if the block of the
+    /// current one was reorganized.
+    async fn check_reorg(&mut self) -> Result<(), IndexerError<T::Error, S::Error, H::Error>> {
+        let mut reorged_block = None;
+        while let Some(checkpoint) = self.checkpoint() {
+            if self.is_canonical(&checkpoint).await? {
+                if reorged_block.is_some() {
+                    self.handler
+                        .rollback(&checkpoint)
+                        .await
+                        .map_err(IndexerError::Handler)?;
+                    self.store
+                        .save(&checkpoint)
+                        .await
+                        .map_err(IndexerError::Store)?;
+                }
+                return Ok(());
+            }
+
+            reorged_block.get_or_insert(checkpoint.block_number);
+            if let Some(recent) = self.recent.as_mut() {
+                recent.pop_back();
+            }
+        }
+
+        match reorged_block {
+            Some(block_number) => {
+                // Reload the stored checkpoint on the next poll
+                self.recent = None;
+                Err(IndexerError::ReorgTooDeep(block_number))
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn is_canonical(
+        &self,
+        checkpoint: &Checkpoint,
+    ) -> Result<bool, IndexerError<T::Error, S::Error, H::Error>> {
+        let block = self
+            .contract
+            .provider()
+            .get_block_with_tx_hashes(&BlockId::Number(checkpoint.block_number))
+            .await
+            .map_err(IndexerError::Provider)?;
+
+        Ok(match block {
+            MaybePendingBlockWithTxHashes::Block(block) => {
+                block.block_hash == checkpoint.block_hash
+            }
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => false,
+        })
+    }
+
+    fn checkpoint(&self) -> Option<Checkpoint> {
+        self.recent
+            .as_ref()
+            .and_then(|recent| recent.back())
+            .copied()
+    }
+
+    /// Keeps the last checkpoint of each of the last `reorg_depth` blocks with events.
+    fn push_checkpoint(&mut self, checkpoint: Checkpoint) {
+        let recent = self.recent.get_or_insert_with(VecDeque::new);
+        if let Some(last) = recent.back_mut() {
+            if last.block_number == checkpoint.block_number {
+                *last = checkpoint;
+                return;
+            }
+        }
+
+        recent.push_back(checkpoint);
+        while recent.len() > self.reorg_depth {
+            recent.pop_front();
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl CheckpointStore for MemoryCheckpointStore {
+    type Error = std::convert::Infallible;
+
+    async fn load(&self) -> Result<Option<Checkpoint>, Self::Error> {
+        Ok(*self.checkpoint.lock().unwrap())
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Self::Error> {
+        *self.checkpoint.lock().unwrap() = Some(*checkpoint);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::json;
+    use starknet_core::{event::DecodeEventError, utils::get_selector_from_name};
+    use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse};
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Transfer {
+        to: FieldElement,
+    }
+
+    impl StarknetEvent for Transfer {
+        fn selector() -> FieldElement {
+            get_selector_from_name("Transfer").unwrap()
+        }
+
+        fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError> {
+            starknet_core::event::strip_selector(keys, Self::selector())?;
+            Ok(Self {
+                to: starknet_core::codec::CairoDeserialize::cairo_deserialize_all(data)?,
+            })
+        }
+    }
+
+    /// A chain of blocks with `Transfer` events, where blocks can be replaced.
+    #[derive(Debug, Default)]
+    struct MockChain {
+        block_hashes: Mutex<HashMap<u64, u64>>,
+        events: Mutex<Vec<(u64, u64)>>,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    impl MockChain {
+        fn set_block(&self, block_number: u64, block_hash: u64, transfers: &[u64]) {
+            self.block_hashes
+                .lock()
+                .unwrap()
+                .insert(block_number, block_hash);
+
+            let mut events = self.events.lock().unwrap();
+            events.retain(|(number, _)| *number != block_number);
+            events.extend(transfers.iter().map(|to| (block_number, *to)));
+            events.sort_by_key(|(number, _)| *number);
+        }
+
+        fn block_hash(&self, block_number: u64) -> u64 {
+            self.block_hashes.lock().unwrap()[&block_number]
+        }
+    }
+
+    #[async_trait]
+    impl JsonRpcTransport for MockChain {
+        type Error = MockError;
+
+        async fn send_request<P, R>(
+            &self,
+            method: JsonRpcMethod,
+            params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: Serialize + Send,
+            R: DeserializeOwned,
+        {
+            let params = serde_json::to_value(params).unwrap();
+            let result = match serde_json::to_value(method).unwrap().as_str().unwrap() {
+                "starknet_blockNumber" => {
+                    json!(self.block_hashes.lock().unwrap().keys().max().unwrap())
+                }
+                "starknet_getBlockWithTxHashes" => {
+                    let block_number = params[0]["block_number"].as_u64().unwrap();
+                    json!({
+                        "status": "ACCEPTED_ON_L2",
+                        "block_hash": format!("{:#x}", self.block_hash(block_number)),
+                        "parent_hash": "0x0",
+                        "block_number": block_number,
+                        "new_root": "0x0",
+                        "timestamp": 0,
+                        "sequencer_address": "0x0",
+                        "transactions": [],
+                    })
+                }
+                "starknet_getEvents" => {
+                    let from_block = params[0]["from_block"]["block_number"].as_u64().unwrap();
+                    let to_block = params[0]["to_block"]["block_number"].as_u64().unwrap();
+                    let events = self
+                        .events
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .filter(|(number, _)| (from_block..=to_block).contains(number))
+                        .map(|(number, to)| {
+                            json!({
+                                "from_address": "0x1",
+                                "keys": [format!("{:#x}", Transfer::selector())],
+                                "data": [format!("{to:#x}")],
+                                "block_hash": format!("{:#x}", self.block_hash(*number)),
+                                "block_number": number,
+                                "transaction_hash": "0x3",
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    json!({ "events": events })
+                }
+                method => panic!("unexpected method {method}"),
+            };
+
+            Ok(serde_json::from_value(json!({ "id": 1, "result": result })).unwrap())
+        }
+    }
+
+    /// Keeps the recipients of handled transfers by block, like an application database.
+    #[derive(Debug, Default)]
+    struct Recipients {
+        transfers: Vec<(u64, FieldElement)>,
+        rollbacks: Vec<u64>,
+    }
+
+    #[async_trait]
+    impl EventHandler<Transfer> for Recipients {
+        type Error = std::convert::Infallible;
+
+        async fn handle(&mut self, event: &TypedEvent<Transfer>) -> Result<(), Self::Error> {
+            self.transfers.push((event.block_number, event.event.to));
+            Ok(())
+        }
+
+        async fn rollback(&mut self, checkpoint: &Checkpoint) -> Result<(), Self::Error> {
+            self.rollbacks.push(checkpoint.block_number);
+            self.transfers
+                .retain(|(block_number, _)| *block_number <= checkpoint.block_number);
+            Ok(())
+        }
+    }
+
+    fn recipients(transfers: &[(u64, u64)]) -> Vec<(u64, FieldElement)> {
+        transfers
+            .iter()
+            .map(|(block_number, to)| (*block_number, FieldElement::from(*to)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_index_with_reorg() {
+        let chain = MockChain::default();
+        chain.set_block(5, 0x55, &[0xa, 0xb]);
+        chain.set_block(6, 0x66, &[0xc]);
+        let contract = Contract::new(FieldElement::ONE, JsonRpcClient::new(&chain));
+
+        let mut indexer = EventIndexer::new(
+            &contract,
+            MemoryCheckpointStore::default(),
+            Recipients::default(),
+        )
+        .from_block(5);
+
+        assert_eq!(indexer.poll().await.unwrap(), 3);
+        assert_eq!(indexer.poll().await.unwrap(), 0);
+
+        // New events are added to the last block, and block 6 is then replaced
+        chain.set_block(6, 0x66, &[0xc, 0xd]);
+        assert_eq!(indexer.poll().await.unwrap(), 1);
+        chain.set_block(6, 0x67, &[0xe]);
+        chain.set_block(7, 0x77, &[0xf]);
+        assert_eq!(indexer.poll().await.unwrap(), 2);
+
+        let checkpoint = indexer.store().load().await.unwrap().unwrap();
+        assert_eq!(checkpoint.block_number, 7);
+        assert_eq!(checkpoint.block_hash, FieldElement::from(0x77u32));
+        assert_eq!(checkpoint.event_index, 0);
+
+        let handler = indexer.into_handler();
+        assert_eq!(handler.rollbacks, vec![5]);
+        assert_eq!(
+            handler.transfers,
+            recipients(&[(5, 0xa), (5, 0xb), (6, 0xe), (7, 0xf)])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_store() {
+        let chain = MockChain::default();
+        chain.set_block(1, 0x11, &[0xa, 0xb, 0xc]);
+        let contract = Contract::new(FieldElement::ONE, JsonRpcClient::new(&chain));
+
+        let store = MemoryCheckpointStore::default();
+        store
+            .save(&Checkpoint {
+                block_number: 1,
+                block_hash: FieldElement::from(0x11u32),
+                transaction_hash: FieldElement::THREE,
+                event_index: 1,
+            })
+            .await
+            .unwrap();
+
+        let mut indexer = EventIndexer::new(&contract, store, Recipients::default());
+        assert_eq!(indexer.poll().await.unwrap(), 1);
+        assert_eq!(indexer.into_handler().transfers, recipients(&[(1, 0xc)]));
+
+        // Without older checkpoints, a reorg of the stored one can't be recovered from
+        let store = MemoryCheckpointStore::default();
+        store
+            .save(&Checkpoint {
+                block_number: 1,
+                block_hash: FieldElement::from(0x12u32),
+                transaction_hash: FieldElement::THREE,
+                event_index: 1,
+            })
+            .await
+            .unwrap();
+        let mut indexer = EventIndexer::new(&contract, store, Recipients::default());
+        assert!(matches!(
+            indexer.poll().await,
+            Err(IndexerError::ReorgTooDeep(1))
+        ));
+    }
+}
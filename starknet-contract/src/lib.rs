@@ -1,2 +1,49 @@
+mod abi_codec;
+pub use abi_codec::{AbiCodec, AbiCodecError, DecodedEvent};
+
+mod abi_compat;
+pub use abi_compat::{AbiChange, AbiDiff, EntrypointKind, IncompatibleAbiError};
+
+mod batch;
+pub use batch::{BatchDecodeError, BatchResults, BatchSimulation, BatchSimulationError, CallBatch};
+
+pub mod codegen;
+
+mod contract;
+pub use contract::{Contract, ContractCallError, FunctionCall};
+
+pub mod erc20;
+pub use erc20::{Erc20, Erc20Error};
+
+pub mod erc721;
+pub use erc721::Erc721;
+
+pub mod erc1155;
+pub use erc1155::Erc1155;
+
+mod events;
+pub use events::{EventQuery, EventStream, EventStreamError, TypedEvent};
+
 mod factory;
-pub use factory::ContractFactory;
+pub use factory::{
+    ContractFactory, DeployAndWaitError, Deployment, DeploymentError, PendingDeployment,
+};
+
+mod indexer;
+pub use indexer::{
+    Checkpoint, CheckpointStore, EventHandler, EventIndexer, IndexerError, MemoryCheckpointStore,
+};
+
+mod multicall;
+pub use multicall::{Multicall, MulticallResult, MulticallResults};
+
+mod reducer;
+pub use reducer::{Erc20Balances, Reducer, Snapshot, StateReplay};
+
+mod revert;
+pub use revert::{revert_reasons, ContractError};
+
+mod storage;
+pub use storage::{StorageRead, StorageReadError};
+
+pub mod trace;
@@ -0,0 +1,234 @@
+use crate::{ContractCallError, FunctionCall};
+
+use starknet_accounts::Call;
+use starknet_core::{
+    codec::{CairoDeserialize, DecodeError, FeltReader},
+    types::{BlockId, CallFunction, FieldElement},
+};
+use starknet_providers::Provider;
+use std::marker::PhantomData;
+
+/// Selector for entrypoint `aggregate`.
+const SELECTOR_AGGREGATE: FieldElement = FieldElement::from_mont([
+    10143872062486929310,
+    2869016847263578790,
+    10170527792622061643,
+    111923219413731522,
+]);
+
+/// View calls executed together through a deployed aggregator contract, in a single
+/// `starknet_call`. This is an alternative to batching requests for endpoints that don't
+/// support JSON-RPC batches.
+///
+/// The aggregator is expected to implement:
+///
+/// ```cairo
+/// func aggregate(calls_len: felt, calls: felt*) -> (block_number: felt, result_len: felt, result: felt*)
+/// ```
+///
+/// where `calls_len` is the number of calls, each encoded as `to, selector, calldata_len,
+/// calldata...`, and `result` holds the result of each call prefixed by its length.
+///
+/// ```ignore
+/// let mut multicall = Multicall::new(aggregator_address, &provider);
+/// let balance = multicall.add(&token.balance_of(holder));
+/// let supply = multicall.add(&token.total_supply());
+///
+/// let results = multicall.call().await?;
+/// let (balance, supply) = (results.get(balance)?, results.get(supply)?);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Multicall<'p, P> {
+    provider: &'p P,
+    aggregator: FieldElement,
+    calls: Vec<Call>,
+    block_id: BlockId,
+}
+
+/// Handle to the result of a call added to a [Multicall], decoded as `R` with
+/// [MulticallResults::get].
+#[derive(Debug)]
+pub struct MulticallResult<R> {
+    index: usize,
+    _output: PhantomData<fn() -> R>,
+}
+
+/// The raw results of all calls of a [Multicall], in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MulticallResults {
+    pub block_number: u64,
+    pub results: Vec<Vec<FieldElement>>,
+}
+
+impl<'p, P> Multicall<'p, P> {
+    pub fn new(aggregator: FieldElement, provider: &'p P) -> Self {
+        Self {
+            provider,
+            aggregator,
+            calls: vec![],
+            block_id: BlockId::Latest,
+        }
+    }
+
+    /// Adds a call, returning the handle to retrieve its decoded result with.
+    pub fn add<Q, R>(&mut self, call: &FunctionCall<'_, Q, R>) -> MulticallResult<R> {
+        self.calls.push(call.as_call().clone());
+
+        MulticallResult {
+            index: self.calls.len() - 1,
+            _output: PhantomData,
+        }
+    }
+
+    /// Sets the block to execute the calls at. Defaults to [BlockId::Latest].
+    pub fn block_id(self, block_id: BlockId) -> Self {
+        Self { block_id, ..self }
+    }
+
+    /// The calldata sent to the aggregator.
+    pub fn aggregate_calldata(&self) -> Vec<FieldElement> {
+        let mut calldata = vec![self.calls.len().into()];
+        for call in self.calls.iter() {
+            calldata.push(call.to);
+            calldata.push(call.selector);
+            calldata.push(call.calldata.len().into());
+            calldata.extend_from_slice(&call.calldata);
+        }
+        calldata
+    }
+
+    /// Splits the raw aggregator output into the result of each call.
+    fn split_results(&self, output: &[FieldElement]) -> Result<MulticallResults, DecodeError> {
+        let mut reader = FeltReader::new(output);
+
+        let block_number = reader.read()?;
+        let result_len: u64 = reader.read()?;
+        let result_len = result_len as usize;
+        if result_len > reader.remaining() {
+            return Err(DecodeError::UnexpectedEnd);
+        } else if result_len < reader.remaining() {
+            return Err(DecodeError::TrailingElements(
+                reader.remaining() - result_len,
+            ));
+        }
+
+        let results = (0..self.calls.len())
+            .map(|_| reader.read())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        Ok(MulticallResults {
+            block_number,
+            results,
+        })
+    }
+}
+
+impl<'p, P> Multicall<'p, P>
+where
+    P: Provider + Sync,
+{
+    /// Executes all calls at once.
+    pub async fn call(&self) -> Result<MulticallResults, ContractCallError<P::Error>> {
+        let result = self
+            .provider
+            .call_contract(
+                CallFunction {
+                    contract_address: self.aggregator,
+                    entry_point_selector: SELECTOR_AGGREGATE,
+                    calldata: self.aggregate_calldata(),
+                },
+                self.block_id,
+            )
+            .await
+            .map_err(ContractCallError::Provider)?;
+
+        self.split_results(&result.result)
+            .map_err(ContractCallError::Decode)
+    }
+}
+
+impl<R> Clone for MulticallResult<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for MulticallResult<R> {}
+
+impl MulticallResults {
+    /// Decodes the result of a call from the [Multicall] these results come from.
+    pub fn get<R>(&self, handle: MulticallResult<R>) -> Result<R, DecodeError>
+    where
+        R: CairoDeserialize,
+    {
+        R::cairo_deserialize_all(
+            self.results
+                .get(handle.index)
+                .ok_or(DecodeError::UnexpectedEnd)?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Erc20;
+
+    use starknet_core::{codec::U256, utils::get_selector_from_name};
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_aggregate_selector() {
+        assert_eq!(
+            SELECTOR_AGGREGATE,
+            get_selector_from_name("aggregate").unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_aggregate_round_trip() {
+        let token = Erc20::new(FieldElement::TWO, ());
+        let mut multicall = Multicall::new(FieldElement::ONE, &());
+
+        let decimals = multicall.add(&token.decimals());
+        let balance = multicall.add(&token.balance_of(FieldElement::THREE));
+
+        let calldata = multicall.aggregate_calldata();
+        assert_eq!(calldata[0], FieldElement::TWO);
+        assert_eq!(
+            calldata[1..3],
+            [FieldElement::TWO, token.decimals().as_call().selector]
+        );
+        assert_eq!(calldata[3], FieldElement::ZERO);
+        assert_eq!(calldata[6..8], [FieldElement::ONE, FieldElement::THREE]);
+
+        let results = multicall
+            .split_results(&[
+                FieldElement::from(100u8),
+                FieldElement::from(5u8),
+                FieldElement::ONE,
+                FieldElement::from(18u8),
+                FieldElement::TWO,
+                FieldElement::from(7u8),
+                FieldElement::ZERO,
+            ])
+            .unwrap();
+
+        assert_eq!(results.block_number, 100);
+        assert_eq!(results.get(decimals).unwrap(), 18);
+        assert_eq!(results.get(balance).unwrap(), U256::from(7u128));
+
+        // Truncated output
+        assert!(multicall
+            .split_results(&[
+                FieldElement::ONE,
+                FieldElement::from(5u8),
+                FieldElement::ONE
+            ])
+            .is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{erc20::Transfer, EventStream, EventStreamError, TypedEvent};
+
+use starknet_core::{codec::U256, event::StarknetEvent, types::FieldElement};
+use starknet_providers::jsonrpc::JsonRpcTransport;
+use std::collections::BTreeMap;
+
+/// Derives state from the events of type `E` emitted by a contract, in the order they were
+/// emitted.
+pub trait Reducer<E> {
+    type State: Clone;
+
+    fn reduce(&self, state: &mut Self::State, event: &TypedEvent<E>);
+}
+
+/// The state derived from all events up to and including block `block_number`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<S> {
+    pub block_number: u64,
+    /// Number of events reduced into the state.
+    pub event_count: u64,
+    pub state: S,
+}
+
+/// Reconstructs state by replaying historical events through a [Reducer], taking a [Snapshot]
+/// every [checkpoint_interval](StateReplay::checkpoint_interval) blocks.
+///
+/// Snapshots allow looking up past state without replaying from the start, and resuming a replay
+/// with [from_snapshot](StateReplay::from_snapshot).
+///
+/// ```ignore
+/// let mut replay = StateReplay::new(Erc20Balances).checkpoint_interval(10_000);
+/// replay.replay(token.events::<Transfer>().stream()).await?;
+/// let balance = replay.state().get(&holder);
+/// ```
+#[derive(Debug)]
+pub struct StateReplay<R, E>
+where
+    R: Reducer<E>,
+{
+    reducer: R,
+    state: R::State,
+    last_block: Option<u64>,
+    event_count: u64,
+    checkpoint_interval: u64,
+    snapshots: Vec<Snapshot<R::State>>,
+}
+
+/// A [Reducer] computing the balances of an ERC-20 token from its `Transfer` events. Mints and
+/// burns are transfers from and to the zero address, whose balance is not tracked.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Erc20Balances;
+
+impl<R, E> StateReplay<R, E>
+where
+    R: Reducer<E>,
+    R::State: Default,
+{
+    pub fn new(reducer: R) -> Self {
+        Self::with_state(reducer, Default::default())
+    }
+}
+
+impl<R, E> StateReplay<R, E>
+where
+    R: Reducer<E>,
+{
+    /// Replays events on top of `state` instead of the default state.
+    pub fn with_state(reducer: R, state: R::State) -> Self {
+        Self {
+            reducer,
+            state,
+            last_block: None,
+            event_count: 0,
+            checkpoint_interval: 0,
+            snapshots: vec![],
+        }
+    }
+
+    /// Resumes from `snapshot`. Events should then be replayed from block
+    /// `snapshot.block_number + 1`.
+    pub fn from_snapshot(reducer: R, snapshot: Snapshot<R::State>) -> Self {
+        Self {
+            reducer,
+            state: snapshot.state,
+            last_block: Some(snapshot.block_number),
+            event_count: snapshot.event_count,
+            checkpoint_interval: 0,
+            snapshots: vec![],
+        }
+    }
+
+    /// Sets the number of blocks between snapshots. Defaults to `0`, in which case no snapshot is
+    /// taken unless requested with [checkpoint](StateReplay::checkpoint).
+    pub fn checkpoint_interval(self, checkpoint_interval: u64) -> Self {
+        Self {
+            checkpoint_interval,
+            ..self
+        }
+    }
+
+    /// The state derived from the events reduced so far.
+    pub fn state(&self) -> &R::State {
+        &self.state
+    }
+
+    pub fn into_state(self) -> R::State {
+        self.state
+    }
+
+    /// The block of the last event reduced.
+    pub fn last_block(&self) -> Option<u64> {
+        self.last_block
+    }
+
+    /// Snapshots taken so far, in ascending block order.
+    pub fn snapshots(&self) -> &[Snapshot<R::State>] {
+        &self.snapshots
+    }
+
+    /// The latest snapshot taken at or before `block_number`.
+    pub fn snapshot_at(&self, block_number: u64) -> Option<&Snapshot<R::State>> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.block_number <= block_number)
+    }
+
+    /// Reduces the next event, which must not be from an earlier block than the previous one.
+    ///
+    /// Snapshots are taken as the first event of a block past a checkpoint boundary is reduced,
+    /// as only then are all events of the previous blocks known to have been reduced.
+    pub fn apply(&mut self, event: &TypedEvent<E>) {
+        if let Some(last_block) = self.last_block {
+            if self.checkpoint_interval > 0
+                && event.block_number / self.checkpoint_interval
+                    > last_block / self.checkpoint_interval
+            {
+                self.checkpoint(event.block_number - 1);
+            }
+        }
+
+        self.reducer.reduce(&mut self.state, event);
+        self.last_block = Some(event.block_number);
+        self.event_count += 1;
+    }
+
+    /// Takes a snapshot of the current state as of block `block_number`, e.g. once all events up
+    /// to the block have been replayed.
+    pub fn checkpoint(&mut self, block_number: u64) -> &Snapshot<R::State> {
+        self.snapshots.push(Snapshot {
+            block_number,
+            event_count: self.event_count,
+            state: self.state.clone(),
+        });
+        self.snapshots.last().expect("snapshot was just pushed")
+    }
+
+    /// Reduces all remaining events of `stream`, returning the number of events reduced.
+    pub async fn replay<T>(
+        &mut self,
+        mut stream: EventStream<'_, T, E>,
+    ) -> Result<u64, EventStreamError<T::Error>>
+    where
+        T: JsonRpcTransport + Sync,
+        E: StarknetEvent,
+    {
+        let mut count = 0;
+        while let Some(event) = stream.next().await {
+            self.apply(&event?);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl Reducer<Transfer> for Erc20Balances {
+    type State = BTreeMap<FieldElement, U256>;
+
+    fn reduce(&self, state: &mut Self::State, event: &TypedEvent<Transfer>) {
+        let transfer = &event.event;
+        if transfer.from != FieldElement::ZERO {
+            let balance = state.entry(transfer.from).or_default();
+            *balance = wrapping_sub(*balance, transfer.value);
+        }
+        if transfer.to != FieldElement::ZERO {
+            let balance = state.entry(transfer.to).or_default();
+            *balance = wrapping_add(*balance, transfer.value);
+        }
+    }
+}
+
+fn wrapping_add(a: U256, b: U256) -> U256 {
+    let (low, carry) = a.low.overflowing_add(b.low);
+    U256::from_words(low, a.high.wrapping_add(b.high).wrapping_add(carry as u128))
+}
+
+fn wrapping_sub(a: U256, b: U256) -> U256 {
+    let (low, borrow) = a.low.overflowing_sub(b.low);
+    U256::from_words(
+        low,
+        a.high.wrapping_sub(b.high).wrapping_sub(borrow as u128),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(block_number: u64, from: u64, to: u64, value: u128) -> TypedEvent<Transfer> {
+        TypedEvent {
+            event: Transfer {
+                from: from.into(),
+                to: to.into(),
+                value: value.into(),
+            },
+            from_address: FieldElement::ONE,
+            block_hash: FieldElement::ZERO,
+            block_number,
+            transaction_hash: FieldElement::ZERO,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_erc20_balances_with_checkpoints() {
+        let alice = FieldElement::from(0xau64);
+        let bob = FieldElement::from(0xbu64);
+
+        let mut replay = StateReplay::new(Erc20Balances).checkpoint_interval(10);
+        for event in [
+            transfer(3, 0, 0xa, 100),
+            transfer(8, 0xa, 0xb, 30),
+            transfer(8, 0xa, 0xb, 20),
+            transfer(25, 0xb, 0, 50),
+        ] {
+            replay.apply(&event);
+        }
+
+        assert_eq!(replay.state().get(&alice), Some(&U256::from(50u128)));
+        assert_eq!(replay.state().get(&bob), Some(&U256::from(0u128)));
+
+        assert_eq!(replay.snapshots().len(), 1);
+        let snapshot = replay.snapshot_at(30).unwrap();
+        assert_eq!(snapshot.block_number, 24);
+        assert_eq!(snapshot.event_count, 3);
+        assert_eq!(snapshot.state.get(&bob), Some(&U256::from(50u128)));
+        assert!(replay.snapshot_at(23).is_none());
+
+        // Resuming from the snapshot gives the same state
+        let mut resumed = StateReplay::from_snapshot(Erc20Balances, snapshot.clone());
+        resumed.apply(&transfer(25, 0xb, 0, 50));
+        assert_eq!(resumed.state(), replay.state());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_u256_wrapping_arithmetic() {
+        let max_low = U256::from_words(u128::MAX, 0);
+        assert_eq!(
+            wrapping_add(max_low, U256::from(1u128)),
+            U256::from_words(0, 1)
+        );
+        assert_eq!(
+            wrapping_sub(U256::from_words(0, 1), U256::from(1u128)),
+            max_low
+        );
+    }
+}
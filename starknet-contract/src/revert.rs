@@ -0,0 +1,169 @@
+use crate::ContractCallError;
+
+use starknet_core::{
+    types::{FieldElement, TransactionFailureReason},
+    utils::parse_cairo_short_string,
+};
+use starknet_providers::ProviderError;
+
+/// Marker preceding the message of a Cairo 0 `with_attr error_message` block in error traces.
+const ERROR_MESSAGE_MARKER: &str = "Error message: ";
+
+/// Marker preceding the felts a Cairo 1 contract panicked with.
+const FAILURE_REASON_MARKER: &str = "failure reason: ";
+
+/// Errors a contract reverts with, so that failed calls and transactions can be matched on:
+///
+/// ```ignore
+/// match token.transfer(recipient, amount).call().await {
+///     Err(err) if err.revert() == Some(Erc20Error::InsufficientBalance) => { /* ... */ }
+///     result => { /* ... */ }
+/// }
+/// ```
+///
+/// Implemented by bindings, including those [generated](crate::codegen) with declared errors.
+pub trait ContractError: Sized {
+    /// Decodes a single revert reason, i.e. the message of a Cairo 0 `with_attr error_message`
+    /// block or the short string a Cairo 1 contract panicked with.
+    fn from_revert_reason(reason: &str) -> Option<Self>;
+
+    /// Decodes the first known revert reason found in the error message of a failed call,
+    /// simulation or transaction.
+    fn from_error_message(message: &str) -> Option<Self> {
+        revert_reasons(message)
+            .iter()
+            .find_map(|reason| Self::from_revert_reason(reason))
+    }
+
+    fn from_failure_reason(failure_reason: &TransactionFailureReason) -> Option<Self> {
+        failure_reason
+            .error_message
+            .as_deref()
+            .and_then(Self::from_error_message)
+    }
+}
+
+impl<P> ContractCallError<P>
+where
+    P: std::fmt::Display,
+{
+    /// Decodes the error the contract reverted with, if the call failed with a known revert
+    /// reason.
+    pub fn revert<E>(&self) -> Option<E>
+    where
+        E: ContractError,
+    {
+        match self {
+            Self::Provider(ProviderError::Other(err)) => E::from_error_message(&err.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the revert reasons from the error message of a failed call, simulation or
+/// transaction, in the order they appear.
+///
+/// Cairo 0 reasons are the messages of `with_attr error_message` blocks, reported on their own
+/// line. Cairo 1 reasons are the felts a contract panicked with, which are returned as short
+/// strings when valid and as hex otherwise.
+pub fn revert_reasons(message: &str) -> Vec<String> {
+    let mut reasons = vec![];
+
+    for line in message.lines() {
+        if let Some((_, reason)) = line.split_once(ERROR_MESSAGE_MARKER) {
+            reasons.push(reason.trim().to_owned());
+        }
+
+        let mut rest = line;
+        while let Some((_, after)) = rest.split_once(FAILURE_REASON_MARKER) {
+            let felts = after
+                .trim_start()
+                .strip_prefix('[')
+                .and_then(|felts| felts.split_once(']'));
+            match felts {
+                Some((felts, after)) => {
+                    reasons.extend(
+                        felts
+                            .split(',')
+                            .filter_map(|felt| panic_reason(felt.trim())),
+                    );
+                    rest = after;
+                }
+                None => break,
+            }
+        }
+    }
+
+    reasons
+}
+
+fn panic_reason(felt: &str) -> Option<String> {
+    let value = FieldElement::from_hex_be(felt).ok()?;
+    match parse_cairo_short_string(&value) {
+        Ok(short_string)
+            if !short_string.is_empty()
+                && short_string
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() || c == ' ') =>
+        {
+            Some(short_string)
+        }
+        _ => Some(format!("{value:#x}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestError {
+        InsufficientBalance,
+        Overflow,
+    }
+
+    impl ContractError for TestError {
+        fn from_revert_reason(reason: &str) -> Option<Self> {
+            match reason {
+                "ERC20: transfer amount exceeds balance" | "ERC20: insufficient balance" => {
+                    Some(Self::InsufficientBalance)
+                }
+                "u256_add Overflow" => Some(Self::Overflow),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_cairo_0_revert_reasons() {
+        let message = "Error at pc=0:1207:\nAn ASSERT_EQ instruction failed: 1 != 0.\n\
+            Cairo traceback (most recent call last):\n\
+            Unknown location (pc=0:1222)\n\
+            Error message: ERC20: transfer amount exceeds balance\n\
+            Unknown location (pc=0:1302)\n";
+
+        assert_eq!(
+            revert_reasons(message),
+            vec!["ERC20: transfer amount exceeds balance"]
+        );
+        assert_eq!(
+            TestError::from_error_message(message),
+            Some(TestError::InsufficientBalance)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_cairo_1_revert_reasons() {
+        let message = "Execution was reverted; failure reason: \
+            [0x753235365f616464204f766572666c6f77, 0x1234].";
+
+        assert_eq!(revert_reasons(message), vec!["u256_add Overflow", "0x1234"]);
+        assert_eq!(
+            TestError::from_error_message(message),
+            Some(TestError::Overflow)
+        );
+        assert_eq!(TestError::from_error_message("Out of gas"), None);
+    }
+}
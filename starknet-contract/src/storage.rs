@@ -0,0 +1,198 @@
+use crate::{AbiCodec, AbiCodecError, Contract};
+
+use serde_json::Value;
+use starknet_core::{
+    codec::{CairoDeserialize, DecodeError},
+    types::{AsStarknetError, BlockId, FieldElement, StarknetError},
+    utils::{get_storage_var_address, NonAsciiNameError},
+};
+use starknet_providers::{Provider, ProviderError};
+
+/// A read of a storage variable, created with [Contract::storage].
+///
+/// Values larger than a felt, such as structs, are stored in consecutive slots starting at the
+/// variable address, so the number of slots to read is set with [size](StorageRead::size) when
+/// reading raw or typed values. When reading with an ABI, it's derived from the declared type
+/// instead.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct StorageRead<'p, P> {
+    provider: &'p P,
+    contract_address: FieldElement,
+    address: FieldElement,
+    size: usize,
+    block_id: BlockId,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageReadError<P> {
+    #[error(transparent)]
+    Provider(ProviderError<P>),
+    #[error("failed to decode storage value: {0}")]
+    Decode(#[source] DecodeError),
+    #[error(transparent)]
+    Abi(AbiCodecError),
+}
+
+impl<P> AsStarknetError for StorageReadError<P> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::Provider(err) => err.starknet_error(),
+            Self::Decode(_) | Self::Abi(_) => None,
+        }
+    }
+}
+
+impl<P> Contract<P> {
+    /// Reads the storage variable `name`. For mappings, `keys` holds the map keys, with
+    /// values larger than a felt such as `Uint256` given as all their felts in order.
+    pub fn storage(
+        &self,
+        name: &str,
+        keys: &[FieldElement],
+    ) -> Result<StorageRead<'_, P>, NonAsciiNameError> {
+        Ok(StorageRead {
+            provider: self.provider(),
+            contract_address: self.address(),
+            address: get_storage_var_address(name, keys)?,
+            size: 1,
+            block_id: BlockId::Latest,
+        })
+    }
+}
+
+impl<'p, P> StorageRead<'p, P> {
+    /// The address of the first storage slot of the variable.
+    pub fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    /// Sets the number of consecutive slots to read. Defaults to 1.
+    pub fn size(self, size: usize) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Sets the block to read the storage at. Defaults to [BlockId::Latest].
+    pub fn block_id(self, block_id: BlockId) -> Self {
+        Self { block_id, ..self }
+    }
+}
+
+impl<'p, P> StorageRead<'p, P>
+where
+    P: Provider + Sync,
+{
+    /// Reads the raw value of each slot.
+    pub async fn read_raw(&self) -> Result<Vec<FieldElement>, StorageReadError<P::Error>> {
+        let mut slots = Vec::with_capacity(self.size);
+        for offset in 0..self.size {
+            slots.push(
+                self.provider
+                    .get_storage_at(
+                        self.contract_address,
+                        self.address + FieldElement::from(offset),
+                        self.block_id,
+                    )
+                    .await
+                    .map_err(StorageReadError::Provider)?,
+            );
+        }
+        Ok(slots)
+    }
+
+    /// Reads and decodes the slots as `R`.
+    pub async fn read<R>(&self) -> Result<R, StorageReadError<P::Error>>
+    where
+        R: CairoDeserialize,
+    {
+        R::cairo_deserialize_all(&self.read_raw().await?).map_err(StorageReadError::Decode)
+    }
+
+    /// Reads the variable as the Cairo type `type_name` declared in its source, with structs
+    /// resolved from `codec`.
+    pub async fn read_with_abi(
+        self,
+        codec: &AbiCodec,
+        type_name: &str,
+    ) -> Result<Value, StorageReadError<P::Error>> {
+        let size = codec
+            .storage_size(type_name)
+            .map_err(StorageReadError::Abi)?;
+        let slots = self.size(size).read_raw().await?;
+
+        codec
+            .decode_storage(type_name, &slots)
+            .map_err(StorageReadError::Abi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::json;
+    use starknet_core::{codec::U256, utils::starknet_keccak};
+    use starknet_providers::jsonrpc::{
+        JsonRpcClient, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+    };
+    use std::sync::Mutex;
+
+    /// Serves the storage slot at `base + offset` as `offset + 1`, recording the keys requested.
+    #[derive(Debug)]
+    struct MockTransport {
+        base: FieldElement,
+        keys: Mutex<Vec<FieldElement>>,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    #[async_trait]
+    impl JsonRpcTransport for MockTransport {
+        type Error = MockError;
+
+        async fn send_request<P, R>(
+            &self,
+            _method: JsonRpcMethod,
+            params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: Serialize + Send,
+            R: DeserializeOwned,
+        {
+            let params = serde_json::to_value(params).unwrap();
+            let key = FieldElement::from_hex_be(params[1].as_str().unwrap()).unwrap();
+            self.keys.lock().unwrap().push(key);
+
+            let value = key - self.base + FieldElement::ONE;
+            Ok(
+                serde_json::from_value(json!({ "id": 1, "result": format!("{value:#x}") }))
+                    .unwrap(),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_map_entry() {
+        let holder = FieldElement::from_hex_be("0x1234").unwrap();
+        let base = get_storage_var_address("ERC20_balances", &[holder]).unwrap();
+        let transport = MockTransport {
+            base,
+            keys: Mutex::new(vec![]),
+        };
+        let contract = Contract::new(FieldElement::ONE, JsonRpcClient::new(&transport));
+
+        let read = contract.storage("ERC20_balances", &[holder]).unwrap();
+        assert_ne!(read.address(), starknet_keccak(b"ERC20_balances"));
+
+        let balance: U256 = read.size(2).read().await.unwrap();
+        assert_eq!(balance, U256::from_words(1, 2));
+        assert_eq!(
+            *transport.keys.lock().unwrap(),
+            vec![base, base + FieldElement::ONE]
+        );
+    }
+}
@@ -0,0 +1,515 @@
+//! Annotated call trees of transaction traces, for debugging transactions and simulations.
+//!
+//! A [TraceDecoder] resolves entrypoint names and decodes calldata, results and events with the
+//! ABIs of known contracts:
+//!
+//! ```ignore
+//! let decoder = TraceDecoder::new()
+//!     .with_contract_abi(token_address, &token_abi)
+//!     .with_class_abi(account_class_hash, &account_abi);
+//!
+//! let simulation = account.execute(calls).simulate().await?;
+//! println!("{}", decoder.decode(&simulation.trace).display().ansi(true));
+//! ```
+
+use crate::{AbiCodec, DecodedEvent};
+
+use serde_json::Value;
+use starknet_core::{
+    types::{
+        state_update::StateDiff,
+        trace::{FunctionInvocation, OrderedEventResponse},
+        AbiEntry, ExecutionResources, FieldElement, TransactionTrace,
+    },
+    utils::{get_selector_from_name, get_storage_var_address},
+};
+use std::{collections::HashMap, fmt};
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Registry of known entrypoints, storage variables and contract ABIs, for annotating traces.
+#[derive(Debug, Clone, Default)]
+pub struct TraceDecoder {
+    selector_names: HashMap<FieldElement, String>,
+    storage_names: HashMap<FieldElement, String>,
+    contract_abis: HashMap<FieldElement, AbiCodec>,
+    class_abis: HashMap<FieldElement, AbiCodec>,
+}
+
+/// A [TransactionTrace] annotated by a [TraceDecoder].
+#[derive(Debug)]
+pub struct AnnotatedTrace<'t> {
+    pub validation: Option<AnnotatedCall<'t>>,
+    pub execution: AnnotatedCall<'t>,
+    pub fee_transfer: Option<AnnotatedCall<'t>>,
+    /// Storage changes of the transaction, sorted by contract and key. Only available when
+    /// decoded with a state diff.
+    pub storage_diffs: Vec<AnnotatedStorageDiff>,
+}
+
+#[derive(Debug)]
+pub struct AnnotatedCall<'t> {
+    pub invocation: &'t FunctionInvocation,
+    /// The name of the entrypoint called, if known.
+    pub entrypoint: Option<String>,
+    /// The calldata decoded with the ABI of the contract, if known.
+    pub calldata: Option<Value>,
+    /// The result decoded with the ABI of the contract, if known.
+    pub result: Option<Value>,
+    pub events: Vec<AnnotatedEvent<'t>>,
+    pub internal_calls: Vec<AnnotatedCall<'t>>,
+}
+
+#[derive(Debug)]
+pub struct AnnotatedEvent<'t> {
+    pub event: &'t OrderedEventResponse,
+    /// The event decoded with the ABI of the emitting contract, if known.
+    pub decoded: Option<DecodedEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedStorageDiff {
+    pub contract_address: FieldElement,
+    pub key: FieldElement,
+    /// The name of the storage variable at `key`, for known variables without keys.
+    pub name: Option<String>,
+    pub value: FieldElement,
+}
+
+/// Renders an [AnnotatedTrace] as an indented call tree. Create one with
+/// [AnnotatedTrace::display].
+#[derive(Debug)]
+pub struct TraceDisplay<'a, 't> {
+    trace: &'a AnnotatedTrace<'t>,
+    ansi: bool,
+}
+
+impl TraceDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds entrypoint names to resolve selectors against.
+    pub fn with_selector_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for name in names.into_iter() {
+            if let Ok(selector) = get_selector_from_name(name.as_ref()) {
+                self.selector_names
+                    .insert(selector, name.as_ref().to_owned());
+            }
+        }
+        self
+    }
+
+    /// Adds storage variable names to resolve storage keys against. Only variables without keys
+    /// can be resolved, as keys of mappings can't be recovered from their addresses.
+    pub fn with_storage_var_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for name in names.into_iter() {
+            if let Ok(address) = get_storage_var_address(name.as_ref(), &[]) {
+                self.storage_names.insert(address, name.as_ref().to_owned());
+            }
+        }
+        self
+    }
+
+    /// Decodes calls to the contract at `contract_address` with `abi`.
+    pub fn with_contract_abi(mut self, contract_address: FieldElement, abi: &[AbiEntry]) -> Self {
+        let codec = AbiCodec::new(abi);
+        self = self.with_selector_names(codec.function_names().collect::<Vec<_>>());
+        self.contract_abis.insert(contract_address, codec);
+        self
+    }
+
+    /// Decodes calls to contracts of class `class_hash` with `abi`. This includes calls
+    /// delegated to the class by proxies.
+    pub fn with_class_abi(mut self, class_hash: FieldElement, abi: &[AbiEntry]) -> Self {
+        let codec = AbiCodec::new(abi);
+        self = self.with_selector_names(codec.function_names().collect::<Vec<_>>());
+        self.class_abis.insert(class_hash, codec);
+        self
+    }
+
+    pub fn decode<'t>(&self, trace: &'t TransactionTrace) -> AnnotatedTrace<'t> {
+        AnnotatedTrace {
+            validation: trace
+                .validate_invocation
+                .as_ref()
+                .map(|invocation| self.decode_call(invocation)),
+            execution: self.decode_call(&trace.function_invocation),
+            fee_transfer: trace
+                .fee_transfer_invocation
+                .as_ref()
+                .map(|invocation| self.decode_call(invocation)),
+            storage_diffs: vec![],
+        }
+    }
+
+    /// Decodes `trace` along with the storage changes of `state_diff`, e.g. of the block of a
+    /// single transaction.
+    pub fn decode_with_state_diff<'t>(
+        &self,
+        trace: &'t TransactionTrace,
+        state_diff: &StateDiff,
+    ) -> AnnotatedTrace<'t> {
+        let mut storage_diffs = state_diff
+            .storage_diffs
+            .iter()
+            .flat_map(|(contract_address, diffs)| {
+                diffs.iter().map(|diff| AnnotatedStorageDiff {
+                    contract_address: *contract_address,
+                    key: diff.key,
+                    name: self.storage_names.get(&diff.key).cloned(),
+                    value: diff.value,
+                })
+            })
+            .collect::<Vec<_>>();
+        storage_diffs
+            .sort_by_key(|diff| (diff.contract_address.to_bytes_be(), diff.key.to_bytes_be()));
+
+        AnnotatedTrace {
+            storage_diffs,
+            ..self.decode(trace)
+        }
+    }
+
+    fn decode_call<'t>(&self, invocation: &'t FunctionInvocation) -> AnnotatedCall<'t> {
+        let codec = self
+            .contract_abis
+            .get(&invocation.contract_address)
+            .or_else(|| {
+                invocation
+                    .class_hash
+                    .and_then(|class_hash| self.class_abis.get(&class_hash))
+            });
+        let entrypoint = invocation
+            .selector
+            .and_then(|selector| self.selector_names.get(&selector).cloned());
+
+        let (calldata, result) = match (codec, &entrypoint) {
+            (Some(codec), Some(entrypoint)) => (
+                codec.decode_calldata(entrypoint, &invocation.calldata).ok(),
+                codec.decode_output(entrypoint, &invocation.result).ok(),
+            ),
+            _ => (None, None),
+        };
+
+        AnnotatedCall {
+            invocation,
+            entrypoint,
+            calldata,
+            result,
+            events: invocation
+                .events
+                .iter()
+                .map(|event| AnnotatedEvent {
+                    event,
+                    decoded: codec
+                        .and_then(|codec| codec.decode_event(&event.keys, &event.data).ok()),
+                })
+                .collect(),
+            internal_calls: invocation
+                .internal_calls
+                .iter()
+                .map(|internal_call| self.decode_call(internal_call))
+                .collect(),
+        }
+    }
+}
+
+impl<'t> AnnotatedTrace<'t> {
+    /// Renders the trace as text, without colors by default.
+    pub fn display(&self) -> TraceDisplay<'_, 't> {
+        TraceDisplay {
+            trace: self,
+            ansi: false,
+        }
+    }
+}
+
+impl<'a, 't> TraceDisplay<'a, 't> {
+    /// Sets whether to render with ANSI colors, e.g. for terminals.
+    pub fn ansi(self, ansi: bool) -> Self {
+        Self { ansi, ..self }
+    }
+
+    fn style(&self, style: &str, text: &str) -> String {
+        if self.ansi {
+            format!("{style}{text}{RESET}")
+        } else {
+            text.to_owned()
+        }
+    }
+
+    fn write_call(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        call: &AnnotatedCall<'_>,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        let invocation = call.invocation;
+
+        let entrypoint = match (&call.entrypoint, invocation.selector) {
+            (Some(name), _) => name.to_owned(),
+            (None, Some(selector)) => format!("{selector:#x}"),
+            (None, None) => String::from("<unknown>"),
+        };
+        writeln!(
+            f,
+            "{indent}{} {}",
+            self.style(CYAN, &entrypoint),
+            self.style(DIM, &format!("@ {:#x}", invocation.contract_address))
+        )?;
+
+        match &call.calldata {
+            Some(calldata) => writeln!(
+                f,
+                "{indent}  calldata: {}",
+                self.style(GREEN, &calldata.to_string())
+            )?,
+            None if !invocation.calldata.is_empty() => writeln!(
+                f,
+                "{indent}  calldata: [{}]",
+                format_felts(&invocation.calldata)
+            )?,
+            None => {}
+        }
+        match &call.result {
+            Some(result) => writeln!(
+                f,
+                "{indent}  result: {}",
+                self.style(GREEN, &result.to_string())
+            )?,
+            None if !invocation.result.is_empty() => writeln!(
+                f,
+                "{indent}  result: [{}]",
+                format_felts(&invocation.result)
+            )?,
+            None => {}
+        }
+
+        for event in call.events.iter() {
+            match &event.decoded {
+                Some(decoded) => writeln!(
+                    f,
+                    "{indent}  event {}: {}",
+                    self.style(BOLD, &decoded.name),
+                    self.style(GREEN, &decoded.fields.to_string())
+                )?,
+                None => writeln!(
+                    f,
+                    "{indent}  event: keys [{}], data [{}]",
+                    format_felts(&event.event.keys),
+                    format_felts(&event.event.data)
+                )?,
+            }
+        }
+
+        writeln!(
+            f,
+            "{indent}  resources: {}",
+            format_resources(&invocation.execution_resources)
+        )?;
+
+        for internal_call in call.internal_calls.iter() {
+            self.write_call(f, internal_call, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 't> fmt::Display for TraceDisplay<'a, 't> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trace = self.trace;
+
+        if let Some(validation) = &trace.validation {
+            writeln!(f, "{}", self.style(BOLD, "validation:"))?;
+            self.write_call(f, validation, 1)?;
+        }
+
+        writeln!(f, "{}", self.style(BOLD, "execution:"))?;
+        self.write_call(f, &trace.execution, 1)?;
+
+        if let Some(fee_transfer) = &trace.fee_transfer {
+            writeln!(f, "{}", self.style(BOLD, "fee transfer:"))?;
+            self.write_call(f, fee_transfer, 1)?;
+        }
+
+        if !trace.storage_diffs.is_empty() {
+            writeln!(f, "{}", self.style(BOLD, "storage diffs:"))?;
+            let mut contract_address = None;
+            for diff in trace.storage_diffs.iter() {
+                if contract_address != Some(diff.contract_address) {
+                    writeln!(f, "  {:#x}", diff.contract_address)?;
+                    contract_address = Some(diff.contract_address);
+                }
+                let key = match &diff.name {
+                    Some(name) => format!("{} ({:#x})", self.style(CYAN, name), diff.key),
+                    None => format!("{:#x}", diff.key),
+                };
+                writeln!(f, "    {key}: {:#x}", diff.value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_felts(felts: &[FieldElement]) -> String {
+    felts
+        .iter()
+        .map(|felt| format!("{felt:#x}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_resources(resources: &ExecutionResources) -> String {
+    let counter = &resources.builtin_instance_counter;
+    let builtins = [
+        ("pedersen", counter.pedersen_builtin),
+        ("range_check", counter.range_check_builtin),
+        ("bitwise", counter.bitwise_builtin),
+        ("output", counter.output_builtin),
+        ("ecdsa", counter.ecdsa_builtin),
+        ("ec_op", counter.ec_op_builtin),
+    ];
+
+    let mut formatted = format!(
+        "{} steps, {} memory holes",
+        resources.n_steps, resources.n_memory_holes
+    );
+    for (name, count) in builtins {
+        if let Some(count) = count.filter(|count| *count > 0) {
+            formatted.push_str(&format!(", {count} {name}"));
+        }
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use starknet_core::types::ContractArtifact;
+
+    fn trace() -> TransactionTrace {
+        serde_json::from_str(include_str!(
+            "../../starknet-core/test-data/raw_gateway_responses/get_transaction_trace/4_with_validation.txt"
+        ))
+        .unwrap()
+    }
+
+    fn oz_account_abi() -> Vec<AbiEntry> {
+        serde_json::from_str::<ContractArtifact>(include_str!(
+            "../test-data/artifacts/oz_account.txt"
+        ))
+        .unwrap()
+        .abi
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_trace() {
+        let account_class_hash = FieldElement::from_hex_be(
+            "0x5079dc27d18918ec7a81be5933620ba90d2191092d70b07110991f7d724920d",
+        )
+        .unwrap();
+        let trace = trace();
+
+        let decoder = TraceDecoder::new().with_class_abi(account_class_hash, &oz_account_abi());
+        let annotated = decoder.decode(&trace);
+
+        let execution = &annotated.execution;
+        assert_eq!(execution.entrypoint.as_deref(), Some("__execute__"));
+        assert_eq!(
+            execution.calldata,
+            Some(json!({
+                "call_array": [{
+                    "to": "0x7f89c70c3351b02ccab9177c86f6c0efdf0dd27ca11d328e9e3d66d4a1e3d0a",
+                    "selector": "0x3d7905601c217734671143d457f0db37f7f8883112abd34b92c4abfeafde0c3",
+                    "data_offset": "0x0",
+                    "data_len": "0x2",
+                }],
+                "calldata": [
+                    "0x6621cb98496006869a03800e7c88cb882a8fbf53651cc626eeb1279d4d00264",
+                    "0x26eae462c46b178dccc46afa1be4b5bc32f37fdd037c0624bed934c26d0bbb3",
+                ],
+            }))
+        );
+        assert_eq!(
+            annotated.validation.as_ref().unwrap().entrypoint.as_deref(),
+            Some("__validate__")
+        );
+
+        // The called contract is unknown
+        let internal_call = &execution.internal_calls[0];
+        assert_eq!(internal_call.entrypoint, None);
+        assert_eq!(internal_call.calldata, None);
+
+        let rendered = annotated.display().to_string();
+        assert!(rendered.starts_with("validation:\n  __validate__ @ 0x5fb7f82414f88e8418bb5f973bbc8fcb660a91913da262f47ecf8e898b83b09\n    calldata: {\"call_array\":"));
+        assert!(rendered.contains(
+            "\n    0x3d7905601c217734671143d457f0db37f7f8883112abd34b92c4abfeafde0c3 @ "
+        ));
+        assert!(!rendered.contains('\x1b'));
+        assert!(annotated
+            .display()
+            .ansi(true)
+            .to_string()
+            .contains("\x1b[36m__execute__\x1b[0m"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_storage_diffs() {
+        let trace = trace();
+        let state_diff: StateDiff = serde_json::from_value(json!({
+            "storage_diffs": {
+                "0x2": [{ "key": "0x5", "value": "0x6" }],
+                "0x1": [
+                    {
+                        "key": format!("{:#x}", get_storage_var_address("total_supply", &[]).unwrap()),
+                        "value": "0x64",
+                    },
+                    { "key": "0x3", "value": "0x4" },
+                ],
+            },
+            "deployed_contracts": [],
+            "declared_contracts": [],
+        }))
+        .unwrap();
+
+        let decoder = TraceDecoder::new().with_storage_var_names(["total_supply"]);
+        let annotated = decoder.decode_with_state_diff(&trace, &state_diff);
+
+        assert_eq!(
+            annotated
+                .storage_diffs
+                .iter()
+                .map(|diff| (diff.contract_address, diff.name.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (FieldElement::ONE, None),
+                (FieldElement::ONE, Some("total_supply")),
+                (FieldElement::TWO, None),
+            ]
+        );
+        assert!(annotated
+            .display()
+            .to_string()
+            .contains("storage diffs:\n  0x1\n    0x3: 0x4\n    total_supply (0x"));
+    }
+}
@@ -0,0 +1,516 @@
+//! The canonical encoding of Cairo values as field elements, used for calldata, return values
+//! and events.
+//!
+//! Implementations for structs and enums are usually generated with
+//! `#[derive(CairoSerialize, CairoDeserialize)]` from `starknet-macros`. Struct fields are
+//! encoded one after the other, and enums as the index of their variant followed by its fields.
+
+use crate::types::FieldElement;
+
+use thiserror::Error;
+
+/// Number of bytes in each full word of a [ByteArray].
+const BYTES_PER_WORD: usize = 31;
+
+pub trait CairoSerialize {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>);
+
+    fn cairo_serialized(&self) -> Vec<FieldElement> {
+        let mut output = vec![];
+        self.cairo_serialize(&mut output);
+        output
+    }
+}
+
+pub trait CairoDeserialize: Sized {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError>;
+
+    /// Decodes a value that must span all of `felts`.
+    fn cairo_deserialize_all(felts: &[FieldElement]) -> Result<Self, DecodeError> {
+        let mut reader = FeltReader::new(felts);
+        let value = reader.read()?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+
+/// Reads values one after the other from a list of field elements.
+#[derive(Debug, Clone)]
+pub struct FeltReader<'a> {
+    felts: std::slice::Iter<'a, FieldElement>,
+}
+
+/// The Cairo `u256` type, made of two 128-bit limbs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct U256 {
+    pub low: u128,
+    pub high: u128,
+}
+
+/// The Cairo `ByteArray` type, i.e. an arbitrary long string of bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ByteArray(pub Vec<u8>);
+
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("not enough elements to decode value")]
+    UnexpectedEnd,
+    #[error("{0} unexpected elements left after decoding value")]
+    TrailingElements(usize),
+    #[error("field element value out of range")]
+    ValueOutOfRange,
+    #[error("invalid enum variant index: {0}")]
+    InvalidVariant(u64),
+    #[error("invalid UTF-8 string")]
+    InvalidUtf8,
+}
+
+impl<'a> FeltReader<'a> {
+    pub fn new(felts: &'a [FieldElement]) -> Self {
+        Self {
+            felts: felts.iter(),
+        }
+    }
+
+    pub fn read<T>(&mut self) -> Result<T, DecodeError>
+    where
+        T: CairoDeserialize,
+    {
+        T::cairo_deserialize(self)
+    }
+
+    pub fn next_felt(&mut self) -> Result<FieldElement, DecodeError> {
+        self.felts.next().copied().ok_or(DecodeError::UnexpectedEnd)
+    }
+
+    /// Number of elements not read yet.
+    pub fn remaining(&self) -> usize {
+        self.felts.len()
+    }
+
+    /// Checks that all elements have been read.
+    pub fn finish(self) -> Result<(), DecodeError> {
+        match self.remaining() {
+            0 => Ok(()),
+            count => Err(DecodeError::TrailingElements(count)),
+        }
+    }
+}
+
+impl U256 {
+    pub fn from_words(low: u128, high: u128) -> Self {
+        Self { low, high }
+    }
+
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut high = [0u8; 16];
+        let mut low = [0u8; 16];
+        high.copy_from_slice(&bytes[..16]);
+        low.copy_from_slice(&bytes[16..]);
+
+        Self {
+            low: u128::from_be_bytes(low),
+            high: u128::from_be_bytes(high),
+        }
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&self.high.to_be_bytes());
+        bytes[16..].copy_from_slice(&self.low.to_be_bytes());
+        bytes
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self::from_words(value, 0)
+    }
+}
+
+impl From<FieldElement> for U256 {
+    fn from(value: FieldElement) -> Self {
+        Self::from_be_bytes(value.to_bytes_be())
+    }
+}
+
+impl From<Vec<u8>> for ByteArray {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for ByteArray {
+    fn from(value: &str) -> Self {
+        Self(value.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for ByteArray {
+    fn from(value: String) -> Self {
+        Self(value.into_bytes())
+    }
+}
+
+impl TryFrom<ByteArray> for String {
+    type Error = DecodeError;
+
+    fn try_from(value: ByteArray) -> Result<Self, Self::Error> {
+        String::from_utf8(value.0).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl CairoSerialize for FieldElement {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        output.push(*self);
+    }
+}
+
+impl CairoDeserialize for FieldElement {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        reader.next_felt()
+    }
+}
+
+impl CairoSerialize for bool {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        output.push(if *self {
+            FieldElement::ONE
+        } else {
+            FieldElement::ZERO
+        });
+    }
+}
+
+impl CairoDeserialize for bool {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        let value = reader.next_felt()?;
+        if value == FieldElement::ZERO {
+            Ok(false)
+        } else if value == FieldElement::ONE {
+            Ok(true)
+        } else {
+            Err(DecodeError::ValueOutOfRange)
+        }
+    }
+}
+
+macro_rules! impl_cairo_serde_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl CairoSerialize for $int {
+                fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+                    output.push((*self).into());
+                }
+            }
+
+            impl CairoDeserialize for $int {
+                fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+                    reader
+                        .next_felt()?
+                        .try_into()
+                        .map_err(|_| DecodeError::ValueOutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_cairo_serde_for_int!(u8, u16, u32, u64);
+
+impl CairoSerialize for u128 {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        // Always below the field prime
+        output.push(FieldElement::from_byte_slice_be(&self.to_be_bytes()).unwrap());
+    }
+}
+
+impl CairoDeserialize for u128 {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        let bytes = reader.next_felt()?.to_bytes_be();
+        if bytes[..16].iter().any(|byte| *byte != 0) {
+            return Err(DecodeError::ValueOutOfRange);
+        }
+
+        let mut low = [0u8; 16];
+        low.copy_from_slice(&bytes[16..]);
+        Ok(u128::from_be_bytes(low))
+    }
+}
+
+impl CairoSerialize for U256 {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        self.low.cairo_serialize(output);
+        self.high.cairo_serialize(output);
+    }
+}
+
+impl CairoDeserialize for U256 {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            low: reader.read()?,
+            high: reader.read()?,
+        })
+    }
+}
+
+/// Encoded as full 31-byte words, followed by the remaining bytes and their count.
+impl CairoSerialize for ByteArray {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        let words = self.0.chunks_exact(BYTES_PER_WORD);
+        let pending_word = words.remainder();
+
+        output.push(words.len().into());
+        for word in words {
+            output.push(FieldElement::from_byte_slice_be(word).unwrap());
+        }
+        output.push(FieldElement::from_byte_slice_be(pending_word).unwrap());
+        output.push(pending_word.len().into());
+    }
+}
+
+impl CairoDeserialize for ByteArray {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        fn push_word(
+            word: FieldElement,
+            len: usize,
+            bytes: &mut Vec<u8>,
+        ) -> Result<(), DecodeError> {
+            let word = word.to_bytes_be();
+            let (padding, word) = word.split_at(32 - len);
+            if padding.iter().any(|byte| *byte != 0) {
+                return Err(DecodeError::ValueOutOfRange);
+            }
+            bytes.extend_from_slice(word);
+            Ok(())
+        }
+
+        let word_count: u64 = reader.read()?;
+
+        let mut bytes = vec![];
+        for _ in 0..word_count {
+            push_word(reader.next_felt()?, BYTES_PER_WORD, &mut bytes)?;
+        }
+
+        let pending_word = reader.next_felt()?;
+        let pending_len: u8 = reader.read()?;
+        if pending_len as usize >= BYTES_PER_WORD {
+            return Err(DecodeError::ValueOutOfRange);
+        }
+        push_word(pending_word, pending_len as usize, &mut bytes)?;
+
+        Ok(Self(bytes))
+    }
+}
+
+impl CairoSerialize for str {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        ByteArray::from(self).cairo_serialize(output);
+    }
+}
+
+/// Strings are encoded as [ByteArray]s.
+impl CairoSerialize for String {
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        self.as_str().cairo_serialize(output);
+    }
+}
+
+impl CairoDeserialize for String {
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        reader.read::<ByteArray>()?.try_into()
+    }
+}
+
+impl<T> CairoSerialize for [T]
+where
+    T: CairoSerialize,
+{
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        output.push(self.len().into());
+        for item in self.iter() {
+            item.cairo_serialize(output);
+        }
+    }
+}
+
+/// Arrays (and spans) are encoded as their length followed by their elements.
+impl<T> CairoSerialize for Vec<T>
+where
+    T: CairoSerialize,
+{
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        self.as_slice().cairo_serialize(output);
+    }
+}
+
+impl<T> CairoDeserialize for Vec<T>
+where
+    T: CairoDeserialize,
+{
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        let len: u64 = reader.read()?;
+        (0..len).map(|_| reader.read()).collect()
+    }
+}
+
+/// Encoded like the Cairo `Option` enum, where `Some` is variant 0 and `None` is variant 1.
+impl<T> CairoSerialize for Option<T>
+where
+    T: CairoSerialize,
+{
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        match self {
+            Some(value) => {
+                output.push(FieldElement::ZERO);
+                value.cairo_serialize(output);
+            }
+            None => output.push(FieldElement::ONE),
+        }
+    }
+}
+
+impl<T> CairoDeserialize for Option<T>
+where
+    T: CairoDeserialize,
+{
+    fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+        match reader.read::<u64>()? {
+            0 => Ok(Some(reader.read()?)),
+            1 => Ok(None),
+            index => Err(DecodeError::InvalidVariant(index)),
+        }
+    }
+}
+
+impl<T> CairoSerialize for &T
+where
+    T: CairoSerialize + ?Sized,
+{
+    fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+        (*self).cairo_serialize(output);
+    }
+}
+
+macro_rules! impl_cairo_serde_for_tuple {
+    ($($name:ident),*) => {
+        impl<$($name),*> CairoSerialize for ($($name,)*)
+        where
+            $($name: CairoSerialize,)*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn cairo_serialize(&self, output: &mut Vec<FieldElement>) {
+                let ($($name,)*) = self;
+                $($name.cairo_serialize(output);)*
+            }
+        }
+
+        impl<$($name),*> CairoDeserialize for ($($name,)*)
+        where
+            $($name: CairoDeserialize,)*
+        {
+            #[allow(unused_variables)]
+            fn cairo_deserialize(reader: &mut FeltReader<'_>) -> Result<Self, DecodeError> {
+                Ok(($(reader.read::<$name>()?,)*))
+            }
+        }
+    };
+}
+
+impl_cairo_serde_for_tuple!();
+impl_cairo_serde_for_tuple!(A);
+impl_cairo_serde_for_tuple!(A, B);
+impl_cairo_serde_for_tuple!(A, B, C);
+impl_cairo_serde_for_tuple!(A, B, C, D);
+impl_cairo_serde_for_tuple!(A, B, C, D, E);
+impl_cairo_serde_for_tuple!(A, B, C, D, E, F);
+impl_cairo_serde_for_tuple!(A, B, C, D, E, F, G);
+impl_cairo_serde_for_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felts(values: &[u64]) -> Vec<FieldElement> {
+        values.iter().map(|value| (*value).into()).collect()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_primitives_round_trip() {
+        let value = (
+            true,
+            300u16,
+            vec![Some(7u8), None],
+            U256::from_words(1, 2),
+            u128::MAX,
+        );
+
+        let encoded = value.cairo_serialized();
+        assert_eq!(&encoded[..8], &felts(&[1, 300, 2, 0, 7, 1, 1, 2])[..]);
+        assert_eq!(
+            encoded[8],
+            FieldElement::from_hex_be("0xffffffffffffffffffffffffffffffff").unwrap()
+        );
+        assert_eq!(
+            <(bool, u16, Vec<Option<u8>>, U256, u128)>::cairo_deserialize_all(&encoded).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_byte_array() {
+        // Test vector from the Cairo corelib
+        let value = "ABCDEFGHIJKLMNOPQRSTUVWXYZ12345ABCDEFGHIJ".to_owned();
+
+        let encoded = value.cairo_serialized();
+        assert_eq!(
+            encoded,
+            vec![
+                FieldElement::ONE,
+                FieldElement::from_hex_be(
+                    "0x4142434445464748494a4b4c4d4e4f505152535455565758595a3132333435"
+                )
+                .unwrap(),
+                FieldElement::from_hex_be("0x4142434445464748494a").unwrap(),
+                FieldElement::from(10u8),
+            ]
+        );
+        assert_eq!(String::cairo_deserialize_all(&encoded).unwrap(), value);
+        assert_eq!(ByteArray::from("").cairo_serialized(), felts(&[0, 0, 0]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decode_errors() {
+        assert!(matches!(
+            u8::cairo_deserialize_all(&felts(&[300])),
+            Err(DecodeError::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            bool::cairo_deserialize_all(&felts(&[2])),
+            Err(DecodeError::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            Option::<u8>::cairo_deserialize_all(&felts(&[2])),
+            Err(DecodeError::InvalidVariant(2))
+        ));
+        assert!(matches!(
+            Vec::<u8>::cairo_deserialize_all(&felts(&[2, 1])),
+            Err(DecodeError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            u8::cairo_deserialize_all(&felts(&[1, 2, 3])),
+            Err(DecodeError::TrailingElements(2))
+        ));
+        assert!(matches!(
+            ByteArray::cairo_deserialize_all(&felts(&[0, 0x4142, 1])),
+            Err(DecodeError::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            String::cairo_deserialize_all(&felts(&[0, 0xff, 1])),
+            Err(DecodeError::InvalidUtf8)
+        ));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::types::FieldElement;
 
-pub use starknet_crypto::{pedersen_hash, Signature};
-use starknet_crypto::{rfc6979_generate_k, sign, verify, SignError, VerifyError};
+pub use starknet_crypto::{
+    pedersen_hash, pedersen_hash_batch, pedersen_hash_many, poseidon_hash, poseidon_hash_many,
+    poseidon_hash_single, Signature,
+};
+use starknet_crypto::{rfc6979_generate_k, sign, verify, verify_strict, SignError, VerifyError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -21,14 +24,7 @@ pub enum EcdsaVerifyError {
 }
 
 pub fn compute_hash_on_elements(data: &[FieldElement]) -> FieldElement {
-    let mut current_hash = FieldElement::ZERO;
-
-    for item in data.iter() {
-        current_hash = pedersen_hash(&current_hash, item);
-    }
-
-    let data_len = FieldElement::from(data.len());
-    pedersen_hash(&current_hash, &data_len)
+    pedersen_hash_many(data)
 }
 
 pub fn ecdsa_sign(
@@ -63,7 +59,26 @@ pub fn ecdsa_verify(
     message_hash: &FieldElement,
     signature: &Signature,
 ) -> Result<bool, EcdsaVerifyError> {
-    match verify(public_key, message_hash, &signature.r, &signature.s) {
+    map_verify_result(verify(public_key, message_hash, &signature.r, &signature.s))
+}
+
+/// Same as [ecdsa_verify], but also rejects high-s signatures, i.e. those not
+/// [normalized](Signature::normalize), with [EcdsaVerifyError::SignatureSOutOfRange].
+pub fn ecdsa_verify_strict(
+    public_key: &FieldElement,
+    message_hash: &FieldElement,
+    signature: &Signature,
+) -> Result<bool, EcdsaVerifyError> {
+    map_verify_result(verify_strict(
+        public_key,
+        message_hash,
+        &signature.r,
+        &signature.s,
+    ))
+}
+
+fn map_verify_result(result: Result<bool, VerifyError>) -> Result<bool, EcdsaVerifyError> {
+    match result {
         Ok(result) => Ok(result),
         Err(VerifyError::InvalidMessageHash) => Err(EcdsaVerifyError::MessageHashOutOfRange),
         Err(VerifyError::InvalidR) => Err(EcdsaVerifyError::SignatureROutOfRange),
@@ -0,0 +1,68 @@
+//! Decoding raw events into typed structs. Implementations of [StarknetEvent] are usually
+//! generated with `#[derive(StarknetEvent)]` from `starknet-macros`.
+
+use crate::{codec::DecodeError, types::FieldElement};
+
+use thiserror::Error;
+
+/// An event type whose first key is the selector of its name, followed by the rest of its keys,
+/// and whose data holds its remaining fields. Fields use the encoding from [crate::codec].
+pub trait StarknetEvent: Sized {
+    /// The selector of the event name, i.e. the expected first key.
+    fn selector() -> FieldElement;
+
+    /// Decodes the event from its raw `keys`, including the selector, and `data`.
+    fn decode(keys: &[FieldElement], data: &[FieldElement]) -> Result<Self, DecodeEventError>;
+}
+
+#[derive(Debug, Error)]
+pub enum DecodeEventError {
+    #[error("event has no keys")]
+    MissingSelector,
+    #[error("event selector mismatch: expected {expected:#064x}, got {actual:#064x}")]
+    SelectorMismatch {
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+/// Checks that the first key is `selector` and returns the remaining keys.
+pub fn strip_selector(
+    keys: &[FieldElement],
+    selector: FieldElement,
+) -> Result<&[FieldElement], DecodeEventError> {
+    match keys.split_first() {
+        Some((actual, keys)) if *actual == selector => Ok(keys),
+        Some((actual, _)) => Err(DecodeEventError::SelectorMismatch {
+            expected: selector,
+            actual: *actual,
+        }),
+        None => Err(DecodeEventError::MissingSelector),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_strip_selector() {
+        let keys = [FieldElement::ONE, FieldElement::TWO];
+
+        assert_eq!(
+            strip_selector(&keys, FieldElement::ONE).unwrap(),
+            &[FieldElement::TWO]
+        );
+        assert!(matches!(
+            strip_selector(&keys, FieldElement::THREE),
+            Err(DecodeEventError::SelectorMismatch { .. })
+        ));
+        assert!(matches!(
+            strip_selector(&[], FieldElement::THREE),
+            Err(DecodeEventError::MissingSelector)
+        ));
+    }
+}
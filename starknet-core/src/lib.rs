@@ -7,6 +7,16 @@ pub mod types;
 
 pub mod crypto;
 
+pub mod proof;
+
+pub mod trie;
+
 pub mod utils;
 
 pub mod chain_id;
+
+pub mod codec;
+
+pub mod event;
+
+pub mod typed_data;
@@ -0,0 +1,307 @@
+//! Verification of Merkle-Patricia proofs against block state roots, for reading contract
+//! storage from untrusted nodes.
+
+use crate::{
+    crypto::pedersen_hash,
+    types::{Block, ContractData, FieldElement, StorageProof, TrieNode},
+};
+
+use std::collections::HashMap;
+
+/// Height of the contract and storage tries, whose keys are 251-bit.
+const TRIE_HEIGHT: usize = 251;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProofError {
+    #[error("block has no state root")]
+    MissingStateRoot,
+    #[error("state commitments with a class trie are not supported")]
+    UnsupportedStateCommitment,
+    #[error("unsupported contract state hash version {0:#x}")]
+    UnsupportedContractStateHashVersion(FieldElement),
+    #[error("proof does not match state root")]
+    StateRootMismatch,
+    #[error("proof node {0:#x} not found")]
+    MissingNode(FieldElement),
+    #[error("invalid edge node at height {0}")]
+    InvalidEdge(usize),
+    #[error("contract data missing for deployed contract")]
+    MissingContractData,
+    #[error("contract data does not match contract trie leaf")]
+    ContractStateMismatch,
+    #[error("proven value {actual:#x} does not match {expected:#x}")]
+    ValueMismatch {
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+}
+
+impl ContractData {
+    /// The hash of the contract state, i.e. its leaf in the contract trie.
+    pub fn hash(&self) -> Result<FieldElement, ProofError> {
+        if self.contract_state_hash_version != FieldElement::ZERO {
+            return Err(ProofError::UnsupportedContractStateHashVersion(
+                self.contract_state_hash_version,
+            ));
+        }
+
+        Ok(pedersen_hash(
+            &pedersen_hash(&pedersen_hash(&self.class_hash, &self.root), &self.nonce),
+            &FieldElement::ZERO,
+        ))
+    }
+}
+
+/// Verifies that storage `key` of contract `contract_address` holds `value` at `block`. A zero
+/// `value` can also be proven for contracts that are not deployed.
+///
+/// Only blocks whose state root is the root of the contract trie, i.e. without class
+/// commitment, are supported.
+pub fn verify_contract_storage(
+    block: &Block,
+    proof: &StorageProof,
+    contract_address: FieldElement,
+    key: FieldElement,
+    value: FieldElement,
+) -> Result<(), ProofError> {
+    let state_root = block.state_root.ok_or(ProofError::MissingStateRoot)?;
+    verify_storage_proof(state_root, proof, contract_address, key, value)
+}
+
+/// Same as [verify_contract_storage], against a state root already known to be valid, e.g. one
+/// verified by a light client.
+pub fn verify_storage_proof(
+    state_root: FieldElement,
+    proof: &StorageProof,
+    contract_address: FieldElement,
+    key: FieldElement,
+    value: FieldElement,
+) -> Result<(), ProofError> {
+    match proof.class_commitment {
+        Some(class_commitment) if class_commitment != FieldElement::ZERO => {
+            return Err(ProofError::UnsupportedStateCommitment)
+        }
+        _ => {}
+    }
+
+    let contracts_root = proof
+        .contract_proof
+        .first()
+        .map_or(FieldElement::ZERO, TrieNode::hash);
+    if contracts_root != state_root {
+        return Err(ProofError::StateRootMismatch);
+    }
+
+    let contract_leaf = get_leaf(
+        contracts_root,
+        contract_address,
+        &index_nodes(&proof.contract_proof),
+    )?;
+    let actual = match (contract_leaf, &proof.contract_data) {
+        // Storage of undeployed contracts is empty
+        (None, _) => FieldElement::ZERO,
+        (Some(leaf), Some(contract_data)) => {
+            if contract_data.hash()? != leaf {
+                return Err(ProofError::ContractStateMismatch);
+            }
+
+            let storage_nodes = index_nodes(contract_data.storage_proofs.iter().flatten());
+            get_leaf(contract_data.root, key, &storage_nodes)?.unwrap_or(FieldElement::ZERO)
+        }
+        (Some(_), None) => return Err(ProofError::MissingContractData),
+    };
+
+    if actual == value {
+        Ok(())
+    } else {
+        Err(ProofError::ValueMismatch {
+            expected: value,
+            actual,
+        })
+    }
+}
+
+/// Walks the trie with `root` down to the leaf of `key`, using proof nodes indexed by hash.
+/// Returns `None` if the proof shows the leaf to be absent.
+pub fn get_leaf(
+    root: FieldElement,
+    key: FieldElement,
+    nodes: &HashMap<FieldElement, &TrieNode>,
+) -> Result<Option<FieldElement>, ProofError> {
+    get_leaf_at_height(root, key, TRIE_HEIGHT, nodes)
+}
+
+/// Same as [get_leaf] for a trie of height `trie_height`.
+pub(crate) fn get_leaf_at_height(
+    root: FieldElement,
+    key: FieldElement,
+    trie_height: usize,
+    nodes: &HashMap<FieldElement, &TrieNode>,
+) -> Result<Option<FieldElement>, ProofError> {
+    if root == FieldElement::ZERO {
+        return Ok(None);
+    }
+
+    let key_bits = key.to_bits_le();
+    // Bit at `height` from the root, most significant first
+    let key_bit = |height: usize| key_bits[trie_height - 1 - height];
+
+    let mut hash = root;
+    let mut height = 0;
+    while height < trie_height {
+        match nodes.get(&hash).ok_or(ProofError::MissingNode(hash))? {
+            TrieNode::Binary { left, right } => {
+                hash = if key_bit(height) { *right } else { *left };
+                height += 1;
+            }
+            TrieNode::Edge { child, path } => {
+                let len = path.len as usize;
+                if len == 0 || height + len > trie_height {
+                    return Err(ProofError::InvalidEdge(height));
+                }
+
+                let path_bits = path.value.to_bits_le();
+                if (0..len).any(|ind| path_bits[len - 1 - ind] != key_bit(height + ind)) {
+                    return Ok(None);
+                }
+
+                hash = *child;
+                height += len;
+            }
+        }
+    }
+
+    Ok(Some(hash))
+}
+
+fn index_nodes<'a, I>(nodes: I) -> HashMap<FieldElement, &'a TrieNode>
+where
+    I: IntoIterator<Item = &'a TrieNode>,
+{
+    nodes.into_iter().map(|node| (node.hash(), node)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::types::EdgePath;
+
+    fn edge(child: FieldElement, value: u64, len: u8) -> TrieNode {
+        TrieNode::Edge {
+            child,
+            path: EdgePath {
+                value: FieldElement::from(value),
+                len,
+            },
+        }
+    }
+
+    /// Builds proofs for a contract whose storage holds `0x11` at key `0x1` and `0x33` at key
+    /// `0x3`, and returns them along with the block proven against.
+    fn storage_proofs() -> (Block, StorageProof) {
+        let leaf_1 = edge(FieldElement::from(0x11u64), 1, 1);
+        let leaf_3 = edge(FieldElement::from(0x33u64), 1, 1);
+        let branch = TrieNode::Binary {
+            left: leaf_1.hash(),
+            right: leaf_3.hash(),
+        };
+        let storage_root = edge(branch.hash(), 0, 249);
+
+        let contract_data = ContractData {
+            class_hash: FieldElement::from(0x1234u64),
+            nonce: FieldElement::ONE,
+            root: storage_root.hash(),
+            contract_state_hash_version: FieldElement::ZERO,
+            storage_proofs: vec![
+                vec![storage_root.clone(), branch.clone(), leaf_1],
+                vec![storage_root, branch, leaf_3],
+            ],
+        };
+        let contract_root = edge(contract_data.hash().unwrap(), 0x42, 251);
+
+        let mut block: Block = serde_json::from_str(include_str!(
+            "../test-data/raw_gateway_responses/get_block/1_with_transactions.txt"
+        ))
+        .unwrap();
+        block.state_root = Some(contract_root.hash());
+
+        let proof = StorageProof {
+            state_commitment: block.state_root,
+            class_commitment: None,
+            contract_proof: vec![contract_root],
+            contract_data: Some(contract_data),
+        };
+        (block, proof)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_verify_contract_storage() {
+        let (block, proof) = storage_proofs();
+        let contract = FieldElement::from(0x42u64);
+
+        for (key, value) in [(0x1u64, 0x11u64), (0x3, 0x33), (0x2, 0)] {
+            verify_contract_storage(
+                &block,
+                &proof,
+                contract,
+                FieldElement::from(key),
+                FieldElement::from(value),
+            )
+            .unwrap();
+        }
+
+        assert!(matches!(
+            verify_contract_storage(
+                &block,
+                &proof,
+                contract,
+                FieldElement::ONE,
+                FieldElement::from(0x12u64)
+            ),
+            Err(ProofError::ValueMismatch { .. })
+        ));
+
+        // The contract trie proves other contracts to be undeployed
+        verify_contract_storage(
+            &block,
+            &proof,
+            FieldElement::from(0x43u64),
+            FieldElement::ONE,
+            FieldElement::ZERO,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_verify_tampered_storage_proof() {
+        let (block, mut proof) = storage_proofs();
+        proof.contract_data.as_mut().unwrap().storage_proofs[0][2] =
+            edge(FieldElement::from(0x12u64), 1, 1);
+
+        assert!(matches!(
+            verify_contract_storage(
+                &block,
+                &proof,
+                FieldElement::from(0x42u64),
+                FieldElement::ONE,
+                FieldElement::from(0x12u64)
+            ),
+            Err(ProofError::MissingNode(_))
+        ));
+
+        proof.contract_data.as_mut().unwrap().nonce = FieldElement::TWO;
+        assert!(matches!(
+            verify_contract_storage(
+                &block,
+                &proof,
+                FieldElement::from(0x42u64),
+                FieldElement::THREE,
+                FieldElement::from(0x33u64)
+            ),
+            Err(ProofError::ContractStateMismatch)
+        ));
+    }
+}
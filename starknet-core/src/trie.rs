@@ -0,0 +1,350 @@
+//! Primitives of the binary Merkle-Patricia tries Starknet commits to state, transactions and
+//! events with.
+
+use crate::{
+    crypto::{pedersen_hash, poseidon_hash},
+    proof::{get_leaf_at_height, ProofError},
+    types::{EdgePath, FieldElement, TrieNode},
+};
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The hash function combining the children of trie nodes. Contract and storage tries use
+/// Pedersen, while class tries use Poseidon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrieHash {
+    Pedersen,
+    Poseidon,
+}
+
+/// A binary Merkle-Patricia trie of height `height`, mapping keys of up to `height` bits to
+/// non-zero values.
+///
+/// ```
+/// # use starknet_core::{trie::{verify_proof, Trie, TrieHash}, types::FieldElement};
+/// let mut trie = Trie::new(251, TrieHash::Pedersen);
+/// trie.insert(FieldElement::ONE, FieldElement::from(0x11u64));
+///
+/// let proof = trie.proof(FieldElement::ONE);
+/// verify_proof(
+///     TrieHash::Pedersen,
+///     251,
+///     trie.root(),
+///     FieldElement::ONE,
+///     FieldElement::from(0x11u64),
+///     &proof,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trie {
+    height: usize,
+    hash: TrieHash,
+    /// Leaves by big-endian key, which sorts them in key order.
+    leaves: BTreeMap<[u8; 32], (FieldElement, FieldElement)>,
+}
+
+impl TrieHash {
+    pub fn hash(&self, x: &FieldElement, y: &FieldElement) -> FieldElement {
+        match self {
+            Self::Pedersen => pedersen_hash(x, y),
+            Self::Poseidon => poseidon_hash(*x, *y),
+        }
+    }
+}
+
+impl TrieNode {
+    pub fn hash(&self) -> FieldElement {
+        self.hash_with(TrieHash::Pedersen)
+    }
+
+    pub fn hash_with(&self, hash: TrieHash) -> FieldElement {
+        match self {
+            Self::Binary { left, right } => hash.hash(left, right),
+            Self::Edge { child, path } => {
+                hash.hash(child, &path.value) + FieldElement::from(path.len)
+            }
+        }
+    }
+}
+
+impl Trie {
+    pub fn new(height: usize, hash: TrieHash) -> Self {
+        Self {
+            height,
+            hash,
+            leaves: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the value of `key`, returning its previous value. Setting a zero value removes the
+    /// leaf.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `key` doesn't fit in `height` bits.
+    pub fn insert(&mut self, key: FieldElement, value: FieldElement) -> FieldElement {
+        assert!(
+            key.to_bits_le()[self.height..].iter().all(|bit| !bit),
+            "key too large for trie height"
+        );
+
+        let previous = if value == FieldElement::ZERO {
+            self.leaves.remove(&key.to_bytes_be())
+        } else {
+            self.leaves.insert(key.to_bytes_be(), (key, value))
+        };
+        previous.map_or(FieldElement::ZERO, |(_, value)| value)
+    }
+
+    /// The value of `key`, which is zero for absent leaves.
+    pub fn get(&self, key: FieldElement) -> FieldElement {
+        self.leaves
+            .get(&key.to_bytes_be())
+            .map_or(FieldElement::ZERO, |(_, value)| *value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn root(&self) -> FieldElement {
+        let leaves = self.sorted_leaves();
+        if leaves.is_empty() {
+            FieldElement::ZERO
+        } else {
+            subtree_root(self.hash, &leaves, self.height)
+        }
+    }
+
+    /// The nodes on the path from the root to the leaf of `key`, root first, as expected by
+    /// [verify_proof]. For absent keys, the path ends at the node proving the leaf absent.
+    pub fn proof(&self, key: FieldElement) -> Vec<TrieNode> {
+        let key_bits = key.to_bits_le();
+        let mut leaves = &self.sorted_leaves()[..];
+        let mut height = self.height;
+        let mut proof = vec![];
+
+        while height > 0 && !leaves.is_empty() {
+            match subtree_node(self.hash, leaves, height) {
+                (node @ TrieNode::Binary { .. }, split) => {
+                    leaves = if key_bits[height - 1] {
+                        &leaves[split..]
+                    } else {
+                        &leaves[..split]
+                    };
+                    height -= 1;
+                    proof.push(node);
+                }
+                (node @ TrieNode::Edge { .. }, common_len) => {
+                    let first = &leaves[0].0;
+                    let on_path = (0..common_len)
+                        .all(|ind| first[height - 1 - ind] == key_bits[height - 1 - ind]);
+                    height -= common_len;
+                    proof.push(node);
+                    if !on_path {
+                        break;
+                    }
+                }
+            }
+        }
+
+        proof
+    }
+
+    fn sorted_leaves(&self) -> Vec<([bool; 256], FieldElement)> {
+        self.leaves
+            .values()
+            .map(|(key, value)| (key.to_bits_le(), *value))
+            .collect()
+    }
+}
+
+/// Computes the root of a trie of height `height` holding `leaves`, as `(key, value)` pairs with
+/// distinct keys. Leaves with a zero value are absent from the trie.
+pub fn compute_root(height: usize, leaves: &[(FieldElement, FieldElement)]) -> FieldElement {
+    let mut trie = Trie::new(height, TrieHash::Pedersen);
+    for (key, value) in leaves {
+        trie.insert(*key, *value);
+    }
+    trie.root()
+}
+
+/// Verifies that `key` holds `value` in the trie with `root`, using the nodes of `proof` such as
+/// returned by [Trie::proof]. A zero `value` is proven by a path showing the leaf absent.
+pub fn verify_proof(
+    hash: TrieHash,
+    height: usize,
+    root: FieldElement,
+    key: FieldElement,
+    value: FieldElement,
+    proof: &[TrieNode],
+) -> Result<(), ProofError> {
+    let nodes = proof
+        .iter()
+        .map(|node| (node.hash_with(hash), node))
+        .collect::<HashMap<_, _>>();
+    let actual = get_leaf_at_height(root, key, height, &nodes)?.unwrap_or(FieldElement::ZERO);
+
+    if actual == value {
+        Ok(())
+    } else {
+        Err(ProofError::ValueMismatch {
+            expected: value,
+            actual,
+        })
+    }
+}
+
+/// The root of the subtree with `height` levels below it holding `leaves`, sorted by key.
+fn subtree_root(
+    hash: TrieHash,
+    leaves: &[([bool; 256], FieldElement)],
+    height: usize,
+) -> FieldElement {
+    if height == 0 {
+        return leaves[0].1;
+    }
+
+    subtree_node(hash, leaves, height).0.hash_with(hash)
+}
+
+/// The top node of the subtree with `height > 0` levels below it holding the non-empty `leaves`,
+/// sorted by key. Also returns the number of leaves on the left of binary nodes, or the path
+/// length of edge nodes.
+fn subtree_node(
+    hash: TrieHash,
+    leaves: &[([bool; 256], FieldElement)],
+    height: usize,
+) -> (TrieNode, usize) {
+    // As keys are sorted, the first and last ones share the prefix common to all keys
+    let first = &leaves[0].0;
+    let last = &leaves[leaves.len() - 1].0;
+    let common_len = (0..height)
+        .take_while(|ind| first[height - 1 - ind] == last[height - 1 - ind])
+        .count();
+
+    if common_len == 0 {
+        let split = leaves.partition_point(|(bits, _)| !bits[height - 1]);
+        let node = TrieNode::Binary {
+            left: subtree_root(hash, &leaves[..split], height - 1),
+            right: subtree_root(hash, &leaves[split..], height - 1),
+        };
+        (node, split)
+    } else {
+        let child = subtree_root(hash, leaves, height - common_len);
+        let path = (0..common_len).fold(FieldElement::ZERO, |path, ind| {
+            path + path
+                + if first[height - 1 - ind] {
+                    FieldElement::ONE
+                } else {
+                    FieldElement::ZERO
+                }
+        });
+        let node = TrieNode::Edge {
+            child,
+            path: EdgePath {
+                value: path,
+                len: common_len as u8,
+            },
+        };
+        (node, common_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_compute_root() {
+        let leaf_1 = TrieNode::Edge {
+            child: FieldElement::from(0x11u64),
+            path: EdgePath {
+                value: FieldElement::ONE,
+                len: 1,
+            },
+        };
+        let leaf_3 = TrieNode::Edge {
+            child: FieldElement::from(0x33u64),
+            path: EdgePath {
+                value: FieldElement::ONE,
+                len: 1,
+            },
+        };
+        let branch = TrieNode::Binary {
+            left: leaf_1.hash(),
+            right: leaf_3.hash(),
+        };
+        let root = TrieNode::Edge {
+            child: branch.hash(),
+            path: EdgePath {
+                value: FieldElement::ZERO,
+                len: 249,
+            },
+        };
+
+        assert_eq!(
+            compute_root(
+                251,
+                &[
+                    (FieldElement::THREE, FieldElement::from(0x33u64)),
+                    (FieldElement::TWO, FieldElement::ZERO),
+                    (FieldElement::ONE, FieldElement::from(0x11u64)),
+                ]
+            ),
+            root.hash()
+        );
+        assert_eq!(compute_root(251, &[]), FieldElement::ZERO);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_trie_proofs() {
+        for hash in [TrieHash::Pedersen, TrieHash::Poseidon] {
+            let mut trie = Trie::new(251, hash);
+            for key in [0x1u64, 0x3, 0x8, 0x9, 0x400] {
+                trie.insert(FieldElement::from(key), FieldElement::from(key + 0x100));
+            }
+            assert_eq!(
+                trie.insert(FieldElement::from(0x400u64), FieldElement::ZERO),
+                FieldElement::from(0x500u64)
+            );
+            assert_eq!(trie.len(), 4);
+
+            let root = trie.root();
+            for key in [0x1u64, 0x3, 0x8, 0x9, 0x2, 0x400, 0x10] {
+                let key = FieldElement::from(key);
+                let value = trie.get(key);
+                verify_proof(hash, 251, root, key, value, &trie.proof(key)).unwrap();
+
+                assert!(matches!(
+                    verify_proof(
+                        hash,
+                        251,
+                        root,
+                        key,
+                        value + FieldElement::ONE,
+                        &trie.proof(key)
+                    ),
+                    Err(ProofError::ValueMismatch { .. })
+                ));
+            }
+        }
+
+        let mut trie = Trie::new(251, TrieHash::Pedersen);
+        trie.insert(FieldElement::ONE, FieldElement::TWO);
+        let mut poseidon_trie = Trie::new(251, TrieHash::Poseidon);
+        poseidon_trie.insert(FieldElement::ONE, FieldElement::TWO);
+        assert_ne!(trie.root(), poseidon_trie.root());
+        assert_eq!(
+            trie.root(),
+            compute_root(251, &[(FieldElement::ONE, FieldElement::TWO)])
+        );
+    }
+}
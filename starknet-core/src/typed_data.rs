@@ -0,0 +1,333 @@
+//! Hashing of off-chain messages following SNIP-12 (revision 0), the Starknet counterpart of
+//! EIP-712. Implementations of [TypedData] are usually generated with `#[derive(TypedData)]`
+//! from `starknet-macros`.
+
+use crate::{
+    crypto::compute_hash_on_elements,
+    types::FieldElement,
+    utils::{get_selector_from_name, starknet_keccak},
+};
+
+use std::collections::BTreeMap;
+
+/// Cairo short string `StarkNet Message`.
+const STARKNET_MESSAGE_PREFIX: FieldElement = FieldElement::from_mont([
+    16156019428408348868,
+    10480951322775611302,
+    18446744073709551605,
+    257012186512350467,
+]);
+
+/// A struct type that can be signed as a SNIP-12 message, or nested in one.
+pub trait TypedData {
+    /// The type name used in encoded types.
+    const TYPE_NAME: &'static str;
+
+    /// Names and types of the struct members in order, e.g. `[("wallet", "felt")]`.
+    fn members() -> Vec<(&'static str, String)>;
+
+    /// Adds the definition of this type and of all struct types it references to `types`,
+    /// keyed by type name.
+    fn collect_types(types: &mut BTreeMap<&'static str, String>);
+
+    /// The encoded member values, as hashed in [struct_hash](TypedData::struct_hash).
+    fn encode_members(&self) -> Vec<FieldElement>;
+
+    /// The definition of this type alone, e.g. `Person(name:felt,wallet:felt)`.
+    fn type_definition() -> String {
+        let members = Self::members()
+            .into_iter()
+            .map(|(name, member_type)| format!("{name}:{member_type}"))
+            .collect::<Vec<_>>();
+
+        format!("{}({})", Self::TYPE_NAME, members.join(","))
+    }
+
+    /// The definition of this type followed by the definitions of all referenced types in
+    /// alphabetical order.
+    fn encode_type() -> String {
+        let mut types = BTreeMap::new();
+        Self::collect_types(&mut types);
+
+        let mut encoded = types
+            .remove(Self::TYPE_NAME)
+            .unwrap_or_else(Self::type_definition);
+        for definition in types.into_values() {
+            encoded.push_str(&definition);
+        }
+        encoded
+    }
+
+    fn type_hash() -> FieldElement {
+        starknet_keccak(Self::encode_type().as_bytes())
+    }
+
+    fn struct_hash(&self) -> FieldElement {
+        let mut elements = vec![Self::type_hash()];
+        elements.extend(self.encode_members());
+        compute_hash_on_elements(&elements)
+    }
+
+    /// The hash to sign for this message to be verified by `account` in `domain`.
+    fn message_hash(&self, domain: &Domain, account: FieldElement) -> FieldElement {
+        compute_hash_on_elements(&[
+            STARKNET_MESSAGE_PREFIX,
+            domain.struct_hash(),
+            account,
+            self.struct_hash(),
+        ])
+    }
+}
+
+/// A value that can be a member of a [TypedData] struct.
+pub trait TypedDataMember {
+    /// The member type name, used when not overridden with `#[typed_data(type = "...")]`.
+    fn type_name() -> String;
+
+    fn encode_member(&self) -> FieldElement;
+
+    /// Adds the definitions of the struct types this member references.
+    fn collect_types(_types: &mut BTreeMap<&'static str, String>) {}
+}
+
+/// The `StarkNetDomain` separating messages of different dapps and chains. `name` is usually a
+/// Cairo short string, while numeric versions such as `"1"` are encoded as numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Domain {
+    pub name: FieldElement,
+    pub version: FieldElement,
+    pub chain_id: FieldElement,
+}
+
+/// A `selector` member, encoded as the selector of the entrypoint name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector(pub String);
+
+impl TypedData for Domain {
+    const TYPE_NAME: &'static str = "StarkNetDomain";
+
+    fn members() -> Vec<(&'static str, String)> {
+        vec![
+            ("name", "felt".into()),
+            ("version", "felt".into()),
+            ("chainId", "felt".into()),
+        ]
+    }
+
+    fn collect_types(types: &mut BTreeMap<&'static str, String>) {
+        types.insert(Self::TYPE_NAME, Self::type_definition());
+    }
+
+    fn encode_members(&self) -> Vec<FieldElement> {
+        vec![self.name, self.version, self.chain_id]
+    }
+}
+
+impl TypedDataMember for FieldElement {
+    fn type_name() -> String {
+        "felt".into()
+    }
+
+    fn encode_member(&self) -> FieldElement {
+        *self
+    }
+}
+
+impl TypedDataMember for bool {
+    fn type_name() -> String {
+        "bool".into()
+    }
+
+    fn encode_member(&self) -> FieldElement {
+        if *self {
+            FieldElement::ONE
+        } else {
+            FieldElement::ZERO
+        }
+    }
+}
+
+macro_rules! impl_typed_data_member_for_uint {
+    ($type:ty, $name:literal) => {
+        impl TypedDataMember for $type {
+            fn type_name() -> String {
+                $name.into()
+            }
+
+            fn encode_member(&self) -> FieldElement {
+                (*self).into()
+            }
+        }
+    };
+}
+
+impl_typed_data_member_for_uint!(u8, "felt");
+impl_typed_data_member_for_uint!(u16, "felt");
+impl_typed_data_member_for_uint!(u32, "felt");
+impl_typed_data_member_for_uint!(u64, "felt");
+
+impl TypedDataMember for u128 {
+    fn type_name() -> String {
+        "u128".into()
+    }
+
+    fn encode_member(&self) -> FieldElement {
+        // Always below the field prime
+        FieldElement::from_byte_slice_be(&self.to_be_bytes()).unwrap()
+    }
+}
+
+impl TypedDataMember for Selector {
+    fn type_name() -> String {
+        "selector".into()
+    }
+
+    /// Names that aren't ASCII have no selector and are encoded as zero.
+    fn encode_member(&self) -> FieldElement {
+        get_selector_from_name(&self.0).unwrap_or_default()
+    }
+}
+
+/// Arrays are encoded as the hash of their elements.
+impl<T> TypedDataMember for Vec<T>
+where
+    T: TypedDataMember,
+{
+    fn type_name() -> String {
+        format!("{}*", T::type_name())
+    }
+
+    fn encode_member(&self) -> FieldElement {
+        compute_hash_on_elements(
+            &self
+                .iter()
+                .map(|element| element.encode_member())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn collect_types(types: &mut BTreeMap<&'static str, String>) {
+        T::collect_types(types);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::cairo_short_string_to_felt;
+
+    struct Person {
+        name: FieldElement,
+        wallet: FieldElement,
+    }
+
+    struct Mail {
+        from: Person,
+        to: Person,
+        contents: FieldElement,
+    }
+
+    impl TypedData for Person {
+        const TYPE_NAME: &'static str = "Person";
+
+        fn members() -> Vec<(&'static str, String)> {
+            vec![("name", "felt".into()), ("wallet", "felt".into())]
+        }
+
+        fn collect_types(types: &mut BTreeMap<&'static str, String>) {
+            types.insert(Self::TYPE_NAME, Self::type_definition());
+        }
+
+        fn encode_members(&self) -> Vec<FieldElement> {
+            vec![self.name, self.wallet]
+        }
+    }
+
+    impl TypedData for Mail {
+        const TYPE_NAME: &'static str = "Mail";
+
+        fn members() -> Vec<(&'static str, String)> {
+            vec![
+                ("from", "Person".into()),
+                ("to", "Person".into()),
+                ("contents", "felt".into()),
+            ]
+        }
+
+        fn collect_types(types: &mut BTreeMap<&'static str, String>) {
+            types.insert(Self::TYPE_NAME, Self::type_definition());
+            Person::collect_types(types);
+        }
+
+        fn encode_members(&self) -> Vec<FieldElement> {
+            vec![
+                self.from.struct_hash(),
+                self.to.struct_hash(),
+                self.contents,
+            ]
+        }
+    }
+
+    fn short_string(value: &str) -> FieldElement {
+        cairo_short_string_to_felt(value).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_message_prefix() {
+        assert_eq!(STARKNET_MESSAGE_PREFIX, short_string("StarkNet Message"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_domain_type_hash() {
+        assert_eq!(
+            Domain::type_hash(),
+            FieldElement::from_hex_be(
+                "0x1bfc207425a47a5dfa1a50a4f5241203f50624ca5fdf5e18755765416b8e288"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_mail_message_hash() {
+        // Example from `starknet.js`
+        let mail = Mail {
+            from: Person {
+                name: short_string("Cow"),
+                wallet: FieldElement::from_hex_be("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826")
+                    .unwrap(),
+            },
+            to: Person {
+                name: short_string("Bob"),
+                wallet: FieldElement::from_hex_be("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB")
+                    .unwrap(),
+            },
+            contents: short_string("Hello, Bob!"),
+        };
+        let domain = Domain {
+            name: short_string("StarkNet Mail"),
+            // Numeric strings are encoded as numbers rather than short strings
+            version: FieldElement::ONE,
+            chain_id: FieldElement::ONE,
+        };
+
+        assert_eq!(
+            Mail::encode_type(),
+            "Mail(from:Person,to:Person,contents:felt)Person(name:felt,wallet:felt)"
+        );
+        assert_eq!(
+            mail.message_hash(
+                &domain,
+                FieldElement::from_hex_be("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826").unwrap()
+            ),
+            FieldElement::from_hex_be(
+                "0x6fcff244f63e38b9d88b9e3378d44757710d1b244282b435cb472053c8d78d0"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -2,10 +2,15 @@ use super::{
     super::serde::unsigned_field_element::{UfeHex, UfeHexOption},
     ConfirmedTransactionReceipt, FieldElement, TransactionType,
 };
+use crate::{
+    crypto::{compute_hash_on_elements, pedersen_hash},
+    trie::compute_root,
+};
 
 use serde::Deserialize;
 use serde_with::serde_as;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockId {
     Hash(FieldElement),
     Number(u64),
@@ -56,10 +61,216 @@ pub struct Block {
     pub starknet_version: Option<String>,
 }
 
+/// Signature of a block by the sequencer, over the Pedersen hash of the signature input.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub struct BlockSignature {
+    pub block_number: u64,
+    #[serde_as(deserialize_as = "Vec<UfeHex>")]
+    pub signature: Vec<FieldElement>,
+    pub signature_input: BlockSignatureInput,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub struct BlockSignatureInput {
+    #[serde_as(as = "UfeHex")]
+    pub block_hash: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub state_diff_commitment: FieldElement,
+}
+
+/// Height of the transaction and event commitment tries, keyed by index.
+const COMMITMENT_TRIE_HEIGHT: usize = 64;
+
+impl Block {
+    /// Computes the hash of the block from its contents, following the format of Starknet v0.7.0
+    /// onwards. Returns `None` for pending blocks.
+    pub fn compute_hash(&self) -> Option<FieldElement> {
+        let block_number = self.block_number?;
+        let state_root = self.state_root?;
+        let transaction_leaves = self.transaction_leaves();
+        let event_leaves = self.event_leaves();
+
+        Some(compute_hash_on_elements(&[
+            FieldElement::from(block_number),
+            state_root,
+            self.sequencer_address.unwrap_or(FieldElement::ZERO),
+            FieldElement::from(self.timestamp),
+            FieldElement::from(transaction_leaves.len()),
+            compute_root(COMMITMENT_TRIE_HEIGHT, &transaction_leaves),
+            FieldElement::from(event_leaves.len()),
+            compute_root(COMMITMENT_TRIE_HEIGHT, &event_leaves),
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            self.parent_block_hash,
+        ]))
+    }
+
+    /// Computes the hash of the block from its contents, following the format of blocks before
+    /// Starknet v0.7.0, which committed to the chain ID instead of events.
+    pub fn compute_legacy_hash(&self, chain_id: FieldElement) -> Option<FieldElement> {
+        let block_number = self.block_number?;
+        let state_root = self.state_root?;
+        let transaction_leaves = self.transaction_leaves();
+
+        Some(compute_hash_on_elements(&[
+            FieldElement::from(block_number),
+            state_root,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::from(transaction_leaves.len()),
+            compute_root(COMMITMENT_TRIE_HEIGHT, &transaction_leaves),
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            chain_id,
+            self.parent_block_hash,
+        ]))
+    }
+
+    fn transaction_leaves(&self) -> Vec<(FieldElement, FieldElement)> {
+        // Declare and deploy account signatures are only committed to since Starknet v0.11.1
+        let signs_all_transactions = self
+            .starknet_version
+            .as_deref()
+            .and_then(parse_version)
+            .is_some_and(|version| version >= (0, 11, 1));
+        self.transactions
+            .iter()
+            .enumerate()
+            .map(|(ind, tx)| {
+                (
+                    FieldElement::from(ind),
+                    transaction_leaf(tx, signs_all_transactions),
+                )
+            })
+            .collect()
+    }
+
+    fn event_leaves(&self) -> Vec<(FieldElement, FieldElement)> {
+        let mut receipts = self.transaction_receipts.iter().collect::<Vec<_>>();
+        receipts.sort_by_key(|receipt| receipt.transaction_index);
+        receipts
+            .iter()
+            .flat_map(|receipt| receipt.events.iter())
+            .enumerate()
+            .map(|(ind, event)| {
+                (
+                    FieldElement::from(ind),
+                    compute_hash_on_elements(&[
+                        event.from_address,
+                        compute_hash_on_elements(&event.keys),
+                        compute_hash_on_elements(&event.data),
+                    ]),
+                )
+            })
+            .collect()
+    }
+}
+
+fn transaction_leaf(tx: &TransactionType, signs_all_transactions: bool) -> FieldElement {
+    let (transaction_hash, signature) = match tx {
+        TransactionType::InvokeFunction(tx) => (tx.transaction_hash, &tx.signature[..]),
+        TransactionType::Declare(tx) if signs_all_transactions => {
+            (tx.transaction_hash, &tx.signature[..])
+        }
+        TransactionType::DeployAccount(tx) if signs_all_transactions => {
+            (tx.transaction_hash, &tx.signature[..])
+        }
+        TransactionType::Declare(tx) => (tx.transaction_hash, &[][..]),
+        TransactionType::DeployAccount(tx) => (tx.transaction_hash, &[][..]),
+        TransactionType::Deploy(tx) => (tx.transaction_hash, &[][..]),
+        TransactionType::L1Handler(tx) => (tx.transaction_hash, &[][..]),
+    };
+    pedersen_hash(&transaction_hash, &compute_hash_on_elements(signature))
+}
+
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_block_signature_deser() {
+        let signature: BlockSignature = serde_json::from_str(
+            r#"{
+                "block_number": 375919,
+                "signature": [
+                    "0x2f0a4f4d3ca9ba1a68f1ad913e3456e1a2f4b0a9a7bbbd3e0c1a3a85a4b8ed8",
+                    "0x4c3a6c7c1f7cdcd1e1a1a8e0e0b0a5b93b5c3dd1f6e0c8b16d7b8e6a3b9d2f1"
+                ],
+                "signature_input": {
+                    "block_hash": "0x5a8c7eb8b8fbe5c6ee76591fe093fe906d93805e9cf1a82d55410b17977e373",
+                    "state_diff_commitment": "0x432e8e2ad833548e1c1077fc298991b055ba1e6f7a17dd332db98f4f428c56c"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(signature.block_number, 375919);
+        assert_eq!(signature.signature.len(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_block_compute_hash() {
+        for raw in [
+            include_str!("../../test-data/raw_gateway_responses/get_block/3_with_events.txt"),
+            include_str!(
+                "../../test-data/raw_gateway_responses/get_block/6_with_sequencer_address.txt"
+            ),
+            include_str!("../../test-data/raw_gateway_responses/get_block/7_with_declare_tx.txt"),
+            include_str!(
+                "../../test-data/raw_gateway_responses/get_block/8_with_starknet_version.txt"
+            ),
+            include_str!("../../test-data/raw_gateway_responses/get_block/10_with_l1_handler.txt"),
+            include_str!(
+                "../../test-data/raw_gateway_responses/get_block/13_without_entry_point.txt"
+            ),
+            include_str!("../../test-data/raw_gateway_responses/get_block/14_deploy_account.txt"),
+        ] {
+            let block: Block = serde_json::from_str(raw).unwrap();
+            assert_eq!(block.compute_hash(), block.block_hash);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_block_compute_legacy_hash() {
+        for raw in [
+            include_str!("../../test-data/raw_gateway_responses/get_block/1_with_transactions.txt"),
+            include_str!(
+                "../../test-data/raw_gateway_responses/get_block/9_with_messages_without_nonce.txt"
+            ),
+        ] {
+            let block: Block = serde_json::from_str(raw).unwrap();
+            assert_ne!(block.compute_hash(), block.block_hash);
+            assert_eq!(
+                block.compute_legacy_hash(crate::chain_id::TESTNET),
+                block.block_hash
+            );
+        }
+
+        let pending: Block = serde_json::from_str(include_str!(
+            "../../test-data/raw_gateway_responses/get_block/4_pending.txt"
+        ))
+        .unwrap();
+        assert_eq!(pending.compute_hash(), None);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_block_deser_with_transactions() {
@@ -1,4 +1,6 @@
-#[derive(Debug, thiserror::Error)]
+/// Errors defined by the Starknet JSON-RPC specification, shared by every layer built on top of
+/// providers. See [AsStarknetError] for finding them in the errors of those layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
 pub enum Error {
     #[error("Failed to write transaction")]
     FailedToReceiveTxn,
@@ -27,3 +29,78 @@ pub enum Error {
     #[error("Invalid contract class")]
     InvalidContractClass,
 }
+
+/// Errors that may be caused by a [Starknet error](Error), for matching on it regardless of the
+/// layer the error is returned by.
+pub trait AsStarknetError {
+    /// The Starknet error causing this error, if any.
+    fn starknet_error(&self) -> Option<Error>;
+
+    /// The JSON-RPC error code of the Starknet error causing this error, if any.
+    fn starknet_error_code(&self) -> Option<i64> {
+        self.starknet_error().map(|err| err.code())
+    }
+}
+
+impl Error {
+    /// The error code assigned by the JSON-RPC specification.
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::FailedToReceiveTxn => 1,
+            Self::ContractNotFound => 20,
+            Self::InvalidMessageSelector => 21,
+            Self::InvalidCallData => 22,
+            Self::BlockNotFound => 24,
+            Self::TxnHashNotFound => 25,
+            Self::InvalidTxnIndex => 27,
+            Self::ClassHashNotFound => 28,
+            Self::PageSizeTooBig => 31,
+            Self::NoBlocks => 32,
+            Self::InvalidContinuationToken => 33,
+            Self::ContractError => 40,
+            Self::InvalidContractClass => 50,
+        }
+    }
+
+    pub fn from_code(code: i64) -> Option<Self> {
+        Some(match code {
+            1 => Self::FailedToReceiveTxn,
+            20 => Self::ContractNotFound,
+            21 => Self::InvalidMessageSelector,
+            22 => Self::InvalidCallData,
+            24 => Self::BlockNotFound,
+            25 => Self::TxnHashNotFound,
+            27 => Self::InvalidTxnIndex,
+            28 => Self::ClassHashNotFound,
+            31 => Self::PageSizeTooBig,
+            32 => Self::NoBlocks,
+            33 => Self::InvalidContinuationToken,
+            40 => Self::ContractError,
+            50 => Self::InvalidContractClass,
+            _ => return None,
+        })
+    }
+}
+
+impl AsStarknetError for Error {
+    fn starknet_error(&self) -> Option<Error> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_error_code_roundtrip() {
+        for code in 0..100 {
+            if let Some(err) = Error::from_code(code) {
+                assert_eq!(err.code(), code);
+            }
+        }
+        assert_eq!(Error::from_code(24), Some(Error::BlockNotFound));
+        assert_eq!(Error::from_code(23), None);
+    }
+}
@@ -2,7 +2,7 @@ use serde::Deserialize;
 
 use super::TransactionTrace;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct FeeEstimate {
     pub overall_fee: u64,
@@ -19,7 +19,7 @@ pub struct TransactionSimulationInfo {
     pub fee_estimation: FeeEstimate,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub enum FeeUnit {
     #[serde(rename = "wei")]
@@ -2,7 +2,7 @@
 pub use ethereum_types::Address as L1Address;
 
 mod block;
-pub use block::{Block, BlockId, BlockStatus};
+pub use block::{Block, BlockId, BlockSignature, BlockSignatureInput, BlockStatus};
 
 mod transaction;
 pub use transaction::{
@@ -19,7 +19,7 @@ pub use transaction_receipt::{
 };
 
 mod error;
-pub use error::Error as StarknetError;
+pub use error::{AsStarknetError, Error as StarknetError};
 
 mod contract_code;
 pub use contract_code::{
@@ -55,4 +55,7 @@ mod fee;
 pub use fee::{FeeEstimate, FeeUnit, TransactionSimulationInfo};
 
 pub mod trace;
-pub use trace::{BlockTraces, TransactionTrace};
+pub use trace::{BlockTraces, TracePrinter, TransactionTrace};
+
+mod storage_proof;
+pub use storage_proof::{ContractData, EdgePath, StorageProof, TrieNode};
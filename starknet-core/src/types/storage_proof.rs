@@ -0,0 +1,111 @@
+use super::{super::serde::unsigned_field_element::UfeHex, FieldElement};
+
+use serde::Deserialize;
+use serde_with::serde_as;
+
+/// Merkle-Patricia proofs of contract storage values against the state root of a block, as
+/// returned by `pathfinder_getProof`.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub struct StorageProof {
+    #[serde(default)]
+    #[serde_as(as = "Option<UfeHex>")]
+    pub state_commitment: Option<FieldElement>,
+    /// Root of the class trie. Absent before Starknet v0.11.0, when the state root was the root
+    /// of the contract trie.
+    #[serde(default)]
+    #[serde_as(as = "Option<UfeHex>")]
+    pub class_commitment: Option<FieldElement>,
+    /// Path from the root of the contract trie to the contract leaf.
+    pub contract_proof: Vec<TrieNode>,
+    /// State of the contract, absent if the contract is not deployed.
+    pub contract_data: Option<ContractData>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub struct ContractData {
+    #[serde_as(as = "UfeHex")]
+    pub class_hash: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub nonce: FieldElement,
+    /// Root of the contract storage trie.
+    #[serde_as(as = "UfeHex")]
+    pub root: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub contract_state_hash_version: FieldElement,
+    /// Paths from the root of the storage trie to each of the requested keys.
+    pub storage_proofs: Vec<Vec<TrieNode>>,
+}
+
+/// A node of a binary Merkle-Patricia trie.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub enum TrieNode {
+    Binary {
+        #[serde_as(as = "UfeHex")]
+        left: FieldElement,
+        #[serde_as(as = "UfeHex")]
+        right: FieldElement,
+    },
+    /// A node skipping `path.len` levels of the trie, where all leaves below share `path`.
+    Edge {
+        #[serde_as(as = "UfeHex")]
+        child: FieldElement,
+        path: EdgePath,
+    },
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
+pub struct EdgePath {
+    #[serde_as(as = "UfeHex")]
+    pub value: FieldElement,
+    pub len: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_storage_proof_deser() {
+        let proof: StorageProof = serde_json::from_str(
+            r#"{
+                "state_commitment": "0x1",
+                "class_commitment": "0x0",
+                "contract_proof": [
+                    { "binary": { "left": "0x2", "right": "0x3" } },
+                    { "edge": { "child": "0x4", "path": { "value": "0x5", "len": 249 } } }
+                ],
+                "contract_data": {
+                    "class_hash": "0x6",
+                    "nonce": "0x0",
+                    "root": "0x7",
+                    "contract_state_hash_version": "0x0",
+                    "storage_proofs": [[]]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(proof.contract_proof.len(), 2);
+        assert_eq!(
+            proof.contract_proof[1],
+            TrieNode::Edge {
+                child: FieldElement::from(4u64),
+                path: EdgePath {
+                    value: FieldElement::from(5u64),
+                    len: 249
+                }
+            }
+        );
+        assert_eq!(proof.contract_data.unwrap().root, FieldElement::from(7u64));
+    }
+}
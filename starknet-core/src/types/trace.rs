@@ -1,9 +1,30 @@
-use super::{super::serde::unsigned_field_element::UfeHex, EntryPointType, ExecutionResources};
+use super::{
+    super::{
+        serde::unsigned_field_element::UfeHex,
+        utils::{get_selector_from_name, parse_cairo_short_string},
+    },
+    EntryPointType, ExecutionResources,
+};
 
 use ethereum_types::Address;
 use serde::Deserialize;
 use serde_with::serde_as;
 use starknet_crypto::FieldElement;
+use std::{collections::HashMap, fmt};
+
+/// Entrypoint names resolved by [TracePrinter] out of the box.
+const WELL_KNOWN_ENTRYPOINTS: [&str; 10] = [
+    "__execute__",
+    "__validate__",
+    "__validate_declare__",
+    "__validate_deploy__",
+    "constructor",
+    "transfer",
+    "transferFrom",
+    "transfer_from",
+    "approve",
+    "mint",
+];
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
@@ -97,6 +118,154 @@ pub struct OrderedL2ToL1MessageResponse {
     pub payload: Vec<FieldElement>,
 }
 
+/// Renders a [TransactionTrace] as an indented call tree, with resolved entrypoint names, call
+/// results decoded as Cairo short strings where possible (e.g. panic reasons) and the execution
+/// resources of each call. Create one with [TransactionTrace::pretty].
+#[derive(Debug)]
+pub struct TracePrinter<'a> {
+    trace: &'a TransactionTrace,
+    selector_names: HashMap<FieldElement, String>,
+}
+
+impl TransactionTrace {
+    pub fn pretty(&self) -> TracePrinter<'_> {
+        TracePrinter::new(self)
+    }
+}
+
+impl<'a> TracePrinter<'a> {
+    pub fn new(trace: &'a TransactionTrace) -> Self {
+        Self {
+            trace,
+            selector_names: HashMap::new(),
+        }
+        .with_selector_names(WELL_KNOWN_ENTRYPOINTS)
+    }
+
+    /// Adds entrypoint names to resolve selectors against.
+    pub fn with_selector_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for name in names.into_iter() {
+            if let Ok(selector) = get_selector_from_name(name.as_ref()) {
+                self.selector_names
+                    .insert(selector, name.as_ref().to_owned());
+            }
+        }
+        self
+    }
+
+    fn write_invocation(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        invocation: &FunctionInvocation,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+
+        let entrypoint = match invocation.selector {
+            Some(selector) => match self.selector_names.get(&selector) {
+                Some(name) => name.to_owned(),
+                None => format!("{selector:#x}"),
+            },
+            None => String::from("<unknown>"),
+        };
+        writeln!(
+            f,
+            "{indent}{entrypoint} @ {:#x}",
+            invocation.contract_address
+        )?;
+
+        if !invocation.calldata.is_empty() {
+            writeln!(
+                f,
+                "{indent}  calldata: [{}]",
+                format_felts(&invocation.calldata)
+            )?;
+        }
+        if !invocation.result.is_empty() {
+            writeln!(
+                f,
+                "{indent}  result: [{}]",
+                format_felts(&invocation.result)
+            )?;
+        }
+
+        let resources = &invocation.execution_resources;
+        writeln!(
+            f,
+            "{indent}  resources: {} steps, {} memory holes{}",
+            resources.n_steps,
+            resources.n_memory_holes,
+            format_builtins(resources)
+        )?;
+
+        for internal_call in invocation.internal_calls.iter() {
+            self.write_invocation(f, internal_call, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for TracePrinter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(validate_invocation) = &self.trace.validate_invocation {
+            writeln!(f, "validation:")?;
+            self.write_invocation(f, validate_invocation, 1)?;
+        }
+
+        writeln!(f, "execution:")?;
+        self.write_invocation(f, &self.trace.function_invocation, 1)?;
+
+        if let Some(fee_transfer_invocation) = &self.trace.fee_transfer_invocation {
+            writeln!(f, "fee transfer:")?;
+            self.write_invocation(f, fee_transfer_invocation, 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats felts as hex, appending the decoded Cairo short string for printable values.
+fn format_felts(felts: &[FieldElement]) -> String {
+    felts
+        .iter()
+        .map(|felt| match parse_cairo_short_string(felt) {
+            Ok(decoded)
+                if !decoded.is_empty()
+                    && decoded.chars().all(|c| c.is_ascii_graphic() || c == ' ') =>
+            {
+                format!("{felt:#x} ('{decoded}')")
+            }
+            _ => format!("{felt:#x}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_builtins(resources: &ExecutionResources) -> String {
+    let counter = &resources.builtin_instance_counter;
+    let builtins = [
+        ("pedersen", counter.pedersen_builtin),
+        ("range_check", counter.range_check_builtin),
+        ("bitwise", counter.bitwise_builtin),
+        ("output", counter.output_builtin),
+        ("ecdsa", counter.ecdsa_builtin),
+        ("ec_op", counter.ec_op_builtin),
+    ];
+
+    builtins
+        .iter()
+        .filter_map(|(name, count)| match count {
+            Some(count) if *count > 0 => Some(format!(", {count} {name}")),
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +329,31 @@ mod tests {
         assert!(&old_tx.function_invocation.call_type.is_none());
         assert!(&old_tx.function_invocation.class_hash.is_none());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_trace_pretty_print() {
+        let trace = serde_json::from_str::<TransactionTrace>(include_str!(
+            "../../test-data/raw_gateway_responses/get_transaction_trace/4_with_validation.txt"
+        ))
+        .unwrap();
+
+        let rendered = trace.pretty().to_string();
+
+        assert!(rendered.starts_with("validation:\n  __validate__ @ "));
+        assert!(rendered.contains("execution:\n  __execute__ @ "));
+        assert!(rendered.contains("steps"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_format_felts_short_string() {
+        assert_eq!(
+            format_felts(&[
+                FieldElement::from_hex_be("0x56414c4944").unwrap(),
+                FieldElement::ZERO
+            ]),
+            "0x56414c4944 ('VALID'), 0x0"
+        );
+    }
 }
@@ -10,6 +10,8 @@ use serde::Deserialize;
 use serde_with::serde_as;
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub enum TransactionType {
@@ -32,7 +34,7 @@ pub struct TransactionStatusInfo {
     #[serde(alias = "tx_failure_reason")]
     pub transaction_failure_reason: Option<TransactionFailureReason>,
 }
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct TransactionFailureReason {
     pub code: String,
@@ -65,6 +67,8 @@ pub enum EntryPointType {
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct DeclareTransaction {
     #[serde_as(as = "UfeHex")]
@@ -85,6 +89,8 @@ pub struct DeclareTransaction {
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct DeployTransaction {
     #[serde_as(deserialize_as = "Vec<UfeHex>")]
@@ -103,6 +109,8 @@ pub struct DeployTransaction {
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct DeployAccountTransaction {
     #[serde_as(deserialize_as = "Vec<UfeHex>")]
@@ -127,6 +135,8 @@ pub struct DeployAccountTransaction {
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct InvokeFunctionTransaction {
     #[serde_as(as = "UfeHex")]
@@ -149,6 +159,8 @@ pub struct InvokeFunctionTransaction {
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct L1HandlerTransaction {
     #[serde_as(as = "UfeHex")]
@@ -169,6 +181,9 @@ pub struct L1HandlerTransaction {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "proptest")]
+    use proptest::prelude::*;
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_deser_full_invoke_transaction() {
@@ -297,4 +312,55 @@ mod tests {
         assert!(tx.block_hash.is_none());
         assert!(tx.transaction_failure_reason.is_none());
     }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_transaction() {
+        use arbitrary::Unstructured;
+
+        // Fuzzer inputs of any length yield a value
+        for len in [0, 1, 64, 1024] {
+            let data = (0..len).map(|byte| byte as u8).collect::<Vec<_>>();
+            Unstructured::new(&data)
+                .arbitrary::<TransactionType>()
+                .unwrap();
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #[test]
+        fn test_invoke_transaction_deser_roundtrip(tx: InvokeFunctionTransaction) {
+            let hex = |felt: &FieldElement| format!("{felt:#x}");
+            let mut raw = serde_json::json!({
+                "type": "INVOKE_FUNCTION",
+                "contract_address": hex(&tx.contract_address),
+                "calldata": tx.calldata.iter().map(hex).collect::<Vec<_>>(),
+                "signature": tx.signature.iter().map(hex).collect::<Vec<_>>(),
+                "transaction_hash": hex(&tx.transaction_hash),
+                "max_fee": hex(&tx.max_fee),
+                "version": hex(&tx.version),
+            });
+            if let Some(selector) = &tx.entry_point_selector {
+                raw["entry_point_selector"] = hex(selector).into();
+            }
+            if let Some(nonce) = &tx.nonce {
+                raw["nonce"] = hex(nonce).into();
+            }
+
+            match serde_json::from_value(raw).unwrap() {
+                TransactionType::InvokeFunction(parsed) => {
+                    prop_assert_eq!(parsed.contract_address, tx.contract_address);
+                    prop_assert_eq!(parsed.entry_point_selector, tx.entry_point_selector);
+                    prop_assert_eq!(parsed.calldata, tx.calldata);
+                    prop_assert_eq!(parsed.signature, tx.signature);
+                    prop_assert_eq!(parsed.transaction_hash, tx.transaction_hash);
+                    prop_assert_eq!(parsed.max_fee, tx.max_fee);
+                    prop_assert_eq!(parsed.nonce, tx.nonce);
+                    prop_assert_eq!(parsed.version, tx.version);
+                }
+                parsed => prop_assert!(false, "unexpected transaction: {parsed:?}"),
+            }
+        }
+    }
 }
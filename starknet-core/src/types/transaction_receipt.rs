@@ -4,12 +4,13 @@ use super::{
     FieldElement,
 };
 
-use ethereum_types::Address as L1Address;
+use ethereum_types::{Address as L1Address, H256};
 use serde::Deserialize;
 use serde_with::serde_as;
+use sha3::{Digest, Keccak256};
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct Receipt {
     #[serde(default)]
@@ -31,7 +32,7 @@ pub struct Receipt {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct ConfirmedReceipt {
     #[serde_as(as = "UfeHex")]
@@ -46,7 +47,7 @@ pub struct ConfirmedReceipt {
     pub actual_fee: FieldElement,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub enum TransactionStatus {
@@ -65,7 +66,7 @@ pub enum TransactionStatus {
     AcceptedOnL1,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct ExecutionResources {
     pub n_steps: u64,
@@ -73,7 +74,7 @@ pub struct ExecutionResources {
     pub builtin_instance_counter: BuiltinInstanceCounter,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct BuiltinInstanceCounter {
     pub pedersen_builtin: Option<u64>,
@@ -85,7 +86,7 @@ pub struct BuiltinInstanceCounter {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct L1ToL2Message {
     pub from_address: L1Address,
@@ -100,7 +101,7 @@ pub struct L1ToL2Message {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct L2ToL1Message {
     #[serde_as(as = "UfeHex")]
@@ -110,8 +111,47 @@ pub struct L2ToL1Message {
     pub payload: Vec<FieldElement>,
 }
 
+impl L1ToL2Message {
+    /// The hash the message is recorded under in the `l1ToL2Messages` mapping of the StarknetCore
+    /// contract. Messages sent before nonces were introduced are hashed without one.
+    pub fn hash(&self) -> H256 {
+        let mut hasher = Keccak256::new();
+        hasher.update(H256::from(self.from_address).as_bytes());
+        hasher.update(self.to_address.to_bytes_be());
+        if let Some(nonce) = self.nonce {
+            hasher.update(nonce.to_bytes_be());
+        }
+        hasher.update(self.selector.to_bytes_be());
+        update_payload(&mut hasher, &self.payload);
+        H256::from_slice(&hasher.finalize())
+    }
+}
+
+impl L2ToL1Message {
+    /// The hash the message is recorded under in the `l2ToL1Messages` mapping of the StarknetCore
+    /// contract, once the block sending it is accepted on L1.
+    pub fn hash(&self) -> H256 {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.from_address.to_bytes_be());
+        hasher.update(H256::from(self.to_address).as_bytes());
+        update_payload(&mut hasher, &self.payload);
+        H256::from_slice(&hasher.finalize())
+    }
+}
+
+/// Hashes a payload the way Solidity `abi.encodePacked` packs a `uint256[]` preceded by its
+/// length.
+fn update_payload(hasher: &mut Keccak256, payload: &[FieldElement]) {
+    hasher.update(FieldElement::from(payload.len() as u64).to_bytes_be());
+    for item in payload.iter() {
+        hasher.update(item.to_bytes_be());
+    }
+}
+
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct Event {
     #[serde_as(as = "UfeHex")]
@@ -128,6 +168,9 @@ mod tests {
 
     use super::*;
 
+    #[cfg(feature = "proptest")]
+    use proptest::prelude::*;
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_receipt_deser_accepted() {
@@ -184,6 +227,79 @@ mod tests {
         assert!(receipt.transaction_failure_reason.is_some());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_l1_to_l2_message_hash() {
+        let mut message = L1ToL2Message {
+            from_address: "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e"
+                .parse()
+                .unwrap(),
+            to_address: FieldElement::from_hex_be(
+                "0x73314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82",
+            )
+            .unwrap(),
+            selector: FieldElement::from_hex_be(
+                "0x2d757788a8d8d6f21d1cd40bce38a8222d70654214e96ff95d8086e684fbee5",
+            )
+            .unwrap(),
+            payload: vec![FieldElement::ONE, FieldElement::TWO],
+            nonce: Some(FieldElement::THREE),
+        };
+
+        let mut packed = vec![];
+        packed.extend_from_slice(&[0u8; 12]);
+        packed.extend_from_slice(message.from_address.as_bytes());
+        packed.extend_from_slice(&message.to_address.to_bytes_be());
+        packed.extend_from_slice(&FieldElement::THREE.to_bytes_be());
+        packed.extend_from_slice(&message.selector.to_bytes_be());
+        for word in [FieldElement::TWO, FieldElement::ONE, FieldElement::TWO] {
+            packed.extend_from_slice(&word.to_bytes_be());
+        }
+        assert_eq!(
+            message.hash(),
+            H256::from_slice(&Keccak256::digest(&packed))
+        );
+
+        // Legacy messages are hashed without the nonce word
+        message.nonce = None;
+        packed.drain(64..96);
+        assert_eq!(
+            message.hash(),
+            H256::from_slice(&Keccak256::digest(&packed))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_l2_to_l1_message_hash() {
+        let message = L2ToL1Message {
+            from_address: FieldElement::from_hex_be(
+                "0x73314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82",
+            )
+            .unwrap(),
+            to_address: "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e"
+                .parse()
+                .unwrap(),
+            payload: vec![FieldElement::ZERO, FieldElement::from(1000u64)],
+        };
+
+        let mut packed = vec![];
+        packed.extend_from_slice(&message.from_address.to_bytes_be());
+        packed.extend_from_slice(&[0u8; 12]);
+        packed.extend_from_slice(message.to_address.as_bytes());
+        for word in [
+            FieldElement::TWO,
+            FieldElement::ZERO,
+            FieldElement::from(1000u64),
+        ] {
+            packed.extend_from_slice(&word.to_bytes_be());
+        }
+        assert_eq!(
+            message.hash(),
+            H256::from_slice(&Keccak256::digest(&packed))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_transaction_status_deser_accepted_on_l2() {
@@ -240,4 +356,21 @@ mod tests {
         assert_eq!(tx.status, TransactionStatus::Rejected);
         assert!(tx.block_hash.is_none());
     }
+
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #[test]
+        fn test_event_deser_roundtrip(event: Event) {
+            let raw = serde_json::json!({
+                "from_address": format!("{:#x}", event.from_address),
+                "keys": event.keys.iter().map(|key| format!("{key:#x}")).collect::<Vec<_>>(),
+                "data": event.data.iter().map(|data| format!("{data:#x}")).collect::<Vec<_>>(),
+            });
+            let parsed: Event = serde_json::from_value(raw).unwrap();
+
+            prop_assert_eq!(parsed.from_address, event.from_address);
+            prop_assert_eq!(parsed.keys, event.keys);
+            prop_assert_eq!(parsed.data, event.data);
+        }
+    }
 }
@@ -1,13 +1,13 @@
 use super::{
     super::serde::{
-        byte_array::base64::serialize as base64_ser,
+        byte_array::base64::{deserialize as base64_de, serialize as base64_ser},
         num_hex::u64 as u64_hex,
         unsigned_field_element::{UfeHex, UfeHexOption},
     },
     AbiEntry, FieldElement, L1Address,
 };
 
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
 use std::sync::Arc;
 
@@ -33,7 +33,10 @@ pub enum AddTransactionResultCode {
     TransactionReceived,
 }
 
-#[derive(Debug, Serialize)]
+/// A signed transaction ready to be submitted to the network. Transactions signed offline or by
+/// another system can be deserialized into this type and broadcast as-is with
+/// [add_transaction](https://docs.rs/starknet-providers/latest/starknet_providers/trait.Provider.html#tymethod.add_transaction).
+#[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionRequest {
     Declare(DeclareTransaction),
@@ -77,7 +80,7 @@ pub struct CallL1Handler {
     pub payload: Vec<FieldElement>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareTransaction {
     pub contract_class: Arc<ContractDefinition>,
     /// The address of the account contract sending the declaration transaction.
@@ -90,7 +93,7 @@ pub struct DeclareTransaction {
     pub nonce: FieldElement,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InvokeFunctionTransaction {
     pub contract_address: FieldElement,
     pub calldata: Vec<FieldElement>,
@@ -99,7 +102,7 @@ pub struct InvokeFunctionTransaction {
     pub nonce: FieldElement,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeployAccountTransaction {
     pub class_hash: FieldElement,
     pub contract_address_salt: FieldElement,
@@ -112,9 +115,11 @@ pub struct DeployAccountTransaction {
     pub nonce: FieldElement,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 pub struct ContractDefinition {
-    #[serde(serialize_with = "base64_ser")]
+    #[serde(serialize_with = "base64_ser", deserialize_with = "base64_de")]
     pub program: Vec<u8>,
     pub entry_points_by_type: EntryPointsByType,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -122,6 +127,8 @@ pub struct ContractDefinition {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct EntryPointsByType {
@@ -132,6 +139,8 @@ pub struct EntryPointsByType {
 
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "proptest", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "no_unknown_fields", serde(deny_unknown_fields))]
 pub struct EntryPoint {
     #[serde(with = "u64_hex")]
@@ -242,6 +251,156 @@ impl Serialize for DeployAccountTransaction {
     }
 }
 
+// We need to manually implement this because `arbitrary_precision` doesn't work with `tag`:
+//   https://github.com/serde-rs/serde/issues/1183
+impl<'de> Deserialize<'de> for TransactionRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let temp_value = serde_json::Value::deserialize(deserializer)?;
+        match &temp_value["type"] {
+            serde_json::Value::String(type_str) => match &type_str[..] {
+                "DECLARE" => Ok(TransactionRequest::Declare(
+                    DeclareTransaction::deserialize(temp_value).map_err(|err| {
+                        DeError::custom(format!("invalid declare variant: {err}"))
+                    })?,
+                )),
+                "INVOKE_FUNCTION" => Ok(TransactionRequest::InvokeFunction(
+                    InvokeFunctionTransaction::deserialize(temp_value).map_err(|err| {
+                        DeError::custom(format!("invalid invoke_function variant: {err}"))
+                    })?,
+                )),
+                "DEPLOY_ACCOUNT" => Ok(TransactionRequest::DeployAccount(
+                    DeployAccountTransaction::deserialize(temp_value).map_err(|err| {
+                        DeError::custom(format!("invalid deploy_account variant: {err}"))
+                    })?,
+                )),
+                _ => Err(DeError::custom(format!(
+                    "unknown transaction type: {type_str}"
+                ))),
+            },
+            _ => Err(DeError::custom("invalid type field")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DeclareTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[serde_as]
+        #[derive(Deserialize)]
+        struct Versioned {
+            #[serde_as(as = "UfeHex")]
+            version: FieldElement,
+            contract_class: ContractDefinition,
+            #[serde_as(as = "UfeHex")]
+            sender_address: FieldElement,
+            #[serde_as(as = "UfeHex")]
+            max_fee: FieldElement,
+            signature: Vec<FieldElement>,
+            #[serde_as(as = "UfeHex")]
+            nonce: FieldElement,
+        }
+
+        let versioned = Versioned::deserialize(deserializer)?;
+        check_version(versioned.version)?;
+
+        Ok(Self {
+            contract_class: Arc::new(versioned.contract_class),
+            sender_address: versioned.sender_address,
+            max_fee: versioned.max_fee,
+            signature: versioned.signature,
+            nonce: versioned.nonce,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for InvokeFunctionTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[serde_as]
+        #[derive(Deserialize)]
+        struct Versioned {
+            #[serde_as(as = "UfeHex")]
+            version: FieldElement,
+            #[serde_as(as = "UfeHex")]
+            contract_address: FieldElement,
+            calldata: Vec<FieldElement>,
+            signature: Vec<FieldElement>,
+            #[serde_as(as = "UfeHex")]
+            max_fee: FieldElement,
+            #[serde_as(as = "UfeHex")]
+            nonce: FieldElement,
+        }
+
+        let versioned = Versioned::deserialize(deserializer)?;
+        check_version(versioned.version)?;
+
+        Ok(Self {
+            contract_address: versioned.contract_address,
+            calldata: versioned.calldata,
+            signature: versioned.signature,
+            max_fee: versioned.max_fee,
+            nonce: versioned.nonce,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for DeployAccountTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[serde_as]
+        #[derive(Deserialize)]
+        struct Versioned {
+            #[serde_as(as = "UfeHex")]
+            version: FieldElement,
+            #[serde_as(as = "UfeHex")]
+            class_hash: FieldElement,
+            #[serde_as(as = "UfeHex")]
+            contract_address_salt: FieldElement,
+            constructor_calldata: Vec<FieldElement>,
+            #[serde_as(as = "UfeHex")]
+            max_fee: FieldElement,
+            signature: Vec<FieldElement>,
+            #[serde_as(as = "UfeHex")]
+            nonce: FieldElement,
+        }
+
+        let versioned = Versioned::deserialize(deserializer)?;
+        check_version(versioned.version)?;
+
+        Ok(Self {
+            class_hash: versioned.class_hash,
+            contract_address_salt: versioned.contract_address_salt,
+            constructor_calldata: versioned.constructor_calldata,
+            max_fee: versioned.max_fee,
+            signature: versioned.signature,
+            nonce: versioned.nonce,
+        })
+    }
+}
+
+// Only version 1 transactions are supported for now, as that's the only version we serialize to
+fn check_version<E>(version: FieldElement) -> Result<(), E>
+where
+    E: DeError,
+{
+    if version == FieldElement::ONE {
+        Ok(())
+    } else {
+        Err(E::custom(format!(
+            "unsupported transaction version: {version}"
+        )))
+    }
+}
+
 fn l1_addr_as_dec<S>(value: &L1Address, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -254,3 +413,121 @@ where
 
     serializer.serialize_str(&addr_in_felt.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "proptest")]
+    use proptest::prelude::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_invoke_request_roundtrip() {
+        let tx = TransactionRequest::InvokeFunction(InvokeFunctionTransaction {
+            contract_address: FieldElement::from_hex_be("0x1234").unwrap(),
+            calldata: vec![FieldElement::ONE, FieldElement::TWO],
+            signature: vec![
+                FieldElement::THREE,
+                FieldElement::from_hex_be("0x4").unwrap(),
+            ],
+            max_fee: FieldElement::from_hex_be("0x3e8").unwrap(),
+            nonce: FieldElement::from_hex_be("0x9").unwrap(),
+        });
+
+        let serialized = serde_json::to_string(&tx).unwrap();
+        let deserialized: TransactionRequest = serde_json::from_str(&serialized).unwrap();
+
+        match deserialized {
+            TransactionRequest::InvokeFunction(deserialized) => {
+                assert_eq!(
+                    deserialized.contract_address,
+                    FieldElement::from_hex_be("0x1234").unwrap()
+                );
+                assert_eq!(
+                    deserialized.calldata,
+                    vec![FieldElement::ONE, FieldElement::TWO]
+                );
+                assert_eq!(deserialized.signature.len(), 2);
+                assert_eq!(
+                    deserialized.nonce,
+                    FieldElement::from_hex_be("0x9").unwrap()
+                );
+            }
+            _ => panic!("unexpected transaction type"),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_deploy_account_request_roundtrip() {
+        let tx = TransactionRequest::DeployAccount(DeployAccountTransaction {
+            class_hash: FieldElement::from_hex_be("0x1234").unwrap(),
+            contract_address_salt: FieldElement::from_hex_be("0x5678").unwrap(),
+            constructor_calldata: vec![FieldElement::ONE],
+            max_fee: FieldElement::from_hex_be("0x3e8").unwrap(),
+            signature: vec![FieldElement::TWO, FieldElement::THREE],
+            nonce: FieldElement::ZERO,
+        });
+
+        let serialized = serde_json::to_string(&tx).unwrap();
+        let deserialized: TransactionRequest = serde_json::from_str(&serialized).unwrap();
+
+        match deserialized {
+            TransactionRequest::DeployAccount(deserialized) => {
+                assert_eq!(
+                    deserialized.contract_address_salt,
+                    FieldElement::from_hex_be("0x5678").unwrap()
+                );
+                assert_eq!(deserialized.constructor_calldata, vec![FieldElement::ONE]);
+            }
+            _ => panic!("unexpected transaction type"),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_request_deser_unsupported_version() {
+        let raw = r#"{
+            "type": "INVOKE_FUNCTION",
+            "version": "0x0",
+            "contract_address": "0x1234",
+            "calldata": [],
+            "signature": [],
+            "max_fee": "0x0",
+            "nonce": "0x0"
+        }"#;
+
+        assert!(serde_json::from_str::<TransactionRequest>(raw).is_err());
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_contract_definition_serde_roundtrip(
+            // ABIs are kept short as generating the default 100 entries of nested lists is slow
+            class in (
+                any::<Vec<u8>>(),
+                any::<EntryPointsByType>(),
+                prop::option::of(prop::collection::vec(
+                    any::<AbiEntry>(),
+                    0..4,
+                )),
+            )
+                .prop_map(|(program, entry_points_by_type, abi)| ContractDefinition {
+                    program,
+                    entry_points_by_type,
+                    abi,
+                })
+        ) {
+            // Classes don't implement `PartialEq`, so they're compared through their serialization
+            let serialized = serde_json::to_value(&class).unwrap();
+            let parsed: ContractDefinition = serde_json::from_value(serialized.clone()).unwrap();
+
+            prop_assert_eq!(&parsed.program, &class.program);
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), serialized);
+        }
+    }
+}
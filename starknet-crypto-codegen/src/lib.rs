@@ -4,12 +4,17 @@
 use std::fmt::Write;
 
 use proc_macro::TokenStream;
+use sha2::{Digest, Sha256};
 use starknet_curve::{
-    curve_params::{PEDERSEN_P0, PEDERSEN_P1, PEDERSEN_P2, PEDERSEN_P3},
+    curve_params::{GENERATOR, PEDERSEN_P0, PEDERSEN_P1, PEDERSEN_P2, PEDERSEN_P3},
     AffinePoint,
 };
+use starknet_ff::FieldElement;
 use syn::{parse_macro_input, LitInt};
 
+/// Width of the Poseidon state.
+const POSEIDON_WIDTH: usize = 3;
+
 #[proc_macro]
 pub fn lookup_table(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitInt);
@@ -26,6 +31,67 @@ pub fn lookup_table(input: TokenStream) -> TokenStream {
     output.parse().unwrap()
 }
 
+/// Generates the multiples of the curve generator used for fixed-base multiplication, with windows
+/// of the given number of bits covering all 252 bits of a scalar.
+#[proc_macro]
+pub fn generator_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LitInt);
+    let bits: u32 = input.base10_parse().expect("invalid bits");
+
+    let mut output = String::new();
+    writeln!(output, "pub const GENERATOR_TABLE_BITS: usize = {bits};").unwrap();
+
+    push_points(&mut output, "GENERATOR", GENERATOR, 252, bits).expect("push_points failed");
+
+    output.parse().unwrap()
+}
+
+/// Generates the round constants of the Hades permutation used by Poseidon, for the given number
+/// of rounds. As in `cairo-lang`, the constant at index `i` is the SHA-256 hash of `Hades{i}`
+/// reduced modulo the field prime.
+#[proc_macro]
+pub fn poseidon_consts(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LitInt);
+    let rounds: usize = input.base10_parse().expect("invalid rounds");
+
+    let mut output = String::new();
+    push_poseidon_consts(&mut output, rounds).expect("push_poseidon_consts failed");
+
+    output.parse().unwrap()
+}
+
+fn push_poseidon_consts(buf: &mut String, rounds: usize) -> std::fmt::Result {
+    writeln!(
+        buf,
+        "pub const POSEIDON_ROUND_CONSTANTS: [[::starknet_ff::FieldElement; {POSEIDON_WIDTH}]; {rounds}] = ["
+    )?;
+    for round in 0..rounds {
+        writeln!(buf, "[")?;
+        for ind in 0..POSEIDON_WIDTH {
+            let digest = Sha256::digest(format!("Hades{}", round * POSEIDON_WIDTH + ind));
+
+            // Reduces the digest modulo the field prime
+            let constant = digest.iter().fold(FieldElement::ZERO, |acc, byte| {
+                acc * FieldElement::from(256u32) + FieldElement::from(*byte)
+            });
+            push_field_element(buf, &constant)?;
+        }
+        writeln!(buf, "],")?;
+    }
+    writeln!(buf, "];")?;
+    Ok(())
+}
+
+fn push_field_element(buf: &mut String, value: &FieldElement) -> std::fmt::Result {
+    let mont = value.into_mont();
+    writeln!(buf, "::starknet_ff::FieldElement::from_mont([")?;
+    for limb in mont {
+        writeln!(buf, "{limb},")?;
+    }
+    writeln!(buf, "]),")?;
+    Ok(())
+}
+
 fn push_points(
     buf: &mut String,
     name: &str,
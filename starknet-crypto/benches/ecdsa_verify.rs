@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use hex_literal::hex;
-use starknet_crypto::{verify, FieldElement};
+use starknet_crypto::{verify, verify_batch, FieldElement};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     let stark_key = hex!("0565ee8f4203a04fbd5de77c678bc3738538f35c0871e377cdc45fcfa79e6bd9");
@@ -18,6 +18,13 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             black_box(verify(&stark_key, &msg_hash, &r_bytes, &s_bytes).unwrap());
         });
     });
+
+    let signatures = vec![(stark_key, msg_hash, r_bytes, s_bytes); 16];
+    c.bench_function("ecdsa_verify_batch_16", |b| {
+        b.iter(|| {
+            black_box(verify_batch(&signatures).unwrap());
+        });
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
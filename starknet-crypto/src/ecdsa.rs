@@ -1,6 +1,6 @@
 use starknet_curve::{
     curve_params::{EC_ORDER, GENERATOR},
-    AffinePoint,
+    AffinePoint, ProjectivePoint,
 };
 
 use crate::{
@@ -16,6 +16,14 @@ const ELEMENT_UPPER_BOUND: FieldElement = FieldElement::from_mont([
     576459263475450960,
 ]);
 
+/// Half of the curve order, rounded down. Signatures with `s` above it are "high-s".
+const EC_ORDER_HALF: FieldElement = FieldElement::from_mont([
+    4469946702800505613,
+    9795004985292149565,
+    4,
+    472735395860118493,
+]);
+
 /// Stark ECDSA signature
 #[derive(Debug)]
 pub struct Signature {
@@ -25,6 +33,28 @@ pub struct Signature {
     pub s: FieldElement,
 }
 
+impl Signature {
+    /// Whether `s` is at most half the curve order, as required by [verify_strict].
+    pub fn is_normalized(&self) -> bool {
+        self.s <= EC_ORDER_HALF
+    }
+
+    /// Returns the equivalent signature with `s` at most half the curve order.
+    ///
+    /// Both `(r, s)` and `(r, EC_ORDER - s)` pass [verify] for the same message, so signatures
+    /// should be normalized before being compared or used as keys.
+    pub fn normalize(&self) -> Signature {
+        Signature {
+            r: self.r,
+            s: if self.is_normalized() {
+                self.s
+            } else {
+                EC_ORDER - self.s
+            },
+        }
+    }
+}
+
 impl fmt::Display for Signature {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -42,7 +72,7 @@ impl fmt::Display for Signature {
 ///
 /// * `private_key`: The private key
 pub fn get_public_key(private_key: &FieldElement) -> FieldElement {
-    (&GENERATOR * &private_key.to_bits_le()).x
+    multiply_generator(private_key).x
 }
 
 /// Computes ECDSA signature given a Stark private key and message hash.
@@ -64,7 +94,7 @@ pub fn sign(
         return Err(SignError::InvalidK);
     }
 
-    let r = (&GENERATOR * &k.to_bits_le()).x;
+    let r = multiply_generator(k).x;
     if r == FieldElement::ZERO || r >= ELEMENT_UPPER_BOUND {
         return Err(SignError::InvalidK);
     }
@@ -95,6 +125,107 @@ pub fn verify(
     r: &FieldElement,
     s: &FieldElement,
 ) -> Result<bool, VerifyError> {
+    let (zw, rw) = verification_scalars(message, r, s)?;
+
+    let full_public_key = AffinePoint::from_x(*public_key);
+
+    let zw_g = &GENERATOR * &zw.to_bits_le();
+    let rw_q = &full_public_key * &rw.to_bits_le();
+
+    Ok((zw_g + rw_q).x == *r || (zw_g - rw_q).x == *r)
+}
+
+/// Same as [verify], but also rejects signatures that are not [normalized](Signature::normalize)
+/// with [VerifyError::InvalidS], so that each message has a single valid signature per `k`.
+///
+/// `r` must be in `[1, 2^251)` and `s` in `[1, EC_ORDER / 2]`.
+///
+/// ### Arguments
+///
+/// * `public_key`: The public key
+/// * `message`: The message hash
+/// * `r`: The `r` value of the signature
+/// * `s`: The `s` value of the signature
+pub fn verify_strict(
+    public_key: &FieldElement,
+    message: &FieldElement,
+    r: &FieldElement,
+    s: &FieldElement,
+) -> Result<bool, VerifyError> {
+    if s > &EC_ORDER_HALF {
+        return Err(VerifyError::InvalidS);
+    }
+
+    verify(public_key, message, r, s)
+}
+
+/// Verifies many signatures at once, returning `true` only if all of them are valid. Each entry
+/// is a `(public_key, message, r, s)` tuple checked as with [verify], and an error is returned if
+/// any of them is malformed.
+///
+/// Points are kept in projective coordinates throughout and compared against `r` without
+/// converting them back, which avoids the field inversions [verify] performs at every step and
+/// makes this several times faster per signature.
+///
+/// Signatures are not combined into a single randomized equation: they only commit to the x
+/// coordinates of the public key and of the signing point, so the signs in each equation are
+/// unknown and can't be checked jointly.
+///
+/// ### Arguments
+///
+/// * `signatures`: The `(public_key, message, r, s)` tuples to verify
+pub fn verify_batch(
+    signatures: &[(FieldElement, FieldElement, FieldElement, FieldElement)],
+) -> Result<bool, VerifyError> {
+    let scalars = signatures
+        .iter()
+        .map(|(_, message, r, s)| verification_scalars(message, r, s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let generator = ProjectivePoint::from(&GENERATOR);
+    for ((public_key, _, r, _), (zw, rw)) in signatures.iter().zip(scalars) {
+        let zw_g = &generator * &zw.to_bits_le();
+        let rw_q = &ProjectivePoint::from(&AffinePoint::from_x(*public_key)) * &rw.to_bits_le();
+
+        let mut sum = zw_g;
+        sum += &rw_q;
+        let mut difference = zw_g;
+        difference += &ProjectivePoint { y: -rw_q.y, ..rw_q };
+
+        if !has_x(&sum, r) && !has_x(&difference, r) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Multiplies the generator by a secret scalar in constant time, using the precomputed generator
+/// table when enabled.
+fn multiply_generator(scalar: &FieldElement) -> AffinePoint {
+    #[cfg(any(feature = "precomputed-tables-4", feature = "precomputed-tables-8"))]
+    {
+        use crate::generator_table::{CURVE_CONSTS_GENERATOR, GENERATOR_TABLE_BITS};
+
+        // Field elements are always below 2^252
+        AffinePoint::fixed_base_multiply_constant_time(
+            &CURVE_CONSTS_GENERATOR,
+            GENERATOR_TABLE_BITS,
+            &scalar.to_bits_le()[..252],
+        )
+    }
+
+    #[cfg(not(any(feature = "precomputed-tables-4", feature = "precomputed-tables-8")))]
+    GENERATOR.multiply_constant_time(&scalar.to_bits_le())
+}
+
+/// Validates the signature and computes the scalars `z * w` and `r * w`, where `w` is the modular
+/// inverse of `s`.
+fn verification_scalars(
+    message: &FieldElement,
+    r: &FieldElement,
+    s: &FieldElement,
+) -> Result<(FieldElement, FieldElement), VerifyError> {
     if message >= &ELEMENT_UPPER_BOUND {
         return Err(VerifyError::InvalidMessageHash);
     }
@@ -105,20 +236,20 @@ pub fn verify(
         return Err(VerifyError::InvalidS);
     }
 
-    let full_public_key = AffinePoint::from_x(*public_key);
-
     let w = mod_inverse(s, &EC_ORDER);
     if w == FieldElement::ZERO || w >= ELEMENT_UPPER_BOUND {
         return Err(VerifyError::InvalidS);
     }
 
-    let zw = mul_mod_floor(message, &w, &EC_ORDER);
-    let zw_g = &GENERATOR * &zw.to_bits_le();
-
-    let rw = mul_mod_floor(r, &w, &EC_ORDER);
-    let rw_q = &full_public_key * &rw.to_bits_le();
+    Ok((
+        mul_mod_floor(message, &w, &EC_ORDER),
+        mul_mod_floor(r, &w, &EC_ORDER),
+    ))
+}
 
-    Ok((&zw_g + &rw_q).x == *r || (&zw_g - &rw_q).x == *r)
+/// Checks whether the affine x coordinate of `point` is `x`, without inverting `point.z`.
+fn has_x(point: &ProjectivePoint, x: &FieldElement) -> bool {
+    !point.infinity && point.x == *x * point.z
 }
 
 #[cfg(test)]
@@ -211,4 +342,102 @@ mod tests {
 
         assert!(verify(&public_key, &message, &signature.r, &signature.s).unwrap());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_verify_batch() {
+        let mut signatures = (1u64..=4)
+            .map(|seed| {
+                let private_key = FieldElement::from(seed * 0x1234567);
+                let message = FieldElement::from(seed);
+                let k = FieldElement::from(seed * 0x7654321);
+
+                let signature = sign(&private_key, &message, &k).unwrap();
+                (
+                    get_public_key(&private_key),
+                    message,
+                    signature.r,
+                    signature.s,
+                )
+            })
+            .collect::<Vec<_>>();
+        signatures.push((
+            field_element_from_be_hex(
+                "01ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+            ),
+            FieldElement::TWO,
+            field_element_from_be_hex(
+                "0411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20",
+            ),
+            field_element_from_be_hex(
+                "0405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b",
+            ),
+        ));
+
+        assert!(verify_batch(&signatures).unwrap());
+        assert!(verify_batch(&[]).unwrap());
+
+        // Signing a different message
+        signatures[2].1 = FieldElement::from(100u64);
+        assert!(!verify_batch(&signatures).unwrap());
+
+        signatures[3].3 = FieldElement::ZERO;
+        assert!(matches!(
+            verify_batch(&signatures),
+            Err(VerifyError::InvalidS)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_normalize() {
+        assert_eq!(EC_ORDER_HALF + EC_ORDER_HALF + FieldElement::ONE, EC_ORDER);
+
+        let private_key = FieldElement::from(0x1234u64);
+        let public_key = get_public_key(&private_key);
+        let message = FieldElement::TWO;
+
+        for k in 1u64..=4 {
+            let signature = sign(&private_key, &message, &FieldElement::from(k)).unwrap();
+            let malleated = Signature {
+                r: signature.r,
+                s: EC_ORDER - signature.s,
+            };
+
+            let (high, low) = if signature.is_normalized() {
+                (malleated, signature)
+            } else {
+                (signature, malleated)
+            };
+            assert!(!high.is_normalized());
+            assert_eq!(high.normalize().s, low.s);
+            assert_eq!(low.normalize().s, low.s);
+
+            assert!(verify(&public_key, &message, &high.r, &high.s).unwrap());
+            assert!(verify_strict(&public_key, &message, &low.r, &low.s).unwrap());
+            assert!(matches!(
+                verify_strict(&public_key, &message, &high.r, &high.s),
+                Err(VerifyError::InvalidS)
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "precomputed-tables-4", feature = "precomputed-tables-8"))]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_multiply_generator_table() {
+        let scalars = [
+            FieldElement::ONE,
+            FieldElement::from(0x1234u64),
+            EC_ORDER - FieldElement::ONE,
+            FieldElement::MAX,
+        ];
+
+        for scalar in scalars.iter() {
+            assert_eq!(
+                multiply_generator(scalar),
+                GENERATOR.multiply_constant_time(&scalar.to_bits_le())
+            );
+        }
+    }
 }
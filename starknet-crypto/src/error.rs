@@ -17,3 +17,16 @@ pub enum VerifyError {
     #[error("Invalid s")]
     InvalidS,
 }
+
+/// Errors when performing [`secp256`](crate::secp256) operations, named after the corresponding
+/// Cairo failures.
+#[derive(Debug, thiserror::Error)]
+pub enum Secp256Error {
+    /// A coordinate is not below the field prime.
+    #[error("Invalid argument")]
+    InvalidArgument,
+    #[error("Signature out of range")]
+    SignatureOutOfRange,
+    #[error("Invalid signature")]
+    InvalidSignature,
+}
@@ -0,0 +1,10 @@
+use starknet_crypto_codegen::generator_table;
+
+#[cfg(feature = "precomputed-tables-8")]
+generator_table!(8);
+
+#[cfg(all(
+    feature = "precomputed-tables-4",
+    not(feature = "precomputed-tables-8")
+))]
+generator_table!(4);
@@ -0,0 +1,190 @@
+use sha2::{Digest, Sha256};
+use starknet_curve::{
+    curve_params::{ALPHA, BETA},
+    AffinePoint, ProjectivePoint,
+};
+
+use crate::FieldElement;
+
+/// The non-square `Z` parameter of the simplified SWU map for the STARK curve, found with the
+/// `find_z_sswu` procedure of RFC 9380.
+const SSWU_Z: u64 = 19;
+
+/// Bytes of uniform randomness reduced into each field element, i.e. `ceil((252 + 128) / 8)` for a
+/// 128-bit security level.
+const FIELD_ELEMENT_BYTES: usize = 48;
+
+/// Hashes `message` to a point on the STARK curve, following the `hash_to_curve` construction of
+/// RFC 9380 with the suite `STARK_XMD:SHA-256_SSWU_RO_`:
+///
+/// - `expand_message_xmd` with SHA-256 expands the message into two field elements of 48 bytes
+///   each;
+/// - each element is mapped to the curve with the simplified SWU map, using `Z = 19`;
+/// - the two points are added. The curve has a cofactor of 1, so no cofactor clearing is needed.
+///
+/// Runs in variable time, so the message must not be secret.
+///
+/// ### Arguments
+///
+/// * `message`: The message to hash
+/// * `dst`: A domain separation tag unique to the application, as specified by RFC 9380, e.g.
+///   `MYAPP-V01-CS01-with-STARK_XMD:SHA-256_SSWU_RO_`
+pub fn hash_to_curve(message: &[u8], dst: &[u8]) -> AffinePoint {
+    let uniform_bytes = expand_message_xmd(message, dst, 2 * FIELD_ELEMENT_BYTES);
+    let (u0, u1) = uniform_bytes.split_at(FIELD_ELEMENT_BYTES);
+
+    let mut point = ProjectivePoint::from(&map_to_curve(bytes_to_field_element(u0)));
+    point += &map_to_curve(bytes_to_field_element(u1));
+
+    if point.infinity {
+        AffinePoint {
+            x: FieldElement::ZERO,
+            y: FieldElement::ZERO,
+            infinity: true,
+        }
+    } else {
+        AffinePoint::from(&point)
+    }
+}
+
+/// `expand_message_xmd` from RFC 9380 with SHA-256.
+fn expand_message_xmd(message: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let oversize_dst;
+    let dst = if dst.len() > 255 {
+        oversize_dst = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+
+    let blocks = len.div_ceil(32);
+    assert!(blocks <= 255 && len <= 65535, "requested too many bytes");
+
+    let b_0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(message)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+
+    let mut output = Vec::with_capacity(blocks * 32);
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+    output.extend_from_slice(&b_i);
+    for i in 2..=blocks {
+        let mut xored = b_0;
+        for (byte, previous) in xored.iter_mut().zip(b_i.iter()) {
+            *byte ^= previous;
+        }
+        b_i = Sha256::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update([dst.len() as u8])
+            .finalize();
+        output.extend_from_slice(&b_i);
+    }
+
+    output.truncate(len);
+    output
+}
+
+/// Interprets big-endian bytes as an integer reduced modulo the field prime.
+fn bytes_to_field_element(bytes: &[u8]) -> FieldElement {
+    let base = FieldElement::from(256u32);
+    bytes.iter().fold(FieldElement::ZERO, |acc, byte| {
+        acc * base + FieldElement::from(*byte)
+    })
+}
+
+/// The simplified SWU map from RFC 9380, section 6.6.2.
+fn map_to_curve(u: FieldElement) -> AffinePoint {
+    let z = FieldElement::from(SSWU_Z);
+    let curve = |x: FieldElement| x * x * x + ALPHA * x + BETA;
+
+    let z_u2 = z * u * u;
+    let x1 = match (z_u2 * z_u2 + z_u2).invert() {
+        Some(tv1) => -BETA * ALPHA.invert().unwrap() * (FieldElement::ONE + tv1),
+        None => BETA * (z * ALPHA).invert().unwrap(),
+    };
+
+    let (x, y) = match curve(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = z_u2 * x1;
+            let y = curve(x2)
+                .sqrt()
+                .expect("one of g(x1) and g(x2) is always square");
+            (x2, y)
+        }
+    };
+
+    let y = if sgn0(&u) == sgn0(&y) { y } else { -y };
+    AffinePoint {
+        x,
+        y,
+        infinity: false,
+    }
+}
+
+fn sgn0(value: &FieldElement) -> bool {
+    value.to_bytes_be()[31] & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-STARK_XMD:SHA-256_SSWU_RO_";
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_expand_message_xmd() {
+        // Test vector from RFC 9380, appendix K.1
+        assert_eq!(
+            expand_message_xmd(b"", b"QUUX-V01-CS02-with-expander-SHA256-128", 0x20),
+            hex::decode("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_hash_to_curve() {
+        for (message, x, y) in [
+            (
+                &b""[..],
+                "0x698714a34f5bd34ca57ede5db0342d58f9de7faf974a566365b131b98cc4e3e",
+                "0x259b1cdbcd6124d187f8f1683c3f0d18fcff06b3b9c2e6296240475457dfc8d",
+            ),
+            (
+                &b"abc"[..],
+                "0x9ec146ace89fcaaddea196e44c5d41cf08fa45d71ac65c19bb8e408111d27e",
+                "0x326353fd55849dec58860f02787cfe70fd614903721564992ed24824947fabf",
+            ),
+        ] {
+            let point = hash_to_curve(message, DST);
+
+            assert_eq!(point.x, FieldElement::from_hex_be(x).unwrap());
+            assert_eq!(point.y, FieldElement::from_hex_be(y).unwrap());
+            assert_eq!(
+                point.y * point.y,
+                point.x * point.x * point.x + ALPHA * point.x + BETA
+            );
+        }
+
+        assert_ne!(
+            hash_to_curve(b"abc", b"OTHER-DST"),
+            hash_to_curve(b"abc", DST)
+        );
+    }
+}
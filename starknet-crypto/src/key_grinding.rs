@@ -0,0 +1,58 @@
+use crypto_bigint::{ArrayEncoding, NonZero, U256};
+use sha2::{Digest, Sha256};
+
+use crate::FieldElement;
+
+const EC_ORDER: U256 =
+    U256::from_be_hex("0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f");
+
+/// The largest multiple of `EC_ORDER` that fits in 256 bits. Digests above it are rejected to
+/// avoid the modulo bias.
+const MAX_ALLOWED_VALUE: U256 =
+    U256::from_be_hex("f80000000000020efffffffffffffff738a13b4b920e9411ae6da5f40b0358b1");
+
+/// Derives a valid Stark private key from arbitrary key material (e.g. a BIP-32 private key or
+/// a hardware-generated secret), using the "key grinding" algorithm from StarkWare's
+/// `starkware-crypto` library, which is also used by Argent X and Braavos.
+///
+/// ### Arguments
+///
+/// * `key_seed`: the key material to derive the private key from
+pub fn grind_key(key_seed: &[u8]) -> FieldElement {
+    let order = NonZero::new(EC_ORDER).unwrap();
+
+    for index in 0..=u8::MAX {
+        let mut hasher = Sha256::new();
+        hasher.update(key_seed);
+        hasher.update([index]);
+        let key = U256::from_be_slice(&hasher.finalize());
+
+        if key < MAX_ALLOWED_VALUE {
+            let key = key % order;
+            return FieldElement::from_bytes_be(&key.to_be_byte_array().into()).unwrap();
+        }
+    }
+
+    // Each round is rejected with a probability lower than 1/32
+    unreachable!("key grinding failed after 256 rounds")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_grind_key() {
+        // Test vector from `starkware-crypto`
+        let key_seed =
+            hex::decode("86f3e7293141f20a8baff320e8ee4accb9d4a4bf2b4d295e8cee784db46e0519")
+                .unwrap();
+        let expected_key = FieldElement::from_hex_be(
+            "05c8c8683596c732541a59e03007b2d30dbbbb873556fe65b5fb63c16688f941",
+        )
+        .unwrap();
+
+        assert_eq!(grind_key(&key_seed), expected_key);
+    }
+}
@@ -3,19 +3,37 @@
 mod ecdsa;
 mod error;
 mod fe_utils;
+#[cfg(any(feature = "precomputed-tables-4", feature = "precomputed-tables-8"))]
+mod generator_table;
+mod hash_to_curve;
+mod key_grinding;
 mod pedersen_hash;
 mod pedersen_points;
+mod poseidon_consts;
+mod poseidon_hash;
 mod rfc6979;
+pub mod secp256;
 
 #[cfg(test)]
 mod test_utils;
 
 pub use starknet_ff::FieldElement;
 
-pub use pedersen_hash::pedersen_hash;
+pub use pedersen_hash::{pedersen_hash, pedersen_hash_batch, pedersen_hash_many};
 
-pub use ecdsa::{get_public_key, sign, verify, Signature};
+#[cfg(feature = "parallel")]
+pub use pedersen_hash::pedersen_hash_batch_parallel;
+
+pub use poseidon_hash::{
+    poseidon_hash, poseidon_hash_many, poseidon_hash_single, poseidon_permute,
+};
+
+pub use hash_to_curve::hash_to_curve;
+
+pub use ecdsa::{get_public_key, sign, verify, verify_batch, verify_strict, Signature};
 
 pub use crate::rfc6979::generate_k as rfc6979_generate_k;
 
-pub use error::{SignError, VerifyError};
+pub use key_grinding::grind_key;
+
+pub use error::{Secp256Error, SignError, VerifyError};
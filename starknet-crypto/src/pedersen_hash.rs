@@ -43,6 +43,50 @@ pub fn pedersen_hash(x: &FieldElement, y: &FieldElement) -> FieldElement {
     result.x
 }
 
+/// Computes the Pedersen hash of an array of elements, chained from zero and finalized with the
+/// array length, as done by `compute_hash_on_elements` in `cairo-lang`.
+///
+/// ### Arguments
+///
+/// * `msgs`: The elements
+pub fn pedersen_hash_many(msgs: &[FieldElement]) -> FieldElement {
+    let current_hash = msgs
+        .iter()
+        .fold(FieldElement::ZERO, |acc, item| pedersen_hash(&acc, item));
+
+    pedersen_hash(&current_hash, &FieldElement::from(msgs.len()))
+}
+
+/// Computes the Pedersen hash of each pair of elements, e.g. the nodes of a level of a Merkle
+/// tree.
+///
+/// ### Arguments
+///
+/// * `pairs`: The pairs of elements to hash
+pub fn pedersen_hash_batch(pairs: &[(FieldElement, FieldElement)]) -> Vec<FieldElement> {
+    pairs.iter().map(|(x, y)| pedersen_hash(x, y)).collect()
+}
+
+/// Computes the same hashes as [pedersen_hash_batch], spreading the pairs across the threads of
+/// the global [rayon] thread pool.
+///
+/// ### Arguments
+///
+/// * `pairs`: The pairs of elements to hash
+#[cfg(feature = "parallel")]
+pub fn pedersen_hash_batch_parallel(pairs: &[(FieldElement, FieldElement)]) -> Vec<FieldElement> {
+    use rayon::prelude::*;
+
+    // Below this many pairs, handing them to another thread costs more than it saves
+    const MIN_PAIRS_PER_TASK: usize = 64;
+
+    pairs
+        .par_iter()
+        .with_min_len(MIN_PAIRS_PER_TASK)
+        .map(|(x, y)| pedersen_hash(x, y))
+        .collect()
+}
+
 #[inline]
 fn bools_to_usize_le(bools: &[bool]) -> usize {
     let mut result: usize = 0;
@@ -77,4 +121,41 @@ mod tests {
 
         assert_eq!(pedersen_hash(&in1, &in2), expected_hash);
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_pedersen_hash_many() {
+        let elements = [FieldElement::ONE, FieldElement::TWO, FieldElement::THREE];
+
+        let chained = pedersen_hash(
+            &pedersen_hash(
+                &pedersen_hash(
+                    &pedersen_hash(&FieldElement::ZERO, &FieldElement::ONE),
+                    &FieldElement::TWO,
+                ),
+                &FieldElement::THREE,
+            ),
+            &FieldElement::THREE,
+        );
+        assert_eq!(pedersen_hash_many(&elements), chained);
+        assert_eq!(
+            pedersen_hash_many(&[]),
+            pedersen_hash(&FieldElement::ZERO, &FieldElement::ZERO)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_pedersen_hash_batch() {
+        let pairs = (0..300u64)
+            .map(|ind| (FieldElement::from(ind), FieldElement::from(ind * 7 + 1)))
+            .collect::<Vec<_>>();
+
+        let hashes = pedersen_hash_batch(&pairs);
+        assert_eq!(hashes.len(), pairs.len());
+        assert_eq!(hashes[299], pedersen_hash(&pairs[299].0, &pairs[299].1));
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(pedersen_hash_batch_parallel(&pairs), hashes);
+    }
 }
@@ -1,3 +1,7 @@
 use starknet_crypto_codegen::lookup_table;
 
+#[cfg(not(feature = "precomputed-tables-8"))]
 lookup_table!(4);
+
+#[cfg(feature = "precomputed-tables-8")]
+lookup_table!(8);
@@ -0,0 +1,3 @@
+use starknet_crypto_codegen::poseidon_consts;
+
+poseidon_consts!(91);
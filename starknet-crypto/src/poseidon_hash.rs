@@ -0,0 +1,183 @@
+use starknet_ff::FieldElement;
+
+use crate::poseidon_consts::POSEIDON_ROUND_CONSTANTS;
+
+const FULL_ROUNDS: usize = 8;
+
+const PARTIAL_ROUNDS: usize = 83;
+
+/// Computes the Starknet Poseidon hash of x and y.
+///
+/// ### Arguments
+///
+/// * `x`: The first element
+/// * `y`: The second element
+pub fn poseidon_hash(x: FieldElement, y: FieldElement) -> FieldElement {
+    let mut state = [x, y, FieldElement::TWO];
+    poseidon_permute(&mut state);
+
+    state[0]
+}
+
+/// Computes the Starknet Poseidon hash of a single element.
+///
+/// ### Arguments
+///
+/// * `x`: The element
+pub fn poseidon_hash_single(x: FieldElement) -> FieldElement {
+    let mut state = [x, FieldElement::ZERO, FieldElement::ONE];
+    poseidon_permute(&mut state);
+
+    state[0]
+}
+
+/// Computes the Starknet Poseidon hash of an arbitrary number of elements, absorbed two at a
+/// time after padding them with `1` and, if needed, `0` to an even length.
+///
+/// ### Arguments
+///
+/// * `msgs`: The elements
+pub fn poseidon_hash_many(msgs: &[FieldElement]) -> FieldElement {
+    let mut state = [FieldElement::ZERO; 3];
+
+    let mut chunks = msgs.chunks_exact(2);
+    for chunk in chunks.by_ref() {
+        state[0] = state[0] + chunk[0];
+        state[1] = state[1] + chunk[1];
+        poseidon_permute(&mut state);
+    }
+    match chunks.remainder() {
+        [last] => {
+            state[0] = state[0] + *last;
+            state[1] = state[1] + FieldElement::ONE;
+        }
+        _ => {
+            state[0] = state[0] + FieldElement::ONE;
+        }
+    }
+    poseidon_permute(&mut state);
+
+    state[0]
+}
+
+/// Applies the Hades permutation used by Poseidon to `state`.
+///
+/// ### Arguments
+///
+/// * `state`: The state to permute
+pub fn poseidon_permute(state: &mut [FieldElement; 3]) {
+    let (first_full_rounds, rest) = POSEIDON_ROUND_CONSTANTS.split_at(FULL_ROUNDS / 2);
+    let (partial_rounds, last_full_rounds) = rest.split_at(PARTIAL_ROUNDS);
+
+    for constants in first_full_rounds {
+        full_round(state, constants);
+    }
+    for constants in partial_rounds {
+        partial_round(state, constants);
+    }
+    for constants in last_full_rounds {
+        full_round(state, constants);
+    }
+}
+
+#[inline]
+fn full_round(state: &mut [FieldElement; 3], constants: &[FieldElement; 3]) {
+    for (value, constant) in state.iter_mut().zip(constants.iter()) {
+        *value = cube(*value + *constant);
+    }
+    mix(state);
+}
+
+/// Rounds in which only the last element goes through the S-box.
+#[inline]
+fn partial_round(state: &mut [FieldElement; 3], constants: &[FieldElement; 3]) {
+    state[0] = state[0] + constants[0];
+    state[1] = state[1] + constants[1];
+    state[2] = cube(state[2] + constants[2]);
+    mix(state);
+}
+
+#[inline]
+fn cube(value: FieldElement) -> FieldElement {
+    value * value * value
+}
+
+/// Multiplies the state by the MDS matrix `[[3, 1, 1], [1, -1, 1], [1, 1, -2]]`.
+#[inline]
+fn mix(state: &mut [FieldElement; 3]) {
+    let sum = state[0] + state[1] + state[2];
+    let [a, b, c] = *state;
+    *state = [sum + a + a, sum - b - b, sum - c - c - c];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_elements() -> [FieldElement; 3] {
+        [
+            FieldElement::from_hex_be(
+                "0xb662f9017fa7956fd70e26129b1833e10ad000fd37b4d9f4e0ce6884b7bbe",
+            )
+            .unwrap(),
+            FieldElement::from_hex_be(
+                "0x1fe356bf76102cdae1bfbdc173602ead228b12904c00dad9cf16e035468bea",
+            )
+            .unwrap(),
+            FieldElement::from_hex_be(
+                "0x75540825a6ecc5dc7d7c2f5f868164182742227f1367d66c43ee51ec7937a81",
+            )
+            .unwrap(),
+        ]
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_poseidon_hash() {
+        let [x, y, expected_hash] = test_elements();
+
+        assert_eq!(poseidon_hash(x, y), expected_hash);
+        assert_eq!(
+            poseidon_hash(FieldElement::ONE, FieldElement::TWO),
+            FieldElement::from_hex_be(
+                "0x5d44a3decb2b2e0cc71071f7b802f45dd792d064f0fc7316c46514f70f9891a"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_poseidon_hash_single() {
+        let [x, _, _] = test_elements();
+
+        assert_eq!(
+            poseidon_hash_single(x),
+            FieldElement::from_hex_be(
+                "0x4a907fe5242331ab653b3c04c51d90df9a1172805c5ca555b32ce588fd58fb0"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_poseidon_hash_many() {
+        let elements = test_elements();
+
+        for (len, expected_hash) in [
+            "0x2272be0f580fd156823304800919530eaa97430e972d7213ee13f4fbf7a5dbc",
+            "0x46583159a2327fea16a2b798d513b8ecfbf0fbf0f45d1948a6c74fb6b0fb49b",
+            "0x2410cb746ec9c95631fc76f1afa650eed9fa2efc20632a42cdbe7d701ed0098",
+            "0x55b4b35c1b6e6a5c523c3b8910be24aad6b3a01ed6c42d90aa2e722803d7368",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            assert_eq!(
+                poseidon_hash_many(&elements[..len]),
+                FieldElement::from_hex_be(expected_hash).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! Arithmetic and ECDSA verification on secp256k1 and secp256r1, following the semantics of the
+//! corresponding Cairo 1 system calls and `starknet::secp256_trait` functions, so that signatures
+//! contracts will verify can be checked off-chain first.
+//!
+//! Integers are 32-byte big-endian, like the `u256` values passed to Cairo. secp256k1 arithmetic
+//! is done by `k256`. secp256r1 arithmetic is not constant time: only use it with public values.
+
+use k256::{
+    elliptic_curve::{
+        ops::Reduce,
+        point::DecompressPoint,
+        sec1::{Coordinates, FromEncodedPoint, ToEncodedPoint},
+        subtle::Choice,
+    },
+    AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar, U256,
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use sha3::{Digest, Keccak256};
+use std::marker::PhantomData;
+
+use crate::Secp256Error;
+
+/// Affine coordinates of a point other than the point at infinity.
+type Affine = ([u8; 32], [u8; 32]);
+
+/// Parameters of a short Weierstrass curve of prime order.
+pub trait Secp256Curve: arithmetic::Arithmetic {
+    /// The field prime.
+    const P: &'static str;
+    /// The curve order.
+    const N: &'static str;
+    const GX: &'static str;
+    const GY: &'static str;
+}
+
+/// The curve used by Bitcoin and Ethereum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1;
+
+/// The NIST P-256 curve, used by passkeys and hardware signers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256r1;
+
+/// A point on the curve `C`, which is the point at infinity when created from `(0, 0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Secp256Point<C> {
+    /// `None` for the point at infinity.
+    coordinates: Option<Affine>,
+    _curve: PhantomData<C>,
+}
+
+mod arithmetic {
+    use super::Affine;
+
+    /// Group operations of a curve, where `None` is the point at infinity. Points passed in are
+    /// always on the curve.
+    pub trait Arithmetic {
+        fn is_on_curve(x: &[u8; 32], y: &[u8; 32]) -> bool;
+
+        fn from_x(x: &[u8; 32], y_parity: bool) -> Option<Affine>;
+
+        fn add(a: &Option<Affine>, b: &Option<Affine>) -> Option<Affine>;
+
+        /// Multiplies by any 256-bit integer.
+        fn mul(scalar: &[u8; 32], point: &Option<Affine>) -> Option<Affine>;
+    }
+}
+
+/// secp256r1 parameters for [BigUint] arithmetic on `y^2 = x^3 + a * x + b`.
+struct R1Params {
+    p: BigUint,
+    a: BigUint,
+    b: BigUint,
+}
+
+const R1_A: &str = "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc";
+const R1_B: &str = "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b";
+
+impl Secp256Curve for Secp256k1 {
+    const P: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+    const N: &'static str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+    const GX: &'static str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const GY: &'static str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+}
+
+impl Secp256Curve for Secp256r1 {
+    const P: &'static str = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+    const N: &'static str = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+    const GX: &'static str = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+    const GY: &'static str = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5";
+}
+
+impl arithmetic::Arithmetic for Secp256k1 {
+    fn is_on_curve(x: &[u8; 32], y: &[u8; 32]) -> bool {
+        k1_affine(x, y).is_some()
+    }
+
+    fn from_x(x: &[u8; 32], y_parity: bool) -> Option<Affine> {
+        Option::<AffinePoint>::from(AffinePoint::decompress(
+            &FieldBytes::from(*x),
+            Choice::from(y_parity as u8),
+        ))
+        .and_then(|point| k1_coordinates(point.into()))
+    }
+
+    fn add(a: &Option<Affine>, b: &Option<Affine>) -> Option<Affine> {
+        k1_coordinates(k1_projective(a) + k1_projective(b))
+    }
+
+    fn mul(scalar: &[u8; 32], point: &Option<Affine>) -> Option<Affine> {
+        let scalar = <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(*scalar));
+        k1_coordinates(k1_projective(point) * scalar)
+    }
+}
+
+impl arithmetic::Arithmetic for Secp256r1 {
+    fn is_on_curve(x: &[u8; 32], y: &[u8; 32]) -> bool {
+        let params = R1Params::new();
+        let y = BigUint::from_bytes_be(y);
+        &y * &y % &params.p == params.curve_rhs(&BigUint::from_bytes_be(x))
+    }
+
+    fn from_x(x: &[u8; 32], y_parity: bool) -> Option<Affine> {
+        let params = R1Params::new();
+        let x = BigUint::from_bytes_be(x);
+
+        let y = params.sqrt(&params.curve_rhs(&x))?;
+        let y = if y.bit(0) == y_parity {
+            y
+        } else {
+            (&params.p - &y) % &params.p
+        };
+        Some((to_bytes(&x), to_bytes(&y)))
+    }
+
+    fn add(a: &Option<Affine>, b: &Option<Affine>) -> Option<Affine> {
+        let params = R1Params::new();
+        params
+            .add(r1_coordinates(a), r1_coordinates(b))
+            .map(|(x, y)| (to_bytes(&x), to_bytes(&y)))
+    }
+
+    fn mul(scalar: &[u8; 32], point: &Option<Affine>) -> Option<Affine> {
+        let params = R1Params::new();
+        params
+            .mul(&BigUint::from_bytes_be(scalar), &r1_coordinates(point))
+            .map(|(x, y)| (to_bytes(&x), to_bytes(&y)))
+    }
+}
+
+impl<C> Secp256Point<C>
+where
+    C: Secp256Curve,
+{
+    /// Creates a point from its coordinates, returning `None` if it's not on the curve, like the
+    /// `secp256_new` system calls. `(0, 0)` is the point at infinity.
+    pub fn new(x: &[u8; 32], y: &[u8; 32]) -> Result<Option<Self>, Secp256Error> {
+        let p = from_hex(C::P);
+        if BigUint::from_bytes_be(x) >= p || BigUint::from_bytes_be(y) >= p {
+            return Err(Secp256Error::InvalidArgument);
+        }
+
+        if x == &[0; 32] && y == &[0; 32] {
+            return Ok(Some(Self::infinity()));
+        }
+        if !C::is_on_curve(x, y) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::from_coordinates(Some((*x, *y)))))
+    }
+
+    /// Finds the point with `x` and the given parity of `y`, or `None` if there's none, like the
+    /// `secp256_get_point_from_x` system calls.
+    pub fn from_x(x: &[u8; 32], y_parity: bool) -> Result<Option<Self>, Secp256Error> {
+        if BigUint::from_bytes_be(x) >= from_hex(C::P) {
+            return Err(Secp256Error::InvalidArgument);
+        }
+
+        Ok(C::from_x(x, y_parity).map(|coordinates| Self::from_coordinates(Some(coordinates))))
+    }
+
+    pub fn generator() -> Self {
+        Self::from_coordinates(Some((
+            to_bytes(&from_hex(C::GX)),
+            to_bytes(&from_hex(C::GY)),
+        )))
+    }
+
+    /// Like the `secp256_add` system calls.
+    pub fn add(&self, other: &Self) -> Self {
+        Self::from_coordinates(C::add(&self.coordinates, &other.coordinates))
+    }
+
+    /// Multiplies the point by `scalar`, which can be any 256-bit integer, like the
+    /// `secp256_mul` system calls.
+    pub fn mul(&self, scalar: &[u8; 32]) -> Self {
+        Self::from_coordinates(C::mul(scalar, &self.coordinates))
+    }
+
+    /// The coordinates of the point, which are `(0, 0)` for the point at infinity, like the
+    /// `secp256_get_xy` system calls.
+    pub fn xy(&self) -> ([u8; 32], [u8; 32]) {
+        self.coordinates.unwrap_or(([0; 32], [0; 32]))
+    }
+
+    fn infinity() -> Self {
+        Self::from_coordinates(None)
+    }
+
+    fn from_coordinates(coordinates: Option<Affine>) -> Self {
+        Self {
+            coordinates,
+            _curve: PhantomData,
+        }
+    }
+}
+
+/// Verifies an ECDSA signature like `is_valid_signature` in Cairo: `r` and `s` must be in
+/// `[1, N)`, and the x coordinate of `(z / s) * G + (r / s) * Q` must equal `r` exactly.
+///
+/// Unlike most ECDSA implementations, the x coordinate is not reduced modulo `N`, so the rare
+/// signatures whose nonce point has an x coordinate of at least `N` are rejected. High-s
+/// signatures are accepted.
+///
+/// ### Arguments
+///
+/// * `msg_hash`: The message hash
+/// * `r`: The `r` value of the signature
+/// * `s`: The `s` value of the signature
+/// * `public_key`: The public key
+pub fn secp256_is_valid_signature<C>(
+    msg_hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    public_key: &Secp256Point<C>,
+) -> bool
+where
+    C: Secp256Curve,
+{
+    let n = from_hex(C::N);
+    let r_value = BigUint::from_bytes_be(r);
+    let s = BigUint::from_bytes_be(s);
+    if !is_signature_entry_valid(&n, &r_value) || !is_signature_entry_valid(&n, &s) {
+        return false;
+    }
+
+    let s_inv = invert_scalar(&n, &s);
+    let u1 = BigUint::from_bytes_be(msg_hash) * &s_inv % &n;
+    let u2 = &r_value * &s_inv % &n;
+
+    let sum = Secp256Point::<C>::generator()
+        .mul(&to_bytes(&u1))
+        .add(&public_key.mul(&to_bytes(&u2)));
+    matches!(sum.coordinates, Some((x, _)) if &x == r)
+}
+
+/// Recovers the public key of an ECDSA signature like `recover_public_key` in Cairo, returning
+/// `None` if there's no point with x coordinate `r`.
+///
+/// ### Arguments
+///
+/// * `msg_hash`: The message hash
+/// * `r`: The `r` value of the signature
+/// * `s`: The `s` value of the signature
+/// * `y_parity`: The parity of the y coordinate of the nonce point
+pub fn secp256_recover_public_key<C>(
+    msg_hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    y_parity: bool,
+) -> Result<Option<Secp256Point<C>>, Secp256Error>
+where
+    C: Secp256Curve,
+{
+    let r_point = match Secp256Point::<C>::from_x(r, y_parity)? {
+        Some(r_point) => r_point,
+        None => return Ok(None),
+    };
+
+    let n = from_hex(C::N);
+    let r = BigUint::from_bytes_be(r);
+    if !is_signature_entry_valid(&n, &r) {
+        return Err(Secp256Error::SignatureOutOfRange);
+    }
+    let r_inv = invert_scalar(&n, &r);
+    let u1 = BigUint::from_bytes_be(msg_hash) * &r_inv % &n;
+    let minus_u1 = (&n - u1) % &n;
+    let u2 = BigUint::from_bytes_be(s) * &r_inv % &n;
+
+    let public_key = Secp256Point::<C>::generator()
+        .mul(&to_bytes(&minus_u1))
+        .add(&r_point.mul(&to_bytes(&u2)));
+    Ok(Some(public_key))
+}
+
+/// Verifies a secp256k1 signature against an Ethereum address like `verify_eth_signature` in
+/// Cairo, which panics where this returns an error.
+///
+/// ### Arguments
+///
+/// * `msg_hash`: The message hash
+/// * `r`: The `r` value of the signature
+/// * `s`: The `s` value of the signature
+/// * `y_parity`: The parity of the y coordinate of the nonce point
+/// * `eth_address`: The Ethereum address of the signer
+pub fn verify_eth_signature(
+    msg_hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    y_parity: bool,
+    eth_address: &[u8; 20],
+) -> Result<(), Secp256Error> {
+    let n = from_hex(Secp256k1::N);
+    if !is_signature_entry_valid(&n, &BigUint::from_bytes_be(r))
+        || !is_signature_entry_valid(&n, &BigUint::from_bytes_be(s))
+    {
+        return Err(Secp256Error::SignatureOutOfRange);
+    }
+
+    let public_key = secp256_recover_public_key::<Secp256k1>(msg_hash, r, s, y_parity)?
+        .ok_or(Secp256Error::InvalidSignature)?;
+    if &public_key_to_eth_address(&public_key) == eth_address {
+        Ok(())
+    } else {
+        Err(Secp256Error::InvalidSignature)
+    }
+}
+
+/// The Ethereum address of a secp256k1 public key, i.e. the last 20 bytes of the Keccak-256 hash
+/// of its coordinates.
+pub fn public_key_to_eth_address(public_key: &Secp256Point<Secp256k1>) -> [u8; 20] {
+    let (x, y) = public_key.xy();
+    let hash = Keccak256::new().chain_update(x).chain_update(y).finalize();
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+fn is_signature_entry_valid(n: &BigUint, value: &BigUint) -> bool {
+    !value.is_zero() && value < n
+}
+
+fn invert_scalar(n: &BigUint, value: &BigUint) -> BigUint {
+    value.modpow(&(n - 2u32), n)
+}
+
+fn k1_affine(x: &[u8; 32], y: &[u8; 32]) -> Option<AffinePoint> {
+    let encoded =
+        EncodedPoint::from_affine_coordinates(&FieldBytes::from(*x), &FieldBytes::from(*y), false);
+    AffinePoint::from_encoded_point(&encoded).into()
+}
+
+fn k1_projective(point: &Option<Affine>) -> ProjectivePoint {
+    match point {
+        Some((x, y)) => k1_affine(x, y).expect("points are on the curve").into(),
+        None => ProjectivePoint::IDENTITY,
+    }
+}
+
+fn k1_coordinates(point: ProjectivePoint) -> Option<Affine> {
+    match point.to_affine().to_encoded_point(false).coordinates() {
+        Coordinates::Uncompressed { x, y } => Some(((*x).into(), (*y).into())),
+        _ => None,
+    }
+}
+
+fn r1_coordinates(point: &Option<Affine>) -> Option<(BigUint, BigUint)> {
+    point
+        .as_ref()
+        .map(|(x, y)| (BigUint::from_bytes_be(x), BigUint::from_bytes_be(y)))
+}
+
+impl R1Params {
+    fn new() -> Self {
+        Self {
+            p: from_hex(Secp256r1::P),
+            a: from_hex(R1_A),
+            b: from_hex(R1_B),
+        }
+    }
+
+    /// `x^3 + a * x + b`
+    fn curve_rhs(&self, x: &BigUint) -> BigUint {
+        (x * x * x + &self.a * x + &self.b) % &self.p
+    }
+
+    /// Square root modulo `p`, which is `3 (mod 4)`.
+    fn sqrt(&self, value: &BigUint) -> Option<BigUint> {
+        let root = value.modpow(&((&self.p + 1u32) >> 2), &self.p);
+        if &root * &root % &self.p == *value {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn add(
+        &self,
+        a: Option<(BigUint, BigUint)>,
+        b: Option<(BigUint, BigUint)>,
+    ) -> Option<(BigUint, BigUint)> {
+        let ((ax, ay), (bx, by)) = match (a, b) {
+            (None, b) => return b,
+            (a, None) => return a,
+            (Some(a), Some(b)) => (a, b),
+        };
+
+        let slope = if ax == bx {
+            if (&ay + &by) % &self.p == BigUint::zero() {
+                return None;
+            }
+            // Tangent slope (3x^2 + a) / 2y
+            (&ax * &ax * 3u32 + &self.a) % &self.p * self.invert(&(&ay * 2u32))
+        } else {
+            (&by + &self.p - &ay) * self.invert(&(&bx + &self.p - &ax))
+        } % &self.p;
+
+        let x = (&slope * &slope + &self.p * 2u32 - &ax - &bx) % &self.p;
+        let y = (slope * ((&ax + &self.p - &x) % &self.p) + &self.p - &ay) % &self.p;
+        Some((x, y))
+    }
+
+    fn mul(
+        &self,
+        scalar: &BigUint,
+        point: &Option<(BigUint, BigUint)>,
+    ) -> Option<(BigUint, BigUint)> {
+        let mut result = None;
+        for bit in (0..scalar.bits()).rev() {
+            result = self.add(result.clone(), result);
+            if scalar.bit(bit) {
+                result = self.add(result, point.clone());
+            }
+        }
+        result
+    }
+
+    fn invert(&self, value: &BigUint) -> BigUint {
+        (value % &self.p).modpow(&(&self.p - 2u32), &self.p)
+    }
+}
+
+fn from_hex(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+fn to_bytes(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut buffer = [0u8; 32];
+    buffer[32 - bytes.len()..].copy_from_slice(&bytes);
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use k256::ecdsa::SigningKey;
+    use sha2::Sha256;
+
+    fn bytes(hex: &str) -> [u8; 32] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_secp256k1_point_operations() {
+        let generator = Secp256Point::<Secp256k1>::generator();
+        let (gx, gy) = generator.xy();
+
+        assert_eq!(
+            Secp256Point::new(&gx, &gy).unwrap(),
+            Some(generator.clone())
+        );
+        assert_eq!(
+            Secp256Point::<Secp256k1>::from_x(&gx, false).unwrap(),
+            Some(generator.clone())
+        );
+        assert_eq!(Secp256Point::<Secp256k1>::new(&gx, &gx).unwrap(), None);
+        assert!(matches!(
+            Secp256Point::<Secp256k1>::new(&[0xff; 32], &gy),
+            Err(Secp256Error::InvalidArgument)
+        ));
+
+        let doubled = generator.add(&generator);
+        assert_eq!(doubled, generator.mul(&bytes(&format!("{:064x}", 2))));
+        assert_eq!(
+            hex::encode(doubled.xy().0),
+            "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"
+        );
+
+        let order = bytes(Secp256k1::N);
+        let infinity = generator.mul(&order);
+        assert_eq!(infinity.xy(), ([0; 32], [0; 32]));
+        assert_eq!(
+            Secp256Point::new(&[0; 32], &[0; 32]).unwrap(),
+            Some(infinity.clone())
+        );
+        assert_eq!(infinity.add(&generator), generator);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_secp256k1_signatures() {
+        let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let msg_hash = bytes("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf");
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&msg_hash).unwrap();
+        let r: [u8; 32] = signature.r().to_bytes().into();
+        let s: [u8; 32] = signature.s().to_bytes().into();
+
+        let encoded = key.verifying_key().to_encoded_point(false);
+        let x: [u8; 32] = (*encoded.x().unwrap()).into();
+        let y: [u8; 32] = (*encoded.y().unwrap()).into();
+        let public_key = Secp256Point::<Secp256k1>::new(&x, &y).unwrap().unwrap();
+
+        assert!(secp256_is_valid_signature(&msg_hash, &r, &s, &public_key));
+        assert!(!secp256_is_valid_signature(
+            &[0x01; 32],
+            &r,
+            &s,
+            &public_key
+        ));
+        assert!(!secp256_is_valid_signature(
+            &msg_hash,
+            &r,
+            &[0; 32],
+            &public_key
+        ));
+        assert_eq!(
+            secp256_recover_public_key::<Secp256k1>(&msg_hash, &r, &s, recovery_id.is_y_odd())
+                .unwrap(),
+            Some(public_key.clone())
+        );
+
+        let eth_address = public_key_to_eth_address(&public_key);
+        verify_eth_signature(&msg_hash, &r, &s, recovery_id.is_y_odd(), &eth_address).unwrap();
+        assert!(matches!(
+            verify_eth_signature(&msg_hash, &r, &s, !recovery_id.is_y_odd(), &eth_address),
+            Err(Secp256Error::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_eth_signature(&msg_hash, &r, &bytes(Secp256k1::N), true, &eth_address),
+            Err(Secp256Error::SignatureOutOfRange)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_secp256r1_signature() {
+        // Test vector from RFC 6979, appendix A.2.5, with SHA-256 and message "sample"
+        let public_key = Secp256Point::<Secp256r1>::new(
+            &bytes("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"),
+            &bytes("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"),
+        )
+        .unwrap()
+        .unwrap();
+        let msg_hash: [u8; 32] = Sha256::digest(b"sample").into();
+        let r = bytes("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716");
+        let s = bytes("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8");
+
+        assert!(secp256_is_valid_signature(&msg_hash, &r, &s, &public_key));
+        assert!(!secp256_is_valid_signature(&msg_hash, &s, &r, &public_key));
+        assert_eq!(
+            secp256_recover_public_key::<Secp256r1>(&msg_hash, &r, &s, false).unwrap(),
+            Some(public_key)
+        );
+        assert_eq!(
+            hex::encode(
+                Secp256Point::<Secp256r1>::generator()
+                    .mul(&bytes(&format!("{:064x}", 2)))
+                    .xy()
+                    .0
+            ),
+            "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978"
+        );
+    }
+}
@@ -1,7 +1,133 @@
 use starknet_ff::FieldElement;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 use crate::curve_params::{ALPHA, BETA};
 
+/// Implements the operators taking points by value, and multiplication by [FieldElement], in
+/// terms of the `&point op &point` and `point op= &point` forms.
+macro_rules! impl_point_ops {
+    ($point:ty) => {
+        impl std::ops::Add<$point> for $point {
+            type Output = $point;
+
+            fn add(self, rhs: $point) -> Self::Output {
+                &self + &rhs
+            }
+        }
+
+        impl std::ops::Add<&$point> for $point {
+            type Output = $point;
+
+            fn add(self, rhs: &$point) -> Self::Output {
+                &self + rhs
+            }
+        }
+
+        impl std::ops::Add<$point> for &$point {
+            type Output = $point;
+
+            fn add(self, rhs: $point) -> Self::Output {
+                self + &rhs
+            }
+        }
+
+        impl std::ops::AddAssign<$point> for $point {
+            fn add_assign(&mut self, rhs: $point) {
+                *self += &rhs;
+            }
+        }
+
+        impl std::ops::Sub<$point> for $point {
+            type Output = $point;
+
+            fn sub(self, rhs: $point) -> Self::Output {
+                &self - &rhs
+            }
+        }
+
+        impl std::ops::Sub<&$point> for $point {
+            type Output = $point;
+
+            fn sub(self, rhs: &$point) -> Self::Output {
+                &self - rhs
+            }
+        }
+
+        impl std::ops::Sub<$point> for &$point {
+            type Output = $point;
+
+            fn sub(self, rhs: $point) -> Self::Output {
+                self - &rhs
+            }
+        }
+
+        impl std::ops::SubAssign<$point> for $point {
+            fn sub_assign(&mut self, rhs: $point) {
+                *self -= &rhs;
+            }
+        }
+
+        impl std::ops::Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> Self::Output {
+                -&self
+            }
+        }
+
+        // Keeps `&point * &scalar.to_bits_le()` compiling now that `Mul` has several
+        // implementations, which prevents coercing the array to a slice
+        impl std::ops::Mul<&[bool; 256]> for &$point {
+            type Output = $point;
+
+            fn mul(self, rhs: &[bool; 256]) -> Self::Output {
+                self * &rhs[..]
+            }
+        }
+
+        /// Multiplies in variable time. Use `multiply_constant_time` for secret scalars.
+        impl std::ops::Mul<&FieldElement> for &$point {
+            type Output = $point;
+
+            fn mul(self, rhs: &FieldElement) -> Self::Output {
+                self * &rhs.to_bits_le()[..]
+            }
+        }
+
+        impl std::ops::Mul<FieldElement> for &$point {
+            type Output = $point;
+
+            fn mul(self, rhs: FieldElement) -> Self::Output {
+                self * &rhs
+            }
+        }
+
+        impl std::ops::Mul<&FieldElement> for $point {
+            type Output = $point;
+
+            fn mul(self, rhs: &FieldElement) -> Self::Output {
+                &self * rhs
+            }
+        }
+
+        impl std::ops::Mul<FieldElement> for $point {
+            type Output = $point;
+
+            fn mul(self, rhs: FieldElement) -> Self::Output {
+                &self * &rhs
+            }
+        }
+    };
+}
+
+/// `3 * BETA`, used by the complete addition formulas.
+const BETA_TIMES_3: FieldElement = FieldElement::from_mont([
+    11590462478555702622,
+    3851094909012586514,
+    188687192569539542,
+    264467933896142207,
+]);
+
 /// A point on an elliptic curve over [FieldElement].
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct AffinePoint {
@@ -36,6 +162,66 @@ impl AffinePoint {
         }
     }
 
+    /// Multiplies the point by `scalar`, given as little-endian bits, in time independent of the
+    /// bits. Slower than multiplying with `*`, which should be preferred when the scalar is not
+    /// secret.
+    pub fn multiply_constant_time(&self, scalar: &[bool]) -> AffinePoint {
+        let product = ProjectivePoint::from(self).multiply_constant_time(scalar);
+        if product.infinity {
+            AffinePoint::identity()
+        } else {
+            AffinePoint::from(&product)
+        }
+    }
+
+    /// Multiplies a fixed base point by `scalar`, given as little-endian bits, in time independent
+    /// of the bits, using a table of precomputed multiples of the base point.
+    ///
+    /// For each window of `window_bits` bits, starting from the least significant one, `table`
+    /// holds the multiples `1..2^window_bits` of `2^(window * window_bits)` times the base point,
+    /// with the last window shortened to the remaining bits of `scalar`. Every entry of a window
+    /// is read regardless of the scalar.
+    pub fn fixed_base_multiply_constant_time(
+        table: &[AffinePoint],
+        window_bits: usize,
+        scalar: &[bool],
+    ) -> AffinePoint {
+        let identity = [FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO];
+        let mut acc = identity;
+
+        let mut offset = 0;
+        for window in scalar.chunks(window_bits) {
+            let digit = window
+                .iter()
+                .enumerate()
+                .fold(0u64, |acc, (ind, bit)| acc | ((*bit as u64) << ind));
+
+            let mut selected = identity;
+            let entries = &table[offset..(offset + (1 << window.len()) - 1)];
+            for (ind, entry) in entries.iter().enumerate() {
+                let choice = digit.ct_eq(&(ind as u64 + 1));
+                let entry = [entry.x, entry.y, FieldElement::ONE];
+                for (selected, entry) in selected.iter_mut().zip(entry.iter()) {
+                    *selected = select(selected, entry, choice);
+                }
+            }
+
+            acc = add_complete(&acc, &selected);
+            offset += entries.len();
+        }
+
+        if acc[2] == FieldElement::ZERO {
+            AffinePoint::identity()
+        } else {
+            let zinv = acc[2].invert().unwrap();
+            AffinePoint {
+                x: acc[0] * zinv,
+                y: acc[1] * zinv,
+                infinity: false,
+            }
+        }
+    }
+
     pub fn double_assign(&mut self) {
         if self.infinity {
             return;
@@ -87,7 +273,11 @@ impl std::ops::AddAssign<&AffinePoint> for AffinePoint {
             return;
         }
         if self.x == rhs.x {
-            self.double_assign();
+            if self.y == rhs.y {
+                self.double_assign();
+            } else {
+                *self = AffinePoint::identity();
+            }
             return;
         }
 
@@ -116,11 +306,19 @@ impl std::ops::Sub<&AffinePoint> for &AffinePoint {
 
 impl std::ops::SubAssign<&AffinePoint> for AffinePoint {
     fn sub_assign(&mut self, rhs: &AffinePoint) {
-        *self += &AffinePoint {
-            x: rhs.x,
-            y: -rhs.y,
-            infinity: rhs.infinity,
-        };
+        *self += &-rhs;
+    }
+}
+
+impl std::ops::Neg for &AffinePoint {
+    type Output = AffinePoint;
+
+    fn neg(self) -> Self::Output {
+        AffinePoint {
+            x: self.x,
+            y: -self.y,
+            infinity: self.infinity,
+        }
     }
 }
 
@@ -151,7 +349,7 @@ impl ProjectivePoint {
         }
     }
 
-    fn identity() -> ProjectivePoint {
+    pub(crate) fn identity() -> ProjectivePoint {
         Self {
             x: FieldElement::ZERO,
             y: FieldElement::ZERO,
@@ -160,6 +358,35 @@ impl ProjectivePoint {
         }
     }
 
+    /// Multiplies the point by `scalar`, given as little-endian bits, with a Montgomery ladder
+    /// whose operations and memory accesses don't depend on the bits.
+    pub fn multiply_constant_time(&self, scalar: &[bool]) -> ProjectivePoint {
+        // The ladder works on points where the identity is `(0, 1, 0)` instead of a flag, as
+        // required by the complete addition formulas
+        let mut r0 = [FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO];
+        let mut r1 = if self.infinity {
+            r0
+        } else {
+            [self.x, self.y, self.z]
+        };
+
+        for bit in scalar.iter().rev() {
+            let bit = Choice::from(*bit as u8);
+            conditional_swap(&mut r0, &mut r1, bit);
+            r1 = add_complete(&r0, &r1);
+            r0 = add_complete(&r0, &r0);
+            conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        let [x, y, z] = r0;
+        ProjectivePoint {
+            x,
+            y,
+            z,
+            infinity: z == FieldElement::ZERO,
+        }
+    }
+
     pub fn double_assign(&mut self) {
         if self.infinity {
             return;
@@ -247,13 +474,17 @@ impl std::ops::AddAssign<&ProjectivePoint> for ProjectivePoint {
         }
         let u0 = self.x * rhs.z;
         let u1 = rhs.x * self.z;
+        let t0 = self.y * rhs.z;
+        let t1 = rhs.y * self.z;
         if u0 == u1 {
-            self.double_assign();
+            if t0 != t1 {
+                self.infinity = true;
+            } else {
+                self.double_assign();
+            }
             return;
         }
 
-        let t0 = self.y * rhs.z;
-        let t1 = rhs.y * self.z;
         let t = t0 - t1;
 
         let u = u0 - u1;
@@ -273,6 +504,43 @@ impl std::ops::AddAssign<&ProjectivePoint> for ProjectivePoint {
     }
 }
 
+impl std::ops::Add<&ProjectivePoint> for &ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn add(self, rhs: &ProjectivePoint) -> Self::Output {
+        let mut copy = *self;
+        copy += rhs;
+        copy
+    }
+}
+
+impl std::ops::Sub<&ProjectivePoint> for &ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn sub(self, rhs: &ProjectivePoint) -> Self::Output {
+        let mut copy = *self;
+        copy -= rhs;
+        copy
+    }
+}
+
+impl std::ops::SubAssign<&ProjectivePoint> for ProjectivePoint {
+    fn sub_assign(&mut self, rhs: &ProjectivePoint) {
+        *self += &-rhs;
+    }
+}
+
+impl std::ops::Neg for &ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn neg(self) -> Self::Output {
+        ProjectivePoint {
+            y: -self.y,
+            ..*self
+        }
+    }
+}
+
 impl std::ops::Mul<&[bool]> for &ProjectivePoint {
     type Output = ProjectivePoint;
 
@@ -289,3 +557,176 @@ impl std::ops::Mul<&[bool]> for &ProjectivePoint {
         product
     }
 }
+
+impl_point_ops!(AffinePoint);
+impl_point_ops!(ProjectivePoint);
+
+/// Adds points in homogeneous projective coordinates with the complete formulas of Renes,
+/// Costello and Batina (2016, algorithm 1), which are branch-free and also handle doubling and
+/// the identity.
+fn add_complete(p: &[FieldElement; 3], q: &[FieldElement; 3]) -> [FieldElement; 3] {
+    let [x1, y1, z1] = *p;
+    let [x2, y2, z2] = *q;
+
+    let t0 = x1 * x2;
+    let t1 = y1 * y2;
+    let t2 = z1 * z2;
+    let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+    let t4 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+    let t5 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+
+    let z3 = ALPHA * t4 + BETA_TIMES_3 * t2;
+    let x3 = t1 - z3;
+    let z3 = t1 + z3;
+    let y3 = x3 * z3;
+
+    let t1 = t0 + t0 + t0 + ALPHA * t2;
+    let t2 = ALPHA * (t0 - ALPHA * t2);
+    let t4 = BETA_TIMES_3 * t4 + t2;
+
+    let y3 = y3 + t1 * t4;
+    let x3 = t3 * x3 - t5 * t4;
+    let z3 = t5 * z3 + t3 * t1;
+
+    [x3, y3, z3]
+}
+
+/// Returns `b` if `choice` is set and `a` otherwise.
+fn select(a: &FieldElement, b: &FieldElement, choice: Choice) -> FieldElement {
+    let a = a.into_mont();
+    let b = b.into_mont();
+    let mut limbs = [0u64; 4];
+    for (limb, (a, b)) in limbs.iter_mut().zip(a.iter().zip(b.iter())) {
+        *limb = u64::conditional_select(a, b, choice);
+    }
+    FieldElement::from_mont(limbs)
+}
+
+fn conditional_swap(a: &mut [FieldElement; 3], b: &mut [FieldElement; 3], choice: Choice) {
+    for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+        let mut a_limbs = a.into_mont();
+        let mut b_limbs = b.into_mont();
+        for (a_limb, b_limb) in a_limbs.iter_mut().zip(b_limbs.iter_mut()) {
+            u64::conditional_swap(a_limb, b_limb, choice);
+        }
+        *a = FieldElement::from_mont(a_limbs);
+        *b = FieldElement::from_mont(b_limbs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::curve_params::GENERATOR;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_beta_times_3() {
+        assert_eq!(BETA_TIMES_3, FieldElement::THREE * BETA);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_multiply_constant_time() {
+        for scalar in [
+            FieldElement::ZERO,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::from(0xdeadbeefu64),
+            FieldElement::from_hex_be(
+                "0x4a724706e80e5ea88b9ee60a7ede83cbc2de27da0659bc72d42179557a978f0",
+            )
+            .unwrap(),
+        ] {
+            let bits = scalar.to_bits_le();
+            assert_eq!(
+                GENERATOR.multiply_constant_time(&bits),
+                &GENERATOR * &bits[..]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_add_complete_edge_cases() {
+        let p = [GENERATOR.x, GENERATOR.y, FieldElement::ONE];
+        let neg_p = [GENERATOR.x, -GENERATOR.y, FieldElement::ONE];
+        let identity = [FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO];
+
+        let mut doubled = GENERATOR;
+        doubled.double_assign();
+        let sum = add_complete(&p, &p);
+        let sum = AffinePoint::from(&ProjectivePoint {
+            x: sum[0],
+            y: sum[1],
+            z: sum[2],
+            infinity: false,
+        });
+        assert_eq!(sum, doubled);
+
+        assert_eq!(add_complete(&p, &neg_p)[2], FieldElement::ZERO);
+
+        let sum = add_complete(&p, &identity);
+        assert_eq!(sum[0] * sum[2].invert().unwrap(), GENERATOR.x);
+        assert_eq!(sum[1] * sum[2].invert().unwrap(), GENERATOR.y);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_fixed_base_multiply_constant_time() {
+        // 3-bit windows over 20 bits leave a shorter last window
+        let mut table = vec![];
+        let mut base = GENERATOR;
+        for window in [3; 6].into_iter().chain([2]) {
+            let mut entry = base;
+            for _ in 1..(1 << window) {
+                table.push(entry);
+                entry += &base;
+            }
+            for _ in 0..window {
+                base.double_assign();
+            }
+        }
+
+        for scalar in [0u64, 1, 7, 8, 0xfffff, 0xabcde] {
+            let bits = FieldElement::from(scalar).to_bits_le();
+            assert_eq!(
+                AffinePoint::fixed_base_multiply_constant_time(&table, 3, &bits[..20]),
+                &GENERATOR * &bits[..]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_point_ops() {
+        let two = FieldElement::TWO;
+        let three = FieldElement::THREE;
+
+        let affine = GENERATOR;
+        assert_eq!(affine + affine, affine * two);
+        assert_eq!(affine * three - affine, affine * two);
+        assert_eq!(affine - affine, affine * FieldElement::ZERO);
+        assert_eq!(-(-affine), affine);
+
+        let mut sum = affine;
+        sum += affine;
+        sum -= &affine;
+        assert_eq!(sum, affine);
+
+        let projective = ProjectivePoint::from(&GENERATOR);
+        assert_eq!(AffinePoint::from(&(projective + projective)), affine * two);
+        assert_eq!(
+            AffinePoint::from(&(projective * three - projective)),
+            affine * two
+        );
+        assert!((projective - projective).infinity);
+        assert_eq!(AffinePoint::from(&-projective), -affine);
+
+        let mut sum = projective;
+        sum += projective;
+        sum -= projective;
+        assert_eq!(AffinePoint::from(&sum), affine);
+    }
+}
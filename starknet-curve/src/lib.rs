@@ -1,7 +1,10 @@
 #![doc = include_str!("../README.md")]
 
 mod ec_point;
+mod msm;
 
 pub mod curve_params;
 
 pub use ec_point::{AffinePoint, ProjectivePoint};
+
+pub use msm::msm;
@@ -0,0 +1,117 @@
+use starknet_ff::FieldElement;
+
+use crate::{AffinePoint, ProjectivePoint};
+
+/// Number of bits needed to represent any [FieldElement].
+const SCALAR_BITS: usize = 252;
+
+/// Computes the multi-scalar multiplication `scalars[0] * points[0] + scalars[1] * points[1] + ..`
+/// with Pippenger's bucket method, which is much faster than summing individual multiplications
+/// for more than a few points. Runs in variable time, so the scalars must not be secret.
+///
+/// ### Panics
+///
+/// Panics if `points` and `scalars` differ in length.
+pub fn msm(points: &[AffinePoint], scalars: &[FieldElement]) -> ProjectivePoint {
+    assert_eq!(
+        points.len(),
+        scalars.len(),
+        "points and scalars must have the same length"
+    );
+
+    let window_bits = window_bits(points.len());
+    let scalars = scalars
+        .iter()
+        .map(|scalar| scalar.to_bits_le())
+        .collect::<Vec<_>>();
+
+    let mut buckets = vec![ProjectivePoint::identity(); (1 << window_bits) - 1];
+    let mut result = ProjectivePoint::identity();
+    for window_start in (0..SCALAR_BITS).step_by(window_bits).rev() {
+        for _ in 0..window_bits {
+            result.double_assign();
+        }
+
+        let window_end = (window_start + window_bits).min(SCALAR_BITS);
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let digit = scalar[window_start..window_end]
+                .iter()
+                .rev()
+                .fold(0usize, |acc, bit| (acc << 1) | (*bit as usize));
+            if digit > 0 {
+                buckets[digit - 1] += point;
+            }
+        }
+
+        // Sums each bucket weighted by its digit, as the sum of the running sums from the top
+        let mut running_sum = ProjectivePoint::identity();
+        let mut window_sum = ProjectivePoint::identity();
+        for bucket in buckets.iter_mut().rev() {
+            running_sum += &*bucket;
+            window_sum += &running_sum;
+            *bucket = ProjectivePoint::identity();
+        }
+
+        result += &window_sum;
+    }
+
+    result
+}
+
+/// Picks the window size minimizing the number of point additions for `len` points.
+fn window_bits(len: usize) -> usize {
+    if len < 32 {
+        3
+    } else {
+        (len as f64).ln().ceil() as usize + 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::curve_params::GENERATOR;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_msm() {
+        for len in [1, 5, 40] {
+            let mut points = vec![];
+            let mut point = GENERATOR;
+            for _ in 0..len {
+                point.double_assign();
+                points.push(point);
+            }
+            let mut scalars = (0..len)
+                .map(|ind| FieldElement::from(0x1234567u64 * (ind + 1)) * scalar_multiplier())
+                .collect::<Vec<_>>();
+            scalars[0] = FieldElement::ZERO;
+            scalars.push(-FieldElement::ONE);
+            points.push(GENERATOR);
+
+            let mut expected = ProjectivePoint::identity();
+            for (point, scalar) in points.iter().zip(scalars.iter()) {
+                expected += &(point * &scalar.to_bits_le()[..]);
+            }
+
+            assert_eq!(
+                AffinePoint::from(&msm(&points, &scalars)),
+                AffinePoint::from(&expected)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_msm_empty() {
+        assert!(msm(&[], &[]).infinity);
+    }
+
+    fn scalar_multiplier() -> FieldElement {
+        FieldElement::from_hex_be(
+            "0x4a724706e80e5ea88b9ee60a7ede83cbc2de27da0659bc72d42179557a978f0",
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,371 @@
+use serde::Deserialize;
+use serde_json::json;
+use serde_with::serde_as;
+use starknet_accounts::SingleOwnerAccount;
+use starknet_core::{chain_id, serde::unsigned_field_element::UfeHex, types::FieldElement};
+use starknet_providers::SequencerGatewayProvider;
+use starknet_signers::{LocalWallet, SigningKey};
+use std::{
+    ffi::OsString,
+    net::TcpListener,
+    process::{Child, Command, ExitStatus, Stdio},
+    time::{Duration, Instant},
+};
+use url::Url;
+
+/// Environment variable overriding the path of the devnet executable.
+const PROGRAM_ENV: &str = "STARKNET_DEVNET";
+
+const DEFAULT_PROGRAM: &str = "starknet-devnet";
+
+const DEFAULT_SEED: u64 = 0;
+
+const DEFAULT_ACCOUNTS: usize = 10;
+
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Address of the ETH fee token predeployed on devnet.
+const FEE_TOKEN_ADDRESS: &str = "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
+
+/// A running devnet process, killed and reaped when dropped.
+///
+/// ```ignore
+/// let devnet = Devnet::spawn().await?;
+/// let account = devnet.account(0);
+/// account.execute(calls).send().await?;
+/// ```
+#[derive(Debug)]
+pub struct Devnet {
+    child: Child,
+    url: Url,
+    client: reqwest::Client,
+    accounts: Vec<PredeployedAccount>,
+}
+
+/// Configuration for spawning a [Devnet].
+#[derive(Debug, Clone)]
+pub struct DevnetBuilder {
+    program: OsString,
+    port: Option<u16>,
+    seed: u64,
+    accounts: usize,
+    startup_timeout: Duration,
+    args: Vec<OsString>,
+}
+
+/// A prefunded account deployed when devnet starts.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct PredeployedAccount {
+    #[serde_as(as = "UfeHex")]
+    pub address: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub private_key: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub public_key: FieldElement,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DevnetError {
+    #[error("failed to spawn devnet: {0}")]
+    Spawn(std::io::Error),
+    #[error("devnet exited before becoming ready: {0}")]
+    Exited(ExitStatus),
+    #[error("devnet not ready after {0:?}")]
+    Timeout(Duration),
+    #[error(transparent)]
+    Network(reqwest::Error),
+}
+
+impl Devnet {
+    pub fn builder() -> DevnetBuilder {
+        DevnetBuilder::default()
+    }
+
+    /// Spawns a devnet with the default configuration.
+    pub async fn spawn() -> Result<Self, DevnetError> {
+        Self::builder().spawn().await
+    }
+
+    /// The base URL of the devnet HTTP server.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn provider(&self) -> SequencerGatewayProvider {
+        SequencerGatewayProvider::new(
+            self.url.join("gateway").unwrap(),
+            self.url.join("feeder_gateway").unwrap(),
+        )
+    }
+
+    pub fn predeployed_accounts(&self) -> &[PredeployedAccount] {
+        &self.accounts
+    }
+
+    /// The predeployed account at `index`, signing with its private key.
+    ///
+    /// Panics if `index` is not lower than the number of predeployed accounts.
+    pub fn account(
+        &self,
+        index: usize,
+    ) -> SingleOwnerAccount<SequencerGatewayProvider, LocalWallet> {
+        let account = &self.accounts[index];
+        SingleOwnerAccount::new(
+            self.provider(),
+            LocalWallet::from(SigningKey::from_secret_scalar(account.private_key)),
+            account.address,
+            chain_id::TESTNET,
+        )
+    }
+
+    pub fn accounts(&self) -> Vec<SingleOwnerAccount<SequencerGatewayProvider, LocalWallet>> {
+        (0..self.accounts.len())
+            .map(|index| self.account(index))
+            .collect()
+    }
+
+    /// The address of the ETH contract in which fees are paid.
+    pub fn fee_token_address(&self) -> FieldElement {
+        FieldElement::from_hex_be(FEE_TOKEN_ADDRESS).unwrap()
+    }
+
+    /// Mints `amount` of the fee token to `address`.
+    pub async fn mint(&self, address: FieldElement, amount: u128) -> Result<(), DevnetError> {
+        self.client
+            .post(self.url.join("mint").unwrap())
+            .json(&json!({
+                "address": format!("{address:#x}"),
+                "amount": amount,
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(DevnetError::Network)?;
+        Ok(())
+    }
+
+    async fn wait_until_alive(&mut self, timeout: Duration) -> Result<(), DevnetError> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait().map_err(DevnetError::Spawn)? {
+                return Err(DevnetError::Exited(status));
+            }
+
+            let response = self
+                .client
+                .get(self.url.join("is_alive").unwrap())
+                .send()
+                .await;
+            if matches!(response, Ok(response) if response.status().is_success()) {
+                return Ok(());
+            }
+
+            if started.elapsed() >= timeout {
+                return Err(DevnetError::Timeout(timeout));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn fetch_predeployed_accounts(&self) -> Result<Vec<PredeployedAccount>, DevnetError> {
+        self.client
+            .get(self.url.join("predeployed_accounts").unwrap())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(DevnetError::Network)?
+            .json()
+            .await
+            .map_err(DevnetError::Network)
+    }
+}
+
+impl Drop for Devnet {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        // Reaps the process so that it doesn't linger as a zombie
+        let _ = self.child.wait();
+    }
+}
+
+impl DevnetBuilder {
+    /// Sets the devnet executable. Defaults to the `STARKNET_DEVNET` environment variable, or
+    /// `starknet-devnet` from `PATH`.
+    pub fn program(self, program: impl Into<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            ..self
+        }
+    }
+
+    /// Sets the port to listen on. Defaults to a free port.
+    pub fn port(self, port: u16) -> Self {
+        Self {
+            port: Some(port),
+            ..self
+        }
+    }
+
+    /// Sets the seed the predeployed accounts are generated from, so that their keys and
+    /// addresses are the same across runs. Defaults to `0`.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Sets the number of predeployed accounts. Defaults to `10`.
+    pub fn accounts(self, accounts: usize) -> Self {
+        Self { accounts, ..self }
+    }
+
+    /// Sets how long to wait for devnet to accept requests. Defaults to 60 seconds.
+    pub fn startup_timeout(self, startup_timeout: Duration) -> Self {
+        Self {
+            startup_timeout,
+            ..self
+        }
+    }
+
+    /// Forks `network`, either a network name like `alpha-mainnet` or a feeder gateway URL, at
+    /// block `block_number`. Contracts not found on devnet are then read from the forked network,
+    /// as of the same block as a [ForkSnapshot](crate::ForkSnapshot) taken at `block_number`.
+    pub fn fork(self, network: impl Into<OsString>, block_number: u64) -> Self {
+        self.arg("--fork-network")
+            .arg(network)
+            .arg("--fork-block")
+            .arg(block_number.to_string())
+    }
+
+    /// Appends an extra command line argument.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Spawns the devnet process, and waits until it accepts requests.
+    pub async fn spawn(self) -> Result<Devnet, DevnetError> {
+        let port = match self.port {
+            Some(port) => port,
+            None => free_port().map_err(DevnetError::Spawn)?,
+        };
+
+        let child = self
+            .command(port)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(DevnetError::Spawn)?;
+
+        // The process is killed on drop from here on, including when it fails to start
+        let mut devnet = Devnet {
+            child,
+            url: Url::parse(&format!("http://127.0.0.1:{port}/")).unwrap(),
+            client: reqwest::Client::new(),
+            accounts: vec![],
+        };
+        devnet.wait_until_alive(self.startup_timeout).await?;
+        devnet.accounts = devnet.fetch_predeployed_accounts().await?;
+
+        Ok(devnet)
+    }
+
+    fn command(&self, port: u16) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .arg("--host")
+            .arg("127.0.0.1")
+            .arg("--port")
+            .arg(port.to_string())
+            .arg("--seed")
+            .arg(self.seed.to_string())
+            .arg("--accounts")
+            .arg(self.accounts.to_string())
+            .args(&self.args);
+        command
+    }
+}
+
+impl Default for DevnetBuilder {
+    fn default() -> Self {
+        Self {
+            program: std::env::var_os(PROGRAM_ENV).unwrap_or_else(|| DEFAULT_PROGRAM.into()),
+            port: None,
+            seed: DEFAULT_SEED,
+            accounts: DEFAULT_ACCOUNTS,
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+            args: vec![],
+        }
+    }
+}
+
+/// Finds a free local port by binding to port `0`.
+fn free_port() -> std::io::Result<u16> {
+    Ok(TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_args() {
+        let command = Devnet::builder()
+            .program("/opt/devnet/bin/starknet-devnet")
+            .seed(42)
+            .accounts(2)
+            .arg("--lite-mode")
+            .fork("alpha-mainnet", 12345)
+            .command(5050);
+
+        assert_eq!(command.get_program(), "/opt/devnet/bin/starknet-devnet");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "--host",
+                "127.0.0.1",
+                "--port",
+                "5050",
+                "--seed",
+                "42",
+                "--accounts",
+                "2",
+                "--lite-mode",
+                "--fork-network",
+                "alpha-mainnet",
+                "--fork-block",
+                "12345"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deser_predeployed_accounts() {
+        let accounts: Vec<PredeployedAccount> = serde_json::from_str(
+            r#"[{
+                "initial_balance": 1000000000000000000000,
+                "address": "0x7e00d496e324876bbc8531f2d9a82bf154d1a04a50218ee74cdd372f75a551a",
+                "private_key": "0xe3e70682c2094cac629f6fbed82c07cd",
+                "public_key": "0x7e52885445756b313ea16849145363ccb73fb4ab0440dbac333cf9d13de82b9"
+            }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            accounts[0].private_key,
+            FieldElement::from_hex_be("0xe3e70682c2094cac629f6fbed82c07cd").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_missing_program() {
+        let result = Devnet::builder()
+            .program("/nonexistent/starknet-devnet")
+            .spawn()
+            .await;
+
+        assert!(matches!(result, Err(DevnetError::Spawn(_))));
+    }
+}
@@ -0,0 +1,314 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use starknet_core::{
+    serde::unsigned_field_element::UfeHex,
+    types::{BlockId, ContractArtifact, FieldElement},
+};
+use starknet_providers::{Provider, ProviderError};
+use std::collections::{btree_map::Entry, BTreeMap};
+
+/// Selects the contracts snapshotted from a live network at a fixed block.
+///
+/// Only the storage keys listed for each contract are read, as the full storage of a contract
+/// can't be enumerated through a provider.
+///
+/// ```ignore
+/// let snapshot = Fork::new(provider, 12345)
+///     .contract(token_address, [get_storage_var_address("ERC20_balances", &[holder])?])
+///     .snapshot()
+///     .await?;
+/// snapshot.load_into(&mut state)?;
+/// ```
+#[derive(Debug)]
+pub struct Fork<P> {
+    provider: P,
+    block_number: u64,
+    contracts: BTreeMap<FieldElement, Vec<FieldElement>>,
+}
+
+/// The classes and storage of selected contracts at a given block, which can be saved as JSON
+/// and loaded into a local state with [load_into](ForkSnapshot::load_into).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForkSnapshot {
+    pub block_number: u64,
+    pub classes: Vec<ClassSnapshot>,
+    pub contracts: Vec<ContractSnapshot>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassSnapshot {
+    #[serde_as(as = "UfeHex")]
+    pub class_hash: FieldElement,
+    pub contract_class: ContractArtifact,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractSnapshot {
+    #[serde_as(as = "UfeHex")]
+    pub address: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub class_hash: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub nonce: FieldElement,
+    pub storage: Vec<StorageEntry>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    #[serde_as(as = "UfeHex")]
+    pub key: FieldElement,
+    #[serde_as(as = "UfeHex")]
+    pub value: FieldElement,
+}
+
+/// A local state a [ForkSnapshot] is loaded into, such as the state of a local execution engine.
+pub trait ForkState {
+    type Error: std::error::Error;
+
+    fn declare_class(
+        &mut self,
+        class_hash: FieldElement,
+        contract_class: &ContractArtifact,
+    ) -> Result<(), Self::Error>;
+
+    fn deploy_contract(
+        &mut self,
+        address: FieldElement,
+        class_hash: FieldElement,
+    ) -> Result<(), Self::Error>;
+
+    fn set_nonce(&mut self, address: FieldElement, nonce: FieldElement) -> Result<(), Self::Error>;
+
+    fn set_storage(
+        &mut self,
+        address: FieldElement,
+        key: FieldElement,
+        value: FieldElement,
+    ) -> Result<(), Self::Error>;
+}
+
+impl<P> Fork<P> {
+    /// Snapshots state as of block `block_number`, so that the same snapshot is taken every time.
+    pub fn new(provider: P, block_number: u64) -> Self {
+        Self {
+            provider,
+            block_number,
+            contracts: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the contract deployed at `address`, reading its storage at `keys`. Can be called
+    /// several times for the same contract to read more keys.
+    pub fn contract(
+        mut self,
+        address: FieldElement,
+        keys: impl IntoIterator<Item = FieldElement>,
+    ) -> Self {
+        self.contracts.entry(address).or_default().extend(keys);
+        self
+    }
+}
+
+impl<P> Fork<P>
+where
+    P: Provider,
+{
+    /// Fetches the classes, nonces and storage of the selected contracts.
+    pub async fn snapshot(&self) -> Result<ForkSnapshot, ProviderError<P::Error>> {
+        let block_id = BlockId::Number(self.block_number);
+
+        let mut classes = BTreeMap::new();
+        let mut contracts = vec![];
+        for (address, keys) in self.contracts.iter() {
+            let class_hash = self.provider.get_class_hash_at(*address, block_id).await?;
+            if let Entry::Vacant(entry) = classes.entry(class_hash) {
+                entry.insert(self.provider.get_class_by_hash(class_hash).await?);
+            }
+
+            let nonce = self.provider.get_nonce(*address, block_id).await?;
+
+            let mut keys = keys.clone();
+            keys.sort();
+            keys.dedup();
+            let mut storage = vec![];
+            for key in keys {
+                let value = self
+                    .provider
+                    .get_storage_at(*address, key, block_id)
+                    .await?;
+                storage.push(StorageEntry { key, value });
+            }
+
+            contracts.push(ContractSnapshot {
+                address: *address,
+                class_hash,
+                nonce,
+                storage,
+            });
+        }
+
+        Ok(ForkSnapshot {
+            block_number: self.block_number,
+            classes: classes
+                .into_iter()
+                .map(|(class_hash, contract_class)| ClassSnapshot {
+                    class_hash,
+                    contract_class,
+                })
+                .collect(),
+            contracts,
+        })
+    }
+}
+
+impl ForkSnapshot {
+    pub fn contract(&self, address: FieldElement) -> Option<&ContractSnapshot> {
+        self.contracts
+            .iter()
+            .find(|contract| contract.address == address)
+    }
+
+    /// The value of storage slot `key` of the contract at `address`, if it was snapshotted.
+    pub fn storage_at(&self, address: FieldElement, key: FieldElement) -> Option<FieldElement> {
+        self.contract(address)?
+            .storage
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value)
+    }
+
+    /// Declares the classes, then deploys the contracts with their nonce and storage.
+    pub fn load_into<S>(&self, state: &mut S) -> Result<(), S::Error>
+    where
+        S: ForkState,
+    {
+        for class in self.classes.iter() {
+            state.declare_class(class.class_hash, &class.contract_class)?;
+        }
+
+        for contract in self.contracts.iter() {
+            state.deploy_contract(contract.address, contract.class_hash)?;
+            state.set_nonce(contract.address, contract.nonce)?;
+            for entry in contract.storage.iter() {
+                state.set_storage(contract.address, entry.key, entry.value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    #[derive(Debug, Default)]
+    struct RecordingState {
+        operations: Vec<String>,
+    }
+
+    impl ForkState for RecordingState {
+        type Error = Infallible;
+
+        fn declare_class(
+            &mut self,
+            class_hash: FieldElement,
+            _contract_class: &ContractArtifact,
+        ) -> Result<(), Self::Error> {
+            self.operations.push(format!("declare {class_hash:#x}"));
+            Ok(())
+        }
+
+        fn deploy_contract(
+            &mut self,
+            address: FieldElement,
+            class_hash: FieldElement,
+        ) -> Result<(), Self::Error> {
+            self.operations
+                .push(format!("deploy {address:#x} {class_hash:#x}"));
+            Ok(())
+        }
+
+        fn set_nonce(
+            &mut self,
+            address: FieldElement,
+            nonce: FieldElement,
+        ) -> Result<(), Self::Error> {
+            self.operations
+                .push(format!("nonce {address:#x} {nonce:#x}"));
+            Ok(())
+        }
+
+        fn set_storage(
+            &mut self,
+            address: FieldElement,
+            key: FieldElement,
+            value: FieldElement,
+        ) -> Result<(), Self::Error> {
+            self.operations
+                .push(format!("storage {address:#x} {key:#x} {value:#x}"));
+            Ok(())
+        }
+    }
+
+    fn snapshot() -> ForkSnapshot {
+        let contract_class: ContractArtifact = serde_json::from_str(include_str!(
+            "../../starknet-core/test-data/contracts/artifacts/oz_account.txt"
+        ))
+        .unwrap();
+
+        ForkSnapshot {
+            block_number: 375919,
+            classes: vec![ClassSnapshot {
+                class_hash: FieldElement::from(0xcu64),
+                contract_class,
+            }],
+            contracts: vec![ContractSnapshot {
+                address: FieldElement::from(0xau64),
+                class_hash: FieldElement::from(0xcu64),
+                nonce: FieldElement::from(2u64),
+                storage: vec![StorageEntry {
+                    key: FieldElement::from(0x10u64),
+                    value: FieldElement::from(0x20u64),
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_load_into() {
+        let mut state = RecordingState::default();
+        snapshot().load_into(&mut state).unwrap();
+
+        assert_eq!(
+            state.operations,
+            [
+                "declare 0xc",
+                "deploy 0xa 0xc",
+                "nonce 0xa 0x2",
+                "storage 0xa 0x10 0x20"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_json_round_trip() {
+        let json = serde_json::to_string(&snapshot()).unwrap();
+        let snapshot: ForkSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(snapshot.block_number, 375919);
+        assert_eq!(
+            snapshot.storage_at(FieldElement::from(0xau64), FieldElement::from(0x10u64)),
+            Some(FieldElement::from(0x20u64))
+        );
+        assert_eq!(
+            snapshot.storage_at(FieldElement::from(0xau64), FieldElement::from(0x11u64)),
+            None
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! Harness spawning a local Starknet devnet, so that integration tests run against a fresh
+//! network with prefunded accounts instead of a public testnet.
+
+mod devnet;
+pub use devnet::{Devnet, DevnetBuilder, DevnetError, PredeployedAccount};
+
+mod fork;
+pub use fork::{ClassSnapshot, ContractSnapshot, Fork, ForkSnapshot, ForkState, StorageEntry};
@@ -588,6 +588,47 @@ impl From<&FieldElement> for U256 {
     }
 }
 
+/// Uniformly distributed over the field when the input holds enough bytes.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for FieldElement {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut bytes: [u8; 32] = u.arbitrary()?;
+        Ok(Self::from_bytes_be(&bytes).unwrap_or_else(|_| {
+            // Values above the prime are brought below `2^251` instead
+            bytes[0] &= 0x07;
+            Self::from_bytes_be(&bytes).unwrap()
+        }))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (U256_BYTE_COUNT, Some(U256_BYTE_COUNT))
+    }
+}
+
+/// Generates `0`, `1`, `-1` and small values a quarter of the time, and random elements below
+/// `2^251` otherwise.
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for FieldElement {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        prop_oneof![
+            1 => Just(Self::ZERO),
+            1 => Just(Self::ONE),
+            1 => Just(Self::MAX),
+            1 => any::<u64>().prop_map(Self::from),
+            12 => any::<[u8; 32]>().prop_map(|mut bytes| {
+                bytes[0] &= 0x07;
+                Self::from_bytes_be(&bytes).unwrap()
+            }),
+        ]
+        .boxed()
+    }
+}
+
 impl<'a> Debug for InnerDebug<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:#064x}", self.0)
@@ -859,4 +900,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_from_bytes() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let felt = FieldElement::arbitrary(&mut Unstructured::new(&[0x01; 32])).unwrap();
+        assert_eq!(felt, FieldElement::from_bytes_be(&[0x01; 32]).unwrap());
+
+        let felt = FieldElement::arbitrary(&mut Unstructured::new(&[0xff; 32])).unwrap();
+        let mut masked = [0xff; 32];
+        masked[0] = 0x07;
+        assert_eq!(felt, FieldElement::from_bytes_be(&masked).unwrap());
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_proptest_roundtrip(felt: FieldElement) {
+            proptest::prop_assert_eq!(FieldElement::from_bytes_be(&felt.to_bytes_be()).unwrap(), felt);
+            proptest::prop_assert_eq!(FieldElement::from_dec_str(&felt.to_string()).unwrap(), felt);
+            proptest::prop_assert_eq!(FieldElement::from_hex_be(&format!("{felt:#x}")).unwrap(), felt);
+        }
+    }
 }
@@ -0,0 +1,131 @@
+#ifndef STARKNET_H
+#define STARKNET_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A field element as 32 big-endian bytes. */
+typedef struct StarknetFelt {
+  uint8_t bytes[32];
+} StarknetFelt;
+
+typedef enum StarknetStatus {
+  STARKNET_STATUS_OK = 0,
+  STARKNET_STATUS_NULL_POINTER = 1,
+  /* The bytes of a felt are not lower than the field prime. */
+  STARKNET_STATUS_INVALID_FELT = 2,
+  /* A string is not valid UTF-8, or doesn't represent the expected value. */
+  STARKNET_STATUS_INVALID_STRING = 3,
+  STARKNET_STATUS_BUFFER_TOO_SMALL = 4,
+  STARKNET_STATUS_DIVISION_BY_ZERO = 5,
+  /* The message hash to sign is out of range. */
+  STARKNET_STATUS_INVALID_MESSAGE_HASH = 6,
+  /* The public key is not a valid curve point. */
+  STARKNET_STATUS_INVALID_PUBLIC_KEY = 7,
+} StarknetStatus;
+
+/* Buffer length fitting the hex representation of any felt, including the NUL terminator. */
+#define STARKNET_FELT_HEX_LEN 67
+
+/* Field elements */
+
+StarknetStatus starknet_felt_from_hex(const char *hex, StarknetFelt *out);
+
+StarknetStatus starknet_felt_from_dec(const char *dec, StarknetFelt *out);
+
+StarknetStatus starknet_felt_to_hex(const StarknetFelt *felt, char *out, size_t out_len);
+
+StarknetStatus starknet_felt_add(const StarknetFelt *a, const StarknetFelt *b, StarknetFelt *out);
+
+StarknetStatus starknet_felt_sub(const StarknetFelt *a, const StarknetFelt *b, StarknetFelt *out);
+
+StarknetStatus starknet_felt_mul(const StarknetFelt *a, const StarknetFelt *b, StarknetFelt *out);
+
+StarknetStatus starknet_felt_div(const StarknetFelt *a, const StarknetFelt *b, StarknetFelt *out);
+
+StarknetStatus starknet_felt_floor_div(const StarknetFelt *a,
+                                       const StarknetFelt *b,
+                                       StarknetFelt *out);
+
+/* Hashing */
+
+StarknetStatus starknet_pedersen_hash(const StarknetFelt *a,
+                                      const StarknetFelt *b,
+                                      StarknetFelt *out);
+
+StarknetStatus starknet_compute_hash_on_elements(const StarknetFelt *data,
+                                                 size_t len,
+                                                 StarknetFelt *out);
+
+StarknetStatus starknet_poseidon_hash(const StarknetFelt *x,
+                                      const StarknetFelt *y,
+                                      StarknetFelt *out);
+
+StarknetStatus starknet_poseidon_hash_single(const StarknetFelt *x, StarknetFelt *out);
+
+StarknetStatus starknet_poseidon_hash_many(const StarknetFelt *data,
+                                           size_t len,
+                                           StarknetFelt *out);
+
+/* Signatures */
+
+StarknetStatus starknet_get_public_key(const StarknetFelt *private_key, StarknetFelt *out);
+
+StarknetStatus starknet_sign(const StarknetFelt *private_key,
+                             const StarknetFelt *message_hash,
+                             StarknetFelt *out_r,
+                             StarknetFelt *out_s);
+
+StarknetStatus starknet_verify(const StarknetFelt *public_key,
+                               const StarknetFelt *message_hash,
+                               const StarknetFelt *r,
+                               const StarknetFelt *s,
+                               bool *out);
+
+/* Selectors and addresses */
+
+StarknetStatus starknet_get_selector_from_name(const char *name, StarknetFelt *out);
+
+StarknetStatus starknet_get_contract_address(const StarknetFelt *salt,
+                                             const StarknetFelt *class_hash,
+                                             const StarknetFelt *calldata,
+                                             size_t calldata_len,
+                                             const StarknetFelt *deployer_address,
+                                             StarknetFelt *out);
+
+/* Transaction hashes */
+
+StarknetStatus starknet_invoke_transaction_hash(const StarknetFelt *sender_address,
+                                                const StarknetFelt *calldata,
+                                                size_t calldata_len,
+                                                const StarknetFelt *max_fee,
+                                                const StarknetFelt *chain_id,
+                                                const StarknetFelt *nonce,
+                                                StarknetFelt *out);
+
+StarknetStatus starknet_declare_transaction_hash(const StarknetFelt *sender_address,
+                                                 const StarknetFelt *class_hash,
+                                                 const StarknetFelt *max_fee,
+                                                 const StarknetFelt *chain_id,
+                                                 const StarknetFelt *nonce,
+                                                 StarknetFelt *out);
+
+StarknetStatus starknet_deploy_account_transaction_hash(const StarknetFelt *class_hash,
+                                                        const StarknetFelt *salt,
+                                                        const StarknetFelt *calldata,
+                                                        size_t calldata_len,
+                                                        const StarknetFelt *max_fee,
+                                                        const StarknetFelt *chain_id,
+                                                        const StarknetFelt *nonce,
+                                                        StarknetFelt *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* STARKNET_H */
@@ -0,0 +1,368 @@
+use crate::{read_felt, read_felts, write_result, StarknetFelt, StarknetStatus};
+
+use starknet_core::{
+    crypto::{compute_hash_on_elements, ecdsa_sign, ecdsa_verify, pedersen_hash, Signature},
+    utils::{get_contract_address, get_selector_from_name},
+};
+use starknet_crypto::{poseidon_hash, poseidon_hash_many, poseidon_hash_single};
+use starknet_curve::curve_params::BETA;
+use std::ffi::c_char;
+
+/// Computes the Pedersen hash of `a` and `b` into `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_pedersen_hash(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(pedersen_hash(&read_felt(a)?, &read_felt(b)?).into())
+    })
+}
+
+/// Computes the Pedersen hash chain of the `len` felts at `data`, followed by `len`, into `out`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` felts, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_compute_hash_on_elements(
+    data: *const StarknetFelt,
+    len: usize,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(compute_hash_on_elements(&read_felts(data, len)?).into())
+    })
+}
+
+/// Computes the Poseidon hash of `x` and `y` into `out`.
+///
+/// # Safety
+///
+/// `x` and `y` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_poseidon_hash(
+    x: *const StarknetFelt,
+    y: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(poseidon_hash(read_felt(x)?, read_felt(y)?).into())
+    })
+}
+
+/// Computes the Poseidon hash of the single felt `x` into `out`.
+///
+/// # Safety
+///
+/// `x` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_poseidon_hash_single(
+    x: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || Ok(poseidon_hash_single(read_felt(x)?).into()))
+}
+
+/// Computes the Poseidon hash of the `len` felts at `data` into `out`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` felts, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_poseidon_hash_many(
+    data: *const StarknetFelt,
+    len: usize,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(poseidon_hash_many(&read_felts(data, len)?).into())
+    })
+}
+
+/// Computes the public key of `private_key` into `out`.
+///
+/// # Safety
+///
+/// `private_key` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_get_public_key(
+    private_key: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(starknet_crypto::get_public_key(&read_felt(private_key)?).into())
+    })
+}
+
+/// Signs `message_hash` with `private_key`, writing the signature to `out_r` and `out_s`. The
+/// nonce is generated deterministically as specified by RFC 6979.
+///
+/// # Safety
+///
+/// `private_key` and `message_hash` must be null or valid for reads, and `out_r` and `out_s`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_sign(
+    private_key: *const StarknetFelt,
+    message_hash: *const StarknetFelt,
+    out_r: *mut StarknetFelt,
+    out_s: *mut StarknetFelt,
+) -> StarknetStatus {
+    if out_r.is_null() || out_s.is_null() {
+        return StarknetStatus::NullPointer;
+    }
+
+    let signature = match (read_felt(private_key), read_felt(message_hash)) {
+        (Ok(private_key), Ok(message_hash)) => match ecdsa_sign(&private_key, &message_hash) {
+            Ok(signature) => signature,
+            Err(_) => return StarknetStatus::InvalidMessageHash,
+        },
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    out_r.write(signature.r.into());
+    out_s.write(signature.s.into());
+
+    StarknetStatus::Ok
+}
+
+/// Verifies the signature `(r, s)` of `message_hash` by `public_key`, writing whether it's valid
+/// to `out`. Signatures with `r` or `s` out of range are reported as invalid.
+///
+/// # Safety
+///
+/// `public_key`, `message_hash`, `r` and `s` must be null or valid for reads, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_verify(
+    public_key: *const StarknetFelt,
+    message_hash: *const StarknetFelt,
+    r: *const StarknetFelt,
+    s: *const StarknetFelt,
+    out: *mut bool,
+) -> StarknetStatus {
+    write_result(out, || {
+        let public_key = read_felt(public_key)?;
+        let message_hash = read_felt(message_hash)?;
+        let signature = Signature {
+            r: read_felt(r)?,
+            s: read_felt(s)?,
+        };
+
+        // Verification expects the public key to be the x coordinate of a curve point
+        if (public_key * public_key * public_key + public_key + BETA)
+            .sqrt()
+            .is_none()
+        {
+            return Err(StarknetStatus::InvalidPublicKey);
+        }
+
+        match ecdsa_verify(&public_key, &message_hash, &signature) {
+            Ok(valid) => Ok(valid),
+            Err(starknet_core::crypto::EcdsaVerifyError::MessageHashOutOfRange) => {
+                Err(StarknetStatus::InvalidMessageHash)
+            }
+            Err(_) => Ok(false),
+        }
+    })
+}
+
+/// Computes the entry point selector of the NUL-terminated function name `name` into `out`.
+///
+/// # Safety
+///
+/// `name` must be null or a valid NUL-terminated string, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_get_selector_from_name(
+    name: *const c_char,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        get_selector_from_name(crate::felt::read_str(name)?)
+            .map(Into::into)
+            .map_err(|_| StarknetStatus::InvalidString)
+    })
+}
+
+/// Computes the address of the contract of class `class_hash` deployed by `deployer_address`
+/// with `salt` and the `calldata_len` felts at `calldata` as constructor calldata, into `out`.
+///
+/// # Safety
+///
+/// `salt`, `class_hash` and `deployer_address` must be null or valid for reads, `calldata` must
+/// be valid for reads of `calldata_len` felts, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_get_contract_address(
+    salt: *const StarknetFelt,
+    class_hash: *const StarknetFelt,
+    calldata: *const StarknetFelt,
+    calldata_len: usize,
+    deployer_address: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(get_contract_address(
+            read_felt(salt)?,
+            read_felt(class_hash)?,
+            &read_felts(calldata, calldata_len)?,
+            read_felt(deployer_address)?,
+        )
+        .into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FieldElement;
+
+    fn felt_from_hex(hex: &str) -> StarknetFelt {
+        FieldElement::from_hex_be(hex).unwrap().into()
+    }
+
+    #[test]
+    fn test_pedersen_hash() {
+        // Test case ported from `starknet-crypto`
+        let mut out = StarknetFelt::default();
+        unsafe {
+            assert_eq!(
+                starknet_pedersen_hash(
+                    &felt_from_hex(
+                        "03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb"
+                    ),
+                    &felt_from_hex(
+                        "0208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a"
+                    ),
+                    &mut out
+                ),
+                StarknetStatus::Ok
+            );
+        }
+        assert_eq!(
+            out,
+            felt_from_hex("030e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_hash() {
+        // Test cases ported from `starknet-crypto`
+        let x = felt_from_hex("0xb662f9017fa7956fd70e26129b1833e10ad000fd37b4d9f4e0ce6884b7bbe");
+        let y = felt_from_hex("0x1fe356bf76102cdae1bfbdc173602ead228b12904c00dad9cf16e035468bea");
+        let mut out = StarknetFelt::default();
+
+        unsafe {
+            assert_eq!(starknet_poseidon_hash(&x, &y, &mut out), StarknetStatus::Ok);
+            assert_eq!(
+                out,
+                felt_from_hex("0x75540825a6ecc5dc7d7c2f5f868164182742227f1367d66c43ee51ec7937a81")
+            );
+
+            assert_eq!(
+                starknet_poseidon_hash_single(&x, &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(
+                out,
+                felt_from_hex("0x4a907fe5242331ab653b3c04c51d90df9a1172805c5ca555b32ce588fd58fb0")
+            );
+
+            assert_eq!(
+                starknet_poseidon_hash_many([x, y].as_ptr(), 2, &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(
+                out,
+                felt_from_hex("0x2410cb746ec9c95631fc76f1afa650eed9fa2efc20632a42cdbe7d701ed0098")
+            );
+
+            // Empty inputs may be passed as null
+            assert_eq!(
+                starknet_poseidon_hash_many(std::ptr::null(), 0, &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(
+                out,
+                felt_from_hex("0x2272be0f580fd156823304800919530eaa97430e972d7213ee13f4fbf7a5dbc")
+            );
+
+            assert_eq!(
+                starknet_poseidon_hash(std::ptr::null(), &y, &mut out),
+                StarknetStatus::NullPointer
+            );
+            assert_eq!(
+                starknet_poseidon_hash_many(std::ptr::null(), 1, &mut out),
+                StarknetStatus::NullPointer
+            );
+        }
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let private_key = felt_from_hex("0x1");
+        let message_hash = felt_from_hex("0x1234");
+        let mut public_key = StarknetFelt::default();
+        let mut r = StarknetFelt::default();
+        let mut s = StarknetFelt::default();
+        let mut valid = false;
+
+        unsafe {
+            assert_eq!(
+                starknet_get_public_key(&private_key, &mut public_key),
+                StarknetStatus::Ok
+            );
+            assert_eq!(
+                starknet_sign(&private_key, &message_hash, &mut r, &mut s),
+                StarknetStatus::Ok
+            );
+
+            assert_eq!(
+                starknet_verify(&public_key, &message_hash, &r, &s, &mut valid),
+                StarknetStatus::Ok
+            );
+            assert!(valid);
+
+            assert_eq!(
+                starknet_verify(&public_key, &felt_from_hex("0x1"), &r, &s, &mut valid),
+                StarknetStatus::Ok
+            );
+            assert!(!valid);
+
+            // Public keys must be the x coordinate of a curve point
+            let mut status = StarknetStatus::Ok;
+            for x in 2u64.. {
+                let candidate: FieldElement = x.into();
+                if (candidate * candidate * candidate + candidate + BETA)
+                    .sqrt()
+                    .is_none()
+                {
+                    status = starknet_verify(&candidate.into(), &message_hash, &r, &s, &mut valid);
+                    break;
+                }
+            }
+            assert_eq!(status, StarknetStatus::InvalidPublicKey);
+        }
+    }
+
+    #[test]
+    fn test_get_selector_from_name() {
+        let mut out = StarknetFelt::default();
+        unsafe {
+            assert_eq!(
+                starknet_get_selector_from_name(c"execute".as_ptr(), &mut out),
+                StarknetStatus::Ok
+            );
+        }
+        assert_eq!(
+            out,
+            felt_from_hex("0240060cdb34fcc260f41eac7474ee1d7c80b7e3607daff9ac67c7ea2ebb1c44")
+        );
+    }
+}
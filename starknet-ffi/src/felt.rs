@@ -0,0 +1,254 @@
+use crate::{read_felt, write_result, StarknetFelt, StarknetStatus};
+
+use starknet_core::types::FieldElement;
+use std::ffi::{c_char, CStr};
+
+/// Parses a NUL-terminated `0x`-prefixed hex string into `out`.
+///
+/// # Safety
+///
+/// `hex` must be null or a valid NUL-terminated string, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_from_hex(
+    hex: *const c_char,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        let hex = read_str(hex)?;
+        FieldElement::from_hex_be(hex)
+            .map(Into::into)
+            .map_err(|_| StarknetStatus::InvalidString)
+    })
+}
+
+/// Parses a NUL-terminated decimal string into `out`.
+///
+/// # Safety
+///
+/// `dec` must be null or a valid NUL-terminated string, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_from_dec(
+    dec: *const c_char,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        let dec = read_str(dec)?;
+        FieldElement::from_dec_str(dec)
+            .map(Into::into)
+            .map_err(|_| StarknetStatus::InvalidString)
+    })
+}
+
+/// Writes the `0x`-prefixed hex representation of `felt` to `out` as a NUL-terminated string.
+/// Buffers of 67 bytes fit any felt.
+///
+/// # Safety
+///
+/// `felt` must be null or valid for reads, and `out` must be null or valid for writes of
+/// `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_to_hex(
+    felt: *const StarknetFelt,
+    out: *mut c_char,
+    out_len: usize,
+) -> StarknetStatus {
+    let felt = match read_felt(felt) {
+        Ok(felt) => felt,
+        Err(status) => return status,
+    };
+    if out.is_null() {
+        return StarknetStatus::NullPointer;
+    }
+
+    let hex = format!("{felt:#x}");
+    if hex.len() + 1 > out_len {
+        return StarknetStatus::BufferTooSmall;
+    }
+    std::ptr::copy_nonoverlapping(hex.as_ptr() as *const c_char, out, hex.len());
+    out.add(hex.len()).write(0);
+
+    StarknetStatus::Ok
+}
+
+/// Computes `a + b` into `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_add(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || Ok((read_felt(a)? + read_felt(b)?).into()))
+}
+
+/// Computes `a - b` into `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_sub(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || Ok((read_felt(a)? - read_felt(b)?).into()))
+}
+
+/// Computes `a * b` into `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_mul(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || Ok((read_felt(a)? * read_felt(b)?).into()))
+}
+
+/// Computes the field division `a / b` into `out`, i.e. `a` multiplied by the inverse of `b`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_div(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        let inverse = read_felt(b)?
+            .invert()
+            .ok_or(StarknetStatus::DivisionByZero)?;
+        Ok((read_felt(a)? * inverse).into())
+    })
+}
+
+/// Computes the integer division of `a` by `b`, rounded down, into `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid for reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_felt_floor_div(
+    a: *const StarknetFelt,
+    b: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        let b = read_felt(b)?;
+        if b == FieldElement::ZERO {
+            return Err(StarknetStatus::DivisionByZero);
+        }
+        Ok(read_felt(a)?.floor_div(b).into())
+    })
+}
+
+/// Reads a NUL-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid NUL-terminated string.
+pub(crate) unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, StarknetStatus> {
+    if ptr.is_null() {
+        return Err(StarknetStatus::NullPointer);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| StarknetStatus::InvalidString)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt(value: u64) -> StarknetFelt {
+        FieldElement::from(value).into()
+    }
+
+    #[test]
+    fn test_felt_math() {
+        let mut out = StarknetFelt::default();
+        unsafe {
+            assert_eq!(
+                starknet_felt_add(&felt(2), &felt(3), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, felt(5));
+
+            assert_eq!(
+                starknet_felt_sub(&felt(2), &felt(3), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, (-FieldElement::ONE).into());
+
+            assert_eq!(
+                starknet_felt_div(&felt(6), &felt(3), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, felt(2));
+
+            assert_eq!(
+                starknet_felt_floor_div(&felt(7), &felt(2), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, felt(3));
+
+            assert_eq!(
+                starknet_felt_div(&felt(6), &felt(0), &mut out),
+                StarknetStatus::DivisionByZero
+            );
+            assert_eq!(
+                starknet_felt_mul(&StarknetFelt { bytes: [0xff; 32] }, &felt(1), &mut out),
+                StarknetStatus::InvalidFelt
+            );
+            assert_eq!(
+                starknet_felt_mul(std::ptr::null(), &felt(1), &mut out),
+                StarknetStatus::NullPointer
+            );
+        }
+    }
+
+    #[test]
+    fn test_felt_strings() {
+        let mut out = StarknetFelt::default();
+        let mut buffer = [0 as c_char; 67];
+        unsafe {
+            assert_eq!(
+                starknet_felt_from_hex(c"0x1234".as_ptr(), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, felt(0x1234));
+
+            assert_eq!(
+                starknet_felt_from_dec(c"4660".as_ptr(), &mut out),
+                StarknetStatus::Ok
+            );
+            assert_eq!(out, felt(0x1234));
+
+            assert_eq!(
+                starknet_felt_from_hex(c"0xzz".as_ptr(), &mut out),
+                StarknetStatus::InvalidString
+            );
+
+            assert_eq!(
+                starknet_felt_to_hex(&out, buffer.as_mut_ptr(), buffer.len()),
+                StarknetStatus::Ok
+            );
+            assert_eq!(CStr::from_ptr(buffer.as_ptr()).to_str().unwrap(), "0x1234");
+            assert_eq!(
+                starknet_felt_to_hex(&out, buffer.as_mut_ptr(), 6),
+                StarknetStatus::BufferTooSmall
+            );
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! C ABI for the Starknet primitives implemented in `starknet-core`. See `include/starknet.h`
+//! for the C declarations.
+//!
+//! Field elements are passed as [StarknetFelt], holding 32 big-endian bytes. All functions
+//! return a [StarknetStatus], and only write to their output pointers on success.
+
+use starknet_core::types::FieldElement;
+
+mod crypto;
+pub use crypto::*;
+
+mod felt;
+pub use felt::*;
+
+mod transaction;
+pub use transaction::*;
+
+/// A field element as 32 big-endian bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StarknetFelt {
+    pub bytes: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarknetStatus {
+    Ok = 0,
+    NullPointer = 1,
+    /// The bytes of a felt are not lower than the field prime.
+    InvalidFelt = 2,
+    /// A string is not valid UTF-8, or doesn't represent the expected value.
+    InvalidString = 3,
+    BufferTooSmall = 4,
+    DivisionByZero = 5,
+    /// The message hash to sign is out of range.
+    InvalidMessageHash = 6,
+    /// The public key is not a valid curve point.
+    InvalidPublicKey = 7,
+}
+
+impl From<FieldElement> for StarknetFelt {
+    fn from(value: FieldElement) -> Self {
+        Self {
+            bytes: value.to_bytes_be(),
+        }
+    }
+}
+
+impl TryFrom<StarknetFelt> for FieldElement {
+    type Error = StarknetStatus;
+
+    fn try_from(value: StarknetFelt) -> Result<Self, Self::Error> {
+        FieldElement::from_bytes_be(&value.bytes).map_err(|_| StarknetStatus::InvalidFelt)
+    }
+}
+
+/// Reads the felt at `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for reads.
+pub(crate) unsafe fn read_felt(ptr: *const StarknetFelt) -> Result<FieldElement, StarknetStatus> {
+    match ptr.as_ref() {
+        Some(felt) => (*felt).try_into(),
+        None => Err(StarknetStatus::NullPointer),
+    }
+}
+
+/// Reads the `len` felts at `ptr`, which may only be null if `len` is zero.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for reads of `len` felts.
+pub(crate) unsafe fn read_felts(
+    ptr: *const StarknetFelt,
+    len: usize,
+) -> Result<Vec<FieldElement>, StarknetStatus> {
+    if len == 0 {
+        return Ok(vec![]);
+    }
+    if ptr.is_null() {
+        return Err(StarknetStatus::NullPointer);
+    }
+
+    std::slice::from_raw_parts(ptr, len)
+        .iter()
+        .map(|felt| (*felt).try_into())
+        .collect()
+}
+
+/// Runs `f` and writes its result to `out`, returning the status.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+pub(crate) unsafe fn write_result<T, F>(out: *mut T, f: F) -> StarknetStatus
+where
+    F: FnOnce() -> Result<T, StarknetStatus>,
+{
+    if out.is_null() {
+        return StarknetStatus::NullPointer;
+    }
+    match f() {
+        Ok(value) => {
+            out.write(value);
+            StarknetStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
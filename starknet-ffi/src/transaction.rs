@@ -0,0 +1,227 @@
+use crate::{read_felt, read_felts, write_result, StarknetFelt, StarknetStatus};
+
+use starknet_core::{
+    crypto::compute_hash_on_elements, types::FieldElement, utils::get_contract_address,
+};
+
+/// Cairo string for "invoke"
+const PREFIX_INVOKE: FieldElement = FieldElement::from_mont([
+    18443034532770911073,
+    18446744073709551615,
+    18446744073709551615,
+    513398556346534256,
+]);
+
+/// Cairo string for "declare"
+const PREFIX_DECLARE: FieldElement = FieldElement::from_mont([
+    17542456862011667323,
+    18446744073709551615,
+    18446744073709551615,
+    191557713328401194,
+]);
+
+/// Cairo string for "deploy_account"
+const PREFIX_DEPLOY_ACCOUNT: FieldElement = FieldElement::from_mont([
+    3350261884043292318,
+    18443211694809419988,
+    18446744073709551615,
+    461298303000467581,
+]);
+
+/// Computes the hash of the version 1 invoke transaction sent by `sender_address` with the
+/// `calldata_len` felts at `calldata` as calldata, into `out`.
+///
+/// # Safety
+///
+/// `sender_address`, `max_fee`, `chain_id` and `nonce` must be null or valid for reads,
+/// `calldata` must be valid for reads of `calldata_len` felts, and `out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_invoke_transaction_hash(
+    sender_address: *const StarknetFelt,
+    calldata: *const StarknetFelt,
+    calldata_len: usize,
+    max_fee: *const StarknetFelt,
+    chain_id: *const StarknetFelt,
+    nonce: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(compute_hash_on_elements(&[
+            PREFIX_INVOKE,
+            FieldElement::ONE, // version
+            read_felt(sender_address)?,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&read_felts(calldata, calldata_len)?),
+            read_felt(max_fee)?,
+            read_felt(chain_id)?,
+            read_felt(nonce)?,
+        ])
+        .into())
+    })
+}
+
+/// Computes the hash of the version 1 transaction declaring `class_hash` sent by
+/// `sender_address`, into `out`.
+///
+/// # Safety
+///
+/// `sender_address`, `class_hash`, `max_fee`, `chain_id` and `nonce` must be null or valid for
+/// reads, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_declare_transaction_hash(
+    sender_address: *const StarknetFelt,
+    class_hash: *const StarknetFelt,
+    max_fee: *const StarknetFelt,
+    chain_id: *const StarknetFelt,
+    nonce: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        Ok(compute_hash_on_elements(&[
+            PREFIX_DECLARE,
+            FieldElement::ONE, // version
+            read_felt(sender_address)?,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&[read_felt(class_hash)?]),
+            read_felt(max_fee)?,
+            read_felt(chain_id)?,
+            read_felt(nonce)?,
+        ])
+        .into())
+    })
+}
+
+/// Computes the hash of the version 1 transaction deploying an account of class `class_hash`
+/// with `salt` and the `calldata_len` felts at `calldata` as constructor calldata, into `out`.
+///
+/// # Safety
+///
+/// `class_hash`, `salt`, `max_fee`, `chain_id` and `nonce` must be null or valid for reads,
+/// `calldata` must be valid for reads of `calldata_len` felts, and `out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn starknet_deploy_account_transaction_hash(
+    class_hash: *const StarknetFelt,
+    salt: *const StarknetFelt,
+    calldata: *const StarknetFelt,
+    calldata_len: usize,
+    max_fee: *const StarknetFelt,
+    chain_id: *const StarknetFelt,
+    nonce: *const StarknetFelt,
+    out: *mut StarknetFelt,
+) -> StarknetStatus {
+    write_result(out, || {
+        let class_hash = read_felt(class_hash)?;
+        let salt = read_felt(salt)?;
+        let calldata = read_felts(calldata, calldata_len)?;
+
+        let mut calldata_to_hash = vec![class_hash, salt];
+        calldata_to_hash.extend_from_slice(&calldata);
+
+        Ok(compute_hash_on_elements(&[
+            PREFIX_DEPLOY_ACCOUNT,
+            FieldElement::ONE, // version
+            get_contract_address(salt, class_hash, &calldata, FieldElement::ZERO),
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&calldata_to_hash),
+            read_felt(max_fee)?,
+            read_felt(chain_id)?,
+            read_felt(nonce)?,
+        ])
+        .into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::{chain_id, types::Block};
+
+    fn felt_from_hex(hex: &str) -> StarknetFelt {
+        FieldElement::from_hex_be(hex).unwrap().into()
+    }
+
+    fn felts(values: &[FieldElement]) -> Vec<StarknetFelt> {
+        values.iter().map(|value| (*value).into()).collect()
+    }
+
+    #[test]
+    fn test_transaction_hashes() {
+        use starknet_core::types::TransactionType;
+
+        // Transactions from block 375919 of the Goerli testnet
+        let block: Block = serde_json::from_str(include_str!(
+            "../../starknet-core/test-data/raw_gateway_responses/get_block/14_deploy_account.txt"
+        ))
+        .unwrap();
+        let chain_id = chain_id::TESTNET.into();
+        let mut out = StarknetFelt::default();
+
+        let invoke = block
+            .transactions
+            .iter()
+            .find_map(|tx| match tx {
+                TransactionType::InvokeFunction(tx) if tx.nonce.is_some() => Some(tx),
+                _ => None,
+            })
+            .unwrap();
+        let calldata = felts(&invoke.calldata);
+        unsafe {
+            assert_eq!(
+                starknet_invoke_transaction_hash(
+                    &invoke.contract_address.into(),
+                    calldata.as_ptr(),
+                    calldata.len(),
+                    &invoke.max_fee.into(),
+                    &chain_id,
+                    &invoke.nonce.unwrap().into(),
+                    &mut out,
+                ),
+                StarknetStatus::Ok
+            );
+        }
+        assert_eq!(out, invoke.transaction_hash.into());
+
+        let deploy_account = block
+            .transactions
+            .iter()
+            .find_map(|tx| match tx {
+                TransactionType::DeployAccount(tx) => Some(tx),
+                _ => None,
+            })
+            .unwrap();
+        let calldata = felts(&deploy_account.constructor_calldata);
+        unsafe {
+            assert_eq!(
+                starknet_deploy_account_transaction_hash(
+                    &deploy_account.class_hash.into(),
+                    &deploy_account.contract_address_salt.into(),
+                    calldata.as_ptr(),
+                    calldata.len(),
+                    &deploy_account.max_fee.into(),
+                    &chain_id,
+                    &deploy_account.nonce.into(),
+                    &mut out,
+                ),
+                StarknetStatus::Ok
+            );
+        }
+        assert_eq!(out, deploy_account.transaction_hash.into());
+
+        unsafe {
+            assert_eq!(
+                starknet_declare_transaction_hash(
+                    &felt_from_hex("0x1"),
+                    std::ptr::null(),
+                    &felt_from_hex("0x0"),
+                    &chain_id,
+                    &felt_from_hex("0x0"),
+                    &mut out,
+                ),
+                StarknetStatus::NullPointer
+            );
+        }
+    }
+}
@@ -0,0 +1,686 @@
+use crate::{BlockSource, IngestedBlock};
+
+use starknet_core::types::{FieldElement, TransactionType};
+use std::{collections::HashMap, io::Write, ops::Range};
+
+/// A table of exported data. The columns of each table are stable, so that files exported by
+/// different versions can be loaded together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Table {
+    Blocks,
+    Transactions,
+    Receipts,
+    Events,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub name: &'static str,
+    pub kind: ColumnKind,
+    pub nullable: bool,
+}
+
+/// The type of the values of a column, for formats with typed schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Integer,
+    Felt,
+    FeltList,
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportValue {
+    Null,
+    Integer(u64),
+    Felt(FieldElement),
+    FeltList(Vec<FieldElement>),
+    Text(String),
+}
+
+/// Writes the rows of a [Table] to a file of some format.
+///
+/// Implemented by [CsvWriter], and by `ParquetWriter` behind the `parquet` feature. Other
+/// formats can be supported by implementing this trait on top of their writer, using
+/// [Table::columns] as the schema.
+pub trait TableWriter {
+    type Error: std::error::Error + Send;
+
+    /// Writes a row, with a value for each of the columns of the table.
+    fn write_row(&mut self, row: &[ExportValue]) -> Result<(), Self::Error>;
+
+    /// Flushes the rows written.
+    fn finish(&mut self) -> Result<(), Self::Error>;
+}
+
+/// A [TableWriter] writing CSV with a header row. Field elements are written in hex, and lists
+/// of them as comma-separated hex values. Null values are empty.
+#[derive(Debug)]
+pub struct CsvWriter<W> {
+    writer: W,
+    table: Table,
+    header_written: bool,
+}
+
+/// A writer for each [Table].
+#[derive(Debug)]
+pub struct ExportWriters<W> {
+    pub blocks: W,
+    pub transactions: W,
+    pub receipts: W,
+    pub events: W,
+}
+
+/// Exports blocks, with their transactions, receipts and events, from a [BlockSource] to the
+/// [Table]s of an [ExportWriters]:
+///
+/// ```ignore
+/// let exporter = Exporter::new(provider)
+///     .event_name(get_selector_from_name("Transfer")?, "Transfer");
+/// let mut writers = ExportWriters::csv_files("./export")?;
+/// exporter.export(375000..376000, &mut writers).await?;
+/// writers.finish()?;
+/// ```
+#[derive(Debug)]
+pub struct Exporter<S> {
+    source: S,
+    event_names: HashMap<FieldElement, String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError<S, W> {
+    #[error(transparent)]
+    Source(S),
+    #[error("writer error: {0}")]
+    Writer(W),
+    #[error("block {0} has no block hash")]
+    MissingBlockHash(u64),
+}
+
+const BLOCK_COLUMNS: &[Column] = &[
+    Column::new("number", ColumnKind::Integer),
+    Column::new("hash", ColumnKind::Felt),
+    Column::new("parent_hash", ColumnKind::Felt),
+    Column::new("timestamp", ColumnKind::Integer),
+    Column::new("sequencer_address", ColumnKind::Felt).nullable(),
+    Column::new("state_root", ColumnKind::Felt).nullable(),
+    Column::new("gas_price", ColumnKind::Felt),
+    Column::new("transaction_count", ColumnKind::Integer),
+    Column::new("starknet_version", ColumnKind::Text).nullable(),
+];
+
+const TRANSACTION_COLUMNS: &[Column] = &[
+    Column::new("hash", ColumnKind::Felt),
+    Column::new("block_number", ColumnKind::Integer),
+    Column::new("transaction_index", ColumnKind::Integer),
+    Column::new("type", ColumnKind::Text),
+    Column::new("contract_address", ColumnKind::Felt),
+    Column::new("entry_point_selector", ColumnKind::Felt).nullable(),
+    Column::new("class_hash", ColumnKind::Felt).nullable(),
+    Column::new("nonce", ColumnKind::Felt).nullable(),
+    Column::new("max_fee", ColumnKind::Felt).nullable(),
+    Column::new("version", ColumnKind::Felt),
+    Column::new("calldata", ColumnKind::FeltList),
+    Column::new("signature", ColumnKind::FeltList),
+];
+
+const RECEIPT_COLUMNS: &[Column] = &[
+    Column::new("transaction_hash", ColumnKind::Felt),
+    Column::new("block_number", ColumnKind::Integer),
+    Column::new("transaction_index", ColumnKind::Integer),
+    Column::new("actual_fee", ColumnKind::Felt),
+    Column::new("n_steps", ColumnKind::Integer).nullable(),
+    Column::new("event_count", ColumnKind::Integer),
+    Column::new("l2_to_l1_message_count", ColumnKind::Integer),
+];
+
+const EVENT_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnKind::Integer),
+    Column::new("event_index", ColumnKind::Integer),
+    Column::new("transaction_hash", ColumnKind::Felt),
+    Column::new("transaction_index", ColumnKind::Integer),
+    Column::new("from_address", ColumnKind::Felt),
+    Column::new("selector", ColumnKind::Felt).nullable(),
+    Column::new("name", ColumnKind::Text).nullable(),
+    Column::new("keys", ColumnKind::FeltList),
+    Column::new("data", ColumnKind::FeltList),
+];
+
+impl Table {
+    pub const ALL: [Table; 4] = [
+        Table::Blocks,
+        Table::Transactions,
+        Table::Receipts,
+        Table::Events,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Blocks => "blocks",
+            Table::Transactions => "transactions",
+            Table::Receipts => "receipts",
+            Table::Events => "events",
+        }
+    }
+
+    pub fn columns(&self) -> &'static [Column] {
+        match self {
+            Table::Blocks => BLOCK_COLUMNS,
+            Table::Transactions => TRANSACTION_COLUMNS,
+            Table::Receipts => RECEIPT_COLUMNS,
+            Table::Events => EVENT_COLUMNS,
+        }
+    }
+}
+
+impl Column {
+    const fn new(name: &'static str, kind: ColumnKind) -> Self {
+        Self {
+            name,
+            kind,
+            nullable: false,
+        }
+    }
+
+    const fn nullable(self) -> Self {
+        Self {
+            nullable: true,
+            ..self
+        }
+    }
+}
+
+impl<W> CsvWriter<W> {
+    pub fn new(writer: W, table: Table) -> Self {
+        Self {
+            writer,
+            table,
+            header_written: false,
+        }
+    }
+
+    pub fn table(&self) -> Table {
+        self.table
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> CsvWriter<W>
+where
+    W: Write,
+{
+    fn write_fields<I>(&mut self, fields: I) -> std::io::Result<()>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let line = fields
+            .into_iter()
+            .map(|field| csv_escape(&field))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(self.writer, "{line}")
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        if !self.header_written {
+            self.header_written = true;
+            let names = self.table.columns().iter().map(|column| column.name.into());
+            self.write_fields(names)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W> TableWriter for CsvWriter<W>
+where
+    W: Write,
+{
+    type Error = std::io::Error;
+
+    fn write_row(&mut self, row: &[ExportValue]) -> Result<(), Self::Error> {
+        self.write_header()?;
+        self.write_fields(row.iter().map(|value| {
+            match value {
+                ExportValue::Null => String::new(),
+                ExportValue::Integer(value) => value.to_string(),
+                ExportValue::Felt(value) => format!("{value:#x}"),
+                ExportValue::FeltList(values) => values
+                    .iter()
+                    .map(|value| format!("{value:#x}"))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                ExportValue::Text(value) => value.clone(),
+            }
+        }))
+    }
+
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        self.write_header()?;
+        self.writer.flush()
+    }
+}
+
+impl<W> ExportWriters<W> {
+    /// Creates the writer of each table with `f`.
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(Table) -> W,
+    {
+        Self {
+            blocks: f(Table::Blocks),
+            transactions: f(Table::Transactions),
+            receipts: f(Table::Receipts),
+            events: f(Table::Events),
+        }
+    }
+
+    pub fn get_mut(&mut self, table: Table) -> &mut W {
+        match table {
+            Table::Blocks => &mut self.blocks,
+            Table::Transactions => &mut self.transactions,
+            Table::Receipts => &mut self.receipts,
+            Table::Events => &mut self.events,
+        }
+    }
+}
+
+impl<W> ExportWriters<W>
+where
+    W: TableWriter,
+{
+    pub fn finish(&mut self) -> Result<(), W::Error> {
+        for table in Table::ALL {
+            self.get_mut(table).finish()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ExportWriters<CsvWriter<std::io::BufWriter<std::fs::File>>> {
+    /// CSV writers for files named after each table in `dir`, e.g. `blocks.csv`. Existing files
+    /// are overwritten.
+    pub fn csv_files(dir: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let open = |table: Table| {
+            let file = std::fs::File::create(dir.join(format!("{}.csv", table.name())))?;
+            Ok::<_, std::io::Error>(CsvWriter::new(std::io::BufWriter::new(file), table))
+        };
+        Ok(Self {
+            blocks: open(Table::Blocks)?,
+            transactions: open(Table::Transactions)?,
+            receipts: open(Table::Receipts)?,
+            events: open(Table::Events)?,
+        })
+    }
+}
+
+impl<S> Exporter<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            event_names: HashMap::new(),
+        }
+    }
+
+    /// Decodes events whose first key is `selector` as event `name` in the `name` column of the
+    /// events table.
+    pub fn event_name(mut self, selector: FieldElement, name: impl Into<String>) -> Self {
+        self.event_names.insert(selector, name.into());
+        self
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Writes the rows of `block` to `writers`.
+    pub fn write_block<W>(
+        &self,
+        block: &IngestedBlock,
+        writers: &mut ExportWriters<W>,
+    ) -> Result<(), W::Error>
+    where
+        W: TableWriter,
+    {
+        let block_number = block.block_number;
+        let inner = &block.block;
+
+        writers.blocks.write_row(&[
+            ExportValue::Integer(block_number),
+            ExportValue::Felt(block.block_hash),
+            ExportValue::Felt(inner.parent_block_hash),
+            ExportValue::Integer(inner.timestamp),
+            inner.sequencer_address.into(),
+            inner.state_root.into(),
+            ExportValue::Felt(inner.gas_price),
+            ExportValue::Integer(inner.transactions.len() as u64),
+            inner.starknet_version.clone().into(),
+        ])?;
+
+        for (transaction_index, transaction) in inner.transactions.iter().enumerate() {
+            let mut row = vec![
+                ExportValue::Felt(transaction_hash(transaction)),
+                ExportValue::Integer(block_number),
+                ExportValue::Integer(transaction_index as u64),
+            ];
+            row.extend(transaction_values(transaction));
+            writers.transactions.write_row(&row)?;
+        }
+
+        for receipt in inner.transaction_receipts.iter() {
+            writers.receipts.write_row(&[
+                ExportValue::Felt(receipt.transaction_hash),
+                ExportValue::Integer(block_number),
+                ExportValue::Integer(receipt.transaction_index),
+                ExportValue::Felt(receipt.actual_fee),
+                receipt
+                    .execution_resources
+                    .as_ref()
+                    .map(|resources| resources.n_steps)
+                    .into(),
+                ExportValue::Integer(receipt.events.len() as u64),
+                ExportValue::Integer(receipt.l2_to_l1_messages.len() as u64),
+            ])?;
+        }
+
+        for event in block.events() {
+            let selector = event.event.keys.first().copied();
+            writers.events.write_row(&[
+                ExportValue::Integer(block_number),
+                ExportValue::Integer(event.event_index),
+                ExportValue::Felt(event.transaction_hash),
+                ExportValue::Integer(event.transaction_index),
+                ExportValue::Felt(event.event.from_address),
+                selector.into(),
+                selector
+                    .and_then(|selector| self.event_names.get(&selector).cloned())
+                    .into(),
+                ExportValue::FeltList(event.event.keys.clone()),
+                ExportValue::FeltList(event.event.data.clone()),
+            ])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> Exporter<S>
+where
+    S: BlockSource + Sync,
+{
+    /// Exports the blocks in `blocks`, stopping early at the chain tip, and returns the number
+    /// of blocks exported. Blocks are fetched and written one at a time.
+    pub async fn export<W>(
+        &self,
+        blocks: Range<u64>,
+        writers: &mut ExportWriters<W>,
+    ) -> Result<u64, ExportError<S::Error, W::Error>>
+    where
+        W: TableWriter,
+    {
+        let mut exported = 0;
+        for block_number in blocks {
+            let block = match self
+                .source
+                .block(block_number)
+                .await
+                .map_err(ExportError::Source)?
+            {
+                Some(block) => block,
+                None => break,
+            };
+            let block = IngestedBlock {
+                block_number,
+                block_hash: block
+                    .block_hash
+                    .ok_or(ExportError::MissingBlockHash(block_number))?,
+                block,
+            };
+
+            self.write_block(&block, writers)
+                .map_err(ExportError::Writer)?;
+            exported += 1;
+        }
+
+        Ok(exported)
+    }
+}
+
+impl From<Option<u64>> for ExportValue {
+    fn from(value: Option<u64>) -> Self {
+        value.map_or(ExportValue::Null, ExportValue::Integer)
+    }
+}
+
+impl From<Option<FieldElement>> for ExportValue {
+    fn from(value: Option<FieldElement>) -> Self {
+        value.map_or(ExportValue::Null, ExportValue::Felt)
+    }
+}
+
+impl From<Option<String>> for ExportValue {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(ExportValue::Null, ExportValue::Text)
+    }
+}
+
+fn transaction_hash(transaction: &TransactionType) -> FieldElement {
+    match transaction {
+        TransactionType::Declare(tx) => tx.transaction_hash,
+        TransactionType::Deploy(tx) => tx.transaction_hash,
+        TransactionType::DeployAccount(tx) => tx.transaction_hash,
+        TransactionType::InvokeFunction(tx) => tx.transaction_hash,
+        TransactionType::L1Handler(tx) => tx.transaction_hash,
+    }
+}
+
+/// Values of the transaction columns from `type` onwards.
+fn transaction_values(transaction: &TransactionType) -> [ExportValue; 9] {
+    use ExportValue::{Felt, FeltList, Null, Text};
+
+    match transaction {
+        TransactionType::Declare(tx) => [
+            Text("DECLARE".into()),
+            Felt(tx.sender_address),
+            Null,
+            Felt(tx.class_hash),
+            Felt(tx.nonce),
+            Felt(tx.max_fee),
+            Felt(tx.version),
+            FeltList(vec![]),
+            FeltList(tx.signature.clone()),
+        ],
+        TransactionType::Deploy(tx) => [
+            Text("DEPLOY".into()),
+            Felt(tx.contract_address),
+            Null,
+            Felt(tx.class_hash),
+            Null,
+            Null,
+            Felt(tx.version),
+            FeltList(tx.constructor_calldata.clone()),
+            FeltList(vec![]),
+        ],
+        TransactionType::DeployAccount(tx) => [
+            Text("DEPLOY_ACCOUNT".into()),
+            Felt(tx.contract_address),
+            Null,
+            Felt(tx.class_hash),
+            Felt(tx.nonce),
+            Felt(tx.max_fee),
+            Felt(tx.version),
+            FeltList(tx.constructor_calldata.clone()),
+            FeltList(tx.signature.clone()),
+        ],
+        TransactionType::InvokeFunction(tx) => [
+            Text("INVOKE_FUNCTION".into()),
+            Felt(tx.contract_address),
+            tx.entry_point_selector.into(),
+            Null,
+            tx.nonce.into(),
+            Felt(tx.max_fee),
+            Felt(tx.version),
+            FeltList(tx.calldata.clone()),
+            FeltList(tx.signature.clone()),
+        ],
+        TransactionType::L1Handler(tx) => [
+            Text("L1_HANDLER".into()),
+            Felt(tx.contract_address),
+            Felt(tx.entry_point_selector),
+            Null,
+            tx.nonce.into(),
+            Null,
+            Felt(tx.version),
+            FeltList(tx.calldata.clone()),
+            FeltList(vec![]),
+        ],
+    }
+}
+
+/// Quotes `field` if it contains a separator, quote or line break.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use starknet_core::types::Block;
+    use std::convert::Infallible;
+
+    /// A chain made of a single fixture block.
+    struct FixtureSource;
+
+    #[async_trait]
+    impl BlockSource for FixtureSource {
+        type Error = Infallible;
+
+        async fn block(&self, block_number: u64) -> Result<Option<Block>, Self::Error> {
+            let block: Block = serde_json::from_str(include_str!(
+                "../../starknet-core/test-data/raw_gateway_responses/get_block/14_deploy_account.txt"
+            ))
+            .unwrap();
+            Ok((block.block_number == Some(block_number)).then_some(block))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_csv() {
+        let exporter = Exporter::new(FixtureSource).event_name(
+            starknet_core::utils::get_selector_from_name("Transfer").unwrap(),
+            "Transfer",
+        );
+        let mut writers = ExportWriters::from_fn(|table| CsvWriter::new(vec![], table));
+
+        let exported = exporter.export(375919..376000, &mut writers).await.unwrap();
+        writers.finish().unwrap();
+        assert_eq!(exported, 1);
+
+        let csv = |writer: CsvWriter<Vec<u8>>| String::from_utf8(writer.into_inner()).unwrap();
+        let ExportWriters {
+            blocks,
+            transactions,
+            receipts,
+            events,
+        } = writers;
+
+        let blocks = csv(blocks);
+        let mut lines = blocks.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "number,hash,parent_hash,timestamp,sequencer_address,state_root,gas_price,\
+            transaction_count,starknet_version"
+        );
+        assert!(lines.next().unwrap().starts_with(
+            "375919,0x5a8c7eb8b8fbe5c6ee76591fe093fe906d93805e9cf1a82d55410b17977e373,"
+        ));
+        assert!(lines.next().is_none());
+
+        let transactions = csv(transactions);
+        let receipts = csv(receipts);
+        assert_eq!(transactions.lines().count(), receipts.lines().count());
+        assert!(transactions.lines().count() > 1);
+
+        let events = csv(events);
+        assert!(events
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with("block_number,event_index,"));
+        assert!(events
+            .lines()
+            .skip(1)
+            .all(|line| line.starts_with("375919,")));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn test_export_parquet() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let exporter = Exporter::new(FixtureSource);
+        let mut csv_writers = ExportWriters::from_fn(|table| CsvWriter::new(vec![], table));
+        let mut parquet_writers =
+            ExportWriters::from_fn(|table| crate::ParquetWriter::new(vec![], table).unwrap());
+
+        exporter
+            .export(375919..376000, &mut csv_writers)
+            .await
+            .unwrap();
+        exporter
+            .export(375919..376000, &mut parquet_writers)
+            .await
+            .unwrap();
+        csv_writers.finish().unwrap();
+        parquet_writers.finish().unwrap();
+
+        for table in Table::ALL {
+            let csv_rows = std::str::from_utf8(csv_writers.get_mut(table).writer.as_slice())
+                .unwrap()
+                .lines()
+                .count()
+                - 1;
+            let reader = SerializedFileReader::new(bytes::Bytes::from(
+                parquet_writers.get_mut(table).inner().clone(),
+            ))
+            .unwrap();
+            assert_eq!(
+                reader.metadata().file_metadata().num_rows(),
+                csv_rows as i64
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_csv_values() {
+        let mut writer = CsvWriter::new(vec![], Table::Events);
+        writer
+            .write_row(&[
+                ExportValue::Integer(1),
+                ExportValue::Felt(FieldElement::from(255u64)),
+                ExportValue::Null,
+                ExportValue::FeltList(vec![FieldElement::ONE, FieldElement::TWO]),
+                ExportValue::Text("say \"hi\"".into()),
+            ])
+            .unwrap();
+
+        let csv = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "1,0xff,,\"0x1,0x2\",\"say \"\"hi\"\"\""
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{BlockEvent, BlockIngestor, BlockSource, BlockUpdate, IngestError, Sink};
+
+use async_trait::async_trait;
+use starknet_core::types::{Event, FieldElement};
+use std::future::Future;
+
+/// Errors returned by [EventHandler]s.
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Handles the events matching the [EventFilter] it's registered with on an [Indexer].
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait EventHandler {
+    async fn handle_event(&mut self, event: &BlockEvent<'_>) -> Result<(), HandlerError>;
+
+    /// Called after a reorg, when the events handled from block `block_number` onwards are no
+    /// longer part of the chain and must be reverted.
+    async fn rollback(&mut self, block_number: u64) -> Result<(), HandlerError>;
+}
+
+/// Matches events by emitting contract and selector, i.e. their first key.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    from_address: Option<FieldElement>,
+    selector: Option<FieldElement>,
+}
+
+/// Indexes the blocks of a chain into a [Sink], dispatching their events to the registered
+/// handlers:
+///
+/// ```ignore
+/// let mut indexer = Indexer::new(provider, MemorySink::new())
+///     .from_block(375000)
+///     .register(
+///         EventFilter::new()
+///             .from_address(token)
+///             .selector(get_selector_from_name("Transfer")?),
+///         TransferHandler::default(),
+///     );
+/// indexer.run(|| tokio::time::sleep(Duration::from_secs(10))).await?;
+/// ```
+///
+/// Handlers are called for each block before it's written to the sink, which indexing resumes
+/// from, so they see each event at least once.
+pub struct Indexer<S, K> {
+    ingestor: BlockIngestor<S>,
+    sink: K,
+    handlers: Vec<(EventFilter, Box<dyn EventHandler + Send>)>,
+    reorg_depth: usize,
+    resumed: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError<S, K> {
+    #[error(transparent)]
+    Ingest(IngestError<S>),
+    #[error("sink error: {0}")]
+    Sink(K),
+    #[error("event handler error: {0}")]
+    Handler(HandlerError),
+}
+
+impl EventFilter {
+    /// A filter matching all events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_address(self, from_address: FieldElement) -> Self {
+        Self {
+            from_address: Some(from_address),
+            ..self
+        }
+    }
+
+    pub fn selector(self, selector: FieldElement) -> Self {
+        Self {
+            selector: Some(selector),
+            ..self
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        self.from_address
+            .is_none_or(|from_address| event.from_address == from_address)
+            && self
+                .selector
+                .is_none_or(|selector| event.keys.first() == Some(&selector))
+    }
+}
+
+impl<S, K> Indexer<S, K> {
+    pub fn new(source: S, sink: K) -> Self {
+        let ingestor = BlockIngestor::new(source);
+        Self {
+            reorg_depth: 64,
+            ingestor,
+            sink,
+            handlers: vec![],
+            resumed: false,
+        }
+    }
+
+    /// Sets the first block to index, when the sink has no block yet. Defaults to the genesis
+    /// block.
+    pub fn from_block(self, from_block: u64) -> Self {
+        Self {
+            ingestor: self.ingestor.from_block(from_block),
+            ..self
+        }
+    }
+
+    /// Sets the number of recent blocks kept for handling reorgs. Defaults to `64`.
+    pub fn reorg_depth(self, reorg_depth: usize) -> Self {
+        Self {
+            ingestor: self.ingestor.reorg_depth(reorg_depth),
+            reorg_depth,
+            ..self
+        }
+    }
+
+    /// Registers `handler` for the events matching `filter`. Handlers are called in the order
+    /// they're registered.
+    pub fn register<H>(mut self, filter: EventFilter, handler: H) -> Self
+    where
+        H: EventHandler + Send + 'static,
+    {
+        self.handlers.push((filter, Box::new(handler)));
+        self
+    }
+
+    pub fn ingestor(&self) -> &BlockIngestor<S> {
+        &self.ingestor
+    }
+
+    pub fn sink(&self) -> &K {
+        &self.sink
+    }
+
+    pub fn into_sink(self) -> K {
+        self.sink
+    }
+}
+
+impl<S, K> Indexer<S, K>
+where
+    S: BlockSource + Sync,
+    K: Sink + Send,
+{
+    /// Indexes the blocks up to the chain tip, and returns the number of blocks indexed.
+    ///
+    /// Indexing resumes after the last block written to the sink on the first poll.
+    pub async fn poll(&mut self) -> Result<usize, IndexerError<S::Error, K::Error>> {
+        if !self.resumed {
+            let recent = self
+                .sink
+                .recent_blocks(self.reorg_depth)
+                .await
+                .map_err(IndexerError::Sink)?;
+            if !recent.is_empty() {
+                self.ingestor.resume(recent);
+            }
+            self.resumed = true;
+        }
+
+        let mut indexed = 0;
+        while let Some(update) = self.ingestor.next().await.map_err(IndexerError::Ingest)? {
+            match update {
+                BlockUpdate::Block(block) => {
+                    for event in block.events() {
+                        for (filter, handler) in self.handlers.iter_mut() {
+                            if filter.matches(event.event) {
+                                handler
+                                    .handle_event(&event)
+                                    .await
+                                    .map_err(IndexerError::Handler)?;
+                            }
+                        }
+                    }
+                    self.sink
+                        .write_block(&block)
+                        .await
+                        .map_err(IndexerError::Sink)?;
+                    indexed += 1;
+                }
+                BlockUpdate::Reorg { block_number } => {
+                    for (_, handler) in self.handlers.iter_mut() {
+                        handler
+                            .rollback(block_number)
+                            .await
+                            .map_err(IndexerError::Handler)?;
+                    }
+                    self.sink
+                        .rollback(block_number)
+                        .await
+                        .map_err(IndexerError::Sink)?;
+                }
+            }
+        }
+
+        Ok(indexed)
+    }
+
+    /// Keeps indexing new blocks, calling `sleep` between polls, until an error occurs.
+    pub async fn run<F, Fut>(
+        &mut self,
+        mut sleep: F,
+    ) -> Result<(), IndexerError<S::Error, K::Error>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            self.poll().await?;
+            sleep().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MemorySink;
+    use serde_json::json;
+    use starknet_core::types::Block;
+    use std::{
+        collections::HashMap,
+        convert::Infallible,
+        sync::{Arc, Mutex},
+    };
+
+    /// A chain of `len` blocks, each with a `Transfer` event from contract `0x1` and an
+    /// `Approval` event from contract `0x2`. Blocks are identified by their number and revision,
+    /// so that they can be replaced.
+    #[derive(Debug, Default)]
+    struct MockChain {
+        len: Mutex<u64>,
+        revisions: Mutex<HashMap<u64, u64>>,
+    }
+
+    impl MockChain {
+        fn block_hash(&self, block_number: u64) -> FieldElement {
+            let revision = self
+                .revisions
+                .lock()
+                .unwrap()
+                .get(&block_number)
+                .copied()
+                .unwrap_or_default();
+            FieldElement::from(block_number * 100 + revision)
+        }
+    }
+
+    #[async_trait]
+    impl BlockSource for MockChain {
+        type Error = Infallible;
+
+        async fn block(&self, block_number: u64) -> Result<Option<Block>, Self::Error> {
+            if block_number >= *self.len.lock().unwrap() {
+                return Ok(None);
+            }
+
+            let parent_block_hash = match block_number {
+                0 => FieldElement::ZERO,
+                _ => self.block_hash(block_number - 1),
+            };
+            let block = json!({
+                "block_hash": format!("{:#x}", self.block_hash(block_number)),
+                "block_number": block_number,
+                "parent_block_hash": format!("{:#x}", parent_block_hash),
+                "timestamp": block_number,
+                "status": "ACCEPTED_ON_L2",
+                "gas_price": "0x1",
+                "transactions": [],
+                "transaction_receipts": [{
+                    "transaction_hash": format!("{:#x}", block_number + 1000),
+                    "transaction_index": 0,
+                    "l1_to_l2_consumed_message": null,
+                    "l2_to_l1_messages": [],
+                    "events": [
+                        { "from_address": "0x1", "keys": ["0x10"], "data": [block_number.to_string()] },
+                        { "from_address": "0x2", "keys": ["0x20"], "data": [] },
+                    ],
+                    "actual_fee": "0x0",
+                }],
+                "starknet_version": null,
+            });
+            Ok(Some(serde_json::from_value(block).unwrap()))
+        }
+    }
+
+    /// Records the blocks of handled events, removing them on rollbacks.
+    #[derive(Debug, Default, Clone)]
+    struct RecordingHandler {
+        handled: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn handle_event(&mut self, event: &BlockEvent<'_>) -> Result<(), HandlerError> {
+            assert_eq!(
+                event.event.data,
+                vec![FieldElement::from(event.block_number)]
+            );
+            self.handled.lock().unwrap().push(event.block_number);
+            Ok(())
+        }
+
+        async fn rollback(&mut self, block_number: u64) -> Result<(), HandlerError> {
+            self.handled
+                .lock()
+                .unwrap()
+                .retain(|handled| *handled < block_number);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_with_reorg() {
+        let chain = Arc::new(MockChain::default());
+        *chain.len.lock().unwrap() = 5;
+
+        let handler = RecordingHandler::default();
+        let mut indexer = Indexer::new(ArcChain(chain.clone()), MemorySink::new())
+            .from_block(1)
+            .register(
+                EventFilter::new()
+                    .from_address(FieldElement::ONE)
+                    .selector(FieldElement::from(16u64)),
+                handler.clone(),
+            );
+
+        assert_eq!(indexer.poll().await.unwrap(), 4);
+        assert_eq!(*handler.handled.lock().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(indexer.poll().await.unwrap(), 0);
+
+        // Blocks 3 and 4 are replaced, and block 5 is added
+        chain.revisions.lock().unwrap().extend([(3, 1), (4, 1)]);
+        *chain.len.lock().unwrap() = 6;
+
+        assert_eq!(indexer.poll().await.unwrap(), 3);
+        assert_eq!(*handler.handled.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+
+        let blocks = indexer.sink().blocks();
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| block.block_hash)
+                .collect::<Vec<_>>(),
+            [100u64, 200, 301, 401, 500]
+                .into_iter()
+                .map(FieldElement::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(blocks[0].events.len(), 2);
+
+        // Indexing resumes from the sink
+        let mut resumed = Indexer::new(ArcChain(chain.clone()), indexer.into_sink());
+        *chain.len.lock().unwrap() = 7;
+        assert_eq!(resumed.poll().await.unwrap(), 1);
+        assert_eq!(resumed.sink().blocks().last().unwrap().block_number, 6);
+    }
+
+    #[derive(Debug)]
+    struct ArcChain(Arc<MockChain>);
+
+    #[async_trait]
+    impl BlockSource for ArcChain {
+        type Error = Infallible;
+
+        async fn block(&self, block_number: u64) -> Result<Option<Block>, Self::Error> {
+            self.0.block(block_number).await
+        }
+    }
+}
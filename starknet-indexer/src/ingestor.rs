@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use starknet_core::types::{Block, BlockId, Event, FieldElement, StarknetError};
+use starknet_providers::{Provider, ProviderError};
+use std::collections::VecDeque;
+
+const DEFAULT_REORG_DEPTH: usize = 64;
+
+/// Source of the blocks of a chain, with their transactions and receipts.
+///
+/// Implemented for any [Provider].
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait BlockSource {
+    type Error: std::error::Error + Send;
+
+    /// Fetches block `block_number`, or `None` if it doesn't exist yet.
+    async fn block(&self, block_number: u64) -> Result<Option<Block>, Self::Error>;
+}
+
+/// The number and hash of a block, identifying it among the blocks of any chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRef {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+}
+
+/// A block of the chain followed by a [BlockIngestor].
+#[derive(Debug)]
+pub struct IngestedBlock {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+    pub block: Block,
+}
+
+/// An event emitted in an [IngestedBlock].
+#[derive(Debug, Clone, Copy)]
+pub struct BlockEvent<'b> {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+    pub transaction_hash: FieldElement,
+    pub transaction_index: u64,
+    /// The index of the event among all events of the block.
+    pub event_index: u64,
+    pub event: &'b Event,
+}
+
+#[derive(Debug)]
+pub enum BlockUpdate {
+    /// A new block extending the chain.
+    Block(Box<IngestedBlock>),
+    /// The chain was reorganized, and blocks from `block_number` onwards are no longer part of
+    /// it. They're ingested again as they're included in the new chain.
+    Reorg { block_number: u64 },
+}
+
+/// Streams the blocks of a chain, detecting reorgs from the parent hashes of new blocks.
+///
+/// The hashes of the last [reorg_depth](BlockIngestor::reorg_depth) blocks are kept to find
+/// where the new chain forks from.
+#[derive(Debug)]
+pub struct BlockIngestor<S> {
+    source: S,
+    next_block: u64,
+    recent: VecDeque<BlockRef>,
+    reorg_depth: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError<E> {
+    #[error(transparent)]
+    Source(E),
+    #[error("block {0} has no block hash")]
+    MissingBlockHash(u64),
+    #[error("reorg beyond block {0}")]
+    ReorgTooDeep(u64),
+}
+
+impl<S> BlockIngestor<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            next_block: 0,
+            recent: VecDeque::new(),
+            reorg_depth: DEFAULT_REORG_DEPTH,
+        }
+    }
+
+    /// Sets the first block to ingest. Defaults to the genesis block.
+    pub fn from_block(self, from_block: u64) -> Self {
+        Self {
+            next_block: from_block,
+            ..self
+        }
+    }
+
+    /// Sets the number of recent blocks kept for handling reorgs. Defaults to `64`.
+    pub fn reorg_depth(self, reorg_depth: usize) -> Self {
+        Self {
+            reorg_depth: reorg_depth.max(1),
+            ..self
+        }
+    }
+
+    /// Resumes after `recent`, the last blocks previously ingested in ascending order.
+    pub fn resume(&mut self, recent: Vec<BlockRef>) {
+        if let Some(head) = recent.last() {
+            self.next_block = head.block_number + 1;
+        }
+        self.recent = recent.into();
+        while self.recent.len() > self.reorg_depth {
+            self.recent.pop_front();
+        }
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The last block ingested.
+    pub fn head(&self) -> Option<&BlockRef> {
+        self.recent.back()
+    }
+
+    /// The number of the next block to ingest.
+    pub fn next_block(&self) -> u64 {
+        self.next_block
+    }
+}
+
+impl<S> BlockIngestor<S>
+where
+    S: BlockSource + Sync,
+{
+    /// Fetches the next block, or returns `None` if the chain has no new block yet.
+    ///
+    /// When the next block doesn't extend the last block ingested, the last block is reverted
+    /// instead, until the chain is followed from the block it forks from.
+    pub async fn next(&mut self) -> Result<Option<BlockUpdate>, IngestError<S::Error>> {
+        let block_number = self.next_block;
+        let block = match self
+            .source
+            .block(block_number)
+            .await
+            .map_err(IngestError::Source)?
+        {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let block_hash = block
+            .block_hash
+            .ok_or(IngestError::MissingBlockHash(block_number))?;
+
+        if let Some(head) = self.recent.back() {
+            if block.parent_block_hash != head.block_hash {
+                if self.recent.len() == 1 {
+                    return Err(IngestError::ReorgTooDeep(head.block_number));
+                }
+
+                let reverted = self.recent.pop_back().expect("recent blocks are not empty");
+                self.next_block = reverted.block_number;
+                return Ok(Some(BlockUpdate::Reorg {
+                    block_number: reverted.block_number,
+                }));
+            }
+        }
+
+        self.recent.push_back(BlockRef {
+            block_number,
+            block_hash,
+        });
+        while self.recent.len() > self.reorg_depth {
+            self.recent.pop_front();
+        }
+        self.next_block += 1;
+
+        Ok(Some(BlockUpdate::Block(Box::new(IngestedBlock {
+            block_number,
+            block_hash,
+            block,
+        }))))
+    }
+}
+
+impl IngestedBlock {
+    pub fn block_ref(&self) -> BlockRef {
+        BlockRef {
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+        }
+    }
+
+    /// The events emitted in the block, in order.
+    pub fn events(&self) -> impl Iterator<Item = BlockEvent<'_>> {
+        self.block
+            .transaction_receipts
+            .iter()
+            .flat_map(|receipt| {
+                receipt
+                    .events
+                    .iter()
+                    .map(move |event| (receipt.transaction_hash, receipt.transaction_index, event))
+            })
+            .enumerate()
+            .map(
+                |(event_index, (transaction_hash, transaction_index, event))| BlockEvent {
+                    block_number: self.block_number,
+                    block_hash: self.block_hash,
+                    transaction_hash,
+                    transaction_index,
+                    event_index: event_index as u64,
+                    event,
+                },
+            )
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P> BlockSource for P
+where
+    P: Provider + Sync,
+{
+    type Error = ProviderError<P::Error>;
+
+    async fn block(&self, block_number: u64) -> Result<Option<Block>, Self::Error> {
+        match self.get_block(BlockId::Number(block_number)).await {
+            Ok(block) => Ok(Some(block)),
+            Err(ProviderError::StarknetError(StarknetError::BlockNotFound)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
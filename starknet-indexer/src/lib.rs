@@ -0,0 +1,24 @@
+mod ingestor;
+pub use ingestor::{
+    BlockEvent, BlockIngestor, BlockRef, BlockSource, BlockUpdate, IngestError, IngestedBlock,
+};
+
+mod sink;
+pub use sink::{MemorySink, Sink, StoredBlock, StoredEvent};
+
+mod sql;
+pub use sql::{SqlDialect, SqlExecutor, SqlSink, SqlSinkError, SqlValue};
+
+mod export;
+pub use export::{
+    Column, ColumnKind, CsvWriter, ExportError, ExportValue, ExportWriters, Exporter, Table,
+    TableWriter,
+};
+
+#[cfg(feature = "parquet")]
+mod parquet_writer;
+#[cfg(feature = "parquet")]
+pub use parquet_writer::{ParquetWriter, ParquetWriterError};
+
+mod indexer;
+pub use indexer::{EventFilter, EventHandler, HandlerError, Indexer, IndexerError};
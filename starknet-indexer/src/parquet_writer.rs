@@ -0,0 +1,440 @@
+use crate::{ColumnKind, ExportValue, ExportWriters, Table, TableWriter};
+
+use parquet::{
+    basic::{IntType, LogicalType, Repetition, Type as PhysicalType},
+    data_type::{ByteArray, ByteArrayType, FixedLenByteArray, FixedLenByteArrayType, Int64Type},
+    errors::ParquetError,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::types::{Type, TypePtr},
+};
+use std::{io::Write, sync::Arc};
+
+/// Rows buffered before being written out as a row group.
+const ROW_GROUP_SIZE: usize = 8192;
+
+/// A [TableWriter] writing Parquet, with a schema derived from [Table::columns]:
+///
+/// - integers are `INT64` annotated as unsigned;
+/// - field elements are 32-byte big-endian `FIXED_LEN_BYTE_ARRAY`s;
+/// - lists of field elements are `LIST`s of them;
+/// - text is UTF-8 `BYTE_ARRAY`.
+///
+/// Rows are written in row groups of up to 8192 rows. The file footer is written by
+/// [finish](TableWriter::finish), after which no more rows can be written.
+pub struct ParquetWriter<W>
+where
+    W: Write + Send,
+{
+    writer: SerializedFileWriter<W>,
+    table: Table,
+    rows: Vec<Vec<ExportValue>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParquetWriterError {
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error(transparent)]
+    Parquet(ParquetError),
+    #[error("expected {expected} values per row, got {actual}")]
+    RowLength { expected: usize, actual: usize },
+    #[error("invalid value for column {0}")]
+    InvalidValue(&'static str),
+}
+
+impl<W> ParquetWriter<W>
+where
+    W: Write + Send,
+{
+    pub fn new(writer: W, table: Table) -> Result<Self, ParquetWriterError> {
+        let writer = SerializedFileWriter::new(
+            writer,
+            table_schema(table)?,
+            Arc::new(WriterProperties::default()),
+        )
+        .map_err(ParquetWriterError::Parquet)?;
+
+        Ok(Self {
+            writer,
+            table,
+            rows: vec![],
+        })
+    }
+
+    pub fn table(&self) -> Table {
+        self.table
+    }
+
+    pub fn inner(&self) -> &W {
+        self.writer.inner()
+    }
+
+    fn flush_rows(&mut self) -> Result<(), ParquetWriterError> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut row_group = self
+            .writer
+            .next_row_group()
+            .map_err(ParquetWriterError::Parquet)?;
+        for (index, column) in self.table.columns().iter().enumerate() {
+            let mut column_writer = row_group
+                .next_column()
+                .map_err(ParquetWriterError::Parquet)?
+                .ok_or(ParquetWriterError::InvalidValue(column.name))?;
+            let values = self.rows.iter().map(|row| &row[index]);
+            let invalid = || ParquetWriterError::InvalidValue(column.name);
+
+            // Definition levels are only needed for nullable columns and lists
+            let mut def_levels = vec![];
+            let mut rep_levels = vec![];
+            let null_level = 0;
+            let present_level = i16::from(column.nullable);
+
+            let result = match column.kind {
+                ColumnKind::Integer => {
+                    let mut batch = vec![];
+                    for value in values {
+                        match value {
+                            ExportValue::Integer(value) => {
+                                // Reinterpreted as unsigned through the logical type
+                                batch.push(*value as i64);
+                                def_levels.push(present_level);
+                            }
+                            ExportValue::Null if column.nullable => def_levels.push(null_level),
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    column_writer.typed::<Int64Type>().write_batch(
+                        &batch,
+                        column.nullable.then_some(&def_levels[..]),
+                        None,
+                    )
+                }
+                ColumnKind::Felt => {
+                    let mut batch = vec![];
+                    for value in values {
+                        match value {
+                            ExportValue::Felt(value) => {
+                                batch.push(FixedLenByteArray::from(value.to_bytes_be().to_vec()));
+                                def_levels.push(present_level);
+                            }
+                            ExportValue::Null if column.nullable => def_levels.push(null_level),
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    column_writer.typed::<FixedLenByteArrayType>().write_batch(
+                        &batch,
+                        column.nullable.then_some(&def_levels[..]),
+                        None,
+                    )
+                }
+                ColumnKind::FeltList => {
+                    // Levels of an empty list, and of its elements
+                    let empty_level = present_level;
+                    let element_level = present_level + 1;
+
+                    let mut batch = vec![];
+                    for value in values {
+                        match value {
+                            ExportValue::FeltList(elements) if elements.is_empty() => {
+                                def_levels.push(empty_level);
+                                rep_levels.push(0);
+                            }
+                            ExportValue::FeltList(elements) => {
+                                for (index, element) in elements.iter().enumerate() {
+                                    batch.push(FixedLenByteArray::from(
+                                        element.to_bytes_be().to_vec(),
+                                    ));
+                                    def_levels.push(element_level);
+                                    rep_levels.push(i16::from(index > 0));
+                                }
+                            }
+                            ExportValue::Null if column.nullable => {
+                                def_levels.push(null_level);
+                                rep_levels.push(0);
+                            }
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    column_writer.typed::<FixedLenByteArrayType>().write_batch(
+                        &batch,
+                        Some(&def_levels),
+                        Some(&rep_levels),
+                    )
+                }
+                ColumnKind::Text => {
+                    let mut batch = vec![];
+                    for value in values {
+                        match value {
+                            ExportValue::Text(value) => {
+                                batch.push(ByteArray::from(value.as_str()));
+                                def_levels.push(present_level);
+                            }
+                            ExportValue::Null if column.nullable => def_levels.push(null_level),
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    column_writer.typed::<ByteArrayType>().write_batch(
+                        &batch,
+                        column.nullable.then_some(&def_levels[..]),
+                        None,
+                    )
+                }
+            };
+            result.map_err(ParquetWriterError::Parquet)?;
+            column_writer.close().map_err(ParquetWriterError::Parquet)?;
+        }
+        row_group.close().map_err(ParquetWriterError::Parquet)?;
+
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+impl<W> std::fmt::Debug for ParquetWriter<W>
+where
+    W: Write + Send,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetWriter")
+            .field("table", &self.table)
+            .field("buffered_rows", &self.rows.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W> TableWriter for ParquetWriter<W>
+where
+    W: Write + Send,
+{
+    type Error = ParquetWriterError;
+
+    fn write_row(&mut self, row: &[ExportValue]) -> Result<(), Self::Error> {
+        let expected = self.table.columns().len();
+        if row.len() != expected {
+            return Err(ParquetWriterError::RowLength {
+                expected,
+                actual: row.len(),
+            });
+        }
+        for (column, value) in self.table.columns().iter().zip(row) {
+            let valid = match value {
+                ExportValue::Null => column.nullable,
+                ExportValue::Integer(_) => column.kind == ColumnKind::Integer,
+                ExportValue::Felt(_) => column.kind == ColumnKind::Felt,
+                ExportValue::FeltList(_) => column.kind == ColumnKind::FeltList,
+                ExportValue::Text(_) => column.kind == ColumnKind::Text,
+            };
+            if !valid {
+                return Err(ParquetWriterError::InvalidValue(column.name));
+            }
+        }
+
+        self.rows.push(row.to_vec());
+        if self.rows.len() >= ROW_GROUP_SIZE {
+            self.flush_rows()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        self.flush_rows()?;
+        self.writer
+            .finish()
+            .map_err(ParquetWriterError::Parquet)
+            .map(|_| ())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ExportWriters<ParquetWriter<std::io::BufWriter<std::fs::File>>> {
+    /// Parquet writers for files named after each table in `dir`, e.g. `blocks.parquet`.
+    /// Existing files are overwritten.
+    pub fn parquet_files(dir: impl AsRef<std::path::Path>) -> Result<Self, ParquetWriterError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(ParquetWriterError::Io)?;
+
+        let open = |table: Table| {
+            let file = std::fs::File::create(dir.join(format!("{}.parquet", table.name())))
+                .map_err(ParquetWriterError::Io)?;
+            ParquetWriter::new(std::io::BufWriter::new(file), table)
+        };
+        Ok(Self {
+            blocks: open(Table::Blocks)?,
+            transactions: open(Table::Transactions)?,
+            receipts: open(Table::Receipts)?,
+            events: open(Table::Events)?,
+        })
+    }
+}
+
+fn table_schema(table: Table) -> Result<TypePtr, ParquetWriterError> {
+    let fields = table
+        .columns()
+        .iter()
+        .map(|column| {
+            let repetition = if column.nullable {
+                Repetition::OPTIONAL
+            } else {
+                Repetition::REQUIRED
+            };
+
+            let field = match column.kind {
+                ColumnKind::Integer => {
+                    Type::primitive_type_builder(column.name, PhysicalType::INT64)
+                        .with_repetition(repetition)
+                        .with_logical_type(Some(LogicalType::Integer(IntType {
+                            bit_width: 64,
+                            is_signed: false,
+                        })))
+                        .build()
+                }
+                ColumnKind::Felt => felt_type(column.name, repetition),
+                ColumnKind::FeltList => {
+                    let element = felt_type("element", Repetition::REQUIRED)?;
+                    let list = Type::group_type_builder("list")
+                        .with_repetition(Repetition::REPEATED)
+                        .with_fields(vec![Arc::new(element)])
+                        .build()?;
+                    Type::group_type_builder(column.name)
+                        .with_repetition(repetition)
+                        .with_logical_type(Some(LogicalType::List))
+                        .with_fields(vec![Arc::new(list)])
+                        .build()
+                }
+                ColumnKind::Text => {
+                    Type::primitive_type_builder(column.name, PhysicalType::BYTE_ARRAY)
+                        .with_repetition(repetition)
+                        .with_logical_type(Some(LogicalType::String))
+                        .build()
+                }
+            };
+            field.map(Arc::new)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ParquetWriterError::Parquet)?;
+
+    Type::group_type_builder(table.name())
+        .with_fields(fields)
+        .build()
+        .map(Arc::new)
+        .map_err(ParquetWriterError::Parquet)
+}
+
+fn felt_type(name: &str, repetition: Repetition) -> Result<Type, ParquetError> {
+    Type::primitive_type_builder(name, PhysicalType::FIXED_LEN_BYTE_ARRAY)
+        .with_repetition(repetition)
+        .with_length(32)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+    use starknet_core::types::FieldElement;
+
+    #[test]
+    fn test_parquet_values() {
+        let mut writer = ParquetWriter::new(vec![], Table::Events).unwrap();
+        for (selector, name) in [(Some(FieldElement::TWO), Some("Transfer")), (None, None)] {
+            writer
+                .write_row(&[
+                    ExportValue::Integer(u64::MAX),
+                    ExportValue::Integer(0),
+                    ExportValue::Felt(FieldElement::from(255u64)),
+                    ExportValue::Integer(1),
+                    ExportValue::Felt(FieldElement::MAX),
+                    selector.into(),
+                    name.map(String::from).into(),
+                    ExportValue::FeltList(selector.into_iter().collect()),
+                    ExportValue::FeltList(vec![FieldElement::ONE, FieldElement::TWO]),
+                ])
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = SerializedFileReader::new(bytes::Bytes::from(writer.inner().clone())).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(
+            schema
+                .root_schema()
+                .get_fields()
+                .iter()
+                .map(|field| field.name())
+                .collect::<Vec<_>>(),
+            Table::Events
+                .columns()
+                .iter()
+                .map(|column| column.name)
+                .collect::<Vec<_>>()
+        );
+
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let felt = |value: FieldElement| Field::Bytes(value.to_bytes_be().to_vec().into());
+        let fields = |index: usize| {
+            rows[index]
+                .get_column_iter()
+                .map(|(_, field)| field.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first = fields(0);
+        assert_eq!(first[0], Field::ULong(u64::MAX));
+        assert_eq!(first[2], felt(FieldElement::from(255u64)));
+        assert_eq!(first[4], felt(FieldElement::MAX));
+        assert_eq!(first[5], felt(FieldElement::TWO));
+        assert_eq!(first[6], Field::Str("Transfer".into()));
+        match &first[8] {
+            Field::ListInternal(list) => assert_eq!(
+                list.elements(),
+                [felt(FieldElement::ONE), felt(FieldElement::TWO)]
+            ),
+            field => panic!("unexpected field {field:?}"),
+        }
+
+        let second = fields(1);
+        assert_eq!(second[5], Field::Null);
+        assert_eq!(second[6], Field::Null);
+        match &second[7] {
+            Field::ListInternal(list) => assert!(list.elements().is_empty()),
+            field => panic!("unexpected field {field:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parquet_invalid_row() {
+        let mut writer = ParquetWriter::new(vec![], Table::Blocks).unwrap();
+        assert!(matches!(
+            writer.write_row(&[ExportValue::Integer(1)]),
+            Err(ParquetWriterError::RowLength {
+                expected: 9,
+                actual: 1
+            })
+        ));
+
+        let mut row = vec![ExportValue::Null; 9];
+        assert!(matches!(
+            writer.write_row(&row),
+            Err(ParquetWriterError::InvalidValue("number"))
+        ));
+
+        row[0] = ExportValue::Integer(1);
+        assert!(matches!(
+            writer.write_row(&row),
+            Err(ParquetWriterError::InvalidValue("hash"))
+        ));
+    }
+}
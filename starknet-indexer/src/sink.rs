@@ -0,0 +1,105 @@
+use crate::{BlockRef, IngestedBlock};
+
+use async_trait::async_trait;
+use starknet_core::types::FieldElement;
+use std::convert::Infallible;
+
+/// Storage for the blocks indexed by an [Indexer](crate::Indexer).
+///
+/// Blocks written are also where indexing resumes from after restarts, so each block should be
+/// written atomically.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Sink {
+    type Error: std::error::Error + Send;
+
+    /// The last `count` blocks written, in ascending order.
+    async fn recent_blocks(&mut self, count: usize) -> Result<Vec<BlockRef>, Self::Error>;
+
+    async fn write_block(&mut self, block: &IngestedBlock) -> Result<(), Self::Error>;
+
+    /// Removes the blocks from `block_number` onwards, after they were reverted by a reorg.
+    async fn rollback(&mut self, block_number: u64) -> Result<(), Self::Error>;
+}
+
+/// A [Sink] keeping blocks in memory.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    blocks: Vec<StoredBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredBlock {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+    pub parent_block_hash: FieldElement,
+    pub timestamp: u64,
+    pub transaction_hashes: Vec<FieldElement>,
+    pub events: Vec<StoredEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredEvent {
+    pub transaction_hash: FieldElement,
+    pub from_address: FieldElement,
+    pub keys: Vec<FieldElement>,
+    pub data: Vec<FieldElement>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The blocks written, in ascending order.
+    pub fn blocks(&self) -> &[StoredBlock] {
+        &self.blocks
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Sink for MemorySink {
+    type Error = Infallible;
+
+    async fn recent_blocks(&mut self, count: usize) -> Result<Vec<BlockRef>, Self::Error> {
+        Ok(self.blocks[self.blocks.len().saturating_sub(count)..]
+            .iter()
+            .map(|block| BlockRef {
+                block_number: block.block_number,
+                block_hash: block.block_hash,
+            })
+            .collect())
+    }
+
+    async fn write_block(&mut self, block: &IngestedBlock) -> Result<(), Self::Error> {
+        self.blocks.push(StoredBlock {
+            block_number: block.block_number,
+            block_hash: block.block_hash,
+            parent_block_hash: block.block.parent_block_hash,
+            timestamp: block.block.timestamp,
+            transaction_hashes: block
+                .block
+                .transaction_receipts
+                .iter()
+                .map(|receipt| receipt.transaction_hash)
+                .collect(),
+            events: block
+                .events()
+                .map(|event| StoredEvent {
+                    transaction_hash: event.transaction_hash,
+                    from_address: event.event.from_address,
+                    keys: event.event.keys.clone(),
+                    data: event.event.data.clone(),
+                })
+                .collect(),
+        });
+        Ok(())
+    }
+
+    async fn rollback(&mut self, block_number: u64) -> Result<(), Self::Error> {
+        self.blocks
+            .retain(|block| block.block_number < block_number);
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{BlockRef, IngestedBlock, Sink};
+
+use async_trait::async_trait;
+use starknet_core::types::FieldElement;
+
+/// Connection to a SQL database, through which a [SqlSink] writes blocks.
+///
+/// Implemented on top of the driver of choice, executing statements with the placeholders of
+/// the [SqlDialect] of the sink.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait SqlExecutor {
+    type Error: std::error::Error + Send;
+
+    async fn execute(&mut self, statement: &str, params: &[SqlValue]) -> Result<(), Self::Error>;
+
+    async fn query(
+        &mut self,
+        statement: &str,
+        params: &[SqlValue],
+    ) -> Result<Vec<Vec<SqlValue>>, Self::Error>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    Sqlite,
+}
+
+/// Values bound to statement parameters. Field elements are stored as hex strings, and lists of
+/// them as comma-separated hex strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlValue {
+    Integer(i64),
+    Text(String),
+}
+
+/// A [Sink] writing blocks, transactions and events to the `blocks`, `transactions` and `events`
+/// tables of a SQL database. Tables are created with [create_tables](SqlSink::create_tables).
+#[derive(Debug)]
+pub struct SqlSink<E> {
+    executor: E,
+    dialect: SqlDialect,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlSinkError<E> {
+    #[error(transparent)]
+    Executor(E),
+    #[error("unexpected row returned by query")]
+    UnexpectedRow,
+}
+
+impl<E> SqlSink<E> {
+    pub fn new(executor: E, dialect: SqlDialect) -> Self {
+        Self { executor, dialect }
+    }
+
+    pub fn postgres(executor: E) -> Self {
+        Self::new(executor, SqlDialect::Postgres)
+    }
+
+    pub fn sqlite(executor: E) -> Self {
+        Self::new(executor, SqlDialect::Sqlite)
+    }
+
+    pub fn executor(&self) -> &E {
+        &self.executor
+    }
+
+    pub fn into_executor(self) -> E {
+        self.executor
+    }
+
+    /// The statements creating the tables written to.
+    pub fn schema(&self) -> Vec<String> {
+        let integer = match self.dialect {
+            SqlDialect::Postgres => "BIGINT",
+            SqlDialect::Sqlite => "INTEGER",
+        };
+
+        vec![
+            format!(
+                "CREATE TABLE IF NOT EXISTS blocks (\
+                number {integer} PRIMARY KEY, \
+                hash TEXT NOT NULL, \
+                parent_hash TEXT NOT NULL, \
+                timestamp {integer} NOT NULL)"
+            ),
+            format!(
+                "CREATE TABLE IF NOT EXISTS transactions (\
+                hash TEXT NOT NULL, \
+                block_number {integer} NOT NULL, \
+                transaction_index {integer} NOT NULL, \
+                actual_fee TEXT NOT NULL, \
+                PRIMARY KEY (block_number, transaction_index))"
+            ),
+            format!(
+                "CREATE TABLE IF NOT EXISTS events (\
+                block_number {integer} NOT NULL, \
+                event_index {integer} NOT NULL, \
+                transaction_hash TEXT NOT NULL, \
+                from_address TEXT NOT NULL, \
+                keys TEXT NOT NULL, \
+                data TEXT NOT NULL, \
+                PRIMARY KEY (block_number, event_index))"
+            ),
+        ]
+    }
+
+    /// Parameter placeholders `1..=count` of the dialect, separated by commas.
+    fn placeholders(&self, count: usize) -> String {
+        (1..=count)
+            .map(|ind| match self.dialect {
+                SqlDialect::Postgres => format!("${ind}"),
+                SqlDialect::Sqlite => format!("?{ind}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl<E> SqlSink<E>
+where
+    E: SqlExecutor + Send,
+{
+    /// Creates the tables written to, unless they already exist.
+    pub async fn create_tables(&mut self) -> Result<(), SqlSinkError<E::Error>> {
+        for statement in self.schema() {
+            self.executor
+                .execute(&statement, &[])
+                .await
+                .map_err(SqlSinkError::Executor)?;
+        }
+        Ok(())
+    }
+
+    async fn insert_block(&mut self, block: &IngestedBlock) -> Result<(), E::Error> {
+        let statement = format!(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp) VALUES ({})",
+            self.placeholders(4)
+        );
+        self.executor
+            .execute(
+                &statement,
+                &[
+                    integer(block.block_number),
+                    felt(block.block_hash),
+                    felt(block.block.parent_block_hash),
+                    integer(block.block.timestamp),
+                ],
+            )
+            .await?;
+
+        let statement = format!(
+            "INSERT INTO transactions (hash, block_number, transaction_index, actual_fee) \
+            VALUES ({})",
+            self.placeholders(4)
+        );
+        for receipt in block.block.transaction_receipts.iter() {
+            self.executor
+                .execute(
+                    &statement,
+                    &[
+                        felt(receipt.transaction_hash),
+                        integer(block.block_number),
+                        integer(receipt.transaction_index),
+                        felt(receipt.actual_fee),
+                    ],
+                )
+                .await?;
+        }
+
+        let statement = format!(
+            "INSERT INTO events (block_number, event_index, transaction_hash, from_address, \
+            keys, data) VALUES ({})",
+            self.placeholders(6)
+        );
+        for event in block.events() {
+            self.executor
+                .execute(
+                    &statement,
+                    &[
+                        integer(event.block_number),
+                        integer(event.event_index),
+                        felt(event.transaction_hash),
+                        felt(event.event.from_address),
+                        felts(&event.event.keys),
+                        felts(&event.event.data),
+                    ],
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<E> Sink for SqlSink<E>
+where
+    E: SqlExecutor + Send,
+{
+    type Error = SqlSinkError<E::Error>;
+
+    async fn recent_blocks(&mut self, count: usize) -> Result<Vec<BlockRef>, Self::Error> {
+        let statement = format!(
+            "SELECT number, hash FROM blocks ORDER BY number DESC LIMIT {}",
+            self.placeholders(1)
+        );
+        let rows = self
+            .executor
+            .query(&statement, &[SqlValue::Integer(count as i64)])
+            .await
+            .map_err(SqlSinkError::Executor)?;
+
+        let mut blocks = rows
+            .into_iter()
+            .map(|row| match &row[..] {
+                [SqlValue::Integer(number), SqlValue::Text(hash)] => Ok(BlockRef {
+                    block_number: *number as u64,
+                    block_hash: FieldElement::from_hex_be(hash)
+                        .map_err(|_| SqlSinkError::UnexpectedRow)?,
+                }),
+                _ => Err(SqlSinkError::UnexpectedRow),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        blocks.reverse();
+
+        Ok(blocks)
+    }
+
+    async fn write_block(&mut self, block: &IngestedBlock) -> Result<(), Self::Error> {
+        self.executor
+            .execute("BEGIN", &[])
+            .await
+            .map_err(SqlSinkError::Executor)?;
+
+        match self.insert_block(block).await {
+            Ok(()) => self
+                .executor
+                .execute("COMMIT", &[])
+                .await
+                .map_err(SqlSinkError::Executor),
+            Err(err) => {
+                // The original error is more relevant than a failure to roll back
+                let _ = self.executor.execute("ROLLBACK", &[]).await;
+                Err(SqlSinkError::Executor(err))
+            }
+        }
+    }
+
+    async fn rollback(&mut self, block_number: u64) -> Result<(), Self::Error> {
+        for table in ["events", "transactions"] {
+            let statement = format!(
+                "DELETE FROM {table} WHERE block_number >= {}",
+                self.placeholders(1)
+            );
+            self.executor
+                .execute(&statement, &[integer(block_number)])
+                .await
+                .map_err(SqlSinkError::Executor)?;
+        }
+
+        let statement = format!(
+            "DELETE FROM blocks WHERE number >= {}",
+            self.placeholders(1)
+        );
+        self.executor
+            .execute(&statement, &[integer(block_number)])
+            .await
+            .map_err(SqlSinkError::Executor)
+    }
+}
+
+fn integer(value: u64) -> SqlValue {
+    SqlValue::Integer(value as i64)
+}
+
+fn felt(value: FieldElement) -> SqlValue {
+    SqlValue::Text(format!("{value:#x}"))
+}
+
+fn felts(values: &[FieldElement]) -> SqlValue {
+    SqlValue::Text(
+        values
+            .iter()
+            .map(|value| format!("{value:#x}"))
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    /// Records executed statements, answering queries with `rows`.
+    #[derive(Debug, Default)]
+    struct RecordingExecutor {
+        statements: Vec<(String, Vec<SqlValue>)>,
+        rows: Vec<Vec<SqlValue>>,
+    }
+
+    #[async_trait]
+    impl SqlExecutor for RecordingExecutor {
+        type Error = Infallible;
+
+        async fn execute(
+            &mut self,
+            statement: &str,
+            params: &[SqlValue],
+        ) -> Result<(), Self::Error> {
+            self.statements
+                .push((statement.to_owned(), params.to_owned()));
+            Ok(())
+        }
+
+        async fn query(
+            &mut self,
+            statement: &str,
+            params: &[SqlValue],
+        ) -> Result<Vec<Vec<SqlValue>>, Self::Error> {
+            self.statements
+                .push((statement.to_owned(), params.to_owned()));
+            Ok(self.rows.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sql_sink_statements() {
+        let block: starknet_core::types::Block = serde_json::from_str(include_str!(
+            "../../starknet-core/test-data/raw_gateway_responses/get_block/14_deploy_account.txt"
+        ))
+        .unwrap();
+        let block = IngestedBlock {
+            block_number: block.block_number.unwrap(),
+            block_hash: block.block_hash.unwrap(),
+            block,
+        };
+
+        let mut sink = SqlSink::sqlite(RecordingExecutor::default());
+        sink.write_block(&block).await.unwrap();
+        sink.rollback(block.block_number).await.unwrap();
+
+        let statements = &sink.executor().statements;
+        let transaction_count = block.block.transaction_receipts.len();
+        let event_count = block.events().count();
+        assert_eq!(statements.len(), 3 + transaction_count + event_count + 3,);
+        assert_eq!(statements[0].0, "BEGIN");
+        assert_eq!(
+            statements[1],
+            (
+                "INSERT INTO blocks (number, hash, parent_hash, timestamp) VALUES (?1, ?2, ?3, ?4)"
+                    .to_owned(),
+                vec![
+                    SqlValue::Integer(375919),
+                    SqlValue::Text(
+                        "0x5a8c7eb8b8fbe5c6ee76591fe093fe906d93805e9cf1a82d55410b17977e373"
+                            .to_owned()
+                    ),
+                    SqlValue::Text(
+                        "0x3b2fedf93bfc89131b29d179c52a8bd6c8c65debf9994c488aef2513b68c84e"
+                            .to_owned()
+                    ),
+                    SqlValue::Integer(block.block.timestamp as i64),
+                ]
+            )
+        );
+        assert_eq!(statements[2 + transaction_count + event_count].0, "COMMIT");
+        assert_eq!(
+            statements.last().unwrap().0,
+            "DELETE FROM blocks WHERE number >= ?1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sql_sink_recent_blocks() {
+        let mut sink = SqlSink::postgres(RecordingExecutor {
+            statements: vec![],
+            rows: vec![
+                vec![SqlValue::Integer(8), SqlValue::Text("0x8".to_owned())],
+                vec![SqlValue::Integer(7), SqlValue::Text("0x7".to_owned())],
+            ],
+        });
+
+        assert_eq!(
+            sink.recent_blocks(2).await.unwrap(),
+            vec![
+                BlockRef {
+                    block_number: 7,
+                    block_hash: FieldElement::from(7u64),
+                },
+                BlockRef {
+                    block_number: 8,
+                    block_hash: FieldElement::from(8u64),
+                },
+            ]
+        );
+        assert_eq!(
+            sink.executor().statements[0],
+            (
+                "SELECT number, hash FROM blocks ORDER BY number DESC LIMIT $1".to_owned(),
+                vec![SqlValue::Integer(2)]
+            )
+        );
+        assert!(sink.schema()[0].contains("number BIGINT PRIMARY KEY"));
+    }
+}
@@ -0,0 +1,140 @@
+use starknet_core::{
+    crypto::{ecdsa_verify, pedersen_hash, Signature},
+    types::{Block, BlockSignature, FieldElement},
+};
+
+/// Header fields of a block whose hash and sequencer signature have been verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedHeader {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+    pub parent_block_hash: FieldElement,
+    pub state_root: FieldElement,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HeaderError {
+    #[error("pending blocks cannot be verified")]
+    PendingBlock,
+    #[error("block hash does not match block contents")]
+    BlockHashMismatch,
+    #[error("signature is for another block")]
+    SignatureMismatch,
+    #[error("invalid sequencer signature")]
+    InvalidSignature,
+}
+
+/// Verifies that `block` hashes to its block hash, and that `signature` is a signature of it by
+/// the sequencer with `sequencer_public_key`.
+///
+/// Blocks before Starknet v0.7.0 are hashed with `chain_id`.
+pub fn verify_header(
+    block: &Block,
+    signature: &BlockSignature,
+    chain_id: FieldElement,
+    sequencer_public_key: FieldElement,
+) -> Result<VerifiedHeader, HeaderError> {
+    let (block_number, block_hash, state_root) =
+        match (block.block_number, block.block_hash, block.state_root) {
+            (Some(block_number), Some(block_hash), Some(state_root)) => {
+                (block_number, block_hash, state_root)
+            }
+            _ => return Err(HeaderError::PendingBlock),
+        };
+
+    if block.compute_hash() != Some(block_hash)
+        && block.compute_legacy_hash(chain_id) != Some(block_hash)
+    {
+        return Err(HeaderError::BlockHashMismatch);
+    }
+
+    let input = &signature.signature_input;
+    if signature.block_number != block_number || input.block_hash != block_hash {
+        return Err(HeaderError::SignatureMismatch);
+    }
+    let (r, s) = match signature.signature[..] {
+        [r, s] => (r, s),
+        _ => return Err(HeaderError::InvalidSignature),
+    };
+    let message_hash = pedersen_hash(&input.block_hash, &input.state_diff_commitment);
+    if !matches!(
+        ecdsa_verify(&sequencer_public_key, &message_hash, &Signature { r, s }),
+        Ok(true)
+    ) {
+        return Err(HeaderError::InvalidSignature);
+    }
+
+    Ok(VerifiedHeader {
+        block_number,
+        block_hash,
+        parent_block_hash: block.parent_block_hash,
+        state_root,
+        timestamp: block.timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::{crypto::ecdsa_sign, types::BlockSignatureInput};
+
+    fn sign(block: &Block, private_key: FieldElement) -> BlockSignature {
+        let block_hash = block.block_hash.unwrap();
+        let state_diff_commitment = FieldElement::from_hex_be("0x1234").unwrap();
+        let signature = ecdsa_sign(
+            &private_key,
+            &pedersen_hash(&block_hash, &state_diff_commitment),
+        )
+        .unwrap();
+
+        BlockSignature {
+            block_number: block.block_number.unwrap(),
+            signature: vec![signature.r, signature.s],
+            signature_input: BlockSignatureInput {
+                block_hash,
+                state_diff_commitment,
+            },
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_verify_header() {
+        let block: Block = serde_json::from_str(include_str!(
+            "../../starknet-core/test-data/raw_gateway_responses/get_block/14_deploy_account.txt"
+        ))
+        .unwrap();
+        let private_key = FieldElement::from_hex_be("0x1").unwrap();
+        let public_key = starknet_crypto::get_public_key(&private_key);
+        let signature = sign(&block, private_key);
+        let chain_id = FieldElement::ZERO;
+
+        let header = verify_header(&block, &signature, chain_id, public_key).unwrap();
+        assert_eq!(header.block_number, 375919);
+        assert_eq!(header.block_hash, block.block_hash.unwrap());
+        assert_eq!(header.parent_block_hash, block.parent_block_hash);
+        assert_eq!(header.state_root, block.state_root.unwrap());
+
+        let other_key = starknet_crypto::get_public_key(&FieldElement::TWO);
+        assert!(matches!(
+            verify_header(&block, &signature, chain_id, other_key),
+            Err(HeaderError::InvalidSignature)
+        ));
+
+        let mut other_signature = sign(&block, private_key);
+        other_signature.block_number += 1;
+        assert!(matches!(
+            verify_header(&block, &other_signature, chain_id, public_key),
+            Err(HeaderError::SignatureMismatch)
+        ));
+
+        let mut tampered = block;
+        tampered.timestamp += 1;
+        assert!(matches!(
+            verify_header(&tampered, &signature, chain_id, public_key),
+            Err(HeaderError::BlockHashMismatch)
+        ));
+    }
+}
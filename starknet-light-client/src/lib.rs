@@ -0,0 +1,8 @@
+//! Light client following Starknet block headers, so that wallets can verify what untrusted
+//! nodes serve instead of trusting them.
+
+mod header;
+pub use header::{verify_header, HeaderError, VerifiedHeader};
+
+mod light_client;
+pub use light_client::{LightClient, LightClientError, StorageVerificationError};
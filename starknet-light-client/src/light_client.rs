@@ -0,0 +1,247 @@
+use crate::header::{verify_header, HeaderError, VerifiedHeader};
+
+use starknet_core::{
+    proof::{verify_storage_proof, ProofError},
+    types::{BlockId, FieldElement, StarknetError, StorageProof},
+};
+use starknet_providers::{Provider, ProviderError};
+use std::{collections::VecDeque, future::Future};
+
+const DEFAULT_MAX_HEADERS: usize = 1024;
+
+/// Follows the chain from a trusted checkpoint, keeping the most recent verified headers.
+///
+/// Headers are only accepted when their block hash matches the block contents, they're signed
+/// by the sequencer, and they extend the latest verified header. Storage read from any node can
+/// then be verified against their state roots:
+///
+/// ```ignore
+/// let mut client = LightClient::new(provider, chain_id::MAINNET, sequencer_key, checkpoint);
+/// client.sync().await?;
+///
+/// let block_number = client.latest().block_number;
+/// let proof = rpc.get_proof(&BlockId::Number(block_number), contract, &[key]).await?;
+/// client.verify_contract_storage(block_number, &proof, contract, key, value)?;
+/// ```
+#[derive(Debug)]
+pub struct LightClient<P> {
+    provider: P,
+    chain_id: FieldElement,
+    sequencer_public_key: FieldElement,
+    headers: VecDeque<VerifiedHeader>,
+    max_headers: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LightClientError<P> {
+    #[error(transparent)]
+    Provider(ProviderError<P>),
+    #[error("block {block_number}: {error}")]
+    Header {
+        block_number: u64,
+        error: HeaderError,
+    },
+    /// The chain reorganized past the oldest retained header.
+    #[error("reorg beyond block {0}")]
+    ReorgTooDeep(u64),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageVerificationError {
+    #[error("block {0} not verified")]
+    UnknownBlock(u64),
+    #[error(transparent)]
+    Proof(ProofError),
+}
+
+impl<P> LightClient<P> {
+    /// Starts following the chain from `checkpoint`, a header obtained from a trusted source.
+    /// Headers are verified against the signatures of the sequencer with `sequencer_public_key`.
+    pub fn new(
+        provider: P,
+        chain_id: FieldElement,
+        sequencer_public_key: FieldElement,
+        checkpoint: VerifiedHeader,
+    ) -> Self {
+        Self {
+            provider,
+            chain_id,
+            sequencer_public_key,
+            headers: VecDeque::from([checkpoint]),
+            max_headers: DEFAULT_MAX_HEADERS,
+        }
+    }
+
+    /// Sets the number of recent headers kept for verifying proofs, and for rolling back on
+    /// reorgs. Defaults to `1024`.
+    pub fn max_headers(self, max_headers: usize) -> Self {
+        Self {
+            max_headers: max_headers.max(1),
+            ..self
+        }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// The most recent verified header.
+    pub fn latest(&self) -> &VerifiedHeader {
+        self.headers.back().expect("headers are never empty")
+    }
+
+    /// The verified header of block `block_number`, if retained.
+    pub fn header(&self, block_number: u64) -> Option<&VerifiedHeader> {
+        let oldest = self.headers.front()?.block_number;
+        block_number
+            .checked_sub(oldest)
+            .and_then(|ind| self.headers.get(ind as usize))
+    }
+
+    /// The verified state root of block `block_number`.
+    pub fn state_root(&self, block_number: u64) -> Option<FieldElement> {
+        self.header(block_number).map(|header| header.state_root)
+    }
+
+    /// Verifies that storage `key` of contract `contract_address` holds `value` at verified
+    /// block `block_number`.
+    pub fn verify_contract_storage(
+        &self,
+        block_number: u64,
+        proof: &StorageProof,
+        contract_address: FieldElement,
+        key: FieldElement,
+        value: FieldElement,
+    ) -> Result<(), StorageVerificationError> {
+        let state_root = self
+            .state_root(block_number)
+            .ok_or(StorageVerificationError::UnknownBlock(block_number))?;
+        verify_storage_proof(state_root, proof, contract_address, key, value)
+            .map_err(StorageVerificationError::Proof)
+    }
+
+    fn push(&mut self, header: VerifiedHeader) {
+        self.headers.push_back(header);
+        while self.headers.len() > self.max_headers {
+            self.headers.pop_front();
+        }
+    }
+}
+
+impl<P> LightClient<P>
+where
+    P: Provider + Sync,
+{
+    /// Fetches and verifies the header of block `block_number`, without checking that it
+    /// belongs to the followed chain.
+    pub async fn verify_block(
+        &self,
+        block_number: u64,
+    ) -> Result<VerifiedHeader, LightClientError<P::Error>> {
+        let block = self
+            .provider
+            .get_block(BlockId::Number(block_number))
+            .await
+            .map_err(LightClientError::Provider)?;
+        let signature = self
+            .provider
+            .get_block_signature(BlockId::Number(block_number))
+            .await
+            .map_err(LightClientError::Provider)?;
+
+        verify_header(&block, &signature, self.chain_id, self.sequencer_public_key).map_err(
+            |error| LightClientError::Header {
+                block_number,
+                error,
+            },
+        )
+    }
+
+    /// Verifies new blocks up to the chain tip, and returns the number of headers added.
+    ///
+    /// When a new block doesn't extend the latest verified header, the chain was reorganized,
+    /// and the header is dropped to follow the new chain from its parent.
+    pub async fn sync(&mut self) -> Result<usize, LightClientError<P::Error>> {
+        let mut added = 0;
+        loop {
+            let latest = self.latest().clone();
+            let header = match self.verify_block(latest.block_number + 1).await {
+                Ok(header) => header,
+                Err(LightClientError::Provider(ProviderError::StarknetError(
+                    StarknetError::BlockNotFound,
+                ))) => return Ok(added),
+                Err(err) => return Err(err),
+            };
+
+            if header.parent_block_hash == latest.block_hash {
+                self.push(header);
+                added += 1;
+            } else if self.headers.len() > 1 {
+                self.headers.pop_back();
+                added = added.saturating_sub(1);
+            } else {
+                return Err(LightClientError::ReorgTooDeep(latest.block_number));
+            }
+        }
+    }
+
+    /// Keeps following the chain, calling `sleep` between syncs, until an error occurs.
+    pub async fn follow<F, Fut>(&mut self, mut sleep: F) -> Result<(), LightClientError<P::Error>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            self.sync().await?;
+            sleep().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(block_number: u64) -> VerifiedHeader {
+        VerifiedHeader {
+            block_number,
+            block_hash: FieldElement::from(block_number + 100),
+            parent_block_hash: FieldElement::from(block_number + 99),
+            state_root: FieldElement::from(block_number + 200),
+            timestamp: block_number,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_retained_headers() {
+        let mut client =
+            LightClient::new((), FieldElement::ZERO, FieldElement::ZERO, header(10)).max_headers(2);
+        client.push(header(11));
+        client.push(header(12));
+
+        assert_eq!(client.latest(), &header(12));
+        assert_eq!(client.header(10), None);
+        assert_eq!(client.header(11), Some(&header(11)));
+        assert_eq!(client.state_root(12), Some(FieldElement::from(212u64)));
+        assert_eq!(client.header(13), None);
+
+        let proof: StorageProof = serde_json::from_value(serde_json::json!({
+            "state_commitment": "0x0",
+            "class_commitment": "0x0",
+            "contract_proof": [],
+            "contract_data": null,
+        }))
+        .unwrap();
+        assert!(matches!(
+            client.verify_contract_storage(
+                10,
+                &proof,
+                FieldElement::ONE,
+                FieldElement::ONE,
+                FieldElement::ONE
+            ),
+            Err(StorageVerificationError::UnknownBlock(10))
+        ));
+    }
+}
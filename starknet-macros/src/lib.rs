@@ -1,115 +1,600 @@
 use proc_macro::TokenStream;
+use quote::ToTokens;
 use starknet_core::{
     types::FieldElement,
-    utils::{cairo_short_string_to_felt, get_selector_from_name},
+    utils::{cairo_short_string_to_felt, get_selector_from_name, CairoShortStringToFeltError},
 };
-use syn::{parse_macro_input, LitStr};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Lit, LitStr, Meta, NestedMeta};
 
+/// Computes the selector of an entrypoint or event name at compile time, i.e. the same value as
+/// `get_selector_from_name`, as a `FieldElement` usable in `const` items. Non-ASCII names are
+/// compile errors.
 #[proc_macro]
 pub fn selector(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
-    let str_value = input.value();
-
-    let selector_value = get_selector_from_name(&str_value).expect("invalid selector name");
-    let selector_raw = selector_value.into_mont();
-
-    format!(
-        "{}::from_mont([{}, {}, {}, {}])",
-        field_element_path(),
-        selector_raw[0],
-        selector_raw[1],
-        selector_raw[2],
-        selector_raw[3],
-    )
-    .parse()
-    .unwrap()
+    match get_selector_from_name(&input.value()) {
+        Ok(selector_value) => field_element_tokens(selector_value),
+        Err(_) => syn::Error::new(input.span(), "selector name must be ASCII")
+            .to_compile_error()
+            .into(),
+    }
 }
 
+/// Encodes an ASCII string of up to 31 characters as a Cairo short string at compile time, as
+/// used for chain ids, token symbols and error codes. Longer or non-ASCII strings are compile
+/// errors.
 #[proc_macro]
 pub fn short_string(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
     let str_value = input.value();
 
-    let felt_value = cairo_short_string_to_felt(&str_value).expect("invalid Cairo short string");
-    let felt_raw = felt_value.into_mont();
-
-    format!(
-        "{}::from_mont([{}, {}, {}, {}])",
-        field_element_path(),
-        felt_raw[0],
-        felt_raw[1],
-        felt_raw[2],
-        felt_raw[3],
-    )
-    .parse()
-    .unwrap()
+    match cairo_short_string_to_felt(&str_value) {
+        Ok(felt_value) => field_element_tokens(felt_value),
+        Err(CairoShortStringToFeltError::StringTooLong) => syn::Error::new(
+            input.span(),
+            format!(
+                "short string exceeds maximum length of 31 characters: got {}",
+                str_value.len()
+            ),
+        )
+        .to_compile_error()
+        .into(),
+        Err(err) => syn::Error::new(input.span(), err).to_compile_error().into(),
+    }
 }
 
+/// Converts a literal into a `FieldElement` at compile time. Both integer literals and string
+/// literals, in decimal or `0x`-prefixed hex, are accepted:
+///
+/// ```ignore
+/// const DECIMALS: FieldElement = felt!(1000000000000000000);
+/// const ADDRESS: FieldElement = felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
+/// ```
+///
+/// Malformed values and values out of range of the field are compile errors.
 #[proc_macro]
 pub fn felt(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as LitStr);
+    let input = parse_macro_input!(input as Lit);
 
-    let str_value = input.value();
+    let str_value = match &input {
+        Lit::Str(lit) => lit.value(),
+        Lit::Int(lit) if lit.suffix().is_empty() => lit.base10_digits().to_owned(),
+        _ => {
+            return syn::Error::new(
+                input.span(),
+                "expected an unsuffixed integer literal or a string literal",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
 
     let felt_value = if str_value.starts_with("0x") {
-        FieldElement::from_hex_be(&str_value).expect("invalid FieldElement value")
+        FieldElement::from_hex_be(&str_value)
     } else {
-        FieldElement::from_dec_str(&str_value).expect("invalid FieldElement value")
+        FieldElement::from_dec_str(&str_value)
     };
 
-    let felt_raw = felt_value.into_mont();
-
-    format!(
-        "{}::from_mont([{}, {}, {}, {}])",
-        field_element_path(),
-        felt_raw[0],
-        felt_raw[1],
-        felt_raw[2],
-        felt_raw[3],
-    )
-    .parse()
-    .unwrap()
+    match felt_value {
+        Ok(felt_value) => field_element_tokens(felt_value),
+        Err(err) => invalid_felt_error(&input, err),
+    }
 }
 
+/// Converts a decimal string literal into a `FieldElement` at compile time.
 #[proc_macro]
 pub fn felt_dec(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
-    let str_value = input.value();
-
-    let felt_value = FieldElement::from_dec_str(&str_value).expect("invalid FieldElement value");
-    let felt_raw = felt_value.into_mont();
-
-    format!(
-        "{}::from_mont([{}, {}, {}, {}])",
-        field_element_path(),
-        felt_raw[0],
-        felt_raw[1],
-        felt_raw[2],
-        felt_raw[3],
-    )
-    .parse()
-    .unwrap()
+    match FieldElement::from_dec_str(&input.value()) {
+        Ok(felt_value) => field_element_tokens(felt_value),
+        Err(err) => invalid_felt_error(&input, err),
+    }
 }
 
+/// Converts a hex string literal into a `FieldElement` at compile time.
 #[proc_macro]
 pub fn felt_hex(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
-    let str_value = input.value();
+    match FieldElement::from_hex_be(&input.value()) {
+        Ok(felt_value) => field_element_tokens(felt_value),
+        Err(err) => invalid_felt_error(&input, err),
+    }
+}
+
+/// Implements `CairoSerialize`, encoding struct fields in order, and enums as their variant
+/// index followed by the fields of the variant.
+#[proc_macro_derive(CairoSerialize)]
+pub fn derive_cairo_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_cairo_serialize(&input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `CairoDeserialize`, with the same encoding as `#[derive(CairoSerialize)]`.
+#[proc_macro_derive(CairoDeserialize)]
+pub fn derive_cairo_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_cairo_deserialize(&input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_cairo_serialize(input: &DeriveInput) -> syn::Result<String> {
+    ensure_not_generic(input, "CairoSerialize")?;
+
+    let felt = field_element_path();
+    let codec = format!("{}::codec", core_path());
+    let serialize =
+        |value: &str| format!("{codec}::CairoSerialize::cairo_serialize({value}, __output);");
 
-    let felt_value = FieldElement::from_hex_be(&str_value).expect("invalid FieldElement value");
-    let felt_raw = felt_value.into_mont();
+    let body = match &input.data {
+        Data::Struct(data) => field_names(&data.fields)
+            .iter()
+            .map(|name| serialize(&format!("&self.{name}")))
+            .collect::<String>(),
+        Data::Enum(data) => {
+            let mut arms = String::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let bindings = field_bindings(&variant.fields);
+                arms.push_str(&format!(
+                    "Self::{}{} => {{ {} {} }}",
+                    variant.ident,
+                    bindings_pattern(&variant.fields, &bindings),
+                    serialize(&format!("&{index}u64")),
+                    bindings
+                        .iter()
+                        .map(|binding| serialize(binding))
+                        .collect::<String>(),
+                ));
+            }
+            format!("match self {{ {arms} }}")
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "CairoSerialize cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(format!(
+        "impl {codec}::CairoSerialize for {} {{
+            #[allow(unused_variables)]
+            fn cairo_serialize(&self, __output: &mut ::std::vec::Vec<{felt}>) {{
+                {body}
+            }}
+        }}",
+        input.ident
+    ))
+}
+
+fn expand_cairo_deserialize(input: &DeriveInput) -> syn::Result<String> {
+    ensure_not_generic(input, "CairoDeserialize")?;
+
+    let codec = format!("{}::codec", core_path());
+
+    let body = match &input.data {
+        Data::Struct(data) => format!(
+            "::core::result::Result::Ok(Self{})",
+            construct_fields(&data.fields)
+        ),
+        Data::Enum(data) => {
+            let mut arms = String::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                arms.push_str(&format!(
+                    "{index} => ::core::result::Result::Ok(Self::{}{}),",
+                    variant.ident,
+                    construct_fields(&variant.fields),
+                ));
+            }
+            format!(
+                "match __reader.read::<u64>()? {{
+                    {arms}
+                    index => ::core::result::Result::Err({codec}::DecodeError::InvalidVariant(index)),
+                }}"
+            )
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "CairoDeserialize cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(format!(
+        "impl {codec}::CairoDeserialize for {} {{
+            #[allow(unused_variables)]
+            fn cairo_deserialize(
+                __reader: &mut {codec}::FeltReader<'_>,
+            ) -> ::core::result::Result<Self, {codec}::DecodeError> {{
+                {body}
+            }}
+        }}",
+        input.ident
+    ))
+}
+
+fn ensure_not_generic(input: &DeriveInput, derive: &str) -> syn::Result<()> {
+    if input.generics.params.is_empty() {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("{derive} cannot be derived for generic types"),
+        ))
+    }
+}
+
+/// Names used to access the fields on `self`, i.e. identifiers, or indices for tuple structs.
+fn field_names(fields: &Fields) -> Vec<String> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        })
+        .collect()
+}
+
+fn field_bindings(fields: &Fields) -> Vec<String> {
+    (0..fields.len())
+        .map(|index| format!("__field{index}"))
+        .collect()
+}
+
+/// The pattern binding the fields of an enum variant to `bindings`.
+fn bindings_pattern(fields: &Fields, bindings: &[String]) -> String {
+    match fields {
+        Fields::Named(_) => format!(
+            " {{ {} }}",
+            field_names(fields)
+                .iter()
+                .zip(bindings.iter())
+                .map(|(name, binding)| format!("{name}: {binding},"))
+                .collect::<String>()
+        ),
+        Fields::Unnamed(_) => format!("({})", bindings.join(", ")),
+        Fields::Unit => String::new(),
+    }
+}
+
+/// Reads fields in order to construct a struct or an enum variant.
+fn construct_fields(fields: &Fields) -> String {
+    match fields {
+        Fields::Named(_) => format!(
+            " {{ {} }}",
+            field_names(fields)
+                .iter()
+                .map(|name| format!("{name}: __reader.read()?,"))
+                .collect::<String>()
+        ),
+        Fields::Unnamed(_) => format!(
+            "({})",
+            (0..fields.len())
+                .map(|_| "__reader.read()?,")
+                .collect::<String>()
+        ),
+        Fields::Unit => String::new(),
+    }
+}
+
+/// Implements `StarknetEvent` for a struct with named fields, along with `TryFrom` conversions
+/// from raw events.
+///
+/// The selector is derived from the struct name, or from `#[event(name = "...")]` when the
+/// on-chain name differs. Fields are read from the event data in order, except fields marked
+/// with `#[event(key)]`, which are read from the keys following the selector. Field types must
+/// implement `CairoDeserialize`.
+#[proc_macro_derive(StarknetEvent, attributes(event))]
+pub fn derive_starknet_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_starknet_event(&input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `TypedData` for hashing the struct as a SNIP-12 message, and `TypedDataMember` for
+/// nesting it in other messages.
+///
+/// The type name defaults to the struct name and can be set with `#[typed_data(name = "...")]`.
+/// Members can be renamed with `#[typed_data(rename = "...")]`, and their type name overridden
+/// with `#[typed_data(type = "...")]`, e.g. for `ContractAddress` or `string` members stored as
+/// felts.
+#[proc_macro_derive(TypedData, attributes(typed_data))]
+pub fn derive_typed_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_typed_data(&input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_typed_data(input: &DeriveInput) -> syn::Result<String> {
+    let ident = &input.ident;
+    ensure_not_generic(input, "TypedData")?;
+
+    let mut name = ident.to_string();
+    for meta in helper_attributes(&input.attrs, "typed_data")? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("name") => {
+                name = string_value(&value)?
+            }
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "unknown typed_data attribute",
+                ))
+            }
+        }
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "TypedData can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "TypedData can only be derived for structs",
+            ))
+        }
+    };
+
+    let typed_data = format!("{}::typed_data", core_path());
+    let member = format!("{typed_data}::TypedDataMember");
+
+    let mut members = String::new();
+    let mut collect_types = String::new();
+    let mut encode_members = String::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+
+        let mut member_name = field_ident.to_string();
+        let mut member_type = None;
+        for meta in helper_attributes(&field.attrs, "typed_data")? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("rename") => {
+                    member_name = string_value(&value)?
+                }
+                NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("type") => {
+                    member_type = Some(string_value(&value)?)
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "unknown typed_data attribute",
+                    ))
+                }
+            }
+        }
+
+        let member_type = match member_type {
+            Some(member_type) => format!("{member_type:?}.into()"),
+            None => format!(
+                "<{} as {member}>::type_name()",
+                field_type.to_token_stream()
+            ),
+        };
+        members.push_str(&format!("({:?}, {member_type}),", member_name));
+        collect_types.push_str(&format!(
+            "<{} as {member}>::collect_types(types);",
+            field_type.to_token_stream()
+        ));
+        encode_members.push_str(&format!("{member}::encode_member(&self.{field_ident}),"));
+    }
+
+    let felt = field_element_path();
+    let types = "&mut ::std::collections::BTreeMap<&'static str, ::std::string::String>";
+    Ok(format!(
+        "impl {typed_data}::TypedData for {ident} {{
+            const TYPE_NAME: &'static str = {name:?};
+
+            fn members() -> ::std::vec::Vec<(&'static str, ::std::string::String)> {{
+                ::std::vec![{members}]
+            }}
+
+            fn collect_types(types: {types}) {{
+                if types.contains_key(Self::TYPE_NAME) {{
+                    return;
+                }}
+                types.insert(
+                    Self::TYPE_NAME,
+                    <Self as {typed_data}::TypedData>::type_definition(),
+                );
+                {collect_types}
+            }}
+
+            fn encode_members(&self) -> ::std::vec::Vec<{felt}> {{
+                ::std::vec![{encode_members}]
+            }}
+        }}
+
+        impl {member} for {ident} {{
+            fn type_name() -> ::std::string::String {{
+                <Self as {typed_data}::TypedData>::TYPE_NAME.into()
+            }}
+
+            fn encode_member(&self) -> {felt} {{
+                <Self as {typed_data}::TypedData>::struct_hash(self)
+            }}
+
+            fn collect_types(types: {types}) {{
+                <Self as {typed_data}::TypedData>::collect_types(types)
+            }}
+        }}"
+    ))
+}
+
+fn expand_starknet_event(input: &DeriveInput) -> syn::Result<String> {
+    let ident = &input.ident;
+    ensure_not_generic(input, "StarknetEvent")?;
+
+    let mut name = ident.to_string();
+    for meta in helper_attributes(&input.attrs, "event")? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("name") => {
+                name = string_value(&value)?
+            }
+            meta => return Err(syn::Error::new_spanned(meta, "unknown event attribute")),
+        }
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => vec![],
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "StarknetEvent can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "StarknetEvent can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut field_reads = String::new();
+    for field in fields {
+        let mut is_key = false;
+        for meta in helper_attributes(&field.attrs, "event")? {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("key") => is_key = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("data") => is_key = false,
+                meta => return Err(syn::Error::new_spanned(meta, "unknown event attribute")),
+            }
+        }
+
+        field_reads.push_str(&format!(
+            "{}: {}.read()?,",
+            field.ident.as_ref().unwrap(),
+            if is_key { "__keys" } else { "__data" }
+        ));
+    }
+
+    let selector_raw = get_selector_from_name(&name)
+        .map_err(|_| syn::Error::new_spanned(ident, "invalid event name"))?
+        .into_mont();
+
+    let felt = field_element_path();
+    let event = format!("{}::event", core_path());
+    let codec = format!("{}::codec", core_path());
+    let mut output = format!(
+        "impl {event}::StarknetEvent for {ident} {{
+            fn selector() -> {felt} {{
+                {felt}::from_mont([{}, {}, {}, {}])
+            }}
+
+            fn decode(
+                keys: &[{felt}],
+                data: &[{felt}],
+            ) -> ::core::result::Result<Self, {event}::DecodeEventError> {{
+                let mut __keys = {codec}::FeltReader::new({event}::strip_selector(
+                    keys,
+                    <Self as {event}::StarknetEvent>::selector(),
+                )?);
+                let mut __data = {codec}::FeltReader::new(data);
+                let __value = Self {{ {field_reads} }};
+                __keys.finish()?;
+                __data.finish()?;
+                ::core::result::Result::Ok(__value)
+            }}
+        }}",
+        selector_raw[0], selector_raw[1], selector_raw[2], selector_raw[3],
+    );
+
+    for raw_event in [
+        format!("{}::types::Event", core_path()),
+        format!("{}::jsonrpc::models::Event", providers_path()),
+        format!("{}::jsonrpc::models::EmittedEvent", providers_path()),
+    ] {
+        for raw_event in [format!("&{raw_event}"), raw_event] {
+            output.push_str(&format!(
+                "impl ::core::convert::TryFrom<{raw_event}> for {ident} {{
+                    type Error = {event}::DecodeEventError;
+
+                    fn try_from(value: {raw_event}) -> ::core::result::Result<Self, Self::Error> {{
+                        <Self as {event}::StarknetEvent>::decode(&value.keys, &value.data)
+                    }}
+                }}"
+            ));
+        }
+    }
+
+    Ok(output)
+}
+
+/// The items of all `#[{name}(...)]` attributes.
+fn helper_attributes(attrs: &[syn::Attribute], name: &str) -> syn::Result<Vec<NestedMeta>> {
+    let mut metas = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("expected #[{name}(...)]"),
+                ))
+            }
+        }
+    }
+    Ok(metas)
+}
+
+/// The value of a `name = "..."` attribute item.
+fn string_value(value: &syn::MetaNameValue) -> syn::Result<String> {
+    match &value.lit {
+        Lit::Str(lit) => Ok(lit.value()),
+        lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn invalid_felt_error<T, E>(tokens: T, err: E) -> TokenStream
+where
+    T: syn::spanned::Spanned,
+    E: std::fmt::Display,
+{
+    syn::Error::new(tokens.span(), format!("invalid FieldElement value: {err}"))
+        .to_compile_error()
+        .into()
+}
+
+/// Expands to a constant expression for `value`.
+fn field_element_tokens(value: FieldElement) -> TokenStream {
+    let raw = value.into_mont();
 
     format!(
         "{}::from_mont([{}, {}, {}, {}])",
         field_element_path(),
-        felt_raw[0],
-        felt_raw[1],
-        felt_raw[2],
-        felt_raw[3],
+        raw[0],
+        raw[1],
+        raw[2],
+        raw[3],
     )
     .parse()
     .unwrap()
@@ -124,3 +609,23 @@ fn field_element_path() -> &'static str {
 fn field_element_path() -> &'static str {
     "::starknet::core::types::FieldElement"
 }
+
+#[cfg(feature = "use_imported_type")]
+fn core_path() -> &'static str {
+    "::starknet_core"
+}
+
+#[cfg(not(feature = "use_imported_type"))]
+fn core_path() -> &'static str {
+    "::starknet::core"
+}
+
+#[cfg(feature = "use_imported_type")]
+fn providers_path() -> &'static str {
+    "::starknet_providers"
+}
+
+#[cfg(not(feature = "use_imported_type"))]
+fn providers_path() -> &'static str {
+    "::starknet::providers"
+}
@@ -0,0 +1,94 @@
+//! The subset of the Solidity ABI used by the StarknetCore messaging functions, where every
+//! argument is a `uint256`, a `bytes32` or a `uint256[]`.
+
+use ethereum_types::{H256, U256};
+use sha3::{Digest, Keccak256};
+use starknet_core::types::FieldElement;
+
+const WORD_SIZE: usize = 32;
+
+/// A function or event parameter.
+pub(crate) enum Token<'a> {
+    Word([u8; WORD_SIZE]),
+    Array(&'a [FieldElement]),
+}
+
+impl<'a> From<FieldElement> for Token<'a> {
+    fn from(value: FieldElement) -> Self {
+        Self::Word(value.to_bytes_be())
+    }
+}
+
+impl<'a> From<H256> for Token<'a> {
+    fn from(value: H256) -> Self {
+        Self::Word(value.to_fixed_bytes())
+    }
+}
+
+impl<'a> From<&'a [FieldElement]> for Token<'a> {
+    fn from(value: &'a [FieldElement]) -> Self {
+        Self::Array(value)
+    }
+}
+
+pub(crate) fn keccak256(data: &[u8]) -> H256 {
+    H256::from_slice(&Keccak256::digest(data))
+}
+
+/// Encodes a call to the function with `signature`, e.g. `consumeMessageFromL2(uint256,uint256[])`.
+pub(crate) fn encode_call(signature: &str, tokens: &[Token<'_>]) -> Vec<u8> {
+    let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+
+    let mut tail = vec![];
+    for token in tokens.iter() {
+        match token {
+            Token::Word(word) => data.extend_from_slice(word),
+            Token::Array(items) => {
+                let offset = tokens.len() * WORD_SIZE + tail.len();
+                data.extend_from_slice(&word_from_usize(offset));
+                tail.extend_from_slice(&word_from_usize(items.len()));
+                for item in items.iter() {
+                    tail.extend_from_slice(&item.to_bytes_be());
+                }
+            }
+        }
+    }
+    data.extend_from_slice(&tail);
+
+    data
+}
+
+/// The `index`-th word of ABI encoded `data`.
+pub(crate) fn word(data: &[u8], index: usize) -> Option<&[u8]> {
+    data.get(index * WORD_SIZE..(index + 1) * WORD_SIZE)
+}
+
+pub(crate) fn decode_u256(data: &[u8], index: usize) -> Option<U256> {
+    word(data, index).map(U256::from_big_endian)
+}
+
+pub(crate) fn decode_felt(word: &[u8]) -> Option<FieldElement> {
+    FieldElement::from_bytes_be(&word.try_into().ok()?).ok()
+}
+
+/// Decodes the `uint256[]` whose offset is the `index`-th word of `data`.
+pub(crate) fn decode_felt_array(data: &[u8], index: usize) -> Option<Vec<FieldElement>> {
+    let offset = decode_usize(data, index)?;
+    let data = data.get(offset..)?;
+    let len = decode_usize(data, 0)?;
+
+    (1..=len)
+        .map(|index| word(data, index).and_then(decode_felt))
+        .collect()
+}
+
+fn decode_usize(data: &[u8], index: usize) -> Option<usize> {
+    let value = decode_u256(data, index)?;
+    (value <= U256::from(usize::MAX)).then(|| value.as_usize())
+}
+
+fn word_from_usize(value: usize) -> [u8; WORD_SIZE] {
+    let mut word = [0u8; WORD_SIZE];
+    U256::from(value).to_big_endian(&mut word);
+    word
+}
@@ -0,0 +1,224 @@
+use crate::{EthLog, EthereumClient};
+
+use alloy::{
+    primitives::{Address as AlloyAddress, Bytes, B256, U256 as AlloyU256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+    transports::TransportError,
+};
+use async_trait::async_trait;
+use ethereum_types::{Address, H256, U256};
+
+/// [EthereumClient] on top of an alloy [Provider]. Transactions are signed by the provider, so
+/// attach a wallet filler to it to sign locally, or leave it without one to send transactions
+/// from an account managed by the node.
+#[derive(Debug, Clone)]
+pub struct AlloyEthereumClient<P> {
+    provider: P,
+}
+
+impl<P> AlloyEthereumClient<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P> EthereumClient for AlloyEthereumClient<P>
+where
+    P: Provider + Send + Sync,
+{
+    type Error = TransportError;
+
+    async fn call(&self, to: Address, data: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        let request = TransactionRequest::default()
+            .to(to_alloy_address(to))
+            .input(Bytes::from(data).into());
+        Ok(self.provider.call(request).await?.to_vec())
+    }
+
+    async fn send_transaction(
+        &self,
+        to: Address,
+        data: Vec<u8>,
+        value: U256,
+    ) -> Result<H256, Self::Error> {
+        let request = TransactionRequest::default()
+            .to(to_alloy_address(to))
+            .input(Bytes::from(data).into())
+            .value(to_alloy_u256(value));
+        let pending = self.provider.send_transaction(request).await?;
+        Ok(from_alloy_b256(*pending.tx_hash()))
+    }
+
+    async fn transaction_logs(
+        &self,
+        transaction_hash: H256,
+    ) -> Result<Option<Vec<EthLog>>, Self::Error> {
+        let receipt = self
+            .provider
+            .get_transaction_receipt(to_alloy_b256(transaction_hash))
+            .await?;
+        Ok(receipt.map(|receipt| {
+            receipt
+                .inner
+                .logs()
+                .iter()
+                .map(|log| EthLog {
+                    address: from_alloy_address(log.inner.address),
+                    topics: log
+                        .inner
+                        .data
+                        .topics()
+                        .iter()
+                        .copied()
+                        .map(from_alloy_b256)
+                        .collect(),
+                    data: log.inner.data.data.to_vec(),
+                })
+                .collect()
+        }))
+    }
+}
+
+pub fn to_alloy_address(address: Address) -> AlloyAddress {
+    AlloyAddress::from(address.to_fixed_bytes())
+}
+
+pub fn from_alloy_address(address: AlloyAddress) -> Address {
+    Address::from(address.into_array())
+}
+
+pub fn to_alloy_b256(hash: H256) -> B256 {
+    B256::from(hash.to_fixed_bytes())
+}
+
+pub fn from_alloy_b256(hash: B256) -> H256 {
+    H256::from(hash.0)
+}
+
+pub fn to_alloy_u256(value: U256) -> AlloyU256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    AlloyU256::from_be_bytes(bytes)
+}
+
+pub fn from_alloy_u256(value: AlloyU256) -> U256 {
+    U256::from_big_endian(&value.to_be_bytes::<32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloy::{providers::ProviderBuilder, transports::mock::Asserter};
+    use serde_json::json;
+
+    fn mocked_client() -> (AlloyEthereumClient<impl Provider>, Asserter) {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        (AlloyEthereumClient::new(provider), asserter)
+    }
+
+    #[test]
+    fn test_conversions() {
+        let address: Address = "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e"
+            .parse()
+            .unwrap();
+        let hash = H256::from_low_u64_be(0x1234);
+        let value = U256::from_dec_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_alloy_address(address).to_string().to_lowercase(),
+            "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e"
+        );
+        assert_eq!(from_alloy_address(to_alloy_address(address)), address);
+        assert_eq!(from_alloy_b256(to_alloy_b256(hash)), hash);
+        assert_eq!(to_alloy_u256(value), AlloyU256::MAX);
+        assert_eq!(from_alloy_u256(to_alloy_u256(value)), value);
+        assert_eq!(to_alloy_u256(U256::from(1000)), AlloyU256::from(1000));
+    }
+
+    #[tokio::test]
+    async fn test_call_and_send() {
+        let (client, asserter) = mocked_client();
+
+        asserter.push_success(&"0x0102");
+        assert_eq!(
+            client.call(Address::zero(), vec![0xab]).await.unwrap(),
+            vec![1, 2]
+        );
+
+        let transaction_hash = B256::repeat_byte(1);
+        asserter.push_success(&transaction_hash);
+        assert_eq!(
+            client
+                .send_transaction(Address::zero(), vec![0xab], U256::from(1000))
+                .await
+                .unwrap(),
+            H256::repeat_byte(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_logs() {
+        let (client, asserter) = mocked_client();
+        let core_address = "0xde29d060d45901fb19ed6c6e959eb22d8626708e";
+
+        asserter.push_success(&json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "logs": [{
+                "address": core_address,
+                "topics": [format!("{:?}", H256::repeat_byte(2))],
+                "data": "0x0304",
+                "blockHash": format!("{:?}", H256::repeat_byte(3)),
+                "blockNumber": "0x1",
+                "transactionHash": format!("{:?}", H256::repeat_byte(1)),
+                "transactionIndex": "0x0",
+                "logIndex": "0x0",
+                "removed": false
+            }],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": format!("{:?}", H256::repeat_byte(1)),
+            "transactionIndex": "0x0",
+            "blockHash": format!("{:?}", H256::repeat_byte(3)),
+            "blockNumber": "0x1",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "from": "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e",
+            "to": core_address,
+            "contractAddress": null
+        }));
+        assert_eq!(
+            client
+                .transaction_logs(H256::repeat_byte(1))
+                .await
+                .unwrap()
+                .unwrap(),
+            vec![EthLog {
+                address: core_address.parse().unwrap(),
+                topics: vec![H256::repeat_byte(2)],
+                data: vec![3, 4],
+            }]
+        );
+
+        asserter.push_success(&serde_json::Value::Null);
+        assert!(client
+            .transaction_logs(H256::repeat_byte(1))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use ethereum_types::{Address, H256, U256};
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use url::Url;
+
+/// Access to an Ethereum node, for calling and transacting with the StarknetCore contract.
+///
+/// [HttpEthereumClient] implements it for nodes managing the sending account, and
+/// `AlloyEthereumClient` (behind the `alloy` feature) for alloy providers. Implement it on top
+/// of any other Ethereum library as needed.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait EthereumClient {
+    type Error: std::error::Error + Send + Sync;
+
+    /// Executes a read-only call against the latest block, returning the raw return data.
+    async fn call(&self, to: Address, data: Vec<u8>) -> Result<Vec<u8>, Self::Error>;
+
+    /// Sends a transaction from the client account, returning its hash.
+    async fn send_transaction(
+        &self,
+        to: Address,
+        data: Vec<u8>,
+        value: U256,
+    ) -> Result<H256, Self::Error>;
+
+    /// The logs emitted by a transaction, or `None` if it hasn't been mined yet.
+    async fn transaction_logs(
+        &self,
+        transaction_hash: H256,
+    ) -> Result<Option<Vec<EthLog>>, Self::Error>;
+}
+
+/// A log emitted by an Ethereum contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+/// [EthereumClient] over Ethereum JSON-RPC, sending transactions with `eth_sendTransaction` from
+/// an account managed by the node, e.g. a prefunded Anvil account.
+#[derive(Debug, Clone)]
+pub struct HttpEthereumClient {
+    client: Client,
+    url: Url,
+    from: Address,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpEthereumClientError {
+    #[error(transparent)]
+    Network(reqwest::Error),
+    #[error("JSON-RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("invalid hex data: {0}")]
+    InvalidHex(hex::FromHexError),
+    #[error("empty JSON-RPC result")]
+    EmptyResult,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcReceipt {
+    logs: Vec<RpcLog>,
+}
+
+#[derive(Deserialize)]
+struct RpcLog {
+    address: Address,
+    topics: Vec<H256>,
+    data: String,
+}
+
+impl HttpEthereumClient {
+    /// Sends transactions from `from`, which must be managed by the node at `url`.
+    pub fn new(url: impl Into<Url>, from: Address) -> Self {
+        Self::new_with_client(url, from, Client::new())
+    }
+
+    pub fn new_with_client(url: impl Into<Url>, from: Address, client: Client) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            from,
+        }
+    }
+
+    async fn send_request<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<Option<R>, HttpEthereumClientError>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let response: RpcResponse<R> = self
+            .client
+            .post(self.url.clone())
+            .json(&json!({
+                "id": 1,
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await
+            .map_err(HttpEthereumClientError::Network)?
+            .json()
+            .await
+            .map_err(HttpEthereumClientError::Network)?;
+
+        match response.error {
+            Some(error) => Err(HttpEthereumClientError::Rpc {
+                code: error.code,
+                message: error.message,
+            }),
+            None => Ok(response.result),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl EthereumClient for HttpEthereumClient {
+    type Error = HttpEthereumClientError;
+
+    async fn call(&self, to: Address, data: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        let result: Option<String> = self
+            .send_request(
+                "eth_call",
+                json!([{ "to": to, "data": encode_hex(&data) }, "latest"]),
+            )
+            .await?;
+        decode_hex(&result.ok_or(HttpEthereumClientError::EmptyResult)?)
+    }
+
+    async fn send_transaction(
+        &self,
+        to: Address,
+        data: Vec<u8>,
+        value: U256,
+    ) -> Result<H256, Self::Error> {
+        let result: Option<H256> = self
+            .send_request(
+                "eth_sendTransaction",
+                json!([{
+                    "from": self.from,
+                    "to": to,
+                    "data": encode_hex(&data),
+                    "value": value,
+                }]),
+            )
+            .await?;
+        result.ok_or(HttpEthereumClientError::EmptyResult)
+    }
+
+    async fn transaction_logs(
+        &self,
+        transaction_hash: H256,
+    ) -> Result<Option<Vec<EthLog>>, Self::Error> {
+        let receipt: Option<RpcReceipt> = self
+            .send_request("eth_getTransactionReceipt", json!([transaction_hash]))
+            .await?;
+        receipt
+            .map(|receipt| {
+                receipt
+                    .logs
+                    .into_iter()
+                    .map(|log| {
+                        Ok(EthLog {
+                            address: log.address,
+                            topics: log.topics,
+                            data: decode_hex(&log.data)?,
+                        })
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(data))
+}
+
+fn decode_hex(data: &str) -> Result<Vec<u8>, HttpEthereumClientError> {
+    hex::decode(data.trim_start_matches("0x")).map_err(HttpEthereumClientError::InvalidHex)
+}
@@ -0,0 +1,428 @@
+use crate::{
+    abi,
+    client::{EthLog, EthereumClient},
+};
+
+use ethereum_types::{Address, H256, U256};
+use starknet_core::types::{FieldElement, L1ToL2Message, L2ToL1Message, TransactionStatus};
+use starknet_providers::{Provider, ProviderError};
+
+const SEND_MESSAGE_TO_L2: &str = "sendMessageToL2(uint256,uint256,uint256[])";
+
+const CONSUME_MESSAGE_FROM_L2: &str = "consumeMessageFromL2(uint256,uint256[])";
+
+const L1_TO_L2_MESSAGES: &str = "l1ToL2Messages(bytes32)";
+
+const L2_TO_L1_MESSAGES: &str = "l2ToL1Messages(bytes32)";
+
+const LOG_MESSAGE_TO_L2: &str = "LogMessageToL2(address,uint256,uint256,uint256[],uint256,uint256)";
+
+/// The StarknetCore contract on Ethereum, through which messages between L1 and L2 are sent.
+///
+/// Messages are tracked end-to-end by their hashes, as computed by [L1ToL2Message::hash] and
+/// [L2ToL1Message::hash]:
+///
+/// ```ignore
+/// let tx_hash = core.send_message_to_l2(l2_contract, selector, &payload, fee).await?;
+/// // Once the L1 transaction is mined
+/// for sent in core.sent_messages(tx_hash).await?.unwrap_or_default() {
+///     let status = core.l1_to_l2_message_status(&sent.message).await?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StarknetCore<C> {
+    address: Address,
+    client: C,
+}
+
+/// An L1 -> L2 message sent in an L1 transaction.
+#[derive(Debug)]
+pub struct SentMessage {
+    pub message: L1ToL2Message,
+    /// The fee paid for the L2 execution of the message.
+    pub fee: U256,
+}
+
+/// Status of an L1 -> L2 message known to have been sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1ToL2MessageStatus {
+    /// Waiting to be consumed on L2, with the fee paid for it.
+    Pending { fee: U256 },
+    /// Consumed on L2, or cancelled on L1.
+    Consumed,
+}
+
+/// Status of an L2 -> L1 message sent in an accepted L2 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L2ToL1MessageStatus {
+    /// The L2 block sending the message is not accepted on L1 yet.
+    NotOnL1,
+    /// Ready to be consumed on L1.
+    Consumable,
+    /// Consumed on L1 by its recipient.
+    Consumed,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StarknetCoreError<C> {
+    #[error(transparent)]
+    Client(C),
+    #[error("invalid response from StarknetCore contract")]
+    InvalidResponse,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MessageTrackingError<C, P> {
+    #[error(transparent)]
+    StarknetCore(StarknetCoreError<C>),
+    #[error(transparent)]
+    Provider(ProviderError<P>),
+}
+
+impl<C> StarknetCore<C> {
+    pub fn new(address: Address, client: C) -> Self {
+        Self { address, client }
+    }
+
+    /// The StarknetCore contract on Ethereum mainnet.
+    pub fn mainnet(client: C) -> Self {
+        Self::new(
+            "0xc662c410c0ecf747543f5ba90660f6abebd9c8c4"
+                .parse()
+                .unwrap(),
+            client,
+        )
+    }
+
+    /// The StarknetCore contract on Goerli, used by Starknet testnet.
+    pub fn goerli(client: C) -> Self {
+        Self::new(
+            "0xde29d060d45901fb19ed6c6e959eb22d8626708e"
+                .parse()
+                .unwrap(),
+            client,
+        )
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+}
+
+impl<C> StarknetCore<C>
+where
+    C: EthereumClient + Sync,
+{
+    /// Sends a message to the L1 handler `selector` of L2 contract `to_address`, paying `fee` wei
+    /// for its execution on L2. Returns the hash of the L1 transaction.
+    pub async fn send_message_to_l2(
+        &self,
+        to_address: FieldElement,
+        selector: FieldElement,
+        payload: &[FieldElement],
+        fee: U256,
+    ) -> Result<H256, StarknetCoreError<C::Error>> {
+        let data = abi::encode_call(
+            SEND_MESSAGE_TO_L2,
+            &[to_address.into(), selector.into(), payload.into()],
+        );
+        self.client
+            .send_transaction(self.address, data, fee)
+            .await
+            .map_err(StarknetCoreError::Client)
+    }
+
+    /// The L1 -> L2 messages sent in an L1 transaction, or `None` if it hasn't been mined yet.
+    pub async fn sent_messages(
+        &self,
+        transaction_hash: H256,
+    ) -> Result<Option<Vec<SentMessage>>, StarknetCoreError<C::Error>> {
+        let logs = match self
+            .client
+            .transaction_logs(transaction_hash)
+            .await
+            .map_err(StarknetCoreError::Client)?
+        {
+            Some(logs) => logs,
+            None => return Ok(None),
+        };
+
+        let topic = abi::keccak256(LOG_MESSAGE_TO_L2.as_bytes());
+        logs.iter()
+            .filter(|log| log.address == self.address && log.topics.first() == Some(&topic))
+            .map(|log| decode_sent_message(log).ok_or(StarknetCoreError::InvalidResponse))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    pub async fn l1_to_l2_message_status(
+        &self,
+        message: &L1ToL2Message,
+    ) -> Result<L1ToL2MessageStatus, StarknetCoreError<C::Error>> {
+        // The mapping holds the fee plus one while the message is pending
+        let value = self.get_message(L1_TO_L2_MESSAGES, message.hash()).await?;
+        Ok(if value.is_zero() {
+            L1ToL2MessageStatus::Consumed
+        } else {
+            L1ToL2MessageStatus::Pending {
+                fee: value - U256::one(),
+            }
+        })
+    }
+
+    /// Consumes a message sent from L2 contract `from_address`. The client account must be the
+    /// recipient of the message, so this is mostly useful for messages sent to EOAs.
+    pub async fn consume_message_from_l2(
+        &self,
+        from_address: FieldElement,
+        payload: &[FieldElement],
+    ) -> Result<H256, StarknetCoreError<C::Error>> {
+        let data = abi::encode_call(
+            CONSUME_MESSAGE_FROM_L2,
+            &[from_address.into(), payload.into()],
+        );
+        self.client
+            .send_transaction(self.address, data, U256::zero())
+            .await
+            .map_err(StarknetCoreError::Client)
+    }
+
+    /// The number of copies of the message ready to be consumed on L1.
+    pub async fn l2_to_l1_message_count(
+        &self,
+        message: &L2ToL1Message,
+    ) -> Result<U256, StarknetCoreError<C::Error>> {
+        self.get_message(L2_TO_L1_MESSAGES, message.hash()).await
+    }
+
+    /// The L2 -> L1 messages sent in L2 transaction `transaction_hash`, along with their status.
+    pub async fn l2_to_l1_message_statuses<P>(
+        &self,
+        provider: &P,
+        transaction_hash: FieldElement,
+    ) -> Result<Vec<(L2ToL1Message, L2ToL1MessageStatus)>, MessageTrackingError<C::Error, P::Error>>
+    where
+        P: Provider + Sync,
+    {
+        let receipt = provider
+            .get_transaction_receipt(transaction_hash)
+            .await
+            .map_err(MessageTrackingError::Provider)?;
+
+        let mut statuses = vec![];
+        for message in receipt.l2_to_l1_messages.into_iter() {
+            let count = self
+                .l2_to_l1_message_count(&message)
+                .await
+                .map_err(MessageTrackingError::StarknetCore)?;
+
+            let status = if !count.is_zero() {
+                L2ToL1MessageStatus::Consumable
+            } else if receipt.status == TransactionStatus::AcceptedOnL1 {
+                L2ToL1MessageStatus::Consumed
+            } else {
+                L2ToL1MessageStatus::NotOnL1
+            };
+            statuses.push((message, status));
+        }
+
+        Ok(statuses)
+    }
+
+    async fn get_message(
+        &self,
+        signature: &str,
+        message_hash: H256,
+    ) -> Result<U256, StarknetCoreError<C::Error>> {
+        let data = abi::encode_call(signature, &[message_hash.into()]);
+        let result = self
+            .client
+            .call(self.address, data)
+            .await
+            .map_err(StarknetCoreError::Client)?;
+        abi::decode_u256(&result, 0).ok_or(StarknetCoreError::InvalidResponse)
+    }
+}
+
+/// Decodes a `LogMessageToL2` event, whose indexed topics are the sender, recipient and selector.
+fn decode_sent_message(log: &EthLog) -> Option<SentMessage> {
+    let from_address = Address::from_slice(&log.topics.get(1)?[12..]);
+    let to_address = abi::decode_felt(log.topics.get(2)?.as_bytes())?;
+    let selector = abi::decode_felt(log.topics.get(3)?.as_bytes())?;
+
+    let payload = abi::decode_felt_array(&log.data, 0)?;
+    let nonce = abi::decode_felt(abi::word(&log.data, 1)?)?;
+    let fee = abi::decode_u256(&log.data, 2)?;
+
+    Some(SentMessage {
+        message: L1ToL2Message {
+            from_address,
+            to_address,
+            selector,
+            payload,
+            nonce: Some(nonce),
+        },
+        fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock error")]
+    struct MockError;
+
+    /// Records the transactions sent, and serves calls and logs from fixed responses.
+    #[derive(Default)]
+    struct MockClient {
+        call_result: Vec<u8>,
+        logs: Option<Vec<EthLog>>,
+        sent: Mutex<Vec<(Vec<u8>, U256)>>,
+    }
+
+    #[async_trait]
+    impl EthereumClient for MockClient {
+        type Error = MockError;
+
+        async fn call(&self, _to: Address, _data: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+            Ok(self.call_result.clone())
+        }
+
+        async fn send_transaction(
+            &self,
+            _to: Address,
+            data: Vec<u8>,
+            value: U256,
+        ) -> Result<H256, Self::Error> {
+            self.sent.lock().unwrap().push((data, value));
+            Ok(H256::repeat_byte(1))
+        }
+
+        async fn transaction_logs(
+            &self,
+            _transaction_hash: H256,
+        ) -> Result<Option<Vec<EthLog>>, Self::Error> {
+            Ok(self.logs.clone())
+        }
+    }
+
+    fn word(value: u64) -> Vec<u8> {
+        FieldElement::from(value).to_bytes_be().to_vec()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_selectors() {
+        for (signature, selector) in [
+            (SEND_MESSAGE_TO_L2, "3e3aa6c5"),
+            (CONSUME_MESSAGE_FROM_L2, "2c9dd5c0"),
+            (L1_TO_L2_MESSAGES, "77c7d7a9"),
+            (L2_TO_L1_MESSAGES, "a46efaf3"),
+        ] {
+            assert_eq!(
+                hex::encode(&abi::keccak256(signature.as_bytes())[..4]),
+                selector
+            );
+        }
+        assert_eq!(
+            format!("{:x}", abi::keccak256(LOG_MESSAGE_TO_L2.as_bytes())),
+            "db80dd488acf86d17c747445b0eabb5d57c541d3bd7b6b87af987858e5066b2b"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_message_to_l2() {
+        let core = StarknetCore::mainnet(MockClient::default());
+
+        core.send_message_to_l2(
+            FieldElement::from(0x1234u64),
+            FieldElement::from(0x5678u64),
+            &[FieldElement::ONE, FieldElement::TWO],
+            U256::from(1000),
+        )
+        .await
+        .unwrap();
+
+        let mut expected = hex::decode("3e3aa6c5").unwrap();
+        for value in [0x1234, 0x5678, 0x60, 2, 1, 2] {
+            expected.extend(word(value));
+        }
+        assert_eq!(
+            core.client().sent.lock().unwrap()[..],
+            [(expected, U256::from(1000))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sent_messages() {
+        let from_address: Address = "0xc3511006c04ef1d78af4c8e0e74ec18a6e64ff9e"
+            .parse()
+            .unwrap();
+        let core = StarknetCore::goerli(MockClient {
+            logs: Some(vec![EthLog {
+                address: StarknetCore::goerli(()).address(),
+                topics: vec![
+                    abi::keccak256(LOG_MESSAGE_TO_L2.as_bytes()),
+                    H256::from(from_address),
+                    H256::from_low_u64_be(0x1234),
+                    H256::from_low_u64_be(0x5678),
+                ],
+                data: [0x60, 7, 1000, 1, 42].into_iter().flat_map(word).collect(),
+            }]),
+            ..Default::default()
+        });
+
+        let sent = core
+            .sent_messages(H256::repeat_byte(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].fee, U256::from(1000));
+        assert_eq!(sent[0].message.from_address, from_address);
+        assert_eq!(sent[0].message.to_address, FieldElement::from(0x1234u64));
+        assert_eq!(sent[0].message.selector, FieldElement::from(0x5678u64));
+        assert_eq!(sent[0].message.payload, vec![FieldElement::from(42u64)]);
+        assert_eq!(sent[0].message.nonce, Some(FieldElement::from(7u64)));
+    }
+
+    #[tokio::test]
+    async fn test_l1_to_l2_message_status() {
+        let message = L1ToL2Message {
+            from_address: Address::zero(),
+            to_address: FieldElement::ONE,
+            selector: FieldElement::TWO,
+            payload: vec![],
+            nonce: Some(FieldElement::ZERO),
+        };
+
+        let pending = StarknetCore::mainnet(MockClient {
+            call_result: word(1001),
+            ..Default::default()
+        });
+        assert_eq!(
+            pending.l1_to_l2_message_status(&message).await.unwrap(),
+            L1ToL2MessageStatus::Pending {
+                fee: U256::from(1000)
+            }
+        );
+
+        let consumed = StarknetCore::mainnet(MockClient {
+            call_result: word(0),
+            ..Default::default()
+        });
+        assert_eq!(
+            consumed.l1_to_l2_message_status(&message).await.unwrap(),
+            L1ToL2MessageStatus::Consumed
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! L1 <-> L2 messaging through the StarknetCore contract on Ethereum.
+
+mod abi;
+
+#[cfg(feature = "alloy")]
+mod alloy_client;
+#[cfg(feature = "alloy")]
+pub use alloy_client::{
+    from_alloy_address, from_alloy_b256, from_alloy_u256, to_alloy_address, to_alloy_b256,
+    to_alloy_u256, AlloyEthereumClient,
+};
+
+mod client;
+pub use client::{EthLog, EthereumClient, HttpEthereumClient, HttpEthereumClientError};
+
+mod core_contract;
+pub use core_contract::{
+    L1ToL2MessageStatus, L2ToL1MessageStatus, MessageTrackingError, SentMessage, StarknetCore,
+    StarknetCoreError,
+};
@@ -0,0 +1,85 @@
+use crate::{format_felt, parse_felt, parse_felts, MobileError, RpcProvider, Signer};
+
+use starknet_accounts::{Account as _, SingleOwnerAccount};
+use starknet_core::utils::get_selector_from_name;
+use starknet_providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet_signers::LocalWallet;
+use std::sync::Arc;
+
+/// An account controlled by a single [Signer], sending transactions through a [RpcProvider].
+#[derive(Debug, uniffi::Object)]
+pub struct Account {
+    inner: SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct Call {
+    pub to: String,
+    pub function_name: String,
+    pub calldata: Vec<String>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Account {
+    #[uniffi::constructor]
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        signer: Arc<Signer>,
+        address: String,
+        chain_id: String,
+    ) -> Result<Arc<Self>, MobileError> {
+        Ok(Arc::new(Self {
+            inner: SingleOwnerAccount::new(
+                provider.client(),
+                signer.wallet(),
+                parse_felt(&address)?,
+                parse_felt(&chain_id)?,
+            ),
+        }))
+    }
+
+    pub fn address(&self) -> String {
+        format_felt(self.inner.address())
+    }
+
+    pub fn chain_id(&self) -> String {
+        format_felt(self.inner.chain_id())
+    }
+
+    /// Estimates the overall fee of executing `calls`, in wei.
+    pub async fn estimate_fee(&self, calls: Vec<Call>) -> Result<u64, MobileError> {
+        Ok(self
+            .inner
+            .execute(parse_calls(calls)?)
+            .estimate_fee()
+            .await
+            .map_err(|err| MobileError::Account(err.to_string()))?
+            .overall_fee)
+    }
+
+    /// Sends an invoke transaction executing `calls`, returning its transaction hash.
+    pub async fn execute(&self, calls: Vec<Call>) -> Result<String, MobileError> {
+        Ok(format_felt(
+            self.inner
+                .execute(parse_calls(calls)?)
+                .send()
+                .await
+                .map_err(|err| MobileError::Account(err.to_string()))?
+                .transaction_hash,
+        ))
+    }
+}
+
+fn parse_calls(calls: Vec<Call>) -> Result<Vec<starknet_accounts::Call>, MobileError> {
+    calls
+        .into_iter()
+        .map(|call| {
+            Ok(starknet_accounts::Call {
+                to: parse_felt(&call.to)?,
+                selector: get_selector_from_name(&call.function_name)
+                    .map_err(|_| MobileError::InvalidFunctionName(call.function_name))?,
+                calldata: parse_felts(&call.calldata)?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,52 @@
+//! Signers, accounts and a JSON-RPC provider exported to Kotlin and Swift with
+//! [UniFFI](https://mozilla.github.io/uniffi-rs/): objects are shared as [Arc](std::sync::Arc),
+//! and field elements are passed as `0x`-prefixed hex strings.
+
+use starknet_core::types::FieldElement;
+
+mod account;
+pub use account::{Account, Call};
+
+mod provider;
+pub use provider::RpcProvider;
+
+mod signer;
+pub use signer::{verify_signature, Signature, Signer};
+
+uniffi::setup_scaffolding!();
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum MobileError {
+    #[error("invalid field element: {0}")]
+    InvalidFelt(String),
+    #[error("invalid function name: {0}")]
+    InvalidFunctionName(String),
+    #[error("invalid url: {0}")]
+    InvalidUrl(String),
+    #[error("keystore error: {0}")]
+    Keystore(String),
+    #[error("signing error: {0}")]
+    Signing(String),
+    #[error("provider error: {0}")]
+    Provider(String),
+    #[error("account error: {0}")]
+    Account(String),
+}
+
+pub(crate) fn parse_felt(value: &str) -> Result<FieldElement, MobileError> {
+    if value.starts_with("0x") {
+        FieldElement::from_hex_be(value)
+    } else {
+        FieldElement::from_dec_str(value)
+    }
+    .map_err(|_| MobileError::InvalidFelt(value.to_owned()))
+}
+
+pub(crate) fn parse_felts(values: &[String]) -> Result<Vec<FieldElement>, MobileError> {
+    values.iter().map(|value| parse_felt(value)).collect()
+}
+
+pub(crate) fn format_felt(value: FieldElement) -> String {
+    format!("{value:#x}")
+}
@@ -0,0 +1,131 @@
+use crate::{format_felt, parse_felt, parse_felts, MobileError};
+
+use starknet_core::utils::get_selector_from_name;
+use starknet_providers::jsonrpc::{
+    models::{BlockId, BlockTag, FunctionCall},
+    HttpTransport, JsonRpcClient,
+};
+use std::sync::Arc;
+use url::Url;
+
+/// A JSON-RPC provider querying the latest block.
+#[derive(Debug, uniffi::Object)]
+pub struct RpcProvider {
+    url: Url,
+    client: JsonRpcClient<HttpTransport>,
+}
+
+const LATEST: BlockId = BlockId::Tag(BlockTag::Latest);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl RpcProvider {
+    #[uniffi::constructor]
+    pub fn new(url: String) -> Result<Arc<Self>, MobileError> {
+        let url = Url::parse(&url).map_err(|_| MobileError::InvalidUrl(url))?;
+        Ok(Arc::new(Self {
+            client: JsonRpcClient::new(HttpTransport::new(url.clone())),
+            url,
+        }))
+    }
+
+    pub fn url(&self) -> String {
+        self.url.to_string()
+    }
+
+    pub async fn block_number(&self) -> Result<u64, MobileError> {
+        self.client.block_number().await.map_err(provider_error)
+    }
+
+    pub async fn chain_id(&self) -> Result<String, MobileError> {
+        Ok(format_felt(
+            self.client.chain_id().await.map_err(provider_error)?,
+        ))
+    }
+
+    /// Calls the view function `function_name` of `contract_address`.
+    pub async fn call(
+        &self,
+        contract_address: String,
+        function_name: String,
+        calldata: Vec<String>,
+    ) -> Result<Vec<String>, MobileError> {
+        let request = FunctionCall {
+            contract_address: parse_felt(&contract_address)?,
+            entry_point_selector: get_selector_from_name(&function_name)
+                .map_err(|_| MobileError::InvalidFunctionName(function_name))?,
+            calldata: parse_felts(&calldata)?,
+        };
+
+        Ok(self
+            .client
+            .call(request, &LATEST)
+            .await
+            .map_err(provider_error)?
+            .into_iter()
+            .map(format_felt)
+            .collect())
+    }
+
+    pub async fn get_nonce(&self, contract_address: String) -> Result<String, MobileError> {
+        Ok(format_felt(
+            self.client
+                .get_nonce(&LATEST, parse_felt(&contract_address)?)
+                .await
+                .map_err(provider_error)?,
+        ))
+    }
+
+    pub async fn get_storage_at(
+        &self,
+        contract_address: String,
+        key: String,
+    ) -> Result<String, MobileError> {
+        Ok(format_felt(
+            self.client
+                .get_storage_at(parse_felt(&contract_address)?, parse_felt(&key)?, &LATEST)
+                .await
+                .map_err(provider_error)?,
+        ))
+    }
+}
+
+impl RpcProvider {
+    /// A client of the same endpoint, for accounts to own.
+    pub(crate) fn client(&self) -> JsonRpcClient<HttpTransport> {
+        JsonRpcClient::new(HttpTransport::new(self.url.clone()))
+    }
+}
+
+fn provider_error<E>(err: E) -> MobileError
+where
+    E: std::fmt::Display,
+{
+    MobileError::Provider(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_provider() {
+        let provider = RpcProvider::new("http://localhost:9545".into()).unwrap();
+        assert_eq!(provider.url(), "http://localhost:9545/");
+
+        assert!(matches!(
+            RpcProvider::new("localhost".into()),
+            Err(MobileError::InvalidUrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_provider() {
+        // Nothing listens on the discard port, so the connection is refused right away
+        let provider = RpcProvider::new("http://127.0.0.1:9".into()).unwrap();
+
+        assert!(matches!(
+            provider.block_number().await,
+            Err(MobileError::Provider(_))
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{format_felt, parse_felt, MobileError};
+
+use starknet_signers::{LocalWallet, SigningKey, VerifyingKey};
+use std::sync::Arc;
+
+/// A Stark key held in memory.
+#[derive(Debug, uniffi::Object)]
+pub struct Signer {
+    key: SigningKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct Signature {
+    pub r: String,
+    pub s: String,
+}
+
+#[uniffi::export]
+impl Signer {
+    #[uniffi::constructor]
+    pub fn from_private_key(private_key: String) -> Result<Arc<Self>, MobileError> {
+        Ok(Arc::new(Self {
+            key: SigningKey::from_secret_scalar(parse_felt(&private_key)?),
+        }))
+    }
+
+    #[uniffi::constructor]
+    pub fn random() -> Arc<Self> {
+        Arc::new(Self {
+            key: SigningKey::from_random(),
+        })
+    }
+
+    /// Loads the key from the Web3 Secret Storage file at `path`.
+    #[uniffi::constructor]
+    pub fn from_keystore(path: String, password: String) -> Result<Arc<Self>, MobileError> {
+        Ok(Arc::new(Self {
+            key: SigningKey::from_keystore(path, &password)
+                .map_err(|err| MobileError::Keystore(err.to_string()))?,
+        }))
+    }
+
+    /// Saves the key to `path` as a Web3 Secret Storage file encrypted with `password`.
+    pub fn save_as_keystore(&self, path: String, password: String) -> Result<(), MobileError> {
+        self.key
+            .save_as_keystore(path, &password)
+            .map_err(|err| MobileError::Keystore(err.to_string()))
+    }
+
+    pub fn public_key(&self) -> String {
+        format_felt(self.key.verifying_key().scalar())
+    }
+
+    pub fn sign_hash(&self, hash: String) -> Result<Signature, MobileError> {
+        let signature = self
+            .key
+            .sign(&parse_felt(&hash)?)
+            .map_err(|err| MobileError::Signing(err.to_string()))?;
+
+        Ok(Signature {
+            r: format_felt(signature.r),
+            s: format_felt(signature.s),
+        })
+    }
+}
+
+impl Signer {
+    pub(crate) fn wallet(&self) -> LocalWallet {
+        LocalWallet::from_signing_key(SigningKey::from_secret_scalar(self.key.secret_scalar()))
+    }
+}
+
+#[uniffi::export]
+pub fn verify_signature(
+    public_key: String,
+    hash: String,
+    signature: Signature,
+) -> Result<bool, MobileError> {
+    VerifyingKey::from_scalar(parse_felt(&public_key)?)
+        .verify(
+            &parse_felt(&hash)?,
+            &starknet_core::crypto::Signature {
+                r: parse_felt(&signature.r)?,
+                s: parse_felt(&signature.s)?,
+            },
+        )
+        .map_err(|err| MobileError::Signing(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = Signer::from_private_key("0x1".into()).unwrap();
+        let signature = signer.sign_hash("0x1234".into()).unwrap();
+
+        assert!(verify_signature(signer.public_key(), "0x1234".into(), signature.clone()).unwrap());
+        assert!(!verify_signature(signer.public_key(), "0x1235".into(), signature).unwrap());
+        assert!(matches!(
+            Signer::from_private_key("0xzz".into()),
+            Err(MobileError::InvalidFelt(_))
+        ));
+    }
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let path = std::env::temp_dir()
+            .join("starknet-mobile-test-keystore.json")
+            .to_string_lossy()
+            .into_owned();
+
+        let signer = Signer::random();
+        signer
+            .save_as_keystore(path.clone(), "password".into())
+            .unwrap();
+
+        let loaded = Signer::from_keystore(path.clone(), "password".into()).unwrap();
+        assert_eq!(loaded.public_key(), signer.public_key());
+        assert!(matches!(
+            Signer::from_keystore(path.clone(), "wrong".into()),
+            Err(MobileError::Keystore(_))
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
-use starknet_core::{serde::unsigned_field_element::UfeHex, types::FieldElement};
+use starknet_core::{
+    serde::unsigned_field_element::UfeHex,
+    types::{FieldElement, StorageProof},
+};
 
 use crate::jsonrpc::models::*;
 
@@ -73,6 +76,8 @@ pub enum JsonRpcMethod {
     AddDeployTransaction,
     #[serde(rename = "starknet_addDeployAccountTransaction")]
     AddDeployAccountTransaction,
+    #[serde(rename = "pathfinder_getProof")]
+    GetProof,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -411,6 +416,26 @@ where
             .0)
     }
 
+    /// Get Merkle-Patricia proofs of the storage values at the given keys, to be verified with
+    /// [verify_contract_storage](starknet_core::proof::verify_contract_storage). Only supported
+    /// by pathfinder nodes.
+    pub async fn get_proof(
+        &self,
+        block_id: &BlockId,
+        contract_address: FieldElement,
+        keys: &[FieldElement],
+    ) -> Result<StorageProof, JsonRpcClientError<T::Error>> {
+        self.send_request(
+            JsonRpcMethod::GetProof,
+            [
+                serde_json::to_value(block_id)?,
+                serde_json::to_value(Felt(contract_address))?,
+                serde_json::to_value(FeltArray(keys.to_vec()))?,
+            ],
+        )
+        .await
+    }
+
     /// Submit a new transaction to be added to the chain
     pub async fn add_invoke_transaction(
         &self,
@@ -468,11 +493,13 @@ where
         P: Serialize + Send,
         R: DeserializeOwned,
     {
-        match self
-            .transport
-            .send_request(method, params)
-            .await
-            .map_err(JsonRpcClientError::TransportError)?
+        match instrument!(
+            self.transport.send_request(method, params),
+            "jsonrpc_request",
+            method = ?method
+        )
+        .await
+        .map_err(JsonRpcClientError::TransportError)?
         {
             JsonRpcResponse::Success { result, .. } => Ok(result),
             JsonRpcResponse::Error { error, .. } => {
@@ -5,10 +5,11 @@ use crate::{
 
 use async_trait::async_trait;
 use starknet_core::types::{
-    AccountTransaction, AddTransactionResult, Block, BlockId, BlockTraces, CallContractResult,
-    CallFunction, CallL1Handler, ContractAddresses, ContractArtifact, ContractCode, FeeEstimate,
-    FieldElement, StateUpdate, TransactionInfo, TransactionReceipt, TransactionRequest,
-    TransactionSimulationInfo, TransactionStatusInfo, TransactionTrace,
+    AccountTransaction, AddTransactionResult, Block, BlockId, BlockSignature, BlockTraces,
+    CallContractResult, CallFunction, CallL1Handler, ContractAddresses, ContractArtifact,
+    ContractCode, FeeEstimate, FieldElement, StarknetError, StateUpdate, TransactionInfo,
+    TransactionReceipt, TransactionRequest, TransactionSimulationInfo, TransactionStatusInfo,
+    TransactionTrace,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -118,6 +119,13 @@ where
         Err(ProviderError::Other(Self::Error::NotSupported))
     }
 
+    async fn get_block_signature(
+        &self,
+        _block_identifier: BlockId,
+    ) -> Result<BlockSignature, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
     async fn get_state_update(
         &self,
         _block_identifier: BlockId,
@@ -143,10 +151,12 @@ where
 
     async fn get_class_hash_at(
         &self,
-        _contract_address: FieldElement,
-        _block_identifier: BlockId,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
     ) -> Result<FieldElement, ProviderError<Self::Error>> {
-        Err(ProviderError::Other(Self::Error::NotSupported))
+        self.get_class_hash_at(&block_identifier.into(), contract_address)
+            .await
+            .map_err(|err| err.into())
     }
 
     async fn get_class_by_hash(
@@ -156,13 +166,22 @@ where
         Err(ProviderError::Other(Self::Error::NotSupported))
     }
 
+    async fn get_raw_class_by_hash(
+        &self,
+        _class_hash: FieldElement,
+    ) -> Result<serde_json::Value, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
     async fn get_storage_at(
         &self,
-        _contract_address: FieldElement,
-        _key: FieldElement,
-        _block_identifier: BlockId,
+        contract_address: FieldElement,
+        key: FieldElement,
+        block_identifier: BlockId,
     ) -> Result<FieldElement, ProviderError<Self::Error>> {
-        Err(ProviderError::Other(Self::Error::NotSupported))
+        self.get_storage_at(contract_address, key, &block_identifier.into())
+            .await
+            .map_err(|err| err.into())
     }
 
     async fn get_nonce(
@@ -251,10 +270,48 @@ impl<T> From<JsonRpcClientError<T>> for ProviderError<JsonRpcProviderError<T>> {
             }
             JsonRpcClientError::RpcError(err) => match err {
                 super::RpcError::Code(code) => ProviderError::StarknetError(code.into()),
-                super::RpcError::Unknown(err) => {
-                    ProviderError::Other(JsonRpcProviderError::UnknownRpcError(err))
-                }
+                // Codes of the specification the client models don't cover yet
+                super::RpcError::Unknown(err) => match StarknetError::from_code(err.code) {
+                    Some(sn_err) => ProviderError::StarknetError(sn_err),
+                    None => ProviderError::Other(JsonRpcProviderError::UnknownRpcError(err)),
+                },
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonrpc::RpcError;
+    use starknet_core::types::AsStarknetError;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_rpc_error_to_starknet_error() {
+        let known: ProviderError<JsonRpcProviderError<std::convert::Infallible>> =
+            JsonRpcClientError::RpcError(RpcError::Code(ErrorCode::BlockNotFound)).into();
+        assert_eq!(known.starknet_error(), Some(StarknetError::BlockNotFound));
+        assert_eq!(known.starknet_error_code(), Some(24));
+
+        let unmodeled: ProviderError<JsonRpcProviderError<std::convert::Infallible>> =
+            JsonRpcClientError::RpcError(RpcError::Unknown(JsonRpcError {
+                code: 50,
+                message: "Invalid contract class".into(),
+            }))
+            .into();
+        assert_eq!(
+            unmodeled.starknet_error(),
+            Some(StarknetError::InvalidContractClass)
+        );
+
+        let unknown: ProviderError<JsonRpcProviderError<std::convert::Infallible>> =
+            JsonRpcClientError::RpcError(RpcError::Unknown(JsonRpcError {
+                code: -32603,
+                message: "Internal error".into(),
+            }))
+            .into();
+        assert_eq!(unknown.starknet_error(), None);
+    }
+}
@@ -1,5 +1,21 @@
 #![doc = include_str!("../README.md")]
 
+/// Instruments a future with a span when the `tracing` feature is enabled. Span fields are not
+/// evaluated otherwise.
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {{
+        let span = tracing::info_span!($($span)+);
+        tracing::Instrument::instrument($future, span)
+    }};
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {
+        $future
+    };
+}
+
 mod provider;
 pub use provider::{Provider, ProviderError};
 
@@ -9,3 +25,14 @@ pub use sequencer_gateway::{
 };
 
 pub mod jsonrpc;
+
+#[cfg(feature = "mock")]
+mod mock;
+#[cfg(feature = "mock")]
+pub use mock::{ChainFixture, MockProvider, MockProviderError};
+
+mod local;
+pub use local::{
+    ExecutionBackend, ExecutionError, LocalExecutionProvider, LocalExecutionProviderError,
+    LocalState, StateReader,
+};
@@ -0,0 +1,724 @@
+use crate::{Provider, ProviderError};
+
+use async_trait::async_trait;
+use starknet_core::types::{
+    AccountTransaction, AddTransactionResult, Block, BlockId, BlockSignature, BlockTraces,
+    CallContractResult, CallFunction, CallL1Handler, ContractAddresses, ContractArtifact,
+    ContractCode, FeeEstimate, FieldElement, StarknetError, StateUpdate, TransactionInfo,
+    TransactionReceipt, TransactionRequest, TransactionSimulationInfo, TransactionStatusInfo,
+    TransactionTrace,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+/// State of the network read by an [ExecutionBackend] while executing a transaction.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait StateReader {
+    type Error: Error + Send;
+
+    async fn get_storage_at(
+        &self,
+        contract_address: FieldElement,
+        key: FieldElement,
+    ) -> Result<FieldElement, Self::Error>;
+
+    async fn get_nonce(&self, contract_address: FieldElement) -> Result<FieldElement, Self::Error>;
+
+    async fn get_class_hash_at(
+        &self,
+        contract_address: FieldElement,
+    ) -> Result<FieldElement, Self::Error>;
+
+    async fn get_class(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<Arc<ContractArtifact>, Self::Error>;
+}
+
+/// A Cairo execution engine running transactions locally against a [StateReader], instead of
+/// having the sequencer simulate them.
+///
+/// No engine is bundled with this crate: implementations wrap one such as blockifier, converting
+/// transactions and state reads to and from its types.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ExecutionBackend {
+    async fn estimate_fee<S>(
+        &self,
+        state: &S,
+        tx: &AccountTransaction,
+    ) -> Result<FeeEstimate, ExecutionError<S::Error>>
+    where
+        S: StateReader + Sync;
+
+    async fn simulate_transaction<S>(
+        &self,
+        state: &S,
+        tx: &AccountTransaction,
+    ) -> Result<TransactionSimulationInfo, ExecutionError<S::Error>>
+    where
+        S: StateReader + Sync;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExecutionError<S> {
+    #[error("failed to read state: {0}")]
+    State(#[source] S),
+    /// The transaction failed, as the sequencer would have rejected it.
+    #[error(transparent)]
+    StarknetError(StarknetError),
+    #[error(transparent)]
+    Backend(Box<dyn Error + Send + Sync>),
+}
+
+/// State read from a provider as of a fixed block, and cached so that each value is only fetched
+/// once. Values can also be set beforehand, overriding the state of the network.
+#[derive(Debug)]
+pub struct LocalState<P> {
+    provider: P,
+    block_id: BlockId,
+    storage: Mutex<HashMap<(FieldElement, FieldElement), FieldElement>>,
+    nonces: Mutex<HashMap<FieldElement, FieldElement>>,
+    class_hashes: Mutex<HashMap<FieldElement, FieldElement>>,
+    classes: Mutex<HashMap<FieldElement, Arc<ContractArtifact>>>,
+}
+
+/// A [Provider] estimating fees and simulating transactions with an [ExecutionBackend], against
+/// the [LocalState] of the block it was created for.
+///
+/// Estimates and simulations in other blocks, along with every other request, are sent to the
+/// wrapped provider.
+#[derive(Debug)]
+pub struct LocalExecutionProvider<P, B> {
+    state: LocalState<P>,
+    backend: B,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalExecutionProviderError<E> {
+    #[error(transparent)]
+    Provider(E),
+    #[error(transparent)]
+    Backend(Box<dyn Error + Send + Sync>),
+}
+
+impl<P> LocalState<P> {
+    pub fn new(provider: P, block_id: BlockId) -> Self {
+        Self {
+            provider,
+            block_id,
+            storage: Mutex::new(HashMap::new()),
+            nonces: Mutex::new(HashMap::new()),
+            class_hashes: Mutex::new(HashMap::new()),
+            classes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    pub fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+
+    pub fn set_storage(
+        &self,
+        contract_address: FieldElement,
+        key: FieldElement,
+        value: FieldElement,
+    ) {
+        self.storage
+            .lock()
+            .unwrap()
+            .insert((contract_address, key), value);
+    }
+
+    pub fn set_nonce(&self, contract_address: FieldElement, nonce: FieldElement) {
+        self.nonces.lock().unwrap().insert(contract_address, nonce);
+    }
+
+    pub fn set_class_hash_at(&self, contract_address: FieldElement, class_hash: FieldElement) {
+        self.class_hashes
+            .lock()
+            .unwrap()
+            .insert(contract_address, class_hash);
+    }
+
+    pub fn set_class(&self, class_hash: FieldElement, contract_class: Arc<ContractArtifact>) {
+        self.classes
+            .lock()
+            .unwrap()
+            .insert(class_hash, contract_class);
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P> StateReader for LocalState<P>
+where
+    P: Provider + Sync + Send,
+{
+    type Error = ProviderError<P::Error>;
+
+    async fn get_storage_at(
+        &self,
+        contract_address: FieldElement,
+        key: FieldElement,
+    ) -> Result<FieldElement, Self::Error> {
+        if let Some(value) = self.storage.lock().unwrap().get(&(contract_address, key)) {
+            return Ok(*value);
+        }
+
+        let value = self
+            .provider
+            .get_storage_at(contract_address, key, self.block_id)
+            .await?;
+        self.set_storage(contract_address, key, value);
+        Ok(value)
+    }
+
+    async fn get_nonce(&self, contract_address: FieldElement) -> Result<FieldElement, Self::Error> {
+        if let Some(nonce) = self.nonces.lock().unwrap().get(&contract_address) {
+            return Ok(*nonce);
+        }
+
+        let nonce = self
+            .provider
+            .get_nonce(contract_address, self.block_id)
+            .await?;
+        self.set_nonce(contract_address, nonce);
+        Ok(nonce)
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        contract_address: FieldElement,
+    ) -> Result<FieldElement, Self::Error> {
+        if let Some(class_hash) = self.class_hashes.lock().unwrap().get(&contract_address) {
+            return Ok(*class_hash);
+        }
+
+        let class_hash = self
+            .provider
+            .get_class_hash_at(contract_address, self.block_id)
+            .await?;
+        self.set_class_hash_at(contract_address, class_hash);
+        Ok(class_hash)
+    }
+
+    async fn get_class(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<Arc<ContractArtifact>, Self::Error> {
+        if let Some(contract_class) = self.classes.lock().unwrap().get(&class_hash) {
+            return Ok(contract_class.clone());
+        }
+
+        let contract_class = Arc::new(self.provider.get_class_by_hash(class_hash).await?);
+        self.set_class(class_hash, contract_class.clone());
+        Ok(contract_class)
+    }
+}
+
+impl<P, B> LocalExecutionProvider<P, B> {
+    /// Executes transactions locally in block `block_id`, which should be a fixed block rather
+    /// than [BlockId::Latest] or [BlockId::Pending] for the cached state to remain accurate.
+    pub fn new(provider: P, backend: B, block_id: BlockId) -> Self {
+        Self::with_state(LocalState::new(provider, block_id), backend)
+    }
+
+    pub fn with_state(state: LocalState<P>, backend: B) -> Self {
+        Self { state, backend }
+    }
+
+    pub fn state(&self) -> &LocalState<P> {
+        &self.state
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P, B> Provider for LocalExecutionProvider<P, B>
+where
+    P: Provider + Sync + Send,
+    B: ExecutionBackend + Sync + Send,
+{
+    type Error = LocalExecutionProviderError<P::Error>;
+
+    async fn add_transaction(
+        &self,
+        tx: TransactionRequest,
+    ) -> Result<AddTransactionResult, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .add_transaction(tx)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_contract_addresses(
+        &self,
+    ) -> Result<ContractAddresses, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_contract_addresses()
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn call_contract(
+        &self,
+        call_function: CallFunction,
+        block_identifier: BlockId,
+    ) -> Result<CallContractResult, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .call_contract(call_function, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn estimate_fee(
+        &self,
+        tx: AccountTransaction,
+        block_identifier: BlockId,
+    ) -> Result<FeeEstimate, ProviderError<Self::Error>> {
+        if block_identifier != self.state.block_id {
+            return self
+                .state
+                .provider
+                .estimate_fee(tx, block_identifier)
+                .await
+                .map_err(map_provider_error);
+        }
+
+        self.backend
+            .estimate_fee(&self.state, &tx)
+            .await
+            .map_err(map_execution_error)
+    }
+
+    /// Transactions are estimated independently, without applying the state changes of the
+    /// previous ones when executed locally.
+    async fn estimate_fee_bulk(
+        &self,
+        txs: &[AccountTransaction],
+        block_identifier: BlockId,
+    ) -> Result<Vec<FeeEstimate>, ProviderError<Self::Error>> {
+        if block_identifier != self.state.block_id {
+            return self
+                .state
+                .provider
+                .estimate_fee_bulk(txs, block_identifier)
+                .await
+                .map_err(map_provider_error);
+        }
+
+        let mut estimates = vec![];
+        for tx in txs.iter() {
+            estimates.push(
+                self.backend
+                    .estimate_fee(&self.state, tx)
+                    .await
+                    .map_err(map_execution_error)?,
+            );
+        }
+        Ok(estimates)
+    }
+
+    async fn estimate_message_fee(
+        &self,
+        call_l1_handler: CallL1Handler,
+        block_identifier: BlockId,
+    ) -> Result<FeeEstimate, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .estimate_message_fee(call_l1_handler, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn simulate_transaction(
+        &self,
+        tx: AccountTransaction,
+        block_identifier: BlockId,
+    ) -> Result<TransactionSimulationInfo, ProviderError<Self::Error>> {
+        if block_identifier != self.state.block_id {
+            return self
+                .state
+                .provider
+                .simulate_transaction(tx, block_identifier)
+                .await
+                .map_err(map_provider_error);
+        }
+
+        self.backend
+            .simulate_transaction(&self.state, &tx)
+            .await
+            .map_err(map_execution_error)
+    }
+
+    async fn get_block(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<Block, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_block(block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_block_traces(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<BlockTraces, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_block_traces(block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_block_signature(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<BlockSignature, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_block_signature(block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_state_update(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<StateUpdate, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_state_update(block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_code(
+        &self,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<ContractCode, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_code(contract_address, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_full_contract(
+        &self,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<ContractArtifact, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_full_contract(contract_address, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_class_hash_at(contract_address, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<ContractArtifact, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_class_by_hash(class_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_raw_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<serde_json::Value, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_raw_class_by_hash(class_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_storage_at(
+        &self,
+        contract_address: FieldElement,
+        key: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_storage_at(contract_address, key, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_nonce(
+        &self,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_nonce(contract_address, block_identifier)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction_status(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionStatusInfo, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction_status(transaction_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionInfo, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction(transaction_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionReceipt, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction_receipt(transaction_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction_trace(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionTrace, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction_trace(transaction_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_block_hash_by_id(
+        &self,
+        block_number: u64,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_block_hash_by_id(block_number)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_block_id_by_hash(
+        &self,
+        block_hash: FieldElement,
+    ) -> Result<u64, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_block_id_by_hash(block_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction_hash_by_id(
+        &self,
+        transaction_number: u64,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction_hash_by_id(transaction_number)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_transaction_id_by_hash(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<u64, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_transaction_id_by_hash(transaction_hash)
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_last_batch_id(&self) -> Result<u64, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_last_batch_id()
+            .await
+            .map_err(map_provider_error)
+    }
+
+    async fn get_l1_blockchain_id(&self) -> Result<u64, ProviderError<Self::Error>> {
+        self.state
+            .provider
+            .get_l1_blockchain_id()
+            .await
+            .map_err(map_provider_error)
+    }
+}
+
+fn map_provider_error<E>(err: ProviderError<E>) -> ProviderError<LocalExecutionProviderError<E>> {
+    match err {
+        ProviderError::StarknetError(err) => ProviderError::StarknetError(err),
+        ProviderError::RateLimited => ProviderError::RateLimited,
+        ProviderError::Other(err) => {
+            ProviderError::Other(LocalExecutionProviderError::Provider(err))
+        }
+    }
+}
+
+fn map_execution_error<E>(
+    err: ExecutionError<ProviderError<E>>,
+) -> ProviderError<LocalExecutionProviderError<E>> {
+    match err {
+        ExecutionError::State(err) => map_provider_error(err),
+        ExecutionError::StarknetError(err) => ProviderError::StarknetError(err),
+        ExecutionError::Backend(err) => {
+            ProviderError::Other(LocalExecutionProviderError::Backend(err))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SequencerGatewayProvider;
+
+    use starknet_core::types::{FeeUnit, InvokeFunctionTransactionRequest};
+
+    const FEE_KEY: FieldElement = FieldElement::ONE;
+
+    /// Charges the fee stored at [FEE_KEY] of the sender, failing when it's zero.
+    struct StorageFeeBackend;
+
+    #[async_trait]
+    impl ExecutionBackend for StorageFeeBackend {
+        async fn estimate_fee<S>(
+            &self,
+            state: &S,
+            tx: &AccountTransaction,
+        ) -> Result<FeeEstimate, ExecutionError<S::Error>>
+        where
+            S: StateReader + Sync,
+        {
+            let sender = match tx {
+                AccountTransaction::InvokeFunction(tx) => tx.contract_address,
+                _ => return Err(ExecutionError::Backend("unsupported transaction".into())),
+            };
+            let fee = state
+                .get_storage_at(sender, FEE_KEY)
+                .await
+                .map_err(ExecutionError::State)?;
+            if fee == FieldElement::ZERO {
+                return Err(ExecutionError::StarknetError(StarknetError::ContractError));
+            }
+
+            Ok(FeeEstimate {
+                overall_fee: fee.try_into().unwrap(),
+                unit: FeeUnit::Wei,
+                gas_price: 1,
+                gas_usage: fee.try_into().unwrap(),
+            })
+        }
+
+        async fn simulate_transaction<S>(
+            &self,
+            _state: &S,
+            _tx: &AccountTransaction,
+        ) -> Result<TransactionSimulationInfo, ExecutionError<S::Error>>
+        where
+            S: StateReader + Sync,
+        {
+            Err(ExecutionError::Backend("simulation not supported".into()))
+        }
+    }
+
+    fn invoke(contract_address: FieldElement) -> AccountTransaction {
+        AccountTransaction::InvokeFunction(InvokeFunctionTransactionRequest {
+            contract_address,
+            calldata: vec![],
+            signature: vec![],
+            max_fee: FieldElement::ZERO,
+            nonce: FieldElement::ZERO,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_locally() {
+        let provider = LocalExecutionProvider::new(
+            SequencerGatewayProvider::starknet_alpha_goerli(),
+            StorageFeeBackend,
+            BlockId::Number(375919),
+        );
+        provider
+            .state()
+            .set_storage(FieldElement::ONE, FEE_KEY, FieldElement::from(1234u64));
+        provider
+            .state()
+            .set_storage(FieldElement::TWO, FEE_KEY, FieldElement::ZERO);
+
+        let estimates = provider
+            .estimate_fee_bulk(&[invoke(FieldElement::ONE)], BlockId::Number(375919))
+            .await
+            .unwrap();
+        assert_eq!(estimates[0].overall_fee, 1234);
+
+        assert!(matches!(
+            provider
+                .estimate_fee(invoke(FieldElement::TWO), BlockId::Number(375919))
+                .await,
+            Err(ProviderError::StarknetError(StarknetError::ContractError))
+        ));
+        assert!(matches!(
+            provider
+                .simulate_transaction(invoke(FieldElement::ONE), BlockId::Number(375919))
+                .await,
+            Err(ProviderError::Other(LocalExecutionProviderError::Backend(
+                _
+            )))
+        ));
+    }
+}
@@ -0,0 +1,873 @@
+use crate::{Provider, ProviderError};
+
+use async_trait::async_trait;
+use starknet_core::{
+    crypto::compute_hash_on_elements,
+    types::{
+        AccountTransaction, AddTransactionResult, AddTransactionResultCode, Block, BlockId,
+        BlockSignature, BlockTraces, CallContractResult, CallFunction, CallL1Handler,
+        ContractAddresses, ContractArtifact, ContractCode, FeeEstimate, FeeUnit, FieldElement,
+        StarknetError, StateUpdate, TransactionFailureReason, TransactionInfo, TransactionReceipt,
+        TransactionRequest, TransactionSimulationInfo, TransactionStatus, TransactionStatusInfo,
+        TransactionTrace,
+    },
+    utils::{get_contract_address, get_selector_from_name, get_storage_var_address},
+};
+use std::{collections::HashMap, sync::Mutex};
+
+const FEE_TOKEN_ADDRESS: &str = "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
+
+/// Declares the state of a fake chain served by a [MockProvider]:
+///
+/// ```ignore
+/// let provider = ChainFixture::new()
+///     .declare(account_class_hash, &account_artifact)
+///     .account(address, account_class_hash, FieldElement::from(10u64.pow(18)))
+///     .deploy(token, token_class_hash)
+///     .call_result(token, get_selector_from_name("decimals")?, vec![18u64.into()])
+///     .build();
+/// ```
+///
+/// Everything not declared reads as it would on an empty chain, e.g. storage is zero.
+#[derive(Debug)]
+pub struct ChainFixture {
+    state: ChainState,
+}
+
+/// A [Provider] serving the fake chain of a [ChainFixture], for testing code built on providers
+/// without a network.
+///
+/// Transactions added are accepted in a block of their own if their nonce is the next nonce of
+/// the sender, and rejected otherwise. Transaction hashes are derived from the sender, nonce and
+/// number of transactions added so far, so that the same scenario always yields the same hashes.
+///
+/// State is that of the latest block regardless of the requested block, and block hashes are
+/// the block numbers.
+#[derive(Debug)]
+pub struct MockProvider {
+    state: Mutex<ChainState>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MockProviderError {
+    #[error("Method not supported")]
+    NotSupported,
+    /// A contract revert, with the message a node would return.
+    #[error("{0}")]
+    Reverted(String),
+    #[error(transparent)]
+    Json(serde_json::Error),
+}
+
+#[derive(Debug)]
+struct ChainState {
+    block_number: u64,
+    fee_token: FieldElement,
+    fee_estimate: FeeEstimate,
+    gas_prices: HashMap<u64, u64>,
+    /// Artifacts are kept serialized, as they can't be cloned.
+    classes: HashMap<FieldElement, serde_json::Value>,
+    class_hashes: HashMap<FieldElement, FieldElement>,
+    nonces: HashMap<FieldElement, FieldElement>,
+    storage: HashMap<(FieldElement, FieldElement), FieldElement>,
+    call_results: HashMap<(FieldElement, FieldElement), Vec<FieldElement>>,
+    call_reverts: HashMap<(FieldElement, FieldElement), String>,
+    validation_reverts: HashMap<FieldElement, String>,
+    execution_reverts: Vec<(FieldElement, FieldElement, String)>,
+    receipts: HashMap<FieldElement, TransactionReceipt>,
+    submitted: Vec<TransactionRequest>,
+}
+
+impl ChainFixture {
+    pub fn new() -> Self {
+        Self {
+            state: ChainState {
+                block_number: 0,
+                fee_token: FieldElement::from_hex_be(FEE_TOKEN_ADDRESS).unwrap(),
+                fee_estimate: FeeEstimate {
+                    overall_fee: 10_000_000_000_000,
+                    unit: FeeUnit::Wei,
+                    gas_price: 1_000_000_000,
+                    gas_usage: 10_000,
+                },
+                gas_prices: HashMap::new(),
+                classes: HashMap::new(),
+                class_hashes: HashMap::new(),
+                nonces: HashMap::new(),
+                storage: HashMap::new(),
+                call_results: HashMap::new(),
+                call_reverts: HashMap::new(),
+                validation_reverts: HashMap::new(),
+                execution_reverts: vec![],
+                receipts: HashMap::new(),
+                submitted: vec![],
+            },
+        }
+    }
+
+    /// Sets the latest block. Defaults to `0`.
+    pub fn block_number(mut self, block_number: u64) -> Self {
+        self.state.block_number = block_number;
+        self
+    }
+
+    /// Sets the address of the ERC-20 contract in which fees are paid. Defaults to the ETH
+    /// contract.
+    pub fn fee_token(mut self, fee_token: FieldElement) -> Self {
+        self.state.fee_token = fee_token;
+        self
+    }
+
+    /// Sets the estimate returned for all transactions.
+    pub fn fee_estimate(mut self, fee_estimate: FeeEstimate) -> Self {
+        self.state.fee_estimate = fee_estimate;
+        self
+    }
+
+    /// Sets the gas price of block `block_number`. Defaults to the gas price of the fee estimate.
+    pub fn gas_price(mut self, block_number: u64, gas_price: u64) -> Self {
+        self.state.gas_prices.insert(block_number, gas_price);
+        self
+    }
+
+    /// Declares `artifact` as class `class_hash`. The hash isn't checked against the artifact.
+    pub fn declare(mut self, class_hash: FieldElement, artifact: &ContractArtifact) -> Self {
+        self.state.classes.insert(
+            class_hash,
+            serde_json::to_value(artifact).expect("artifacts serialize to JSON"),
+        );
+        self
+    }
+
+    /// Declares class `class_hash` from its JSON, e.g. for Sierra classes which can't be built as
+    /// a [ContractArtifact]. Fetching it fails if it's not a valid artifact either.
+    pub fn declare_json(mut self, class_hash: FieldElement, class: serde_json::Value) -> Self {
+        self.state.classes.insert(class_hash, class);
+        self
+    }
+
+    pub fn deploy(mut self, address: FieldElement, class_hash: FieldElement) -> Self {
+        self.state.class_hashes.insert(address, class_hash);
+        self
+    }
+
+    /// Deploys an account of class `class_hash` at `address`, holding `balance` of the fee
+    /// token.
+    pub fn account(
+        self,
+        address: FieldElement,
+        class_hash: FieldElement,
+        balance: FieldElement,
+    ) -> Self {
+        let fee_token = self.state.fee_token;
+        self.deploy(address, class_hash)
+            .storage(fee_token, balance_key(address), balance)
+    }
+
+    pub fn nonce(mut self, address: FieldElement, nonce: FieldElement) -> Self {
+        self.state.nonces.insert(address, nonce);
+        self
+    }
+
+    pub fn storage(
+        mut self,
+        address: FieldElement,
+        key: FieldElement,
+        value: FieldElement,
+    ) -> Self {
+        self.state.storage.insert((address, key), value);
+        self
+    }
+
+    /// Returns `result` for calls to function `selector` of contract `address`, whatever the
+    /// calldata.
+    ///
+    /// Without a canned result, calls to `balanceOf` on the fee token return the balance in its
+    /// storage, and other calls fail.
+    pub fn call_result(
+        mut self,
+        address: FieldElement,
+        selector: FieldElement,
+        result: Vec<FieldElement>,
+    ) -> Self {
+        self.state.call_results.insert((address, selector), result);
+        self
+    }
+
+    /// Makes calls to function `selector` of contract `address` revert with `message`, whatever
+    /// the calldata.
+    pub fn call_revert(
+        mut self,
+        address: FieldElement,
+        selector: FieldElement,
+        message: impl Into<String>,
+    ) -> Self {
+        self.state
+            .call_reverts
+            .insert((address, selector), message.into());
+        self
+    }
+
+    /// Makes simulations of invoke transactions sent by `account` revert with `message`, as when
+    /// `__validate__` rejects them.
+    pub fn validation_revert(mut self, account: FieldElement, message: impl Into<String>) -> Self {
+        self.state
+            .validation_reverts
+            .insert(account, message.into());
+        self
+    }
+
+    /// Makes simulations of invoke transactions calling function `selector` of contract
+    /// `address` revert with `message`. Calls are found by looking for `address` followed by
+    /// `selector` in the `__execute__` calldata, as laid out by both call encodings.
+    ///
+    /// Other invoke transactions simulate successfully, with an empty trace and the fee
+    /// estimate of the fixture.
+    pub fn execution_revert(
+        mut self,
+        address: FieldElement,
+        selector: FieldElement,
+        message: impl Into<String>,
+    ) -> Self {
+        self.state
+            .execution_reverts
+            .push((address, selector, message.into()));
+        self
+    }
+
+    /// Adds the receipt of a transaction, e.g. with the events it emitted.
+    pub fn receipt(mut self, receipt: TransactionReceipt) -> Self {
+        self.state
+            .receipts
+            .insert(receipt.transaction_hash, receipt);
+        self
+    }
+
+    pub fn build(self) -> MockProvider {
+        MockProvider {
+            state: Mutex::new(self.state),
+        }
+    }
+}
+
+impl Default for ChainFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockProvider {
+    /// The latest block.
+    pub fn block_number(&self) -> u64 {
+        self.state.lock().unwrap().block_number
+    }
+
+    /// The transactions added, in order, including those rejected.
+    pub fn submitted(&self) -> Vec<TransactionRequest> {
+        self.state.lock().unwrap().submitted.clone()
+    }
+
+    pub fn set_storage(&self, address: FieldElement, key: FieldElement, value: FieldElement) {
+        self.state
+            .lock()
+            .unwrap()
+            .storage
+            .insert((address, key), value);
+    }
+
+    /// Adds the receipt of a transaction, replacing the receipt of a transaction added with the
+    /// same hash.
+    pub fn set_receipt(&self, receipt: TransactionReceipt) {
+        self.state
+            .lock()
+            .unwrap()
+            .receipts
+            .insert(receipt.transaction_hash, receipt);
+    }
+}
+
+impl ChainState {
+    fn storage_at(&self, address: FieldElement, key: FieldElement) -> FieldElement {
+        self.storage
+            .get(&(address, key))
+            .copied()
+            .unwrap_or(FieldElement::ZERO)
+    }
+
+    fn nonce_of(&self, address: FieldElement) -> FieldElement {
+        self.nonces
+            .get(&address)
+            .copied()
+            .unwrap_or(FieldElement::ZERO)
+    }
+
+    fn add_transaction(&mut self, tx: TransactionRequest) -> AddTransactionResult {
+        let (sender, nonce, deployed_class_hash) = match &tx {
+            TransactionRequest::Declare(tx) => (tx.sender_address, tx.nonce, None),
+            TransactionRequest::InvokeFunction(tx) => (tx.contract_address, tx.nonce, None),
+            TransactionRequest::DeployAccount(tx) => (
+                get_contract_address(
+                    tx.contract_address_salt,
+                    tx.class_hash,
+                    &tx.constructor_calldata,
+                    FieldElement::ZERO,
+                ),
+                tx.nonce,
+                Some(tx.class_hash),
+            ),
+        };
+        let transaction_hash =
+            compute_hash_on_elements(&[sender, nonce, (self.submitted.len() as u64).into()]);
+        self.submitted.push(tx);
+
+        let receipt = if nonce == self.nonce_of(sender) {
+            self.nonces.insert(sender, nonce + FieldElement::ONE);
+            if let Some(class_hash) = deployed_class_hash {
+                self.class_hashes.insert(sender, class_hash);
+            }
+            self.block_number += 1;
+
+            TransactionReceipt {
+                block_hash: Some(self.block_number.into()),
+                block_number: Some(self.block_number),
+                events: vec![],
+                execution_resources: None,
+                l1_to_l2_consumed_message: None,
+                l2_to_l1_messages: vec![],
+                status: TransactionStatus::AcceptedOnL2,
+                transaction_failure_reason: None,
+                transaction_hash,
+                transaction_index: Some(0),
+                actual_fee: Some(self.fee_estimate.overall_fee.into()),
+            }
+        } else {
+            TransactionReceipt {
+                block_hash: None,
+                block_number: None,
+                events: vec![],
+                execution_resources: None,
+                l1_to_l2_consumed_message: None,
+                l2_to_l1_messages: vec![],
+                status: TransactionStatus::Rejected,
+                transaction_failure_reason: Some(TransactionFailureReason {
+                    code: "INVALID_TRANSACTION_NONCE".into(),
+                    error_message: Some(format!(
+                        "Invalid transaction nonce. Expected: {}, got: {}.",
+                        self.nonce_of(sender),
+                        nonce
+                    )),
+                }),
+                transaction_hash,
+                transaction_index: None,
+                actual_fee: None,
+            }
+        };
+        self.receipts.insert(transaction_hash, receipt);
+
+        AddTransactionResult {
+            code: AddTransactionResultCode::TransactionReceived,
+            transaction_hash,
+            address: deployed_class_hash.map(|_| sender),
+            class_hash: None,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Provider for MockProvider {
+    type Error = MockProviderError;
+
+    async fn add_transaction(
+        &self,
+        tx: TransactionRequest,
+    ) -> Result<AddTransactionResult, ProviderError<Self::Error>> {
+        Ok(self.state.lock().unwrap().add_transaction(tx))
+    }
+
+    async fn get_contract_addresses(
+        &self,
+    ) -> Result<ContractAddresses, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn call_contract(
+        &self,
+        call_function: CallFunction,
+        _block_identifier: BlockId,
+    ) -> Result<CallContractResult, ProviderError<Self::Error>> {
+        let state = self.state.lock().unwrap();
+        let address = call_function.contract_address;
+        let selector = call_function.entry_point_selector;
+
+        if let Some(message) = state.call_reverts.get(&(address, selector)) {
+            return Err(ProviderError::Other(Self::Error::Reverted(message.clone())));
+        }
+        if let Some(result) = state.call_results.get(&(address, selector)) {
+            return Ok(CallContractResult {
+                result: result.clone(),
+            });
+        }
+        if !state.class_hashes.contains_key(&address) {
+            return Err(ProviderError::StarknetError(
+                StarknetError::ContractNotFound,
+            ));
+        }
+        match call_function.calldata.first() {
+            Some(owner)
+                if address == state.fee_token
+                    && selector == get_selector_from_name("balanceOf").unwrap() =>
+            {
+                let key = balance_key(*owner);
+                Ok(CallContractResult {
+                    result: vec![
+                        state.storage_at(address, key),
+                        state.storage_at(address, key + FieldElement::ONE),
+                    ],
+                })
+            }
+            _ => Err(ProviderError::StarknetError(StarknetError::ContractError)),
+        }
+    }
+
+    async fn estimate_fee(
+        &self,
+        _tx: AccountTransaction,
+        _block_identifier: BlockId,
+    ) -> Result<FeeEstimate, ProviderError<Self::Error>> {
+        Ok(self.state.lock().unwrap().fee_estimate.clone())
+    }
+
+    async fn estimate_fee_bulk(
+        &self,
+        txs: &[AccountTransaction],
+        _block_identifier: BlockId,
+    ) -> Result<Vec<FeeEstimate>, ProviderError<Self::Error>> {
+        let fee_estimate = self.state.lock().unwrap().fee_estimate.clone();
+        Ok(vec![fee_estimate; txs.len()])
+    }
+
+    async fn estimate_message_fee(
+        &self,
+        _call_l1_handler: CallL1Handler,
+        _block_identifier: BlockId,
+    ) -> Result<FeeEstimate, ProviderError<Self::Error>> {
+        Ok(self.state.lock().unwrap().fee_estimate.clone())
+    }
+
+    async fn simulate_transaction(
+        &self,
+        tx: AccountTransaction,
+        _block_identifier: BlockId,
+    ) -> Result<TransactionSimulationInfo, ProviderError<Self::Error>> {
+        let state = self.state.lock().unwrap();
+        let tx = match tx {
+            AccountTransaction::InvokeFunction(tx) => tx,
+            _ => return Err(ProviderError::Other(Self::Error::NotSupported)),
+        };
+
+        if let Some(message) = state.validation_reverts.get(&tx.contract_address) {
+            return Err(ProviderError::Other(Self::Error::Reverted(message.clone())));
+        }
+        if let Some((_, _, message)) =
+            state
+                .execution_reverts
+                .iter()
+                .find(|(address, selector, _)| {
+                    tx.calldata
+                        .windows(2)
+                        .any(|pair| pair == [*address, *selector])
+                })
+        {
+            return Err(ProviderError::Other(Self::Error::Reverted(message.clone())));
+        }
+
+        serde_json::from_value(serde_json::json!({
+            "trace": {
+                "function_invocation": {
+                    "caller_address": "0x0",
+                    "contract_address": format!("{:#x}", tx.contract_address),
+                    "calldata": [],
+                    "call_type": null,
+                    "class_hash": null,
+                    "selector": null,
+                    "entry_point_type": null,
+                    "result": [],
+                    "execution_resources": {
+                        "n_steps": 0,
+                        "n_memory_holes": 0,
+                        "builtin_instance_counter": {},
+                    },
+                    "internal_calls": [],
+                    "events": [],
+                    "messages": [],
+                },
+                "signature": [],
+            },
+            "fee_estimation": {
+                "overall_fee": state.fee_estimate.overall_fee,
+                "unit": "wei",
+                "gas_price": state.fee_estimate.gas_price,
+                "gas_usage": state.fee_estimate.gas_usage,
+            },
+        }))
+        .map_err(|err| ProviderError::Other(Self::Error::Json(err)))
+    }
+
+    async fn get_block(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<Block, ProviderError<Self::Error>> {
+        let block_number = match block_identifier {
+            BlockId::Hash(block_hash) => self.get_block_id_by_hash(block_hash).await?,
+            BlockId::Number(block_number) => {
+                self.get_block_hash_by_id(block_number).await?;
+                block_number
+            }
+            BlockId::Pending | BlockId::Latest => self.block_number(),
+        };
+
+        let state = self.state.lock().unwrap();
+        let gas_price = state
+            .gas_prices
+            .get(&block_number)
+            .copied()
+            .unwrap_or(state.fee_estimate.gas_price);
+
+        serde_json::from_value(serde_json::json!({
+            "block_hash": format!("{:#x}", block_number),
+            "block_number": block_number,
+            "parent_block_hash": format!("{:#x}", block_number.saturating_sub(1)),
+            "timestamp": 0,
+            "status": "ACCEPTED_ON_L2",
+            "gas_price": format!("{:#x}", gas_price),
+            "transactions": [],
+            "transaction_receipts": [],
+            "starknet_version": null,
+        }))
+        .map_err(|err| ProviderError::Other(Self::Error::Json(err)))
+    }
+
+    async fn get_block_traces(
+        &self,
+        _block_identifier: BlockId,
+    ) -> Result<BlockTraces, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_block_signature(
+        &self,
+        _block_identifier: BlockId,
+    ) -> Result<BlockSignature, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_state_update(
+        &self,
+        _block_identifier: BlockId,
+    ) -> Result<StateUpdate, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_code(
+        &self,
+        _contract_address: FieldElement,
+        _block_identifier: BlockId,
+    ) -> Result<ContractCode, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_full_contract(
+        &self,
+        contract_address: FieldElement,
+        block_identifier: BlockId,
+    ) -> Result<ContractArtifact, ProviderError<Self::Error>> {
+        let class_hash = self
+            .get_class_hash_at(contract_address, block_identifier)
+            .await?;
+        self.get_class_by_hash(class_hash).await
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        contract_address: FieldElement,
+        _block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        self.state
+            .lock()
+            .unwrap()
+            .class_hashes
+            .get(&contract_address)
+            .copied()
+            .ok_or(ProviderError::StarknetError(
+                StarknetError::ContractNotFound,
+            ))
+    }
+
+    async fn get_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<ContractArtifact, ProviderError<Self::Error>> {
+        let artifact = self
+            .state
+            .lock()
+            .unwrap()
+            .classes
+            .get(&class_hash)
+            .cloned()
+            .ok_or(ProviderError::StarknetError(
+                StarknetError::ClassHashNotFound,
+            ))?;
+        serde_json::from_value(artifact).map_err(|err| ProviderError::Other(Self::Error::Json(err)))
+    }
+
+    async fn get_raw_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<serde_json::Value, ProviderError<Self::Error>> {
+        self.state
+            .lock()
+            .unwrap()
+            .classes
+            .get(&class_hash)
+            .cloned()
+            .ok_or(ProviderError::StarknetError(
+                StarknetError::ClassHashNotFound,
+            ))
+    }
+
+    async fn get_storage_at(
+        &self,
+        contract_address: FieldElement,
+        key: FieldElement,
+        _block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        Ok(self.state.lock().unwrap().storage_at(contract_address, key))
+    }
+
+    async fn get_nonce(
+        &self,
+        contract_address: FieldElement,
+        _block_identifier: BlockId,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        Ok(self.state.lock().unwrap().nonce_of(contract_address))
+    }
+
+    async fn get_transaction_status(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionStatusInfo, ProviderError<Self::Error>> {
+        Ok(
+            match self.state.lock().unwrap().receipts.get(&transaction_hash) {
+                Some(receipt) => TransactionStatusInfo {
+                    block_hash: receipt.block_hash,
+                    status: receipt.status,
+                    transaction_failure_reason: receipt.transaction_failure_reason.clone(),
+                },
+                None => TransactionStatusInfo {
+                    block_hash: None,
+                    status: TransactionStatus::NotReceived,
+                    transaction_failure_reason: None,
+                },
+            },
+        )
+    }
+
+    async fn get_transaction(
+        &self,
+        _transaction_hash: FieldElement,
+    ) -> Result<TransactionInfo, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionReceipt, ProviderError<Self::Error>> {
+        self.state
+            .lock()
+            .unwrap()
+            .receipts
+            .get(&transaction_hash)
+            .cloned()
+            .ok_or(ProviderError::StarknetError(StarknetError::TxnHashNotFound))
+    }
+
+    async fn get_transaction_trace(
+        &self,
+        _transaction_hash: FieldElement,
+    ) -> Result<TransactionTrace, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_block_hash_by_id(
+        &self,
+        block_number: u64,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        if block_number <= self.block_number() {
+            Ok(block_number.into())
+        } else {
+            Err(ProviderError::StarknetError(StarknetError::BlockNotFound))
+        }
+    }
+
+    async fn get_block_id_by_hash(
+        &self,
+        block_hash: FieldElement,
+    ) -> Result<u64, ProviderError<Self::Error>> {
+        u64::try_from(block_hash)
+            .ok()
+            .filter(|block_number| *block_number <= self.block_number())
+            .ok_or(ProviderError::StarknetError(StarknetError::BlockNotFound))
+    }
+
+    async fn get_transaction_hash_by_id(
+        &self,
+        _transaction_number: u64,
+    ) -> Result<FieldElement, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_transaction_id_by_hash(
+        &self,
+        _transaction_hash: FieldElement,
+    ) -> Result<u64, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_last_batch_id(&self) -> Result<u64, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+
+    async fn get_l1_blockchain_id(&self) -> Result<u64, ProviderError<Self::Error>> {
+        Err(ProviderError::Other(Self::Error::NotSupported))
+    }
+}
+
+/// The storage key of the low word of the balance of `owner` in an ERC-20 contract.
+fn balance_key(owner: FieldElement) -> FieldElement {
+    get_storage_var_address("ERC20_balances", &[owner]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::InvokeFunctionTransactionRequest;
+
+    fn invoke(sender: FieldElement, nonce: u64) -> TransactionRequest {
+        TransactionRequest::InvokeFunction(InvokeFunctionTransactionRequest {
+            contract_address: sender,
+            calldata: vec![],
+            signature: vec![],
+            max_fee: FieldElement::ONE,
+            nonce: nonce.into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_scenario() {
+        let account = FieldElement::from(0x1234u64);
+        let provider = ChainFixture::new()
+            .block_number(100)
+            .account(account, FieldElement::ONE, FieldElement::from(5000u64))
+            .deploy(
+                FieldElement::from_hex_be(FEE_TOKEN_ADDRESS).unwrap(),
+                FieldElement::TWO,
+            )
+            .build();
+
+        let balance = provider
+            .call_contract(
+                CallFunction {
+                    contract_address: FieldElement::from_hex_be(FEE_TOKEN_ADDRESS).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![account],
+                },
+                BlockId::Latest,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            balance.result,
+            vec![FieldElement::from(5000u64), FieldElement::ZERO]
+        );
+
+        let accepted = provider.add_transaction(invoke(account, 0)).await.unwrap();
+        let receipt = provider
+            .get_transaction_receipt(accepted.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(receipt.status, TransactionStatus::AcceptedOnL2);
+        assert_eq!(receipt.block_number, Some(101));
+        assert_eq!(
+            provider.get_nonce(account, BlockId::Latest).await.unwrap(),
+            FieldElement::ONE
+        );
+
+        // Replaying the nonce is rejected
+        let replayed = provider.add_transaction(invoke(account, 0)).await.unwrap();
+        assert_ne!(replayed.transaction_hash, accepted.transaction_hash);
+        let status = provider
+            .get_transaction_status(replayed.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(status.status, TransactionStatus::Rejected);
+        assert_eq!(
+            status.transaction_failure_reason.unwrap().code,
+            "INVALID_TRANSACTION_NONCE"
+        );
+
+        assert_eq!(provider.submitted().len(), 2);
+        assert_eq!(provider.block_number(), 101);
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_missing_state() {
+        let provider = ChainFixture::new().build();
+
+        assert!(matches!(
+            provider
+                .get_class_hash_at(FieldElement::ONE, BlockId::Latest)
+                .await,
+            Err(ProviderError::StarknetError(
+                StarknetError::ContractNotFound
+            ))
+        ));
+        assert!(matches!(
+            provider.get_class_by_hash(FieldElement::ONE).await,
+            Err(ProviderError::StarknetError(
+                StarknetError::ClassHashNotFound
+            ))
+        ));
+        assert_eq!(
+            provider
+                .get_transaction_status(FieldElement::ONE)
+                .await
+                .unwrap()
+                .status,
+            TransactionStatus::NotReceived
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_blocks() {
+        let provider = ChainFixture::new().block_number(10).gas_price(9, 5).build();
+
+        let latest = provider.get_block(BlockId::Latest).await.unwrap();
+        assert_eq!(latest.block_number, Some(10));
+        assert_eq!(latest.gas_price, FieldElement::from(1_000_000_000u64));
+
+        let block = provider
+            .get_block(BlockId::Hash(FieldElement::from(9u64)))
+            .await
+            .unwrap();
+        assert_eq!(block.block_number, Some(9));
+        assert_eq!(block.gas_price, FieldElement::from(5u64));
+
+        assert!(matches!(
+            provider.get_block(BlockId::Number(11)).await,
+            Err(ProviderError::StarknetError(StarknetError::BlockNotFound))
+        ));
+    }
+}
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use starknet_core::types::{
-    AccountTransaction, AddTransactionResult, Block, BlockId, BlockTraces, CallContractResult,
-    CallFunction, CallL1Handler, ContractAddresses, ContractArtifact, ContractCode, FeeEstimate,
-    FieldElement, StarknetError, StateUpdate, TransactionInfo, TransactionReceipt,
-    TransactionRequest, TransactionSimulationInfo, TransactionStatusInfo, TransactionTrace,
+    AccountTransaction, AddTransactionResult, AsStarknetError, Block, BlockId, BlockSignature,
+    BlockTraces, CallContractResult, CallFunction, CallL1Handler, ContractAddresses,
+    ContractArtifact, ContractCode, FeeEstimate, FieldElement, StarknetError, StateUpdate,
+    TransactionInfo, TransactionReceipt, TransactionRequest, TransactionSimulationInfo,
+    TransactionStatusInfo, TransactionTrace,
 };
 use std::error::Error;
 
@@ -14,6 +15,9 @@ use std::error::Error;
 pub trait Provider {
     type Error: Error + Send;
 
+    /// Submits a signed transaction to the network. The transaction is sent as-is, so requests
+    /// signed offline or by another system (e.g. deserialized from JSON) can be broadcast
+    /// without access to the signer.
     async fn add_transaction(
         &self,
         tx: TransactionRequest,
@@ -62,6 +66,11 @@ pub trait Provider {
         block_identifier: BlockId,
     ) -> Result<BlockTraces, ProviderError<Self::Error>>;
 
+    async fn get_block_signature(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<BlockSignature, ProviderError<Self::Error>>;
+
     async fn get_state_update(
         &self,
         block_identifier: BlockId,
@@ -90,6 +99,13 @@ pub trait Provider {
         class_hash: FieldElement,
     ) -> Result<ContractArtifact, ProviderError<Self::Error>>;
 
+    /// Same as [Provider::get_class_by_hash], but returns the class as JSON without deserializing
+    /// it into a [ContractArtifact], so that Cairo 1 (Sierra) classes can be inspected as well.
+    async fn get_raw_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<serde_json::Value, ProviderError<Self::Error>>;
+
     async fn get_storage_at(
         &self,
         contract_address: FieldElement,
@@ -157,3 +173,12 @@ pub enum ProviderError<E> {
     #[error(transparent)]
     Other(E),
 }
+
+impl<E> AsStarknetError for ProviderError<E> {
+    fn starknet_error(&self) -> Option<StarknetError> {
+        match self {
+            Self::StarknetError(err) => Some(*err),
+            Self::RateLimited | Self::Other(_) => None,
+        }
+    }
+}
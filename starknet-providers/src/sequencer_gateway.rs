@@ -8,10 +8,11 @@ use serde_with::serde_as;
 use starknet_core::{
     serde::unsigned_field_element::UfeHex,
     types::{
-        AccountTransaction, AddTransactionResult, Block, BlockId, BlockTraces, CallContractResult,
-        CallFunction, CallL1Handler, ContractAddresses, ContractArtifact, ContractCode,
-        FeeEstimate, FieldElement, StarknetError, StateUpdate, TransactionInfo, TransactionReceipt,
-        TransactionRequest, TransactionSimulationInfo, TransactionStatusInfo, TransactionTrace,
+        AccountTransaction, AddTransactionResult, Block, BlockId, BlockSignature, BlockTraces,
+        CallContractResult, CallFunction, CallL1Handler, ContractAddresses, ContractArtifact,
+        ContractCode, FeeEstimate, FieldElement, StarknetError, StateUpdate, TransactionInfo,
+        TransactionReceipt, TransactionRequest, TransactionSimulationInfo, TransactionStatusInfo,
+        TransactionTrace,
     },
 };
 use url::Url;
@@ -226,9 +227,15 @@ impl Provider for SequencerGatewayProvider {
     ) -> Result<AddTransactionResult, ProviderError<Self::Error>> {
         let request_url = self.extend_gateway_url("add_transaction");
 
-        self.send_post_request::<_, GatewayResponse<_>>(request_url, &tx)
-            .await?
-            .into()
+        instrument!(
+            async {
+                self.send_post_request::<_, GatewayResponse<_>>(request_url, &tx)
+                    .await?
+                    .into()
+            },
+            "add_transaction"
+        )
+        .await
     }
 
     async fn get_contract_addresses(
@@ -262,9 +269,15 @@ impl Provider for SequencerGatewayProvider {
         let mut request_url = self.extend_feeder_gateway_url("estimate_fee");
         append_block_id(&mut request_url, block_identifier);
 
-        self.send_post_request::<_, GatewayResponse<_>>(request_url, &tx)
-            .await?
-            .into()
+        instrument!(
+            async {
+                self.send_post_request::<_, GatewayResponse<_>>(request_url, &tx)
+                    .await?
+                    .into()
+            },
+            "estimate_fee"
+        )
+        .await
     }
 
     async fn estimate_fee_bulk(
@@ -330,6 +343,18 @@ impl Provider for SequencerGatewayProvider {
             .into()
     }
 
+    async fn get_block_signature(
+        &self,
+        block_identifier: BlockId,
+    ) -> Result<BlockSignature, ProviderError<Self::Error>> {
+        let mut request_url = self.extend_feeder_gateway_url("get_signature");
+        append_block_id(&mut request_url, block_identifier);
+
+        self.send_get_request::<GatewayResponse<_>>(request_url)
+            .await?
+            .into()
+    }
+
     async fn get_state_update(
         &self,
         block_identifier: BlockId,
@@ -417,6 +442,25 @@ impl Provider for SequencerGatewayProvider {
             .into()
     }
 
+    async fn get_raw_class_by_hash(
+        &self,
+        class_hash: FieldElement,
+    ) -> Result<serde_json::Value, ProviderError<Self::Error>> {
+        let mut request_url = self.extend_feeder_gateway_url("get_class_by_hash");
+        request_url
+            .query_pairs_mut()
+            .append_pair("classHash", &format!("{class_hash:#x}"));
+
+        // Any JSON would deserialize as `Data`, so errors are checked for first
+        let value = self
+            .send_get_request::<serde_json::Value>(request_url)
+            .await?;
+        match SequencerError::deserialize(&value) {
+            Ok(err) => GatewayResponse::SequencerError(err).into(),
+            Err(_) => Ok(value),
+        }
+    }
+
     async fn get_storage_at(
         &self,
         contract_address: FieldElement,
@@ -460,9 +504,16 @@ impl Provider for SequencerGatewayProvider {
             .query_pairs_mut()
             .append_pair("transactionHash", &format!("{transaction_hash:#x}"));
 
-        self.send_get_request::<GatewayResponse<_>>(request_url)
-            .await?
-            .into()
+        instrument!(
+            async {
+                self.send_get_request::<GatewayResponse<_>>(request_url)
+                    .await?
+                    .into()
+            },
+            "get_transaction_status",
+            transaction_hash = %format_args!("{transaction_hash:#x}")
+        )
+        .await
     }
 
     async fn get_transaction(
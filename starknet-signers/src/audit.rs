@@ -0,0 +1,256 @@
+use crate::{clock::system_time, SignContext, SignRequest, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{crypto::Signature, types::FieldElement};
+use std::error::Error;
+
+/// Stores [AuditRecord]s, e.g. in a database, an append-only log or a SIEM.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait AuditSink {
+    type Error: Error + Send;
+
+    async fn record(&self, record: &AuditRecord) -> Result<(), Self::Error>;
+}
+
+/// Wraps a signer so that every signing attempt is recorded to an [AuditSink], whether it
+/// succeeds or not.
+///
+/// Signatures are only returned once recorded: if the sink fails, the signature is withheld and
+/// the sink error is returned instead.
+#[derive(Debug)]
+pub struct AuditedSigner<S, A> {
+    signer: S,
+    sink: A,
+    metadata: Vec<(String, String)>,
+    clock: fn() -> u64,
+}
+
+/// A signing attempt.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    /// The Unix timestamp in seconds.
+    pub timestamp: u64,
+    pub hash: FieldElement,
+    pub context: SignContext,
+    /// Key-value pairs supplied by the caller, e.g. the service or the user requesting the
+    /// signature.
+    pub metadata: Vec<(String, String)>,
+    pub outcome: AuditOutcome,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuditOutcome {
+    Signed { r: FieldElement, s: FieldElement },
+    Failed { error: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuditedSignerError<S, A> {
+    #[error(transparent)]
+    Signer(S),
+    #[error("failed to record signing attempt: {0}")]
+    Sink(A),
+}
+
+impl<S, A> AuditedSigner<S, A> {
+    pub fn new(signer: S, sink: A) -> Self {
+        Self {
+            signer,
+            sink,
+            metadata: vec![],
+            clock: system_time,
+        }
+    }
+
+    /// Adds a key-value pair to the metadata of every record.
+    pub fn with_metadata(mut self, key: String, value: String) -> Self {
+        self.metadata.push((key, value));
+        self
+    }
+
+    /// Uses `clock` to get the current Unix timestamp in seconds. Defaults to the system clock.
+    pub fn with_clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn sink(&self) -> &A {
+        &self.sink
+    }
+
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+}
+
+impl<S, A> AuditedSigner<S, A>
+where
+    S: Signer + Sync + Send,
+    A: AuditSink + Sync + Send,
+{
+    /// Same as [sign_request](Signer::sign_request), with `metadata` added to the record on top
+    /// of the metadata set with [with_metadata](Self::with_metadata).
+    pub async fn sign_request_with_metadata(
+        &self,
+        request: &SignRequest,
+        metadata: &[(String, String)],
+    ) -> Result<Signature, AuditedSignerError<S::SignError, A::Error>> {
+        let result = self.signer.sign_request(request).await;
+
+        let record = AuditRecord {
+            timestamp: (self.clock)(),
+            hash: request.hash,
+            context: request.context.clone(),
+            metadata: self
+                .metadata
+                .iter()
+                .chain(metadata.iter())
+                .cloned()
+                .collect(),
+            outcome: match &result {
+                Ok(signature) => AuditOutcome::Signed {
+                    r: signature.r,
+                    s: signature.s,
+                },
+                Err(err) => AuditOutcome::Failed {
+                    error: err.to_string(),
+                },
+            },
+        };
+        self.sink
+            .record(&record)
+            .await
+            .map_err(AuditedSignerError::Sink)?;
+
+        result.map_err(AuditedSignerError::Signer)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<S, A> Signer for AuditedSigner<S, A>
+where
+    S: Signer + Sync + Send,
+    A: AuditSink + Sync + Send,
+{
+    type GetPublicKeyError = S::GetPublicKeyError;
+    type SignError = AuditedSignerError<S::SignError, A::Error>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        self.signer.get_public_key().await
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.sign_request(&SignRequest::blind(*hash)).await
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError> {
+        self.sign_request_with_metadata(request, &[]).await
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LocalWallet, PolicySigner, SigningKey, SigningPolicy};
+
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySink {
+        records: Mutex<Vec<AuditRecord>>,
+        fail: bool,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("sink unavailable")]
+    struct SinkError;
+
+    #[async_trait]
+    impl AuditSink for MemorySink {
+        type Error = SinkError;
+
+        async fn record(&self, record: &AuditRecord) -> Result<(), Self::Error> {
+            if self.fail {
+                return Err(SinkError);
+            }
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+    }
+
+    fn wallet() -> LocalWallet {
+        LocalWallet::from_signing_key(SigningKey::from_secret_scalar(FieldElement::ONE))
+    }
+
+    #[tokio::test]
+    async fn test_records_signatures() {
+        let signer = AuditedSigner::new(wallet(), MemorySink::default())
+            .with_metadata("service".into(), "payouts".into())
+            .with_clock(|| 1_700_000_000);
+
+        let signature = signer
+            .sign_request_with_metadata(
+                &SignRequest::blind(FieldElement::TWO),
+                &[("user".into(), "alice".into())],
+            )
+            .await
+            .unwrap();
+
+        let records = signer.sink().records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].timestamp, 1_700_000_000);
+        assert_eq!(records[0].hash, FieldElement::TWO);
+        assert_eq!(
+            records[0].metadata,
+            vec![
+                ("service".to_owned(), "payouts".to_owned()),
+                ("user".to_owned(), "alice".to_owned())
+            ]
+        );
+        assert!(matches!(
+            records[0].outcome,
+            AuditOutcome::Signed { r, s } if r == signature.r && s == signature.s
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_records_failures() {
+        let signer = AuditedSigner::new(
+            PolicySigner::new(wallet(), SigningPolicy::new()),
+            MemorySink::default(),
+        );
+
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::ONE).await,
+            Err(AuditedSignerError::Signer(_))
+        ));
+
+        let records = signer.sink().records.lock().unwrap();
+        assert!(matches!(
+            &records[0].outcome,
+            AuditOutcome::Failed { error } if error == "signing hashes without context is not allowed"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_withholds_unrecorded_signatures() {
+        let signer = AuditedSigner::new(
+            wallet(),
+            MemorySink {
+                fail: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::ONE).await,
+            Err(AuditedSignerError::Sink(SinkError))
+        ));
+    }
+}
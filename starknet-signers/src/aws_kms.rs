@@ -0,0 +1,337 @@
+use crate::{envelope::signing_key_from_encoded_plaintext, Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const KMS_SERVICE: &str = "kms";
+const DECRYPT_TARGET: &str = "TrentService.Decrypt";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// A signer whose Stark private key is encrypted with an AWS KMS key, as KMS doesn't support the
+/// Stark curve. The key is decrypted with the KMS `Decrypt` API every time it's used, so access
+/// can be controlled and audited through IAM and CloudTrail.
+///
+/// The encrypted key is the `CiphertextBlob` returned by KMS `Encrypt`, with either the raw
+/// 32-byte scalar or its hex representation as the plaintext.
+#[derive(Debug, Clone)]
+pub struct AwsKmsSigner {
+    client: reqwest::Client,
+    region: String,
+    endpoint: Option<String>,
+    credentials: AwsCredentials,
+    encrypted_key: Vec<u8>,
+    key_id: Option<String>,
+}
+
+/// Static or temporary credentials of an IAM principal.
+#[derive(Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AwsKmsError {
+    #[error("missing AWS configuration: {0}")]
+    MissingConfiguration(&'static str),
+    #[error(transparent)]
+    Http(reqwest::Error),
+    #[error("KMS error {error_type}: {message}")]
+    Kms { error_type: String, message: String },
+    #[error("decrypted plaintext is not a valid private key")]
+    InvalidPlaintext,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct DecryptRequest<'a> {
+    ciphertext_blob: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_id: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DecryptResponse {
+    plaintext: String,
+}
+
+#[derive(Deserialize)]
+struct KmsErrorResponse {
+    #[serde(rename = "__type")]
+    error_type: String,
+    #[serde(default, alias = "Message")]
+    message: String,
+}
+
+/// Inputs to AWS Signature Version 4. `headers` must be lowercase and sorted by name.
+struct SigV4Request<'a> {
+    method: &'a str,
+    path: &'a str,
+    query: &'a str,
+    headers: &'a [(&'a str, &'a str)],
+    payload: &'a [u8],
+    region: &'a str,
+    service: &'a str,
+    amz_date: &'a str,
+}
+
+impl AwsKmsSigner {
+    pub fn new(region: String, credentials: AwsCredentials, encrypted_key: Vec<u8>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            region,
+            endpoint: None,
+            credentials,
+            encrypted_key,
+            key_id: None,
+        }
+    }
+
+    /// Reads the region and credentials from the standard `AWS_REGION` (or `AWS_DEFAULT_REGION`),
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` environment
+    /// variables, which are also set for IAM roles on Lambda and ECS.
+    pub fn from_env(encrypted_key: Vec<u8>) -> Result<Self, AwsKmsError> {
+        let region = std::env::var("AWS_REGION")
+            .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+            .map_err(|_| AwsKmsError::MissingConfiguration("AWS_REGION"))?;
+
+        Ok(Self::new(
+            region,
+            AwsCredentials::from_env()?,
+            encrypted_key,
+        ))
+    }
+
+    /// Restricts decryption to the given KMS key ID or ARN, as recommended by AWS.
+    pub fn with_key_id(mut self, key_id: String) -> Self {
+        self.key_id = Some(key_id);
+        self
+    }
+
+    /// Overrides the KMS endpoint host, e.g. for VPC endpoints or FIPS endpoints.
+    pub fn with_endpoint(mut self, host: String) -> Self {
+        self.endpoint = Some(host);
+        self
+    }
+
+    async fn decrypt_key(&self) -> Result<SigningKey, AwsKmsError> {
+        let host = self
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("kms.{}.amazonaws.com", self.region));
+        let payload = serde_json::to_vec(&DecryptRequest {
+            ciphertext_blob: base64::encode(&self.encrypted_key),
+            key_id: self.key_id.as_deref(),
+        })
+        .expect("serialization of a plain struct cannot fail");
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut headers = vec![
+            ("content-type", CONTENT_TYPE),
+            ("host", host.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", session_token.as_str()));
+        }
+        headers.push(("x-amz-target", DECRYPT_TARGET));
+
+        let authorization = self.credentials.sign_v4(&SigV4Request {
+            method: "POST",
+            path: "/",
+            query: "",
+            headers: &headers,
+            payload: &payload,
+            region: &self.region,
+            service: KMS_SERVICE,
+            amz_date: &amz_date,
+        });
+
+        let mut request = self
+            .client
+            .post(format!("https://{host}/"))
+            .header("authorization", authorization)
+            .body(payload);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, *value);
+        }
+
+        let response = request.send().await.map_err(AwsKmsError::Http)?;
+        if !response.status().is_success() {
+            let error: KmsErrorResponse = response.json().await.map_err(AwsKmsError::Http)?;
+            return Err(AwsKmsError::Kms {
+                error_type: error.error_type,
+                message: error.message,
+            });
+        }
+
+        let response: DecryptResponse = response.json().await.map_err(AwsKmsError::Http)?;
+        signing_key_from_encoded_plaintext(response.plaintext, |encoded| base64::decode(encoded))
+            .ok_or(AwsKmsError::InvalidPlaintext)
+    }
+}
+
+impl AwsCredentials {
+    pub fn from_env() -> Result<Self, AwsKmsError> {
+        Ok(Self {
+            access_key_id: std::env::var("AWS_ACCESS_KEY_ID")
+                .map_err(|_| AwsKmsError::MissingConfiguration("AWS_ACCESS_KEY_ID"))?,
+            secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY")
+                .map_err(|_| AwsKmsError::MissingConfiguration("AWS_SECRET_ACCESS_KEY"))?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+
+    /// Computes the `Authorization` header value for the request.
+    fn sign_v4(&self, request: &SigV4Request<'_>) -> String {
+        let date = &request.amz_date[..8];
+        let scope = format!(
+            "{}/{}/{}/aws4_request",
+            date, request.region, request.service
+        );
+
+        let canonical_headers: String = request
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let signed_headers = request
+            .headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            request.method,
+            request.path,
+            request.query,
+            canonical_headers,
+            signed_headers,
+            hex::encode(Sha256::digest(request.payload))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            request.amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [request.region, request.service, "aws4_request"]
+            .iter()
+            .fold(
+                hmac_sha256(
+                    format!("AWS4{}", self.secret_access_key).as_bytes(),
+                    date.as_bytes(),
+                ),
+                |key, part| hmac_sha256(&key, part.as_bytes()),
+            );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+// Manually implemented to keep the secret out of logs
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for AwsKmsSigner {
+    type GetPublicKeyError = AwsKmsError;
+    type SignError = AwsKmsError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(self.decrypt_key().await?.verifying_key())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.decrypt_key()
+            .await?
+            .sign(hash)
+            .map_err(AwsKmsError::EcdsaSignError)
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_sign_v4() {
+        // Example from the AWS Signature Version 4 documentation
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            session_token: None,
+        };
+
+        let authorization = credentials.sign_v4(&SigV4Request {
+            method: "GET",
+            path: "/",
+            query: "Action=ListUsers&Version=2010-05-08",
+            headers: &[
+                (
+                    "content-type",
+                    "application/x-www-form-urlencoded; charset=utf-8",
+                ),
+                ("host", "iam.amazonaws.com"),
+                ("x-amz-date", "20150830T123600Z"),
+            ],
+            payload: b"",
+            region: "us-east-1",
+            service: "iam",
+            amz_date: "20150830T123600Z",
+        });
+
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+            SignedHeaders=content-type;host;x-amz-date, \
+            Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decrypt_request_serialization() {
+        let request = DecryptRequest {
+            ciphertext_blob: base64::encode([1, 2, 3]),
+            key_id: Some("alias/starknet"),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"CiphertextBlob":"AQID","KeyId":"alias/starknet"}"#
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{envelope::signing_key_from_encoded_plaintext, Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+
+const API_VERSION: &str = "7.4";
+const DEFAULT_ALGORITHM: &str = "RSA-OAEP-256";
+const MANAGED_IDENTITY_TOKEN_URL: &str = "http://169.254.169.254/metadata/identity/oauth2/token?api-version=2018-02-01&resource=https%3A%2F%2Fvault.azure.net";
+
+/// A signer whose Stark private key is encrypted with a Key Vault (or Managed HSM) key, as Key
+/// Vault doesn't support the Stark curve. The key is decrypted with the Key Vault `decrypt`
+/// operation every time it's used.
+///
+/// The encrypted key is the `value` returned by the Key Vault `encrypt` operation, with either
+/// the raw 32-byte scalar or its hex representation as the plaintext.
+#[derive(Debug, Clone)]
+pub struct AzureKeyVaultSigner {
+    client: reqwest::Client,
+    vault_url: String,
+    key_name: String,
+    key_version: Option<String>,
+    algorithm: String,
+    credentials: AzureCredentials,
+    encrypted_key: Vec<u8>,
+}
+
+/// How to obtain the Microsoft Entra ID access token used to call Key Vault.
+#[derive(Clone)]
+pub enum AzureCredentials {
+    /// A token for the `https://vault.azure.net` resource obtained out of band, e.g. with
+    /// `az account get-access-token --resource https://vault.azure.net`.
+    AccessToken(String),
+    /// The managed identity of the current Azure VM, fetched from the instance metadata service.
+    ManagedIdentity,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AzureKeyVaultError {
+    #[error(transparent)]
+    Http(reqwest::Error),
+    #[error("Key Vault error {code}: {message}")]
+    KeyVault { code: String, message: String },
+    #[error("decrypted plaintext is not a valid private key")]
+    InvalidPlaintext,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+#[derive(Serialize)]
+struct DecryptRequest<'a> {
+    alg: &'a str,
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct DecryptResponse {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetails,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetails {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl AzureKeyVaultSigner {
+    /// `vault_url` is the vault endpoint, e.g. `https://my-vault.vault.azure.net`.
+    pub fn new(
+        vault_url: String,
+        key_name: String,
+        credentials: AzureCredentials,
+        encrypted_key: Vec<u8>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            vault_url: vault_url.trim_end_matches('/').to_owned(),
+            key_name,
+            key_version: None,
+            algorithm: DEFAULT_ALGORITHM.to_owned(),
+            credentials,
+            encrypted_key,
+        }
+    }
+
+    /// Pins the key version used for decryption. The latest version is used otherwise.
+    pub fn with_key_version(mut self, key_version: String) -> Self {
+        self.key_version = Some(key_version);
+        self
+    }
+
+    /// Sets the encryption algorithm the key was encrypted with. Defaults to `RSA-OAEP-256`.
+    pub fn with_algorithm(mut self, algorithm: String) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    fn decrypt_url(&self) -> String {
+        match &self.key_version {
+            Some(version) => format!(
+                "{}/keys/{}/{}/decrypt?api-version={}",
+                self.vault_url, self.key_name, version, API_VERSION
+            ),
+            None => format!(
+                "{}/keys/{}/decrypt?api-version={}",
+                self.vault_url, self.key_name, API_VERSION
+            ),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, AzureKeyVaultError> {
+        match &self.credentials {
+            AzureCredentials::AccessToken(token) => Ok(token.clone()),
+            AzureCredentials::ManagedIdentity => {
+                let response = self
+                    .client
+                    .get(MANAGED_IDENTITY_TOKEN_URL)
+                    .header("Metadata", "true")
+                    .send()
+                    .await
+                    .map_err(AzureKeyVaultError::Http)?
+                    .error_for_status()
+                    .map_err(AzureKeyVaultError::Http)?;
+
+                let token: TokenResponse =
+                    response.json().await.map_err(AzureKeyVaultError::Http)?;
+                Ok(token.access_token)
+            }
+        }
+    }
+
+    async fn decrypt_key(&self) -> Result<SigningKey, AzureKeyVaultError> {
+        let access_token = self.access_token().await?;
+
+        let response = self
+            .client
+            .post(self.decrypt_url())
+            .bearer_auth(access_token)
+            .json(&DecryptRequest {
+                alg: &self.algorithm,
+                value: base64::encode_config(&self.encrypted_key, base64::URL_SAFE_NO_PAD),
+            })
+            .send()
+            .await
+            .map_err(AzureKeyVaultError::Http)?;
+
+        if !response.status().is_success() {
+            let error: ErrorResponse = response.json().await.map_err(AzureKeyVaultError::Http)?;
+            return Err(AzureKeyVaultError::KeyVault {
+                code: error.error.code,
+                message: error.error.message,
+            });
+        }
+
+        let response: DecryptResponse = response.json().await.map_err(AzureKeyVaultError::Http)?;
+        signing_key_from_encoded_plaintext(response.value, |encoded| {
+            base64::decode_config(encoded, base64::URL_SAFE_NO_PAD)
+        })
+        .ok_or(AzureKeyVaultError::InvalidPlaintext)
+    }
+}
+
+// Manually implemented to keep the token out of logs
+impl std::fmt::Debug for AzureCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessToken(_) => f.write_str("AccessToken(..)"),
+            Self::ManagedIdentity => f.write_str("ManagedIdentity"),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for AzureKeyVaultSigner {
+    type GetPublicKeyError = AzureKeyVaultError;
+    type SignError = AzureKeyVaultError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(self.decrypt_key().await?.verifying_key())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.decrypt_key()
+            .await?
+            .sign(hash)
+            .map_err(AzureKeyVaultError::EcdsaSignError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decrypt_url() {
+        let signer = AzureKeyVaultSigner::new(
+            "https://my-vault.vault.azure.net/".into(),
+            "starknet".into(),
+            AzureCredentials::ManagedIdentity,
+            vec![],
+        );
+        assert_eq!(
+            signer.decrypt_url(),
+            "https://my-vault.vault.azure.net/keys/starknet/decrypt?api-version=7.4"
+        );
+
+        let signer = signer.with_key_version("78deebed173b48e48f55abf87ed4cf71".into());
+        assert_eq!(
+            signer.decrypt_url(),
+            "https://my-vault.vault.azure.net/keys/starknet/78deebed173b48e48f55abf87ed4cf71\
+            /decrypt?api-version=7.4"
+        );
+    }
+}
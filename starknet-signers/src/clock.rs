@@ -0,0 +1,20 @@
+//! The default clock of signers keeping track of time.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The current Unix timestamp in seconds.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn system_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// The current Unix timestamp in seconds, from `Date.now()` as `SystemTime` isn't available on
+/// `wasm32-unknown-unknown`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn system_time() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
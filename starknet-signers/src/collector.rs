@@ -0,0 +1,296 @@
+//! Gathering signatures from the owners of a multisig account out of band, e.g. when each owner
+//! reviews and signs requests on their own device.
+//!
+//! Requests are handed to a [SignatureTransport], which carries them to owners over whatever
+//! channel is in use (an HTTP service, a message queue, a chat bot...) and brings back their
+//! signatures. [SignatureCollector] verifies them and assembles the multisig signature.
+
+use crate::SignRequest;
+
+use async_trait::async_trait;
+use starknet_core::{crypto::Signature, types::FieldElement};
+use starknet_crypto::verify;
+use std::error::Error;
+
+/// Carries signing requests to the owners of a multisig account and their signatures back.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait SignatureTransport {
+    type Error: Error + Send;
+
+    /// Makes `request` available to owners for signing.
+    async fn publish(&self, request: &SignRequest) -> Result<(), Self::Error>;
+
+    /// Waits for the next signature submitted for the request with `hash`. Returns `None` once
+    /// no more signatures can be expected, e.g. when the request expired or was cancelled.
+    async fn next_signature(
+        &self,
+        hash: &FieldElement,
+    ) -> Result<Option<PartialSignature>, Self::Error>;
+}
+
+/// The signature of a single owner.
+#[derive(Debug)]
+pub struct PartialSignature {
+    /// The public key of the owner.
+    pub signer: FieldElement,
+    pub signature: Signature,
+}
+
+/// Signatures of at least the threshold of owners for the same hash.
+#[derive(Debug)]
+pub struct CollectedSignature {
+    signatures: Vec<PartialSignature>,
+}
+
+/// Collects signatures from a set of owners until a threshold is met.
+///
+/// Signatures from unknown signers, invalid signatures and repeated signatures from the same
+/// owner are discarded, so a faulty or compromised transport can delay collection but not get an
+/// invalid signature assembled.
+#[derive(Debug)]
+pub struct SignatureCollector<T> {
+    transport: T,
+    owners: Vec<FieldElement>,
+    threshold: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureCollectorError<T> {
+    #[error(transparent)]
+    Transport(T),
+    #[error("only {collected} of {threshold} required signatures were collected")]
+    Incomplete { collected: usize, threshold: usize },
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("threshold must be between 1 and the number of owners")]
+pub struct InvalidThresholdError;
+
+impl<T> SignatureCollector<T>
+where
+    T: SignatureTransport + Sync + Send,
+{
+    /// Accepts signatures from `owners`, which are the public keys of the multisig account
+    /// owners, and requires `threshold` of them.
+    pub fn new(
+        transport: T,
+        owners: Vec<FieldElement>,
+        threshold: usize,
+    ) -> Result<Self, InvalidThresholdError> {
+        if threshold == 0 || threshold > owners.len() {
+            return Err(InvalidThresholdError);
+        }
+
+        Ok(Self {
+            transport,
+            owners,
+            threshold,
+        })
+    }
+
+    pub fn owners(&self) -> &[FieldElement] {
+        &self.owners
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Publishes `request` and waits for enough owners to sign it.
+    pub async fn collect(
+        &self,
+        request: &SignRequest,
+    ) -> Result<CollectedSignature, SignatureCollectorError<T::Error>> {
+        self.transport
+            .publish(request)
+            .await
+            .map_err(SignatureCollectorError::Transport)?;
+
+        let mut signatures: Vec<PartialSignature> = vec![];
+        while signatures.len() < self.threshold {
+            let partial = match self
+                .transport
+                .next_signature(&request.hash)
+                .await
+                .map_err(SignatureCollectorError::Transport)?
+            {
+                Some(partial) => partial,
+                None => {
+                    return Err(SignatureCollectorError::Incomplete {
+                        collected: signatures.len(),
+                        threshold: self.threshold,
+                    })
+                }
+            };
+
+            let is_owner = self.owners.contains(&partial.signer);
+            let is_new = !signatures
+                .iter()
+                .any(|existing| existing.signer == partial.signer);
+            let is_valid = verify(
+                &partial.signer,
+                &request.hash,
+                &partial.signature.r,
+                &partial.signature.s,
+            )
+            .unwrap_or(false);
+
+            if is_owner && is_new && is_valid {
+                signatures.push(partial);
+            }
+        }
+
+        // Multisig accounts expect signatures ordered by signer
+        signatures.sort_by_key(|partial| partial.signer.to_bytes_be());
+
+        Ok(CollectedSignature { signatures })
+    }
+}
+
+impl CollectedSignature {
+    /// The signatures ordered by signer public key.
+    pub fn signatures(&self) -> &[PartialSignature] {
+        &self.signatures
+    }
+
+    /// Encodes the signatures as `[signer, r, s]` tuples ordered by signer public key, the layout
+    /// expected by Argent multisig accounts.
+    pub fn to_felts(&self) -> Vec<FieldElement> {
+        self.signatures
+            .iter()
+            .flat_map(|partial| [partial.signer, partial.signature.r, partial.signature.s])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SigningKey;
+
+    use std::sync::Mutex;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    /// Replays a fixed list of submissions.
+    struct MockTransport {
+        published: Mutex<Vec<FieldElement>>,
+        submissions: Mutex<Vec<PartialSignature>>,
+    }
+
+    #[async_trait]
+    impl SignatureTransport for MockTransport {
+        type Error = MockError;
+
+        async fn publish(&self, request: &SignRequest) -> Result<(), Self::Error> {
+            self.published.lock().unwrap().push(request.hash);
+            Ok(())
+        }
+
+        async fn next_signature(
+            &self,
+            _hash: &FieldElement,
+        ) -> Result<Option<PartialSignature>, Self::Error> {
+            let mut submissions = self.submissions.lock().unwrap();
+            Ok(if submissions.is_empty() {
+                None
+            } else {
+                Some(submissions.remove(0))
+            })
+        }
+    }
+
+    fn keys() -> Vec<SigningKey> {
+        (1..=3).map(SigningKey::from_test_seed).collect()
+    }
+
+    fn partial(key: &SigningKey, hash: &FieldElement) -> PartialSignature {
+        PartialSignature {
+            signer: key.verifying_key().scalar(),
+            signature: key.sign(hash).unwrap(),
+        }
+    }
+
+    fn collector(submissions: Vec<PartialSignature>) -> SignatureCollector<MockTransport> {
+        SignatureCollector::new(
+            MockTransport {
+                published: Mutex::new(vec![]),
+                submissions: Mutex::new(submissions),
+            },
+            keys()
+                .iter()
+                .map(|key| key.verifying_key().scalar())
+                .collect(),
+            2,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_collect_discards_bad_submissions() {
+        let keys = keys();
+        let hash = FieldElement::from_hex_be("0x1234").unwrap();
+        let outsider = SigningKey::from_test_seed(4);
+
+        let mut forged = partial(&keys[0], &hash);
+        forged.signer = keys[1].verifying_key().scalar();
+
+        let collector = collector(vec![
+            partial(&keys[2], &hash),
+            partial(&keys[2], &hash),
+            partial(&outsider, &hash),
+            forged,
+            partial(&keys[0], &hash),
+            partial(&keys[1], &hash),
+        ]);
+
+        let collected = collector.collect(&SignRequest::blind(hash)).await.unwrap();
+
+        let mut expected = vec![keys[0].verifying_key(), keys[2].verifying_key()]
+            .into_iter()
+            .map(|key| key.scalar())
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|signer| signer.to_bytes_be());
+
+        assert_eq!(
+            collected
+                .signatures()
+                .iter()
+                .map(|partial| partial.signer)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(collected.to_felts().len(), 6);
+        assert_eq!(collected.to_felts()[0], expected[0]);
+        assert_eq!(*collector.transport.published.lock().unwrap(), vec![hash]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_incomplete() {
+        let hash = FieldElement::from_hex_be("0x1234").unwrap();
+        let collector = collector(vec![partial(&keys()[0], &hash)]);
+
+        assert!(matches!(
+            collector.collect(&SignRequest::blind(hash)).await,
+            Err(SignatureCollectorError::Incomplete {
+                collected: 1,
+                threshold: 2
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_invalid_threshold() {
+        let transport = || MockTransport {
+            published: Mutex::new(vec![]),
+            submissions: Mutex::new(vec![]),
+        };
+
+        assert!(SignatureCollector::new(transport(), vec![FieldElement::ONE], 0).is_err());
+        assert!(SignatureCollector::new(transport(), vec![FieldElement::ONE], 2).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{Infallible, SignRequest, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{crypto::Signature, types::FieldElement};
+use std::{error::Error, future::Future};
+
+/// Asks for approval before a [ConfirmingSigner] signs a request, e.g. by prompting on the
+/// terminal or posting to a chat channel and waiting for a reaction.
+///
+/// Implemented for async closures taking an owned [SignRequest] and resolving to whether the
+/// request is approved.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Confirmation {
+    type Error: Error + Send;
+
+    async fn confirm(&self, request: &SignRequest) -> Result<bool, Self::Error>;
+}
+
+/// Wraps a signer so that every request is approved through a [Confirmation] before being
+/// signed.
+///
+/// Requests for fee estimation are confirmed as well. Implementations can tell them apart with
+/// [SignRequest::is_fee_estimation].
+#[derive(Debug)]
+pub struct ConfirmingSigner<S, C> {
+    signer: S,
+    confirmation: C,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfirmingSignerError<S, C> {
+    #[error("request was not confirmed")]
+    Rejected,
+    #[error(transparent)]
+    Confirmation(C),
+    #[error(transparent)]
+    Signer(S),
+}
+
+impl<S, C> ConfirmingSigner<S, C> {
+    pub fn new(signer: S, confirmation: C) -> Self {
+        Self {
+            signer,
+            confirmation,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<S, C> Signer for ConfirmingSigner<S, C>
+where
+    S: Signer + Sync + Send,
+    C: Confirmation + Sync + Send,
+{
+    type GetPublicKeyError = S::GetPublicKeyError;
+    type SignError = ConfirmingSignerError<S::SignError, C::Error>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        self.signer.get_public_key().await
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.sign_request(&SignRequest::blind(*hash)).await
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError> {
+        let confirmed = self
+            .confirmation
+            .confirm(request)
+            .await
+            .map_err(ConfirmingSignerError::Confirmation)?;
+        if !confirmed {
+            return Err(ConfirmingSignerError::Rejected);
+        }
+
+        self.signer
+            .sign_request(request)
+            .await
+            .map_err(ConfirmingSignerError::Signer)
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl<F, Fut> Confirmation for F
+where
+    F: Fn(SignRequest) -> Fut + Sync,
+    Fut: Future<Output = bool> + Send,
+{
+    type Error = Infallible;
+
+    async fn confirm(&self, request: &SignRequest) -> Result<bool, Self::Error> {
+        Ok(self(request.clone()).await)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+impl<F, Fut> Confirmation for F
+where
+    F: Fn(SignRequest) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Error = Infallible;
+
+    async fn confirm(&self, request: &SignRequest) -> Result<bool, Self::Error> {
+        Ok(self(request.clone()).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LocalWallet, SigningKey};
+
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    fn wallet() -> LocalWallet {
+        LocalWallet::from_signing_key(SigningKey::from_secret_scalar(FieldElement::ONE))
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_request_is_signed() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let signer = ConfirmingSigner::new(wallet(), {
+            let calls = calls.clone();
+            move |request: SignRequest| {
+                let calls = calls.clone();
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    request.hash == FieldElement::TWO
+                }
+            }
+        });
+
+        let signature = signer.sign_hash(&FieldElement::TWO).await.unwrap();
+        assert!(wallet()
+            .get_public_key()
+            .await
+            .unwrap()
+            .verify(&FieldElement::TWO, &signature)
+            .unwrap());
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::THREE).await,
+            Err(ConfirmingSignerError::Rejected)
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_error() {
+        struct Unreachable;
+
+        #[derive(Debug, thiserror::Error)]
+        #[error("approver unreachable")]
+        struct UnreachableError;
+
+        #[async_trait]
+        impl Confirmation for Unreachable {
+            type Error = UnreachableError;
+
+            async fn confirm(&self, _request: &SignRequest) -> Result<bool, Self::Error> {
+                Err(UnreachableError)
+            }
+        }
+
+        let signer = ConfirmingSigner::new(wallet(), Unreachable);
+
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::ONE).await,
+            Err(ConfirmingSignerError::Confirmation(UnreachableError))
+        ));
+    }
+}
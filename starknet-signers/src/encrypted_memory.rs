@@ -0,0 +1,281 @@
+use crate::{clock::system_time, Infallible, KeystoreError, Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+use std::{error::Error, sync::Mutex, time::Duration};
+use zeroize::Zeroize;
+
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Supplies the passphrase of an [EncryptedMemorySigner] when it needs to be unlocked, e.g. by
+/// prompting an operator or reading it from a secret manager.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait PassphraseProvider {
+    type Error: Error + Send;
+
+    async fn passphrase(&self) -> Result<String, Self::Error>;
+}
+
+/// A signer that keeps its key encrypted in memory, in the same format as keystore files, and
+/// only decrypts it when signing.
+///
+/// Once unlocked, the key stays decrypted until it has been idle for the configured timeout, to
+/// avoid asking for the passphrase on every signature. As there's no background task, an idle
+/// key is wiped the next time the signer is used, or when [lock_if_idle](Self::lock_if_idle) is
+/// called, which long-running services should do periodically.
+#[derive(Debug)]
+pub struct EncryptedMemorySigner<P> {
+    encrypted_key: String,
+    public_key: VerifyingKey,
+    passphrase: P,
+    idle_timeout: Duration,
+    clock: fn() -> u64,
+    unlocked: Mutex<Option<UnlockedKey>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptedMemorySignerError<P> {
+    #[error(transparent)]
+    Passphrase(P),
+    #[error(transparent)]
+    Keystore(KeystoreError),
+    #[error("decrypted key does not match the public key of the signer")]
+    KeyMismatch,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+#[derive(Debug)]
+struct UnlockedKey {
+    key: SigningKey,
+    last_used: u64,
+}
+
+impl<P> EncryptedMemorySigner<P> {
+    /// Encrypts `key` with `passphrase`, which must later be returned by `provider`. The
+    /// signer starts locked.
+    pub fn new(key: SigningKey, passphrase: &str, provider: P) -> Result<Self, KeystoreError> {
+        Ok(Self::from_parts(
+            key.to_keystore_json(passphrase)?,
+            key.verifying_key(),
+            provider,
+        ))
+    }
+
+    /// Uses a key that's already encrypted, e.g. read from a keystore file. The key is
+    /// decrypted once to get its public key, and the signer starts locked.
+    pub fn from_keystore_json(
+        json: String,
+        passphrase: &str,
+        provider: P,
+    ) -> Result<Self, KeystoreError> {
+        let public_key = SigningKey::from_keystore_json(&json, passphrase)?.verifying_key();
+        Ok(Self::from_parts(json, public_key, provider))
+    }
+
+    fn from_parts(encrypted_key: String, public_key: VerifyingKey, passphrase: P) -> Self {
+        Self {
+            encrypted_key,
+            public_key,
+            passphrase,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            clock: system_time,
+            unlocked: Mutex::new(None),
+        }
+    }
+
+    /// Sets how long the key stays decrypted after it was last used. Defaults to 5 minutes. A
+    /// zero timeout asks for the passphrase on every signature.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Uses `clock` to get the current Unix timestamp in seconds for idle timeouts. Defaults to
+    /// the system clock.
+    pub fn with_clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.lock().unwrap().is_some()
+    }
+
+    /// Wipes the decrypted key, if any.
+    pub fn lock(&self) {
+        self.unlocked.lock().unwrap().take();
+    }
+
+    /// Wipes the decrypted key if it has been idle for longer than the timeout.
+    pub fn lock_if_idle(&self) {
+        let now = (self.clock)();
+        let mut unlocked = self.unlocked.lock().unwrap();
+        if matches!(&*unlocked, Some(key) if self.is_idle(key, now)) {
+            unlocked.take();
+        }
+    }
+
+    fn is_idle(&self, key: &UnlockedKey, now: u64) -> bool {
+        now.saturating_sub(key.last_used) >= self.idle_timeout.as_secs()
+    }
+
+    /// Signs with the decrypted key if it's still unlocked.
+    fn sign_unlocked(&self, hash: &FieldElement) -> Option<Result<Signature, EcdsaSignError>> {
+        let now = (self.clock)();
+        let mut unlocked = self.unlocked.lock().unwrap();
+
+        match unlocked.as_mut() {
+            Some(key) if !self.is_idle(key, now) => {
+                key.last_used = now;
+                Some(key.key.sign(hash))
+            }
+            _ => {
+                unlocked.take();
+                None
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<P> Signer for EncryptedMemorySigner<P>
+where
+    P: PassphraseProvider + Sync + Send,
+{
+    type GetPublicKeyError = Infallible;
+    type SignError = EncryptedMemorySignerError<P::Error>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(self.public_key.clone())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        if let Some(result) = self.sign_unlocked(hash) {
+            return result.map_err(EncryptedMemorySignerError::EcdsaSignError);
+        }
+
+        let mut passphrase = self
+            .passphrase
+            .passphrase()
+            .await
+            .map_err(EncryptedMemorySignerError::Passphrase)?;
+        let key = SigningKey::from_keystore_json(&self.encrypted_key, &passphrase);
+        passphrase.zeroize();
+
+        let key = key.map_err(EncryptedMemorySignerError::Keystore)?;
+        if key.verifying_key().scalar() != self.public_key.scalar() {
+            return Err(EncryptedMemorySignerError::KeyMismatch);
+        }
+
+        let signature = key
+            .sign(hash)
+            .map_err(EncryptedMemorySignerError::EcdsaSignError)?;
+
+        if !self.idle_timeout.is_zero() {
+            *self.unlocked.lock().unwrap() = Some(UnlockedKey {
+                key,
+                last_used: (self.clock)(),
+            });
+        }
+
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    static NOW: AtomicU64 = AtomicU64::new(1_000_000);
+
+    /// Always returns the right passphrase, counting how many times it was asked for.
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PassphraseProvider for CountingProvider {
+        type Error = Infallible;
+
+        async fn passphrase(&self) -> Result<String, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok("passphrase".into())
+        }
+    }
+
+    fn signer() -> EncryptedMemorySigner<CountingProvider> {
+        EncryptedMemorySigner::new(
+            SigningKey::from_secret_scalar(FieldElement::ONE),
+            "passphrase",
+            CountingProvider::default(),
+        )
+        .unwrap()
+        .with_idle_timeout(Duration::from_secs(60))
+        .with_clock(|| NOW.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_relocks_after_idle_timeout() {
+        let signer = signer();
+        let public_key = signer.get_public_key().await.unwrap();
+        assert!(!signer.is_unlocked());
+
+        let signature = signer.sign_hash(&FieldElement::TWO).await.unwrap();
+        assert!(public_key.verify(&FieldElement::TWO, &signature).unwrap());
+        assert!(signer.is_unlocked());
+
+        NOW.fetch_add(30, Ordering::SeqCst);
+        signer.sign_hash(&FieldElement::TWO).await.unwrap();
+        signer.lock_if_idle();
+        assert!(signer.is_unlocked());
+        assert_eq!(signer.passphrase.calls.load(Ordering::SeqCst), 1);
+
+        NOW.fetch_add(60, Ordering::SeqCst);
+        signer.lock_if_idle();
+        assert!(!signer.is_unlocked());
+
+        signer.sign_hash(&FieldElement::TWO).await.unwrap();
+        assert_eq!(signer.passphrase.calls.load(Ordering::SeqCst), 2);
+
+        signer.lock();
+        assert!(!signer.is_unlocked());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_passphrase() {
+        struct WrongProvider;
+
+        #[async_trait]
+        impl PassphraseProvider for WrongProvider {
+            type Error = Infallible;
+
+            async fn passphrase(&self) -> Result<String, Self::Error> {
+                Ok("wrong".into())
+            }
+        }
+
+        let signer = EncryptedMemorySigner::new(
+            SigningKey::from_secret_scalar(FieldElement::ONE),
+            "passphrase",
+            WrongProvider,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::TWO).await,
+            Err(EncryptedMemorySignerError::Keystore(
+                KeystoreError::MacMismatch
+            ))
+        ));
+        assert!(!signer.is_unlocked());
+    }
+}
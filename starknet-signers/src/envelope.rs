@@ -0,0 +1,80 @@
+//! Shared logic for signers that keep the Stark private key encrypted by a cloud key management
+//! service, as none of them support the Stark curve natively. The key is decrypted every time
+//! it's needed instead of being kept by the signer, so access can be revoked and audited by the
+//! service. The decrypted key still lives in memory while a signature is computed: buffers
+//! holding it are zeroized right after the [SigningKey] is parsed, and the [SigningKey] wipes
+//! itself on drop.
+
+use crate::SigningKey;
+
+use starknet_core::types::FieldElement;
+use zeroize::Zeroize;
+
+/// Decodes a decrypted private key returned as text (e.g. base64) with `decode`, then parses it
+/// with [signing_key_from_plaintext]. Both the encoded and the decoded plaintext are zeroized.
+pub(crate) fn signing_key_from_encoded_plaintext<E>(
+    mut encoded: String,
+    decode: impl FnOnce(&str) -> Result<Vec<u8>, E>,
+) -> Option<SigningKey> {
+    let plaintext = decode(&encoded);
+    encoded.zeroize();
+
+    let mut plaintext = plaintext.ok()?;
+    let signing_key = signing_key_from_plaintext(&plaintext);
+    plaintext.zeroize();
+
+    signing_key
+}
+
+/// Parses a decrypted private key, which can either be the raw 32-byte big-endian scalar or its
+/// hex representation.
+pub(crate) fn signing_key_from_plaintext(plaintext: &[u8]) -> Option<SigningKey> {
+    let secret_scalar = match <&[u8; 32]>::try_from(plaintext) {
+        Ok(bytes) => FieldElement::from_bytes_be(bytes).ok()?,
+        Err(_) => FieldElement::from_hex_be(std::str::from_utf8(plaintext).ok()?.trim()).ok()?,
+    };
+
+    Some(SigningKey::from_secret_scalar(secret_scalar))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_signing_key_from_plaintext() {
+        let private_key = FieldElement::from_hex_be(
+            "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79",
+        )
+        .unwrap();
+
+        let from_bytes = signing_key_from_plaintext(&private_key.to_bytes_be()).unwrap();
+        let from_hex = signing_key_from_plaintext(
+            b"0x0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79\n",
+        )
+        .unwrap();
+
+        assert_eq!(from_bytes.secret_scalar(), private_key);
+        assert_eq!(from_hex.secret_scalar(), private_key);
+        assert!(signing_key_from_plaintext(b"not a key").is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_signing_key_from_encoded_plaintext() {
+        let private_key = FieldElement::from_hex_be("0x1234").unwrap();
+
+        let signing_key =
+            signing_key_from_encoded_plaintext(hex::encode(private_key.to_bytes_be()), |encoded| {
+                hex::decode(encoded)
+            })
+            .unwrap();
+
+        assert_eq!(signing_key.secret_scalar(), private_key);
+        assert!(
+            signing_key_from_encoded_plaintext("zz".into(), |encoded| hex::decode(encoded))
+                .is_none()
+        );
+    }
+}
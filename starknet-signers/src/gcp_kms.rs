@@ -0,0 +1,197 @@
+use crate::{envelope::signing_key_from_encoded_plaintext, Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+
+const KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com/v1";
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// A signer whose Stark private key is encrypted with a Cloud KMS (or Cloud HSM) key, as Cloud
+/// KMS doesn't support the Stark curve. The key is decrypted every time it's used.
+///
+/// The encrypted key is the `ciphertext` returned by Cloud KMS `encrypt`, with either the raw
+/// 32-byte scalar or its hex representation as the plaintext.
+#[derive(Debug, Clone)]
+pub struct GcpKmsSigner {
+    client: reqwest::Client,
+    key_name: String,
+    credentials: GcpCredentials,
+    encrypted_key: Vec<u8>,
+}
+
+/// How to obtain the OAuth 2.0 access token used to call Cloud KMS.
+#[derive(Clone)]
+pub enum GcpCredentials {
+    /// A token obtained out of band, e.g. with `gcloud auth print-access-token`.
+    AccessToken(String),
+    /// The default service account of the current environment, fetched from the metadata
+    /// server available on Compute Engine, GKE, Cloud Run and Cloud Functions.
+    MetadataServer,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GcpKmsError {
+    #[error(transparent)]
+    Http(reqwest::Error),
+    #[error("Cloud KMS error {status}: {message}")]
+    Kms { status: String, message: String },
+    #[error("decrypted plaintext is not a valid private key")]
+    InvalidPlaintext,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+#[derive(Serialize)]
+struct DecryptRequest {
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+struct DecryptResponse {
+    plaintext: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetails,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetails {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl GcpKmsSigner {
+    /// `key_name` is the resource name of the crypto key, in the form of
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*`.
+    pub fn new(key_name: String, credentials: GcpCredentials, encrypted_key: Vec<u8>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            key_name,
+            credentials,
+            encrypted_key,
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, GcpKmsError> {
+        match &self.credentials {
+            GcpCredentials::AccessToken(token) => Ok(token.clone()),
+            GcpCredentials::MetadataServer => {
+                let response = self
+                    .client
+                    .get(METADATA_TOKEN_URL)
+                    .header("Metadata-Flavor", "Google")
+                    .send()
+                    .await
+                    .map_err(GcpKmsError::Http)?
+                    .error_for_status()
+                    .map_err(GcpKmsError::Http)?;
+
+                let token: TokenResponse = response.json().await.map_err(GcpKmsError::Http)?;
+                Ok(token.access_token)
+            }
+        }
+    }
+
+    async fn decrypt_key(&self) -> Result<SigningKey, GcpKmsError> {
+        let access_token = self.access_token().await?;
+
+        let response = self
+            .client
+            .post(format!("{}/{}:decrypt", KMS_ENDPOINT, self.key_name))
+            .bearer_auth(access_token)
+            .json(&DecryptRequest {
+                ciphertext: base64::encode(&self.encrypted_key),
+            })
+            .send()
+            .await
+            .map_err(GcpKmsError::Http)?;
+
+        if !response.status().is_success() {
+            let error: ErrorResponse = response.json().await.map_err(GcpKmsError::Http)?;
+            return Err(GcpKmsError::Kms {
+                status: error.error.status,
+                message: error.error.message,
+            });
+        }
+
+        let response: DecryptResponse = response.json().await.map_err(GcpKmsError::Http)?;
+        signing_key_from_encoded_plaintext(response.plaintext, |encoded| base64::decode(encoded))
+            .ok_or(GcpKmsError::InvalidPlaintext)
+    }
+}
+
+// Manually implemented to keep the token out of logs
+impl std::fmt::Debug for GcpCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessToken(_) => f.write_str("AccessToken(..)"),
+            Self::MetadataServer => f.write_str("MetadataServer"),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for GcpKmsSigner {
+    type GetPublicKeyError = GcpKmsError;
+    type SignError = GcpKmsError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(self.decrypt_key().await?.verifying_key())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.decrypt_key()
+            .await?
+            .sign(hash)
+            .map_err(GcpKmsError::EcdsaSignError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_error_response_deser() {
+        let error: ErrorResponse = serde_json::from_str(
+            r#"{
+                "error": {
+                    "code": 400,
+                    "message": "Decryption failed: the ciphertext is invalid.",
+                    "status": "INVALID_ARGUMENT"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(error.error.status, "INVALID_ARGUMENT");
+        assert_eq!(
+            error.error.message,
+            "Decryption failed: the ciphertext is invalid."
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_credentials_debug_hides_token() {
+        let credentials = GcpCredentials::AccessToken("ya29.secret".into());
+
+        assert_eq!(format!("{credentials:?}"), "AccessToken(..)");
+    }
+}
@@ -0,0 +1,327 @@
+//! Signing with browser extension wallets such as Argent X and Braavos, through the
+//! `window.starknet` objects they inject into web pages.
+
+use crate::{SignContext, SignRequest, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use js_sys::{Array, Function, Promise, Reflect, JSON};
+use serde_json::{json, Value};
+use starknet_core::{
+    crypto::Signature,
+    types::FieldElement,
+    utils::{cairo_short_string_to_felt, get_selector_from_name},
+};
+use std::collections::HashMap;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// The global object injected by the wallet that was last selected by the user.
+pub const DEFAULT_WALLET: &str = "starknet";
+pub const ARGENT_X: &str = "starknet_argentX";
+pub const BRAAVOS: &str = "starknet_braavos";
+
+/// A wallet injected into the page, which signs with its own keys after asking for approval.
+///
+/// Wallets display the entry points being called by name, while only selectors are known when
+/// signing. Names of the entry points to be called must therefore be registered with
+/// [with_entrypoint_names](Self::with_entrypoint_names) beforehand.
+///
+/// Only handles to the wallet are kept, so this type is `Send` and `Sync` even though it wraps
+/// JavaScript objects.
+#[derive(Debug, Clone)]
+pub struct InjectedWallet {
+    global_name: String,
+    address: FieldElement,
+    chain_id: FieldElement,
+    entrypoint_names: HashMap<FieldElement, String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InjectedWalletError {
+    #[error("wallet `{0}` not found")]
+    NotFound(String),
+    #[error("wallet error: {0}")]
+    Wallet(String),
+    #[error("unexpected response from wallet")]
+    InvalidResponse,
+    #[error("no entry point name registered for selector {0:#064x}")]
+    UnknownEntrypoint(FieldElement),
+    #[error("entry point name is not ASCII")]
+    NonAsciiEntrypoint,
+    #[error("wallets don't sign hashes without context")]
+    BlindSigningUnsupported,
+    #[error("expected a signature with 2 elements but got {0}")]
+    UnexpectedSignatureLength(usize),
+}
+
+impl InjectedWallet {
+    /// Connects to the wallet injected as `window[global_name]`, prompting the user to allow the
+    /// page to access it if needed.
+    pub async fn connect(global_name: &str) -> Result<Self, InjectedWalletError> {
+        let wallet = Self::wallet_object(global_name)?;
+        call_method(&wallet, "enable", &[]).await?;
+
+        let address = get_property(&wallet, "selectedAddress")?
+            .as_string()
+            .and_then(|address| FieldElement::from_hex_be(&address).ok())
+            .ok_or(InjectedWalletError::InvalidResponse)?;
+        let chain_id = get_property(&wallet, "chainId")?
+            .as_string()
+            .and_then(|chain_id| parse_chain_id(&chain_id))
+            .ok_or(InjectedWalletError::InvalidResponse)?;
+
+        Ok(Self {
+            global_name: global_name.to_owned(),
+            address,
+            chain_id,
+            entrypoint_names: HashMap::new(),
+        })
+    }
+
+    /// Registers entry point names so that calls to them can be displayed by the wallet.
+    pub fn with_entrypoint_names<I, S>(mut self, names: I) -> Result<Self, InjectedWalletError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for name in names.into_iter() {
+            let name = name.into();
+            let selector = get_selector_from_name(&name)
+                .map_err(|_| InjectedWalletError::NonAsciiEntrypoint)?;
+            self.entrypoint_names.insert(selector, name);
+        }
+        Ok(self)
+    }
+
+    /// The account address selected in the wallet when connecting.
+    pub fn address(&self) -> FieldElement {
+        self.address
+    }
+
+    /// The chain ID selected in the wallet when connecting.
+    pub fn chain_id(&self) -> FieldElement {
+        self.chain_id
+    }
+
+    /// Asks the wallet to sign the transaction described by `request`, returning the signature
+    /// as produced by the account contract, which can have any number of elements.
+    pub async fn sign_transaction(
+        &self,
+        request: &SignRequest,
+    ) -> Result<Vec<FieldElement>, InjectedWalletError> {
+        let (method, args) = match &request.context {
+            SignContext::Blind => return Err(InjectedWalletError::BlindSigningUnsupported),
+            SignContext::Invoke(context) => {
+                let calls = context
+                    .calls
+                    .iter()
+                    .map(|call| {
+                        let entrypoint = self
+                            .entrypoint_names
+                            .get(&call.selector)
+                            .ok_or(InjectedWalletError::UnknownEntrypoint(call.selector))?;
+                        Ok(json!({
+                            "contractAddress": format!("{:#x}", call.to),
+                            "entrypoint": entrypoint,
+                            "calldata": felts_to_json(&call.calldata),
+                        }))
+                    })
+                    .collect::<Result<Vec<_>, InjectedWalletError>>()?;
+
+                (
+                    "signTransaction",
+                    vec![
+                        Value::Array(calls),
+                        json!({
+                            "walletAddress": format!("{:#x}", context.sender_address),
+                            "chainId": format!("{:#x}", context.chain_id),
+                            "nonce": format!("{:#x}", context.nonce),
+                            "maxFee": format!("{:#x}", context.max_fee),
+                            "version": "0x1",
+                        }),
+                    ],
+                )
+            }
+            SignContext::Declare(context) => (
+                "signDeclareTransaction",
+                vec![json!({
+                    "classHash": format!("{:#x}", context.class_hash),
+                    "senderAddress": format!("{:#x}", context.sender_address),
+                    "chainId": format!("{:#x}", context.chain_id),
+                    "nonce": format!("{:#x}", context.nonce),
+                    "maxFee": format!("{:#x}", context.max_fee),
+                    "version": "0x1",
+                })],
+            ),
+            SignContext::DeployAccount(context) => (
+                "signDeployAccountTransaction",
+                vec![json!({
+                    "classHash": format!("{:#x}", context.class_hash),
+                    "contractAddress": format!("{:#x}", context.contract_address),
+                    "addressSalt": format!("{:#x}", context.contract_address_salt),
+                    "constructorCalldata": felts_to_json(&context.constructor_calldata),
+                    "chainId": format!("{:#x}", context.chain_id),
+                    "nonce": format!("{:#x}", context.nonce),
+                    "maxFee": format!("{:#x}", context.max_fee),
+                    "version": "0x1",
+                })],
+            ),
+        };
+
+        let args = args.iter().map(json_to_js).collect::<Result<Vec<_>, _>>()?;
+        let signer = self.signer_object()?;
+        let signature = js_to_json(&call_method(&signer, method, &args).await?)?;
+
+        parse_signature(&signature).ok_or(InjectedWalletError::InvalidResponse)
+    }
+
+    /// Asks the wallet to sign a SNIP-12 typed data message.
+    pub async fn sign_message(
+        &self,
+        typed_data: &Value,
+    ) -> Result<Vec<FieldElement>, InjectedWalletError> {
+        let signer = self.signer_object()?;
+        let signature =
+            js_to_json(&call_method(&signer, "signMessage", &[json_to_js(typed_data)?]).await?)?;
+
+        parse_signature(&signature).ok_or(InjectedWalletError::InvalidResponse)
+    }
+
+    fn wallet_object(global_name: &str) -> Result<JsValue, InjectedWalletError> {
+        match Reflect::get(&js_sys::global(), &JsValue::from_str(global_name)) {
+            Ok(wallet) if wallet.is_object() => Ok(wallet),
+            _ => Err(InjectedWalletError::NotFound(global_name.to_owned())),
+        }
+    }
+
+    fn signer_object(&self) -> Result<JsValue, InjectedWalletError> {
+        let account = get_property(&Self::wallet_object(&self.global_name)?, "account")?;
+        get_property(&account, "signer")
+    }
+}
+
+#[async_trait(?Send)]
+impl Signer for InjectedWallet {
+    type GetPublicKeyError = InjectedWalletError;
+    type SignError = InjectedWalletError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        let public_key = call_method(&self.signer_object()?, "getPubKey", &[])
+            .await?
+            .as_string()
+            .and_then(|public_key| FieldElement::from_hex_be(&public_key).ok())
+            .ok_or(InjectedWalletError::InvalidResponse)?;
+
+        Ok(VerifyingKey::from_scalar(public_key))
+    }
+
+    async fn sign_hash(&self, _hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        Err(InjectedWalletError::BlindSigningUnsupported)
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError> {
+        match self.sign_transaction(request).await?.as_slice() {
+            [r, s] => Ok(Signature { r: *r, s: *s }),
+            signature => Err(InjectedWalletError::UnexpectedSignatureLength(
+                signature.len(),
+            )),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+/// Chain IDs are exposed either as hex-encoded felts or as plain short strings.
+fn parse_chain_id(chain_id: &str) -> Option<FieldElement> {
+    if chain_id.starts_with("0x") {
+        FieldElement::from_hex_be(chain_id).ok()
+    } else {
+        cairo_short_string_to_felt(chain_id).ok()
+    }
+}
+
+/// Signatures are arrays of either hex or decimal strings.
+fn parse_signature(signature: &Value) -> Option<Vec<FieldElement>> {
+    signature
+        .as_array()?
+        .iter()
+        .map(|item| {
+            let item = item.as_str()?;
+            if item.starts_with("0x") {
+                FieldElement::from_hex_be(item).ok()
+            } else {
+                FieldElement::from_dec_str(item).ok()
+            }
+        })
+        .collect()
+}
+
+fn felts_to_json(felts: &[FieldElement]) -> Vec<String> {
+    felts.iter().map(|felt| format!("{felt:#x}")).collect()
+}
+
+fn get_property(target: &JsValue, name: &str) -> Result<JsValue, InjectedWalletError> {
+    Reflect::get(target, &JsValue::from_str(name)).map_err(js_error)
+}
+
+/// Calls `target[name](...args)` and awaits the result if it's a promise.
+async fn call_method(
+    target: &JsValue,
+    name: &str,
+    args: &[JsValue],
+) -> Result<JsValue, InjectedWalletError> {
+    let method: Function = get_property(target, name)?
+        .dyn_into()
+        .map_err(|_| InjectedWalletError::Wallet(format!("`{name}` is not supported")))?;
+    let result = method
+        .apply(target, &args.iter().collect::<Array>())
+        .map_err(js_error)?;
+
+    JsFuture::from(Promise::resolve(&result))
+        .await
+        .map_err(js_error)
+}
+
+fn json_to_js(value: &Value) -> Result<JsValue, InjectedWalletError> {
+    JSON::parse(&value.to_string()).map_err(js_error)
+}
+
+fn js_to_json(value: &JsValue) -> Result<Value, InjectedWalletError> {
+    let json: String = JSON::stringify(value).map_err(js_error)?.into();
+    serde_json::from_str(&json).map_err(|_| InjectedWalletError::InvalidResponse)
+}
+
+fn js_error(value: JsValue) -> InjectedWalletError {
+    let message = value
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| value.as_string())
+        .unwrap_or_else(|| format!("{value:?}"));
+    InjectedWalletError::Wallet(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_chain_id() {
+        let expected = cairo_short_string_to_felt("SN_GOERLI").unwrap();
+
+        assert_eq!(parse_chain_id("SN_GOERLI"), Some(expected));
+        assert_eq!(parse_chain_id("0x534e5f474f45524c49"), Some(expected));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_signature() {
+        assert_eq!(
+            parse_signature(&json!(["0x1", "2"])),
+            Some(vec![FieldElement::ONE, FieldElement::TWO])
+        );
+        assert_eq!(parse_signature(&json!({ "r": "0x1" })), None);
+    }
+}
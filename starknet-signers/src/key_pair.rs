@@ -1,12 +1,26 @@
+use rand::{CryptoRng, RngCore};
 use starknet_core::{
     crypto::{ecdsa_sign, ecdsa_verify, EcdsaSignError, EcdsaVerifyError, Signature},
     types::FieldElement,
 };
 use starknet_crypto::get_public_key;
+#[cfg(any(test, feature = "test-keys"))]
+use starknet_crypto::grind_key;
+use starknet_curve::curve_params::EC_ORDER;
+use std::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    path::Path,
+    ptr::NonNull,
+    sync::atomic::{compiler_fence, Ordering},
+};
+use zeroize::Zeroize;
 
-#[derive(Debug, Clone)]
+use crate::keystore::{self, KeystoreError};
+
+/// A Stark private key. The secret scalar is kept at a fixed heap location that's wiped when the
+/// key is dropped, and never shows up in [Debug] output.
 pub struct SigningKey {
-    secret_scalar: FieldElement,
+    secret_scalar: SecretScalar,
 }
 
 #[derive(Debug, Clone)]
@@ -14,24 +28,205 @@ pub struct VerifyingKey {
     scalar: FieldElement,
 }
 
+/// Heap storage for a secret scalar that's never moved or copied by the container itself, so
+/// that a single location needs to be wiped on drop.
+struct SecretScalar {
+    ptr: NonNull<FieldElement>,
+    layout: Layout,
+    #[cfg(all(unix, feature = "mlock"))]
+    locked: bool,
+}
+
 impl SigningKey {
     pub fn from_secret_scalar(secret_scalar: FieldElement) -> Self {
-        Self { secret_scalar }
+        Self {
+            secret_scalar: SecretScalar::new(secret_scalar),
+        }
+    }
+
+    /// Generates a new key with the thread-local random number generator of the `rand` crate.
+    pub fn from_random() -> Self {
+        Self::from_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a new key with `rng`, which allows using a source of randomness other than the
+    /// operating system's, e.g. on platforms without one.
+    pub fn from_rng<R>(rng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut buffer = [0u8; 32];
+
+        // Rejection sampling over 252 bits, where more than half of the values are below the order
+        let secret_scalar = loop {
+            rng.fill_bytes(&mut buffer);
+            buffer[0] &= 0x0f;
+
+            match FieldElement::from_bytes_be(&buffer) {
+                Ok(value) if value != FieldElement::ZERO && value < EC_ORDER => break value,
+                _ => continue,
+            }
+        };
+        buffer.zeroize();
+
+        Self::from_secret_scalar(secret_scalar)
+    }
+
+    /// Deterministically derives a key from `seed` for reproducible tests. Keys generated this
+    /// way are trivially guessable and must never hold funds.
+    #[cfg(any(test, feature = "test-keys"))]
+    pub fn from_test_seed(seed: u64) -> Self {
+        Self::from_secret_scalar(grind_key(&seed.to_be_bytes()))
     }
 
+    /// Returns a copy of the secret scalar. Unlike the key itself, the copy isn't wiped when
+    /// dropped.
     pub fn secret_scalar(&self) -> FieldElement {
-        self.secret_scalar
+        *self.secret_scalar.get()
+    }
+
+    /// Moves the secret scalar into its own memory page and locks it with `mlock` so that it never
+    /// gets swapped to disk. Clones of a locked key are locked as well when possible.
+    #[cfg(all(unix, feature = "mlock"))]
+    pub fn lock_memory(&mut self) -> std::io::Result<()> {
+        if !self.secret_scalar.locked {
+            self.secret_scalar = SecretScalar::new_locked(*self.secret_scalar.get())?;
+        }
+        Ok(())
     }
 
     pub fn verifying_key(&self) -> VerifyingKey {
-        VerifyingKey::from_scalar(get_public_key(&self.secret_scalar))
+        VerifyingKey::from_scalar(get_public_key(self.secret_scalar.get()))
     }
 
     pub fn sign(&self, hash: &FieldElement) -> Result<Signature, EcdsaSignError> {
-        ecdsa_sign(&self.secret_scalar, hash)
+        ecdsa_sign(self.secret_scalar.get(), hash)
+    }
+
+    /// Loads the key from an encrypted keystore file in the Web3 Secret Storage format, as
+    /// created by `starkli`.
+    pub fn from_keystore<P>(path: P, password: &str) -> Result<Self, KeystoreError>
+    where
+        P: AsRef<Path>,
+    {
+        let json = std::fs::read_to_string(path).map_err(KeystoreError::Io)?;
+        Self::from_keystore_json(&json, password)
+    }
+
+    pub(crate) fn from_keystore_json(json: &str, password: &str) -> Result<Self, KeystoreError> {
+        let mut secret = keystore::decrypt(json, password)?;
+
+        let secret_scalar = FieldElement::from_bytes_be(&secret);
+        secret.zeroize();
+
+        Ok(Self::from_secret_scalar(
+            secret_scalar.map_err(|_| KeystoreError::InvalidKey)?,
+        ))
+    }
+
+    pub(crate) fn to_keystore_json(&self, password: &str) -> Result<String, KeystoreError> {
+        let mut secret = self.secret_scalar.get().to_bytes_be();
+        let json = keystore::encrypt(&secret, password);
+        secret.zeroize();
+
+        json
+    }
+
+    /// Encrypts the key with `password` and saves it as a keystore file in the Web3 Secret
+    /// Storage format, which can be used with `starkli`.
+    pub fn save_as_keystore<P>(&self, path: P, password: &str) -> Result<(), KeystoreError>
+    where
+        P: AsRef<Path>,
+    {
+        let json = self.to_keystore_json(password)?;
+        std::fs::write(path, json).map_err(KeystoreError::Io)
+    }
+}
+
+impl Clone for SigningKey {
+    fn clone(&self) -> Self {
+        #[cfg(all(unix, feature = "mlock"))]
+        if self.secret_scalar.locked {
+            if let Ok(secret_scalar) = SecretScalar::new_locked(*self.secret_scalar.get()) {
+                return Self { secret_scalar };
+            }
+        }
+
+        Self::from_secret_scalar(*self.secret_scalar.get())
+    }
+}
+
+// Manually implemented to keep the secret out of logs
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey").finish_non_exhaustive()
+    }
+}
+
+impl SecretScalar {
+    fn new(value: FieldElement) -> Self {
+        Self::with_layout(value, Layout::new::<FieldElement>())
+    }
+
+    #[cfg(all(unix, feature = "mlock"))]
+    fn new_locked(value: FieldElement) -> std::io::Result<Self> {
+        // A whole page is used as `mlock` works on pages, and unlocking a shared page on drop would
+        // unlock other secrets as well
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let layout = Layout::from_size_align(page_size, page_size)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::Unsupported))?;
+
+        let mut secret = Self::with_layout(value, layout);
+        if unsafe { libc::mlock(secret.ptr.as_ptr() as *const libc::c_void, page_size) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        secret.locked = true;
+
+        Ok(secret)
+    }
+
+    fn with_layout(value: FieldElement, layout: Layout) -> Self {
+        // SAFETY: `layout` is never zero-sized as it's at least as large as `FieldElement`
+        let ptr = unsafe { alloc(layout) } as *mut FieldElement;
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout));
+        // SAFETY: the pointer is valid and properly aligned for `FieldElement`
+        unsafe { ptr.as_ptr().write(value) };
+
+        Self {
+            ptr,
+            layout,
+            #[cfg(all(unix, feature = "mlock"))]
+            locked: false,
+        }
+    }
+
+    fn get(&self) -> &FieldElement {
+        // SAFETY: the pointer is initialized in `with_layout` and only freed on drop
+        unsafe { self.ptr.as_ref() }
     }
 }
 
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        // SAFETY: the pointer is valid until deallocated below. The volatile write and the fence
+        // prevent the wipe from being optimized away
+        unsafe { self.ptr.as_ptr().write_volatile(FieldElement::ZERO) };
+        compiler_fence(Ordering::SeqCst);
+
+        #[cfg(all(unix, feature = "mlock"))]
+        if self.locked {
+            unsafe { libc::munlock(self.ptr.as_ptr() as *const libc::c_void, self.layout.size()) };
+        }
+
+        // SAFETY: the pointer was allocated with the same layout in `with_layout`
+        unsafe { dealloc(self.ptr.as_ptr() as *mut u8, self.layout) };
+    }
+}
+
+// SAFETY: the container exclusively owns the allocation, just like `Box<FieldElement>`
+unsafe impl Send for SecretScalar {}
+unsafe impl Sync for SecretScalar {}
+
 impl VerifyingKey {
     pub fn from_scalar(scalar: FieldElement) -> Self {
         Self { scalar }
@@ -68,6 +263,49 @@ mod tests {
         assert_eq!(signing_key.secret_scalar(), private_key);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_from_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let key = SigningKey::from_rng(&mut StdRng::seed_from_u64(1));
+
+        assert_eq!(
+            key.secret_scalar(),
+            SigningKey::from_rng(&mut StdRng::seed_from_u64(1)).secret_scalar()
+        );
+        assert_ne!(
+            key.secret_scalar(),
+            SigningKey::from_rng(&mut StdRng::seed_from_u64(2)).secret_scalar()
+        );
+        assert!(key.secret_scalar().to_bytes_be() < EC_ORDER.to_bytes_be());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_from_random() {
+        let key = SigningKey::from_random();
+
+        assert_ne!(key.secret_scalar(), FieldElement::ZERO);
+        assert_ne!(
+            key.secret_scalar(),
+            SigningKey::from_random().secret_scalar()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_from_test_seed() {
+        assert_eq!(
+            SigningKey::from_test_seed(42).secret_scalar(),
+            grind_key(&42u64.to_be_bytes())
+        );
+        assert_ne!(
+            SigningKey::from_test_seed(42).secret_scalar(),
+            SigningKey::from_test_seed(43).secret_scalar()
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_get_verifying_key() {
@@ -115,6 +353,56 @@ mod tests {
         assert_eq!(signature.s, expected_s);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_debug_hides_secret_scalar() {
+        let signing_key = SigningKey::from_secret_scalar(
+            FieldElement::from_hex_be(
+                "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79",
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(format!("{signing_key:?}"), "SigningKey { .. }");
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "mlock"))]
+    fn test_lock_memory() {
+        let private_key = FieldElement::from_hex_be(
+            "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79",
+        )
+        .unwrap();
+
+        let mut signing_key = SigningKey::from_secret_scalar(private_key);
+        // Locking can legitimately fail when `RLIMIT_MEMLOCK` is too low
+        if signing_key.lock_memory().is_ok() {
+            let cloned_key = signing_key.clone();
+
+            assert!(cloned_key.secret_scalar.locked);
+            assert_eq!(cloned_key.secret_scalar(), private_key);
+        }
+        assert_eq!(signing_key.secret_scalar(), private_key);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_keystore_round_trip() {
+        let private_key = FieldElement::from_hex_be(
+            "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79",
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join("starknet-signers-test-keystore.json");
+
+        let signing_key = SigningKey::from_secret_scalar(private_key);
+        signing_key.save_as_keystore(&path, "password").unwrap();
+
+        let loaded_key = SigningKey::from_keystore(&path, "password").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded_key.secret_scalar(), private_key);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     fn test_hash_out_of_range() {
@@ -0,0 +1,328 @@
+//! Encrypted key files in the [Web3 Secret Storage](https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/)
+//! format, as used by `starkli` and Ethereum tooling.
+
+use aes::{
+    cipher::{KeyIvInit, StreamCipher},
+    Aes128,
+};
+use ctr::Ctr128BE;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+
+const CIPHER_AES_128_CTR: &str = "aes-128-ctr";
+const PRF_HMAC_SHA256: &str = "hmac-sha256";
+
+/// Same scrypt parameters as `starkli` for newly created keystores.
+const DEFAULT_SCRYPT_N: u32 = 8192;
+const DEFAULT_SCRYPT_R: u32 = 8;
+const DEFAULT_SCRYPT_P: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error(transparent)]
+    Json(serde_json::Error),
+    #[error("unsupported keystore version: {0}")]
+    UnsupportedVersion(u64),
+    #[error("unsupported cipher: {0}")]
+    UnsupportedCipher(String),
+    #[error("unsupported pseudorandom function: {0}")]
+    UnsupportedPrf(String),
+    #[error("invalid keystore parameters")]
+    InvalidParameters,
+    #[error("incorrect password")]
+    MacMismatch,
+    #[error("decrypted key is not a valid private key")]
+    InvalidKey,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreFile {
+    #[serde(alias = "Crypto")]
+    crypto: CryptoJson,
+    id: String,
+    version: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    #[serde(flatten)]
+    kdf: KdfJson,
+    mac: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+enum KdfJson {
+    Scrypt(ScryptParams),
+    Pbkdf2(Pbkdf2Params),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u32,
+    p: u32,
+    r: u32,
+    salt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Pbkdf2Params {
+    c: u32,
+    dklen: usize,
+    prf: String,
+    salt: String,
+}
+
+/// Encrypts a 32-byte secret with `password`, returning the keystore JSON.
+pub(crate) fn encrypt(secret: &[u8; 32], password: &str) -> Result<String, KeystoreError> {
+    let mut rng = rand::thread_rng();
+
+    let mut salt = [0u8; 32];
+    rng.fill_bytes(&mut salt);
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut iv);
+    let mut id = [0u8; 16];
+    rng.fill_bytes(&mut id);
+
+    let kdf = KdfJson::Scrypt(ScryptParams {
+        dklen: 32,
+        n: DEFAULT_SCRYPT_N,
+        p: DEFAULT_SCRYPT_P,
+        r: DEFAULT_SCRYPT_R,
+        salt: hex::encode(salt),
+    });
+
+    serde_json::to_string(&encrypt_with(secret, password, kdf, iv, id)?)
+        .map_err(KeystoreError::Json)
+}
+
+/// Decrypts the 32-byte secret stored in the keystore JSON.
+pub(crate) fn decrypt(json: &str, password: &str) -> Result<[u8; 32], KeystoreError> {
+    let keystore: KeystoreFile = serde_json::from_str(json).map_err(KeystoreError::Json)?;
+
+    if keystore.version != 3 {
+        return Err(KeystoreError::UnsupportedVersion(keystore.version));
+    }
+    if keystore.crypto.cipher != CIPHER_AES_128_CTR {
+        return Err(KeystoreError::UnsupportedCipher(keystore.crypto.cipher));
+    }
+
+    let derived_key = derive_key(&keystore.crypto.kdf, password)?;
+    let mut ciphertext = decode_hex(&keystore.crypto.ciphertext)?;
+    let iv: [u8; 16] = decode_hex(&keystore.crypto.cipherparams.iv)?
+        .try_into()
+        .map_err(|_| KeystoreError::InvalidParameters)?;
+
+    let mac = decode_hex(&keystore.crypto.mac)?;
+    if !bool::from(compute_mac(&derived_key, &ciphertext).ct_eq(&mac[..])) {
+        return Err(KeystoreError::MacMismatch);
+    }
+
+    aes_128_ctr(&derived_key, &iv, &mut ciphertext);
+
+    ciphertext.try_into().map_err(|_| KeystoreError::InvalidKey)
+}
+
+fn encrypt_with(
+    secret: &[u8; 32],
+    password: &str,
+    kdf: KdfJson,
+    iv: [u8; 16],
+    id: [u8; 16],
+) -> Result<KeystoreFile, KeystoreError> {
+    let derived_key = derive_key(&kdf, password)?;
+
+    let mut ciphertext = secret.to_vec();
+    aes_128_ctr(&derived_key, &iv, &mut ciphertext);
+    let mac = compute_mac(&derived_key, &ciphertext);
+
+    Ok(KeystoreFile {
+        crypto: CryptoJson {
+            cipher: CIPHER_AES_128_CTR.into(),
+            cipherparams: CipherParams {
+                iv: hex::encode(iv),
+            },
+            ciphertext: hex::encode(ciphertext),
+            kdf,
+            mac: hex::encode(mac),
+        },
+        id: format_uuid_v4(id),
+        version: 3,
+    })
+}
+
+fn derive_key(kdf: &KdfJson, password: &str) -> Result<Vec<u8>, KeystoreError> {
+    match kdf {
+        KdfJson::Scrypt(params) => {
+            if params.dklen < 32
+                || params.n < 2
+                || !params.n.is_power_of_two()
+                || params.r == 0
+                || params.p == 0
+            {
+                return Err(KeystoreError::InvalidParameters);
+            }
+
+            let scrypt_params = scrypt::Params::new(
+                params.n.trailing_zeros() as u8,
+                params.r,
+                params.p,
+                params.dklen,
+            )
+            .map_err(|_| KeystoreError::InvalidParameters)?;
+
+            let mut derived_key = vec![0u8; params.dklen];
+            scrypt::scrypt(
+                password.as_bytes(),
+                &decode_hex(&params.salt)?,
+                &scrypt_params,
+                &mut derived_key,
+            )
+            .map_err(|_| KeystoreError::InvalidParameters)?;
+            Ok(derived_key)
+        }
+        KdfJson::Pbkdf2(params) => {
+            if params.prf != PRF_HMAC_SHA256 {
+                return Err(KeystoreError::UnsupportedPrf(params.prf.clone()));
+            }
+            if params.dklen < 32 || params.c == 0 {
+                return Err(KeystoreError::InvalidParameters);
+            }
+
+            let mut derived_key = vec![0u8; params.dklen];
+            pbkdf2::pbkdf2_hmac::<Sha256>(
+                password.as_bytes(),
+                &decode_hex(&params.salt)?,
+                params.c,
+                &mut derived_key,
+            );
+            Ok(derived_key)
+        }
+    }
+}
+
+/// Encrypts or decrypts `data` in place with AES-128-CTR, keyed with the first half of
+/// `derived_key`.
+fn aes_128_ctr(derived_key: &[u8], iv: &[u8; 16], data: &mut [u8]) {
+    Ctr128BE::<Aes128>::new(derived_key[..16].into(), iv.into()).apply_keystream(data);
+}
+
+fn compute_mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived_key[16..32]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|_| KeystoreError::InvalidParameters)
+}
+
+fn format_uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79";
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decrypt_scrypt() {
+        // Generated with Python `hashlib` and `cryptography`
+        let json = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6465666768696a6b6c6d6e6f70717273" },
+                "ciphertext": "814eb902e4a64b711133e432915159faf6d03bf4843efb6b3ed1e8d585c99e0a",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 1024,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+                },
+                "mac": "88c586d33c28e5a912e64f73221fb745b0a1895b74f754a257c24904cc6fdc5b"
+            },
+            "id": "d4b4f5a1-8b41-4c9b-a1a4-51c4e8d8c62b",
+            "version": 3
+        }"#;
+
+        assert_eq!(hex::encode(decrypt(json, "password").unwrap()), SECRET);
+        assert!(matches!(
+            decrypt(json, "wrong password"),
+            Err(KeystoreError::MacMismatch)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_decrypt_pbkdf2() {
+        // Generated with Python `hashlib` and `cryptography`
+        let json = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6465666768696a6b6c6d6e6f70717273" },
+                "ciphertext": "1ddd076262018ae67e393a71c3b28956b600e4202b2a53f834d78f7ffdcd41dc",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 1024,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+                },
+                "mac": "c2dcd87feed7b25dc36fd63428644fb339922bb9eaf10a9922bf3f58250e5f7d"
+            },
+            "id": "d4b4f5a1-8b41-4c9b-a1a4-51c4e8d8c62b",
+            "version": 3
+        }"#;
+
+        assert_eq!(hex::encode(decrypt(json, "password").unwrap()), SECRET);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_encrypt_round_trip() {
+        let secret: [u8; 32] = hex::decode(SECRET).unwrap().try_into().unwrap();
+        let kdf = KdfJson::Pbkdf2(Pbkdf2Params {
+            c: 16,
+            dklen: 32,
+            prf: PRF_HMAC_SHA256.into(),
+            salt: "00".repeat(32),
+        });
+
+        let keystore = encrypt_with(&secret, "password", kdf, [1u8; 16], [2u8; 16]).unwrap();
+        let json = serde_json::to_string(&keystore).unwrap();
+
+        assert_eq!(keystore.id, "02020202-0202-4202-8202-020202020202");
+        assert_eq!(decrypt(&json, "password").unwrap(), secret);
+    }
+}
@@ -1,11 +1,117 @@
+/// Instruments a future with a span when the `tracing` feature is enabled. Span fields are not
+/// evaluated otherwise.
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {{
+        let span = tracing::info_span!($($span)+);
+        tracing::Instrument::instrument($future, span)
+    }};
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {
+        $future
+    };
+}
+
+mod clock;
+
 mod key_pair;
 pub use key_pair::{SigningKey, VerifyingKey};
 
+mod keystore;
+pub use keystore::KeystoreError;
+
+mod mnemonic;
+pub use mnemonic::{DerivationPath, InvalidDerivationPathError, MnemonicError, MnemonicWallet};
+
 mod signer;
 pub use signer::Signer;
 
+mod sign_request;
+pub use sign_request::{
+    CallContext, CalldataEncoding, DeclareContext, DeployAccountContext, InvokeContext,
+    SignContext, SignRequest,
+};
+
+mod audit;
+pub use audit::{AuditOutcome, AuditRecord, AuditSink, AuditedSigner, AuditedSignerError};
+
+pub mod collector;
+pub use collector::{SignatureCollector, SignatureTransport};
+
+mod confirming;
+pub use confirming::{Confirmation, ConfirmingSigner, ConfirmingSignerError};
+
+mod encrypted_memory;
+pub use encrypted_memory::{EncryptedMemorySigner, EncryptedMemorySignerError, PassphraseProvider};
+
+mod policy;
+pub use policy::{PolicySigner, PolicySignerError, PolicyViolation, SigningPolicy};
+
+pub mod secp256k1;
+pub use secp256k1::ledger::LedgerEthSigner;
+pub use secp256k1::{stark_key_from_eth_signature, Secp256k1Signature, Secp256k1Signer};
+
+pub mod threshold;
+pub use threshold::{ThresholdSigner, ThresholdWallet};
+
+pub mod wallet_import;
+pub use wallet_import::{ArgentXBackup, BraavosBackup};
+
 pub mod local_wallet;
 pub use local_wallet::LocalWallet;
 
+#[cfg(any(
+    feature = "aws-kms",
+    feature = "azure-key-vault",
+    feature = "gcp-kms",
+    all(unix, feature = "pkcs11"),
+    feature = "vault"
+))]
+mod envelope;
+
+#[cfg(feature = "aws-kms")]
+pub mod aws_kms;
+#[cfg(feature = "aws-kms")]
+pub use aws_kms::AwsKmsSigner;
+
+#[cfg(feature = "azure-key-vault")]
+pub mod azure_key_vault;
+#[cfg(feature = "azure-key-vault")]
+pub use azure_key_vault::AzureKeyVaultSigner;
+
+#[cfg(feature = "gcp-kms")]
+pub mod gcp_kms;
+#[cfg(feature = "gcp-kms")]
+pub use gcp_kms::GcpKmsSigner;
+
+#[cfg(all(target_arch = "wasm32", feature = "injected"))]
+pub mod injected;
+#[cfg(all(target_arch = "wasm32", feature = "injected"))]
+pub use injected::InjectedWallet;
+
+#[cfg(all(unix, feature = "pkcs11"))]
+pub mod pkcs11;
+#[cfg(all(unix, feature = "pkcs11"))]
+pub use pkcs11::Pkcs11Signer;
+
+#[cfg(feature = "vault")]
+pub mod vault_transit;
+#[cfg(feature = "vault")]
+pub use vault_transit::VaultTransitSigner;
+
+pub mod walletconnect;
+pub use walletconnect::WalletConnectSigner;
+
+pub mod webauthn;
+#[cfg(feature = "webauthn")]
+pub use webauthn::WebauthnSigner;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod yubikey;
+#[cfg(not(target_arch = "wasm32"))]
+pub use yubikey::YubiKeySigner;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Infallible {}
@@ -34,7 +34,12 @@ impl Signer for LocalWallet {
     }
 
     async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
-        Ok(self.private_key.sign(hash)?)
+        instrument!(
+            async { Ok(self.private_key.sign(hash)?) },
+            "sign",
+            hash = %format_args!("{hash:#x}")
+        )
+        .await
     }
 }
 
@@ -0,0 +1,468 @@
+//! BIP-39 mnemonic phrases and BIP-32 hierarchical deterministic derivation of Stark keys.
+//!
+//! Stark keys are derived the same way as Argent X and Braavos do: a secp256k1 private key is
+//! derived from the mnemonic seed with BIP-32, and then turned into a valid Stark private key
+//! with [grind_key].
+
+use crate::SigningKey;
+
+use hmac::{Hmac, Mac};
+use k256::{
+    elliptic_curve::{sec1::ToEncodedPoint, PrimeField},
+    ProjectivePoint, Scalar,
+};
+use sha2::{Digest, Sha256, Sha512};
+use starknet_crypto::grind_key;
+use std::{fmt::Display, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+const WORDLIST: &str = include_str!("./english.txt");
+const SEED_ROUNDS: u32 = 2048;
+const HARDENED: u32 = 0x80000000;
+
+/// The purpose used by Argent X and Braavos, followed by the Starknet coin type.
+const STARKNET_BASE_PATH: [u32; 4] = [44 | HARDENED, 9004 | HARDENED, HARDENED, 0];
+const EIP2645_PURPOSE: u32 = 2645;
+/// The purpose followed by the Ethereum coin type, as used by MetaMask and the Ledger Ethereum app.
+const ETHEREUM_BASE_PATH: [u32; 4] = [44 | HARDENED, 60 | HARDENED, HARDENED, 0];
+
+/// A wallet holding the seed of a BIP-39 mnemonic phrase, from which any number of Stark keys can
+/// be derived deterministically.
+#[derive(Clone)]
+pub struct MnemonicWallet {
+    seed: Vec<u8>,
+}
+
+/// A BIP-32 derivation path, e.g. `m/44'/9004'/0'/0/0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath {
+    indices: Vec<u32>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MnemonicError {
+    #[error("invalid number of words: {0}")]
+    InvalidWordCount(usize),
+    #[error("unknown word: {0}")]
+    UnknownWord(String),
+    #[error("invalid checksum")]
+    InvalidChecksum,
+    #[error("invalid seed length: {0} bytes")]
+    InvalidSeedLength(usize),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid derivation path")]
+pub struct InvalidDerivationPathError;
+
+impl MnemonicWallet {
+    /// Parses an English BIP-39 mnemonic phrase, validating its checksum.
+    pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        Self::from_phrase_with_passphrase(phrase, "")
+    }
+
+    /// Same as [from_phrase](Self::from_phrase), but with the optional BIP-39 passphrase (also
+    /// known as the "25th word").
+    pub fn from_phrase_with_passphrase(
+        phrase: &str,
+        passphrase: &str,
+    ) -> Result<Self, MnemonicError> {
+        let phrase: String = phrase.nfkd().collect();
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+        validate_checksum(&words)?;
+
+        let salt: String = format!("mnemonic{passphrase}").nfkd().collect();
+        let mut seed = vec![0u8; 64];
+        pbkdf2::pbkdf2_hmac::<Sha512>(
+            words.join(" ").as_bytes(),
+            salt.as_bytes(),
+            SEED_ROUNDS,
+            &mut seed,
+        );
+
+        Ok(Self { seed })
+    }
+
+    /// Uses a BIP-32 seed of 16 to 64 bytes directly, e.g. one obtained from a mnemonic phrase
+    /// elsewhere.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, MnemonicError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(MnemonicError::InvalidSeedLength(seed.len()));
+        }
+
+        Ok(Self {
+            seed: seed.to_vec(),
+        })
+    }
+
+    /// Derives the Stark private key at `path`.
+    pub fn derive(&self, path: &DerivationPath) -> SigningKey {
+        let secp256k1_key = self.derive_secp256k1(path);
+        SigningKey::from_secret_scalar(grind_key(&secp256k1_key))
+    }
+
+    /// Derives the raw secp256k1 private key at `path`, before grinding.
+    pub(crate) fn derive_secp256k1(&self, path: &DerivationPath) -> [u8; 32] {
+        derive_secp256k1_key(&self.seed, &path.indices)
+    }
+}
+
+impl DerivationPath {
+    /// The path of the account at `index` in Argent X and Braavos, `m/44'/9004'/0'/0/{index}`.
+    pub fn starknet(index: u32) -> Self {
+        let mut indices = STARKNET_BASE_PATH.to_vec();
+        indices.push(index);
+        Self { indices }
+    }
+
+    /// The path of the Ethereum account at `index` in MetaMask and Ledger Live,
+    /// `m/44'/60'/0'/0/{index}`.
+    pub fn ethereum(index: u32) -> Self {
+        let mut indices = ETHEREUM_BASE_PATH.to_vec();
+        indices.push(index);
+        Self { indices }
+    }
+
+    /// The EIP-2645 path `m/2645'/{layer}'/{application}'/{eth_address_1}'/{eth_address_2}'/{index}`
+    /// where `layer` and `application` are the lowest 31 bits of the SHA-256 hashes of their
+    /// names, and `eth_address_1` and `eth_address_2` are the lowest 31 bits and the next 31 bits
+    /// of the Ethereum address respectively.
+    pub fn eip2645(layer: &str, application: &str, eth_address: &[u8; 20], index: u32) -> Self {
+        let eth_address_low = u64::from_be_bytes(eth_address[12..].try_into().unwrap());
+
+        Self {
+            indices: vec![
+                EIP2645_PURPOSE | HARDENED,
+                lowest_31_bits(&Sha256::digest(layer.as_bytes())) | HARDENED,
+                lowest_31_bits(&Sha256::digest(application.as_bytes())) | HARDENED,
+                (eth_address_low & 0x7fffffff) as u32 | HARDENED,
+                ((eth_address_low >> 31) & 0x7fffffff) as u32 | HARDENED,
+                index,
+            ],
+        }
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = InvalidDerivationPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = s.split('/');
+        if segments.next() != Some("m") {
+            return Err(InvalidDerivationPathError);
+        }
+
+        let indices = segments
+            .map(|segment| {
+                let (segment, hardened) = match segment.strip_suffix(['\'', 'h']) {
+                    Some(segment) => (segment, true),
+                    None => (segment, false),
+                };
+
+                match segment.parse::<u32>() {
+                    Ok(index) if index < HARDENED => {
+                        Ok(if hardened { index | HARDENED } else { index })
+                    }
+                    _ => Err(InvalidDerivationPathError),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { indices })
+    }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m")?;
+        for index in self.indices.iter() {
+            if index & HARDENED == 0 {
+                write!(f, "/{index}")?;
+            } else {
+                write!(f, "/{}'", index & !HARDENED)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MnemonicWallet {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+    }
+}
+
+// Manually implemented to keep the seed out of logs
+impl std::fmt::Debug for MnemonicWallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MnemonicWallet").finish_non_exhaustive()
+    }
+}
+
+fn validate_checksum(words: &[&str]) -> Result<(), MnemonicError> {
+    if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err(MnemonicError::InvalidWordCount(words.len()));
+    }
+
+    let mut bits = Vec::with_capacity(words.len() * 11);
+    for word in words.iter() {
+        let index = WORDLIST
+            .lines()
+            .position(|candidate| candidate == *word)
+            .ok_or_else(|| MnemonicError::UnknownWord(word.to_string()))?;
+        bits.extend((0..11).rev().map(|bit| (index >> bit) & 1 == 1));
+    }
+
+    let checksum_len = words.len() / 3;
+    let (entropy_bits, checksum_bits) = bits.split_at(bits.len() - checksum_len);
+
+    let entropy = entropy_bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8))
+        .collect::<Vec<_>>();
+    let hash = Sha256::digest(&entropy);
+
+    let checksum_matches = checksum_bits
+        .iter()
+        .enumerate()
+        .all(|(ind, bit)| ((hash[0] >> (7 - ind)) & 1 == 1) == *bit);
+    if checksum_matches {
+        Ok(())
+    } else {
+        Err(MnemonicError::InvalidChecksum)
+    }
+}
+
+pub(crate) fn derive_secp256k1_key(seed: &[u8], indices: &[u32]) -> [u8; 32] {
+    let (master_key, mut chain_code) = split_hmac(b"Bitcoin seed", &[seed]);
+
+    // The chance of getting an invalid master key is lower than 1 in 2^127, and unlike child keys
+    // BIP-32 has no next index to fall back on, so it's simply treated as unreachable here
+    let mut key = scalar_from_bytes(&master_key)
+        .filter(|key| !bool::from(key.is_zero()))
+        .expect("invalid BIP-32 master key");
+
+    for index in indices.iter() {
+        // BIP-32 says to proceed with the next index when the child key is invalid
+        let mut index = *index;
+        (key, chain_code) = loop {
+            match derive_child_key(&key, &chain_code, index) {
+                Some(child) => break child,
+                None => index = index.wrapping_add(1),
+            }
+        };
+    }
+
+    key.to_bytes().into()
+}
+
+/// Derives the child key and chain code at `index`, or `None` if the child key is invalid.
+fn derive_child_key(
+    parent_key: &Scalar,
+    chain_code: &[u8; 32],
+    index: u32,
+) -> Option<(Scalar, [u8; 32])> {
+    let (tweak, child_chain_code) = if index & HARDENED == 0 {
+        let public_key = (ProjectivePoint::GENERATOR * parent_key)
+            .to_affine()
+            .to_encoded_point(true);
+        split_hmac(chain_code, &[public_key.as_bytes(), &index.to_be_bytes()])
+    } else {
+        split_hmac(
+            chain_code,
+            &[&[0], &parent_key.to_bytes(), &index.to_be_bytes()],
+        )
+    };
+
+    let child_key = scalar_from_bytes(&tweak)? + parent_key;
+    if bool::from(child_key.is_zero()) {
+        None
+    } else {
+        Some((child_key, child_chain_code))
+    }
+}
+
+fn split_hmac(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC can take key of any size");
+    for chunk in data.iter() {
+        mac.update(chunk);
+    }
+    let output = mac.finalize().into_bytes();
+
+    (
+        output[..32].try_into().unwrap(),
+        output[32..].try_into().unwrap(),
+    )
+}
+
+/// Parses a big-endian scalar, or `None` if it's not below the curve order.
+fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr((*bytes).into()))
+}
+
+fn lowest_31_bits(hash: &[u8]) -> u32 {
+    u32::from_be_bytes(hash[hash.len() - 4..].try_into().unwrap()) & 0x7fffffff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FieldElement;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_seed() {
+        // Test vector from the Trezor reference implementation
+        let wallet = MnemonicWallet::from_phrase_with_passphrase(PHRASE, "TREZOR").unwrap();
+
+        assert_eq!(
+            hex::encode(&wallet.seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e\
+            1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_validate_checksum() {
+        for phrase in [
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo \
+            zoo zoo vote",
+        ] {
+            assert!(MnemonicWallet::from_phrase(phrase).is_ok());
+        }
+
+        assert!(matches!(
+            MnemonicWallet::from_phrase(&PHRASE.replace("about", "above")),
+            Err(MnemonicError::InvalidChecksum)
+        ));
+        assert!(matches!(
+            MnemonicWallet::from_phrase(&PHRASE.replace("about", "starknet")),
+            Err(MnemonicError::UnknownWord(_))
+        ));
+        assert!(matches!(
+            MnemonicWallet::from_phrase("abandon abandon about"),
+            Err(MnemonicError::InvalidWordCount(3))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_derive_secp256k1_key() {
+        // Test vector 1 from BIP-32
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path: DerivationPath = "m/0'/1".parse().unwrap();
+
+        assert_eq!(
+            hex::encode(derive_secp256k1_key(&seed, path.indices())),
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_scalar_from_bytes() {
+        // Keys not below the curve order are invalid in BIP-32
+        assert!(scalar_from_bytes(&[0xff; 32]).is_none());
+        assert!(scalar_from_bytes(&[0x01; 32]).is_some());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_derive() {
+        let wallet = MnemonicWallet::from_phrase(PHRASE).unwrap();
+
+        assert_eq!(
+            wallet.derive(&DerivationPath::starknet(0)).secret_scalar(),
+            FieldElement::from_hex_be(
+                "001b8e16cdf31892c56c0370f0e4ca0da096ef4e0c81007b3ba10b11452f8971"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            wallet.derive(&DerivationPath::starknet(1)).secret_scalar(),
+            FieldElement::from_hex_be(
+                "06d582b352685f7c37a2faa748536c741c3a8c660cb011bce57457a32cd04d1a"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_eip2645_path() {
+        let eth_address: [u8; 20] = hex::decode("a4864d977b944315389d1765ffa7e66f74ee8cd7")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let path = DerivationPath::eip2645("starkex", "starkdeployement", &eth_address, 0);
+
+        assert_eq!(
+            path.to_string(),
+            "m/2645'/579218131'/891216374'/1961790679'/2135936222'/0"
+        );
+        assert_eq!(
+            MnemonicWallet::from_phrase(PHRASE)
+                .unwrap()
+                .derive(&path)
+                .secret_scalar(),
+            FieldElement::from_hex_be(
+                "0433ce62185aa3ef5549258b0dd627489f2e498c6d2e4ee0d6c65203c8b0ab61"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_from_seed() {
+        let path = DerivationPath::starknet(0);
+        let wallet = MnemonicWallet::from_phrase(PHRASE).unwrap();
+
+        assert_eq!(
+            MnemonicWallet::from_seed(&wallet.seed)
+                .unwrap()
+                .derive(&path)
+                .secret_scalar(),
+            wallet.derive(&path).secret_scalar()
+        );
+        assert!(matches!(
+            MnemonicWallet::from_seed(&[0u8; 8]),
+            Err(MnemonicError::InvalidSeedLength(8))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_derivation_path_parsing() {
+        let path: DerivationPath = "m/44'/9004'/0'/0/7".parse().unwrap();
+        assert_eq!(path, DerivationPath::starknet(7));
+        assert_eq!(path.to_string(), "m/44'/9004'/0'/0/7");
+        assert_eq!(
+            "m/44h/9004h/0h/0/7".parse::<DerivationPath>().unwrap(),
+            path
+        );
+
+        assert_eq!(
+            "m/44'/60'/0'/0/0".parse::<DerivationPath>().unwrap(),
+            DerivationPath::ethereum(0)
+        );
+
+        assert!("44'/9004'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+        assert!("m/0''".parse::<DerivationPath>().is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Minimal bindings to the PKCS#11 v2.40 C API, covering only the functions used by
+//! [Pkcs11Signer](super::Pkcs11Signer).
+
+#![allow(non_snake_case, non_camel_case_types)]
+
+use std::os::raw::{c_ulong, c_void};
+
+pub type CK_ULONG = c_ulong;
+pub type CK_RV = CK_ULONG;
+pub type CK_SLOT_ID = CK_ULONG;
+pub type CK_SESSION_HANDLE = CK_ULONG;
+pub type CK_OBJECT_HANDLE = CK_ULONG;
+pub type CK_BBOOL = u8;
+
+pub const CKR_OK: CK_RV = 0x0;
+pub const CKR_USER_ALREADY_LOGGED_IN: CK_RV = 0x100;
+pub const CKR_CRYPTOKI_ALREADY_INITIALIZED: CK_RV = 0x191;
+
+pub const CKF_OS_LOCKING_OK: CK_ULONG = 0x2;
+pub const CKF_SERIAL_SESSION: CK_ULONG = 0x4;
+
+pub const CKU_USER: CK_ULONG = 1;
+
+pub const CKA_CLASS: CK_ULONG = 0x0;
+pub const CKA_LABEL: CK_ULONG = 0x3;
+pub const CKA_EC_POINT: CK_ULONG = 0x181;
+
+pub const CKO_PUBLIC_KEY: CK_ULONG = 0x2;
+pub const CKO_PRIVATE_KEY: CK_ULONG = 0x3;
+pub const CKO_SECRET_KEY: CK_ULONG = 0x4;
+
+pub const CKM_ECDSA: CK_ULONG = 0x1041;
+pub const CKM_AES_CBC_PAD: CK_ULONG = 0x1085;
+
+#[repr(C)]
+pub struct CK_VERSION {
+    pub major: u8,
+    pub minor: u8,
+}
+
+#[repr(C)]
+pub struct CK_ATTRIBUTE {
+    pub type_: CK_ULONG,
+    pub pValue: *mut c_void,
+    pub ulValueLen: CK_ULONG,
+}
+
+#[repr(C)]
+pub struct CK_MECHANISM {
+    pub mechanism: CK_ULONG,
+    pub pParameter: *mut c_void,
+    pub ulParameterLen: CK_ULONG,
+}
+
+#[repr(C)]
+pub struct CK_C_INITIALIZE_ARGS {
+    pub CreateMutex: *mut c_void,
+    pub DestroyMutex: *mut c_void,
+    pub LockMutex: *mut c_void,
+    pub UnlockMutex: *mut c_void,
+    pub flags: CK_ULONG,
+    pub pReserved: *mut c_void,
+}
+
+#[repr(C)]
+pub struct CK_TOKEN_INFO {
+    pub label: [u8; 32],
+    pub manufacturerID: [u8; 32],
+    pub model: [u8; 16],
+    pub serialNumber: [u8; 16],
+    pub flags: CK_ULONG,
+    pub ulMaxSessionCount: CK_ULONG,
+    pub ulSessionCount: CK_ULONG,
+    pub ulMaxRwSessionCount: CK_ULONG,
+    pub ulRwSessionCount: CK_ULONG,
+    pub ulMaxPinLen: CK_ULONG,
+    pub ulMinPinLen: CK_ULONG,
+    pub ulTotalPublicMemory: CK_ULONG,
+    pub ulFreePublicMemory: CK_ULONG,
+    pub ulTotalPrivateMemory: CK_ULONG,
+    pub ulFreePrivateMemory: CK_ULONG,
+    pub hardwareVersion: CK_VERSION,
+    pub firmwareVersion: CK_VERSION,
+    pub utcTime: [u8; 16],
+}
+
+type Unused = Option<unsafe extern "C" fn()>;
+
+/// The leading entries of `CK_FUNCTION_LIST`, in the order defined by the specification.
+/// Entries after `C_Sign` are never accessed and therefore omitted.
+#[repr(C)]
+pub struct CK_FUNCTION_LIST {
+    pub version: CK_VERSION,
+    pub C_Initialize: Option<unsafe extern "C" fn(pInitArgs: *mut c_void) -> CK_RV>,
+    pub C_Finalize: Option<unsafe extern "C" fn(pReserved: *mut c_void) -> CK_RV>,
+    pub C_GetInfo: Unused,
+    pub C_GetFunctionList: Unused,
+    pub C_GetSlotList: Option<
+        unsafe extern "C" fn(
+            tokenPresent: CK_BBOOL,
+            pSlotList: *mut CK_SLOT_ID,
+            pulCount: *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_GetSlotInfo: Unused,
+    pub C_GetTokenInfo:
+        Option<unsafe extern "C" fn(slotID: CK_SLOT_ID, pInfo: *mut CK_TOKEN_INFO) -> CK_RV>,
+    pub C_GetMechanismList: Unused,
+    pub C_GetMechanismInfo: Unused,
+    pub C_InitToken: Unused,
+    pub C_InitPIN: Unused,
+    pub C_SetPIN: Unused,
+    pub C_OpenSession: Option<
+        unsafe extern "C" fn(
+            slotID: CK_SLOT_ID,
+            flags: CK_ULONG,
+            pApplication: *mut c_void,
+            Notify: *mut c_void,
+            phSession: *mut CK_SESSION_HANDLE,
+        ) -> CK_RV,
+    >,
+    pub C_CloseSession: Option<unsafe extern "C" fn(hSession: CK_SESSION_HANDLE) -> CK_RV>,
+    pub C_CloseAllSessions: Unused,
+    pub C_GetSessionInfo: Unused,
+    pub C_GetOperationState: Unused,
+    pub C_SetOperationState: Unused,
+    pub C_Login: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            userType: CK_ULONG,
+            pPin: *const u8,
+            ulPinLen: CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_Logout: Unused,
+    pub C_CreateObject: Unused,
+    pub C_CopyObject: Unused,
+    pub C_DestroyObject: Unused,
+    pub C_GetObjectSize: Unused,
+    pub C_GetAttributeValue: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            hObject: CK_OBJECT_HANDLE,
+            pTemplate: *mut CK_ATTRIBUTE,
+            ulCount: CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_SetAttributeValue: Unused,
+    pub C_FindObjectsInit: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            pTemplate: *mut CK_ATTRIBUTE,
+            ulCount: CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_FindObjects: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            phObject: *mut CK_OBJECT_HANDLE,
+            ulMaxObjectCount: CK_ULONG,
+            pulObjectCount: *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_FindObjectsFinal: Option<unsafe extern "C" fn(hSession: CK_SESSION_HANDLE) -> CK_RV>,
+    pub C_EncryptInit: Unused,
+    pub C_Encrypt: Unused,
+    pub C_EncryptUpdate: Unused,
+    pub C_EncryptFinal: Unused,
+    pub C_DecryptInit: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            pMechanism: *mut CK_MECHANISM,
+            hKey: CK_OBJECT_HANDLE,
+        ) -> CK_RV,
+    >,
+    pub C_Decrypt: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            pEncryptedData: *const u8,
+            ulEncryptedDataLen: CK_ULONG,
+            pData: *mut u8,
+            pulDataLen: *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_DecryptUpdate: Unused,
+    pub C_DecryptFinal: Unused,
+    pub C_DigestInit: Unused,
+    pub C_Digest: Unused,
+    pub C_DigestUpdate: Unused,
+    pub C_DigestKey: Unused,
+    pub C_DigestFinal: Unused,
+    pub C_SignInit: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            pMechanism: *mut CK_MECHANISM,
+            hKey: CK_OBJECT_HANDLE,
+        ) -> CK_RV,
+    >,
+    pub C_Sign: Option<
+        unsafe extern "C" fn(
+            hSession: CK_SESSION_HANDLE,
+            pData: *const u8,
+            ulDataLen: CK_ULONG,
+            pSignature: *mut u8,
+            pulSignatureLen: *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+}
+
+pub type CK_C_GetFunctionList =
+    unsafe extern "C" fn(ppFunctionList: *mut *const CK_FUNCTION_LIST) -> CK_RV;
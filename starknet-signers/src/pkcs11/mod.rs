@@ -0,0 +1,620 @@
+use crate::{envelope::signing_key_from_plaintext, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::{
+        raw::{c_ulong, c_void},
+        unix::ffi::OsStrExt,
+    },
+    path::Path,
+    ptr,
+    sync::Mutex,
+};
+
+mod ffi;
+use ffi::*;
+
+/// Byte length of Stark curve scalars and coordinates.
+const SCALAR_LEN: usize = 32;
+
+/// A signer backed by a hardware security module accessed through a PKCS#11 module.
+///
+/// Few modules support the Stark curve, so two modes are available. See [Pkcs11KeyMode].
+pub struct Pkcs11Signer {
+    module: Pkcs11Module,
+    slot: CK_SLOT_ID,
+    pin: String,
+    key_label: String,
+    mode: Pkcs11KeyMode,
+    // Serializes access to the module, as sessions must not be used concurrently
+    lock: Mutex<()>,
+}
+
+/// Where the Stark private key lives.
+#[derive(Debug, Clone)]
+pub enum Pkcs11KeyMode {
+    /// The token holds an EC key pair on the Stark curve, with both objects labelled with the
+    /// key label, and signs with `CKM_ECDSA`.
+    Native,
+    /// Software fallback for modules lacking Stark curve support: the private key is encrypted
+    /// with the AES key labelled with the key label, using `CKM_AES_CBC_PAD`. It's decrypted
+    /// inside the HSM every time it's needed and used for signing in software.
+    Wrapped {
+        encrypted_key: Vec<u8>,
+        iv: [u8; 16],
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Pkcs11Error {
+    #[error("failed to load PKCS#11 module: {0}")]
+    Load(String),
+    #[error("PKCS#11 module does not provide {0}")]
+    MissingFunction(&'static str),
+    #[error("{function} failed with CK_RV {rv:#x}")]
+    Call { function: &'static str, rv: c_ulong },
+    #[error("no token labelled \"{0}\"")]
+    TokenNotFound(String),
+    #[error("no key labelled \"{0}\"")]
+    KeyNotFound(String),
+    #[error("invalid EC point")]
+    InvalidPublicKey,
+    #[error("invalid signature returned by module")]
+    InvalidSignature,
+    #[error("decrypted plaintext is not a valid private key")]
+    InvalidPlaintext,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+struct Pkcs11Module {
+    library: *mut c_void,
+    functions: *const CK_FUNCTION_LIST,
+    initialized: bool,
+}
+
+struct Session<'a> {
+    functions: &'a CK_FUNCTION_LIST,
+    handle: CK_SESSION_HANDLE,
+}
+
+// The module is initialized with `CKF_OS_LOCKING_OK`, and [Pkcs11Signer] serializes all calls.
+unsafe impl Send for Pkcs11Module {}
+unsafe impl Sync for Pkcs11Module {}
+
+macro_rules! ck_call {
+    ($functions:expr, $name:ident($($arg:expr),* $(,)?)) => {{
+        let function = $functions
+            .$name
+            .ok_or(Pkcs11Error::MissingFunction(stringify!($name)))?;
+        check(stringify!($name), function($($arg),*))
+    }};
+}
+
+impl Pkcs11Signer {
+    /// Loads the PKCS#11 module at `module_path` and selects the token labelled `token_label`.
+    pub fn new<P>(
+        module_path: P,
+        token_label: &str,
+        pin: String,
+        key_label: String,
+        mode: Pkcs11KeyMode,
+    ) -> Result<Self, Pkcs11Error>
+    where
+        P: AsRef<Path>,
+    {
+        let module = Pkcs11Module::load(module_path.as_ref())?;
+        let slot = module.find_slot(token_label)?;
+
+        Ok(Self {
+            module,
+            slot,
+            pin,
+            key_label,
+            mode,
+            lock: Mutex::new(()),
+        })
+    }
+
+    fn public_key(&self) -> Result<FieldElement, Pkcs11Error> {
+        let _guard = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+        let session = self.module.open_session(self.slot, &self.pin)?;
+
+        match &self.mode {
+            Pkcs11KeyMode::Native => {
+                let key = session.find_object(CKO_PUBLIC_KEY, &self.key_label)?;
+                parse_ec_point(&session.get_attribute(key, CKA_EC_POINT)?)
+                    .ok_or(Pkcs11Error::InvalidPublicKey)
+            }
+            Pkcs11KeyMode::Wrapped { encrypted_key, iv } => {
+                let signing_key = self.unwrap_key(&session, encrypted_key, iv)?;
+                Ok(signing_key.verifying_key().scalar())
+            }
+        }
+    }
+
+    fn sign(&self, hash: &FieldElement) -> Result<Signature, Pkcs11Error> {
+        let _guard = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+        let session = self.module.open_session(self.slot, &self.pin)?;
+
+        match &self.mode {
+            Pkcs11KeyMode::Native => {
+                let key = session.find_object(CKO_PRIVATE_KEY, &self.key_label)?;
+                let message = encode_message_hash(hash).map_err(Pkcs11Error::EcdsaSignError)?;
+                parse_signature(&session.sign(key, &message)?)
+            }
+            Pkcs11KeyMode::Wrapped { encrypted_key, iv } => self
+                .unwrap_key(&session, encrypted_key, iv)?
+                .sign(hash)
+                .map_err(Pkcs11Error::EcdsaSignError),
+        }
+    }
+
+    fn unwrap_key(
+        &self,
+        session: &Session<'_>,
+        encrypted_key: &[u8],
+        iv: &[u8; 16],
+    ) -> Result<crate::SigningKey, Pkcs11Error> {
+        let key = session.find_object(CKO_SECRET_KEY, &self.key_label)?;
+
+        let mut plaintext = session.decrypt_aes_cbc_pad(key, iv, encrypted_key)?;
+        let signing_key = signing_key_from_plaintext(&plaintext);
+        plaintext.iter_mut().for_each(|byte| *byte = 0);
+
+        signing_key.ok_or(Pkcs11Error::InvalidPlaintext)
+    }
+}
+
+impl Pkcs11Module {
+    fn load(path: &Path) -> Result<Self, Pkcs11Error> {
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| Pkcs11Error::Load("path contains a null byte".into()))?;
+
+        unsafe {
+            let library = libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+            if library.is_null() {
+                return Err(Pkcs11Error::Load(last_dl_error()));
+            }
+
+            let symbol = libc::dlsym(library, c"C_GetFunctionList".as_ptr());
+            if symbol.is_null() {
+                let error = last_dl_error();
+                libc::dlclose(library);
+                return Err(Pkcs11Error::Load(error));
+            }
+
+            let mut module = Self {
+                library,
+                functions: ptr::null(),
+                initialized: false,
+            };
+
+            let get_function_list: CK_C_GetFunctionList = std::mem::transmute(symbol);
+            check(
+                "C_GetFunctionList",
+                get_function_list(&mut module.functions),
+            )?;
+            if module.functions.is_null() {
+                return Err(Pkcs11Error::MissingFunction("C_GetFunctionList"));
+            }
+
+            let mut init_args = CK_C_INITIALIZE_ARGS {
+                CreateMutex: ptr::null_mut(),
+                DestroyMutex: ptr::null_mut(),
+                LockMutex: ptr::null_mut(),
+                UnlockMutex: ptr::null_mut(),
+                flags: CKF_OS_LOCKING_OK,
+                pReserved: ptr::null_mut(),
+            };
+            match ck_call!(
+                module.functions(),
+                C_Initialize(&mut init_args as *mut _ as *mut c_void)
+            ) {
+                Ok(()) => module.initialized = true,
+                // Another component of the process already initialized the module
+                Err(Pkcs11Error::Call {
+                    rv: CKR_CRYPTOKI_ALREADY_INITIALIZED,
+                    ..
+                }) => {}
+                Err(err) => return Err(err),
+            }
+
+            Ok(module)
+        }
+    }
+
+    fn functions(&self) -> &CK_FUNCTION_LIST {
+        unsafe { &*self.functions }
+    }
+
+    fn find_slot(&self, token_label: &str) -> Result<CK_SLOT_ID, Pkcs11Error> {
+        let functions = self.functions();
+
+        unsafe {
+            let mut count: CK_ULONG = 0;
+            ck_call!(functions, C_GetSlotList(1, ptr::null_mut(), &mut count))?;
+
+            let mut slots = vec![0; count as usize];
+            ck_call!(functions, C_GetSlotList(1, slots.as_mut_ptr(), &mut count))?;
+            slots.truncate(count as usize);
+
+            for slot in slots {
+                let mut info: CK_TOKEN_INFO = std::mem::zeroed();
+                ck_call!(functions, C_GetTokenInfo(slot, &mut info))?;
+
+                if padded_label_matches(&info.label, token_label) {
+                    return Ok(slot);
+                }
+            }
+        }
+
+        Err(Pkcs11Error::TokenNotFound(token_label.to_owned()))
+    }
+
+    fn open_session(&self, slot: CK_SLOT_ID, pin: &str) -> Result<Session<'_>, Pkcs11Error> {
+        let functions = self.functions();
+
+        let mut handle: CK_SESSION_HANDLE = 0;
+        unsafe {
+            ck_call!(
+                functions,
+                C_OpenSession(
+                    slot,
+                    CKF_SERIAL_SESSION,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    &mut handle
+                )
+            )?;
+        }
+        let session = Session { functions, handle };
+
+        unsafe {
+            match ck_call!(
+                functions,
+                C_Login(handle, CKU_USER, pin.as_ptr(), pin.len() as CK_ULONG)
+            ) {
+                // Login state is shared by all sessions of the application
+                Ok(())
+                | Err(Pkcs11Error::Call {
+                    rv: CKR_USER_ALREADY_LOGGED_IN,
+                    ..
+                }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(session)
+    }
+}
+
+impl Drop for Pkcs11Module {
+    fn drop(&mut self) {
+        unsafe {
+            if self.initialized {
+                if let Some(finalize) = self.functions().C_Finalize {
+                    finalize(ptr::null_mut());
+                }
+            }
+            libc::dlclose(self.library);
+        }
+    }
+}
+
+impl<'a> Session<'a> {
+    fn find_object(&self, class: CK_ULONG, label: &str) -> Result<CK_OBJECT_HANDLE, Pkcs11Error> {
+        let mut class = class;
+        let mut template = [
+            CK_ATTRIBUTE {
+                type_: CKA_CLASS,
+                pValue: &mut class as *mut _ as *mut c_void,
+                ulValueLen: std::mem::size_of::<CK_ULONG>() as CK_ULONG,
+            },
+            CK_ATTRIBUTE {
+                type_: CKA_LABEL,
+                pValue: label.as_ptr() as *mut c_void,
+                ulValueLen: label.len() as CK_ULONG,
+            },
+        ];
+
+        let mut object: CK_OBJECT_HANDLE = 0;
+        let mut count: CK_ULONG = 0;
+        unsafe {
+            ck_call!(
+                self.functions,
+                C_FindObjectsInit(
+                    self.handle,
+                    template.as_mut_ptr(),
+                    template.len() as CK_ULONG
+                )
+            )?;
+            let result = ck_call!(
+                self.functions,
+                C_FindObjects(self.handle, &mut object, 1, &mut count)
+            );
+            ck_call!(self.functions, C_FindObjectsFinal(self.handle))?;
+            result?;
+        }
+
+        if count == 0 {
+            Err(Pkcs11Error::KeyNotFound(label.to_owned()))
+        } else {
+            Ok(object)
+        }
+    }
+
+    fn get_attribute(
+        &self,
+        object: CK_OBJECT_HANDLE,
+        attribute: CK_ULONG,
+    ) -> Result<Vec<u8>, Pkcs11Error> {
+        let mut template = CK_ATTRIBUTE {
+            type_: attribute,
+            pValue: ptr::null_mut(),
+            ulValueLen: 0,
+        };
+
+        unsafe {
+            // The first call only queries the length of the value
+            ck_call!(
+                self.functions,
+                C_GetAttributeValue(self.handle, object, &mut template, 1)
+            )?;
+
+            let mut value = vec![0u8; template.ulValueLen as usize];
+            template.pValue = value.as_mut_ptr() as *mut c_void;
+            ck_call!(
+                self.functions,
+                C_GetAttributeValue(self.handle, object, &mut template, 1)
+            )?;
+            value.truncate(template.ulValueLen as usize);
+
+            Ok(value)
+        }
+    }
+
+    fn sign(&self, key: CK_OBJECT_HANDLE, message: &[u8]) -> Result<Vec<u8>, Pkcs11Error> {
+        let mut mechanism = CK_MECHANISM {
+            mechanism: CKM_ECDSA,
+            pParameter: ptr::null_mut(),
+            ulParameterLen: 0,
+        };
+
+        let mut signature = vec![0u8; 2 * SCALAR_LEN];
+        let mut signature_len = signature.len() as CK_ULONG;
+        unsafe {
+            ck_call!(self.functions, C_SignInit(self.handle, &mut mechanism, key))?;
+            ck_call!(
+                self.functions,
+                C_Sign(
+                    self.handle,
+                    message.as_ptr(),
+                    message.len() as CK_ULONG,
+                    signature.as_mut_ptr(),
+                    &mut signature_len
+                )
+            )?;
+        }
+        signature.truncate(signature_len as usize);
+
+        Ok(signature)
+    }
+
+    fn decrypt_aes_cbc_pad(
+        &self,
+        key: CK_OBJECT_HANDLE,
+        iv: &[u8; 16],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Pkcs11Error> {
+        let mut iv = *iv;
+        let mut mechanism = CK_MECHANISM {
+            mechanism: CKM_AES_CBC_PAD,
+            pParameter: iv.as_mut_ptr() as *mut c_void,
+            ulParameterLen: iv.len() as CK_ULONG,
+        };
+
+        // Padding is removed, so the plaintext is never longer than the ciphertext
+        let mut plaintext = vec![0u8; ciphertext.len()];
+        let mut plaintext_len = plaintext.len() as CK_ULONG;
+        unsafe {
+            ck_call!(
+                self.functions,
+                C_DecryptInit(self.handle, &mut mechanism, key)
+            )?;
+            ck_call!(
+                self.functions,
+                C_Decrypt(
+                    self.handle,
+                    ciphertext.as_ptr(),
+                    ciphertext.len() as CK_ULONG,
+                    plaintext.as_mut_ptr(),
+                    &mut plaintext_len
+                )
+            )?;
+        }
+        plaintext.truncate(plaintext_len as usize);
+
+        Ok(plaintext)
+    }
+}
+
+impl<'a> Drop for Session<'a> {
+    fn drop(&mut self) {
+        if let Some(close_session) = self.functions.C_CloseSession {
+            unsafe {
+                close_session(self.handle);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Pkcs11Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pkcs11Signer")
+            .field("slot", &self.slot)
+            .field("key_label", &self.key_label)
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for Pkcs11Signer {
+    type GetPublicKeyError = Pkcs11Error;
+    type SignError = Pkcs11Error;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(VerifyingKey::from_scalar(self.public_key()?))
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.sign(hash)
+    }
+}
+
+fn check(function: &'static str, rv: CK_RV) -> Result<(), Pkcs11Error> {
+    if rv == CKR_OK {
+        Ok(())
+    } else {
+        Err(Pkcs11Error::Call { function, rv })
+    }
+}
+
+fn last_dl_error() -> String {
+    unsafe {
+        let error = libc::dlerror();
+        if error.is_null() {
+            "unknown error".into()
+        } else {
+            CStr::from_ptr(error).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Token labels are padded with spaces to 32 bytes.
+fn padded_label_matches(padded: &[u8; 32], label: &str) -> bool {
+    let end = padded
+        .iter()
+        .rposition(|byte| *byte != b' ' && *byte != 0)
+        .map_or(0, |index| index + 1);
+
+    &padded[..end] == label.as_bytes()
+}
+
+/// `CKM_ECDSA` truncates the message to the bit length of the curve order, which is 252 bits
+/// for the Stark curve. The hash is therefore shifted left by 4 bits so that the truncated
+/// value is the hash itself.
+fn encode_message_hash(hash: &FieldElement) -> Result<[u8; SCALAR_LEN], EcdsaSignError> {
+    let bytes = hash.to_bytes_be();
+    // Same range as enforced by `ecdsa_sign`
+    if bytes[0] & 0xf8 != 0 {
+        return Err(EcdsaSignError::MessageHashOutOfRange);
+    }
+
+    let mut shifted = [0u8; SCALAR_LEN];
+    for index in 0..SCALAR_LEN {
+        let next = bytes.get(index + 1).copied().unwrap_or(0);
+        shifted[index] = (bytes[index] << 4) | (next >> 4);
+    }
+
+    Ok(shifted)
+}
+
+/// Extracts the x coordinate from an uncompressed EC point, either raw or wrapped in a DER
+/// `OCTET STRING` as mandated by the specification.
+fn parse_ec_point(value: &[u8]) -> Option<FieldElement> {
+    let point = if value.len() == 1 + 2 * SCALAR_LEN {
+        value
+    } else {
+        match value {
+            [0x04, len, rest @ ..] if *len as usize == rest.len() => rest,
+            [0x04, 0x81, len, rest @ ..] if *len as usize == rest.len() => rest,
+            _ => return None,
+        }
+    };
+
+    match point {
+        [0x04, coordinates @ ..] if coordinates.len() == 2 * SCALAR_LEN => {
+            FieldElement::from_byte_slice_be(&coordinates[..SCALAR_LEN]).ok()
+        }
+        _ => None,
+    }
+}
+
+/// `CKM_ECDSA` signatures are the concatenation of `r` and `s`, each as long as the curve order.
+fn parse_signature(signature: &[u8]) -> Result<Signature, Pkcs11Error> {
+    if signature.len() != 2 * SCALAR_LEN {
+        return Err(Pkcs11Error::InvalidSignature);
+    }
+
+    let (r, s) = signature.split_at(signature.len() / 2);
+    Ok(Signature {
+        r: FieldElement::from_byte_slice_be(r).map_err(|_| Pkcs11Error::InvalidSignature)?,
+        s: FieldElement::from_byte_slice_be(s).map_err(|_| Pkcs11Error::InvalidSignature)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_message_hash() {
+        let hash = FieldElement::from_hex_be(
+            "06fea80189363a786037ed3e7ba546dad0ef7de49fccae0e31eb658b7dd4ea76",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(encode_message_hash(&hash).unwrap()),
+            "6fea80189363a786037ed3e7ba546dad0ef7de49fccae0e31eb658b7dd4ea760"
+        );
+
+        let out_of_range = FieldElement::from_hex_be(
+            "0800000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(encode_message_hash(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_parse_ec_point() {
+        let x = "02c5dbad71c92a45cc4b40573ae661f8147869a91d57b8d9b8f48c8af7f83159";
+        let y = "00000000000000000000000000000000000000000000000000000000000000aa";
+        let raw = hex::decode(format!("04{x}{y}")).unwrap();
+        let der = hex::decode(format!("0441{}", hex::encode(&raw))).unwrap();
+
+        let expected = FieldElement::from_hex_be(x).unwrap();
+        assert_eq!(parse_ec_point(&raw), Some(expected));
+        assert_eq!(parse_ec_point(&der), Some(expected));
+        assert_eq!(parse_ec_point(&raw[1..]), None);
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        let mut raw = vec![0u8; 64];
+        raw[31] = 1;
+        raw[63] = 2;
+
+        let signature = parse_signature(&raw).unwrap();
+        assert_eq!(signature.r, FieldElement::ONE);
+        assert_eq!(signature.s, FieldElement::TWO);
+        assert!(parse_signature(&raw[1..]).is_err());
+    }
+
+    #[test]
+    fn test_padded_label_matches() {
+        let mut padded = [b' '; 32];
+        padded[..8].copy_from_slice(b"starknet");
+
+        assert!(padded_label_matches(&padded, "starknet"));
+        assert!(!padded_label_matches(&padded, "stark"));
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{clock::system_time, CallContext, SignContext, SignRequest, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{crypto::Signature, types::FieldElement, utils::get_selector_from_name};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// Token functions whose `Uint256` amount counts toward daily value limits, along with the
+/// offset of the amount in their calldata.
+const VALUE_FUNCTIONS: [(&str, usize); 4] = [
+    ("transfer", 1),
+    ("transferFrom", 2),
+    ("approve", 1),
+    ("increaseAllowance", 1),
+];
+
+/// Wraps a signer so that only transactions allowed by a [SigningPolicy] are signed. This is
+/// meant as a last line of defense for hot keys in automated services, where a compromised or
+/// buggy caller should not be able to get arbitrary transactions signed.
+///
+/// Hashes without a [SignContext] are never signed, as there's nothing to check them against.
+/// Neither are hashes that don't match the transaction hash computed from their context.
+#[derive(Debug)]
+pub struct PolicySigner<S> {
+    signer: S,
+    policy: SigningPolicy,
+    clock: fn() -> u64,
+    spending: Mutex<Spending>,
+}
+
+/// What a [PolicySigner] is allowed to sign. Everything not explicitly allowed is denied.
+#[derive(Debug, Clone, Default)]
+pub struct SigningPolicy {
+    contracts: HashMap<FieldElement, Option<HashSet<FieldElement>>>,
+    max_fee: Option<FieldElement>,
+    daily_value_limits: HashMap<FieldElement, u128>,
+    allow_declarations: bool,
+    allow_account_deployments: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PolicySignerError<S> {
+    #[error(transparent)]
+    Policy(PolicyViolation),
+    #[error(transparent)]
+    Signer(S),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyViolation {
+    #[error("signing hashes without context is not allowed")]
+    BlindSigning,
+    #[error("hash {0:#064x} doesn't match the hash computed from its context")]
+    HashMismatch(FieldElement),
+    #[error("declarations are not allowed")]
+    Declaration,
+    #[error("account deployments are not allowed")]
+    AccountDeployment,
+    #[error("calling contract {0:#064x} is not allowed")]
+    ContractNotAllowed(FieldElement),
+    #[error("calling selector {selector:#064x} on contract {contract:#064x} is not allowed")]
+    SelectorNotAllowed {
+        contract: FieldElement,
+        selector: FieldElement,
+    },
+    #[error("max fee {max_fee:#x} exceeds limit of {limit:#x}")]
+    MaxFeeExceeded {
+        max_fee: FieldElement,
+        limit: FieldElement,
+    },
+    #[error("value sent from token {token:#064x} exceeds daily limit of {limit}")]
+    DailyValueExceeded { token: FieldElement, limit: u128 },
+    #[error("invalid calldata for value transfer on token {0:#064x}")]
+    InvalidValueCalldata(FieldElement),
+}
+
+#[derive(Debug, Default)]
+struct Spending {
+    next_id: u64,
+    entries: Vec<SpendingEntry>,
+}
+
+#[derive(Debug)]
+struct SpendingEntry {
+    id: u64,
+    timestamp: u64,
+    token: FieldElement,
+    amount: u128,
+}
+
+impl SigningPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows calling any function on `contract`.
+    pub fn allow_contract(mut self, contract: FieldElement) -> Self {
+        self.contracts.insert(contract, None);
+        self
+    }
+
+    /// Allows calling the function with `selector` on `contract`. Has no effect if all functions
+    /// are already allowed on `contract`.
+    pub fn allow_call(mut self, contract: FieldElement, selector: FieldElement) -> Self {
+        if let Some(selectors) = self
+            .contracts
+            .entry(contract)
+            .or_insert_with(|| Some(HashSet::new()))
+        {
+            selectors.insert(selector);
+        }
+        self
+    }
+
+    /// Rejects transactions with a max fee higher than `max_fee`.
+    pub fn max_fee(mut self, max_fee: FieldElement) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    /// Limits the amount of `token` transferred or approved through `transfer`, `transferFrom`,
+    /// `approve` and `increaseAllowance` over any 24-hour period. Calls to `token` still need to
+    /// be allowed separately.
+    pub fn max_value_per_day(mut self, token: FieldElement, limit: u128) -> Self {
+        self.daily_value_limits.insert(token, limit);
+        self
+    }
+
+    pub fn allow_declarations(mut self) -> Self {
+        self.allow_declarations = true;
+        self
+    }
+
+    pub fn allow_account_deployments(mut self) -> Self {
+        self.allow_account_deployments = true;
+        self
+    }
+
+    fn check_call(&self, call: &CallContext) -> Result<(), PolicyViolation> {
+        match self.contracts.get(&call.to) {
+            None => Err(PolicyViolation::ContractNotAllowed(call.to)),
+            Some(Some(selectors)) if !selectors.contains(&call.selector) => {
+                Err(PolicyViolation::SelectorNotAllowed {
+                    contract: call.to,
+                    selector: call.selector,
+                })
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn check_max_fee(&self, max_fee: FieldElement) -> Result<(), PolicyViolation> {
+        match self.max_fee {
+            Some(limit) if max_fee > limit => {
+                Err(PolicyViolation::MaxFeeExceeded { max_fee, limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The value sent by `call` if it's subject to a daily limit.
+    fn call_value(&self, call: &CallContext) -> Result<Option<u128>, PolicyViolation> {
+        if !self.daily_value_limits.contains_key(&call.to) {
+            return Ok(None);
+        }
+
+        let offset = match VALUE_FUNCTIONS
+            .iter()
+            .find(|(name, _)| get_selector_from_name(name).unwrap() == call.selector)
+        {
+            Some((_, offset)) => *offset,
+            None => return Ok(None),
+        };
+
+        match &call.calldata[..] {
+            calldata if calldata.len() >= offset + 2 => {
+                let low = calldata[offset].to_bytes_be();
+                let high = calldata[offset + 1];
+                if high != FieldElement::ZERO || low[..16].iter().any(|byte| *byte != 0) {
+                    // Doesn't fit in `u128` so it's over any limit
+                    Ok(Some(u128::MAX))
+                } else {
+                    Ok(Some(u128::from_be_bytes(low[16..].try_into().unwrap())))
+                }
+            }
+            _ => Err(PolicyViolation::InvalidValueCalldata(call.to)),
+        }
+    }
+}
+
+impl<S> PolicySigner<S> {
+    pub fn new(signer: S, policy: SigningPolicy) -> Self {
+        Self {
+            signer,
+            policy,
+            clock: system_time,
+            spending: Mutex::new(Spending::default()),
+        }
+    }
+
+    /// Uses `clock` to get the current Unix timestamp in seconds for daily value limits. Defaults
+    /// to the system clock.
+    pub fn with_clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn policy(&self) -> &SigningPolicy {
+        &self.policy
+    }
+
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+
+    /// Checks `request` against the policy, recording the value it sends. Returns the ID of the
+    /// spending entries to remove if signing fails.
+    fn check(&self, request: &SignRequest) -> Result<Option<u64>, PolicyViolation> {
+        if let SignContext::Blind = request.context {
+            return Err(PolicyViolation::BlindSigning);
+        }
+        if !request.matches_context() {
+            return Err(PolicyViolation::HashMismatch(request.hash));
+        }
+
+        let context = match &request.context {
+            SignContext::Blind => unreachable!(),
+            SignContext::Declare(context) => {
+                if !self.policy.allow_declarations {
+                    return Err(PolicyViolation::Declaration);
+                }
+                self.policy.check_max_fee(context.max_fee)?;
+                return Ok(None);
+            }
+            SignContext::DeployAccount(context) => {
+                if !self.policy.allow_account_deployments {
+                    return Err(PolicyViolation::AccountDeployment);
+                }
+                self.policy.check_max_fee(context.max_fee)?;
+                return Ok(None);
+            }
+            SignContext::Invoke(context) => context,
+        };
+
+        self.policy.check_max_fee(context.max_fee)?;
+
+        let mut values: HashMap<FieldElement, u128> = HashMap::new();
+        for call in context.calls.iter() {
+            self.policy.check_call(call)?;
+            if let Some(value) = self.policy.call_value(call)? {
+                let total = values.entry(call.to).or_default();
+                *total = total.saturating_add(value);
+            }
+        }
+
+        // Signatures over query-version hashes can't be used in transactions that get accepted
+        if values.is_empty() || request.is_fee_estimation() {
+            return Ok(None);
+        }
+
+        let now = (self.clock)();
+        let mut spending = self.spending.lock().unwrap();
+        spending
+            .entries
+            .retain(|entry| entry.timestamp + SECONDS_PER_DAY > now);
+
+        for (token, value) in values.iter() {
+            let limit = self.policy.daily_value_limits[token];
+            let spent = spending
+                .entries
+                .iter()
+                .filter(|entry| entry.token == *token)
+                .fold(*value, |acc, entry| acc.saturating_add(entry.amount));
+            if spent > limit {
+                return Err(PolicyViolation::DailyValueExceeded {
+                    token: *token,
+                    limit,
+                });
+            }
+        }
+
+        let id = spending.next_id;
+        spending.next_id += 1;
+        spending
+            .entries
+            .extend(values.into_iter().map(|(token, amount)| SpendingEntry {
+                id,
+                timestamp: now,
+                token,
+                amount,
+            }));
+
+        Ok(Some(id))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<S> Signer for PolicySigner<S>
+where
+    S: Signer + Sync + Send,
+{
+    type GetPublicKeyError = S::GetPublicKeyError;
+    type SignError = PolicySignerError<S::SignError>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        self.signer.get_public_key().await
+    }
+
+    async fn sign_hash(&self, _hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        Err(PolicySignerError::Policy(PolicyViolation::BlindSigning))
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError> {
+        let spending_id = self.check(request).map_err(PolicySignerError::Policy)?;
+
+        match self.signer.sign_request(request).await {
+            Ok(signature) => Ok(signature),
+            Err(err) => {
+                if let Some(id) = spending_id {
+                    self.spending
+                        .lock()
+                        .unwrap()
+                        .entries
+                        .retain(|entry| entry.id != id);
+                }
+                Err(PolicySignerError::Signer(err))
+            }
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CalldataEncoding, InvokeContext, LocalWallet, SigningKey};
+
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static NOW: AtomicU64 = AtomicU64::new(1_000_000);
+
+    fn token() -> FieldElement {
+        FieldElement::from_hex_be("0x1234").unwrap()
+    }
+
+    fn transfer(amount: u64) -> CallContext {
+        CallContext {
+            to: token(),
+            selector: get_selector_from_name("transfer").unwrap(),
+            calldata: vec![FieldElement::ONE, amount.into(), FieldElement::ZERO],
+        }
+    }
+
+    fn invoke_context(calls: Vec<CallContext>, max_fee: u64) -> InvokeContext {
+        InvokeContext {
+            sender_address: FieldElement::TWO,
+            chain_id: FieldElement::ONE,
+            encoding: CalldataEncoding::New,
+            calls,
+            max_fee: max_fee.into(),
+            nonce: FieldElement::ZERO,
+            is_query: false,
+        }
+    }
+
+    fn request(context: InvokeContext) -> SignRequest {
+        SignRequest {
+            hash: context.transaction_hash(),
+            context: SignContext::Invoke(context),
+        }
+    }
+
+    fn invoke(calls: Vec<CallContext>, max_fee: u64) -> SignRequest {
+        request(invoke_context(calls, max_fee))
+    }
+
+    fn query(calls: Vec<CallContext>) -> SignRequest {
+        request(InvokeContext {
+            is_query: true,
+            ..invoke_context(calls, 0)
+        })
+    }
+
+    fn signer(policy: SigningPolicy) -> PolicySigner<LocalWallet> {
+        PolicySigner::new(
+            LocalWallet::from_signing_key(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            policy,
+        )
+        .with_clock(|| NOW.load(Ordering::SeqCst))
+    }
+
+    fn violation(
+        result: Result<Signature, PolicySignerError<crate::local_wallet::SignError>>,
+    ) -> PolicyViolation {
+        match result {
+            Err(PolicySignerError::Policy(violation)) => violation,
+            _ => panic!("expected policy violation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allowlist() {
+        let selector = get_selector_from_name("transfer").unwrap();
+        let signer = signer(
+            SigningPolicy::new()
+                .allow_call(token(), selector)
+                .max_fee(1000u64.into()),
+        );
+
+        assert!(signer
+            .sign_request(&invoke(vec![transfer(1)], 1000))
+            .await
+            .is_ok());
+        assert_eq!(
+            violation(signer.sign_request(&invoke(vec![transfer(1)], 1001)).await),
+            PolicyViolation::MaxFeeExceeded {
+                max_fee: 1001u64.into(),
+                limit: 1000u64.into(),
+            }
+        );
+
+        let approve = CallContext {
+            selector: get_selector_from_name("approve").unwrap(),
+            ..transfer(1)
+        };
+        assert_eq!(
+            violation(signer.sign_request(&invoke(vec![approve.clone()], 0)).await),
+            PolicyViolation::SelectorNotAllowed {
+                contract: token(),
+                selector: approve.selector,
+            }
+        );
+
+        let other = CallContext {
+            to: FieldElement::TWO,
+            ..transfer(1)
+        };
+        assert_eq!(
+            violation(
+                signer
+                    .sign_request(&invoke(vec![transfer(1), other], 0))
+                    .await
+            ),
+            PolicyViolation::ContractNotAllowed(FieldElement::TWO)
+        );
+
+        assert_eq!(
+            violation(signer.sign_hash(&FieldElement::ONE).await),
+            PolicyViolation::BlindSigning
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_mismatch() {
+        let signer = signer(SigningPolicy::new().allow_contract(token()));
+
+        // A hash for a different transaction can't be signed with an allowed context
+        let other = FieldElement::from_hex_be("0x5678").unwrap();
+        let mut request = invoke(vec![transfer(1)], 0);
+        request.hash = invoke_context(
+            vec![CallContext {
+                to: other,
+                ..transfer(1)
+            }],
+            0,
+        )
+        .transaction_hash();
+        assert_eq!(
+            violation(signer.sign_request(&request).await),
+            PolicyViolation::HashMismatch(request.hash)
+        );
+
+        // Neither can a hash using a different calldata encoding
+        let mut request = invoke(vec![transfer(1)], 0);
+        request.hash = InvokeContext {
+            encoding: CalldataEncoding::Legacy,
+            ..invoke_context(vec![transfer(1)], 0)
+        }
+        .transaction_hash();
+        assert_eq!(
+            violation(signer.sign_request(&request).await),
+            PolicyViolation::HashMismatch(request.hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daily_value_limit() {
+        let signer = signer(
+            SigningPolicy::new()
+                .allow_contract(token())
+                .max_value_per_day(token(), 100),
+        );
+
+        assert!(signer
+            .sign_request(&invoke(vec![transfer(60)], 1))
+            .await
+            .is_ok());
+        // Query-version signatures don't count toward the limit
+        assert!(signer
+            .sign_request(&query(vec![transfer(60)]))
+            .await
+            .is_ok());
+        assert_eq!(
+            violation(
+                signer
+                    .sign_request(&invoke(vec![transfer(20), transfer(21)], 1))
+                    .await
+            ),
+            PolicyViolation::DailyValueExceeded {
+                token: token(),
+                limit: 100,
+            }
+        );
+        // Transactions with a zero max fee do
+        assert!(signer
+            .sign_request(&invoke(vec![transfer(40)], 0))
+            .await
+            .is_ok());
+        assert_eq!(
+            violation(signer.sign_request(&invoke(vec![transfer(1)], 1)).await),
+            PolicyViolation::DailyValueExceeded {
+                token: token(),
+                limit: 100,
+            }
+        );
+
+        NOW.fetch_add(SECONDS_PER_DAY, Ordering::SeqCst);
+        assert!(signer
+            .sign_request(&invoke(vec![transfer(100)], 1))
+            .await
+            .is_ok());
+    }
+}
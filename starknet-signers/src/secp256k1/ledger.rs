@@ -0,0 +1,257 @@
+//! Signing with keys held by the Ledger Ethereum app.
+//!
+//! Communication with the device is left to a [LedgerTransport] implementation, e.g. over USB HID
+//! or WebHID, which exchanges raw APDUs. No transport is bundled, so this module has no
+//! dependencies of its own and isn't behind a feature: wrap the HID library your application
+//! already uses, such as `ledger-transport-hid`.
+
+use super::{Secp256k1Signature, Secp256k1Signer};
+use crate::DerivationPath;
+
+use async_trait::async_trait;
+use k256::ecdsa::VerifyingKey;
+use starknet_core::types::FieldElement;
+use std::error::Error;
+
+const CLA: u8 = 0xe0;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+const STATUS_OK: u16 = 0x9000;
+const STATUS_USER_REJECTED: u16 = 0x6985;
+const MAX_PATH_DEPTH: usize = 10;
+
+/// Exchanges APDUs with a Ledger device.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait LedgerTransport {
+    type Error: Error + Send;
+
+    /// Sends `command` to the device and returns the response, including the trailing 2-byte
+    /// status word.
+    async fn exchange(&self, command: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// A [Secp256k1Signer] using the key at a derivation path in the Ledger Ethereum app. Users
+/// confirm each signature on the device.
+#[derive(Debug)]
+pub struct LedgerEthSigner<T> {
+    transport: T,
+    path: DerivationPath,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LedgerError<T> {
+    #[error(transparent)]
+    Transport(T),
+    #[error("request rejected on device")]
+    UserRejected,
+    #[error("device returned status {0:#06x}")]
+    Status(u16),
+    #[error("invalid response from device")]
+    InvalidResponse,
+    #[error("derivation path is deeper than {MAX_PATH_DEPTH} levels")]
+    PathTooDeep,
+}
+
+impl<T> LedgerEthSigner<T>
+where
+    T: LedgerTransport + Sync + Send,
+{
+    /// Uses the key at `path`, typically [DerivationPath::ethereum].
+    pub fn new(transport: T, path: DerivationPath) -> Self {
+        Self { transport, path }
+    }
+
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    async fn send(&self, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>, LedgerError<T::Error>> {
+        let mut command = vec![CLA, ins, p1, 0x00, data.len() as u8];
+        command.extend_from_slice(data);
+
+        let mut response = self
+            .transport
+            .exchange(&command)
+            .await
+            .map_err(LedgerError::Transport)?;
+        if response.len() < 2 {
+            return Err(LedgerError::InvalidResponse);
+        }
+
+        let status = response.split_off(response.len() - 2);
+        match u16::from_be_bytes([status[0], status[1]]) {
+            STATUS_OK => Ok(response),
+            STATUS_USER_REJECTED => Err(LedgerError::UserRejected),
+            status => Err(LedgerError::Status(status)),
+        }
+    }
+
+    fn encode_path(&self) -> Result<Vec<u8>, LedgerError<T::Error>> {
+        let indices = self.path.indices();
+        if indices.len() > MAX_PATH_DEPTH {
+            return Err(LedgerError::PathTooDeep);
+        }
+
+        let mut encoded = vec![indices.len() as u8];
+        for index in indices.iter() {
+            encoded.extend_from_slice(&index.to_be_bytes());
+        }
+        Ok(encoded)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<T> Secp256k1Signer for LedgerEthSigner<T>
+where
+    T: LedgerTransport + Sync + Send,
+{
+    type GetPublicKeyError = LedgerError<T::Error>;
+    type SignError = LedgerError<T::Error>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        // P1 of 0 returns the key without asking for confirmation
+        let response = self
+            .send(INS_GET_PUBLIC_KEY, 0x00, &self.encode_path()?)
+            .await?;
+
+        // Response is the length-prefixed uncompressed key followed by the address
+        let key_len = *response.first().ok_or(LedgerError::InvalidResponse)? as usize;
+        let key = response
+            .get(1..(1 + key_len))
+            .ok_or(LedgerError::InvalidResponse)?;
+
+        VerifyingKey::from_sec1_bytes(key).map_err(|_| LedgerError::InvalidResponse)
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Secp256k1Signature, Self::SignError> {
+        let message = hash.to_bytes_be();
+
+        let mut data = self.encode_path()?;
+        data.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        data.extend_from_slice(&message);
+
+        // Response is `v || r || s`, where `v` is 27 for even `y` and 28 for odd `y`
+        let response = self.send(INS_SIGN_PERSONAL_MESSAGE, 0x00, &data).await?;
+        if response.len() != 65 {
+            return Err(LedgerError::InvalidResponse);
+        }
+
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&response[1..33]);
+        s.copy_from_slice(&response[33..]);
+
+        Ok(Secp256k1Signature {
+            r,
+            s,
+            y_parity: response[0] == 28,
+        })
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::{eip191_hash, tests::test_key};
+
+    use k256::ecdsa::SigningKey;
+    use std::sync::Mutex;
+
+    /// Emulates the Ethereum app with a fixed key.
+    struct MockTransport {
+        key: SigningKey,
+        reject: bool,
+        commands: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock transport error")]
+    struct MockError;
+
+    #[async_trait]
+    impl LedgerTransport for MockTransport {
+        type Error = MockError;
+
+        async fn exchange(&self, command: &[u8]) -> Result<Vec<u8>, Self::Error> {
+            self.commands.lock().unwrap().push(command.to_vec());
+
+            if self.reject {
+                return Ok(vec![0x69, 0x85]);
+            }
+
+            let mut response = match command[1] {
+                INS_GET_PUBLIC_KEY => {
+                    let key = self.key.verifying_key().to_encoded_point(false);
+                    let mut response = vec![key.len() as u8];
+                    response.extend_from_slice(key.as_bytes());
+                    response
+                }
+                INS_SIGN_PERSONAL_MESSAGE => {
+                    let hash =
+                        FieldElement::from_byte_slice_be(&command[command.len() - 32..]).unwrap();
+                    let (signature, recovery_id) = self
+                        .key
+                        .sign_prehash_recoverable(&eip191_hash(&hash))
+                        .unwrap();
+                    let mut response = vec![27 + recovery_id.to_byte()];
+                    response.extend_from_slice(&signature.to_bytes());
+                    response
+                }
+                _ => unreachable!(),
+            };
+            response.extend_from_slice(&[0x90, 0x00]);
+            Ok(response)
+        }
+    }
+
+    fn signer(reject: bool) -> LedgerEthSigner<MockTransport> {
+        LedgerEthSigner::new(
+            MockTransport {
+                key: test_key(),
+                reject,
+                commands: Mutex::new(vec![]),
+            },
+            DerivationPath::ethereum(0),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signing() {
+        let signer = signer(false);
+        let hash = FieldElement::from_hex_be("0x1234abcd").unwrap();
+
+        assert_eq!(
+            &signer.get_public_key().await.unwrap(),
+            test_key().verifying_key()
+        );
+        assert_eq!(
+            signer.sign_hash(&hash).await.unwrap(),
+            Secp256k1Signer::sign_hash(&test_key(), &hash)
+                .await
+                .unwrap()
+        );
+
+        let commands = signer.transport.commands.lock().unwrap();
+        assert_eq!(
+            hex::encode(&commands[1]),
+            "e008000039058000002c8000003c80000000000000000000000000000020000000\
+            000000000000000000000000000000000000000000000000001234abcd"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ledger_rejection() {
+        let signer = signer(true);
+
+        assert!(matches!(
+            signer.sign_hash(&FieldElement::ONE).await,
+            Err(LedgerError::UserRejected)
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+//! Signing with Ethereum (secp256k1) keys for Eth-flavored account classes.
+
+use crate::{Infallible, SigningKey as StarkSigningKey};
+
+use async_trait::async_trait;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use sha3::{Digest, Keccak256};
+use starknet_core::types::FieldElement;
+use starknet_crypto::grind_key;
+use std::error::Error;
+
+pub mod ledger;
+
+/// Signs Starknet hashes with a secp256k1 key.
+///
+/// Hashes are signed as [EIP-191](https://eips.ethereum.org/EIPS/eip-191) personal messages of
+/// their 32-byte big-endian representation, the same way wallets like MetaMask and the Ledger
+/// Ethereum app sign arbitrary messages.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Secp256k1Signer {
+    type GetPublicKeyError: Error + Send;
+    type SignError: Error + Send;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError>;
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Secp256k1Signature, Self::SignError>;
+
+    /// Whether signing requires user interaction, e.g. confirming on a hardware device. Defaults
+    /// to `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// A secp256k1 signature with the parity of the `y` coordinate of its nonce point, which allows
+/// recovering the public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Secp256k1Signature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    pub y_parity: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid Ethereum signature")]
+pub struct InvalidEthSignatureError;
+
+impl Secp256k1Signature {
+    /// Parses the 65-byte `r || s || v` signature returned by Ethereum wallets for
+    /// `personal_sign` and `eth_signTypedData`, where `v` is either 27/28 or 0/1.
+    pub fn from_rsv(bytes: &[u8]) -> Result<Self, InvalidEthSignatureError> {
+        if bytes.len() != 65 {
+            return Err(InvalidEthSignatureError);
+        }
+
+        let y_parity = match bytes[64] {
+            0 | 27 => false,
+            1 | 28 => true,
+            _ => return Err(InvalidEthSignatureError),
+        };
+
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..64]);
+
+        Ok(Self { r, s, y_parity })
+    }
+
+    /// Encodes the signature as `[r.low, r.high, s.low, s.high, y_parity]`, the layout expected by
+    /// Eth-flavored account classes taking a `Secp256k1Signature` argument.
+    pub fn to_felts(&self) -> Vec<FieldElement> {
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&u256_to_felts(&self.r));
+        encoded.extend_from_slice(&u256_to_felts(&self.s));
+        encoded.push(if self.y_parity {
+            FieldElement::ONE
+        } else {
+            FieldElement::ZERO
+        });
+        encoded
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Secp256k1Signer for SigningKey {
+    type GetPublicKeyError = Infallible;
+    type SignError = k256::ecdsa::Error;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(*self.verifying_key())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Secp256k1Signature, Self::SignError> {
+        let (signature, recovery_id) = self.sign_prehash_recoverable(&eip191_hash(hash))?;
+
+        let signature_bytes = signature.to_bytes();
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&signature_bytes[..32]);
+        s.copy_from_slice(&signature_bytes[32..]);
+
+        Ok(Secp256k1Signature {
+            r,
+            s,
+            y_parity: recovery_id.is_y_odd(),
+        })
+    }
+}
+
+/// The 20-byte Ethereum address of `public_key`.
+pub fn eth_address(public_key: &VerifyingKey) -> [u8; 20] {
+    let public_key = public_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Hashes the 32-byte big-endian representation of `hash` as an EIP-191 personal message.
+pub fn eip191_hash(hash: &FieldElement) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"\x19Ethereum Signed Message:\n32");
+    hasher.update(hash.to_bytes_be());
+    hasher.finalize().into()
+}
+
+/// Derives a Stark private key from an Ethereum signature by grinding its `r` value, the way
+/// StarkEx exchanges derive the L2 key of users from a fixed message signed with their L1 wallet.
+///
+/// As signatures are deterministic (RFC 6979), signing the same message with the same wallet
+/// always gives back the same key.
+pub fn stark_key_from_eth_signature(signature: &Secp256k1Signature) -> StarkSigningKey {
+    StarkSigningKey::from_secret_scalar(grind_key(&signature.r))
+}
+
+/// Signs `message` as an EIP-191 personal message, as done by `personal_sign` in Ethereum
+/// wallets.
+pub fn sign_personal_message(
+    key: &SigningKey,
+    message: &[u8],
+) -> Result<Secp256k1Signature, k256::ecdsa::Error> {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()));
+    hasher.update(message);
+
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&hasher.finalize())?;
+
+    let signature_bytes = signature.to_bytes();
+    let mut r = [0u8; 32];
+    let mut s = [0u8; 32];
+    r.copy_from_slice(&signature_bytes[..32]);
+    s.copy_from_slice(&signature_bytes[32..]);
+
+    Ok(Secp256k1Signature {
+        r,
+        s,
+        y_parity: recovery_id.is_y_odd(),
+    })
+}
+
+/// Splits a 256-bit big-endian integer into its `(low, high)` 128-bit halves.
+pub(crate) fn u256_to_felts(value: &[u8; 32]) -> [FieldElement; 2] {
+    let mut low = [0u8; 32];
+    let mut high = [0u8; 32];
+    low[16..].copy_from_slice(&value[16..]);
+    high[16..].copy_from_slice(&value[..16]);
+
+    [
+        FieldElement::from_bytes_be(&low).unwrap(),
+        FieldElement::from_bytes_be(&high).unwrap(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use k256::ecdsa::{RecoveryId, Signature};
+
+    pub(crate) fn test_key() -> SigningKey {
+        SigningKey::from_slice(&[
+            0x4c, 0x08, 0x83, 0xa6, 0x91, 0x02, 0x93, 0x7d, 0x62, 0x31, 0x47, 0x1b, 0x5d, 0xbb,
+            0x62, 0x04, 0xfe, 0x51, 0x29, 0x61, 0x70, 0x82, 0x79, 0x2a, 0xe4, 0x68, 0xd0, 0x1a,
+            0x3f, 0x36, 0x23, 0x18,
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_eth_address() {
+        // Well-known address of this test key
+        assert_eq!(
+            hex::encode(eth_address(test_key().verifying_key())),
+            "2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_recovers_to_signer() {
+        let key = test_key();
+        let hash = FieldElement::from_hex_be("0x1234abcd").unwrap();
+
+        let signature = Secp256k1Signer::sign_hash(&key, &hash).await.unwrap();
+
+        let mut signature_bytes = [0u8; 64];
+        signature_bytes[..32].copy_from_slice(&signature.r);
+        signature_bytes[32..].copy_from_slice(&signature.s);
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            &eip191_hash(&hash),
+            &Signature::from_slice(&signature_bytes).unwrap(),
+            RecoveryId::new(signature.y_parity, false),
+        )
+        .unwrap();
+
+        assert_eq!(&recovered, key.verifying_key());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_stark_key_from_eth_signature() {
+        // Test vector from `starkware-crypto`
+        let signature = Secp256k1Signature::from_rsv(
+            &hex::decode(
+                "21fbf0696d5e0aa2ef41a2b4ffb623bcaf070461d61cf7251c74161f82fec3a4\
+                370854bc0a34b3ab487c1bc021cd318c734c51ae29374f2beb0e6f2dd49b4bf41c",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(signature.y_parity);
+        assert_eq!(
+            stark_key_from_eth_signature(&signature).secret_scalar(),
+            FieldElement::from_hex_be(
+                "0766f11e90cd7c7b43085b56da35c781f8c067ac0d578eabdceebc4886435bda"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_sign_personal_message_recovers_to_signer() {
+        let key = test_key();
+        let message = b"Only sign this request if you've initiated an action with StarkEx.";
+
+        let signature = sign_personal_message(&key, message).unwrap();
+        assert_eq!(signature, sign_personal_message(&key, message).unwrap());
+
+        let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        prefixed.extend_from_slice(message);
+
+        let mut signature_bytes = [0u8; 64];
+        signature_bytes[..32].copy_from_slice(&signature.r);
+        signature_bytes[32..].copy_from_slice(&signature.s);
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            &Keccak256::digest(&prefixed),
+            &Signature::from_slice(&signature_bytes).unwrap(),
+            RecoveryId::new(signature.y_parity, false),
+        )
+        .unwrap();
+
+        assert_eq!(&recovered, key.verifying_key());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_from_rsv_rejects_invalid_v() {
+        let mut bytes = [0u8; 65];
+        bytes[64] = 29;
+
+        assert!(Secp256k1Signature::from_rsv(&bytes).is_err());
+        assert!(Secp256k1Signature::from_rsv(&bytes[..64]).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_to_felts() {
+        let mut r = [0u8; 32];
+        r[15] = 1;
+        r[31] = 2;
+        let signature = Secp256k1Signature {
+            r,
+            s: [0u8; 32],
+            y_parity: true,
+        };
+
+        assert_eq!(
+            signature.to_felts(),
+            vec![
+                FieldElement::TWO,
+                FieldElement::ONE,
+                FieldElement::ZERO,
+                FieldElement::ZERO,
+                FieldElement::ONE,
+            ]
+        );
+    }
+}
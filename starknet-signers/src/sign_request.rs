@@ -0,0 +1,254 @@
+use starknet_core::{crypto::compute_hash_on_elements, types::FieldElement};
+
+/// Cairo string for "invoke"
+const PREFIX_INVOKE: FieldElement = FieldElement::from_mont([
+    18443034532770911073,
+    18446744073709551615,
+    18446744073709551615,
+    513398556346534256,
+]);
+
+/// Cairo string for "declare"
+const PREFIX_DECLARE: FieldElement = FieldElement::from_mont([
+    17542456862011667323,
+    18446744073709551615,
+    18446744073709551615,
+    191557713328401194,
+]);
+
+/// Cairo string for "deploy_account"
+const PREFIX_DEPLOY_ACCOUNT: FieldElement = FieldElement::from_mont([
+    3350261884043292318,
+    18443211694809419988,
+    18446744073709551615,
+    461298303000467581,
+]);
+
+/// A hash to be signed along with what it commits to, so that interactive signers can present
+/// meaningful prompts instead of asking users to blind sign an opaque hash.
+#[derive(Debug, Clone)]
+pub struct SignRequest {
+    pub hash: FieldElement,
+    pub context: SignContext,
+}
+
+/// What the hash in a [SignRequest] was computed from.
+#[derive(Debug, Clone)]
+pub enum SignContext {
+    /// Nothing is known about the hash.
+    Blind,
+    Invoke(InvokeContext),
+    Declare(DeclareContext),
+    DeployAccount(DeployAccountContext),
+}
+
+/// An `INVOKE` transaction.
+#[derive(Debug, Clone)]
+pub struct InvokeContext {
+    pub sender_address: FieldElement,
+    pub chain_id: FieldElement,
+    /// How `calls` are laid out in the `__execute__` calldata.
+    pub encoding: CalldataEncoding,
+    pub calls: Vec<CallContext>,
+    pub max_fee: FieldElement,
+    pub nonce: FieldElement,
+    /// Whether the hash uses the query version `2^128 + 1`, which makes the signature only valid
+    /// for fee estimation and simulation.
+    pub is_query: bool,
+}
+
+/// The layout of the calldata sent to the account's `__execute__` entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalldataEncoding {
+    /// The call array layout used by Cairo 0 accounts: all call metadata followed by the
+    /// concatenated calldata.
+    Legacy,
+    /// The `Array<Call>` layout used by Cairo 1 accounts, with each call's calldata inlined.
+    New,
+}
+
+/// A single call in a multicall.
+#[derive(Debug, Clone)]
+pub struct CallContext {
+    pub to: FieldElement,
+    pub selector: FieldElement,
+    pub calldata: Vec<FieldElement>,
+}
+
+/// A `DECLARE` transaction.
+#[derive(Debug, Clone)]
+pub struct DeclareContext {
+    pub sender_address: FieldElement,
+    pub chain_id: FieldElement,
+    pub class_hash: FieldElement,
+    pub max_fee: FieldElement,
+    pub nonce: FieldElement,
+    /// Whether the hash uses the query version `2^128 + 1`.
+    pub is_query: bool,
+}
+
+/// A `DEPLOY_ACCOUNT` transaction.
+#[derive(Debug, Clone)]
+pub struct DeployAccountContext {
+    pub contract_address: FieldElement,
+    pub chain_id: FieldElement,
+    pub class_hash: FieldElement,
+    pub contract_address_salt: FieldElement,
+    pub constructor_calldata: Vec<FieldElement>,
+    pub max_fee: FieldElement,
+    pub nonce: FieldElement,
+    /// Whether the hash uses the query version `2^128 + 1`.
+    pub is_query: bool,
+}
+
+impl SignRequest {
+    /// A request without any context about the hash.
+    pub fn blind(hash: FieldElement) -> Self {
+        Self {
+            hash,
+            context: SignContext::Blind,
+        }
+    }
+
+    /// Whether the signature is only valid for fee estimation and simulation, as the hash uses
+    /// the query version.
+    pub fn is_fee_estimation(&self) -> bool {
+        match &self.context {
+            SignContext::Blind => false,
+            SignContext::Invoke(context) => context.is_query,
+            SignContext::Declare(context) => context.is_query,
+            SignContext::DeployAccount(context) => context.is_query,
+        }
+    }
+
+    /// Whether `hash` is the transaction hash computed from `context`. Always `false` for
+    /// [SignContext::Blind].
+    pub fn matches_context(&self) -> bool {
+        match &self.context {
+            SignContext::Blind => false,
+            SignContext::Invoke(context) => context.transaction_hash() == self.hash,
+            SignContext::Declare(context) => context.transaction_hash() == self.hash,
+            SignContext::DeployAccount(context) => context.transaction_hash() == self.hash,
+        }
+    }
+}
+
+impl InvokeContext {
+    /// The `__execute__` calldata of the calls.
+    pub fn calldata(&self) -> Vec<FieldElement> {
+        let mut calldata: Vec<FieldElement> = vec![self.calls.len().into()];
+        match self.encoding {
+            CalldataEncoding::Legacy => {
+                let mut concated_calldata: Vec<FieldElement> = vec![];
+                for call in self.calls.iter() {
+                    calldata.push(call.to);
+                    calldata.push(call.selector);
+                    calldata.push(concated_calldata.len().into()); // data_offset
+                    calldata.push(call.calldata.len().into()); // data_len
+                    concated_calldata.extend_from_slice(&call.calldata);
+                }
+                calldata.push(concated_calldata.len().into());
+                calldata.extend(concated_calldata);
+            }
+            CalldataEncoding::New => {
+                for call in self.calls.iter() {
+                    calldata.push(call.to);
+                    calldata.push(call.selector);
+                    calldata.push(call.calldata.len().into());
+                    calldata.extend_from_slice(&call.calldata);
+                }
+            }
+        }
+        calldata
+    }
+
+    pub fn transaction_hash(&self) -> FieldElement {
+        compute_hash_on_elements(&[
+            PREFIX_INVOKE,
+            transaction_version(self.is_query),
+            self.sender_address,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&self.calldata()),
+            self.max_fee,
+            self.chain_id,
+            self.nonce,
+        ])
+    }
+}
+
+impl DeclareContext {
+    pub fn transaction_hash(&self) -> FieldElement {
+        compute_hash_on_elements(&[
+            PREFIX_DECLARE,
+            transaction_version(self.is_query),
+            self.sender_address,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&[self.class_hash]),
+            self.max_fee,
+            self.chain_id,
+            self.nonce,
+        ])
+    }
+}
+
+impl DeployAccountContext {
+    pub fn transaction_hash(&self) -> FieldElement {
+        let mut calldata_to_hash = vec![self.class_hash, self.contract_address_salt];
+        calldata_to_hash.extend_from_slice(&self.constructor_calldata);
+
+        compute_hash_on_elements(&[
+            PREFIX_DEPLOY_ACCOUNT,
+            transaction_version(self.is_query),
+            self.contract_address,
+            FieldElement::ZERO, // entry_point_selector
+            compute_hash_on_elements(&calldata_to_hash),
+            self.max_fee,
+            self.chain_id,
+            self.nonce,
+        ])
+    }
+}
+
+/// Version 1, or its query counterpart `2^128 + 1`.
+fn transaction_version(is_query: bool) -> FieldElement {
+    if is_query {
+        FieldElement::from_hex_be("0x100000000000000000000000000000001").unwrap()
+    } else {
+        FieldElement::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_is_fee_estimation() {
+        let context = InvokeContext {
+            sender_address: FieldElement::ONE,
+            chain_id: FieldElement::TWO,
+            encoding: CalldataEncoding::New,
+            calls: vec![],
+            max_fee: FieldElement::ZERO,
+            nonce: FieldElement::ZERO,
+            is_query: true,
+        };
+
+        let estimation = SignRequest {
+            hash: FieldElement::ONE,
+            context: SignContext::Invoke(context.clone()),
+        };
+        let execution = SignRequest {
+            hash: FieldElement::ONE,
+            context: SignContext::Invoke(InvokeContext {
+                is_query: false,
+                ..context
+            }),
+        };
+
+        assert!(estimation.is_fee_estimation());
+        assert!(!execution.is_fee_estimation());
+        assert!(!SignRequest::blind(FieldElement::ONE).is_fee_estimation());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::VerifyingKey;
+use crate::{SignRequest, VerifyingKey};
 
 use async_trait::async_trait;
 use starknet_core::{crypto::Signature, types::FieldElement};
@@ -13,4 +13,20 @@ pub trait Signer {
     async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError>;
 
     async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError>;
+
+    /// Signs the hash in `request`. Signers that interact with users should override this to show
+    /// the request context. Defaults to signing the hash with [sign_hash](Self::sign_hash).
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError>
+    where
+        Self: Sync,
+    {
+        self.sign_hash(&request.hash).await
+    }
+
+    /// Whether signing requires user interaction, e.g. confirming on a hardware device. Callers
+    /// should avoid requesting signatures that users aren't expecting from such signers. Defaults
+    /// to `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,523 @@
+//! Reference t-of-n threshold ECDSA on the Stark curve with a trusted dealer.
+//!
+//! The dealer knows the full key and prepares single-use presignatures: for a random nonce `k`,
+//! it computes `r` from `k * G` and distributes Shamir shares of `k^-1` and `k^-1 * x` to all
+//! parties. As `s = k^-1 * z + r * (k^-1 * x)` is linear in these shares, any `threshold` parties
+//! can then each produce a share of `s` on their own, which are combined without reconstructing
+//! any secret.
+//!
+//! This is meant to show how [ThresholdSigner] fits together and for testing integrations. As the
+//! dealer is a single point of compromise, production setups should use a protocol with
+//! distributed key generation instead.
+
+use super::ThresholdSigner;
+use crate::{SignRequest, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use starknet_core::{crypto::Signature, types::FieldElement};
+use starknet_crypto::get_public_key;
+use starknet_curve::curve_params::EC_ORDER;
+use std::{collections::BTreeSet, fmt::Debug, sync::Mutex};
+
+/// Splits a key among parties and prepares presignatures for them.
+pub struct TrustedDealer {
+    key: SigningKey,
+    threshold: usize,
+    parties: usize,
+    next_presignature_id: u64,
+}
+
+/// One party's share of a presignature. Each presignature must only ever be used once, as
+/// signing two different hashes with the same nonce reveals the key.
+#[derive(Clone)]
+pub struct PresignatureShare {
+    id: u64,
+    index: u32,
+    r: FieldElement,
+    inverse_nonce_share: FieldElement,
+    scaled_key_share: FieldElement,
+}
+
+/// A party's share of a signature.
+#[derive(Debug, Clone)]
+pub struct SignatureShare {
+    pub index: u32,
+    pub r: FieldElement,
+    pub s: FieldElement,
+}
+
+/// A party holding presignature shares.
+#[derive(Debug)]
+pub struct ThresholdParty {
+    index: u32,
+    presignatures: Mutex<Vec<PresignatureShare>>,
+}
+
+/// Runs the signing protocol among [ThresholdParty] instances in the same process.
+#[derive(Debug)]
+pub struct LocalThresholdSigner {
+    public_key: VerifyingKey,
+    threshold: usize,
+    parties: Vec<ThresholdParty>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DealerError {
+    #[error("threshold must be between 1 and the number of parties")]
+    InvalidThreshold,
+    #[error("no presignature is held by enough parties")]
+    PresignaturesExhausted,
+    #[error("presignature {0} not found")]
+    UnknownPresignature(u64),
+    #[error("signature shares are for different presignatures")]
+    MismatchedShares,
+    #[error("not enough signature shares")]
+    NotEnoughShares,
+    #[error("message hash out of range")]
+    MessageHashOutOfRange,
+    #[error("presignature produced an invalid signature")]
+    InvalidSignature,
+}
+
+impl TrustedDealer {
+    /// Shares `key` among `parties` parties, any `threshold` of which can sign.
+    pub fn new(key: SigningKey, threshold: usize, parties: usize) -> Result<Self, DealerError> {
+        if threshold == 0 || threshold > parties || parties >= u32::MAX as usize {
+            return Err(DealerError::InvalidThreshold);
+        }
+
+        Ok(Self {
+            key,
+            threshold,
+            parties,
+            next_presignature_id: 0,
+        })
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Prepares `count` presignatures, returning the shares for each party in order of their
+    /// index starting from 1.
+    pub fn presign<R>(&mut self, rng: &mut R, count: usize) -> Vec<Vec<PresignatureShare>>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let order = to_biguint(&EC_ORDER);
+        let key = to_biguint(&self.key.secret_scalar());
+        let mut shares = vec![Vec::with_capacity(count); self.parties];
+
+        for _ in 0..count {
+            let (k, r) = loop {
+                let k = random_scalar(rng);
+                let r = get_public_key(&k);
+                if r != FieldElement::ZERO && r.to_bytes_be()[0] < 0x08 {
+                    break (k, r);
+                }
+            };
+
+            let inverse_nonce = to_biguint(&k).modpow(&(&order - 2u32), &order);
+            let scaled_key = &inverse_nonce * &key % &order;
+
+            let inverse_nonce_shares = split(&inverse_nonce, self.threshold, self.parties, rng);
+            let scaled_key_shares = split(&scaled_key, self.threshold, self.parties, rng);
+
+            let id = self.next_presignature_id;
+            self.next_presignature_id += 1;
+
+            for (index, (inverse_nonce_share, scaled_key_share)) in inverse_nonce_shares
+                .into_iter()
+                .zip(scaled_key_shares)
+                .enumerate()
+            {
+                shares[index].push(PresignatureShare {
+                    id,
+                    index: index as u32 + 1,
+                    r,
+                    inverse_nonce_share,
+                    scaled_key_share,
+                });
+            }
+        }
+
+        shares
+    }
+}
+
+impl Debug for TrustedDealer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrustedDealer")
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PresignatureShare {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl Debug for PresignatureShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PresignatureShare")
+            .field("id", &self.id)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ThresholdParty {
+    pub fn new(index: u32, presignatures: Vec<PresignatureShare>) -> Self {
+        Self {
+            index,
+            presignatures: Mutex::new(presignatures),
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn add_presignatures(&self, presignatures: Vec<PresignatureShare>) {
+        self.presignatures.lock().unwrap().extend(presignatures);
+    }
+
+    /// The IDs of the presignatures not used yet.
+    pub fn presignature_ids(&self) -> Vec<u64> {
+        self.presignatures
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|presignature| presignature.id)
+            .collect()
+    }
+
+    /// Discards presignature `id` without using it. Once any quorum has used a presignature, it
+    /// must be discarded by all other parties, as a disjoint quorum signing another hash with it
+    /// would reveal the key.
+    pub fn discard_presignature(&self, id: u64) {
+        self.presignatures
+            .lock()
+            .unwrap()
+            .retain(|presignature| presignature.id != id);
+    }
+
+    /// Produces a share of the signature of `hash` with presignature `id`, which is then
+    /// discarded. Other parties holding `id` must [discard](Self::discard_presignature) it too.
+    pub fn sign_share(&self, id: u64, hash: &FieldElement) -> Result<SignatureShare, DealerError> {
+        if hash.to_bytes_be()[0] >= 0x08 {
+            return Err(DealerError::MessageHashOutOfRange);
+        }
+
+        let presignature = {
+            let mut presignatures = self.presignatures.lock().unwrap();
+            let position = presignatures
+                .iter()
+                .position(|presignature| presignature.id == id)
+                .ok_or(DealerError::UnknownPresignature(id))?;
+            presignatures.remove(position)
+        };
+
+        let order = to_biguint(&EC_ORDER);
+        let s = (to_biguint(&presignature.inverse_nonce_share) * to_biguint(hash)
+            + to_biguint(&presignature.r) * to_biguint(&presignature.scaled_key_share))
+            % &order;
+
+        Ok(SignatureShare {
+            index: self.index,
+            r: presignature.r,
+            s: from_biguint(&s),
+        })
+    }
+}
+
+impl LocalThresholdSigner {
+    pub fn new(
+        public_key: VerifyingKey,
+        threshold: usize,
+        parties: Vec<ThresholdParty>,
+    ) -> Result<Self, DealerError> {
+        if threshold == 0 || threshold > parties.len() {
+            return Err(DealerError::InvalidThreshold);
+        }
+
+        Ok(Self {
+            public_key,
+            threshold,
+            parties,
+        })
+    }
+
+    pub fn parties(&self) -> &[ThresholdParty] {
+        &self.parties
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ThresholdSigner for LocalThresholdSigner {
+    type Error = DealerError;
+
+    fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    fn parties(&self) -> usize {
+        self.parties.len()
+    }
+
+    async fn get_group_public_key(&self) -> Result<VerifyingKey, Self::Error> {
+        Ok(self.public_key.clone())
+    }
+
+    async fn sign_with_quorum(&self, request: &SignRequest) -> Result<Signature, Self::Error> {
+        // Uses the oldest presignature held by enough parties
+        let ids = self
+            .parties
+            .iter()
+            .flat_map(|party| party.presignature_ids())
+            .collect::<BTreeSet<_>>();
+        let (id, quorum) = ids
+            .into_iter()
+            .find_map(|id| {
+                let holders = self
+                    .parties
+                    .iter()
+                    .filter(|party| party.presignature_ids().contains(&id))
+                    .take(self.threshold)
+                    .collect::<Vec<_>>();
+                (holders.len() == self.threshold).then_some((id, holders))
+            })
+            .ok_or(DealerError::PresignaturesExhausted)?;
+
+        let shares = quorum
+            .iter()
+            .map(|party| party.sign_share(id, &request.hash))
+            .collect::<Result<Vec<_>, _>>();
+
+        // Parties outside the quorum must never sign with the same nonce
+        for party in self.parties.iter() {
+            party.discard_presignature(id);
+        }
+
+        combine_signature_shares(&shares?)
+    }
+}
+
+/// Combines signature shares from any `threshold` parties into a signature.
+pub fn combine_signature_shares(shares: &[SignatureShare]) -> Result<Signature, DealerError> {
+    let r = shares.first().ok_or(DealerError::NotEnoughShares)?.r;
+    if shares.iter().any(|share| share.r != r) {
+        return Err(DealerError::MismatchedShares);
+    }
+
+    let order = to_biguint(&EC_ORDER);
+    let indices = shares
+        .iter()
+        .map(|share| BigUint::from(share.index))
+        .collect::<Vec<_>>();
+
+    let mut s = BigUint::from(0u32);
+    for (i, share) in shares.iter().enumerate() {
+        // Lagrange coefficient for evaluating at zero
+        let mut numerator = BigUint::from(1u32);
+        let mut denominator = BigUint::from(1u32);
+        for (j, index) in indices.iter().enumerate() {
+            if i != j {
+                numerator = numerator * index % &order;
+                denominator = denominator * ((index + &order - &indices[i]) % &order) % &order;
+            }
+        }
+        if denominator == BigUint::from(0u32) {
+            return Err(DealerError::MismatchedShares);
+        }
+
+        let coefficient = numerator * denominator.modpow(&(&order - 2u32), &order) % &order;
+        s = (s + coefficient * to_biguint(&share.s)) % &order;
+    }
+
+    let s = from_biguint(&s);
+    if s == FieldElement::ZERO {
+        return Err(DealerError::InvalidSignature);
+    }
+
+    Ok(Signature { r, s })
+}
+
+/// Shamir shares of `secret` for indices `1..=parties`.
+fn split<R>(secret: &BigUint, threshold: usize, parties: usize, rng: &mut R) -> Vec<FieldElement>
+where
+    R: RngCore + CryptoRng,
+{
+    let order = to_biguint(&EC_ORDER);
+    let coefficients = (1..threshold)
+        .map(|_| to_biguint(&random_scalar(rng)))
+        .collect::<Vec<_>>();
+
+    (1..=parties)
+        .map(|index| {
+            let index = BigUint::from(index);
+            let share = coefficients
+                .iter()
+                .rev()
+                .fold(BigUint::from(0u32), |acc, coefficient| {
+                    (acc + coefficient) * &index % &order
+                });
+            from_biguint(&((share + secret) % &order))
+        })
+        .collect()
+}
+
+/// A uniformly random non-zero scalar.
+fn random_scalar<R>(rng: &mut R) -> FieldElement
+where
+    R: RngCore + CryptoRng,
+{
+    let order = to_biguint(&EC_ORDER);
+    loop {
+        let mut buffer = [0u8; 32];
+        rng.fill_bytes(&mut buffer);
+        buffer[0] &= 0x0f;
+
+        let scalar = BigUint::from_bytes_be(&buffer);
+        if scalar != BigUint::from(0u32) && scalar < order {
+            return from_biguint(&scalar);
+        }
+    }
+}
+
+fn to_biguint(value: &FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&value.to_bytes_be())
+}
+
+fn from_biguint(value: &BigUint) -> FieldElement {
+    let bytes = value.to_bytes_be();
+    let mut buffer = [0u8; 32];
+    buffer[(32 - bytes.len())..].copy_from_slice(&bytes);
+    FieldElement::from_bytes_be(&buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{threshold::ThresholdWallet, Signer};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn setup(
+        threshold: usize,
+        parties: usize,
+        presignatures: usize,
+    ) -> (VerifyingKey, Vec<ThresholdParty>) {
+        let mut rng = StdRng::seed_from_u64(1);
+        let key = SigningKey::from_secret_scalar(
+            FieldElement::from_hex_be(
+                "0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79",
+            )
+            .unwrap(),
+        );
+        let mut dealer = TrustedDealer::new(key, threshold, parties).unwrap();
+
+        let parties = dealer
+            .presign(&mut rng, presignatures)
+            .into_iter()
+            .enumerate()
+            .map(|(index, presignatures)| ThresholdParty::new(index as u32 + 1, presignatures))
+            .collect();
+
+        (dealer.verifying_key(), parties)
+    }
+
+    #[tokio::test]
+    async fn test_two_of_two() {
+        let (public_key, parties) = setup(2, 2, 2);
+        let wallet =
+            ThresholdWallet::new(LocalThresholdSigner::new(public_key, 2, parties).unwrap());
+        let hash = FieldElement::from_hex_be("0x1234").unwrap();
+
+        let first = wallet.sign_hash(&hash).await.unwrap();
+        let second = wallet.sign_hash(&hash).await.unwrap();
+        assert_ne!(first.r, second.r);
+
+        assert!(matches!(
+            wallet.sign_hash(&hash).await,
+            Err(crate::threshold::ThresholdWalletError::Threshold(
+                DealerError::PresignaturesExhausted
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_two_of_four_disjoint_quorums() {
+        let (public_key, parties) = setup(2, 4, 2);
+
+        // Parties 1 and 2 can only sign with presignature 0, so the second signature has to be
+        // produced by parties 3 and 4
+        parties[0].discard_presignature(1);
+        parties[1].discard_presignature(1);
+
+        let signer = LocalThresholdSigner::new(public_key.clone(), 2, parties).unwrap();
+        let first_hash = FieldElement::from_hex_be("0x1234").unwrap();
+        let second_hash = FieldElement::from_hex_be("0x5678").unwrap();
+
+        let first = signer
+            .sign_with_quorum(&SignRequest::blind(first_hash))
+            .await
+            .unwrap();
+        let second = signer
+            .sign_with_quorum(&SignRequest::blind(second_hash))
+            .await
+            .unwrap();
+
+        assert_ne!(first.r, second.r);
+        assert!(public_key.verify(&first_hash, &first).unwrap());
+        assert!(public_key.verify(&second_hash, &second).unwrap());
+        assert!(signer
+            .parties()
+            .iter()
+            .all(|party| party.presignature_ids().is_empty()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_two_of_three_any_quorum() {
+        let (public_key, parties) = setup(2, 3, 3);
+        let hash = FieldElement::from_hex_be("0x1234").unwrap();
+
+        for (id, quorum) in [[0, 1], [0, 2], [1, 2]].into_iter().enumerate() {
+            let id = id as u64;
+            let shares = quorum
+                .iter()
+                .map(|index| parties[*index].sign_share(id, &hash).unwrap())
+                .collect::<Vec<_>>();
+            let signature = combine_signature_shares(&shares).unwrap();
+
+            assert!(public_key.verify(&hash, &signature).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_single_share_is_not_a_signature() {
+        let (public_key, parties) = setup(2, 3, 1);
+        let hash = FieldElement::from_hex_be("0x1234").unwrap();
+
+        let share = parties[0].sign_share(0, &hash).unwrap();
+        let signature = combine_signature_shares(&[share]).unwrap();
+
+        assert!(!public_key.verify(&hash, &signature).unwrap());
+        assert!(matches!(
+            parties[0].sign_share(0, &hash),
+            Err(DealerError::UnknownPresignature(0))
+        ));
+    }
+}
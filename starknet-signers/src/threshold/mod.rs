@@ -0,0 +1,109 @@
+//! Signing with keys split among several parties, as done by MPC custody providers.
+//!
+//! Custody providers implement [ThresholdSigner] on top of their own protocol or API, and
+//! [ThresholdWallet] turns any such implementation into a [Signer]. A reference implementation
+//! for t-of-n signing with a trusted dealer is available in [dealer].
+
+use crate::{SignRequest, Signer, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{crypto::Signature, types::FieldElement};
+use std::error::Error;
+
+pub mod dealer;
+
+/// A group of parties holding shares of a key, any [threshold](Self::threshold) of which must
+/// cooperate to produce a signature. Implementations coordinate the signing protocol and return
+/// the combined signature.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ThresholdSigner {
+    type Error: Error + Send;
+
+    /// The number of parties needed to sign.
+    fn threshold(&self) -> usize;
+
+    /// The number of parties holding key shares.
+    fn parties(&self) -> usize;
+
+    /// The public key of the shared key.
+    async fn get_group_public_key(&self) -> Result<VerifyingKey, Self::Error>;
+
+    /// Runs the signing protocol for the hash in `request` among a quorum of parties. The context
+    /// is passed on so that parties can apply their own approval policies.
+    async fn sign_with_quorum(&self, request: &SignRequest) -> Result<Signature, Self::Error>;
+
+    /// Whether parties require human approval before signing. Defaults to `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// A [Signer] backed by a [ThresholdSigner]. Combined signatures are verified against the group
+/// public key, so that misbehaving parties can't get invalid signatures into transactions.
+#[derive(Debug)]
+pub struct ThresholdWallet<T> {
+    signer: T,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThresholdWalletError<E> {
+    #[error(transparent)]
+    Threshold(E),
+    #[error("combined signature is invalid for the group public key")]
+    InvalidSignature,
+}
+
+impl<T> ThresholdWallet<T> {
+    pub fn new(signer: T) -> Self {
+        Self { signer }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.signer
+    }
+
+    pub fn into_inner(self) -> T {
+        self.signer
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<T> Signer for ThresholdWallet<T>
+where
+    T: ThresholdSigner + Sync + Send,
+{
+    type GetPublicKeyError = T::Error;
+    type SignError = ThresholdWalletError<T::Error>;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        self.signer.get_group_public_key().await
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.sign_request(&SignRequest::blind(*hash)).await
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<Signature, Self::SignError> {
+        let public_key = self
+            .signer
+            .get_group_public_key()
+            .await
+            .map_err(ThresholdWalletError::Threshold)?;
+        let signature = self
+            .signer
+            .sign_with_quorum(request)
+            .await
+            .map_err(ThresholdWalletError::Threshold)?;
+
+        match public_key.verify(&request.hash, &signature) {
+            Ok(true) => Ok(signature),
+            _ => Err(ThresholdWalletError::InvalidSignature),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+}
@@ -0,0 +1,400 @@
+use crate::{envelope::signing_key_from_encoded_plaintext, Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+use std::collections::HashMap;
+
+const DEFAULT_MOUNT: &str = "transit";
+const DEFAULT_APPROLE_MOUNT: &str = "approle";
+
+/// A signer using the transit secrets engine of HashiCorp Vault, so that Stark keys are governed
+/// by existing Vault policies.
+///
+/// As the built-in transit engine doesn't support the Stark curve, two modes are available:
+///
+/// - [VaultTransitMode::Plugin] for a transit-compatible secrets engine plugin with Stark keys,
+///   which never reveals the private key. Its `sign` endpoint is called with the hash as a
+///   prehashed input, and must return the 64-byte `r || s` signature in the usual
+///   `vault:v{version}:{base64}` format. Its `keys` endpoint must return the hex public key of
+///   each key version as `public_key`.
+/// - [VaultTransitMode::WrappedKey] for a Stark private key encrypted with a regular transit key,
+///   where the key is decrypted every time it's used. The plaintext is either the raw 32-byte
+///   scalar or its hex representation.
+#[derive(Debug, Clone)]
+pub struct VaultTransitSigner {
+    client: reqwest::Client,
+    address: String,
+    namespace: Option<String>,
+    mount: String,
+    key_name: String,
+    auth: VaultAuth,
+    mode: VaultTransitMode,
+}
+
+/// How to authenticate with Vault.
+#[derive(Clone)]
+pub enum VaultAuth {
+    /// A token obtained out of band, e.g. with `vault login`.
+    Token(String),
+    /// A token obtained with the AppRole auth method mounted at `approle`. Logs in again every
+    /// time a token is needed.
+    AppRole { role_id: String, secret_id: String },
+}
+
+#[derive(Debug, Clone)]
+pub enum VaultTransitMode {
+    /// Signing with the Stark key `key_name` of a secrets engine plugin.
+    Plugin,
+    /// Decrypting `ciphertext` (in the `vault:v{version}:{base64}` format returned by transit
+    /// `encrypt`) with the transit key `key_name`.
+    WrappedKey { ciphertext: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultTransitError {
+    #[error(transparent)]
+    Http(reqwest::Error),
+    #[error("Vault error {status}: {}", .errors.join(", "))]
+    Vault { status: u16, errors: Vec<String> },
+    #[error("decrypted plaintext is not a valid private key")]
+    InvalidPlaintext,
+    #[error("invalid signature returned by Vault")]
+    InvalidSignature,
+    #[error("invalid public key returned by Vault")]
+    InvalidPublicKey,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+#[derive(Serialize)]
+struct AppRoleLoginRequest<'a> {
+    role_id: &'a str,
+    secret_id: &'a str,
+}
+
+#[derive(Deserialize)]
+struct AppRoleLoginResponse {
+    auth: AppRoleAuth,
+}
+
+#[derive(Deserialize)]
+struct AppRoleAuth {
+    client_token: String,
+}
+
+#[derive(Serialize)]
+struct DecryptRequest<'a> {
+    ciphertext: &'a str,
+}
+
+#[derive(Serialize)]
+struct SignRequest {
+    input: String,
+    prehashed: bool,
+}
+
+#[derive(Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct DecryptResponse {
+    plaintext: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct KeyResponse {
+    latest_version: u64,
+    keys: HashMap<String, KeyVersion>,
+}
+
+#[derive(Deserialize)]
+struct KeyVersion {
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+impl VaultTransitSigner {
+    /// `address` is the Vault server address, e.g. `https://vault.example.com:8200`.
+    pub fn new(address: String, key_name: String, auth: VaultAuth, mode: VaultTransitMode) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            address: address.trim_end_matches('/').to_owned(),
+            namespace: None,
+            mount: DEFAULT_MOUNT.to_owned(),
+            key_name,
+            auth,
+            mode,
+        }
+    }
+
+    /// Sets the path the secrets engine is mounted at. Defaults to `transit`.
+    pub fn with_mount(mut self, mount: String) -> Self {
+        self.mount = mount.trim_matches('/').to_owned();
+        self
+    }
+
+    /// Sets the Vault Enterprise namespace of all requests.
+    pub fn with_namespace(mut self, namespace: String) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/v1/{}", self.address, path)
+    }
+
+    fn engine_url(&self, operation: &str) -> String {
+        self.url(&format!("{}/{}/{}", self.mount, operation, self.key_name))
+    }
+
+    async fn token(&self) -> Result<String, VaultTransitError> {
+        match &self.auth {
+            VaultAuth::Token(token) => Ok(token.clone()),
+            VaultAuth::AppRole { role_id, secret_id } => {
+                let request = self
+                    .client
+                    .post(self.url(&format!("auth/{DEFAULT_APPROLE_MOUNT}/login")))
+                    .json(&AppRoleLoginRequest { role_id, secret_id });
+
+                let response: AppRoleLoginResponse = self.send(request).await?;
+                Ok(response.auth.client_token)
+            }
+        }
+    }
+
+    async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, VaultTransitError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = match &self.namespace {
+            Some(namespace) => request.header("X-Vault-Namespace", namespace),
+            None => request,
+        };
+
+        let response = request.send().await.map_err(VaultTransitError::Http)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error: ErrorResponse = response.json().await.map_err(VaultTransitError::Http)?;
+            return Err(VaultTransitError::Vault {
+                status: status.as_u16(),
+                errors: error.errors,
+            });
+        }
+
+        response.json().await.map_err(VaultTransitError::Http)
+    }
+
+    async fn decrypt_key(&self, ciphertext: &str) -> Result<SigningKey, VaultTransitError> {
+        let request = self
+            .client
+            .post(self.engine_url("decrypt"))
+            .header("X-Vault-Token", self.token().await?)
+            .json(&DecryptRequest { ciphertext });
+
+        let response: DataResponse<DecryptResponse> = self.send(request).await?;
+        signing_key_from_encoded_plaintext(response.data.plaintext, |encoded| {
+            base64::decode(encoded)
+        })
+        .ok_or(VaultTransitError::InvalidPlaintext)
+    }
+
+    async fn plugin_public_key(&self) -> Result<VerifyingKey, VaultTransitError> {
+        let request = self
+            .client
+            .get(self.engine_url("keys"))
+            .header("X-Vault-Token", self.token().await?);
+
+        let response: DataResponse<KeyResponse> = self.send(request).await?;
+        let key = response
+            .data
+            .keys
+            .get(&response.data.latest_version.to_string())
+            .ok_or(VaultTransitError::InvalidPublicKey)?;
+
+        FieldElement::from_hex_be(&key.public_key)
+            .map(VerifyingKey::from_scalar)
+            .map_err(|_| VaultTransitError::InvalidPublicKey)
+    }
+
+    async fn plugin_sign(&self, hash: &FieldElement) -> Result<Signature, VaultTransitError> {
+        let request = self
+            .client
+            .post(self.engine_url("sign"))
+            .header("X-Vault-Token", self.token().await?)
+            .json(&SignRequest {
+                input: base64::encode(hash.to_bytes_be()),
+                prehashed: true,
+            });
+
+        let response: DataResponse<SignResponse> = self.send(request).await?;
+        parse_signature(&response.data.signature).ok_or(VaultTransitError::InvalidSignature)
+    }
+}
+
+/// Parses a `vault:v{version}:{base64}` signature wrapping the 32-byte `r` and `s` values.
+fn parse_signature(signature: &str) -> Option<Signature> {
+    let mut parts = signature.splitn(3, ':');
+    if parts.next()? != "vault" || !parts.next()?.starts_with('v') {
+        return None;
+    }
+
+    let bytes = base64::decode(parts.next()?).ok()?;
+    if bytes.len() != 64 {
+        return None;
+    }
+
+    Some(Signature {
+        r: FieldElement::from_byte_slice_be(&bytes[..32]).ok()?,
+        s: FieldElement::from_byte_slice_be(&bytes[32..]).ok()?,
+    })
+}
+
+// Manually implemented to keep the credentials out of logs
+impl std::fmt::Debug for VaultAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token(_) => f.write_str("Token(..)"),
+            Self::AppRole { role_id, .. } => f
+                .debug_struct("AppRole")
+                .field("role_id", role_id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for VaultTransitSigner {
+    type GetPublicKeyError = VaultTransitError;
+    type SignError = VaultTransitError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        match &self.mode {
+            VaultTransitMode::Plugin => self.plugin_public_key().await,
+            VaultTransitMode::WrappedKey { ciphertext } => {
+                Ok(self.decrypt_key(ciphertext).await?.verifying_key())
+            }
+        }
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        match &self.mode {
+            VaultTransitMode::Plugin => self.plugin_sign(hash).await,
+            VaultTransitMode::WrappedKey { ciphertext } => self
+                .decrypt_key(ciphertext)
+                .await?
+                .sign(hash)
+                .map_err(VaultTransitError::EcdsaSignError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_engine_url() {
+        let signer = VaultTransitSigner::new(
+            "https://vault.example.com:8200/".into(),
+            "starknet".into(),
+            VaultAuth::Token("hvs.secret".into()),
+            VaultTransitMode::Plugin,
+        );
+        assert_eq!(
+            signer.engine_url("sign"),
+            "https://vault.example.com:8200/v1/transit/sign/starknet"
+        );
+
+        let signer = signer.with_mount("/stark-transit/".into());
+        assert_eq!(
+            signer.engine_url("keys"),
+            "https://vault.example.com:8200/v1/stark-transit/keys/starknet"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_parse_signature() {
+        let mut bytes = [0u8; 64];
+        bytes[31] = 1;
+        bytes[63] = 2;
+
+        let signature = parse_signature(&format!("vault:v3:{}", base64::encode(bytes))).unwrap();
+        assert_eq!(signature.r, FieldElement::ONE);
+        assert_eq!(signature.s, FieldElement::TWO);
+
+        assert!(parse_signature(&format!("vault:3:{}", base64::encode(bytes))).is_none());
+        assert!(parse_signature(&format!("vault:v3:{}", base64::encode(&bytes[1..]))).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_key_response_deser() {
+        let response: DataResponse<KeyResponse> = serde_json::from_str(
+            r#"{
+                "request_id": "0e5e2528-d2ee-6a3b-e927-a4c2cf5c4e5a",
+                "data": {
+                    "latest_version": 2,
+                    "keys": {
+                        "1": { "public_key": "0x1" },
+                        "2": { "public_key": "0x2" }
+                    },
+                    "name": "starknet",
+                    "type": "stark"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.data.latest_version, 2);
+        assert_eq!(response.data.keys["2"].public_key, "0x2");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_error_response_deser() {
+        let error: ErrorResponse =
+            serde_json::from_str(r#"{"errors":["1 error occurred:\n\t* permission denied\n\n"]}"#)
+                .unwrap();
+
+        assert_eq!(
+            error.errors,
+            vec!["1 error occurred:\n\t* permission denied\n\n"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_auth_debug_hides_secrets() {
+        let token = VaultAuth::Token("hvs.secret".into());
+        let app_role = VaultAuth::AppRole {
+            role_id: "signer".into(),
+            secret_id: "secret".into(),
+        };
+
+        assert_eq!(format!("{token:?}"), "Token(..)");
+        assert_eq!(
+            format!("{app_role:?}"),
+            r#"AppRole { role_id: "signer", .. }"#
+        );
+    }
+}
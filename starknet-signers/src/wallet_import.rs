@@ -0,0 +1,205 @@
+//! Recovering the account keys of the Argent X and Braavos browser wallets.
+//!
+//! Both wallets derive the key of the account at `index` at [DerivationPath::starknet] and turn
+//! it into a Stark private key with [grind_key], but from different BIP-32 seeds:
+//!
+//! - Braavos uses the BIP-39 seed of its recovery phrase, same as [MnemonicWallet].
+//! - Argent X uses the Ethereum private key at `m/44'/60'/0'/0/0` of its recovery phrase as the
+//!   seed. That key is also what the encrypted backup file downloaded from the extension holds.
+
+use crate::{
+    keystore, mnemonic::derive_secp256k1_key, DerivationPath, KeystoreError, MnemonicError,
+    MnemonicWallet, SigningKey,
+};
+
+use starknet_crypto::grind_key;
+use std::path::Path;
+use zeroize::Zeroize;
+
+/// The secret of an Argent X wallet, from which the keys of all its accounts are derived.
+pub struct ArgentXBackup {
+    secret: [u8; 32],
+}
+
+/// The recovery phrase of a Braavos wallet, from which the keys of all its accounts are derived.
+#[derive(Debug, Clone)]
+pub struct BraavosBackup {
+    wallet: MnemonicWallet,
+}
+
+impl ArgentXBackup {
+    /// Loads an Argent X backup file, which is an encrypted keystore in the Web3 Secret Storage
+    /// format as created by ethers.js.
+    pub fn from_backup_file<P>(path: P, password: &str) -> Result<Self, KeystoreError>
+    where
+        P: AsRef<Path>,
+    {
+        let json = std::fs::read_to_string(path).map_err(KeystoreError::Io)?;
+        Self::from_backup_json(&json, password)
+    }
+
+    /// Same as [from_backup_file](Self::from_backup_file), but with the content of the file.
+    pub fn from_backup_json(json: &str, password: &str) -> Result<Self, KeystoreError> {
+        Ok(Self {
+            secret: keystore::decrypt(json, password)?,
+        })
+    }
+
+    /// Recovers the wallet from its English BIP-39 recovery phrase.
+    pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        let wallet = MnemonicWallet::from_phrase(phrase)?;
+        Ok(Self {
+            secret: wallet.derive_secp256k1(&DerivationPath::ethereum(0)),
+        })
+    }
+
+    /// Derives the key of the account at `index`.
+    pub fn derive(&self, index: u32) -> SigningKey {
+        // Argent X passes the secret to BIP-32 as a minimal hex string, which drops leading zeros
+        let seed_start = self
+            .secret
+            .iter()
+            .position(|byte| *byte != 0)
+            .unwrap_or(self.secret.len());
+
+        let mut secp256k1_key = derive_secp256k1_key(
+            &self.secret[seed_start..],
+            DerivationPath::starknet(index).indices(),
+        );
+        let key = SigningKey::from_secret_scalar(grind_key(&secp256k1_key));
+        secp256k1_key.zeroize();
+
+        key
+    }
+}
+
+impl BraavosBackup {
+    /// Recovers the wallet from its English BIP-39 recovery phrase.
+    pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        Ok(Self {
+            wallet: MnemonicWallet::from_phrase(phrase)?,
+        })
+    }
+
+    /// Derives the key of the account at `index`.
+    pub fn derive(&self, index: u32) -> SigningKey {
+        self.wallet.derive(&DerivationPath::starknet(index))
+    }
+}
+
+impl Drop for ArgentXBackup {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+// Manually implemented to keep the secret out of logs
+impl std::fmt::Debug for ArgentXBackup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgentXBackup").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::types::FieldElement;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // Encrypts the Ethereum key of `PHRASE` with password `argent`, as exported by the extension
+    const BACKUP_JSON: &str = r#"{
+        "address": "9858effd232b4033e47d90003d41ec34ecaeda94",
+        "id": "0f3b4c4e-5d2c-4a47-9f0e-3c3f2ab0e6f1",
+        "version": 3,
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {
+                "iv": "101112131415161718191a1b1c1d1e1f"
+            },
+            "ciphertext": "58f63c6e36c3bd9f783d8b0c1e03d711b62eb4e80b77cf9db6a63d750072a6fd",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+                "n": 1024,
+                "dklen": 64,
+                "p": 1,
+                "r": 8
+            },
+            "mac": "f64d136acf162abdad60f924cba3466652a9ebf38f583b846c581023eb24441b"
+        },
+        "x-ethers": {
+            "client": "ethers.js",
+            "mnemonicCounter": "00112233445566778899aabbccddeeff",
+            "mnemonicCiphertext": "000102030405060708090a0b0c0d0e0f",
+            "path": "m/44'/60'/0'/0/0",
+            "locale": "en",
+            "version": "0.1"
+        }
+    }"#;
+
+    fn argent_x_keys() -> [FieldElement; 2] {
+        [
+            FieldElement::from_hex_be(
+                "018a556cbd949d1e6d25ed391bf032559fb6055f321c3e02714f7a6268bff3d1",
+            )
+            .unwrap(),
+            FieldElement::from_hex_be(
+                "00d0be385d5735a38651e3c5bea440321f5d36468a52057801ae0cb3dbb4876c",
+            )
+            .unwrap(),
+        ]
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_argent_x_from_phrase() {
+        let backup = ArgentXBackup::from_phrase(PHRASE).unwrap();
+
+        for (index, expected) in argent_x_keys().iter().enumerate() {
+            assert_eq!(backup.derive(index as u32).secret_scalar(), *expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_argent_x_from_backup_json() {
+        let backup = ArgentXBackup::from_backup_json(BACKUP_JSON, "argent").unwrap();
+
+        for (index, expected) in argent_x_keys().iter().enumerate() {
+            assert_eq!(backup.derive(index as u32).secret_scalar(), *expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_argent_x_wrong_password() {
+        assert!(matches!(
+            ArgentXBackup::from_backup_json(BACKUP_JSON, "wrong"),
+            Err(KeystoreError::MacMismatch)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_braavos_from_phrase() {
+        let backup = BraavosBackup::from_phrase(PHRASE).unwrap();
+
+        assert_eq!(
+            backup.derive(0).secret_scalar(),
+            FieldElement::from_hex_be(
+                "001b8e16cdf31892c56c0370f0e4ca0da096ef4e0c81007b3ba10b11452f8971"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            backup.derive(1).secret_scalar(),
+            FieldElement::from_hex_be(
+                "06d582b352685f7c37a2faa748536c741c3a8c660cb011bce57457a32cd04d1a"
+            )
+            .unwrap()
+        );
+    }
+}
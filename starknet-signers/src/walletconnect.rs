@@ -0,0 +1,517 @@
+//! Remote signing with mobile wallets over WalletConnect v2.
+//!
+//! This module implements the Starknet side of the protocol: pairing URIs, the session proposal
+//! namespace, session accounts and the `starknet_signTypedData` and
+//! `starknet_requestAddInvokeTransaction` requests. The relay connection itself (websocket
+//! transport, envelope encryption and relay authentication) is provided by implementing
+//! [WalletConnectRelay], typically on top of an existing WalletConnect client.
+//!
+//! No relay client is bundled, so this module has no dependencies of its own and isn't behind a
+//! feature. Bring your own transport by forwarding [SessionRequest]s to the session of the
+//! WalletConnect SDK your application already uses.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet_core::{
+    types::FieldElement,
+    utils::{cairo_short_string_to_felt, parse_cairo_short_string},
+};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// The CAIP-2 namespace of Starknet chains.
+pub const STARKNET_NAMESPACE: &str = "starknet";
+
+pub const METHOD_SIGN_TYPED_DATA: &str = "starknet_signTypedData";
+pub const METHOD_REQUEST_ADD_INVOKE_TRANSACTION: &str = "starknet_requestAddInvokeTransaction";
+
+const SESSION_EVENTS: [&str; 2] = ["chainChanged", "accountsChanged"];
+
+/// Sends session requests to the wallet through an established WalletConnect session.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait WalletConnectRelay {
+    type Error: Error + Send;
+
+    /// Publishes a `wc_sessionRequest` on `session_topic` and waits for the wallet to respond.
+    /// Returns the `result` of the JSON-RPC response.
+    async fn session_request(
+        &self,
+        session_topic: &str,
+        chain_id: &str,
+        request: &SessionRequest,
+    ) -> Result<Value, WalletConnectError<Self::Error>>;
+}
+
+/// The `request` field of `wc_sessionRequest` params.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRequest {
+    pub method: String,
+    pub params: Value,
+}
+
+/// Proxies signing to a wallet connected through a WalletConnect session. Every request must be
+/// approved by the user in the wallet.
+///
+/// Wallets don't sign raw hashes over WalletConnect, so this can't be used as a
+/// [Signer](crate::Signer). Instead, typed data is signed with
+/// [sign_typed_data](Self::sign_typed_data), and transactions are signed and broadcast by the
+/// wallet itself with [request_add_invoke_transaction](Self::request_add_invoke_transaction).
+#[derive(Debug, Clone)]
+pub struct WalletConnectSigner<R> {
+    relay: R,
+    session_topic: String,
+    account: SessionAccount,
+}
+
+/// A pairing URI as shown in QR codes, e.g. `wc:{topic}@2?relay-protocol=irn&symKey={key}`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PairingUri {
+    pub topic: String,
+    pub sym_key: [u8; 32],
+    pub relay_protocol: String,
+    pub relay_data: Option<String>,
+}
+
+/// An account approved in a session, in the CAIP-10 format of `starknet:{chain}:{address}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionAccount {
+    pub chain_id: FieldElement,
+    pub address: FieldElement,
+}
+
+/// A call in `starknet_requestAddInvokeTransaction`. Wallets expect entry point names instead of
+/// selectors so that they can be displayed to users.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCall {
+    #[serde(serialize_with = "serialize_felt_hex")]
+    pub contract_address: FieldElement,
+    pub entrypoint: String,
+    #[serde(serialize_with = "serialize_felt_hex_vec")]
+    pub calldata: Vec<FieldElement>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WalletConnectError<T> {
+    #[error(transparent)]
+    Relay(T),
+    #[error("wallet error {code}: {message}")]
+    Wallet { code: i64, message: String },
+    #[error("unexpected response from wallet")]
+    InvalidResponse,
+    #[error("chain ID is not a valid short string")]
+    InvalidChainId,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseWalletConnectError {
+    #[error("invalid pairing URI")]
+    InvalidPairingUri,
+    #[error("invalid CAIP-10 account")]
+    InvalidAccount,
+    #[error("invalid chain ID")]
+    InvalidChainId,
+}
+
+#[derive(Deserialize)]
+struct SignTypedDataResponse {
+    signature: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct AddInvokeTransactionResponse {
+    transaction_hash: String,
+}
+
+impl<R> WalletConnectSigner<R>
+where
+    R: WalletConnectRelay,
+{
+    pub fn new(relay: R, session_topic: String, account: SessionAccount) -> Self {
+        Self {
+            relay,
+            session_topic,
+            account,
+        }
+    }
+
+    pub fn account(&self) -> &SessionAccount {
+        &self.account
+    }
+
+    /// Asks the wallet to sign a SNIP-12 typed data message, returning the signature as produced
+    /// by the account contract.
+    pub async fn sign_typed_data(
+        &self,
+        typed_data: Value,
+    ) -> Result<Vec<FieldElement>, WalletConnectError<R::Error>> {
+        let result = self
+            .request(
+                METHOD_SIGN_TYPED_DATA,
+                json!({
+                    "accountAddress": format!("{:#064x}", self.account.address),
+                    "typedData": typed_data,
+                }),
+            )
+            .await?;
+
+        let response: SignTypedDataResponse =
+            serde_json::from_value(result).map_err(|_| WalletConnectError::InvalidResponse)?;
+        response
+            .signature
+            .iter()
+            .map(|item| parse_felt(item))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(WalletConnectError::InvalidResponse)
+    }
+
+    /// Asks the wallet to sign and broadcast an `INVOKE` transaction, returning its hash.
+    pub async fn request_add_invoke_transaction(
+        &self,
+        calls: &[WalletCall],
+    ) -> Result<FieldElement, WalletConnectError<R::Error>> {
+        let result = self
+            .request(
+                METHOD_REQUEST_ADD_INVOKE_TRANSACTION,
+                json!({
+                    "accountAddress": format!("{:#064x}", self.account.address),
+                    "executionRequest": {
+                        "calls": calls,
+                    },
+                }),
+            )
+            .await?;
+
+        let response: AddInvokeTransactionResponse =
+            serde_json::from_value(result).map_err(|_| WalletConnectError::InvalidResponse)?;
+        parse_felt(&response.transaction_hash).ok_or(WalletConnectError::InvalidResponse)
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, WalletConnectError<R::Error>> {
+        self.relay
+            .session_request(
+                &self.session_topic,
+                &caip2_chain_id(self.account.chain_id)
+                    .map_err(|_| WalletConnectError::InvalidChainId)?,
+                &SessionRequest {
+                    method: method.to_owned(),
+                    params,
+                },
+            )
+            .await
+    }
+}
+
+/// The `requiredNamespaces` of a session proposal requesting Starknet accounts on `chain_ids`.
+pub fn required_namespaces(chain_ids: &[FieldElement]) -> Result<Value, ParseWalletConnectError> {
+    let chains = chain_ids
+        .iter()
+        .map(|chain_id| caip2_chain_id(*chain_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(json!({
+        STARKNET_NAMESPACE: {
+            "chains": chains,
+            "methods": [METHOD_SIGN_TYPED_DATA, METHOD_REQUEST_ADD_INVOKE_TRANSACTION],
+            "events": SESSION_EVENTS,
+        }
+    }))
+}
+
+/// Extracts the Starknet accounts from the `namespaces` of a settled session.
+pub fn session_accounts(
+    namespaces: &Value,
+) -> Result<Vec<SessionAccount>, ParseWalletConnectError> {
+    let accounts = match namespaces[STARKNET_NAMESPACE]["accounts"].as_array() {
+        Some(accounts) => accounts,
+        None => return Ok(vec![]),
+    };
+
+    accounts
+        .iter()
+        .map(|account| {
+            account
+                .as_str()
+                .ok_or(ParseWalletConnectError::InvalidAccount)?
+                .parse()
+        })
+        .collect()
+}
+
+/// Formats a chain ID as CAIP-2, e.g. `starknet:SN_MAIN`.
+fn caip2_chain_id(chain_id: FieldElement) -> Result<String, ParseWalletConnectError> {
+    let reference =
+        parse_cairo_short_string(&chain_id).map_err(|_| ParseWalletConnectError::InvalidChainId)?;
+    Ok(format!("{STARKNET_NAMESPACE}:{reference}"))
+}
+
+fn parse_felt(value: &str) -> Option<FieldElement> {
+    if value.starts_with("0x") {
+        FieldElement::from_hex_be(value).ok()
+    } else {
+        FieldElement::from_dec_str(value).ok()
+    }
+}
+
+impl FromStr for SessionAccount {
+    type Err = ParseWalletConnectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(STARKNET_NAMESPACE), Some(reference), Some(address), None) => Ok(Self {
+                chain_id: cairo_short_string_to_felt(reference)
+                    .map_err(|_| ParseWalletConnectError::InvalidChainId)?,
+                address: FieldElement::from_hex_be(address)
+                    .map_err(|_| ParseWalletConnectError::InvalidAccount)?,
+            }),
+            _ => Err(ParseWalletConnectError::InvalidAccount),
+        }
+    }
+}
+
+impl FromStr for PairingUri {
+    type Err = ParseWalletConnectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (topic, rest) = s
+            .strip_prefix("wc:")
+            .and_then(|rest| rest.split_once('@'))
+            .ok_or(ParseWalletConnectError::InvalidPairingUri)?;
+        let (version, query) = rest
+            .split_once('?')
+            .ok_or(ParseWalletConnectError::InvalidPairingUri)?;
+        if version != "2" || topic.is_empty() {
+            return Err(ParseWalletConnectError::InvalidPairingUri);
+        }
+
+        let mut sym_key = None;
+        let mut relay_protocol = None;
+        let mut relay_data = None;
+        for pair in query.split('&') {
+            match pair.split_once('=') {
+                Some(("symKey", value)) => {
+                    let mut key = [0u8; 32];
+                    hex::decode_to_slice(value, &mut key)
+                        .map_err(|_| ParseWalletConnectError::InvalidPairingUri)?;
+                    sym_key = Some(key);
+                }
+                Some(("relay-protocol", value)) => relay_protocol = Some(value.to_owned()),
+                Some(("relay-data", value)) => relay_data = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            topic: topic.to_owned(),
+            sym_key: sym_key.ok_or(ParseWalletConnectError::InvalidPairingUri)?,
+            relay_protocol: relay_protocol.ok_or(ParseWalletConnectError::InvalidPairingUri)?,
+            relay_data,
+        })
+    }
+}
+
+impl Display for PairingUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "wc:{}@2?relay-protocol={}&symKey={}",
+            self.topic,
+            self.relay_protocol,
+            hex::encode(self.sym_key)
+        )?;
+        if let Some(relay_data) = &self.relay_data {
+            write!(f, "&relay-data={relay_data}")?;
+        }
+        Ok(())
+    }
+}
+
+// Manually implemented to keep the key out of logs
+impl std::fmt::Debug for PairingUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PairingUri")
+            .field("topic", &self.topic)
+            .field("relay_protocol", &self.relay_protocol)
+            .finish_non_exhaustive()
+    }
+}
+
+fn serialize_felt_hex<S>(value: &FieldElement, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&format!("{value:#x}"))
+}
+
+fn serialize_felt_hex_vec<S>(value: &[FieldElement], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(value.iter().map(|item| format!("{item:#x}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use starknet_core::chain_id;
+    use std::sync::Mutex;
+
+    const PAIRING_URI: &str = "wc:7f6e504bfad60b485450578e05678ed3e8e8c4751d3c6160be17160d63ec90f9@2\
+        ?relay-protocol=irn&symKey=587d5484ce2a2a6ee3ba1962fdd7e8588e06200c46823bd18fbd67def96ad303";
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock relay error")]
+    struct MockRelayError;
+
+    /// Records requests and replies with a fixed result.
+    struct MockRelay {
+        result: Value,
+        requests: Mutex<Vec<(String, String, SessionRequest)>>,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    impl WalletConnectRelay for MockRelay {
+        type Error = MockRelayError;
+
+        async fn session_request(
+            &self,
+            session_topic: &str,
+            chain_id: &str,
+            request: &SessionRequest,
+        ) -> Result<Value, WalletConnectError<Self::Error>> {
+            self.requests.lock().unwrap().push((
+                session_topic.to_owned(),
+                chain_id.to_owned(),
+                request.clone(),
+            ));
+            Ok(self.result.clone())
+        }
+    }
+
+    fn mock_signer(result: Value) -> WalletConnectSigner<MockRelay> {
+        WalletConnectSigner::new(
+            MockRelay {
+                result,
+                requests: Mutex::new(vec![]),
+            },
+            "session".into(),
+            SessionAccount {
+                chain_id: chain_id::TESTNET,
+                address: FieldElement::from_hex_be("0x1234").unwrap(),
+            },
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_pairing_uri_round_trip() {
+        let uri: PairingUri = PAIRING_URI.parse().unwrap();
+
+        assert_eq!(
+            uri.topic,
+            "7f6e504bfad60b485450578e05678ed3e8e8c4751d3c6160be17160d63ec90f9"
+        );
+        assert_eq!(uri.relay_protocol, "irn");
+        assert_eq!(uri.sym_key[0], 0x58);
+        assert_eq!(uri.to_string(), PAIRING_URI);
+
+        assert!("wc:topic@1?relay-protocol=irn&symKey=00"
+            .parse::<PairingUri>()
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_session_namespaces() {
+        assert_eq!(
+            required_namespaces(&[chain_id::MAINNET, chain_id::TESTNET]).unwrap(),
+            json!({
+                "starknet": {
+                    "chains": ["starknet:SN_MAIN", "starknet:SN_GOERLI"],
+                    "methods": [
+                        "starknet_signTypedData",
+                        "starknet_requestAddInvokeTransaction"
+                    ],
+                    "events": ["chainChanged", "accountsChanged"]
+                }
+            })
+        );
+
+        let accounts = session_accounts(&json!({
+            "starknet": {
+                "accounts": ["starknet:SN_MAIN:0x0123"],
+                "methods": ["starknet_signTypedData"],
+                "events": []
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            accounts,
+            vec![SessionAccount {
+                chain_id: chain_id::MAINNET,
+                address: FieldElement::from_hex_be("0x123").unwrap(),
+            }]
+        );
+
+        assert!("eip155:1:0x0123".parse::<SessionAccount>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_typed_data() {
+        let signer = mock_signer(json!({ "signature": ["0x1", "2"] }));
+
+        let signature = signer
+            .sign_typed_data(json!({ "primaryType": "Mail" }))
+            .await
+            .unwrap();
+        assert_eq!(signature, vec![FieldElement::ONE, FieldElement::TWO]);
+
+        let requests = signer.relay.requests.lock().unwrap();
+        let (topic, chain_id, request) = &requests[0];
+        assert_eq!(topic, "session");
+        assert_eq!(chain_id, "starknet:SN_GOERLI");
+        assert_eq!(request.method, "starknet_signTypedData");
+        assert_eq!(
+            request.params["accountAddress"],
+            "0x0000000000000000000000000000000000000000000000000000000000001234"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_add_invoke_transaction() {
+        let signer = mock_signer(json!({ "transaction_hash": "0xabc" }));
+
+        let transaction_hash = signer
+            .request_add_invoke_transaction(&[WalletCall {
+                contract_address: FieldElement::from_hex_be("0x49d").unwrap(),
+                entrypoint: "transfer".into(),
+                calldata: vec![FieldElement::ONE, FieldElement::TWO],
+            }])
+            .await
+            .unwrap();
+        assert_eq!(
+            transaction_hash,
+            FieldElement::from_hex_be("0xabc").unwrap()
+        );
+
+        let requests = signer.relay.requests.lock().unwrap();
+        assert_eq!(
+            requests[0].2.params["executionRequest"],
+            json!({
+                "calls": [{
+                    "contractAddress": "0x49d",
+                    "entrypoint": "transfer",
+                    "calldata": ["0x1", "0x2"]
+                }]
+            })
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::secp256k1::u256_to_felts;
+
+use starknet_core::types::FieldElement;
+
+/// A WebAuthn assertion produced by an authenticator for a secp256r1 credential, as verified
+/// on-chain by Cartridge Controller-style accounts.
+#[derive(Debug, Clone)]
+pub struct WebauthnAssertion {
+    /// Raw `authenticatorData` bytes.
+    pub authenticator_data: Vec<u8>,
+    /// Raw `clientDataJSON` bytes.
+    pub client_data_json: Vec<u8>,
+    /// Big-endian `r` of the secp256r1 signature.
+    pub r: [u8; 32],
+    /// Big-endian `s` of the secp256r1 signature.
+    pub s: [u8; 32],
+    /// Parity of the `y` coordinate of the signature point `R`.
+    pub y_parity: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebauthnEncodingError {
+    #[error("field `{0}` not found in client data JSON")]
+    MissingClientDataField(&'static str),
+}
+
+impl WebauthnAssertion {
+    /// Encodes the assertion into the felt array layout of the `WebauthnAssertion` Cairo struct:
+    ///
+    /// ```text
+    /// authenticator_data: Array<u8>,
+    /// client_data_json: Array<u8>,
+    /// signature: (r: u256, s: u256, y_parity: bool),
+    /// type_offset: usize,
+    /// challenge_offset: usize,
+    /// challenge_length: usize,
+    /// origin_offset: usize,
+    /// origin_length: usize,
+    /// ```
+    ///
+    /// Offsets point to the first byte of the corresponding string value in `client_data_json`.
+    pub fn to_felts(&self) -> Result<Vec<FieldElement>, WebauthnEncodingError> {
+        let (type_offset, _) = find_json_string(&self.client_data_json, "type")?;
+        let (challenge_offset, challenge_length) =
+            find_json_string(&self.client_data_json, "challenge")?;
+        let (origin_offset, origin_length) = find_json_string(&self.client_data_json, "origin")?;
+
+        let mut felts = vec![];
+
+        felts.push(self.authenticator_data.len().into());
+        felts.extend(
+            self.authenticator_data
+                .iter()
+                .map(|byte| FieldElement::from(*byte)),
+        );
+
+        felts.push(self.client_data_json.len().into());
+        felts.extend(
+            self.client_data_json
+                .iter()
+                .map(|byte| FieldElement::from(*byte)),
+        );
+
+        felts.extend_from_slice(&u256_to_felts(&self.r));
+        felts.extend_from_slice(&u256_to_felts(&self.s));
+        felts.push(if self.y_parity {
+            FieldElement::ONE
+        } else {
+            FieldElement::ZERO
+        });
+
+        felts.push(type_offset.into());
+        felts.push(challenge_offset.into());
+        felts.push(challenge_length.into());
+        felts.push(origin_offset.into());
+        felts.push(origin_length.into());
+
+        Ok(felts)
+    }
+}
+
+/// Finds the offset and length of the string value of `key` in a serialized JSON object.
+fn find_json_string(
+    json: &[u8],
+    key: &'static str,
+) -> Result<(usize, usize), WebauthnEncodingError> {
+    let pattern = format!("\"{key}\":\"");
+    let pattern = pattern.as_bytes();
+
+    let offset = json
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|position| position + pattern.len())
+        .ok_or(WebauthnEncodingError::MissingClientDataField(key))?;
+    let length = json[offset..]
+        .iter()
+        .position(|byte| *byte == b'"')
+        .ok_or(WebauthnEncodingError::MissingClientDataField(key))?;
+
+    Ok((offset, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_webauthn_assertion_encoding() {
+        let client_data_json =
+            br#"{"type":"webauthn.get","challenge":"AAEC","origin":"https://x.cartridge.gg"}"#;
+
+        let mut r = [0u8; 32];
+        r[15] = 1;
+        r[31] = 2;
+        let mut s = [0u8; 32];
+        s[31] = 3;
+
+        let assertion = WebauthnAssertion {
+            authenticator_data: vec![0xaa, 0xbb],
+            client_data_json: client_data_json.to_vec(),
+            r,
+            s,
+            y_parity: true,
+        };
+
+        let felts = assertion.to_felts().unwrap();
+        let json_len = client_data_json.len();
+
+        assert_eq!(felts[0], FieldElement::TWO);
+        assert_eq!(felts[1], FieldElement::from(0xaau8));
+        assert_eq!(felts[3], json_len.into());
+
+        let tail = &felts[4 + json_len..];
+        assert_eq!(
+            tail,
+            &[
+                FieldElement::TWO,   // r.low
+                FieldElement::ONE,   // r.high
+                FieldElement::THREE, // s.low
+                FieldElement::ZERO,  // s.high
+                FieldElement::ONE,   // y_parity
+                FieldElement::from(9u8),
+                FieldElement::from(36u8),
+                FieldElement::from(4u8),
+                FieldElement::from(52u8),
+                FieldElement::from(22u8),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn test_webauthn_missing_field() {
+        let assertion = WebauthnAssertion {
+            authenticator_data: vec![],
+            client_data_json: br#"{"type":"webauthn.get"}"#.to_vec(),
+            r: [0u8; 32],
+            s: [0u8; 32],
+            y_parity: false,
+        };
+
+        assert!(matches!(
+            assertion.to_felts(),
+            Err(WebauthnEncodingError::MissingClientDataField("challenge"))
+        ));
+    }
+}
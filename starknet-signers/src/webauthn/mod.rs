@@ -0,0 +1,12 @@
+//! Signing with passkeys and other FIDO2 authenticators for WebAuthn-based account contracts.
+
+mod assertion;
+pub use assertion::{WebauthnAssertion, WebauthnEncodingError};
+
+#[cfg(feature = "webauthn")]
+mod signer;
+#[cfg(feature = "webauthn")]
+pub use signer::{
+    challenge_from_hash, client_data_json, AssertionRequest, AuthenticatorResponse,
+    Secp256r1PublicKey, WebauthnAuthenticator, WebauthnSigner, WebauthnSignerError,
+};
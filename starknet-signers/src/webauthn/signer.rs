@@ -0,0 +1,323 @@
+use super::WebauthnAssertion;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use starknet_core::types::FieldElement;
+use starknet_crypto::secp256::{
+    secp256_is_valid_signature, secp256_recover_public_key, Secp256Point, Secp256r1,
+};
+use std::error::Error;
+
+/// Access to an authenticator holding a secp256r1 credential, e.g. through
+/// `navigator.credentials.get()` in browsers or CTAP2 for security keys.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait WebauthnAuthenticator {
+    type Error: Error + Send;
+
+    /// Gets an assertion over `request.challenge`. Implementations talking to authenticators
+    /// directly can build the client data with [client_data_json].
+    async fn get_assertion(
+        &self,
+        request: &AssertionRequest,
+    ) -> Result<AuthenticatorResponse, Self::Error>;
+}
+
+#[derive(Debug, Clone)]
+pub struct AssertionRequest {
+    pub rp_id: String,
+    pub credential_id: Vec<u8>,
+    pub challenge: Vec<u8>,
+}
+
+/// The fields of an `AuthenticatorAssertionResponse`.
+#[derive(Debug, Clone)]
+pub struct AuthenticatorResponse {
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+    /// DER-encoded ECDSA signature over `authenticator_data || sha256(client_data_json)`.
+    pub signature: Vec<u8>,
+}
+
+/// A secp256r1 public key as found in the COSE key of a credential.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Secp256r1PublicKey {
+    pub x: [u8; 32],
+    pub y: [u8; 32],
+}
+
+/// Signs transaction hashes by requesting assertions for a WebAuthn credential. The challenge
+/// is the 32-byte big-endian transaction hash, which account contracts check against the
+/// `challenge` field of the client data.
+#[derive(Debug)]
+pub struct WebauthnSigner<A> {
+    authenticator: A,
+    rp_id: String,
+    credential_id: Vec<u8>,
+    public_key: Secp256r1PublicKey,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebauthnSignerError<A> {
+    #[error(transparent)]
+    Authenticator(A),
+    #[error("invalid client data: {0}")]
+    InvalidClientData(&'static str),
+    #[error("invalid DER signature")]
+    InvalidSignatureEncoding,
+    #[error("signature is invalid for the credential public key")]
+    InvalidSignature,
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+}
+
+impl<A> WebauthnSigner<A>
+where
+    A: WebauthnAuthenticator + Sync + Send,
+{
+    pub fn new(
+        authenticator: A,
+        rp_id: String,
+        credential_id: Vec<u8>,
+        public_key: Secp256r1PublicKey,
+    ) -> Self {
+        Self {
+            authenticator,
+            rp_id,
+            credential_id,
+            public_key,
+        }
+    }
+
+    pub fn rp_id(&self) -> &str {
+        &self.rp_id
+    }
+
+    pub fn credential_id(&self) -> &[u8] {
+        &self.credential_id
+    }
+
+    pub fn public_key(&self) -> &Secp256r1PublicKey {
+        &self.public_key
+    }
+
+    /// Requests an assertion with [challenge_from_hash] and checks it against the
+    /// credential public key. Requires user presence on the authenticator.
+    pub async fn sign_hash(
+        &self,
+        hash: &FieldElement,
+    ) -> Result<WebauthnAssertion, WebauthnSignerError<A::Error>> {
+        let challenge = challenge_from_hash(hash);
+        let response = self
+            .authenticator
+            .get_assertion(&AssertionRequest {
+                rp_id: self.rp_id.clone(),
+                credential_id: self.credential_id.clone(),
+                challenge: challenge.to_vec(),
+            })
+            .await
+            .map_err(WebauthnSignerError::Authenticator)?;
+
+        let client_data: ClientData = serde_json::from_slice(&response.client_data_json)
+            .map_err(|_| WebauthnSignerError::InvalidClientData("malformed JSON"))?;
+        if client_data.kind != "webauthn.get" {
+            return Err(WebauthnSignerError::InvalidClientData("unexpected type"));
+        }
+        if client_data.challenge != base64_url_encode(&challenge) {
+            return Err(WebauthnSignerError::InvalidClientData("challenge mismatch"));
+        }
+
+        let (r, s) = parse_der_signature(&response.signature)
+            .ok_or(WebauthnSignerError::InvalidSignatureEncoding)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&response.authenticator_data);
+        hasher.update(Sha256::digest(&response.client_data_json));
+        let message_hash: [u8; 32] = hasher.finalize().into();
+
+        let y_parity = nonce_point_parity(&self.public_key, &message_hash, &r, &s)
+            .ok_or(WebauthnSignerError::InvalidSignature)?;
+
+        Ok(WebauthnAssertion {
+            authenticator_data: response.authenticator_data,
+            client_data_json: response.client_data_json,
+            r,
+            s,
+            y_parity,
+        })
+    }
+}
+
+/// The WebAuthn challenge committing to `hash`.
+pub fn challenge_from_hash(hash: &FieldElement) -> [u8; 32] {
+    hash.to_bytes_be()
+}
+
+/// The `clientDataJSON` of an assertion over `challenge` as serialized by browsers, for
+/// authenticators that only sign its hash.
+pub fn client_data_json(challenge: &[u8], origin: &str) -> Vec<u8> {
+    // Field order matters as the signature covers the exact bytes
+    format!(
+        r#"{{"type":"webauthn.get","challenge":"{}","origin":{},"crossOrigin":false}}"#,
+        base64_url_encode(challenge),
+        serde_json::Value::from(origin),
+    )
+    .into_bytes()
+}
+
+/// The parity of the y coordinate of the nonce point of a signature, if it's valid for
+/// `public_key` as verified by account contracts.
+fn nonce_point_parity(
+    public_key: &Secp256r1PublicKey,
+    message_hash: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+) -> Option<bool> {
+    let public_key = Secp256Point::<Secp256r1>::new(&public_key.x, &public_key.y).ok()??;
+    if !secp256_is_valid_signature(message_hash, r, s, &public_key) {
+        return None;
+    }
+
+    [false, true].into_iter().find(|y_parity| {
+        secp256_recover_public_key::<Secp256r1>(message_hash, r, s, *y_parity)
+            .ok()
+            .flatten()
+            .as_ref()
+            == Some(&public_key)
+    })
+}
+
+fn base64_url_encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+/// Parses `SEQUENCE { INTEGER r, INTEGER s }` into fixed-size big-endian values.
+fn parse_der_signature(der: &[u8]) -> Option<([u8; 32], [u8; 32])> {
+    fn parse_integer(data: &[u8]) -> Option<([u8; 32], &[u8])> {
+        let (&tag, data) = data.split_first()?;
+        let (&len, data) = data.split_first()?;
+        if tag != 0x02 || data.len() < len as usize {
+            return None;
+        }
+        let (value, rest) = data.split_at(len as usize);
+
+        // Strip the sign byte added when the highest bit is set
+        let value = match value {
+            [0, rest @ ..] => rest,
+            value => value,
+        };
+        if value.len() > 32 {
+            return None;
+        }
+
+        let mut integer = [0u8; 32];
+        integer[(32 - value.len())..].copy_from_slice(value);
+        Some((integer, rest))
+    }
+
+    match der {
+        [0x30, len, body @ ..] if *len as usize == body.len() => {
+            let (r, rest) = parse_integer(body)?;
+            let (s, rest) = parse_integer(rest)?;
+            rest.is_empty().then_some((r, s))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replays a response recorded from a software authenticator.
+    struct MockAuthenticator;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock authenticator error")]
+    struct MockError;
+
+    #[async_trait]
+    impl WebauthnAuthenticator for MockAuthenticator {
+        type Error = MockError;
+
+        async fn get_assertion(
+            &self,
+            request: &AssertionRequest,
+        ) -> Result<AuthenticatorResponse, Self::Error> {
+            assert_eq!(request.rp_id, "cartridge.gg");
+
+            Ok(AuthenticatorResponse {
+                authenticator_data: hex::decode(
+                    "49960de5880e8c687434170f6476605b8fe4aeb9a28632c7995cf3ba831d9763\
+                    0500000001",
+                )
+                .unwrap(),
+                client_data_json: client_data_json(&request.challenge, "https://x.cartridge.gg"),
+                signature: hex::decode(
+                    "304502206ce3d5789b6e1ced55566bd73c3b4fef2ed9ec7636e209b7f9746821740701a9\
+                    022100ec99804b2213304a9089b228c920f90187d58e8de7690114a96c49ec0e152fb5",
+                )
+                .unwrap(),
+            })
+        }
+    }
+
+    fn signer() -> WebauthnSigner<MockAuthenticator> {
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        hex::decode_to_slice(
+            "5f928baba50e4d9cad7801756ddb033bc6be91a66b4fc6e8476fcf659426df25",
+            &mut x,
+        )
+        .unwrap();
+        hex::decode_to_slice(
+            "3943875dc69342e08b6f5c384877e0686a0e2482babc8c34c25a9a081eecf518",
+            &mut y,
+        )
+        .unwrap();
+
+        WebauthnSigner::new(
+            MockAuthenticator,
+            "cartridge.gg".into(),
+            vec![1, 2, 3],
+            Secp256r1PublicKey { x, y },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash() {
+        let assertion = signer()
+            .sign_hash(&FieldElement::from_hex_be("0x1234abcd").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(assertion.client_data_json).unwrap(),
+            r#"{"type":"webauthn.get","challenge":"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABI0q80","origin":"https://x.cartridge.gg","crossOrigin":false}"#
+        );
+        assert_eq!(
+            hex::encode(assertion.r),
+            "6ce3d5789b6e1ced55566bd73c3b4fef2ed9ec7636e209b7f9746821740701a9"
+        );
+        assert_eq!(
+            hex::encode(assertion.s),
+            "ec99804b2213304a9089b228c920f90187d58e8de7690114a96c49ec0e152fb5"
+        );
+        assert!(assertion.y_parity);
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_rejects_invalid_signature() {
+        // Signature doesn't match the client data for this challenge
+        assert!(matches!(
+            signer().sign_hash(&FieldElement::ONE).await,
+            Err(WebauthnSignerError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{Signer, SigningKey, VerifyingKey};
+
+use async_trait::async_trait;
+use starknet_core::{
+    crypto::{EcdsaSignError, Signature},
+    types::FieldElement,
+};
+use starknet_crypto::grind_key;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+const DEFAULT_PROGRAM: &str = "ykchalresp";
+const HMAC_SHA1_RESPONSE_LEN: usize = 20;
+
+/// A signer whose Stark private key is derived from the HMAC-SHA1 challenge-response of a
+/// YubiKey OTP slot. The slot secret never leaves the device, and the same challenge always
+/// yields the same key, which is obtained by grinding the response with [grind_key].
+///
+/// As PIV doesn't support the Stark curve, PIV-backed keys are instead available through
+/// `Pkcs11Signer` (behind the `pkcs11` feature) with the `ykcs11` module in wrapped mode.
+///
+/// The device is accessed through the `ykchalresp` tool from `yubikey-personalization`, which must
+/// be installed separately, and is run with `tokio::process` so it requires a Tokio runtime.
+/// Slots configured with `ykman otp chalresp --touch` blink and wait for a touch before
+/// responding.
+#[derive(Debug)]
+pub struct YubiKeySigner {
+    program: PathBuf,
+    slot: YubiKeySlot,
+    challenge: Vec<u8>,
+    touch_policy: TouchPolicy,
+    requires_touch: bool,
+    cached_key: Option<SigningKey>,
+}
+
+/// The OTP slot programmed for HMAC-SHA1 challenge-response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YubiKeySlot {
+    /// The slot activated by a short touch.
+    Short,
+    /// The slot activated by a long touch.
+    Long,
+}
+
+/// When the YubiKey is challenged, which for touch-protected slots is also when a touch is
+/// required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPolicy {
+    /// Challenges the YubiKey for every signature and public key request. The derived key is
+    /// discarded right after use.
+    Always,
+    /// Challenges the YubiKey once when the signer is created and keeps the derived key in
+    /// memory for the lifetime of the signer.
+    Cached,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum YubiKeyError {
+    #[error("failed to run {program}: {source}")]
+    Spawn {
+        program: String,
+        source: std::io::Error,
+    },
+    #[error("challenge-response failed: {0}")]
+    ChallengeResponse(String),
+    #[error("invalid challenge-response output")]
+    InvalidResponse,
+    #[error(transparent)]
+    EcdsaSignError(EcdsaSignError),
+}
+
+impl YubiKeySigner {
+    /// Creates a signer using `ykchalresp` from `PATH`. With [TouchPolicy::Cached], the YubiKey is
+    /// challenged right away.
+    pub async fn new(
+        slot: YubiKeySlot,
+        challenge: Vec<u8>,
+        touch_policy: TouchPolicy,
+    ) -> Result<Self, YubiKeyError> {
+        Self::with_program(DEFAULT_PROGRAM.into(), slot, challenge, touch_policy).await
+    }
+
+    /// Same as [new](Self::new), but with a custom path to `ykchalresp`.
+    pub async fn with_program(
+        program: PathBuf,
+        slot: YubiKeySlot,
+        challenge: Vec<u8>,
+        touch_policy: TouchPolicy,
+    ) -> Result<Self, YubiKeyError> {
+        let mut signer = Self {
+            program,
+            slot,
+            challenge,
+            touch_policy,
+            requires_touch: true,
+            cached_key: None,
+        };
+
+        if touch_policy == TouchPolicy::Cached {
+            signer.cached_key = Some(signer.derive_key().await?);
+        }
+
+        Ok(signer)
+    }
+
+    pub fn touch_policy(&self) -> TouchPolicy {
+        self.touch_policy
+    }
+
+    /// Sets whether the slot was configured to require a touch, which makes signing interactive
+    /// with [TouchPolicy::Always]. Slots are assumed to require a touch by default.
+    pub fn set_requires_touch(&mut self, requires_touch: bool) -> &mut Self {
+        self.requires_touch = requires_touch;
+        self
+    }
+
+    async fn signing_key(&self) -> Result<SigningKey, YubiKeyError> {
+        match &self.cached_key {
+            Some(key) => Ok(key.clone()),
+            None => self.derive_key().await,
+        }
+    }
+
+    async fn derive_key(&self) -> Result<SigningKey, YubiKeyError> {
+        let output = Command::new(&self.program)
+            .arg(match self.slot {
+                YubiKeySlot::Short => "-1",
+                YubiKeySlot::Long => "-2",
+            })
+            .arg("-H")
+            .arg("-x")
+            .arg(hex::encode(&self.challenge))
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|source| YubiKeyError::Spawn {
+                program: self.program.display().to_string(),
+                source,
+            })?;
+
+        if !output.status.success() {
+            return Err(YubiKeyError::ChallengeResponse(
+                String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            ));
+        }
+
+        let response = parse_response(&output.stdout)?;
+        Ok(SigningKey::from_secret_scalar(grind_key(&response)))
+    }
+}
+
+/// Parses the hex-encoded HMAC-SHA1 digest printed by `ykchalresp`.
+fn parse_response(stdout: &[u8]) -> Result<[u8; HMAC_SHA1_RESPONSE_LEN], YubiKeyError> {
+    let stdout = std::str::from_utf8(stdout).map_err(|_| YubiKeyError::InvalidResponse)?;
+    let response = hex::decode(stdout.trim()).map_err(|_| YubiKeyError::InvalidResponse)?;
+
+    response
+        .try_into()
+        .map_err(|_| YubiKeyError::InvalidResponse)
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for YubiKeySigner {
+    type GetPublicKeyError = YubiKeyError;
+    type SignError = YubiKeyError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        Ok(self.signing_key().await?.verifying_key())
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        self.signing_key()
+            .await?
+            .sign(hash)
+            .map_err(YubiKeyError::EcdsaSignError)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.requires_touch && self.touch_policy == TouchPolicy::Always
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            hex::encode(parse_response(b"09f5a9187c3ba1a1bfdd4b3dd9f0a4c9dc29c9bd\n").unwrap()),
+            "09f5a9187c3ba1a1bfdd4b3dd9f0a4c9dc29c9bd"
+        );
+        assert!(parse_response(b"09f5a9187c3ba1a1\n").is_err());
+        assert!(parse_response(b"Yubikey core error: timeout\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_derive_key() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Stands in for `ykchalresp`, echoing the arguments so that they're covered by the key
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("ykchalresp");
+        std::fs::write(
+            &program,
+            "#!/bin/sh\necho \"$@\" | sha1sum | cut -d ' ' -f 1\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // SHA-1 of "-2 -H -x 737461726b6e6574\n"
+        let expected_key =
+            grind_key(&hex::decode("80c14500809eb364e1e1d5bc910bf735ccaa4924").unwrap());
+
+        for touch_policy in [TouchPolicy::Always, TouchPolicy::Cached] {
+            let signer = YubiKeySigner::with_program(
+                program.clone(),
+                YubiKeySlot::Long,
+                b"starknet".to_vec(),
+                touch_policy,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                signer.cached_key.is_some(),
+                touch_policy == TouchPolicy::Cached
+            );
+            assert_eq!(
+                signer.signing_key().await.unwrap().secret_scalar(),
+                expected_key
+            );
+            assert_eq!(signer.is_interactive(), touch_policy == TouchPolicy::Always);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_program() {
+        let result = YubiKeySigner::with_program(
+            "/nonexistent/ykchalresp".into(),
+            YubiKeySlot::Long,
+            b"starknet".to_vec(),
+            TouchPolicy::Cached,
+        )
+        .await;
+        assert!(matches!(result, Err(YubiKeyError::Spawn { .. })));
+
+        // The YubiKey is only challenged on use
+        let mut signer = YubiKeySigner::with_program(
+            "/nonexistent/ykchalresp".into(),
+            YubiKeySlot::Long,
+            b"starknet".to_vec(),
+            TouchPolicy::Always,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            signer.signing_key().await,
+            Err(YubiKeyError::Spawn { .. })
+        ));
+
+        signer.set_requires_touch(false);
+        assert!(!signer.is_interactive());
+    }
+}
@@ -0,0 +1,119 @@
+// Code generated by `starknet_contract::codegen`. Do not edit.
+
+use ::starknet::{
+    contract::{Contract, ContractError, FunctionCall},
+    core::{
+        codec::U256,
+        types::{CallL1Handler, FieldElement, L1Address},
+    },
+    macros::{selector, CairoDeserialize, CairoSerialize, StarknetEvent},
+};
+
+#[derive(Debug, Clone)]
+pub struct Token<P> {
+    contract: Contract<P>,
+}
+
+/// Constructor arguments of [Token], for deploying it with `deploy_with_args`.
+#[derive(Debug, Clone, PartialEq, Eq, CairoSerialize, CairoDeserialize)]
+pub struct TokenConstructor {
+    pub name: FieldElement,
+    pub symbol: FieldElement,
+    pub recipient: FieldElement,
+}
+
+/// Errors [Token] reverts with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    /// Reverted with `ERC20: transfer amount exceeds balance` or `ERC20: insufficient balance`.
+    InsufficientBalance,
+    /// Reverted with `ERC20: insufficient allowance`.
+    InsufficientAllowance,
+}
+
+impl ContractError for TokenError {
+    fn from_revert_reason(reason: &str) -> Option<Self> {
+        match reason {
+            "ERC20: transfer amount exceeds balance" | "ERC20: insufficient balance" => Some(Self::InsufficientBalance),
+            "ERC20: insufficient allowance" => Some(Self::InsufficientAllowance),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CairoSerialize, CairoDeserialize)]
+pub struct Lock {
+    pub owner: FieldElement,
+    pub amount: U256,
+    pub range: (FieldElement, FieldElement),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, StarknetEvent)]
+#[event(name = "Transfer")]
+pub struct Transfer {
+    pub from_: FieldElement,
+    pub to: FieldElement,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, StarknetEvent)]
+#[event(name = "locks_released")]
+pub struct LocksReleased {
+    pub ids: Vec<FieldElement>,
+}
+
+impl<P> Token<P> {
+    pub fn new(address: FieldElement, provider: P) -> Self {
+        Self {
+            contract: Contract::new(address, provider),
+        }
+    }
+
+    pub fn contract(&self) -> &Contract<P> {
+        &self.contract
+    }
+
+    pub fn address(&self) -> FieldElement {
+        self.contract.address()
+    }
+
+    /// View function `name`.
+    pub fn name(&self) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(selector!("name"), &())
+    }
+
+    /// View function `balanceOf`.
+    pub fn balance_of(&self, account: FieldElement) -> FunctionCall<'_, P, U256> {
+        self.contract.function(selector!("balanceOf"), &(account,))
+    }
+
+    /// Function `transfer`.
+    pub fn transfer(
+        &self,
+        recipient: FieldElement,
+        amount: U256,
+    ) -> FunctionCall<'_, P, FieldElement> {
+        self.contract.function(selector!("transfer"), &(recipient, amount))
+    }
+
+    /// View function `getLocks`.
+    pub fn get_locks(&self, ids: &[FieldElement]) -> FunctionCall<'_, P, (Vec<Lock>, U256)> {
+        self.contract.function(selector!("getLocks"), &(ids,))
+    }
+
+    /// Function `lock`.
+    pub fn lock(&self, lock: &Lock) -> FunctionCall<'_, P, ()> {
+        self.contract.function(selector!("lock"), &(lock,))
+    }
+
+    /// Message to L1 handler `handle_deposit`.
+    pub fn handle_deposit_message(
+        &self,
+        from_address: L1Address,
+        account: FieldElement,
+        amount: U256,
+    ) -> CallL1Handler {
+        self.contract
+            .l1_handler(from_address, selector!("handle_deposit"), &(account, amount))
+    }
+}
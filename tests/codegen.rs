@@ -0,0 +1,83 @@
+use starknet::{
+    contract::{codegen::Bindings, ContractError},
+    core::{
+        codec::{CairoSerialize, U256},
+        types::{AbiEntry, FieldElement},
+        utils::get_selector_from_name,
+    },
+    macros::felt,
+};
+
+#[allow(dead_code)]
+mod token {
+    include!("bindings/token.rs");
+}
+
+fn token_abi() -> Vec<AbiEntry> {
+    serde_json::from_str(include_str!("bindings/token.abi.json")).unwrap()
+}
+
+fn token_bindings() -> Bindings {
+    Bindings::new("Token", token_abi())
+        .error(
+            "InsufficientBalance",
+            "ERC20: transfer amount exceeds balance",
+        )
+        .error("InsufficientBalance", "ERC20: insufficient balance")
+        .error("InsufficientAllowance", "ERC20: insufficient allowance")
+}
+
+#[test]
+fn generated_bindings_are_up_to_date() {
+    let source = token_bindings().generate_source().unwrap();
+
+    if std::env::var("UPDATE_BINDINGS").is_ok() {
+        std::fs::write("tests/bindings/token.rs", &source).unwrap();
+    }
+
+    assert_eq!(source, include_str!("bindings/token.rs"));
+}
+
+#[test]
+fn generated_bindings_encode_calls() {
+    let token = token::Token::new(FieldElement::ONE, ());
+
+    let call = token.transfer(felt!("0x2"), U256::from(5u128)).into_call();
+    assert_eq!(call.selector, get_selector_from_name("transfer").unwrap());
+    assert_eq!(call.calldata, vec![felt!("0x2"), felt!("5"), felt!("0")]);
+
+    let call = token.get_locks(&[felt!("7"), felt!("8")]).into_call();
+    assert_eq!(call.calldata, vec![felt!("2"), felt!("7"), felt!("8")]);
+
+    let message = token.handle_deposit_message(Default::default(), felt!("0x3"), U256::from(9u128));
+    assert_eq!(message.payload, vec![felt!("0x3"), felt!("9"), felt!("0")]);
+
+    let constructor = token::TokenConstructor {
+        name: felt!("1"),
+        symbol: felt!("2"),
+        recipient: felt!("3"),
+    };
+    assert_eq!(
+        constructor.cairo_serialized(),
+        vec![felt!("1"), felt!("2"), felt!("3")]
+    );
+}
+
+#[test]
+fn generated_errors_decode_revert_reasons() {
+    assert_eq!(
+        token::TokenError::from_error_message(
+            "Error at pc=0:10:\nError message: ERC20: transfer amount exceeds balance\n"
+        ),
+        Some(token::TokenError::InsufficientBalance)
+    );
+    assert_eq!(
+        token::TokenError::from_revert_reason("ERC20: insufficient balance"),
+        Some(token::TokenError::InsufficientBalance)
+    );
+    assert_eq!(
+        token::TokenError::from_revert_reason("ERC20: insufficient allowance"),
+        Some(token::TokenError::InsufficientAllowance)
+    );
+    assert_eq!(token::TokenError::from_revert_reason("Out of gas"), None);
+}
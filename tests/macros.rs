@@ -1,11 +1,75 @@
 use starknet::{
     core::{
-        types::FieldElement,
+        codec::{CairoDeserialize, CairoSerialize, DecodeError, U256},
+        event::{DecodeEventError, StarknetEvent},
+        typed_data::{Domain, TypedData},
+        types::{Event, FieldElement},
         utils::{cairo_short_string_to_felt, get_selector_from_name},
     },
-    macros::{felt, felt_dec, felt_hex, selector, short_string},
+    macros::{
+        felt, felt_dec, felt_hex, selector, short_string, CairoDeserialize, CairoSerialize,
+        StarknetEvent, TypedData,
+    },
+    providers::jsonrpc::models::EmittedEvent,
 };
 
+#[derive(Debug, PartialEq, Eq, StarknetEvent)]
+struct Transfer {
+    #[event(key)]
+    from: FieldElement,
+    #[event(key)]
+    to: FieldElement,
+    amount: u64,
+    memo: Vec<FieldElement>,
+}
+
+#[derive(Debug, PartialEq, Eq, StarknetEvent)]
+#[event(name = "initialized")]
+struct Initialized {
+    arg: FieldElement,
+}
+
+#[derive(Debug, PartialEq, Eq, CairoSerialize, CairoDeserialize)]
+struct Order {
+    id: u64,
+    amount: U256,
+    side: Side,
+    note: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, CairoSerialize, CairoDeserialize)]
+enum Side {
+    Buy,
+    Sell(FieldElement),
+    Limit { price: u128, expiry: u64 },
+}
+
+#[derive(Debug, PartialEq, Eq, CairoSerialize, CairoDeserialize)]
+struct Pair(FieldElement, (bool, Vec<u8>));
+
+#[derive(TypedData)]
+struct Person {
+    name: FieldElement,
+    wallet: FieldElement,
+}
+
+#[derive(TypedData)]
+struct Mail {
+    from: Person,
+    to: Person,
+    contents: FieldElement,
+}
+
+#[derive(TypedData)]
+#[typed_data(name = "Order")]
+struct LimitOrder {
+    #[typed_data(type = "ContractAddress")]
+    maker: FieldElement,
+    #[typed_data(rename = "minAmount")]
+    min_amount: u128,
+    recipients: Vec<Person>,
+}
+
 #[test]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 fn selector_can_generate_correct_selector() {
@@ -15,6 +79,19 @@ fn selector_can_generate_correct_selector() {
     assert_eq!(macro_value, function_call_value);
 }
 
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn selector_can_be_used_in_const() {
+    const SELECTOR_EXECUTE: FieldElement = selector!("__execute__");
+    const SELECTOR_DEFAULT: FieldElement = selector!("__default__");
+
+    assert_eq!(
+        SELECTOR_EXECUTE,
+        get_selector_from_name("__execute__").unwrap()
+    );
+    assert_eq!(SELECTOR_DEFAULT, FieldElement::ZERO);
+}
+
 #[test]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 fn short_string_can_generate_correct_short_string() {
@@ -24,6 +101,23 @@ fn short_string_can_generate_correct_short_string() {
     assert_eq!(macro_value, function_call_value);
 }
 
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn short_string_can_be_used_in_const() {
+    const CHAIN_ID: FieldElement = short_string!("SN_GOERLI");
+    const MAX_LENGTH: FieldElement = short_string!("0123456789012345678901234567890");
+
+    assert_eq!(
+        CHAIN_ID,
+        FieldElement::from_hex_be("0x534e5f474f45524c49").unwrap()
+    );
+    assert_eq!(
+        MAX_LENGTH,
+        cairo_short_string_to_felt("0123456789012345678901234567890").unwrap()
+    );
+    assert_eq!(short_string!(""), FieldElement::ZERO);
+}
+
 #[test]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 fn felt_with_dec_string() {
@@ -42,6 +136,19 @@ fn felt_with_hex_string() {
     assert_eq!(macro_value, function_call_value);
 }
 
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn felt_with_int_literal() {
+    const ONE_ETHER: FieldElement = felt!(1000000000000000000);
+
+    assert_eq!(ONE_ETHER, FieldElement::from(1000000000000000000u64));
+    assert_eq!(felt!(0x123456789abcdef), felt!("0x123456789abcdef"));
+    assert_eq!(
+        felt!(3618502788666131213697322783095070105623107215331596699973092056135872020480),
+        -FieldElement::ONE
+    );
+}
+
 #[test]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 fn felt_dec() {
@@ -59,3 +166,144 @@ fn felt_hex() {
 
     assert_eq!(macro_value, function_call_value);
 }
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn starknet_event_selector() {
+    assert_eq!(Transfer::selector(), selector!("Transfer"));
+    assert_eq!(Initialized::selector(), selector!("initialized"));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn starknet_event_from_raw_events() {
+    let event = Event {
+        from_address: FieldElement::ONE,
+        keys: vec![selector!("Transfer"), felt!("0x11"), felt!("0x22")],
+        data: vec![felt!("1000"), felt!("1"), felt!("0x33")],
+    };
+
+    assert_eq!(
+        Transfer::try_from(&event).unwrap(),
+        Transfer {
+            from: felt!("0x11"),
+            to: felt!("0x22"),
+            amount: 1000,
+            memo: vec![felt!("0x33")],
+        }
+    );
+
+    let emitted = EmittedEvent {
+        from_address: FieldElement::ONE,
+        keys: vec![selector!("initialized")],
+        data: vec![felt!("0x99")],
+        block_hash: FieldElement::ONE,
+        block_number: 1,
+        transaction_hash: FieldElement::ONE,
+    };
+    assert_eq!(
+        Initialized::try_from(emitted).unwrap(),
+        Initialized { arg: felt!("0x99") }
+    );
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn starknet_event_rejects_mismatches() {
+    assert!(matches!(
+        Transfer::decode(&[selector!("initialized")], &[]),
+        Err(DecodeEventError::SelectorMismatch { .. })
+    ));
+    assert!(matches!(
+        Initialized::decode(&[selector!("initialized"), felt!("0x1")], &[felt!("0x99")]),
+        Err(DecodeEventError::Decode(DecodeError::TrailingElements(1)))
+    ));
+    assert!(matches!(
+        Initialized::decode(&[selector!("initialized")], &[]),
+        Err(DecodeEventError::Decode(DecodeError::UnexpectedEnd))
+    ));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn cairo_serde_structs_and_enums() {
+    let order = Order {
+        id: 7,
+        amount: U256::from_words(100, 1),
+        side: Side::Limit {
+            price: 50,
+            expiry: 60,
+        },
+        note: None,
+    };
+
+    let encoded = order.cairo_serialized();
+    assert_eq!(
+        encoded,
+        ["7", "100", "1", "2", "50", "60", "1"]
+            .into_iter()
+            .map(|value| FieldElement::from_dec_str(value).unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Order::cairo_deserialize_all(&encoded).unwrap(), order);
+
+    let side = Side::Sell(felt!("0x1234"));
+    assert_eq!(side.cairo_serialized(), vec![felt!("1"), felt!("0x1234")]);
+    assert_eq!(
+        Side::cairo_deserialize_all(&side.cairo_serialized()).unwrap(),
+        side
+    );
+    assert!(matches!(
+        Side::cairo_deserialize_all(&[felt!("3")]),
+        Err(DecodeError::InvalidVariant(3))
+    ));
+
+    let pair = Pair(felt!("0x1"), (true, vec![2, 3]));
+    assert_eq!(
+        pair.cairo_serialized(),
+        vec![felt!("1"), felt!("1"), felt!("2"), felt!("2"), felt!("3")]
+    );
+    assert_eq!(
+        Pair::cairo_deserialize_all(&pair.cairo_serialized()).unwrap(),
+        pair
+    );
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn typed_data_message_hash() {
+    let mail = Mail {
+        from: Person {
+            name: short_string!("Cow"),
+            wallet: felt!("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826"),
+        },
+        to: Person {
+            name: short_string!("Bob"),
+            wallet: felt!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+        },
+        contents: short_string!("Hello, Bob!"),
+    };
+    let domain = Domain {
+        name: short_string!("StarkNet Mail"),
+        version: felt!("1"),
+        chain_id: felt!("1"),
+    };
+
+    assert_eq!(
+        Mail::encode_type(),
+        "Mail(from:Person,to:Person,contents:felt)Person(name:felt,wallet:felt)"
+    );
+    assert_eq!(
+        mail.message_hash(&domain, felt!("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826")),
+        felt!("0x6fcff244f63e38b9d88b9e3378d44757710d1b244282b435cb472053c8d78d0")
+    );
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn typed_data_attributes() {
+    assert_eq!(
+        LimitOrder::encode_type(),
+        "Order(maker:ContractAddress,minAmount:u128,recipients:Person*)Person(name:felt,wallet:felt)"
+    );
+}